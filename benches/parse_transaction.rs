@@ -0,0 +1,75 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use solana_streamer_sdk::streaming::event_parser::{EventParserFactory, Protocol};
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransactionWithStatusMeta,
+};
+use std::fs;
+
+const FIXTURES: &[(&str, &str, Protocol)] = &[
+    (
+        "pumpfun_cpi",
+        "tests/fixtures/pumpfun_cpi_tx.json",
+        Protocol::PumpFun,
+    ),
+    (
+        "pumpfun_direct",
+        "tests/fixtures/pumpfun_direct_tx.json",
+        Protocol::PumpFun,
+    ),
+    (
+        "pumpswap_cpi",
+        "tests/fixtures/pumpswap_cpi_tx.json",
+        Protocol::PumpSwap,
+    ),
+    (
+        "bonk_cpi",
+        "tests/fixtures/bonk_cpi_tx.json",
+        Protocol::Bonk,
+    ),
+    (
+        "bonk_direct",
+        "tests/fixtures/bonk_direct_tx.json",
+        Protocol::Bonk,
+    ),
+];
+
+fn load_fixture(path: &str) -> (EncodedTransactionWithStatusMeta, String, u64) {
+    let tx: EncodedConfirmedTransactionWithStatusMeta =
+        serde_json::from_str(&fs::read_to_string(path).expect("fixture exists"))
+            .expect("fixture is a confirmed transaction");
+    let signature = tx
+        .transaction
+        .transaction
+        .decode()
+        .map(|t| t.signatures[0].to_string())
+        .unwrap_or_default();
+    (tx.transaction, signature, tx.slot)
+}
+
+fn bench_parse_transaction(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let mut group = c.benchmark_group("parse_transaction");
+    group.throughput(Throughput::Elements(1));
+    for (name, path, protocol) in FIXTURES {
+        let (tx, signature, slot) = load_fixture(path);
+        let parser = EventParserFactory::create_parser(protocol.clone());
+        group.bench_function(*name, |b| {
+            b.iter(|| {
+                runtime.block_on(parser.parse_transaction(
+                    black_box(tx.clone()),
+                    &signature,
+                    Some(slot),
+                    None,
+                    0,
+                    None,
+                ))
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse_transaction);
+criterion_main!(benches);
@@ -0,0 +1,70 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::trackers::ShardedMap;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+const ACTIVE_MINTS: usize = 200_000;
+const UPDATES_PER_THREAD: usize = 50_000;
+
+trait Store: Send + Sync + 'static {
+    fn bump(&self, key: Pubkey);
+}
+
+impl Store for RwLock<HashMap<Pubkey, u64>> {
+    fn bump(&self, key: Pubkey) {
+        *self.write().unwrap().entry(key).or_insert(0) += 1;
+    }
+}
+
+impl Store for ShardedMap<Pubkey, u64> {
+    fn bump(&self, key: Pubkey) {
+        self.upsert(key, || 0, |count| *count += 1);
+    }
+}
+
+fn run<S: Store>(store: &Arc<S>, keys: &Arc<Vec<Pubkey>>, threads: usize) {
+    let handles: Vec<_> = (0..threads)
+        .map(|t| {
+            let store = store.clone();
+            let keys = keys.clone();
+            thread::spawn(move || {
+                for i in 0..UPDATES_PER_THREAD {
+                    store.bump(keys[(i * 7919 + t * 104_729) % keys.len()]);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+fn bench_concurrent_updates(c: &mut Criterion) {
+    let keys: Arc<Vec<Pubkey>> =
+        Arc::new((0..ACTIVE_MINTS).map(|_| Pubkey::new_unique()).collect());
+    let single: Arc<RwLock<HashMap<Pubkey, u64>>> =
+        Arc::new(RwLock::new(HashMap::with_capacity(ACTIVE_MINTS)));
+    let sharded: Arc<ShardedMap<Pubkey, u64>> = Arc::new(ShardedMap::with_capacity(ACTIVE_MINTS));
+
+    let mut group = c.benchmark_group("concurrent_updates");
+    group.sample_size(20);
+    for threads in [1, 4, 8, 16] {
+        group.throughput(Throughput::Elements((threads * UPDATES_PER_THREAD) as u64));
+        group.bench_with_input(
+            BenchmarkId::new("rwlock_hashmap", threads),
+            &threads,
+            |b, &n| b.iter(|| run(&single, &keys, n)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("sharded_map", threads),
+            &threads,
+            |b, &n| b.iter(|| run(&sharded, &keys, n)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_concurrent_updates);
+criterion_main!(benches);
@@ -0,0 +1,21 @@
+/// 重新生成 `src/protos/events.rs`
+///
+/// The generated code is checked in so builds don't need `protoc`. After editing
+/// `proto/events.proto`, regenerate it with
+/// `cargo build --features server,protogen` (requires `protoc` on the PATH) and
+/// commit the result; never edit the generated file by hand.
+fn main() {
+    #[cfg(feature = "protogen")]
+    generate_event_protos();
+}
+
+#[cfg(feature = "protogen")]
+fn generate_event_protos() {
+    println!("cargo:rerun-if-changed=proto/events.proto");
+    tonic_build::configure()
+        .build_client(true)
+        .build_server(true)
+        .out_dir("src/protos")
+        .compile_protos(&["proto/events.proto"], &["proto"])
+        .expect("failed to generate src/protos/events.rs from proto/events.proto");
+}
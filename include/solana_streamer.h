@@ -0,0 +1,94 @@
+#ifndef SOLANA_STREAMER_H
+#define SOLANA_STREAMER_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Return code of a successful call
+ */
+#define SOLANA_STREAMER_OK 0
+
+/**
+ * Return code of a failed call, see [`solana_streamer_last_error`]
+ */
+#define SOLANA_STREAMER_ERROR -1
+
+/**
+ * 订阅句柄，用 [`solana_streamer_unsubscribe`] 释放
+ */
+typedef struct SolanaStreamerSubscription SolanaStreamerSubscription;
+
+/**
+ * Receives one event as its type name and JSON payload
+ */
+typedef void (*SolanaStreamerEventCallback)(const char *event_type,
+                                            const char *json,
+                                            void *user_data);
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Message of the last error on the calling thread, or null
+ *
+ * The string stays valid until the next failing call on the same thread.
+ */
+const char *solana_streamer_last_error(void);
+
+/**
+ * 解析一笔交易，每个事件调用一次 `callback`
+ *
+ * `tx_json` is an `EncodedTransactionWithStatusMeta` as returned by the
+ * `getTransaction` RPC. `slot` 0 means unknown.
+ *
+ * # Safety
+ * All strings must be valid NUL terminated UTF-8; `callback` is called on the
+ * calling thread before the function returns.
+ */
+int32_t solana_streamer_parse_transaction(const char *tx_json,
+                                          const char *signature,
+                                          const char *protocols,
+                                          uint64_t slot,
+                                          SolanaStreamerEventCallback callback,
+                                          void *user_data);
+
+/**
+ * 在后台订阅Yellowstone gRPC
+ *
+ * `x_token` may be null. The programs of `protocols` are subscribed to. Returns
+ * null on invalid arguments.
+ *
+ * # Safety
+ * All strings must be valid NUL terminated UTF-8. `callback` is called from
+ * runtime threads and must be safe to call with `user_data` from any thread until
+ * [`solana_streamer_unsubscribe`] returns.
+ */
+SolanaStreamerSubscription *solana_streamer_subscribe(const char *endpoint,
+                                                      const char *x_token,
+                                                      const char *protocols,
+                                                      SolanaStreamerEventCallback callback,
+                                                      void *user_data);
+
+/**
+ * 停止订阅并释放句柄
+ *
+ * No callback is running or started once this returns, so `user_data` may be
+ * freed afterwards.
+ *
+ * # Safety
+ * `subscription` must come from [`solana_streamer_subscribe`] and must not be
+ * used afterwards. Null is ignored.
+ */
+void solana_streamer_unsubscribe(SolanaStreamerSubscription *subscription);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SOLANA_STREAMER_H */
@@ -0,0 +1,134 @@
+//! 从样本交易生成协议模块骨架
+//!
+//! ```text
+//! scaffold-protocol --name <module> --program <PROGRAM_ID> [--rpc <URL>] [--out <DIR>] <SIGNATURE>...
+//! ```
+//!
+//! Fetches the sample transactions, writes them as fixtures and renders the
+//! protocol module and its test under the output directory (`scaffold` by default).
+
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_streamer_sdk::common::AnyResult;
+use solana_streamer_sdk::streaming::simulation::scaffold::{fixture_path, ProtocolScaffold};
+use solana_transaction_status::UiTransactionEncoding;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const DEFAULT_RPC: &str = "https://api.mainnet-beta.solana.com";
+const USAGE: &str = "usage: scaffold-protocol --name <module> --program <PROGRAM_ID> [--rpc <URL>] [--out <DIR>] <SIGNATURE>...";
+
+struct Args {
+    name: String,
+    program_id: Pubkey,
+    rpc: String,
+    out: PathBuf,
+    signatures: Vec<String>,
+}
+
+fn parse_args() -> AnyResult<Args> {
+    let mut name = None;
+    let mut program_id = None;
+    let mut rpc = DEFAULT_RPC.to_string();
+    let mut out = PathBuf::from("scaffold");
+    let mut signatures = vec![];
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| anyhow::anyhow!("{} needs a value", arg))
+        };
+        match arg.as_str() {
+            "--name" => name = Some(value()?),
+            "--program" => program_id = Some(Pubkey::from_str(&value()?)?),
+            "--rpc" => rpc = value()?,
+            "--out" => out = PathBuf::from(value()?),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            _ => signatures.push(arg),
+        }
+    }
+    match (name, program_id) {
+        (Some(name), Some(program_id)) if !signatures.is_empty() => Ok(Args {
+            name,
+            program_id,
+            rpc,
+            out,
+            signatures,
+        }),
+        _ => Err(anyhow::anyhow!(USAGE)),
+    }
+}
+
+fn write(path: &Path, contents: &str) -> AnyResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> AnyResult<()> {
+    let args = parse_args()?;
+    let client = RpcClient::new_with_commitment(args.rpc.clone(), CommitmentConfig::confirmed());
+    let mut scaffold = ProtocolScaffold::new(args.name.clone(), args.program_id);
+
+    for signature in &args.signatures {
+        let tx = client
+            .get_transaction_with_config(
+                &Signature::from_str(signature)?,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .await?;
+        let calls = scaffold.add_transaction(signature, &tx.transaction)?;
+        println!("{}: {} call(s) to {}", signature, calls, args.program_id);
+        write(
+            &args.out.join(fixture_path(&args.name, signature)),
+            &serde_json::to_string_pretty(&tx)?,
+        )?;
+    }
+
+    for candidate in scaffold.instructions() {
+        println!(
+            "instruction {} {:?}: {} sample(s), {} field(s), {} account(s)",
+            candidate.name(),
+            candidate.discriminator,
+            candidate.samples,
+            candidate.fields.len(),
+            candidate.accounts.len()
+        );
+    }
+    for candidate in scaffold.events() {
+        println!(
+            "event {} {:?}: {} sample(s), {} field(s)",
+            candidate.name(),
+            candidate.discriminator,
+            candidate.samples,
+            candidate.fields.len()
+        );
+    }
+
+    let files = scaffold.render();
+    let module = args
+        .out
+        .join("src/streaming/event_parser/protocols")
+        .join(&args.name);
+    write(&module.join("mod.rs"), &files.mod_rs)?;
+    write(&module.join("events.rs"), &files.events_rs)?;
+    write(&module.join("parser.rs"), &files.parser_rs)?;
+    write(
+        &args.out.join(format!("tests/{}_parsers.rs", args.name)),
+        &files.test_rs,
+    )?;
+    Ok(())
+}
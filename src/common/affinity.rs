@@ -0,0 +1,31 @@
+/// 将当前线程绑定到指定CPU核心
+///
+/// Returns false if the core does not exist or the platform refused. Without the
+/// `affinity` feature threads are never pinned and this always returns false.
+pub fn pin_current_thread(core: usize) -> bool {
+    #[cfg(feature = "affinity")]
+    {
+        let pinned = core_affinity::get_core_ids()
+            .and_then(|ids| ids.into_iter().find(|id| id.id == core))
+            .is_some_and(core_affinity::set_for_current);
+        if !pinned {
+            log::warn!("Failed to pin thread to core {}", core);
+        }
+        pinned
+    }
+    #[cfg(not(feature = "affinity"))]
+    {
+        log::warn!(
+            "Cannot pin thread to core {}: built without the affinity feature",
+            core
+        );
+        false
+    }
+}
+
+/// Number of cores threads can be pinned to
+pub fn available_cores() -> usize {
+    std::thread::available_parallelism()
+        .map(|cores| cores.get())
+        .unwrap_or(1)
+}
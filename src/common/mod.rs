@@ -1,2 +1,3 @@
+pub mod affinity;
 pub mod types;
 pub use types::*;
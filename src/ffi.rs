@@ -0,0 +1,253 @@
+//! C接口 - 供C/C++、Go (cgo) 等非Rust服务嵌入解析器
+//!
+//! Link against the `cdylib` built with `--features ffi` and include
+//! `include/solana_streamer.h`, generated with
+//! `cbindgen --config cbindgen.toml --output include/solana_streamer.h`.
+//! Events are delivered as UTF-8 JSON through a callback; the strings are only
+//! valid during the call. Functions returning an error set a message that
+//! [`solana_streamer_last_error`] returns on the same thread.
+//!
+//! ```c
+//! void on_event(const char *event_type, const char *json, void *user_data) {
+//!     printf("%s %s\n", event_type, json);
+//! }
+//!
+//! SolanaStreamerSubscription *sub = solana_streamer_subscribe(
+//!     "https://solana-yellowstone-grpc.publicnode.com:443", NULL, "PumpFun,Bonk",
+//!     on_event, NULL);
+//! // ...
+//! solana_streamer_unsubscribe(sub);
+//! ```
+
+use std::cell::RefCell;
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use solana_transaction_status::EncodedTransactionWithStatusMeta;
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::{EventParserFactory, Protocol, UnifiedEvent};
+use crate::streaming::{StreamHandle, YellowstoneGrpc};
+
+/// Return code of a successful call
+pub const SOLANA_STREAMER_OK: i32 = 0;
+/// Return code of a failed call, see [`solana_streamer_last_error`]
+pub const SOLANA_STREAMER_ERROR: i32 = -1;
+
+/// Receives one event as its type name and JSON payload
+pub type SolanaStreamerEventCallback =
+    unsafe extern "C" fn(event_type: *const c_char, json: *const c_char, user_data: *mut c_void);
+
+/// 订阅句柄，用 [`solana_streamer_unsubscribe`] 释放
+pub struct SolanaStreamerSubscription {
+    handle: StreamHandle,
+    target: Arc<CallbackTarget>,
+}
+
+static RUNTIME: LazyLock<tokio::runtime::Runtime> = LazyLock::new(|| {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .thread_name("solana-streamer-ffi")
+        .build()
+        .expect("failed to build tokio runtime")
+});
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(error: impl ToString) {
+    let message = CString::new(error.to_string().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// 调用方保证回调可在任意线程上使用user_data
+struct CallbackTarget {
+    callback: SolanaStreamerEventCallback,
+    user_data: *mut c_void,
+    /// Cleared on unsubscribe; held for reading during a callback
+    active: RwLock<bool>,
+}
+
+unsafe impl Send for CallbackTarget {}
+unsafe impl Sync for CallbackTarget {}
+
+impl CallbackTarget {
+    fn new(callback: SolanaStreamerEventCallback, user_data: *mut c_void) -> Self {
+        Self {
+            callback,
+            user_data,
+            active: RwLock::new(true),
+        }
+    }
+
+    fn deliver(&self, event: &dyn UnifiedEvent) {
+        let active = self.active.read().unwrap_or_else(|e| e.into_inner());
+        if !*active {
+            return;
+        }
+        let (Ok(event_type), Ok(json)) = (
+            CString::new(event.event_type().to_string()),
+            CString::new(event.to_json()),
+        ) else {
+            return;
+        };
+        unsafe { (self.callback)(event_type.as_ptr(), json.as_ptr(), self.user_data) };
+    }
+}
+
+unsafe fn read_str<'a>(ptr: *const c_char, name: &str) -> AnyResult<&'a str> {
+    if ptr.is_null() {
+        return Err(anyhow::anyhow!("{} is null", name));
+    }
+    Ok(CStr::from_ptr(ptr).to_str()?)
+}
+
+/// Comma separated protocol names, e.g. "PumpFun,RaydiumCpmm"
+fn parse_protocols(protocols: &str) -> AnyResult<Vec<Protocol>> {
+    protocols
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(Protocol::from_str)
+        .collect()
+}
+
+/// Message of the last error on the calling thread, or null
+///
+/// The string stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn solana_streamer_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// 解析一笔交易，每个事件调用一次 `callback`
+///
+/// `tx_json` is an `EncodedTransactionWithStatusMeta` as returned by the
+/// `getTransaction` RPC. `slot` 0 means unknown.
+///
+/// # Safety
+/// All strings must be valid NUL terminated UTF-8; `callback` is called on the
+/// calling thread before the function returns.
+#[no_mangle]
+pub unsafe extern "C" fn solana_streamer_parse_transaction(
+    tx_json: *const c_char,
+    signature: *const c_char,
+    protocols: *const c_char,
+    slot: u64,
+    callback: SolanaStreamerEventCallback,
+    user_data: *mut c_void,
+) -> i32 {
+    let result = (|| -> AnyResult<()> {
+        let tx: EncodedTransactionWithStatusMeta =
+            serde_json::from_str(read_str(tx_json, "tx_json")?)?;
+        let signature = read_str(signature, "signature")?;
+        let protocols = parse_protocols(read_str(protocols, "protocols")?)?;
+        let slot = (slot != 0).then_some(slot);
+        let target = CallbackTarget::new(callback, user_data);
+        RUNTIME.block_on(async {
+            let program_received_time_ms = chrono::Utc::now().timestamp_millis();
+            for protocol in protocols {
+                let parser = EventParserFactory::create_parser(protocol);
+                let events = parser
+                    .parse_transaction(
+                        tx.clone(),
+                        signature,
+                        slot,
+                        None,
+                        program_received_time_ms,
+                        None,
+                    )
+                    .await?;
+                for event in events {
+                    target.deliver(event.as_ref());
+                }
+            }
+            Ok(())
+        })
+    })();
+    match result {
+        Ok(()) => SOLANA_STREAMER_OK,
+        Err(e) => {
+            set_last_error(e);
+            SOLANA_STREAMER_ERROR
+        }
+    }
+}
+
+/// 在后台订阅Yellowstone gRPC
+///
+/// `x_token` may be null. The programs of `protocols` are subscribed to. Returns
+/// null on invalid arguments or when the connection fails.
+///
+/// # Safety
+/// All strings must be valid NUL terminated UTF-8. `callback` is called from
+/// runtime threads and must be safe to call with `user_data` from any thread until
+/// [`solana_streamer_unsubscribe`] returns.
+#[no_mangle]
+pub unsafe extern "C" fn solana_streamer_subscribe(
+    endpoint: *const c_char,
+    x_token: *const c_char,
+    protocols: *const c_char,
+    callback: SolanaStreamerEventCallback,
+    user_data: *mut c_void,
+) -> *mut SolanaStreamerSubscription {
+    let result = (|| -> AnyResult<SolanaStreamerSubscription> {
+        let endpoint = read_str(endpoint, "endpoint")?.to_string();
+        let x_token = match x_token.is_null() {
+            true => None,
+            false => Some(read_str(x_token, "x_token")?.to_string()),
+        };
+        let protocols = parse_protocols(read_str(protocols, "protocols")?)?;
+        let grpc = YellowstoneGrpc::new(endpoint, x_token)?;
+        let request = grpc
+            .subscribe_request_builder()
+            .with_protocols(&protocols)
+            .build()?;
+        let target = Arc::new(CallbackTarget::new(callback, user_data));
+        let stream_target = target.clone();
+        let handle = RUNTIME.block_on(grpc.spawn_events_with_request(
+            protocols,
+            None,
+            request,
+            move |event: Box<dyn UnifiedEvent>| stream_target.deliver(event.as_ref()),
+        ))?;
+        Ok(SolanaStreamerSubscription { handle, target })
+    })();
+    match result {
+        Ok(subscription) => Box::into_raw(Box::new(subscription)),
+        Err(e) => {
+            set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// 停止订阅并释放句柄
+///
+/// Stops the stream without draining it. No callback is running or started once
+/// this returns, so `user_data` may be freed afterwards.
+///
+/// # Safety
+/// `subscription` must come from [`solana_streamer_subscribe`] and must not be
+/// used afterwards. Null is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn solana_streamer_unsubscribe(
+    subscription: *mut SolanaStreamerSubscription,
+) {
+    if subscription.is_null() {
+        return;
+    }
+    let subscription = Box::from_raw(subscription);
+    *subscription
+        .target
+        .active
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) = false;
+    subscription.handle.abort();
+}
@@ -1,3 +1,15 @@
 pub mod streaming;
+#[cfg(any(feature = "shredstream", feature = "server"))]
 pub mod protos;
-pub mod common;
\ No newline at end of file
+pub mod common;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "python")]
+mod python;
+
+/// 可选的全局分配器，减少高吞吐下事件分配的开销
+///
+/// Register it in the application binary:
+/// `#[global_allocator] static GLOBAL: solana_streamer_sdk::MiMalloc = solana_streamer_sdk::MiMalloc;`
+#[cfg(feature = "mimalloc")]
+pub use mimalloc::MiMalloc;
@@ -20,6 +20,10 @@ use solana_streamer_sdk::{
     },
 };
 
+#[cfg(feature = "mimalloc")]
+#[global_allocator]
+static GLOBAL: solana_streamer_sdk::MiMalloc = solana_streamer_sdk::MiMalloc;
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     test_grpc().await?;
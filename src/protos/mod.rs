@@ -1,2 +1,6 @@
+#[cfg(feature = "server")]
+pub mod events;
+#[cfg(feature = "shredstream")]
 pub mod shared;
+#[cfg(feature = "shredstream")]
 pub mod shredstream;
@@ -0,0 +1,197 @@
+//! Python绑定
+//!
+//! Build with `maturin develop --features python`. Events are passed to Python as
+//! dicts with the same fields as their JSON serialization.
+//!
+//! ```python
+//! import solana_streamer_sdk as sss
+//!
+//! stream = sss.YellowstoneStream("https://solana-yellowstone-grpc.publicnode.com:443")
+//! stream.subscribe(["PumpFun"], lambda event: print(event["metadata"]["event_type"]))
+//! ```
+
+use std::str::FromStr;
+use std::sync::{LazyLock, Mutex};
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use solana_transaction_status::EncodedTransactionWithStatusMeta;
+
+use crate::streaming::event_parser::{EventParserFactory, Protocol, UnifiedEvent};
+use crate::streaming::shutdown::DEFAULT_SHUTDOWN_GRACE;
+use crate::streaming::{StreamHandle, YellowstoneGrpc};
+
+/// 绑定共用的tokio运行时
+static RUNTIME: LazyLock<tokio::runtime::Runtime> = LazyLock::new(|| {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .thread_name("solana-streamer-py")
+        .build()
+        .expect("failed to build tokio runtime")
+});
+
+fn parse_protocols(protocols: Vec<String>) -> PyResult<Vec<Protocol>> {
+    protocols
+        .iter()
+        .map(|name| Protocol::from_str(name).map_err(|e| PyValueError::new_err(e.to_string())))
+        .collect()
+}
+
+/// Convert an event to a dict through its JSON form
+fn event_to_dict(py: Python<'_>, event: &dyn UnifiedEvent) -> PyResult<PyObject> {
+    let json = py.import_bound("json")?;
+    Ok(json.call_method1("loads", (event.to_json(),))?.unbind())
+}
+
+/// 解析一笔交易
+///
+/// `tx_json` is an `EncodedTransactionWithStatusMeta` as returned by the
+/// `getTransaction` RPC with `json` or `base64` encoding.
+#[pyfunction]
+#[pyo3(signature = (tx_json, signature, protocols, slot = None))]
+fn parse_transaction(
+    py: Python<'_>,
+    tx_json: &str,
+    signature: &str,
+    protocols: Vec<String>,
+    slot: Option<u64>,
+) -> PyResult<Vec<PyObject>> {
+    let tx: EncodedTransactionWithStatusMeta =
+        serde_json::from_str(tx_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let protocols = parse_protocols(protocols)?;
+    let events = py.allow_threads(|| {
+        RUNTIME.block_on(async {
+            let program_received_time_ms = chrono::Utc::now().timestamp_millis();
+            let mut events = Vec::new();
+            for protocol in protocols {
+                let parser = EventParserFactory::create_parser(protocol);
+                let parsed = parser
+                    .parse_transaction(
+                        tx.clone(),
+                        signature,
+                        slot,
+                        None,
+                        program_received_time_ms,
+                        None,
+                    )
+                    .await
+                    .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+                events.extend(parsed);
+            }
+            Ok::<_, PyErr>(events)
+        })
+    })?;
+    events
+        .iter()
+        .map(|event| event_to_dict(py, event.as_ref()))
+        .collect()
+}
+
+/// Yellowstone gRPC订阅
+#[pyclass]
+struct YellowstoneStream {
+    endpoint: String,
+    x_token: Option<String>,
+    handle: Mutex<Option<StreamHandle>>,
+}
+
+#[pymethods]
+impl YellowstoneStream {
+    #[new]
+    #[pyo3(signature = (endpoint, x_token = None))]
+    fn new(endpoint: String, x_token: Option<String>) -> Self {
+        Self {
+            endpoint,
+            x_token,
+            handle: Mutex::new(None),
+        }
+    }
+
+    /// Start streaming in the background and call `callback(event: dict)` per event
+    ///
+    /// Without account filters the programs of `protocols` are subscribed to.
+    /// Replaces a running subscription of this stream.
+    #[pyo3(signature = (protocols, callback, account_include = vec![], account_exclude = vec![], account_required = vec![]))]
+    fn subscribe(
+        &self,
+        py: Python<'_>,
+        protocols: Vec<String>,
+        callback: PyObject,
+        mut account_include: Vec<String>,
+        account_exclude: Vec<String>,
+        account_required: Vec<String>,
+    ) -> PyResult<()> {
+        let protocols = parse_protocols(protocols)?;
+        if account_include.is_empty() && account_required.is_empty() {
+            account_include = protocols
+                .iter()
+                .flat_map(|protocol| protocol.get_program_id())
+                .map(|program_id| program_id.to_string())
+                .collect();
+        }
+        let grpc = YellowstoneGrpc::new(self.endpoint.clone(), self.x_token.clone())
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let request = grpc
+            .subscribe_request_builder()
+            .include_accounts(account_include)
+            .exclude_accounts(account_exclude)
+            .require_accounts(account_required)
+            .build()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let handle = py
+            .allow_threads(|| {
+                RUNTIME.block_on(grpc.spawn_events_with_request(
+                    protocols,
+                    None,
+                    request,
+                    move |event: Box<dyn UnifiedEvent>| {
+                        Python::with_gil(|py| {
+                            let result = event_to_dict(py, event.as_ref())
+                                .and_then(|event| callback.call1(py, (event,)));
+                            if let Err(e) = result {
+                                e.print(py);
+                            }
+                        })
+                    },
+                ))
+            })
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        if let Some(previous) = self
+            .handle
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .replace(handle)
+        {
+            previous.abort();
+        }
+        Ok(())
+    }
+
+    /// Stop the running subscription after delivering the events already received
+    #[pyo3(signature = (grace_secs = DEFAULT_SHUTDOWN_GRACE.as_secs_f64()))]
+    fn stop(&self, py: Python<'_>, grace_secs: f64) -> PyResult<()> {
+        let Some(handle) = self.handle.lock().unwrap_or_else(|e| e.into_inner()).take() else {
+            return Ok(());
+        };
+        let grace = std::time::Duration::try_from_secs_f64(grace_secs)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        py.allow_threads(|| RUNTIME.block_on(handle.shutdown(grace)))
+            .map(|_| ())
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    }
+
+    fn is_running(&self) -> bool {
+        self.handle
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+}
+
+#[pymodule]
+fn solana_streamer_sdk(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(parse_transaction, m)?)?;
+    m.add_class::<YellowstoneStream>()?;
+    Ok(())
+}
@@ -0,0 +1,202 @@
+use log::warn;
+use lru::LruCache;
+use solana_sdk::{
+    message::v0::MessageAddressTableLookup, pubkey::Pubkey, transaction::VersionedTransaction,
+};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::common::AnyResult;
+use crate::streaming::memory::{MemoryAccount, MemoryBudget};
+
+/// Default number of lookup tables kept in the cache
+pub const DEFAULT_ALT_CACHE_CAPACITY: usize = 10_000;
+
+/// 地址查找表加载器
+#[async_trait::async_trait]
+pub trait LookupTableLoader: Send + Sync {
+    /// Load the addresses stored in a lookup table
+    async fn load(&self, table: &Pubkey) -> AnyResult<Vec<Pubkey>>;
+}
+
+/// 通过RPC加载地址查找表
+#[cfg(feature = "rpc")]
+#[async_trait::async_trait]
+impl LookupTableLoader for crate::common::SolanaRpcClient {
+    async fn load(&self, table: &Pubkey) -> AnyResult<Vec<Pubkey>> {
+        use solana_sdk::address_lookup_table::state::AddressLookupTable;
+
+        let data = self.get_account_data(table).await?;
+        let table = AddressLookupTable::deserialize(&data)
+            .map_err(|e| anyhow::anyhow!("Invalid address lookup table {}: {}", table, e))?;
+        Ok(table.addresses.to_vec())
+    }
+}
+
+/// 地址查找表解析器 - 为仅有VersionedTransaction的数据源补全账户列表
+///
+/// Shred and mempool sources don't carry `loaded_addresses`, so instructions that
+/// reference accounts from an address lookup table would otherwise resolve to the
+/// wrong keys. Tables are cached in a bounded LRU; a cached table that is too short
+/// for a lookup is reloaded once, since tables can be extended.
+pub struct AltResolver {
+    loader: Arc<dyn LookupTableLoader>,
+    cache: Mutex<LruCache<Pubkey, Arc<Vec<Pubkey>>>>,
+    account: Option<MemoryAccount>,
+    // 无法解析查找表而跳过的交易数
+    unresolved: AtomicU64,
+}
+
+impl AltResolver {
+    pub fn new(loader: Arc<dyn LookupTableLoader>) -> Self {
+        Self::with_capacity(loader, DEFAULT_ALT_CACHE_CAPACITY)
+    }
+
+    pub fn with_capacity(loader: Arc<dyn LookupTableLoader>, capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            loader,
+            cache: Mutex::new(LruCache::new(capacity)),
+            account: None,
+            unresolved: AtomicU64::new(0),
+        }
+    }
+
+    /// Charge cached tables to `budget`, evicting least recently used tables while
+    /// it is exceeded
+    pub fn with_memory_budget(mut self, budget: &MemoryBudget) -> Self {
+        self.account = Some(budget.account("alt_resolver.tables"));
+        self
+    }
+
+    /// Resolver that loads tables through an RPC client
+    #[cfg(feature = "rpc")]
+    pub fn from_rpc(client: Arc<crate::common::SolanaRpcClient>) -> Self {
+        Self::new(client)
+    }
+
+    /// Seed or replace a cached table, e.g. from an account subscription
+    pub fn insert_table(&self, table: Pubkey, addresses: Vec<Pubkey>) {
+        self.store(table, Arc::new(addresses));
+    }
+
+    pub fn invalidate(&self, table: &Pubkey) {
+        let removed = self
+            .cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pop(table);
+        if let (Some(account), Some(addresses)) = (&self.account, removed) {
+            account.release(table_size(&addresses), 1);
+        }
+    }
+
+    fn store(&self, table: Pubkey, addresses: Arc<Vec<Pubkey>>) {
+        let size = table_size(&addresses);
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        // push返回被替换或按容量淘汰的表
+        let displaced = cache.push(table, addresses);
+        let Some(account) = &self.account else {
+            return;
+        };
+        account.charge(size, 1);
+        if let Some((key, addresses)) = displaced {
+            account.release(table_size(&addresses), 1);
+            if key != table {
+                account.record_evictions(1);
+            }
+        }
+        while account.over_budget() && cache.len() > 1 {
+            let Some((_, addresses)) = cache.pop_lru() else {
+                break;
+            };
+            account.release(table_size(&addresses), 1);
+            account.record_evictions(1);
+        }
+    }
+
+    pub fn cached_tables(&self) -> usize {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Number of transactions [`Self::resolve_for_parsing`] skipped
+    pub fn unresolved(&self) -> u64 {
+        self.unresolved.load(Ordering::Relaxed)
+    }
+
+    /// Full account list to parse a transaction with, None if its lookup tables
+    /// can't be resolved
+    ///
+    /// Parsing with only the static keys would give events of instructions that
+    /// reference loaded accounts the wrong accounts, so such transactions are
+    /// skipped instead; they are logged and counted in [`Self::unresolved`].
+    pub async fn resolve_for_parsing(&self, tx: &VersionedTransaction) -> Option<Vec<Pubkey>> {
+        match self.resolve_accounts(tx).await {
+            Ok(accounts) => Some(accounts),
+            Err(e) => {
+                self.unresolved.fetch_add(1, Ordering::Relaxed);
+                warn!(
+                    "Skipping {}, its lookup tables can't be resolved: {:?}",
+                    tx.signatures.first().copied().unwrap_or_default(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Full account list of a transaction: static keys, then the writable and
+    /// readonly addresses loaded from its lookup tables, in runtime order
+    pub async fn resolve_accounts(&self, tx: &VersionedTransaction) -> AnyResult<Vec<Pubkey>> {
+        let mut accounts = tx.message.static_account_keys().to_vec();
+        let Some(lookups) = tx.message.address_table_lookups() else {
+            return Ok(accounts);
+        };
+        let mut tables = Vec::with_capacity(lookups.len());
+        for lookup in lookups {
+            tables.push(self.table_for(lookup).await?);
+        }
+        for (lookup, table) in lookups.iter().zip(&tables) {
+            accounts.extend(lookup.writable_indexes.iter().map(|i| table[*i as usize]));
+        }
+        for (lookup, table) in lookups.iter().zip(&tables) {
+            accounts.extend(lookup.readonly_indexes.iter().map(|i| table[*i as usize]));
+        }
+        Ok(accounts)
+    }
+
+    async fn table_for(&self, lookup: &MessageAddressTableLookup) -> AnyResult<Arc<Vec<Pubkey>>> {
+        let required = lookup
+            .writable_indexes
+            .iter()
+            .chain(&lookup.readonly_indexes)
+            .max()
+            .map_or(0, |max| *max as usize + 1);
+        let cached = self
+            .cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&lookup.account_key)
+            .cloned();
+        if let Some(table) = cached.filter(|table| table.len() >= required) {
+            return Ok(table);
+        }
+        let table = Arc::new(self.loader.load(&lookup.account_key).await?);
+        if table.len() < required {
+            return Err(anyhow::anyhow!(
+                "Address lookup table {} has {} addresses, index {} requested",
+                lookup.account_key,
+                table.len(),
+                required - 1
+            ));
+        }
+        self.store(lookup.account_key, table.clone());
+        Ok(table)
+    }
+}
+
+/// 缓存中一张表的估算大小
+fn table_size(addresses: &[Pubkey]) -> usize {
+    std::mem::size_of::<Pubkey>() * (addresses.len() + 1) + std::mem::size_of::<Vec<Pubkey>>()
+}
@@ -0,0 +1,231 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::warn;
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinHandle;
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::UnifiedEvent;
+use crate::streaming::pipeline::SharedEvent;
+
+/// Default number of handler invocations running at once
+pub const DEFAULT_HANDLER_CONCURRENCY: usize = 64;
+
+/// Default number of events queued before new events are dropped
+pub const DEFAULT_HANDLER_QUEUE_CAPACITY: usize = 10_000;
+
+type HandlerFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+type ErasedAsyncHandler = Box<dyn Fn(&SharedEvent) -> Option<HandlerFuture> + Send + Sync>;
+
+struct AsyncRegistration {
+    name: String,
+    handler: ErasedAsyncHandler,
+}
+
+/// 异步处理器统计
+#[derive(Debug, Default)]
+pub struct AsyncHandlerStats {
+    /// Events dropped because the queue was full
+    pub dropped: AtomicU64,
+    /// Handler invocations that finished in time
+    pub completed: AtomicU64,
+    /// Handler invocations cancelled by the timeout
+    pub timed_out: AtomicU64,
+}
+
+/// 异步事件处理器
+///
+/// Runs `async` handlers (database writes, HTTP calls) off the delivery path. The
+/// callback only queues the event; a dispatcher task starts one invocation per
+/// matching handler, with at most `concurrency` invocations running at once, and
+/// cancels invocations that run longer than the timeout. When the queue is full,
+/// new events are dropped and counted instead of stalling the source.
+///
+/// # Usage Example
+/// ```ignore
+/// let handlers = AsyncHandlerRegistry::new()
+///     .with_concurrency(16)
+///     .with_timeout(Duration::from_secs(2))
+///     .on("store_trade", move |e: PumpFunTradeEvent| {
+///         let db = db.clone();
+///         async move { db.insert_trade(&e).await.ok(); }
+///     })
+///     .spawn();
+/// grpc.subscribe_events_v2(protocols, None, include, vec![], vec![], None, handlers.callback()).await?;
+/// ```
+pub struct AsyncHandlerRegistry {
+    handlers: Vec<AsyncRegistration>,
+    concurrency: usize,
+    timeout: Option<Duration>,
+    queue_capacity: usize,
+}
+
+impl Default for AsyncHandlerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsyncHandlerRegistry {
+    pub fn new() -> Self {
+        Self {
+            handlers: vec![],
+            concurrency: DEFAULT_HANDLER_CONCURRENCY,
+            timeout: None,
+            queue_capacity: DEFAULT_HANDLER_QUEUE_CAPACITY,
+        }
+    }
+
+    /// Maximum number of handler invocations running at once
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Cancel handler invocations running longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Number of events queued before new events are dropped
+    pub fn with_queue_capacity(mut self, capacity: usize) -> Self {
+        self.queue_capacity = capacity.max(1);
+        self
+    }
+
+    /// Handle events of type `T` with a clone of the event
+    pub fn on<T, F, Fut>(mut self, name: impl Into<String>, handler: F) -> Self
+    where
+        T: UnifiedEvent + Clone + 'static,
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let handler: ErasedAsyncHandler = Box::new(move |event: &SharedEvent| {
+            let event = event.as_any().downcast_ref::<T>()?.clone();
+            Some(Box::pin(handler(event)) as HandlerFuture)
+        });
+        self.handlers.push(AsyncRegistration {
+            name: name.into(),
+            handler,
+        });
+        self
+    }
+
+    /// Handle every event
+    pub fn on_any<F, Fut>(mut self, name: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(SharedEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let handler: ErasedAsyncHandler = Box::new(move |event: &SharedEvent| {
+            Some(Box::pin(handler(event.clone())) as HandlerFuture)
+        });
+        self.handlers.push(AsyncRegistration {
+            name: name.into(),
+            handler,
+        });
+        self
+    }
+
+    /// Number of registered handlers
+    pub fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    /// Start the dispatcher task; must be called within a tokio runtime
+    pub fn spawn(self) -> AsyncHandlerDispatcher {
+        let (sender, receiver) = mpsc::channel(self.queue_capacity);
+        let stats = Arc::new(AsyncHandlerStats::default());
+        let task = tokio::spawn(self.run(receiver, stats.clone()));
+        AsyncHandlerDispatcher {
+            sender,
+            stats,
+            task,
+        }
+    }
+
+    async fn run(self, mut receiver: mpsc::Receiver<SharedEvent>, stats: Arc<AsyncHandlerStats>) {
+        let permits = Arc::new(Semaphore::new(self.concurrency));
+        while let Some(event) = receiver.recv().await {
+            for registration in &self.handlers {
+                let Some(future) = (registration.handler)(&event) else {
+                    continue;
+                };
+                let Ok(permit) = permits.clone().acquire_owned().await else {
+                    return;
+                };
+                let stats = stats.clone();
+                let timeout = self.timeout;
+                let name = registration.name.clone();
+                tokio::spawn(async move {
+                    let _permit = permit;
+                    match timeout {
+                        Some(timeout) => {
+                            if tokio::time::timeout(timeout, future).await.is_err() {
+                                stats.timed_out.fetch_add(1, Ordering::Relaxed);
+                                warn!("Handler {} timed out after {:?}", name, timeout);
+                                return;
+                            }
+                        }
+                        None => future.await,
+                    }
+                    stats.completed.fetch_add(1, Ordering::Relaxed);
+                });
+            }
+        }
+        // 等待所有正在执行的处理器结束
+        let _ = permits.acquire_many(self.concurrency as u32).await;
+    }
+}
+
+/// 运行中的异步处理器
+pub struct AsyncHandlerDispatcher {
+    sender: mpsc::Sender<SharedEvent>,
+    stats: Arc<AsyncHandlerStats>,
+    task: JoinHandle<()>,
+}
+
+impl AsyncHandlerDispatcher {
+    /// Queue an event, returns false if it was dropped
+    pub fn dispatch(&self, event: Box<dyn UnifiedEvent>) -> bool {
+        if self.sender.try_send(Arc::from(event)).is_err() {
+            self.stats.dropped.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        true
+    }
+
+    /// Callback queueing every event, for use with the subscribe methods
+    pub fn callback(&self) -> impl Fn(Box<dyn UnifiedEvent>) + Send + Sync {
+        let sender = self.sender.clone();
+        let stats = self.stats.clone();
+        move |event: Box<dyn UnifiedEvent>| {
+            if sender.try_send(Arc::from(event)).is_err() {
+                stats.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn stats(&self) -> Arc<AsyncHandlerStats> {
+        self.stats.clone()
+    }
+
+    /// Stop accepting events and wait for the queued events and running handlers
+    ///
+    /// Callbacks created with [`callback`](Self::callback) keep the queue open
+    /// until they are dropped.
+    pub async fn close(self) -> AnyResult<()> {
+        drop(self.sender);
+        self.task.await?;
+        Ok(())
+    }
+}
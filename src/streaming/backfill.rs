@@ -0,0 +1,215 @@
+use log::{info, warn};
+use solana_sdk::pubkey::Pubkey;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::common::{AnyResult, SolanaRpcClient};
+use crate::streaming::checkpoint::ResumeCheckpoint;
+use crate::streaming::event_parser::{
+    common::FailedTxPolicy, core::post_process::PostProcessorChain, Protocol, UnifiedEvent,
+};
+use crate::streaming::replay::{ReplayEngine, ReplayStats, RpcBlockSource};
+
+const LIVE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 历史数据回填 - 通过getBlock补齐检查点与实时流之间的空缺
+///
+/// Typical startup:
+///
+/// ```ignore
+/// let checkpoint = Arc::new(ResumeCheckpoint::load("checkpoint.json")?);
+/// let backfiller = Backfiller::new(rpc_client, protocols.clone(), checkpoint);
+/// // Live events at or below the checkpoint were delivered before the restart
+/// grpc.subscribe_events_v2(protocols, ..., backfiller.live_callback(callback.clone())).await?;
+/// // Meanwhile, in another task: fetch checkpoint + 1 .. first live slot - 1
+/// backfiller.fill_gap(&callback).await?;
+/// ```
+///
+/// The live callback advances the checkpoint as live slots complete, but only once
+/// the gap before the first live slot is filled: until then a restart has to
+/// resume from before the gap.
+pub struct Backfiller {
+    client: Arc<SolanaRpcClient>,
+    engine: ReplayEngine,
+    checkpoint: Arc<ResumeCheckpoint>,
+    // 实时流收到的第一个slot，0表示尚未收到
+    first_live_slot: Arc<AtomicU64>,
+    // 实时流中已完整交付的最高slot，0表示尚无
+    live_completed_slot: Arc<AtomicU64>,
+    // 检查点与实时流之间的空缺是否已补齐
+    gap_filled: Arc<AtomicBool>,
+    resume_slot: u64,
+}
+
+impl Backfiller {
+    pub fn new(
+        client: Arc<SolanaRpcClient>,
+        protocols: Vec<Protocol>,
+        checkpoint: Arc<ResumeCheckpoint>,
+    ) -> Self {
+        let resume_slot = checkpoint.slot();
+        Self {
+            client,
+            engine: ReplayEngine::new(protocols),
+            checkpoint,
+            first_live_slot: Arc::new(AtomicU64::new(0)),
+            live_completed_slot: Arc::new(AtomicU64::new(0)),
+            // 空检查点没有需要补齐的空缺
+            gap_filled: Arc::new(AtomicBool::new(resume_slot == 0)),
+            resume_slot,
+        }
+    }
+
+    pub fn with_bot_wallet(mut self, bot_wallet: Pubkey) -> Self {
+        self.engine = self.engine.with_bot_wallet(bot_wallet);
+        self
+    }
+
+    pub fn with_failed_tx_policy(mut self, failed_tx_policy: FailedTxPolicy) -> Self {
+        self.engine = self.engine.with_failed_tx_policy(failed_tx_policy);
+        self
+    }
+
+    pub fn with_include_raw(mut self, include_raw: bool) -> Self {
+        self.engine = self.engine.with_include_raw(include_raw);
+        self
+    }
+
+    pub fn with_post_processors(mut self, post_processors: PostProcessorChain) -> Self {
+        self.engine = self.engine.with_post_processors(post_processors);
+        self
+    }
+
+    pub fn checkpoint(&self) -> &Arc<ResumeCheckpoint> {
+        &self.checkpoint
+    }
+
+    /// First slot seen by the live stream, if any
+    pub fn first_live_slot(&self) -> Option<u64> {
+        match self.first_live_slot.load(Ordering::Acquire) {
+            0 => None,
+            slot => Some(slot),
+        }
+    }
+
+    /// Whether the gap between the checkpoint and the live stream is filled
+    pub fn is_gap_filled(&self) -> bool {
+        self.gap_filled.load(Ordering::Acquire)
+    }
+
+    /// Wrap the live stream callback
+    ///
+    /// Records the first live slot and drops events the previous run already
+    /// delivered (at or below the checkpoint loaded at startup). A live slot is
+    /// complete once an event of a later slot was delivered; completed slots advance
+    /// the checkpoint when the gap is filled.
+    pub fn live_callback<F>(&self, callback: F) -> impl Fn(Box<dyn UnifiedEvent>) + Send + Sync
+    where
+        F: Fn(Box<dyn UnifiedEvent>) + Send + Sync,
+    {
+        let first_live_slot = self.first_live_slot.clone();
+        let live_completed_slot = self.live_completed_slot.clone();
+        let gap_filled = self.gap_filled.clone();
+        let checkpoint = self.checkpoint.clone();
+        let resume_slot = self.resume_slot;
+        // 最近交付的live slot
+        let current_slot = AtomicU64::new(0);
+        move |event: Box<dyn UnifiedEvent>| {
+            let slot = event.slot();
+            let _ = first_live_slot.compare_exchange(0, slot, Ordering::AcqRel, Ordering::Acquire);
+            if slot <= resume_slot {
+                return;
+            }
+            callback(event);
+
+            let previous = current_slot.fetch_max(slot, Ordering::AcqRel);
+            if previous == 0 || slot <= previous {
+                return;
+            }
+            live_completed_slot.fetch_max(previous, Ordering::AcqRel);
+            if gap_filled.load(Ordering::Acquire) {
+                if let Err(e) = checkpoint.advance(previous) {
+                    warn!("Failed to advance the checkpoint to slot {}: {}", previous, e);
+                }
+            }
+        }
+    }
+
+    /// Mark the gap filled and catch the checkpoint up with the live stream
+    fn finish_gap(&self) -> AnyResult<()> {
+        self.gap_filled.store(true, Ordering::Release);
+        let live_completed_slot = self.live_completed_slot.load(Ordering::Acquire);
+        if live_completed_slot > 0 {
+            self.checkpoint.advance(live_completed_slot)?;
+        }
+        self.checkpoint.save()
+    }
+
+    /// Wait for the live stream to start, then backfill the gap before it
+    ///
+    /// Does nothing for an empty checkpoint, since there is no known starting point.
+    pub async fn fill_gap<F>(&self, callback: &F) -> AnyResult<ReplayStats>
+    where
+        F: Fn(Box<dyn UnifiedEvent>) + Send + Sync,
+    {
+        if self.resume_slot == 0 {
+            return Ok(ReplayStats::default());
+        }
+        let first_live_slot = loop {
+            if let Some(slot) = self.first_live_slot() {
+                break slot;
+            }
+            tokio::time::sleep(LIVE_POLL_INTERVAL).await;
+        };
+        if first_live_slot <= self.resume_slot + 1 {
+            self.finish_gap()?;
+            return Ok(ReplayStats::default());
+        }
+        let stats = self
+            .backfill(self.resume_slot + 1, first_live_slot - 1, callback)
+            .await?;
+        self.finish_gap()?;
+        Ok(stats)
+    }
+
+    /// Parse every transaction of the inclusive slot range, advancing the checkpoint
+    /// after each slot
+    ///
+    /// Stops with an error at a block the node can't return; the checkpoint then
+    /// stays before it, so the range resumes there.
+    pub async fn backfill<F>(
+        &self,
+        start_slot: u64,
+        end_slot: u64,
+        callback: &F,
+    ) -> AnyResult<ReplayStats>
+    where
+        F: Fn(Box<dyn UnifiedEvent>) + Send + Sync,
+    {
+        info!("Backfilling slots {}..={}", start_slot, end_slot);
+        let mut stats = ReplayStats::default();
+        for slot in start_slot..=end_slot {
+            let transactions = RpcBlockSource::fetch_block(&self.client, slot).await?;
+            if !transactions.is_empty() {
+                stats.first_slot.get_or_insert(slot);
+                stats.last_slot = Some(slot);
+            }
+            for tx in transactions {
+                stats.transactions += 1;
+                match self.engine.parse(tx).await {
+                    Ok(events) => {
+                        stats.events += events.len() as u64;
+                        for event in events {
+                            callback(event);
+                        }
+                    }
+                    Err(_) => stats.parse_errors += 1,
+                }
+            }
+            self.checkpoint.advance(slot)?;
+        }
+        self.checkpoint.save()?;
+        Ok(stats)
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::{
+    EventMetadata, EventType, InstructionIndex, ProtocolType,
+};
+use crate::streaming::event_parser::UnifiedEvent;
+
+/// Default number of slots whose events are buffered while waiting for block meta
+pub const DEFAULT_MAX_PENDING_SLOTS: usize = 64;
+
+/// 区块元数据
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockMeta {
+    pub slot: u64,
+    pub blockhash: String,
+    pub parent_slot: u64,
+    pub parent_blockhash: String,
+    /// Unix timestamp in seconds
+    pub block_time: Option<i64>,
+    pub block_height: Option<u64>,
+    /// Number of transactions executed in the block, including ones not subscribed to
+    pub executed_transaction_count: u64,
+}
+
+/// slot状态
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SlotStatus {
+    #[default]
+    Processed,
+    Confirmed,
+    Finalized,
+    FirstShredReceived,
+    Completed,
+    CreatedBank,
+    /// The slot's fork was abandoned
+    Dead,
+}
+
+/// slot状态更新事件
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlotUpdateEvent {
+    pub metadata: EventMetadata,
+    pub parent: Option<u64>,
+    pub status: SlotStatus,
+    /// Reason of a `Dead` slot
+    pub dead_error: Option<String>,
+}
+
+impl_unified_event!(SlotUpdateEvent,);
+
+impl SlotUpdateEvent {
+    pub fn new(
+        slot: u64,
+        parent: Option<u64>,
+        status: SlotStatus,
+        dead_error: Option<String>,
+        program_received_time_ms: i64,
+    ) -> Self {
+        let mut metadata =
+            chain_metadata(slot, None, EventType::SlotUpdate, program_received_time_ms);
+        metadata.set_id(format!("slot:{}:{:?}", slot, status));
+        Self {
+            metadata,
+            parent,
+            status,
+            dead_error,
+        }
+    }
+}
+
+/// 区块元数据事件
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockMetaEvent {
+    pub metadata: EventMetadata,
+    pub block: BlockMeta,
+}
+
+impl_unified_event!(BlockMetaEvent,);
+
+impl BlockMetaEvent {
+    pub fn new(block: BlockMeta, program_received_time_ms: i64) -> Self {
+        let mut metadata = chain_metadata(
+            block.slot,
+            block.block_time,
+            EventType::BlockMeta,
+            program_received_time_ms,
+        );
+        metadata.set_id(format!("block:{}:{}", block.slot, block.blockhash));
+        Self { metadata, block }
+    }
+}
+
+/// 不属于任何交易的链状态事件的元数据
+pub(crate) fn chain_metadata(
+    slot: u64,
+    block_time: Option<i64>,
+    event_type: EventType,
+    program_received_time_ms: i64,
+) -> EventMetadata {
+    let block_time = block_time.unwrap_or_default();
+    EventMetadata::new(
+        String::new(),
+        String::new(),
+        slot,
+        block_time,
+        block_time * 1000,
+        ProtocolType::SDKSystem,
+        event_type,
+        Pubkey::default(),
+        InstructionIndex::default(),
+        program_received_time_ms,
+    )
+}
+
+/// 一个区块内的全部事件
+///
+/// Events are ordered by transaction index, then instruction position.
+#[derive(Debug, Clone)]
+pub struct BlockEvents {
+    pub meta: BlockMeta,
+    pub events: Vec<Box<dyn UnifiedEvent>>,
+}
+
+impl BlockEvents {
+    pub fn slot(&self) -> u64 {
+        self.meta.slot
+    }
+
+    pub fn tx_count(&self) -> u64 {
+        self.meta.executed_transaction_count
+    }
+}
+
+/// 按slot聚合事件，在区块完成时整体输出
+///
+/// Events are buffered per slot until [`BlockEventAggregator::complete`] is called
+/// with the slot's block meta, which the Yellowstone source sends after the last
+/// transaction of the block. Slots that never complete (skipped or dead forks) are
+/// dropped once more than `max_pending_slots` slots are buffered.
+pub struct BlockEventAggregator {
+    pending: BTreeMap<u64, Vec<Box<dyn UnifiedEvent>>>,
+    max_pending_slots: usize,
+    dropped_slots: u64,
+}
+
+impl Default for BlockEventAggregator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlockEventAggregator {
+    pub fn new() -> Self {
+        Self {
+            pending: BTreeMap::new(),
+            max_pending_slots: DEFAULT_MAX_PENDING_SLOTS,
+            dropped_slots: 0,
+        }
+    }
+
+    pub fn with_max_pending_slots(mut self, max_pending_slots: usize) -> Self {
+        self.max_pending_slots = max_pending_slots.max(1);
+        self
+    }
+
+    /// Buffer an event until its slot completes
+    pub fn push(&mut self, event: Box<dyn UnifiedEvent>) {
+        self.pending.entry(event.slot()).or_default().push(event);
+        while self.pending.len() > self.max_pending_slots {
+            if let Some((slot, events)) = self.pending.pop_first() {
+                log::warn!(
+                    "dropping {} events of slot {} without block meta",
+                    events.len(),
+                    slot
+                );
+                self.dropped_slots += 1;
+            }
+        }
+    }
+
+    /// Close the block of `meta.slot` and return its events
+    ///
+    /// Blocks without events of the subscribed protocols are returned empty.
+    pub fn complete(&mut self, meta: BlockMeta) -> BlockEvents {
+        let mut events = self.pending.remove(&meta.slot).unwrap_or_default();
+        events.sort_by_key(|event| (event.metadata().tx_index, event.index()));
+        BlockEvents { meta, events }
+    }
+
+    /// Slots currently waiting for their block meta
+    pub fn pending_slots(&self) -> Vec<u64> {
+        self.pending.keys().copied().collect()
+    }
+
+    /// Slots dropped because their block meta never arrived
+    pub fn dropped_slots(&self) -> u64 {
+        self.dropped_slots
+    }
+}
@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use log::{info, warn};
+use tonic::metadata::{Ascii, MetadataKey, MetadataValue};
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint};
+use tonic::{Request, Status};
+
+use crate::common::AnyResult;
+use crate::protos::events::event_stream_client::EventStreamClient;
+use crate::protos::events::{StreamedEvent, SubscribeEventsRequest};
+use crate::streaming::event_parser::{common::event_from_json, UnifiedEvent};
+use crate::streaming::security::ServerAuth;
+use crate::streaming::server::SUBSCRIBE_EVENTS_PATH;
+
+const DEFAULT_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// 事件转发桥 - 以另一个streamer实例的 [`EventServer`] 作为事件源
+///
+/// Connects to an upstream `EventServer` and rebuilds the typed events from their
+/// JSON payloads, so downstream instances see the same event types and metadata
+/// (including the upstream `program_received_time_ms`) as the parsing tier. Chain
+/// it into a local `EventServer` to build regional delivery tiers:
+///
+/// ```ignore
+/// let regional = EventServer::default();
+/// let bridge = EventBridge::new("https://parser.internal:50051")
+///     .with_auth(ServerAuth::token("secret"))
+///     .with_ca_certificate(std::fs::read("ca.pem")?);
+/// tokio::spawn(bridge.subscribe(regional.callback()));
+/// regional.serve("0.0.0.0:50051".parse()?).await?;
+/// ```
+///
+/// Upstream redaction removes fields from the payload; events whose payload no
+/// longer deserializes are skipped.
+///
+/// [`EventServer`]: crate::streaming::EventServer
+#[derive(Clone)]
+pub struct EventBridge {
+    endpoint: String,
+    auth: ServerAuth,
+    ca_certificate: Option<Vec<u8>>,
+    request: SubscribeEventsRequest,
+    reconnect_delay: Duration,
+}
+
+impl EventBridge {
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            auth: ServerAuth::None,
+            ca_certificate: None,
+            request: SubscribeEventsRequest::default(),
+            reconnect_delay: DEFAULT_RECONNECT_DELAY,
+        }
+    }
+
+    /// Credentials expected by the upstream server
+    pub fn with_auth(mut self, auth: ServerAuth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Connect over TLS, trusting the given PEM CA certificate
+    pub fn with_ca_certificate(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.ca_certificate = Some(pem.into());
+        self
+    }
+
+    /// Only forward these event types (e.g. "PumpFunBuy")
+    pub fn with_event_types(mut self, event_types: Vec<String>) -> Self {
+        self.request.event_types = event_types;
+        self
+    }
+
+    /// Only forward these protocols (e.g. "PumpFun")
+    pub fn with_protocols(mut self, protocols: Vec<String>) -> Self {
+        self.request.protocols = protocols;
+        self
+    }
+
+    /// Wait before reconnecting after the upstream stream ends or fails
+    pub fn with_reconnect_delay(mut self, delay: Duration) -> Self {
+        self.reconnect_delay = delay;
+        self
+    }
+
+    /// Rebuild a typed event from a streamed event
+    pub fn decode(event: &StreamedEvent) -> AnyResult<Box<dyn UnifiedEvent>> {
+        event_from_json(&event.event_type, &event.payload_json)
+    }
+
+    /// Forward upstream events to `callback`, reconnecting until the future is dropped
+    pub async fn subscribe<F>(self, callback: F) -> AnyResult<()>
+    where
+        F: Fn(Box<dyn UnifiedEvent>) + Send + Sync,
+    {
+        loop {
+            match self.forward(&callback).await {
+                Ok(()) => info!("Upstream event stream {} ended", self.endpoint),
+                Err(e) => warn!("Upstream event stream {} failed: {}", self.endpoint, e),
+            }
+            tokio::time::sleep(self.reconnect_delay).await;
+        }
+    }
+
+    /// Forward events until the upstream stream ends
+    pub async fn forward<F>(&self, callback: &F) -> AnyResult<()>
+    where
+        F: Fn(Box<dyn UnifiedEvent>) + Send + Sync,
+    {
+        let mut endpoint = Endpoint::from_shared(self.endpoint.clone())?;
+        if let Some(pem) = &self.ca_certificate {
+            endpoint = endpoint
+                .tls_config(ClientTlsConfig::new().ca_certificate(Certificate::from_pem(pem)))?;
+        }
+        let channel = endpoint.connect().await?;
+        let auth = self.auth.clone();
+        let mut client =
+            EventStreamClient::with_interceptor(channel, move |mut request: Request<()>| {
+                let now = chrono::Utc::now().timestamp();
+                for (name, value) in auth.client_headers(SUBSCRIBE_EVENTS_PATH, now) {
+                    let value: MetadataValue<Ascii> = value
+                        .parse()
+                        .map_err(|_| Status::invalid_argument("invalid auth header"))?;
+                    request
+                        .metadata_mut()
+                        .insert(MetadataKey::from_static(name), value);
+                }
+                Ok(request)
+            });
+        let mut stream = client
+            .subscribe_events(self.request.clone())
+            .await?
+            .into_inner();
+        info!("Forwarding events from {}", self.endpoint);
+        while let Some(event) = stream.message().await? {
+            match Self::decode(&event) {
+                Ok(decoded) => callback(decoded),
+                Err(e) => warn!(
+                    "Skipping {} event {} from {}: {}",
+                    event.event_type, event.id, self.endpoint, e
+                ),
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::common::AnyResult;
+
+/// Slots between automatic saves of a file backed checkpoint
+const DEFAULT_SAVE_INTERVAL_SLOTS: u64 = 100;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CheckpointFile {
+    slot: u64,
+}
+
+/// 续传检查点 - 记录已完整处理的最高slot
+///
+/// A restarted deployment resumes from `slot() + 1`. Optionally backed by a small
+/// JSON file that is rewritten every `save_interval` slots and on `save()`.
+#[derive(Debug)]
+pub struct ResumeCheckpoint {
+    path: Option<PathBuf>,
+    slot: AtomicU64,
+    saved_slot: AtomicU64,
+    save_interval: u64,
+}
+
+impl Default for ResumeCheckpoint {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl ResumeCheckpoint {
+    /// In-memory checkpoint starting at `slot`
+    pub fn new(slot: u64) -> Self {
+        Self {
+            path: None,
+            slot: AtomicU64::new(slot),
+            saved_slot: AtomicU64::new(slot),
+            save_interval: DEFAULT_SAVE_INTERVAL_SLOTS,
+        }
+    }
+
+    /// Load a file backed checkpoint; a missing file starts at slot 0
+    pub fn load(path: impl AsRef<Path>) -> AnyResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let slot = if path.exists() {
+            serde_json::from_str::<CheckpointFile>(&std::fs::read_to_string(&path)?)?.slot
+        } else {
+            0
+        };
+        Ok(Self {
+            path: Some(path),
+            ..Self::new(slot)
+        })
+    }
+
+    pub fn with_save_interval(mut self, slots: u64) -> Self {
+        self.save_interval = slots.max(1);
+        self
+    }
+
+    /// Highest fully processed slot, 0 if nothing was processed yet
+    pub fn slot(&self) -> u64 {
+        self.slot.load(Ordering::Acquire)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slot() == 0
+    }
+
+    /// Mark `slot` as processed; never moves the checkpoint backwards
+    pub fn advance(&self, slot: u64) -> AnyResult<()> {
+        let previous = self.slot.fetch_max(slot, Ordering::AcqRel);
+        if slot > previous
+            && slot
+                >= self
+                    .saved_slot
+                    .load(Ordering::Acquire)
+                    .saturating_add(self.save_interval)
+        {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Persist the checkpoint if it is file backed
+    pub fn save(&self) -> AnyResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let slot = self.slot();
+        // 先写临时文件再重命名，避免崩溃时留下不完整的文件
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(&CheckpointFile { slot })?)?;
+        std::fs::rename(&tmp, path)?;
+        self.saved_slot.fetch_max(slot, Ordering::AcqRel);
+        Ok(())
+    }
+}
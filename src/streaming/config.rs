@@ -0,0 +1,507 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::fs::OpenOptions;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::common::AnyResult;
+use crate::streaming::checkpoint::ResumeCheckpoint;
+use crate::streaming::event_parser::{
+    common::{EventType, FailedTxPolicy, ParseOptions},
+    Protocol,
+};
+use crate::streaming::memory::MemoryBudget;
+use crate::streaming::sink::{JsonLinesSink, RedactionConfig, SinkWriter};
+
+/// Prefix of the environment variables read by [`StreamerConfig::from_env`]
+pub const ENV_PREFIX: &str = "SOLANA_STREAMER_";
+
+/// 端点类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointKind {
+    #[default]
+    Yellowstone,
+    Shredstream,
+}
+
+/// 单个数据源端点
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EndpointConfig {
+    pub kind: EndpointKind,
+    pub url: String,
+    pub x_token: Option<String>,
+    pub bearer_token: Option<String>,
+    /// `gzip` or `zstd`
+    pub compression: Option<String>,
+    /// Extra metadata headers sent with every request
+    pub headers: Vec<(String, String)>,
+}
+
+/// 多端点工作方式，对应 `EndpointMode`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointModeConfig {
+    #[default]
+    Failover,
+    Race,
+}
+
+/// 订阅和事件过滤
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FilterConfig {
+    pub account_include: Vec<String>,
+    pub account_exclude: Vec<String>,
+    pub account_required: Vec<String>,
+    /// Event types to deliver; empty delivers all
+    pub event_types: Vec<EventType>,
+    pub failed_tx_policy: FailedTxPolicy,
+    pub include_raw: bool,
+    pub decoded_instructions: bool,
+    pub unknown_instructions: bool,
+}
+
+impl FilterConfig {
+    /// Whether events of the type should be delivered
+    pub fn accepts(&self, event_type: &EventType) -> bool {
+        self.event_types.is_empty() || self.event_types.contains(event_type)
+    }
+
+    pub fn parse_options(&self) -> ParseOptions {
+        ParseOptions::new()
+            .with_failed_tx_policy(self.failed_tx_policy)
+            .with_include_raw(self.include_raw)
+            .with_decoded_instructions(self.decoded_instructions)
+            .with_unknown_instructions(self.unknown_instructions)
+    }
+}
+
+/// 输出端配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SinkConfig {
+    /// JSON Lines appended to a file
+    JsonLines {
+        path: PathBuf,
+        #[serde(default)]
+        redaction: RedactionConfig,
+    },
+    /// JSON Lines on standard output
+    Stdout {
+        #[serde(default)]
+        redaction: RedactionConfig,
+    },
+}
+
+impl SinkConfig {
+    /// Open the sink
+    pub fn writer(&self) -> AnyResult<SinkWriter> {
+        match self {
+            SinkConfig::JsonLines { path, redaction } => {
+                let file = OpenOptions::new().create(true).append(true).open(path)?;
+                Ok(SinkWriter::new(JsonLinesSink::new(BufWriter::new(file)))
+                    .with_redaction(redaction.clone()))
+            }
+            SinkConfig::Stdout { redaction } => {
+                Ok(SinkWriter::new(JsonLinesSink::new(std::io::stdout()))
+                    .with_redaction(redaction.clone()))
+            }
+        }
+    }
+}
+
+/// 性能相关参数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PerformanceConfig {
+    /// Capacity of the parsed event channel
+    pub channel_capacity: usize,
+    /// Async handlers running at once
+    pub handler_concurrency: usize,
+    pub handler_queue_capacity: usize,
+    /// Signatures remembered to drop duplicates across endpoints
+    pub dedup_window: usize,
+    pub endpoint_mode: EndpointModeConfig,
+    pub connect_timeout_secs: u64,
+    pub request_timeout_secs: u64,
+    pub max_decoding_message_size: usize,
+    /// Memory budget of caches and trackers in MiB, 0 for unlimited
+    pub memory_limit_mb: usize,
+}
+
+impl Default for PerformanceConfig {
+    fn default() -> Self {
+        // 与各模块的默认值一致
+        Self {
+            channel_capacity: 10_000,
+            handler_concurrency: 64,
+            handler_queue_capacity: 10_000,
+            dedup_window: 100_000,
+            endpoint_mode: EndpointModeConfig::Failover,
+            connect_timeout_secs: 10,
+            request_timeout_secs: 60,
+            max_decoding_message_size: 1024 * 1024 * 10,
+            memory_limit_mb: 0,
+        }
+    }
+}
+
+/// 续传检查点配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CheckpointConfig {
+    pub path: PathBuf,
+    #[serde(default = "default_save_interval_slots")]
+    pub save_interval_slots: u64,
+}
+
+fn default_save_interval_slots() -> u64 {
+    100
+}
+
+impl CheckpointConfig {
+    pub fn load(&self) -> AnyResult<ResumeCheckpoint> {
+        Ok(ResumeCheckpoint::load(&self.path)?.with_save_interval(self.save_interval_slots))
+    }
+}
+
+/// 整个流水线的配置
+///
+/// Covers endpoints, protocols, filters, sinks, performance knobs and
+/// checkpointing, so a deployment can be driven by a config file:
+///
+/// ```toml
+/// protocols = ["PumpFun", "PumpSwap"]
+///
+/// [[endpoints]]
+/// url = "https://grpc.example.com:443"
+/// x_token = "..."
+///
+/// [filters]
+/// account_include = ["6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P"]
+/// failed_tx_policy = "ParseWithFlag"
+///
+/// [[sinks]]
+/// type = "json_lines"
+/// path = "events.jsonl"
+///
+/// [checkpoint]
+/// path = "checkpoint.json"
+/// ```
+///
+/// Loading validates the whole file and reports every problem at once, with the
+/// path of the offending key.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StreamerConfig {
+    pub endpoints: Vec<EndpointConfig>,
+    /// Protocol names as accepted by `Protocol::from_str`
+    pub protocols: Vec<String>,
+    pub filters: FilterConfig,
+    pub sinks: Vec<SinkConfig>,
+    pub performance: PerformanceConfig,
+    pub checkpoint: Option<CheckpointConfig>,
+}
+
+impl StreamerConfig {
+    /// Parse and validate a TOML document
+    pub fn from_toml(config: &str) -> AnyResult<Self> {
+        let config: Self =
+            toml::from_str(config).map_err(|e| anyhow::anyhow!("invalid config: {}", e))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> AnyResult<Self> {
+        let path = path.as_ref();
+        let config = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("cannot read config {}: {}", path.display(), e))?;
+        Self::from_toml(&config)
+    }
+
+    /// Load from the process environment, see [`StreamerConfig::from_vars`]
+    pub fn from_env() -> AnyResult<Self> {
+        Self::from_vars(std::env::vars())
+    }
+
+    /// Load from `SOLANA_STREAMER_*` variables
+    ///
+    /// `SOLANA_STREAMER_CONFIG` names a TOML file to start from. The other
+    /// variables override it:
+    ///
+    /// - `ENDPOINT`: replaces the endpoints with a single Yellowstone one
+    /// - `X_TOKEN`: x-token of every endpoint
+    /// - `PROTOCOLS`, `ACCOUNT_INCLUDE`, `ACCOUNT_EXCLUDE`, `ACCOUNT_REQUIRED`:
+    ///   comma separated lists
+    /// - `FAILED_TX_POLICY`: `Skip`, `ParseWithFlag` or `OnlyFailed`
+    /// - `CHANNEL_CAPACITY`, `MEMORY_LIMIT_MB`: numbers
+    /// - `CHECKPOINT`: checkpoint file path
+    pub fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> AnyResult<Self> {
+        let vars: Vec<(String, String)> = vars
+            .into_iter()
+            .filter_map(|(key, value)| Some((key.strip_prefix(ENV_PREFIX)?.to_string(), value)))
+            .collect();
+        let var = |name: &str| {
+            vars.iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.trim())
+        };
+        let list = |value: &str| -> Vec<String> {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect()
+        };
+
+        let mut config: Self = match var("CONFIG") {
+            Some(path) => {
+                let config = std::fs::read_to_string(path)
+                    .map_err(|e| anyhow::anyhow!("cannot read config {}: {}", path, e))?;
+                toml::from_str(&config).map_err(|e| anyhow::anyhow!("invalid config: {}", e))?
+            }
+            None => Self::default(),
+        };
+        if let Some(url) = var("ENDPOINT") {
+            config.endpoints = vec![EndpointConfig {
+                url: url.to_string(),
+                ..Default::default()
+            }];
+        }
+        if let Some(x_token) = var("X_TOKEN") {
+            for endpoint in &mut config.endpoints {
+                endpoint.x_token = Some(x_token.to_string());
+            }
+        }
+        if let Some(protocols) = var("PROTOCOLS") {
+            config.protocols = list(protocols);
+        }
+        if let Some(accounts) = var("ACCOUNT_INCLUDE") {
+            config.filters.account_include = list(accounts);
+        }
+        if let Some(accounts) = var("ACCOUNT_EXCLUDE") {
+            config.filters.account_exclude = list(accounts);
+        }
+        if let Some(accounts) = var("ACCOUNT_REQUIRED") {
+            config.filters.account_required = list(accounts);
+        }
+        if let Some(policy) = var("FAILED_TX_POLICY") {
+            config.filters.failed_tx_policy = match policy {
+                "Skip" => FailedTxPolicy::Skip,
+                "ParseWithFlag" => FailedTxPolicy::ParseWithFlag,
+                "OnlyFailed" => FailedTxPolicy::OnlyFailed,
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "{}FAILED_TX_POLICY: expected Skip, ParseWithFlag or OnlyFailed, got {:?}",
+                        ENV_PREFIX,
+                        policy
+                    ))
+                }
+            };
+        }
+        if let Some(capacity) = var("CHANNEL_CAPACITY") {
+            config.performance.channel_capacity = capacity.parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "{}CHANNEL_CAPACITY: expected a number, got {:?}",
+                    ENV_PREFIX,
+                    capacity
+                )
+            })?;
+        }
+        if let Some(limit) = var("MEMORY_LIMIT_MB") {
+            config.performance.memory_limit_mb = limit.parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "{}MEMORY_LIMIT_MB: expected a number, got {:?}",
+                    ENV_PREFIX,
+                    limit
+                )
+            })?;
+        }
+        if let Some(path) = var("CHECKPOINT") {
+            config.checkpoint = Some(CheckpointConfig {
+                path: PathBuf::from(path),
+                save_interval_slots: default_save_interval_slots(),
+            });
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check the whole config, reporting all problems in one error
+    pub fn validate(&self) -> AnyResult<()> {
+        let mut problems = vec![];
+        if self.endpoints.is_empty() {
+            problems.push("endpoints: at least one endpoint is required".to_string());
+        }
+        for (i, endpoint) in self.endpoints.iter().enumerate() {
+            if !endpoint.url.starts_with("http://") && !endpoint.url.starts_with("https://") {
+                problems.push(format!(
+                    "endpoints[{}].url: expected an http:// or https:// URL, got {:?}",
+                    i, endpoint.url
+                ));
+            }
+            if let Some(compression) = &endpoint.compression {
+                if compression != "gzip" && compression != "zstd" {
+                    problems.push(format!(
+                        "endpoints[{}].compression: expected \"gzip\" or \"zstd\", got {:?}",
+                        i, compression
+                    ));
+                }
+            }
+        }
+        if self.protocols.is_empty() {
+            problems.push("protocols: at least one protocol is required".to_string());
+        }
+        for (i, protocol) in self.protocols.iter().enumerate() {
+            if let Err(e) = Protocol::from_str(protocol) {
+                problems.push(format!("protocols[{}]: {}", i, e));
+            }
+        }
+        for (key, accounts) in [
+            ("account_include", &self.filters.account_include),
+            ("account_exclude", &self.filters.account_exclude),
+            ("account_required", &self.filters.account_required),
+        ] {
+            for (i, account) in accounts.iter().enumerate() {
+                if Pubkey::from_str(account).is_err() {
+                    problems.push(format!(
+                        "filters.{}[{}]: {:?} is not a valid base58 pubkey",
+                        key, i, account
+                    ));
+                }
+            }
+        }
+        for (i, sink) in self.sinks.iter().enumerate() {
+            if let SinkConfig::JsonLines { path, .. } = sink {
+                if path.as_os_str().is_empty() {
+                    problems.push(format!("sinks[{}].path: must not be empty", i));
+                }
+            }
+        }
+        let performance = &self.performance;
+        for (key, value) in [
+            ("channel_capacity", performance.channel_capacity),
+            ("handler_concurrency", performance.handler_concurrency),
+            ("handler_queue_capacity", performance.handler_queue_capacity),
+            ("dedup_window", performance.dedup_window),
+            (
+                "max_decoding_message_size",
+                performance.max_decoding_message_size,
+            ),
+        ] {
+            if value == 0 {
+                problems.push(format!("performance.{}: must be greater than 0", key));
+            }
+        }
+        if let Some(checkpoint) = &self.checkpoint {
+            if checkpoint.path.as_os_str().is_empty() {
+                problems.push("checkpoint.path: must not be empty".to_string());
+            }
+            if checkpoint.save_interval_slots == 0 {
+                problems.push("checkpoint.save_interval_slots: must be greater than 0".to_string());
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "invalid config:\n  {}",
+                problems.join("\n  ")
+            ))
+        }
+    }
+
+    pub fn protocols(&self) -> AnyResult<Vec<Protocol>> {
+        self.protocols
+            .iter()
+            .map(|protocol| Protocol::from_str(protocol))
+            .collect()
+    }
+
+    pub fn parse_options(&self) -> ParseOptions {
+        self.filters.parse_options()
+    }
+
+    /// Open every configured sink
+    pub fn sink_writers(&self) -> AnyResult<Vec<SinkWriter>> {
+        self.sinks.iter().map(SinkConfig::writer).collect()
+    }
+
+    /// Budget to share between the caches and trackers of this deployment
+    pub fn memory_budget(&self) -> MemoryBudget {
+        MemoryBudget::new(self.performance.memory_limit_mb * 1024 * 1024)
+    }
+
+    /// Load the configured checkpoint, if any
+    pub fn resume_checkpoint(&self) -> AnyResult<Option<ResumeCheckpoint>> {
+        self.checkpoint
+            .as_ref()
+            .map(CheckpointConfig::load)
+            .transpose()
+    }
+}
+
+#[cfg(feature = "grpc")]
+impl EndpointConfig {
+    /// Connection settings of a Yellowstone endpoint
+    pub fn grpc_config(
+        &self,
+        performance: &PerformanceConfig,
+    ) -> AnyResult<crate::streaming::GrpcConfig> {
+        use crate::streaming::{GrpcCompression, GrpcConfig};
+        use std::time::Duration;
+
+        let mut config = GrpcConfig::new()
+            .with_connect_timeout(Duration::from_secs(performance.connect_timeout_secs))
+            .with_request_timeout(Duration::from_secs(performance.request_timeout_secs))
+            .with_max_decoding_message_size(performance.max_decoding_message_size)
+            .with_tls(self.url.starts_with("https://"));
+        config.x_token = self.x_token.clone();
+        config.bearer_token = self.bearer_token.clone();
+        config.headers = self.headers.clone();
+        config.compression = match self.compression.as_deref() {
+            None => None,
+            Some("gzip") => Some(GrpcCompression::Gzip),
+            Some("zstd") => Some(GrpcCompression::Zstd),
+            Some(other) => return Err(anyhow::anyhow!("unknown compression {:?}", other)),
+        };
+        Ok(config)
+    }
+}
+
+#[cfg(feature = "grpc")]
+impl StreamerConfig {
+    /// Clients of the Yellowstone endpoints in the configured order
+    pub fn yellowstone_clients(&self) -> AnyResult<Vec<crate::streaming::YellowstoneGrpc>> {
+        let parse_options = self.parse_options();
+        self.endpoints
+            .iter()
+            .filter(|endpoint| endpoint.kind == EndpointKind::Yellowstone)
+            .map(|endpoint| {
+                Ok(
+                    crate::streaming::YellowstoneGrpc::new(endpoint.url.clone(), None)?
+                        .with_config(endpoint.grpc_config(&self.performance)?)
+                        .with_failed_tx_policy(parse_options.failed_tx_policy)
+                        .with_include_raw(parse_options.include_raw),
+                )
+            })
+            .collect()
+    }
+
+    /// All Yellowstone endpoints combined with the configured mode and dedup window
+    pub fn multi_endpoint(&self) -> AnyResult<crate::streaming::MultiEndpointGrpc> {
+        use crate::streaming::{EndpointMode, MultiEndpointGrpc};
+
+        let mode = match self.performance.endpoint_mode {
+            EndpointModeConfig::Failover => EndpointMode::Failover,
+            EndpointModeConfig::Race => EndpointMode::Race,
+        };
+        Ok(MultiEndpointGrpc::new(self.yellowstone_clients()?)?
+            .with_mode(mode)
+            .with_dedup_window(self.performance.dedup_window))
+    }
+}
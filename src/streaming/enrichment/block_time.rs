@@ -0,0 +1,213 @@
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::streaming::event_parser::UnifiedEvent;
+use crate::streaming::pipeline::EventMiddleware;
+
+/// 区块时间只有秒级精度
+const BLOCK_TIME_RESOLUTION_MS: i64 = 1_000;
+
+/// 区块时间异常的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockTimeAnomaly {
+    /// block_time is later than the time the event was received
+    Future,
+    /// block_time is much older than the time the event was received
+    Stale,
+    /// block_time moved backwards while the slot moved forwards
+    Backwards,
+    /// block_time moved forwards faster than the slots can explain
+    TooFast,
+}
+
+/// 区块时间健康统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockTimeHealth {
+    /// Events with a block time that were checked
+    pub samples: u64,
+    /// Events flagged as `block_time_suspect`
+    pub suspect: u64,
+    pub future: u64,
+    pub stale: u64,
+    pub backwards: u64,
+    pub too_fast: u64,
+    /// Drift of the last plausible event (received time - block time, milliseconds)
+    pub last_drift_ms: i64,
+    pub min_drift_ms: i64,
+    pub max_drift_ms: i64,
+    /// Sum of the drifts of plausible events, for the mean
+    pub total_drift_ms: i64,
+}
+
+impl BlockTimeHealth {
+    /// Mean drift of plausible events in milliseconds
+    pub fn mean_drift_ms(&self) -> i64 {
+        let plausible = self.samples.saturating_sub(self.suspect);
+        if plausible == 0 {
+            0
+        } else {
+            self.total_drift_ms / plausible as i64
+        }
+    }
+}
+
+#[derive(Default)]
+struct CheckerState {
+    health: BlockTimeHealth,
+    // 最近一个可信事件的 (slot, block_time_ms)
+    reference: Option<(u64, i64)>,
+}
+
+/// 区块时间合理性检查
+///
+/// Cross-checks `block_time_ms` against the time the event was received and against
+/// the slot progression of previously seen events, and sets `block_time_suspect` on
+/// events whose block time is implausible (providers do send corrupt timestamps).
+/// Events without a block time are passed through unchecked. Drift statistics are
+/// available through [`BlockTimeChecker::health`].
+///
+/// The stale check compares against the wall clock, so disable it with
+/// `with_max_lag(None)` when replaying historical data.
+pub struct BlockTimeChecker {
+    max_future_ms: i64,
+    max_lag_ms: Option<i64>,
+    max_slot_ms: i64,
+    state: Mutex<CheckerState>,
+}
+
+impl Default for BlockTimeChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlockTimeChecker {
+    pub fn new() -> Self {
+        Self {
+            max_future_ms: 2_000,
+            max_lag_ms: Some(120_000),
+            max_slot_ms: 2_000,
+            state: Mutex::new(CheckerState::default()),
+        }
+    }
+
+    /// How far block time may be ahead of the received time
+    pub fn with_max_future(mut self, max_future_ms: i64) -> Self {
+        self.max_future_ms = max_future_ms;
+        self
+    }
+
+    /// How far block time may be behind the received time; `None` disables the check
+    pub fn with_max_lag(mut self, max_lag_ms: Option<i64>) -> Self {
+        self.max_lag_ms = max_lag_ms;
+        self
+    }
+
+    /// Longest plausible average slot duration between two events
+    pub fn with_max_slot_duration(mut self, max_slot_ms: i64) -> Self {
+        self.max_slot_ms = max_slot_ms;
+        self
+    }
+
+    pub fn health(&self) -> BlockTimeHealth {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .health
+            .clone()
+    }
+
+    pub fn reset(&self) {
+        *self.state.lock().unwrap_or_else(|e| e.into_inner()) = CheckerState::default();
+    }
+
+    /// Check an event's block time, returns the anomaly if it is implausible
+    pub fn check(
+        &self,
+        slot: u64,
+        block_time_ms: i64,
+        received_time_ms: i64,
+    ) -> Option<BlockTimeAnomaly> {
+        let drift_ms = received_time_ms - block_time_ms;
+        let mut guard = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let state = &mut *guard;
+        let anomaly = if drift_ms < -(self.max_future_ms + BLOCK_TIME_RESOLUTION_MS) {
+            Some(BlockTimeAnomaly::Future)
+        } else if self
+            .max_lag_ms
+            .is_some_and(|max_lag_ms| drift_ms > max_lag_ms)
+        {
+            Some(BlockTimeAnomaly::Stale)
+        } else {
+            state
+                .reference
+                .and_then(|(reference_slot, reference_time_ms)| {
+                    if slot <= reference_slot {
+                        return None;
+                    }
+                    let elapsed_ms = block_time_ms - reference_time_ms;
+                    let max_elapsed_ms = (slot - reference_slot) as i64 * self.max_slot_ms
+                        + BLOCK_TIME_RESOLUTION_MS;
+                    if elapsed_ms < -BLOCK_TIME_RESOLUTION_MS {
+                        Some(BlockTimeAnomaly::Backwards)
+                    } else if elapsed_ms > max_elapsed_ms {
+                        Some(BlockTimeAnomaly::TooFast)
+                    } else {
+                        None
+                    }
+                })
+        };
+
+        let health = &mut state.health;
+        health.samples += 1;
+        match anomaly {
+            Some(anomaly) => {
+                health.suspect += 1;
+                match anomaly {
+                    BlockTimeAnomaly::Future => health.future += 1,
+                    BlockTimeAnomaly::Stale => health.stale += 1,
+                    BlockTimeAnomaly::Backwards => health.backwards += 1,
+                    BlockTimeAnomaly::TooFast => health.too_fast += 1,
+                }
+            }
+            None => {
+                if health.samples - health.suspect == 1 {
+                    health.min_drift_ms = drift_ms;
+                    health.max_drift_ms = drift_ms;
+                } else {
+                    health.min_drift_ms = health.min_drift_ms.min(drift_ms);
+                    health.max_drift_ms = health.max_drift_ms.max(drift_ms);
+                }
+                health.last_drift_ms = drift_ms;
+                health.total_drift_ms += drift_ms;
+                if !state
+                    .reference
+                    .is_some_and(|(reference_slot, _)| slot < reference_slot)
+                {
+                    state.reference = Some((slot, block_time_ms));
+                }
+            }
+        }
+        anomaly
+    }
+}
+
+impl EventMiddleware for BlockTimeChecker {
+    fn name(&self) -> &str {
+        "block_time_checker"
+    }
+
+    fn handle(&self, mut event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        let metadata = event.metadata();
+        if metadata.block_time_ms > 0 {
+            let anomaly = self.check(
+                metadata.slot,
+                metadata.block_time_ms,
+                metadata.program_received_time_ms,
+            );
+            event.metadata_mut().block_time_suspect = anomaly.is_some();
+        }
+        vec![event]
+    }
+}
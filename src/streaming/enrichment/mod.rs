@@ -0,0 +1,57 @@
+pub mod block_time;
+pub mod risk;
+pub mod token_badges;
+
+pub use block_time::{BlockTimeAnomaly, BlockTimeChecker, BlockTimeHealth};
+pub use risk::{
+    DevHoldingRule, FreezeAuthorityRule, MintAuthorityRule, MutableMetadataRule, RiskAnnotator,
+    RiskRule, TokenRiskInfo,
+};
+pub use token_badges::{TokenBadgeProvider, TokenListSource};
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::event_parser::{
+    protocols::{
+        bonk::{BonkPoolCreateEvent, BonkTradeEvent},
+        pumpfun::{PumpFunCreateTokenEvent, PumpFunTradeEvent},
+        pumpswap::{
+            PumpSwapBuyEvent, PumpSwapCreatePoolEvent, PumpSwapDepositEvent, PumpSwapSellEvent,
+            PumpSwapWithdrawEvent,
+        },
+        raydium_clmm::RaydiumClmmSwapV2Event,
+        raydium_cpmm::RaydiumCpmmSwapEvent,
+    },
+    UnifiedEvent,
+};
+
+/// 提取事件涉及的mint地址
+pub fn event_mints(event: &dyn UnifiedEvent) -> Vec<Pubkey> {
+    let any = event.as_any();
+    let mints = if let Some(e) = any.downcast_ref::<PumpFunCreateTokenEvent>() {
+        vec![e.mint]
+    } else if let Some(e) = any.downcast_ref::<PumpFunTradeEvent>() {
+        vec![e.mint]
+    } else if let Some(e) = any.downcast_ref::<PumpSwapBuyEvent>() {
+        vec![e.base_mint, e.quote_mint]
+    } else if let Some(e) = any.downcast_ref::<PumpSwapSellEvent>() {
+        vec![e.base_mint, e.quote_mint]
+    } else if let Some(e) = any.downcast_ref::<PumpSwapCreatePoolEvent>() {
+        vec![e.base_mint, e.quote_mint]
+    } else if let Some(e) = any.downcast_ref::<PumpSwapDepositEvent>() {
+        vec![e.base_mint, e.quote_mint]
+    } else if let Some(e) = any.downcast_ref::<PumpSwapWithdrawEvent>() {
+        vec![e.base_mint, e.quote_mint]
+    } else if let Some(e) = any.downcast_ref::<BonkTradeEvent>() {
+        vec![e.base_token_mint, e.quote_token_mint]
+    } else if let Some(e) = any.downcast_ref::<BonkPoolCreateEvent>() {
+        vec![e.base_mint, e.quote_mint]
+    } else if let Some(e) = any.downcast_ref::<RaydiumCpmmSwapEvent>() {
+        vec![e.input_token_mint, e.output_token_mint]
+    } else if let Some(e) = any.downcast_ref::<RaydiumClmmSwapV2Event>() {
+        vec![e.input_vault_mint, e.output_vault_mint]
+    } else {
+        vec![]
+    };
+    mints.into_iter().filter(|mint| *mint != Pubkey::default()).collect()
+}
@@ -0,0 +1,347 @@
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+
+use crate::streaming::enrichment::event_mints;
+use crate::streaming::event_parser::{
+    common::RiskFlags,
+    protocols::{
+        bonk::{BonkPoolCreateEvent, CurveParams},
+        metaplex::{MetaplexCreateMetadataEvent, MetaplexUpdateMetadataEvent},
+        pumpfun::PumpFunCreateTokenEvent,
+    },
+    UnifiedEvent,
+};
+use crate::streaming::memory::MemoryBudget;
+use crate::streaming::normalize::{is_quote_mint, NormalizedTrade};
+use crate::streaming::pipeline::EventMiddleware;
+use crate::streaming::trackers::ShardedMap;
+
+/// Default share of the supply above which the creator's holding is flagged
+pub const DEFAULT_DEV_HOLDING_BPS: u64 = 1_000;
+
+/// Size of an SPL token mint account without extensions
+const MINT_ACCOUNT_LEN: usize = 82;
+
+/// 代币的风险相关状态
+///
+/// Authorities are `None` both when revoked and when the mint account was never
+/// seen, so unknown tokens are not flagged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenRiskInfo {
+    pub mint_authority: Option<Pubkey>,
+    pub freeze_authority: Option<Pubkey>,
+    /// None until a metadata create or update instruction was seen
+    pub is_mutable: Option<bool>,
+    pub creator: Option<Pubkey>,
+    /// Total supply in base units, 0 when unknown
+    pub supply: u64,
+    /// Tokens the creator bought minus those it sold, as seen on the stream
+    pub creator_balance: u64,
+}
+
+impl TokenRiskInfo {
+    /// Creator holding in basis points of the supply, None when the supply is unknown
+    pub fn dev_holding_bps(&self) -> Option<u64> {
+        if self.supply == 0 {
+            return None;
+        }
+        Some((self.creator_balance as u128 * 10_000 / self.supply as u128) as u64)
+    }
+}
+
+/// 风险规则
+///
+/// Implement it to add custom heuristics; use a [`RiskFlags::custom`] flag so they
+/// don't collide with the built-in ones.
+pub trait RiskRule: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Flag set on events whose token matches the rule
+    fn flag(&self) -> RiskFlags;
+
+    fn check(&self, event: &dyn UnifiedEvent, token: &TokenRiskInfo) -> bool;
+}
+
+/// 铸币权限未放弃
+pub struct MintAuthorityRule;
+
+impl RiskRule for MintAuthorityRule {
+    fn name(&self) -> &str {
+        "mint_authority"
+    }
+
+    fn flag(&self) -> RiskFlags {
+        RiskFlags::MINT_AUTHORITY
+    }
+
+    fn check(&self, _event: &dyn UnifiedEvent, token: &TokenRiskInfo) -> bool {
+        token.mint_authority.is_some()
+    }
+}
+
+/// 存在冻结权限
+pub struct FreezeAuthorityRule;
+
+impl RiskRule for FreezeAuthorityRule {
+    fn name(&self) -> &str {
+        "freeze_authority"
+    }
+
+    fn flag(&self) -> RiskFlags {
+        RiskFlags::FREEZE_AUTHORITY
+    }
+
+    fn check(&self, _event: &dyn UnifiedEvent, token: &TokenRiskInfo) -> bool {
+        token.freeze_authority.is_some()
+    }
+}
+
+/// 创建者持仓超过阈值
+pub struct DevHoldingRule {
+    /// Threshold in basis points of the supply
+    pub max_bps: u64,
+}
+
+impl Default for DevHoldingRule {
+    fn default() -> Self {
+        Self {
+            max_bps: DEFAULT_DEV_HOLDING_BPS,
+        }
+    }
+}
+
+impl RiskRule for DevHoldingRule {
+    fn name(&self) -> &str {
+        "dev_holding"
+    }
+
+    fn flag(&self) -> RiskFlags {
+        RiskFlags::DEV_HOLDING
+    }
+
+    fn check(&self, _event: &dyn UnifiedEvent, token: &TokenRiskInfo) -> bool {
+        token
+            .dev_holding_bps()
+            .is_some_and(|bps| bps > self.max_bps)
+    }
+}
+
+/// 元数据可修改
+pub struct MutableMetadataRule;
+
+impl RiskRule for MutableMetadataRule {
+    fn name(&self) -> &str {
+        "mutable_metadata"
+    }
+
+    fn flag(&self) -> RiskFlags {
+        RiskFlags::MUTABLE_METADATA
+    }
+
+    fn check(&self, _event: &dyn UnifiedEvent, token: &TokenRiskInfo) -> bool {
+        token.is_mutable == Some(true)
+    }
+}
+
+/// 风险标注流水线阶段
+///
+/// Keeps per-mint state learned from the stream (launchpad creates, Metaplex
+/// metadata instructions, the creator's own trades) and from mint accounts passed
+/// to [`RiskAnnotator::update_from_mint_account`], e.g. from an account
+/// subscription. Create and trade events get the flags of every rule their
+/// non-quote mint matches in `EventMetadata::risk`.
+pub struct RiskAnnotator {
+    rules: Vec<Arc<dyn RiskRule>>,
+    tokens: ShardedMap<Pubkey, TokenRiskInfo>,
+}
+
+impl Default for RiskAnnotator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RiskAnnotator {
+    /// Annotator with the built-in rules
+    pub fn new() -> Self {
+        Self::empty()
+            .with_rule(MintAuthorityRule)
+            .with_rule(FreezeAuthorityRule)
+            .with_rule(DevHoldingRule::default())
+            .with_rule(MutableMetadataRule)
+    }
+
+    /// Charge the token risk info to `budget`, evicting least recently used entries while
+    /// it is exceeded
+    pub fn with_memory_budget(mut self, budget: &MemoryBudget) -> Self {
+        self.tokens.set_memory_account(budget.account("risk.tokens"));
+        self
+    }
+
+    /// Annotator without any rule
+    pub fn empty() -> Self {
+        Self {
+            rules: vec![],
+            tokens: ShardedMap::new(),
+        }
+    }
+
+    pub fn with_rule(mut self, rule: impl RiskRule + 'static) -> Self {
+        self.rules.push(Arc::new(rule));
+        self
+    }
+
+    /// Replace the threshold of the built-in dev holding rule
+    pub fn with_dev_holding_threshold(mut self, max_bps: u64) -> Self {
+        self.rules
+            .retain(|rule| rule.flag() != RiskFlags::DEV_HOLDING);
+        self.with_rule(DevHoldingRule { max_bps })
+    }
+
+    pub fn rules(&self) -> Vec<&str> {
+        self.rules.iter().map(|rule| rule.name()).collect()
+    }
+
+    pub fn token_info(&self, mint: &Pubkey) -> Option<TokenRiskInfo> {
+        self.tokens.get(mint)
+    }
+
+    pub fn set_token_info(&self, mint: Pubkey, info: TokenRiskInfo) {
+        self.tokens.insert(mint, info);
+    }
+
+    /// Forget a mint, e.g. once it stopped trading
+    pub fn remove_token(&self, mint: &Pubkey) -> Option<TokenRiskInfo> {
+        self.tokens.remove(mint)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Update the authorities and supply from the data of a mint account
+    ///
+    /// Works for SPL Token and Token-2022 mints. Returns false if the data is not
+    /// an initialized mint.
+    pub fn update_from_mint_account(&self, mint: Pubkey, data: &[u8]) -> bool {
+        let Some((mint_authority, supply, freeze_authority)) = parse_mint_account(data) else {
+            return false;
+        };
+        self.tokens.upsert(mint, TokenRiskInfo::default, |info| {
+            info.mint_authority = mint_authority;
+            info.freeze_authority = freeze_authority;
+            info.supply = supply;
+        });
+        true
+    }
+
+    /// Update the token state from an event
+    pub fn observe(&self, event: &dyn UnifiedEvent) {
+        let any = event.as_any();
+        if let Some(e) = any.downcast_ref::<PumpFunCreateTokenEvent>() {
+            self.tokens.upsert(e.mint, TokenRiskInfo::default, |info| {
+                info.creator = Some(e.creator);
+                info.supply = e.token_total_supply;
+            });
+        } else if let Some(e) = any.downcast_ref::<BonkPoolCreateEvent>() {
+            let supply = match &e.curve_param {
+                CurveParams::Constant { data } => data.supply,
+                CurveParams::Fixed { data } => data.supply,
+                CurveParams::Linear { data } => data.supply,
+            };
+            self.tokens
+                .upsert(e.base_mint, TokenRiskInfo::default, |info| {
+                    info.creator = Some(e.creator);
+                    info.supply = supply;
+                });
+        } else if let Some(e) = any.downcast_ref::<MetaplexCreateMetadataEvent>() {
+            self.tokens.upsert(e.mint, TokenRiskInfo::default, |info| {
+                info.is_mutable = Some(e.is_mutable);
+            });
+        } else if let Some(e) = any.downcast_ref::<MetaplexUpdateMetadataEvent>() {
+            if let Some(is_mutable) = e.is_mutable {
+                self.tokens.upsert(e.mint, TokenRiskInfo::default, |info| {
+                    info.is_mutable = Some(is_mutable);
+                });
+            }
+        } else if let Some(trade) = trade(event) {
+            self.tokens.update(&trade.base_mint, |info| {
+                if info.creator != Some(trade.trader) {
+                    return;
+                }
+                if trade.is_buy {
+                    info.creator_balance = info.creator_balance.saturating_add(trade.amount_out);
+                } else {
+                    info.creator_balance = info.creator_balance.saturating_sub(trade.amount_in);
+                }
+            });
+        }
+    }
+
+    /// Flags of the rules the event's tokens match
+    pub fn assess(&self, event: &dyn UnifiedEvent) -> RiskFlags {
+        let mut flags = RiskFlags::default();
+        for mint in risk_mints(event) {
+            let Some(info) = self.tokens.get(&mint) else {
+                continue;
+            };
+            for rule in &self.rules {
+                if rule.check(event, &info) {
+                    flags.insert(rule.flag());
+                }
+            }
+        }
+        flags
+    }
+}
+
+fn trade(event: &dyn UnifiedEvent) -> Option<NormalizedTrade> {
+    match event.as_any().downcast_ref::<NormalizedTrade>() {
+        Some(trade) => Some(trade.clone()),
+        None => NormalizedTrade::from_event(event),
+    }
+}
+
+/// 需要评估风险的mint：创建和交易事件的非报价代币
+fn risk_mints(event: &dyn UnifiedEvent) -> Vec<Pubkey> {
+    let mut mints = event_mints(event);
+    if let Some(trade) = event.as_any().downcast_ref::<NormalizedTrade>() {
+        mints.push(trade.base_mint);
+    }
+    mints.retain(|mint| !is_quote_mint(mint));
+    mints.dedup();
+    mints
+}
+
+/// 解析mint账户：铸币权限、供应量、冻结权限
+fn parse_mint_account(data: &[u8]) -> Option<(Option<Pubkey>, u64, Option<Pubkey>)> {
+    if data.len() < MINT_ACCOUNT_LEN || data[45] == 0 {
+        return None;
+    }
+    let authority = |offset: usize| {
+        let tag = u32::from_le_bytes(data[offset..offset + 4].try_into().ok()?);
+        if tag == 0 {
+            return None;
+        }
+        Pubkey::try_from(&data[offset + 4..offset + 36]).ok()
+    };
+    let supply = u64::from_le_bytes(data[36..44].try_into().ok()?);
+    Some((authority(0), supply, authority(46)))
+}
+
+impl EventMiddleware for RiskAnnotator {
+    fn name(&self) -> &str {
+        "risk_annotator"
+    }
+
+    fn handle(&self, mut event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        self.observe(event.as_ref());
+        let flags = self.assess(event.as_ref());
+        event.metadata_mut().risk = flags;
+        vec![event]
+    }
+}
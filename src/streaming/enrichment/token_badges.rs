@@ -0,0 +1,211 @@
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use crate::common::AnyResult;
+use crate::streaming::enrichment::event_mints;
+use crate::streaming::event_parser::{common::TokenBadge, UnifiedEvent};
+use crate::streaming::pipeline::EventMiddleware;
+
+/// Jupiter strict token list
+pub const JUPITER_STRICT_LIST_URL: &str = "https://token.jup.ag/strict";
+/// Name used for the Jupiter strict list in badges
+pub const JUPITER_STRICT_LIST_NAME: &str = "jupiter-strict";
+
+/// 代币列表来源
+#[derive(Debug, Clone)]
+pub enum TokenListSource {
+    /// Fixed set of mints, e.g. an internal allow list
+    Static { name: String, mints: Vec<Pubkey> },
+    /// JSON file on disk
+    File { name: String, path: PathBuf },
+    /// JSON document fetched over HTTP (requires the `token-lists` feature)
+    Url { name: String, url: String },
+}
+
+impl TokenListSource {
+    pub fn jupiter_strict() -> Self {
+        Self::Url {
+            name: JUPITER_STRICT_LIST_NAME.to_string(),
+            url: JUPITER_STRICT_LIST_URL.to_string(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Static { name, .. } | Self::File { name, .. } | Self::Url { name, .. } => name,
+        }
+    }
+}
+
+/// 代币认证标记提供者
+///
+/// Caches the mints of each configured token list and tags events with the lists
+/// their mints appear in (`EventMetadata::token_badges`). Lists are replaced as a
+/// whole on refresh, so a mint removed upstream loses its badge on the next refresh.
+pub struct TokenBadgeProvider {
+    sources: Vec<TokenListSource>,
+    // 列表名 -> mint集合
+    lists: RwLock<HashMap<String, HashSet<Pubkey>>>,
+}
+
+impl Default for TokenBadgeProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenBadgeProvider {
+    pub fn new() -> Self {
+        Self {
+            sources: vec![],
+            lists: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_source(mut self, source: TokenListSource) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    pub fn sources(&self) -> &[TokenListSource] {
+        &self.sources
+    }
+
+    /// Replace the contents of a list
+    pub fn set_list(&self, name: &str, mints: impl IntoIterator<Item = Pubkey>) -> usize {
+        let mints: HashSet<Pubkey> = mints.into_iter().collect();
+        let len = mints.len();
+        self.lists
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_string(), mints);
+        len
+    }
+
+    /// Names of the lists that include `mint`
+    pub fn badges(&self, mint: &Pubkey) -> Vec<String> {
+        let lists = self.lists.read().unwrap_or_else(|e| e.into_inner());
+        let mut names: Vec<String> = lists
+            .iter()
+            .filter(|(_, mints)| mints.contains(mint))
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    pub fn is_verified(&self, mint: &Pubkey) -> bool {
+        self.lists
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .any(|mints| mints.contains(mint))
+    }
+
+    /// Load a JSON token list from disk
+    pub fn load_file(&self, name: &str, path: &PathBuf) -> AnyResult<usize> {
+        let data = std::fs::read(path)?;
+        let mints = parse_token_list(&data)?;
+        Ok(self.set_list(name, mints))
+    }
+
+    /// Reload all sources that don't need the network
+    pub fn refresh_local(&self) -> AnyResult<()> {
+        for source in &self.sources {
+            match source {
+                TokenListSource::Static { name, mints } => {
+                    self.set_list(name, mints.iter().copied());
+                }
+                TokenListSource::File { name, path } => {
+                    self.load_file(name, path)?;
+                }
+                TokenListSource::Url { .. } => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Fetch a JSON token list over HTTP
+    #[cfg(feature = "token-lists")]
+    pub async fn fetch_url(&self, name: &str, url: &str) -> AnyResult<usize> {
+        let data = reqwest::get(url).await?.error_for_status()?.bytes().await?;
+        let mints = parse_token_list(&data)?;
+        Ok(self.set_list(name, mints))
+    }
+
+    /// Reload every source; a failing source keeps its previously cached list
+    #[cfg(feature = "token-lists")]
+    pub async fn refresh(&self) -> AnyResult<()> {
+        self.refresh_local()?;
+        for source in &self.sources {
+            if let TokenListSource::Url { name, url } = source {
+                if let Err(e) = self.fetch_url(name, url).await {
+                    log::warn!("Failed to refresh token list {}: {:?}", name, e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Refresh all sources now and then every `interval` in the background
+    #[cfg(feature = "token-lists")]
+    pub fn spawn_refresh(
+        self: std::sync::Arc<Self>,
+        interval: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.refresh().await {
+                    log::error!("Error refreshing token lists: {:?}", e);
+                }
+            }
+        })
+    }
+}
+
+/// Parse a token list: either an array of mint strings or an array of objects
+/// with an `address` (or `mint`) field, optionally wrapped in `{"tokens": [...]}`
+fn parse_token_list(data: &[u8]) -> AnyResult<Vec<Pubkey>> {
+    let value: serde_json::Value = serde_json::from_slice(data)?;
+    let entries = match &value {
+        serde_json::Value::Array(entries) => entries,
+        serde_json::Value::Object(object) => object
+            .get("tokens")
+            .and_then(|tokens| tokens.as_array())
+            .ok_or_else(|| anyhow::anyhow!("token list object has no tokens array"))?,
+        _ => return Err(anyhow::anyhow!("unsupported token list format")),
+    };
+    Ok(entries
+        .iter()
+        .filter_map(|entry| match entry {
+            serde_json::Value::String(address) => Some(address.as_str()),
+            serde_json::Value::Object(token) => token
+                .get("address")
+                .or_else(|| token.get("mint"))
+                .and_then(|address| address.as_str()),
+            _ => None,
+        })
+        .filter_map(|address| Pubkey::from_str(address).ok())
+        .collect())
+}
+
+impl EventMiddleware for TokenBadgeProvider {
+    fn name(&self) -> &str {
+        "token_badges"
+    }
+
+    fn handle(&self, mut event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        let badges: Vec<TokenBadge> = event_mints(event.as_ref())
+            .into_iter()
+            .map(|mint| TokenBadge { mint, lists: self.badges(&mint) })
+            .filter(|badge| !badge.lists.is_empty())
+            .collect();
+        event.metadata_mut().token_badges = badges;
+        vec![event]
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::Instant;
+
+/// 时钟 - 事件时间戳的来源
+///
+/// Injected through [`ParseOptions::with_clock`] so tests can pin timestamps and
+/// latency measurements can use a clock that never goes backwards.
+///
+/// [`ParseOptions::with_clock`]: crate::streaming::event_parser::common::ParseOptions::with_clock
+pub trait Clock: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Milliseconds since the Unix epoch
+    fn now_ms(&self) -> i64;
+}
+
+/// 系统时钟 (wall clock)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn name(&self) -> &str {
+        "system"
+    }
+
+    fn now_ms(&self) -> i64 {
+        chrono::Utc::now().timestamp_millis()
+    }
+}
+
+/// 单调时钟
+///
+/// Wall clock time at creation plus the monotonic time elapsed since, so
+/// differences between readings are never negative even if the system clock is
+/// adjusted.
+#[derive(Debug, Clone, Copy)]
+pub struct MonotonicClock {
+    anchor_ms: i64,
+    started: Instant,
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MonotonicClock {
+    pub fn new() -> Self {
+        Self {
+            anchor_ms: chrono::Utc::now().timestamp_millis(),
+            started: Instant::now(),
+        }
+    }
+}
+
+impl Clock for MonotonicClock {
+    fn name(&self) -> &str {
+        "monotonic"
+    }
+
+    fn now_ms(&self) -> i64 {
+        self.anchor_ms + self.started.elapsed().as_millis() as i64
+    }
+}
+
+/// 测试用时钟，只在显式设置时前进
+///
+/// Clones share the same time.
+#[derive(Debug, Clone, Default)]
+pub struct MockClock {
+    now_ms: Arc<AtomicI64>,
+}
+
+impl MockClock {
+    pub fn new(now_ms: i64) -> Self {
+        Self {
+            now_ms: Arc::new(AtomicI64::new(now_ms)),
+        }
+    }
+
+    pub fn set(&self, now_ms: i64) {
+        self.now_ms.store(now_ms, Ordering::SeqCst);
+    }
+
+    pub fn advance(&self, ms: i64) {
+        self.now_ms.fetch_add(ms, Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    fn now_ms(&self) -> i64 {
+        self.now_ms.load(Ordering::SeqCst)
+    }
+}
+
+static SYSTEM_CLOCK: LazyLock<Arc<dyn Clock>> = LazyLock::new(|| Arc::new(SystemClock));
+
+/// 共享时钟句柄，默认为系统时钟
+#[derive(Clone)]
+pub struct ClockHandle(Arc<dyn Clock>);
+
+impl Default for ClockHandle {
+    fn default() -> Self {
+        Self(SYSTEM_CLOCK.clone())
+    }
+}
+
+impl ClockHandle {
+    pub fn new<C: Clock + 'static>(clock: C) -> Self {
+        Self(Arc::new(clock))
+    }
+
+    pub fn now_ms(&self) -> i64 {
+        self.0.now_ms()
+    }
+
+    pub fn name(&self) -> &str {
+        self.0.name()
+    }
+}
+
+impl fmt::Debug for ClockHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ClockHandle").field(&self.name()).finish()
+    }
+}
+
+impl PartialEq for ClockHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for ClockHandle {}
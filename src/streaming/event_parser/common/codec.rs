@@ -0,0 +1,94 @@
+//! 解析器使用的base58/base64/hex解码
+//!
+//! Every decode on the parse path goes through this module. With the
+//! `fast-decode` feature base64 and hex use SIMD decoders and 32/64 byte base58
+//! values (pubkeys, signatures) a fixed-size decoder; without it the portable
+//! crates are used. Results are identical either way.
+
+use solana_sdk::pubkey::Pubkey;
+
+/// 解码错误
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CodecError {
+    #[error("invalid base58: {0}")]
+    Base58(String),
+    #[error("invalid base64: {0}")]
+    Base64(String),
+    #[error("invalid hex: {0}")]
+    Hex(String),
+}
+
+/// 解码任意长度的base58数据，如指令数据
+pub fn base58_decode(data: &str) -> Result<Vec<u8>, CodecError> {
+    #[cfg(feature = "fast-decode")]
+    {
+        // 定长值走专用解码器
+        match data.len() {
+            32..=44 => {
+                let mut out = [0u8; 32];
+                if five8::decode_32(data, &mut out).is_ok() {
+                    return Ok(out.to_vec());
+                }
+            }
+            64..=88 => {
+                let mut out = [0u8; 64];
+                if five8::decode_64(data, &mut out).is_ok() {
+                    return Ok(out.to_vec());
+                }
+            }
+            _ => {}
+        }
+    }
+    bs58::decode(data)
+        .into_vec()
+        .map_err(|e| CodecError::Base58(e.to_string()))
+}
+
+/// 解码base58编码的公钥
+pub fn decode_pubkey(data: &str) -> Option<Pubkey> {
+    #[cfg(feature = "fast-decode")]
+    {
+        let mut out = [0u8; 32];
+        five8::decode_32(data, &mut out).ok()?;
+        Some(Pubkey::new_from_array(out))
+    }
+    #[cfg(not(feature = "fast-decode"))]
+    {
+        data.parse().ok()
+    }
+}
+
+/// 解码标准base64数据，如程序日志
+pub fn base64_decode(data: &str) -> Result<Vec<u8>, CodecError> {
+    #[cfg(feature = "fast-decode")]
+    {
+        base64_simd::STANDARD
+            .decode_to_vec(data)
+            .map_err(|e| CodecError::Base64(e.to_string()))
+    }
+    #[cfg(not(feature = "fast-decode"))]
+    {
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .map_err(|e| CodecError::Base64(e.to_string()))
+    }
+}
+
+/// 解码hex数据
+pub fn hex_decode(data: &str) -> Result<Vec<u8>, CodecError> {
+    #[cfg(feature = "fast-decode")]
+    {
+        if data.len() % 2 != 0 {
+            return Err(CodecError::Hex("odd number of digits".to_string()));
+        }
+        let mut out = vec![0u8; data.len() / 2];
+        faster_hex::hex_decode(data.as_bytes(), &mut out)
+            .map_err(|e| CodecError::Hex(e.to_string()))?;
+        Ok(out)
+    }
+    #[cfg(not(feature = "fast-decode"))]
+    {
+        hex::decode(data).map_err(|e| CodecError::Hex(e.to_string()))
+    }
+}
@@ -0,0 +1,151 @@
+use serde::de::DeserializeOwned;
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::{
+    core::{
+        DecodedInstructionEvent, ParserDegradedEvent, RawInstructionEvent, UnknownProgramEvent,
+    },
+    protocols::{
+        bonk::{BonkPoolCreateEvent, BonkTradeEvent},
+        pumpfun::{
+            PumpFunCollectCreatorFeeEvent, PumpFunCreateTokenEvent, PumpFunSetCreatorEvent,
+            PumpFunSetParamsEvent, PumpFunTradeEvent, PumpFunWithdrawEvent,
+        },
+        pumpswap::{
+            PumpSwapBuyEvent, PumpSwapCreatePoolEvent, PumpSwapDepositEvent, PumpSwapSellEvent,
+            PumpSwapSetCoinCreatorEvent, PumpSwapUpdateFeeConfigEvent, PumpSwapWithdrawEvent,
+        },
+        raydium_clmm::{RaydiumClmmSwapEvent, RaydiumClmmSwapV2Event},
+        raydium_cpmm::RaydiumCpmmSwapEvent,
+        boop::{BoopCreateTokenEvent, BoopGraduateEvent, BoopTradeEvent},
+        believe::{BelieveCreateTokenEvent, BelieveTradeEvent},
+        meteora_damm::{MeteoraDammBootstrapLiquidityEvent, MeteoraDammLiquidityEvent, MeteoraDammSwapEvent},
+        meteora_dbc::{MeteoraDbcInitializePoolEvent, MeteoraDbcMigrateEvent, MeteoraDbcSwapEvent},
+        phoenix::{PhoenixCancelEvent, PhoenixFeeEvent, PhoenixFillEvent, PhoenixFillSummaryEvent, PhoenixPlaceEvent},
+        sanctum::{SanctumStakePoolEvent, SanctumSwapEvent},
+        drift::{DriftFillEvent, DriftLiquidationEvent, DriftOrderEvent},
+        marginfi::{MarginfiLendingEvent, MarginfiLiquidationEvent},
+        kamino::{KaminoLendingEvent, KaminoLiquidationEvent},
+        tensor::{TensorDelistEvent, TensorListEvent, TensorSwapTradeEvent, TensorTakeEvent},
+        magic_eden::{MagicEdenBidFillEvent, MagicEdenListEvent, MagicEdenSaleEvent},
+        metaplex::{MetaplexCreateMetadataEvent, MetaplexUpdateMetadataEvent, MetaplexVerifyEvent},
+        vote::{VoteEvent, VoteWithdrawEvent},
+        stake::{StakeDeactivateEvent, StakeDelegateEvent, StakeWithdrawEvent},
+    },
+    UnifiedEvent,
+};
+use crate::streaming::block_events::{BlockMetaEvent, SlotUpdateEvent};
+use crate::streaming::normalize::{NormalizedLiquidityEvent, NormalizedTrade};
+use crate::streaming::pipeline::LatencyBudgetExceededEvent;
+use crate::streaming::slot_gaps::SlotGapDetectedEvent;
+use crate::streaming::trackers::{
+    AccountDiffEvent, GraduationProgressEvent, LaunchSnapshotEvent, PoolStateChangedEvent,
+    PositionClosedEvent, PriceUpdatedEvent, WalletSessionClosedEvent, WalletSessionOpenedEvent,
+};
+
+fn boxed<T>(json: &str) -> AnyResult<Box<dyn UnifiedEvent>>
+where
+    T: UnifiedEvent + DeserializeOwned + 'static,
+{
+    Ok(Box::new(serde_json::from_str::<T>(json)?))
+}
+
+/// 根据事件类型名称从JSON还原事件
+///
+/// Inverse of [`UnifiedEvent::to_json`] for the event types produced by this crate;
+/// `event_type` is the name from `EventType::to_string`.
+pub fn event_from_json(event_type: &str, json: &str) -> AnyResult<Box<dyn UnifiedEvent>> {
+    match event_type {
+        "PumpSwapBuy" => boxed::<PumpSwapBuyEvent>(json),
+        "PumpSwapSell" => boxed::<PumpSwapSellEvent>(json),
+        "PumpSwapCreatePool" => boxed::<PumpSwapCreatePoolEvent>(json),
+        "PumpSwapDeposit" => boxed::<PumpSwapDepositEvent>(json),
+        "PumpSwapWithdraw" => boxed::<PumpSwapWithdrawEvent>(json),
+        "PumpSwapUpdateFeeConfig" => boxed::<PumpSwapUpdateFeeConfigEvent>(json),
+        "PumpSwapSetCoinCreator" => boxed::<PumpSwapSetCoinCreatorEvent>(json),
+        "PumpFunCreateToken" => boxed::<PumpFunCreateTokenEvent>(json),
+        "PumpFunBuy" | "PumpFunSell" => boxed::<PumpFunTradeEvent>(json),
+        "PumpFunSetParams" => boxed::<PumpFunSetParamsEvent>(json),
+        "PumpFunSetCreator" => boxed::<PumpFunSetCreatorEvent>(json),
+        "PumpFunCollectCreatorFee" => boxed::<PumpFunCollectCreatorFeeEvent>(json),
+        "PumpFunWithdraw" => boxed::<PumpFunWithdrawEvent>(json),
+        "BonkBuyExactIn" | "BonkBuyExactOut" | "BonkSellExactIn" | "BonkSellExactOut" => {
+            boxed::<BonkTradeEvent>(json)
+        }
+        "BonkInitialize" => boxed::<BonkPoolCreateEvent>(json),
+        "RaydiumCpmmSwapBaseInput" | "RaydiumCpmmSwapBaseOutput" => {
+            boxed::<RaydiumCpmmSwapEvent>(json)
+        }
+        "RaydiumClmmSwap" => boxed::<RaydiumClmmSwapEvent>(json),
+        "RaydiumClmmSwapV2" => boxed::<RaydiumClmmSwapV2Event>(json),
+        "BoopCreateToken" => boxed::<BoopCreateTokenEvent>(json),
+        "BoopBuy" | "BoopSell" => boxed::<BoopTradeEvent>(json),
+        "BoopGraduate" => boxed::<BoopGraduateEvent>(json),
+        "BelieveCreateToken" => boxed::<BelieveCreateTokenEvent>(json),
+        "BelieveTrade" => boxed::<BelieveTradeEvent>(json),
+        "MeteoraDammSwap" => boxed::<MeteoraDammSwapEvent>(json),
+        "MeteoraDammAddLiquidity" | "MeteoraDammRemoveLiquidity" => {
+            boxed::<MeteoraDammLiquidityEvent>(json)
+        }
+        "MeteoraDammBootstrapLiquidity" => boxed::<MeteoraDammBootstrapLiquidityEvent>(json),
+        "MeteoraDbcInitializePool" => boxed::<MeteoraDbcInitializePoolEvent>(json),
+        "MeteoraDbcSwap" => boxed::<MeteoraDbcSwapEvent>(json),
+        "MeteoraDbcMigrate" => boxed::<MeteoraDbcMigrateEvent>(json),
+        "PhoenixFill" => boxed::<PhoenixFillEvent>(json),
+        "PhoenixPlace" => boxed::<PhoenixPlaceEvent>(json),
+        "PhoenixCancel" => boxed::<PhoenixCancelEvent>(json),
+        "PhoenixFillSummary" => boxed::<PhoenixFillSummaryEvent>(json),
+        "PhoenixFee" => boxed::<PhoenixFeeEvent>(json),
+        "SanctumDepositSol" | "SanctumDepositStake" | "SanctumWithdrawSol" | "SanctumWithdrawStake" => {
+            boxed::<SanctumStakePoolEvent>(json)
+        }
+        "SanctumSwap" => boxed::<SanctumSwapEvent>(json),
+        "DriftPerpFill" | "DriftSpotFill" => boxed::<DriftFillEvent>(json),
+        "DriftPlaceOrder" => boxed::<DriftOrderEvent>(json),
+        "DriftLiquidation" => boxed::<DriftLiquidationEvent>(json),
+        "MarginfiDeposit" | "MarginfiRepay" | "MarginfiWithdraw" | "MarginfiBorrow" => {
+            boxed::<MarginfiLendingEvent>(json)
+        }
+        "MarginfiLiquidate" => boxed::<MarginfiLiquidationEvent>(json),
+        "KaminoDeposit" | "KaminoWithdraw" | "KaminoBorrow" | "KaminoRepay" => {
+            boxed::<KaminoLendingEvent>(json)
+        }
+        "KaminoLiquidate" => boxed::<KaminoLiquidationEvent>(json),
+        "TensorList" => boxed::<TensorListEvent>(json),
+        "TensorDelist" => boxed::<TensorDelistEvent>(json),
+        "TensorBuy" | "TensorBidFill" => boxed::<TensorTakeEvent>(json),
+        "TensorSwapTrade" => boxed::<TensorSwapTradeEvent>(json),
+        "MagicEdenList" | "MagicEdenDelist" => boxed::<MagicEdenListEvent>(json),
+        "MagicEdenSale" => boxed::<MagicEdenSaleEvent>(json),
+        "MagicEdenBidFill" => boxed::<MagicEdenBidFillEvent>(json),
+        "MetaplexCreateMetadata" => boxed::<MetaplexCreateMetadataEvent>(json),
+        "MetaplexUpdateMetadata" => boxed::<MetaplexUpdateMetadataEvent>(json),
+        "MetaplexVerify" => boxed::<MetaplexVerifyEvent>(json),
+        "VoteCast" => boxed::<VoteEvent>(json),
+        "VoteWithdraw" => boxed::<VoteWithdrawEvent>(json),
+        "StakeDelegate" => boxed::<StakeDelegateEvent>(json),
+        "StakeDeactivate" => boxed::<StakeDeactivateEvent>(json),
+        "StakeWithdraw" => boxed::<StakeWithdrawEvent>(json),
+        "PriceUpdated" => boxed::<PriceUpdatedEvent>(json),
+        "PoolStateChanged" => boxed::<PoolStateChangedEvent>(json),
+        "PositionClosed" => boxed::<PositionClosedEvent>(json),
+        "AccountDiff" => boxed::<AccountDiffEvent>(json),
+        "WalletSessionOpened" => boxed::<WalletSessionOpenedEvent>(json),
+        "WalletSessionClosed" => boxed::<WalletSessionClosedEvent>(json),
+        "NormalizedTrade" => boxed::<NormalizedTrade>(json),
+        "NormalizedLiquidity" => boxed::<NormalizedLiquidityEvent>(json),
+        "GraduationProgress" => boxed::<GraduationProgressEvent>(json),
+        "LaunchSnapshot" => boxed::<LaunchSnapshotEvent>(json),
+        "SlotUpdate" => boxed::<SlotUpdateEvent>(json),
+        "BlockMeta" => boxed::<BlockMetaEvent>(json),
+        "SlotGapDetected" => boxed::<SlotGapDetectedEvent>(json),
+        "LatencyBudgetExceeded" => boxed::<LatencyBudgetExceededEvent>(json),
+        "DecodedInstruction" => boxed::<DecodedInstructionEvent>(json),
+        "ParserDegraded" => boxed::<ParserDegradedEvent>(json),
+        "RawInstruction" => boxed::<RawInstructionEvent>(json),
+        "UnknownProgram" => boxed::<UnknownProgramEvent>(json),
+        #[cfg(feature = "plugins")]
+        "Plugin" => boxed::<crate::streaming::plugin::PluginEvent>(json),
+        _ => Err(anyhow::anyhow!("Unsupported event type {}", event_type)),
+    }
+}
@@ -0,0 +1,91 @@
+use lru::LruCache;
+use solana_sdk::pubkey::Pubkey;
+use std::num::NonZeroUsize;
+use std::sync::{LazyLock, Mutex};
+
+/// Default number of mints remembered by the global registry
+pub const DEFAULT_DEV_REGISTRY_CAPACITY: usize = 100_000;
+
+static GLOBAL_DEV_REGISTRY: LazyLock<DevAddressRegistry> =
+    LazyLock::new(|| DevAddressRegistry::new(DEFAULT_DEV_REGISTRY_CAPACITY));
+
+/// Dev地址注册表 - 跨交易记录每个mint的创建者地址
+///
+/// Bounded LRU keyed by mint (Bonk pools are keyed by their pool state, which maps
+/// 1:1 to a mint). It lets trades that happen long after the create transaction
+/// still be flagged with `is_dev_create_token_trade`.
+pub struct DevAddressRegistry {
+    inner: Mutex<LruCache<Pubkey, Vec<Pubkey>>>,
+}
+
+impl DevAddressRegistry {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            inner: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Registry shared by all parsers in the process
+    pub fn global() -> &'static DevAddressRegistry {
+        &GLOBAL_DEV_REGISTRY
+    }
+
+    /// Record `dev` as a creator address of `mint`
+    pub fn record(&self, mint: Pubkey, dev: Pubkey) {
+        if dev == Pubkey::default() {
+            return;
+        }
+        let mut cache = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        match cache.get_mut(&mint) {
+            Some(devs) => {
+                if !devs.contains(&dev) {
+                    devs.push(dev);
+                }
+            }
+            None => {
+                cache.put(mint, vec![dev]);
+            }
+        }
+    }
+
+    /// Check whether `wallet` is a known creator address of `mint`
+    pub fn is_dev(&self, mint: &Pubkey, wallet: &Pubkey) -> bool {
+        let mut cache = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        cache
+            .get(mint)
+            .map(|devs| devs.contains(wallet))
+            .unwrap_or(false)
+    }
+
+    /// Get all known creator addresses of `mint`
+    pub fn dev_addresses(&self, mint: &Pubkey) -> Vec<Pubkey> {
+        let cache = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        cache.peek(mint).cloned().unwrap_or_default()
+    }
+
+    /// Resize the registry, evicting the least recently used mints if needed
+    pub fn resize(&self, capacity: usize) {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        self.inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .resize(capacity);
+    }
+
+    pub fn remove(&self, mint: &Pubkey) -> Option<Vec<Pubkey>> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).pop(mint)
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
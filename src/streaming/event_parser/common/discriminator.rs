@@ -0,0 +1,194 @@
+//! Anchor鉴别器计算
+//!
+//! Anchor identifies instructions by the first 8 bytes of `sha256("global:<name>")`,
+//! events by the first 8 bytes of `sha256("event:<name>")` and accounts by those of
+//! `sha256("account:<name>")`. Events emitted through `emit_cpi!` are self-CPIs
+//! whose data starts with [`EVENT_IX_TAG`] followed by the event discriminator.
+//! Everything here is `const`, so protocol modules declare discriminators by name
+//! instead of transcribing bytes.
+
+/// Prefix of event self-CPI data, `sha256("anchor:event")[..8]` read as a
+/// big-endian u64 and written little-endian
+pub const EVENT_IX_TAG: [u8; 8] =
+    u64::from_be_bytes(anchor_discriminator(b"anchor", b"event")).to_le_bytes();
+
+/// Length of an event discriminator in the `0x` hex form used by `GenericEventParseConfig`
+pub const EVENT_DISCRIMINATOR_HEX_LEN: usize = 2 + 32;
+
+/// Instruction discriminator, `sha256("global:<name>")[..8]`
+pub const fn instruction_discriminator(name: &str) -> [u8; 8] {
+    anchor_discriminator(b"global", name.as_bytes())
+}
+
+/// Event discriminator, `sha256("event:<name>")[..8]`
+pub const fn event_discriminator(name: &str) -> [u8; 8] {
+    anchor_discriminator(b"event", name.as_bytes())
+}
+
+/// Account discriminator, `sha256("account:<name>")[..8]`
+pub const fn account_discriminator(name: &str) -> [u8; 8] {
+    anchor_discriminator(b"account", name.as_bytes())
+}
+
+/// Event self-CPI discriminator (`EVENT_IX_TAG` followed by the event
+/// discriminator) as `0x` prefixed lowercase hex; see [`anchor_event_discriminator!`]
+///
+/// [`anchor_event_discriminator!`]: crate::anchor_event_discriminator
+pub const fn event_discriminator_hex(name: &str) -> [u8; EVENT_DISCRIMINATOR_HEX_LEN] {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let event = event_discriminator(name);
+    let mut out = [0u8; EVENT_DISCRIMINATOR_HEX_LEN];
+    out[0] = b'0';
+    out[1] = b'x';
+    let mut i = 0;
+    while i < 16 {
+        let byte = if i < 8 { EVENT_IX_TAG[i] } else { event[i - 8] };
+        out[2 + i * 2] = HEX[(byte >> 4) as usize];
+        out[3 + i * 2] = HEX[(byte & 0x0f) as usize];
+        i += 1;
+    }
+    out
+}
+
+/// `sha256("<namespace>:<name>")[..8]`
+pub const fn anchor_discriminator(namespace: &[u8], name: &[u8]) -> [u8; 8] {
+    let hash = sha256_preimage(namespace, name);
+    let mut out = [0u8; 8];
+    let mut i = 0;
+    while i < 8 {
+        out[i] = hash[i];
+        i += 1;
+    }
+    out
+}
+
+/// 编译期计算Anchor事件鉴别器的十六进制字符串
+///
+/// Expands to the `&'static str` form (e.g. `"0xe445a52e51cb9a1dbddb7fd34ee661ee"`
+/// for `"TradeEvent"`) used as `inner_instruction_discriminator`.
+///
+/// ```ignore
+/// pub const TRADE_EVENT: &str = anchor_event_discriminator!("TradeEvent");
+/// ```
+#[macro_export]
+macro_rules! anchor_event_discriminator {
+    ($name:expr) => {{
+        const HEX: [u8;
+            $crate::streaming::event_parser::common::discriminator::EVENT_DISCRIMINATOR_HEX_LEN] =
+            $crate::streaming::event_parser::common::discriminator::event_discriminator_hex($name);
+        match ::core::str::from_utf8(&HEX) {
+            Ok(hex) => hex,
+            Err(_) => panic!("invalid discriminator hex"),
+        }
+    }};
+}
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const H0: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+// 预映像 "<namespace>:<name>" 加上SHA-256填充后第i个字节
+const fn padded_byte(namespace: &[u8], name: &[u8], i: usize) -> u8 {
+    let len = namespace.len() + 1 + name.len();
+    let padded_len = (len + 9).div_ceil(64) * 64;
+    if i < namespace.len() {
+        namespace[i]
+    } else if i == namespace.len() {
+        b':'
+    } else if i < len {
+        name[i - namespace.len() - 1]
+    } else if i == len {
+        0x80
+    } else if i >= padded_len - 8 {
+        let bits = (len as u64) * 8;
+        (bits >> ((padded_len - 1 - i) * 8)) as u8
+    } else {
+        0
+    }
+}
+
+const fn sha256_preimage(namespace: &[u8], name: &[u8]) -> [u8; 32] {
+    let len = namespace.len() + 1 + name.len();
+    let blocks = (len + 9).div_ceil(64);
+    let mut h = H0;
+    let mut block = 0;
+    while block < blocks {
+        let mut w = [0u32; 64];
+        let mut t = 0;
+        while t < 16 {
+            let at = block * 64 + t * 4;
+            w[t] = u32::from_be_bytes([
+                padded_byte(namespace, name, at),
+                padded_byte(namespace, name, at + 1),
+                padded_byte(namespace, name, at + 2),
+                padded_byte(namespace, name, at + 3),
+            ]);
+            t += 1;
+        }
+        while t < 64 {
+            let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+            let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+            w[t] = w[t - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[t - 7])
+                .wrapping_add(s1);
+            t += 1;
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+        t = 0;
+        while t < 64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let temp1 = hh
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[t])
+                .wrapping_add(w[t]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(maj);
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+            t += 1;
+        }
+        h[0] = h[0].wrapping_add(a);
+        h[1] = h[1].wrapping_add(b);
+        h[2] = h[2].wrapping_add(c);
+        h[3] = h[3].wrapping_add(d);
+        h[4] = h[4].wrapping_add(e);
+        h[5] = h[5].wrapping_add(f);
+        h[6] = h[6].wrapping_add(g);
+        h[7] = h[7].wrapping_add(hh);
+        block += 1;
+    }
+
+    let mut out = [0u8; 32];
+    let mut i = 0;
+    while i < 8 {
+        let bytes = h[i].to_be_bytes();
+        out[i * 4] = bytes[0];
+        out[i * 4 + 1] = bytes[1];
+        out[i * 4 + 2] = bytes[2];
+        out[i * 4 + 3] = bytes[3];
+        i += 1;
+    }
+    out
+}
@@ -0,0 +1,59 @@
+use solana_sdk::{epoch_schedule::EpochSchedule, rent::Rent};
+use std::sync::{LazyLock, RwLock};
+
+// 默认使用主网参数（无warmup，每个epoch 432000个slot）
+static EPOCH_SCHEDULE: LazyLock<RwLock<EpochSchedule>> =
+    LazyLock::new(|| RwLock::new(EpochSchedule::without_warmup()));
+static RENT: LazyLock<RwLock<Rent>> = LazyLock::new(|| RwLock::new(Rent::default()));
+
+/// Epoch与租金上下文 - 根据slot计算epoch信息
+///
+/// Defaults to the mainnet epoch schedule, so events are annotated from slot math
+/// alone. Clusters with a different schedule can install it with
+/// [`EpochContext::set_epoch_schedule`] or, with the `rpc` feature, poll it with
+/// [`EpochContext::refresh_from_rpc`].
+pub struct EpochContext;
+
+impl EpochContext {
+    pub fn epoch_schedule() -> EpochSchedule {
+        EPOCH_SCHEDULE.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn set_epoch_schedule(schedule: EpochSchedule) {
+        *EPOCH_SCHEDULE.write().unwrap_or_else(|e| e.into_inner()) = schedule;
+    }
+
+    pub fn rent() -> Rent {
+        RENT.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn set_rent(rent: Rent) {
+        *RENT.write().unwrap_or_else(|e| e.into_inner()) = rent;
+    }
+
+    /// Returns `(epoch, slot_index, slots_in_epoch)` for a slot
+    pub fn epoch_info(slot: u64) -> (u64, u64, u64) {
+        let schedule = EPOCH_SCHEDULE.read().unwrap_or_else(|e| e.into_inner());
+        let (epoch, slot_index) = schedule.get_epoch_and_slot_index(slot);
+        (epoch, slot_index, schedule.get_slots_in_epoch(epoch))
+    }
+
+    /// Minimum lamports for an account of `data_len` bytes to be rent exempt
+    pub fn rent_exempt_minimum(data_len: usize) -> u64 {
+        RENT.read().unwrap_or_else(|e| e.into_inner()).minimum_balance(data_len)
+    }
+
+    /// Fetch the cluster's epoch schedule and rent parameters
+    #[cfg(feature = "rpc")]
+    pub async fn refresh_from_rpc(
+        client: &crate::common::SolanaRpcClient,
+    ) -> crate::common::AnyResult<()> {
+        let schedule = client.get_epoch_schedule().await?;
+        Self::set_epoch_schedule(schedule);
+        let rent_account = client.get_account(&solana_sdk::sysvar::rent::id()).await?;
+        if let Some(rent) = solana_sdk::account::from_account::<Rent, _>(&rent_account) {
+            Self::set_rent(rent);
+        }
+        Ok(())
+    }
+}
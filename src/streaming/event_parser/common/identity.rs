@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use crate::streaming::event_parser::{
+    common::{EventType, InstructionIndex},
+    UnifiedEvent,
+};
+
+/// 事件的稳定身份 - 签名 + 指令路径 + 事件类型
+///
+/// Unlike `EventMetadata::id`, the key is independent of the parse time and of the
+/// event's position in the parser output, so the same event parsed by two
+/// instances (or two versions of this crate) compares equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct EventKey {
+    pub signature: String,
+    pub index: InstructionIndex,
+    pub event_type: EventType,
+}
+
+impl EventKey {
+    pub fn of(event: &dyn UnifiedEvent) -> Self {
+        let metadata = event.metadata();
+        Self {
+            signature: metadata.signature.clone(),
+            index: metadata.index,
+            event_type: metadata.event_type.clone(),
+        }
+    }
+}
+
+impl PartialEq for dyn UnifiedEvent {
+    fn eq(&self, other: &Self) -> bool {
+        let (left, right) = (self.metadata(), other.metadata());
+        left.signature == right.signature
+            && left.index == right.index
+            && left.event_type == right.event_type
+    }
+}
+
+impl Eq for dyn UnifiedEvent {}
+
+impl Hash for dyn UnifiedEvent {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let metadata = self.metadata();
+        metadata.signature.hash(state);
+        metadata.index.hash(state);
+        metadata.event_type.hash(state);
+    }
+}
+
+/// 64-bit FNV-1a, stable across processes and Rust versions
+pub fn stable_hash(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| (hash ^ *byte as u64).wrapping_mul(PRIME))
+}
+
+/// 事件ID - 签名 + 锚定指令路径 + 事件类型的稳定哈希
+///
+/// `instruction_index` is the instruction the event belongs to: its own position for
+/// instruction events, and the emitting instruction for `emit_cpi!` and log events,
+/// so all representations of one event share an ID and merge. The index source is
+/// not part of the ID.
+pub fn event_id(
+    signature: &str,
+    instruction_index: &InstructionIndex,
+    event_type: &EventType,
+) -> String {
+    let key = format!("{}:{}:{}", signature, instruction_index, event_type.to_string());
+    format!("{:016x}", stable_hash(key.as_bytes()))
+}
+
+/// Stable hash of a value's JSON serialization
+pub fn content_hash_of<T: Serialize>(value: &T) -> u64 {
+    serde_json::to_vec(value).map(|bytes| stable_hash(&bytes)).unwrap_or_default()
+}
+
+/// JSON serialization of a value, empty if it cannot be serialized
+pub fn to_json_of<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+/// 两组事件的差异
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventDiff {
+    /// Events only present on the left side
+    pub only_left: Vec<EventKey>,
+    /// Events only present on the right side
+    pub only_right: Vec<EventKey>,
+    /// Events present on both sides whose content differs
+    pub changed: Vec<EventKey>,
+}
+
+impl EventDiff {
+    pub fn is_empty(&self) -> bool {
+        self.only_left.is_empty() && self.only_right.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare two event sets by identity and content hash
+///
+/// Output order of the parser and timing fields are ignored. Results are sorted by key.
+pub fn diff_events(left: &[Box<dyn UnifiedEvent>], right: &[Box<dyn UnifiedEvent>]) -> EventDiff {
+    let left: HashMap<EventKey, u64> =
+        left.iter().map(|e| (EventKey::of(e.as_ref()), e.content_hash())).collect();
+    let right: HashMap<EventKey, u64> =
+        right.iter().map(|e| (EventKey::of(e.as_ref()), e.content_hash())).collect();
+
+    let mut diff = EventDiff::default();
+    for (key, hash) in &left {
+        match right.get(key) {
+            None => diff.only_left.push(key.clone()),
+            Some(other) if other != hash => diff.changed.push(key.clone()),
+            Some(_) => {}
+        }
+    }
+    diff.only_right = right.keys().filter(|key| !left.contains_key(*key)).cloned().collect();
+    diff.only_left.sort();
+    diff.only_right.sort();
+    diff.changed.sort();
+    diff
+}
@@ -0,0 +1,147 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// 事件解码自交易的哪一部分
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+pub enum IndexSource {
+    /// Instruction data, top-level or CPI
+    #[default]
+    Instruction,
+    /// `emit_cpi!` self-CPI data
+    InnerInstruction,
+    /// `Program data:` log line
+    Log,
+    /// Account update, not tied to an instruction
+    Account,
+}
+
+/// 指令索引 - 事件在交易中的位置
+///
+/// `outer` is the top-level instruction and `inner` the position within its
+/// `innerInstructions` group. CPI and log events carry the position of the
+/// instruction that emitted them. Displays and parses as the instruction path
+/// (`"3"`, `"3.1"`); the source is not part of the path.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+pub struct InstructionIndex {
+    pub outer: u16,
+    pub inner: Option<u16>,
+    pub source: IndexSource,
+}
+
+impl InstructionIndex {
+    /// Top-level instruction
+    pub fn outer(outer: u16) -> Self {
+        Self {
+            outer,
+            inner: None,
+            source: IndexSource::Instruction,
+        }
+    }
+
+    /// Inner instruction `inner` of top-level instruction `outer`
+    pub fn inner(outer: u16, inner: u16) -> Self {
+        Self {
+            outer,
+            inner: Some(inner),
+            source: IndexSource::Instruction,
+        }
+    }
+
+    /// Position of an account update
+    pub fn account() -> Self {
+        Self::default().with_source(IndexSource::Account)
+    }
+
+    pub fn with_source(mut self, source: IndexSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    pub fn is_inner(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Same instruction position, regardless of source
+    pub fn same_instruction(&self, other: &Self) -> bool {
+        self.outer == other.outer && self.inner == other.inner
+    }
+}
+
+impl fmt::Display for InstructionIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            Some(inner) => write!(f, "{}.{}", self.outer, inner),
+            None => write!(f, "{}", self.outer),
+        }
+    }
+}
+
+impl FromStr for InstructionIndex {
+    type Err = std::num::ParseIntError;
+
+    /// Parse an instruction path (`"3"` or `"3.1"`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('.') {
+            Some((outer, inner)) => Ok(Self::inner(outer.parse()?, inner.parse()?)),
+            None => Ok(Self::outer(s.parse()?)),
+        }
+    }
+}
+
+/// 反序列化指令索引，兼容旧版的路径字符串
+///
+/// Metadata serialized before `InstructionIndex` stored the position as an
+/// instruction path (`"3"`, `"3.1"`, or empty when unknown). Only human readable
+/// formats accept the path, binary formats always carry the struct.
+pub(crate) fn deserialize_index_or_path<'de, D>(
+    deserializer: D,
+) -> Result<InstructionIndex, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum IndexOrPath {
+        Index(InstructionIndex),
+        Path(String),
+    }
+
+    if !deserializer.is_human_readable() {
+        return InstructionIndex::deserialize(deserializer);
+    }
+    match IndexOrPath::deserialize(deserializer)? {
+        IndexOrPath::Index(index) => Ok(index),
+        IndexOrPath::Path(path) if path.is_empty() => Ok(InstructionIndex::default()),
+        IndexOrPath::Path(path) => path.parse().map_err(serde::de::Error::custom),
+    }
+}
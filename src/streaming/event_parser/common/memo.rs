@@ -0,0 +1,55 @@
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::{UiInnerInstructions, UiInstruction};
+
+use super::codec;
+
+/// SPL Memo程序ID (v2)
+pub const MEMO_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// SPL Memo程序ID (v1)
+pub const MEMO_V1_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+
+pub fn is_memo_program(program_id: &Pubkey) -> bool {
+    *program_id == MEMO_PROGRAM_ID || *program_id == MEMO_V1_PROGRAM_ID
+}
+
+/// 提取交易中的全部memo
+///
+/// Memos of top-level instructions and of memo CPIs, in instruction order.
+/// Invalid UTF-8 is replaced rather than dropped.
+pub fn extract_memos(
+    instructions: &[CompiledInstruction],
+    inner_instructions: &[UiInnerInstructions],
+    accounts: &[Pubkey],
+) -> Vec<String> {
+    let is_memo = |program_id_index: u8| {
+        accounts
+            .get(program_id_index as usize)
+            .is_some_and(is_memo_program)
+    };
+    let mut memos = Vec::new();
+    for (index, instruction) in instructions.iter().enumerate() {
+        if is_memo(instruction.program_id_index) {
+            memos.push(String::from_utf8_lossy(&instruction.data).into_owned());
+        }
+        for inner in inner_instructions
+            .iter()
+            .filter(|inner| inner.index as usize == index)
+        {
+            for instruction in &inner.instructions {
+                let UiInstruction::Compiled(compiled) = instruction else {
+                    continue;
+                };
+                if !is_memo(compiled.program_id_index) {
+                    continue;
+                }
+                if let Ok(data) = codec::base58_decode(&compiled.data) {
+                    memos.push(String::from_utf8_lossy(&data).into_owned());
+                }
+            }
+        }
+    }
+    memos
+}
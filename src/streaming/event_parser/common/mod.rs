@@ -1,5 +1,19 @@
+pub mod clock;
+pub mod codec;
+pub mod decode;
+pub mod dev_registry;
+pub mod discriminator;
+pub mod epoch;
+pub mod identity;
+pub mod instruction_index;
+pub mod memo;
+pub mod reflect;
+pub mod router;
+pub mod token_balance;
 pub mod types;
+pub mod ui_transaction;
 pub mod utils;
+pub mod view;
 
 /// 自动生成UnifiedEvent trait实现的宏
 #[macro_export]
@@ -47,7 +61,7 @@ macro_rules! impl_unified_event {
                 Box::new(self.clone())
             }
 
-            fn merge(&mut self, other: Box<dyn $crate::streaming::event_parser::core::traits::UnifiedEvent>) {
+            fn merge_from(&mut self, other: &dyn $crate::streaming::event_parser::core::traits::UnifiedEvent) {
                 if let Some(e) = other.as_any().downcast_ref::<$struct_name>() {
                     $(
                         self.$field = e.$field.clone();
@@ -59,12 +73,50 @@ macro_rules! impl_unified_event {
                 self.metadata.transfer_datas = transfer_datas;
             }
 
-            fn index(&self) -> String {
-                self.metadata.index.clone()
+            fn index(&self) -> $crate::streaming::event_parser::common::InstructionIndex {
+                self.metadata.index
+            }
+
+            fn metadata(&self) -> &$crate::streaming::event_parser::common::types::EventMetadata {
+                &self.metadata
+            }
+
+            fn metadata_mut(&mut self) -> &mut $crate::streaming::event_parser::common::types::EventMetadata {
+                &mut self.metadata
+            }
+
+            fn to_json(&self) -> String {
+                $crate::streaming::event_parser::common::identity::to_json_of(self)
+            }
+
+            fn content_hash(&self) -> u64 {
+                let mut normalized = self.clone();
+                normalized.metadata.reset_processing_fields();
+                $crate::streaming::event_parser::common::identity::content_hash_of(&normalized)
             }
         }
     };
 }
 
+pub use clock::{Clock, ClockHandle, MockClock, MonotonicClock, SystemClock};
+pub use decode::event_from_json;
+pub use dev_registry::DevAddressRegistry;
+pub use discriminator::{event_discriminator, instruction_discriminator};
+pub use epoch::EpochContext;
+pub use identity::{diff_events, event_id, EventDiff, EventKey};
+pub use instruction_index::{IndexSource, InstructionIndex};
+pub use memo::{extract_memos, is_memo_program, MEMO_PROGRAM_ID, MEMO_V1_PROGRAM_ID};
+pub use token_balance::{
+    enrich_transfer_datas, net_token_change, relevant_token_balance_changes,
+    token_account_mints, token_balance_changes, TokenAccountMint, TokenBalance,
+    TokenBalanceChange,
+};
+pub use router::{
+    register_router, routed_via, router_of, unregister_router, Router, AXIOM_PROGRAM_IDS,
+    BULLX_PROGRAM_ID, JUPITER_V4_PROGRAM_ID, JUPITER_V6_PROGRAM_ID, OKX_DEX_ROUTER_PROGRAM_ID,
+    PHOTON_PROGRAM_ID, TROJAN_PROGRAM_ID,
+};
 pub use types::*;
+pub use ui_transaction::{compile_inner_instructions, decode_transaction, ui_loaded_addresses};
 pub use utils::*;
+pub use view::ViewError;
@@ -0,0 +1,45 @@
+use serde_json::Value;
+
+use crate::streaming::event_parser::UnifiedEvent;
+
+/// 事件字段反射 - 以JSON树的形式按名称访问事件字段
+///
+/// Field paths are dot separated from the event root, e.g. `user` or
+/// `metadata.signature`. Pubkeys and signatures are exposed as base58 strings.
+pub fn event_fields(event: &dyn UnifiedEvent) -> Value {
+    serde_json::from_str(&event.to_json()).unwrap_or(Value::Null)
+}
+
+/// Get a field by dot separated path
+pub fn get_field<'a>(fields: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(fields, |value, key| value.get(key))
+}
+
+/// Get a mutable field by dot separated path
+pub fn get_field_mut<'a>(fields: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    path.split('.').try_fold(fields, |value, key| value.get_mut(key))
+}
+
+/// Visit every field named `name` at any depth
+pub fn visit_fields_named<F>(fields: &mut Value, name: &str, visit: &mut F)
+where
+    F: FnMut(&mut Value),
+{
+    match fields {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                if key == name {
+                    visit(value);
+                } else {
+                    visit_fields_named(value, name, visit);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                visit_fields_named(value, name, visit);
+            }
+        }
+        _ => {}
+    }
+}
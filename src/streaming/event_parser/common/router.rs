@@ -0,0 +1,115 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{LazyLock, RwLock};
+
+/// Jupiter聚合器v6程序ID
+pub const JUPITER_V6_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+
+/// Jupiter聚合器v4程序ID
+pub const JUPITER_V4_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB");
+
+/// OKX DEX聚合路由程序ID
+pub const OKX_DEX_ROUTER_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("6m2CDdhRgxpH4WjvdzxAYbGxwdGUz5MziiL5jek2kBma");
+
+/// Photon交易程序ID
+pub const PHOTON_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("BSfD6SHZigAfDWSjzD5Q41jw8LmKwtmjskPH9XW1mrRW");
+
+/// BullX交易程序ID
+pub const BULLX_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("BuLLxd1tPR2r6hGR1Tp5Xn9Xv4yDBy2T8bUkG6yqbMXW");
+
+/// Axiom交易程序ID
+pub const AXIOM_PROGRAM_IDS: [Pubkey; 2] = [
+    solana_sdk::pubkey!("AxiomfHaWDemCFBLBayqnEnNwE6b7B2Qz3UmzMpgbMG6"),
+    solana_sdk::pubkey!("AxiomxSitiyXyPjKgJ9XSrdhsydtZsskZTEDam3PxKcC"),
+];
+
+/// Trojan交易程序ID
+pub const TROJAN_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("troY36YiPGqMyAYCNbEqYCdN2tb91Zf7bHcQt7KUi61");
+
+/// 路由/聚合器
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+pub enum Router {
+    Jupiter,
+    OkxDex,
+    Photon,
+    BullX,
+    Axiom,
+    Trojan,
+    /// Router added with [`register_router`]
+    Custom(String),
+}
+
+impl fmt::Display for Router {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Router::Jupiter => write!(f, "Jupiter"),
+            Router::OkxDex => write!(f, "OkxDex"),
+            Router::Photon => write!(f, "Photon"),
+            Router::BullX => write!(f, "BullX"),
+            Router::Axiom => write!(f, "Axiom"),
+            Router::Trojan => write!(f, "Trojan"),
+            Router::Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// 已知路由程序注册表
+static ROUTERS: LazyLock<RwLock<HashMap<Pubkey, Router>>> = LazyLock::new(|| {
+    let mut routers = HashMap::from([
+        (JUPITER_V6_PROGRAM_ID, Router::Jupiter),
+        (JUPITER_V4_PROGRAM_ID, Router::Jupiter),
+        (OKX_DEX_ROUTER_PROGRAM_ID, Router::OkxDex),
+        (PHOTON_PROGRAM_ID, Router::Photon),
+        (BULLX_PROGRAM_ID, Router::BullX),
+        (TROJAN_PROGRAM_ID, Router::Trojan),
+    ]);
+    routers.extend(AXIOM_PROGRAM_IDS.map(|program_id| (program_id, Router::Axiom)));
+    RwLock::new(routers)
+});
+
+/// 注册路由程序，替换同一程序之前的注册
+///
+/// Events whose instruction is invoked through `program_id` get `router` as
+/// `metadata.routed_via`. Also used to add new program versions of the built-in
+/// routers.
+pub fn register_router(program_id: Pubkey, router: Router) {
+    ROUTERS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(program_id, router);
+}
+
+pub fn unregister_router(program_id: &Pubkey) -> Option<Router> {
+    ROUTERS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(program_id)
+}
+
+/// 程序对应的路由，未注册时返回None
+pub fn router_of(program_id: &Pubkey) -> Option<Router> {
+    ROUTERS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(program_id)
+        .cloned()
+}
+
+/// 调用链上最外层的已知路由
+pub fn routed_via(caller_program_ids: &[Pubkey]) -> Option<Router> {
+    let routers = ROUTERS.read().unwrap_or_else(|e| e.into_inner());
+    caller_program_ids
+        .iter()
+        .find_map(|program_id| routers.get(program_id).cloned())
+}
@@ -0,0 +1,177 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::{option_serializer::OptionSerializer, UiTransactionTokenBalance};
+use std::collections::{BTreeMap, HashMap};
+
+use super::codec;
+use super::types::TransferData;
+
+/// 交易元数据中某个代币账户的余额
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenBalance {
+    /// Index of the token account in the transaction's account list
+    pub account_index: u8,
+    pub mint: Pubkey,
+    pub owner: Option<Pubkey>,
+    pub decimals: u8,
+    /// Raw amount in base units
+    pub amount: u64,
+}
+
+impl TokenBalance {
+    /// None for balances with an unparsable mint
+    pub fn from_ui(balance: &UiTransactionTokenBalance) -> Option<Self> {
+        Some(Self {
+            account_index: balance.account_index,
+            mint: codec::decode_pubkey(&balance.mint)?,
+            owner: match &balance.owner {
+                OptionSerializer::Some(owner) => codec::decode_pubkey(owner),
+                _ => None,
+            },
+            decimals: balance.ui_token_amount.decimals,
+            amount: balance.ui_token_amount.amount.parse().unwrap_or_default(),
+        })
+    }
+}
+
+/// 代币账户的mint和精度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenAccountMint {
+    pub mint: Pubkey,
+    pub decimals: u8,
+}
+
+/// 按交易前后余额建立代币账户到mint的映射
+///
+/// Every token account a transaction touches has a pre or post balance, including
+/// accounts created or closed by it, so this covers all accounts of its transfers.
+pub fn token_account_mints(
+    pre_token_balances: &[TokenBalance],
+    post_token_balances: &[TokenBalance],
+    accounts: &[Pubkey],
+) -> HashMap<Pubkey, TokenAccountMint> {
+    pre_token_balances
+        .iter()
+        .chain(post_token_balances)
+        .filter_map(|balance| {
+            let account = accounts.get(balance.account_index as usize)?;
+            Some((
+                *account,
+                TokenAccountMint {
+                    mint: balance.mint,
+                    decimals: balance.decimals,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// 补全转账的mint和精度
+///
+/// Plain SPL `transfer` instructions carry neither; they are looked up by the
+/// source token account, then the destination. SOL transfers are left as they are.
+pub fn enrich_transfer_datas(
+    transfer_datas: &mut [TransferData],
+    mints: &HashMap<Pubkey, TokenAccountMint>,
+) {
+    for transfer in transfer_datas {
+        if transfer.mint.is_some() && transfer.decimals.is_some() {
+            continue;
+        }
+        let Some(mint) = mints
+            .get(&transfer.source)
+            .or_else(|| mints.get(&transfer.destination))
+        else {
+            continue;
+        };
+        transfer.mint.get_or_insert(mint.mint);
+        transfer.decimals.get_or_insert(mint.decimals);
+    }
+}
+
+/// 交易前后某个代币账户的余额变化
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+pub struct TokenBalanceChange {
+    /// Token account
+    pub account: Pubkey,
+    pub owner: Option<Pubkey>,
+    pub mint: Pubkey,
+    pub decimals: u8,
+    /// Raw amount before the transaction, 0 for accounts it created
+    pub pre_amount: u64,
+    /// Raw amount after the transaction, 0 for accounts it closed
+    pub post_amount: u64,
+}
+
+impl TokenBalanceChange {
+    /// Signed change in base units
+    pub fn delta(&self) -> i128 {
+        self.post_amount as i128 - self.pre_amount as i128
+    }
+}
+
+/// 交易中余额发生变化的代币账户，按账户索引排序
+pub fn token_balance_changes(
+    pre_token_balances: &[TokenBalance],
+    post_token_balances: &[TokenBalance],
+    accounts: &[Pubkey],
+) -> Vec<TokenBalanceChange> {
+    let mut changes: BTreeMap<u8, TokenBalanceChange> = BTreeMap::new();
+    for (balance, is_post) in pre_token_balances
+        .iter()
+        .map(|balance| (balance, false))
+        .chain(post_token_balances.iter().map(|balance| (balance, true)))
+    {
+        let Some(account) = accounts.get(balance.account_index as usize) else {
+            continue;
+        };
+        let change = changes
+            .entry(balance.account_index)
+            .or_insert_with(|| TokenBalanceChange {
+                account: *account,
+                owner: balance.owner,
+                mint: balance.mint,
+                decimals: balance.decimals,
+                ..Default::default()
+            });
+        if is_post {
+            change.post_amount = balance.amount;
+        } else {
+            change.pre_amount = balance.amount;
+        }
+        change.owner = change.owner.or(balance.owner);
+    }
+    changes
+        .into_values()
+        .filter(|change| change.delta() != 0)
+        .collect()
+}
+
+/// 某个owner在某个mint上的净变化
+pub fn net_token_change(changes: &[TokenBalanceChange], owner: &Pubkey, mint: &Pubkey) -> i128 {
+    changes
+        .iter()
+        .filter(|change| change.owner.as_ref() == Some(owner) && change.mint == *mint)
+        .map(TokenBalanceChange::delta)
+        .sum()
+}
+
+/// 与一条指令相关的余额变化：账户或其owner出现在指令账户中
+pub fn relevant_token_balance_changes(
+    changes: &[TokenBalanceChange],
+    instruction_accounts: &[Pubkey],
+) -> Vec<TokenBalanceChange> {
+    changes
+        .iter()
+        .filter(|change| {
+            instruction_accounts.contains(&change.account)
+                || change
+                    .owner
+                    .is_some_and(|owner| instruction_accounts.contains(&owner))
+        })
+        .cloned()
+        .collect()
+}
@@ -1,12 +1,30 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
-use solana_sdk::pubkey::Pubkey;
-use solana_transaction_status::UiInstruction;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::{UiInnerInstructions, UiInstruction};
+
+use super::clock::ClockHandle;
+use super::codec;
+use super::epoch::EpochContext;
+use super::identity::{event_id, stable_hash};
+use super::instruction_index::{deserialize_index_or_path, InstructionIndex};
+use super::router::Router;
+use super::token_balance::TokenBalanceChange;
+use crate::streaming::event_parser::core::circuit_breaker::ParserCircuitBreaker;
+use crate::streaming::event_parser::core::post_process::PostProcessorChain;
+use crate::streaming::event_parser::protocols::meteora_damm::vault::unwind_vault_transfers;
 
 #[derive(
-    Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
 )]
 pub enum ProtocolType {
     #[default]
@@ -15,12 +33,39 @@ pub enum ProtocolType {
     Bonk,
     RaydiumCpmm,
     RaydiumClmm,
+    Boop,
+    Believe,
+    MeteoraDamm,
+    MeteoraDbc,
+    Phoenix,
+    Sanctum,
+    Drift,
+    Marginfi,
+    Kamino,
+    Tensor,
+    MagicEden,
+    Metaplex,
+    Vote,
+    Stake,
     SDKSystem,
+    /// Parsed by a dynamically loaded plugin
+    Plugin,
 }
 
 /// 事件类型枚举
 #[derive(
-    Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
 )]
 pub enum EventType {
     // PumpSwap 事件
@@ -30,11 +75,17 @@ pub enum EventType {
     PumpSwapCreatePool,
     PumpSwapDeposit,
     PumpSwapWithdraw,
+    PumpSwapUpdateFeeConfig,
+    PumpSwapSetCoinCreator,
 
     // PumpFun 事件
     PumpFunCreateToken,
     PumpFunBuy,
     PumpFunSell,
+    PumpFunSetParams,
+    PumpFunSetCreator,
+    PumpFunCollectCreatorFee,
+    PumpFunWithdraw,
 
     // Bonk 事件
     BonkBuyExactIn,
@@ -51,8 +102,113 @@ pub enum EventType {
     RaydiumClmmSwap,
     RaydiumClmmSwapV2,
 
+    // Boop 事件
+    BoopCreateToken,
+    BoopBuy,
+    BoopSell,
+    BoopGraduate,
+
+    // Believe 事件
+    BelieveCreateToken,
+    BelieveTrade,
+
+    // Meteora Dynamic AMM 事件
+    MeteoraDammSwap,
+    MeteoraDammAddLiquidity,
+    MeteoraDammRemoveLiquidity,
+    MeteoraDammBootstrapLiquidity,
+
+    // Meteora DBC 事件
+    MeteoraDbcInitializePool,
+    MeteoraDbcSwap,
+    MeteoraDbcMigrate,
+
+    // Phoenix 事件
+    PhoenixFill,
+    PhoenixPlace,
+    PhoenixCancel,
+    PhoenixFillSummary,
+    PhoenixFee,
+
+    // Sanctum 事件
+    SanctumDepositSol,
+    SanctumDepositStake,
+    SanctumWithdrawSol,
+    SanctumWithdrawStake,
+    SanctumSwap,
+
+    // Drift 事件
+    DriftPerpFill,
+    DriftSpotFill,
+    DriftPlaceOrder,
+    DriftLiquidation,
+
+    // marginfi 事件
+    MarginfiDeposit,
+    MarginfiRepay,
+    MarginfiWithdraw,
+    MarginfiBorrow,
+    MarginfiLiquidate,
+
+    // Kamino Lend 事件
+    KaminoDeposit,
+    KaminoWithdraw,
+    KaminoBorrow,
+    KaminoRepay,
+    KaminoLiquidate,
+
+    // Tensor 事件
+    TensorList,
+    TensorDelist,
+    TensorBuy,
+    TensorBidFill,
+    TensorSwapTrade,
+
+    // Magic Eden 事件
+    MagicEdenList,
+    MagicEdenDelist,
+    MagicEdenSale,
+    MagicEdenBidFill,
+
+    // Metaplex Token Metadata 事件
+    MetaplexCreateMetadata,
+    MetaplexUpdateMetadata,
+    MetaplexVerify,
+
+    // Vote 事件
+    VoteCast,
+    VoteWithdraw,
+
+    // Stake 事件
+    StakeDelegate,
+    StakeDeactivate,
+    StakeWithdraw,
+
+    // 派生事件
+    PriceUpdated,
+    PoolStateChanged,
+    PositionClosed,
+    AccountDiff,
+    WalletSessionOpened,
+    WalletSessionClosed,
+    NormalizedTrade,
+    NormalizedLiquidity,
+    GraduationProgress,
+    LaunchSnapshot,
+
+    // 链状态事件
+    SlotUpdate,
+    BlockMeta,
+    SlotGapDetected,
+    LatencyBudgetExceeded,
+
     // 通用事件
     SDKSystem,
+    Plugin,
+    DecodedInstruction,
+    ParserDegraded,
+    RawInstruction,
+    UnknownProgram,
     Unknown,
 }
 
@@ -64,9 +220,15 @@ impl EventType {
             EventType::PumpSwapCreatePool => "PumpSwapCreatePool".to_string(),
             EventType::PumpSwapDeposit => "PumpSwapDeposit".to_string(),
             EventType::PumpSwapWithdraw => "PumpSwapWithdraw".to_string(),
+            EventType::PumpSwapUpdateFeeConfig => "PumpSwapUpdateFeeConfig".to_string(),
+            EventType::PumpSwapSetCoinCreator => "PumpSwapSetCoinCreator".to_string(),
             EventType::PumpFunCreateToken => "PumpFunCreateToken".to_string(),
             EventType::PumpFunBuy => "PumpFunBuy".to_string(),
             EventType::PumpFunSell => "PumpFunSell".to_string(),
+            EventType::PumpFunSetParams => "PumpFunSetParams".to_string(),
+            EventType::PumpFunSetCreator => "PumpFunSetCreator".to_string(),
+            EventType::PumpFunCollectCreatorFee => "PumpFunCollectCreatorFee".to_string(),
+            EventType::PumpFunWithdraw => "PumpFunWithdraw".to_string(),
             EventType::BonkBuyExactIn => "BonkBuyExactIn".to_string(),
             EventType::BonkBuyExactOut => "BonkBuyExactOut".to_string(),
             EventType::BonkSellExactIn => "BonkSellExactIn".to_string(),
@@ -76,7 +238,80 @@ impl EventType {
             EventType::RaydiumCpmmSwapBaseOutput => "RaydiumCpmmSwapBaseOutput".to_string(),
             EventType::RaydiumClmmSwap => "RaydiumClmmSwap".to_string(),
             EventType::RaydiumClmmSwapV2 => "RaydiumClmmSwapV2".to_string(),
+            EventType::BoopCreateToken => "BoopCreateToken".to_string(),
+            EventType::BoopBuy => "BoopBuy".to_string(),
+            EventType::BoopSell => "BoopSell".to_string(),
+            EventType::BoopGraduate => "BoopGraduate".to_string(),
+            EventType::BelieveCreateToken => "BelieveCreateToken".to_string(),
+            EventType::BelieveTrade => "BelieveTrade".to_string(),
+            EventType::MeteoraDammSwap => "MeteoraDammSwap".to_string(),
+            EventType::MeteoraDammAddLiquidity => "MeteoraDammAddLiquidity".to_string(),
+            EventType::MeteoraDammRemoveLiquidity => "MeteoraDammRemoveLiquidity".to_string(),
+            EventType::MeteoraDammBootstrapLiquidity => "MeteoraDammBootstrapLiquidity".to_string(),
+            EventType::MeteoraDbcInitializePool => "MeteoraDbcInitializePool".to_string(),
+            EventType::MeteoraDbcSwap => "MeteoraDbcSwap".to_string(),
+            EventType::MeteoraDbcMigrate => "MeteoraDbcMigrate".to_string(),
+            EventType::PhoenixFill => "PhoenixFill".to_string(),
+            EventType::PhoenixPlace => "PhoenixPlace".to_string(),
+            EventType::PhoenixCancel => "PhoenixCancel".to_string(),
+            EventType::PhoenixFillSummary => "PhoenixFillSummary".to_string(),
+            EventType::PhoenixFee => "PhoenixFee".to_string(),
+            EventType::SanctumDepositSol => "SanctumDepositSol".to_string(),
+            EventType::SanctumDepositStake => "SanctumDepositStake".to_string(),
+            EventType::SanctumWithdrawSol => "SanctumWithdrawSol".to_string(),
+            EventType::SanctumWithdrawStake => "SanctumWithdrawStake".to_string(),
+            EventType::SanctumSwap => "SanctumSwap".to_string(),
+            EventType::DriftPerpFill => "DriftPerpFill".to_string(),
+            EventType::DriftSpotFill => "DriftSpotFill".to_string(),
+            EventType::DriftPlaceOrder => "DriftPlaceOrder".to_string(),
+            EventType::DriftLiquidation => "DriftLiquidation".to_string(),
+            EventType::MarginfiDeposit => "MarginfiDeposit".to_string(),
+            EventType::MarginfiRepay => "MarginfiRepay".to_string(),
+            EventType::MarginfiWithdraw => "MarginfiWithdraw".to_string(),
+            EventType::MarginfiBorrow => "MarginfiBorrow".to_string(),
+            EventType::MarginfiLiquidate => "MarginfiLiquidate".to_string(),
+            EventType::KaminoDeposit => "KaminoDeposit".to_string(),
+            EventType::KaminoWithdraw => "KaminoWithdraw".to_string(),
+            EventType::KaminoBorrow => "KaminoBorrow".to_string(),
+            EventType::KaminoRepay => "KaminoRepay".to_string(),
+            EventType::KaminoLiquidate => "KaminoLiquidate".to_string(),
+            EventType::TensorList => "TensorList".to_string(),
+            EventType::TensorDelist => "TensorDelist".to_string(),
+            EventType::TensorBuy => "TensorBuy".to_string(),
+            EventType::TensorBidFill => "TensorBidFill".to_string(),
+            EventType::TensorSwapTrade => "TensorSwapTrade".to_string(),
+            EventType::MagicEdenList => "MagicEdenList".to_string(),
+            EventType::MagicEdenDelist => "MagicEdenDelist".to_string(),
+            EventType::MagicEdenSale => "MagicEdenSale".to_string(),
+            EventType::MagicEdenBidFill => "MagicEdenBidFill".to_string(),
+            EventType::MetaplexCreateMetadata => "MetaplexCreateMetadata".to_string(),
+            EventType::MetaplexUpdateMetadata => "MetaplexUpdateMetadata".to_string(),
+            EventType::MetaplexVerify => "MetaplexVerify".to_string(),
+            EventType::VoteCast => "VoteCast".to_string(),
+            EventType::VoteWithdraw => "VoteWithdraw".to_string(),
+            EventType::StakeDelegate => "StakeDelegate".to_string(),
+            EventType::StakeDeactivate => "StakeDeactivate".to_string(),
+            EventType::StakeWithdraw => "StakeWithdraw".to_string(),
+            EventType::PriceUpdated => "PriceUpdated".to_string(),
+            EventType::PoolStateChanged => "PoolStateChanged".to_string(),
+            EventType::PositionClosed => "PositionClosed".to_string(),
+            EventType::AccountDiff => "AccountDiff".to_string(),
+            EventType::WalletSessionOpened => "WalletSessionOpened".to_string(),
+            EventType::WalletSessionClosed => "WalletSessionClosed".to_string(),
+            EventType::NormalizedTrade => "NormalizedTrade".to_string(),
+            EventType::NormalizedLiquidity => "NormalizedLiquidity".to_string(),
+            EventType::GraduationProgress => "GraduationProgress".to_string(),
+            EventType::LaunchSnapshot => "LaunchSnapshot".to_string(),
+            EventType::SlotUpdate => "SlotUpdate".to_string(),
+            EventType::BlockMeta => "BlockMeta".to_string(),
+            EventType::SlotGapDetected => "SlotGapDetected".to_string(),
+            EventType::LatencyBudgetExceeded => "LatencyBudgetExceeded".to_string(),
             EventType::SDKSystem => "SDKSystem".to_string(),
+            EventType::Plugin => "Plugin".to_string(),
+            EventType::DecodedInstruction => "DecodedInstruction".to_string(),
+            EventType::ParserDegraded => "ParserDegraded".to_string(),
+            EventType::RawInstruction => "RawInstruction".to_string(),
+            EventType::UnknownProgram => "UnknownProgram".to_string(),
             EventType::Unknown => "Unknown".to_string(),
         }
     }
@@ -133,6 +368,157 @@ impl ProtocolInfo {
     }
 }
 
+/// 失败交易的处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FailedTxPolicy {
+    /// Drop failed transactions
+    #[default]
+    Skip,
+    /// Parse failed transactions like successful ones and set `transaction_failed`
+    ParseWithFlag,
+    /// Parse only failed transactions, e.g. to study reverted snipes
+    OnlyFailed,
+}
+
+impl FailedTxPolicy {
+    /// Whether a transaction with the given outcome should be parsed
+    pub fn accepts(&self, failed: bool) -> bool {
+        match self {
+            FailedTxPolicy::Skip => !failed,
+            FailedTxPolicy::ParseWithFlag => true,
+            FailedTxPolicy::OnlyFailed => failed,
+        }
+    }
+
+    /// Value of the `failed` field of a Yellowstone transaction filter
+    pub fn grpc_filter(&self) -> Option<bool> {
+        match self {
+            FailedTxPolicy::Skip => Some(false),
+            FailedTxPolicy::ParseWithFlag => None,
+            FailedTxPolicy::OnlyFailed => Some(true),
+        }
+    }
+}
+
+/// 交易解析选项
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseOptions {
+    pub failed_tx_policy: FailedTxPolicy,
+    /// Attach the originating instruction to every event as `metadata.raw`
+    pub include_raw: bool,
+    /// Emit a `DecodedInstructionEvent` for every instruction of the protocol
+    pub decoded_instructions: bool,
+    /// Emit an `UnknownProgramEvent` for protocol instructions no parser matches
+    pub unknown_instructions: bool,
+    /// Post-processors run on the events of each transaction
+    #[serde(skip)]
+    pub post_processors: PostProcessorChain,
+    /// Source of the receive and parse timestamps
+    #[serde(skip)]
+    pub clock: ClockHandle,
+    /// Emit `ParserDegradedEvent`s when a protocol's decode failures spike
+    #[serde(skip)]
+    pub circuit_breaker: Option<ParserCircuitBreaker>,
+}
+
+impl ParseOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_failed_tx_policy(mut self, failed_tx_policy: FailedTxPolicy) -> Self {
+        self.failed_tx_policy = failed_tx_policy;
+        self
+    }
+
+    pub fn with_include_raw(mut self, include_raw: bool) -> Self {
+        self.include_raw = include_raw;
+        self
+    }
+
+    pub fn with_decoded_instructions(mut self, decoded_instructions: bool) -> Self {
+        self.decoded_instructions = decoded_instructions;
+        self
+    }
+
+    pub fn with_unknown_instructions(mut self, unknown_instructions: bool) -> Self {
+        self.unknown_instructions = unknown_instructions;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: ClockHandle) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Replace the default dev/bot tagging post-processors
+    pub fn with_post_processors(mut self, post_processors: PostProcessorChain) -> Self {
+        self.post_processors = post_processors;
+        self
+    }
+
+    /// Share `circuit_breaker` between the parsers using these options
+    pub fn with_circuit_breaker(mut self, circuit_breaker: ParserCircuitBreaker) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+}
+
+/// 事件来源指令的原始数据
+///
+/// Only filled when [`ParseOptions::include_raw`] is set, so instructions the SDK
+/// doesn't decode yet can be post-processed without re-fetching the transaction.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+pub struct RawInstruction {
+    pub program_id: Pubkey,
+    pub data: Vec<u8>,
+    /// Instruction accounts resolved against the transaction's account keys
+    pub accounts: Vec<Pubkey>,
+    /// 1 for top-level instructions, 2 and up for CPIs; `None` if not reported
+    pub stack_height: Option<u32>,
+}
+
+impl RawInstruction {
+    /// Find the instruction at an event index
+    pub fn at_index(
+        index: &InstructionIndex,
+        instructions: &[CompiledInstruction],
+        inner_instructions: &[UiInnerInstructions],
+        accounts: &[Pubkey],
+    ) -> Option<Self> {
+        let resolve = |indexes: &[u8]| -> Vec<Pubkey> {
+            indexes.iter().map(|i| accounts.get(*i as usize).copied().unwrap_or_default()).collect()
+        };
+        match index.inner {
+            None => {
+                let instruction = instructions.get(index.outer as usize)?;
+                Some(Self {
+                    program_id: *accounts.get(instruction.program_id_index as usize)?,
+                    data: instruction.data.clone(),
+                    accounts: resolve(&instruction.accounts),
+                    stack_height: Some(1),
+                })
+            }
+            Some(child) => {
+                let inner = inner_instructions
+                    .iter()
+                    .find(|inner| inner.index as u16 == index.outer)?;
+                match inner.instructions.get(child as usize)? {
+                    UiInstruction::Compiled(compiled) => Some(Self {
+                        program_id: *accounts.get(compiled.program_id_index as usize)?,
+                        data: codec::base58_decode(&compiled.data).ok()?,
+                        accounts: resolve(&compiled.accounts),
+                        stack_height: compiled.stack_height,
+                    }),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
 /// 交易数据
 #[derive(
     Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
@@ -147,23 +533,176 @@ pub struct TransferData {
     pub mint: Option<Pubkey>,
 }
 
+/// 代币认证标记 - mint出现在哪些代币列表中
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+pub struct TokenBadge {
+    pub mint: Pubkey,
+    /// Names of the token lists that include the mint
+    pub lists: Vec<String>,
+}
+
+/// 风险标记位图，由`RiskAnnotator`设置
+///
+/// Bits 0-15 are the built-in heuristics, bits 16-31 are free for custom rules,
+/// see [`RiskFlags::custom`].
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+#[serde(transparent)]
+pub struct RiskFlags(pub u32);
+
+impl RiskFlags {
+    /// The mint authority is not revoked, so more tokens can be minted
+    pub const MINT_AUTHORITY: Self = Self(1 << 0);
+    /// A freeze authority can freeze holders' token accounts
+    pub const FREEZE_AUTHORITY: Self = Self(1 << 1);
+    /// The creator holds more than the configured share of the supply
+    pub const DEV_HOLDING: Self = Self(1 << 2);
+    /// The token metadata can still be changed
+    pub const MUTABLE_METADATA: Self = Self(1 << 3);
+
+    /// Flag of custom rule `n` (0-15)
+    pub const fn custom(n: u32) -> Self {
+        Self(1 << (16 + n % 16))
+    }
+
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl std::ops::BitOr for RiskFlags {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+/// 事件结构版本
+///
+/// Bumped on every incompatible change of the event payloads, i.e. removed or
+/// renamed fields or changed field types. Adding fields does not bump it; fields
+/// added since the first release default when missing, so payloads serialized
+/// before versioning, whose metadata also has a string `index`, deserialize with
+/// version 0.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
 /// 事件元数据
 #[derive(
     Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
 )]
 pub struct EventMetadata {
+    /// [`EVENT_SCHEMA_VERSION`] of the release that produced the event
+    #[serde(default)]
+    pub schema_version: u32,
+    /// Deterministic ID shared by all representations of one event, see [`event_id`]
     pub id: String,
     pub signature: String,
     pub slot: u64,
     pub block_time: i64,
     pub block_time_ms: i64,
+    /// Start of parsing, same as `parse_start_time_ms`
     pub program_received_time_ms: i64,
     pub program_handle_time_consuming_ms: i64,
+    /// 数据源消息到达时间，0表示未知
+    #[serde(default)]
+    pub grpc_received_time_ms: i64,
+    #[serde(default)]
+    pub parse_start_time_ms: i64,
+    /// End of parsing and post-processing
+    #[serde(default)]
+    pub parse_end_time_ms: i64,
     pub protocol: ProtocolType,
     pub event_type: EventType,
     pub program_id: Pubkey,
     pub transfer_datas: Vec<TransferData>,
-    pub index: String,
+    /// Position of the event in the transaction
+    #[serde(deserialize_with = "deserialize_index_or_path")]
+    pub index: InstructionIndex,
+    /// 处理超过pipeline截止时间时为true
+    #[serde(default)]
+    pub deadline_exceeded: bool,
+    /// Epoch of `slot`
+    #[serde(default)]
+    pub epoch: u64,
+    /// Position of `slot` within its epoch
+    #[serde(default)]
+    pub slot_index: u64,
+    #[serde(default)]
+    pub slots_in_epoch: u64,
+    /// Verification badges of the event's mints, filled by `TokenBadgeProvider`
+    #[serde(default)]
+    pub token_badges: Vec<TokenBadge>,
+    /// 交易执行失败时为true，见 [`FailedTxPolicy`]
+    #[serde(default)]
+    pub transaction_failed: bool,
+    /// Error of the failed transaction
+    pub transaction_error: Option<String>,
+    /// Originating instruction, see [`ParseOptions::include_raw`]
+    pub raw: Option<RawInstruction>,
+    /// block_time与接收时间或slot进度不符时为true，由`BlockTimeChecker`设置
+    #[serde(default)]
+    pub block_time_suspect: bool,
+    /// Transaction fee in lamports
+    #[serde(default)]
+    pub fee: u64,
+    /// Signers of the transaction, the fee payer first
+    #[serde(default)]
+    pub signers: Vec<Pubkey>,
+    /// 交易在区块中的位置，数据源不提供时为None
+    pub tx_index: Option<u64>,
+    /// Memo program memos of the transaction, in instruction order
+    #[serde(default)]
+    pub memos: Vec<String>,
+    /// Frontend the transaction is attributed to, set by `ReferralTagger`
+    pub referral: Option<String>,
+    /// 调用事件指令的程序链，最外层在前，顶层指令为空
+    ///
+    /// A PumpSwap fill routed through Jupiter has `[JUPITER]`, a direct one none.
+    #[serde(default)]
+    pub caller_program_ids: Vec<Pubkey>,
+    /// Outermost known router on the caller chain, see `register_router`
+    #[serde(default)]
+    pub routed_via: Option<Router>,
+    /// 与事件指令相关的代币余额变化，可与解码的数额交叉核对
+    ///
+    /// Token accounts of the instruction, or owned by one of its accounts, whose
+    /// balance the transaction changed.
+    #[serde(default)]
+    pub token_balance_changes: Vec<TokenBalanceChange>,
+    /// Risk heuristics matched by the event's token, set by `RiskAnnotator`
+    #[serde(default)]
+    pub risk: RiskFlags,
+    /// 有序模式下的交付序号，从1开始连续递增，0表示未排序
+    ///
+    /// Set by `EventSequencer`, see `YellowstoneGrpc::with_sequencer`.
+    #[serde(default)]
+    pub sequence: u64,
 }
 
 impl EventMetadata {
@@ -176,10 +715,12 @@ impl EventMetadata {
         protocol: ProtocolType,
         event_type: EventType,
         program_id: Pubkey,
-        index: String,
+        index: InstructionIndex,
         program_received_time_ms: i64,
     ) -> Self {
+        let (epoch, slot_index, slots_in_epoch) = EpochContext::epoch_info(slot);
         Self {
+            schema_version: EVENT_SCHEMA_VERSION,
             id,
             signature,
             slot,
@@ -187,20 +728,82 @@ impl EventMetadata {
             block_time_ms,
             program_received_time_ms: program_received_time_ms,
             program_handle_time_consuming_ms: 0,
+            grpc_received_time_ms: 0,
+            parse_start_time_ms: program_received_time_ms,
+            parse_end_time_ms: 0,
             protocol,
             event_type,
             program_id,
             transfer_datas: vec![],
             index,
+            deadline_exceeded: false,
+            epoch,
+            slot_index,
+            slots_in_epoch,
+            token_badges: vec![],
+            transaction_failed: false,
+            transaction_error: None,
+            raw: None,
+            block_time_suspect: false,
+            fee: 0,
+            signers: vec![],
+            tx_index: None,
+            memos: vec![],
+            referral: None,
+            caller_program_ids: vec![],
+            routed_via: None,
+            token_balance_changes: vec![],
+            risk: RiskFlags::default(),
+            sequence: 0,
+        }
+    }
+
+    /// Program that invoked the event's instruction, None for top-level instructions
+    pub fn caller_program_id(&self) -> Option<Pubkey> {
+        self.caller_program_ids.last().copied()
+    }
+
+    /// Fee payer of the transaction
+    pub fn fee_payer(&self) -> Option<Pubkey> {
+        self.signers.first().copied()
+    }
+
+    /// Reset fields that depend on when and how fast the event was processed
+    pub fn reset_processing_fields(&mut self) {
+        self.program_received_time_ms = 0;
+        self.program_handle_time_consuming_ms = 0;
+        self.grpc_received_time_ms = 0;
+        self.parse_start_time_ms = 0;
+        self.parse_end_time_ms = 0;
+        self.deadline_exceeded = false;
+        self.block_time_suspect = false;
+        self.sequence = 0;
+    }
+
+    /// Whether any token list includes `mint`
+    pub fn is_verified(&self, mint: &Pubkey) -> bool {
+        self.token_badges.iter().any(|badge| badge.mint == *mint && !badge.lists.is_empty())
+    }
+
+    /// Progress through the current epoch (0.0-1.0)
+    pub fn epoch_progress(&self) -> f64 {
+        if self.slots_in_epoch == 0 {
+            return 0.0;
         }
+        self.slot_index as f64 / self.slots_in_epoch as f64
     }
+
+    /// Set the ID from a custom key, for events not tied to one instruction
+    /// (derived and account events)
     pub fn set_id(&mut self, id: String) {
-        let _id = format!("{}-{}-{}", self.signature, self.event_type.to_string(), id);
-        // 对传入的 id 进行哈希处理
-        let mut hasher = DefaultHasher::new();
-        _id.hash(&mut hasher);
-        let hash_value = hasher.finish();
-        self.id = format!("{:x}", hash_value);
+        let key = format!("{}-{}-{}", self.signature, self.event_type.to_string(), id);
+        self.id = format!("{:016x}", stable_hash(key.as_bytes()));
+    }
+
+    /// Set the ID of an event belonging to the instruction at `instruction_index`,
+    /// see [`event_id`]
+    pub fn set_instruction_id(&mut self, instruction_index: &InstructionIndex) {
+        self.id = event_id(&self.signature, instruction_index, &self.event_type);
     }
 }
 
@@ -211,6 +814,15 @@ pub fn parse_transfer_datas_from_next_instructions(
     accounts: &[Pubkey],
     event_type: EventType,
 ) -> Vec<TransferData> {
+    if matches!(
+        event_type,
+        EventType::MeteoraDammSwap
+            | EventType::MeteoraDammAddLiquidity
+            | EventType::MeteoraDammRemoveLiquidity
+            | EventType::MeteoraDammBootstrapLiquidity
+    ) {
+        return unwind_vault_transfers(inner_instruction, current_index, accounts);
+    }
     let take = match event_type {
         EventType::PumpFunBuy => 4,
         EventType::PumpFunSell => 1,
@@ -220,10 +832,13 @@ pub fn parse_transfer_datas_from_next_instructions(
         | EventType::BonkBuyExactOut
         | EventType::BonkSellExactIn
         | EventType::BonkSellExactOut => 3,
+        EventType::BoopBuy | EventType::BoopSell => 3,
         EventType::RaydiumCpmmSwapBaseInput
         | EventType::RaydiumCpmmSwapBaseOutput
         | EventType::RaydiumClmmSwap
-        | EventType::RaydiumClmmSwapV2 => 2,
+        | EventType::RaydiumClmmSwapV2
+        | EventType::BelieveTrade
+        | EventType::MeteoraDbcSwap => 2,
         _ => 0,
     };
     if take == 0 {
@@ -240,7 +855,10 @@ pub fn parse_transfer_datas_from_next_instructions(
 
     for instruction in next_instructions {
         if let UiInstruction::Compiled(compiled) = instruction {
-            if let Ok(data) = bs58::decode(compiled.data.clone()).into_vec() {
+            if let Ok(data) = codec::base58_decode(&compiled.data) {
+                if data.is_empty() {
+                    continue;
+                }
                 // Token Program: transferChecked
                 // Token 2022 Program: transferChecked
                 if data[0] == 12 {
@@ -0,0 +1,293 @@
+use solana_sdk::{
+    hash::Hash,
+    instruction::CompiledInstruction,
+    message::{
+        v0::{self, MessageAddressTableLookup},
+        Message, MessageHeader, VersionedMessage,
+    },
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+use solana_transaction_status::{
+    parse_accounts::ParsedAccountSource, EncodedTransaction, ParsedInstruction,
+    UiAddressTableLookup, UiCompiledInstruction, UiInnerInstructions, UiInstruction, UiMessage,
+    UiParsedInstruction, UiParsedMessage, UiRawMessage, UiTransaction,
+};
+use std::str::FromStr;
+
+use super::codec;
+
+const SPL_TOKEN_TRANSFER: u8 = 3;
+const SPL_TOKEN_TRANSFER_CHECKED: u8 = 12;
+const SYSTEM_TRANSFER: u32 = 2;
+
+/// 解码任意编码的交易
+///
+/// Like `EncodedTransaction::decode`, which handles the binary encodings, but also
+/// rebuilds `Json` and `JsonParsed` transactions. Instructions of `JsonParsed`
+/// messages that the RPC node parsed into JSON are recompiled from it where the
+/// parsers read them (token and SOL transfers, memos) and kept as data-less
+/// instructions of their program otherwise, so instruction positions still match.
+pub fn decode_transaction(transaction: &EncodedTransaction) -> Option<VersionedTransaction> {
+    let EncodedTransaction::Json(UiTransaction {
+        signatures,
+        message,
+    }) = transaction
+    else {
+        return transaction.decode();
+    };
+    let signatures = signatures
+        .iter()
+        .map(|signature| Signature::from_str(signature).ok())
+        .collect::<Option<Vec<_>>>()?;
+    let message = match message {
+        UiMessage::Raw(message) => raw_message(message)?,
+        UiMessage::Parsed(message) => parsed_message(message)?,
+    };
+    Some(VersionedTransaction {
+        signatures,
+        message,
+    })
+}
+
+/// `JsonParsed` 交易从地址表加载的账户
+///
+/// Nodes leave `loadedAddresses` out of the metadata of `JsonParsed` transactions
+/// and list the loaded accounts with the message keys instead, writable first.
+pub fn ui_loaded_addresses(transaction: &EncodedTransaction) -> Vec<Pubkey> {
+    let EncodedTransaction::Json(UiTransaction {
+        message: UiMessage::Parsed(message),
+        ..
+    }) = transaction
+    else {
+        return vec![];
+    };
+    message
+        .account_keys
+        .iter()
+        .filter(|account| matches!(account.source, Some(ParsedAccountSource::LookupTable)))
+        .filter_map(|account| codec::decode_pubkey(&account.pubkey))
+        .collect()
+}
+
+/// 将 `JsonParsed` 的内联指令就地转为编译格式
+///
+/// `accounts` is the full account list of the transaction. Compiled instructions
+/// are left untouched, so binary-encoded transactions cost nothing.
+pub fn compile_inner_instructions(
+    inner_instructions: &mut [UiInnerInstructions],
+    accounts: &[Pubkey],
+) {
+    for instruction in inner_instructions
+        .iter_mut()
+        .flat_map(|inner| inner.instructions.iter_mut())
+    {
+        let UiInstruction::Parsed(parsed) = instruction else {
+            continue;
+        };
+        if let Some(compiled) = compile_parsed_instruction(parsed, accounts) {
+            *instruction = UiInstruction::Compiled(UiCompiledInstruction {
+                program_id_index: compiled.program_id_index,
+                accounts: compiled.accounts,
+                data: bs58::encode(compiled.data).into_string(),
+                stack_height: parsed_stack_height(parsed),
+            });
+        }
+    }
+}
+
+fn raw_message(message: &UiRawMessage) -> Option<VersionedMessage> {
+    let account_keys = message
+        .account_keys
+        .iter()
+        .map(|key| codec::decode_pubkey(key))
+        .collect::<Option<Vec<_>>>()?;
+    let recent_blockhash = Hash::from_str(&message.recent_blockhash).ok()?;
+    let instructions = message
+        .instructions
+        .iter()
+        .map(|instruction| {
+            Some(CompiledInstruction {
+                program_id_index: instruction.program_id_index,
+                accounts: instruction.accounts.clone(),
+                data: codec::base58_decode(&instruction.data).ok()?,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    versioned_message(
+        message.header,
+        account_keys,
+        recent_blockhash,
+        instructions,
+        message.address_table_lookups.as_deref(),
+    )
+}
+
+fn parsed_message(message: &UiParsedMessage) -> Option<VersionedMessage> {
+    let accounts = message
+        .account_keys
+        .iter()
+        .map(|account| codec::decode_pubkey(&account.pubkey))
+        .collect::<Option<Vec<_>>>()?;
+    let static_accounts: Vec<_> = message
+        .account_keys
+        .iter()
+        .filter(|account| !matches!(account.source, Some(ParsedAccountSource::LookupTable)))
+        .collect();
+    let header = MessageHeader {
+        num_required_signatures: static_accounts.iter().filter(|a| a.signer).count() as u8,
+        num_readonly_signed_accounts: static_accounts
+            .iter()
+            .filter(|a| a.signer && !a.writable)
+            .count() as u8,
+        num_readonly_unsigned_accounts: static_accounts
+            .iter()
+            .filter(|a| !a.signer && !a.writable)
+            .count() as u8,
+    };
+    let recent_blockhash = Hash::from_str(&message.recent_blockhash).ok()?;
+    let instructions = message
+        .instructions
+        .iter()
+        .map(|instruction| match instruction {
+            UiInstruction::Compiled(compiled) => Some(CompiledInstruction {
+                program_id_index: compiled.program_id_index,
+                accounts: compiled.accounts.clone(),
+                data: codec::base58_decode(&compiled.data).ok()?,
+            }),
+            UiInstruction::Parsed(parsed) => compile_parsed_instruction(parsed, &accounts),
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let static_keys = accounts[..static_accounts.len()].to_vec();
+    versioned_message(
+        header,
+        static_keys,
+        recent_blockhash,
+        instructions,
+        message.address_table_lookups.as_deref(),
+    )
+}
+
+fn versioned_message(
+    header: MessageHeader,
+    account_keys: Vec<Pubkey>,
+    recent_blockhash: Hash,
+    instructions: Vec<CompiledInstruction>,
+    address_table_lookups: Option<&[UiAddressTableLookup]>,
+) -> Option<VersionedMessage> {
+    // 旧版交易没有地址表字段
+    let Some(address_table_lookups) = address_table_lookups else {
+        return Some(VersionedMessage::Legacy(Message {
+            header,
+            account_keys,
+            recent_blockhash,
+            instructions,
+        }));
+    };
+    let address_table_lookups = address_table_lookups
+        .iter()
+        .map(|lookup| {
+            Some(MessageAddressTableLookup {
+                account_key: codec::decode_pubkey(&lookup.account_key)?,
+                writable_indexes: lookup.writable_indexes.clone(),
+                readonly_indexes: lookup.readonly_indexes.clone(),
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(VersionedMessage::V0(v0::Message {
+        header,
+        account_keys,
+        recent_blockhash,
+        instructions,
+        address_table_lookups,
+    }))
+}
+
+fn parsed_stack_height(instruction: &UiParsedInstruction) -> Option<u32> {
+    match instruction {
+        UiParsedInstruction::Parsed(parsed) => parsed.stack_height,
+        UiParsedInstruction::PartiallyDecoded(partial) => partial.stack_height,
+    }
+}
+
+/// 将节点解析过的指令还原为编译格式
+fn compile_parsed_instruction(
+    instruction: &UiParsedInstruction,
+    accounts: &[Pubkey],
+) -> Option<CompiledInstruction> {
+    let index_of = |key: &str| {
+        let key = codec::decode_pubkey(key)?;
+        accounts
+            .iter()
+            .position(|account| *account == key)
+            .map(|index| index as u8)
+    };
+    match instruction {
+        UiParsedInstruction::PartiallyDecoded(partial) => Some(CompiledInstruction {
+            program_id_index: index_of(&partial.program_id)?,
+            accounts: partial
+                .accounts
+                .iter()
+                .map(|account| index_of(account))
+                .collect::<Option<Vec<_>>>()?,
+            data: codec::base58_decode(&partial.data).ok()?,
+        }),
+        UiParsedInstruction::Parsed(parsed) => {
+            let (accounts, data) = recompile(parsed, &index_of).unwrap_or_default();
+            Some(CompiledInstruction {
+                program_id_index: index_of(&parsed.program_id)?,
+                accounts,
+                data,
+            })
+        }
+    }
+}
+
+/// 按解析结果重新编码转账和memo指令的账户与数据
+fn recompile(
+    parsed: &ParsedInstruction,
+    index_of: &dyn Fn(&str) -> Option<u8>,
+) -> Option<(Vec<u8>, Vec<u8>)> {
+    let info = &parsed.parsed["info"];
+    let account = |field: &str| info[field].as_str().and_then(index_of);
+    let amount = |value: &serde_json::Value| {
+        value
+            .as_str()
+            .and_then(|amount| amount.parse::<u64>().ok())
+            .or(value.as_u64())
+    };
+    let authority = || account("authority").or_else(|| account("multisigAuthority"));
+    match (parsed.program.as_str(), parsed.parsed["type"].as_str()) {
+        ("spl-token" | "spl-token-2022", Some("transfer")) => {
+            let mut data = vec![SPL_TOKEN_TRANSFER];
+            data.extend_from_slice(&amount(&info["amount"])?.to_le_bytes());
+            Some((
+                vec![account("source")?, account("destination")?, authority()?],
+                data,
+            ))
+        }
+        ("spl-token" | "spl-token-2022", Some("transferChecked")) => {
+            let token_amount = &info["tokenAmount"];
+            let mut data = vec![SPL_TOKEN_TRANSFER_CHECKED];
+            data.extend_from_slice(&amount(&token_amount["amount"])?.to_le_bytes());
+            data.push(token_amount["decimals"].as_u64()? as u8);
+            Some((
+                vec![
+                    account("source")?,
+                    account("mint")?,
+                    account("destination")?,
+                    authority()?,
+                ],
+                data,
+            ))
+        }
+        ("system", Some("transfer")) => {
+            let mut data = SYSTEM_TRANSFER.to_le_bytes().to_vec();
+            data.extend_from_slice(&amount(&info["lamports"])?.to_le_bytes());
+            Some((vec![account("source")?, account("destination")?], data))
+        }
+        ("spl-memo", _) => Some((vec![], parsed.parsed.as_str()?.as_bytes().to_vec())),
+        _ => None,
+    }
+}
@@ -2,6 +2,8 @@ use base64::engine::general_purpose;
 use base64::Engine;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use super::instruction_index::InstructionIndex;
+
 /// 获取当前时间戳
 pub fn current_timestamp() -> i64 {
     SystemTime::now()
@@ -11,6 +13,8 @@ pub fn current_timestamp() -> i64 {
 }
 
 /// 从base64字符串解码数据
+///
+/// Kept for existing callers; parsers decode through [`super::codec::base64_decode`].
 pub fn decode_base64(data: &str) -> Result<Vec<u8>, base64::DecodeError> {
     general_purpose::STANDARD.decode(data)
 }
@@ -36,12 +40,199 @@ pub fn discriminator_matches(data: &str, expected: &str) -> bool {
     &data[..expected.len()] == expected
 }
 
+/// 比较原始字节与十六进制discriminator（"0x..."）的前缀，不分配内存
+pub fn discriminator_hex_matches(data: &[u8], expected: &str) -> bool {
+    let hex = expected.strip_prefix("0x").unwrap_or(expected).as_bytes();
+    if hex.len() % 2 != 0 || data.len() < hex.len() / 2 {
+        return false;
+    }
+    hex.chunks_exact(2).zip(data).all(|(pair, byte)| {
+        match (hex_nibble(pair[0]), hex_nibble(pair[1])) {
+            (Some(high), Some(low)) => (high << 4 | low) == *byte,
+            _ => false,
+        }
+    })
+}
+
+fn hex_nibble(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
 /// 从日志中提取程序数据
 pub fn extract_program_data(log: &str) -> Option<&str> {
     const PROGRAM_DATA_PREFIX: &str = "Program data: ";
     log.strip_prefix(PROGRAM_DATA_PREFIX)
 }
 
+/// 每行日志所属的指令
+///
+/// Follows the `Program <id> invoke [depth]` / `success` / `failed` lines; inner
+/// instructions are numbered in invocation order like `innerInstructions`. Lines
+/// after a truncated or unbalanced log get `None`.
+pub fn log_instruction_indices(logs: &[String]) -> Vec<Option<InstructionIndex>> {
+    let mut indices = Vec::with_capacity(logs.len());
+    let mut stack: Vec<InstructionIndex> = vec![];
+    let mut outer: Option<u16> = None;
+    let mut inner = 0u16;
+    let mut broken = false;
+    for log in logs {
+        let Some(rest) = log.strip_prefix("Program ") else {
+            indices.push(if broken { None } else { stack.last().copied() });
+            continue;
+        };
+        if let Some(depth) = rest
+            .split_once(" invoke [")
+            .and_then(|(_, depth)| depth.strip_suffix(']'))
+            .and_then(|depth| depth.parse::<usize>().ok())
+        {
+            if depth == 1 {
+                let index = outer.map_or(0, |index| index + 1);
+                outer = Some(index);
+                inner = 0;
+                stack.clear();
+                stack.push(InstructionIndex::outer(index));
+                broken = false;
+            } else if let (Some(index), false) = (outer, broken || stack.len() + 1 != depth) {
+                stack.push(InstructionIndex::inner(index, inner));
+                inner += 1;
+            } else {
+                broken = true;
+            }
+            indices.push(if broken { None } else { stack.last().copied() });
+        } else if rest.ends_with(" success") || rest.contains(" failed: ") {
+            indices.push(if broken { None } else { stack.last().copied() });
+            stack.pop();
+        } else {
+            indices.push(if broken { None } else { stack.last().copied() });
+        }
+    }
+    indices
+}
+
+/// 发出第`position`条内联指令的指令
+///
+/// `emit_cpi!` events are self-CPIs, so the invoker is the closest preceding inner
+/// instruction of the same program one stack level up (any level when the stack
+/// height is not recorded), or the outer instruction.
+pub fn invoking_instruction_index(
+    inner_instructions: &solana_transaction_status::UiInnerInstructions,
+    position: usize,
+) -> InstructionIndex {
+    use solana_transaction_status::UiInstruction;
+    let outer = InstructionIndex::outer(inner_instructions.index as u16);
+    let UiInstruction::Compiled(current) = &inner_instructions.instructions[position] else {
+        return outer;
+    };
+    inner_instructions.instructions[..position]
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, instruction)| match instruction {
+            UiInstruction::Compiled(candidate) => {
+                candidate.program_id_index == current.program_id_index
+                    && match (candidate.stack_height, current.stack_height) {
+                        (Some(parent), Some(height)) => parent + 1 == height,
+                        _ => true,
+                    }
+            }
+            _ => false,
+        })
+        .map(|(index, _)| InstructionIndex::inner(outer.outer, index as u16))
+        .unwrap_or(outer)
+}
+
+/// 调用`index`处指令的程序链，最外层在前
+///
+/// Follows `stack_height` back through the inner instructions of the top-level
+/// instruction: the top-level program, then each caller one stack level down.
+/// Empty for top-level instructions. Inner instructions without a recorded stack
+/// height only get the top-level program.
+pub fn caller_program_ids(
+    instructions: &[solana_sdk::instruction::CompiledInstruction],
+    inner_instructions: &[solana_transaction_status::UiInnerInstructions],
+    accounts: &[solana_sdk::pubkey::Pubkey],
+    index: &InstructionIndex,
+) -> Vec<solana_sdk::pubkey::Pubkey> {
+    use solana_transaction_status::UiInstruction;
+    let Some(position) = index.inner else {
+        return vec![];
+    };
+    let program_id =
+        |program_id_index: u8| accounts.get(program_id_index as usize).copied().unwrap_or_default();
+    let Some(outer) = instructions.get(index.outer as usize) else {
+        return vec![];
+    };
+    let mut callers = vec![program_id(outer.program_id_index)];
+    let Some(group) = inner_instructions.iter().find(|inner| inner.index as u16 == index.outer)
+    else {
+        return callers;
+    };
+    let compiled = |position: usize| match group.instructions.get(position) {
+        Some(UiInstruction::Compiled(compiled)) => Some(compiled),
+        _ => None,
+    };
+    let Some(mut height) = compiled(position as usize).and_then(|current| current.stack_height)
+    else {
+        return callers;
+    };
+    // 顶层指令的stack_height为1
+    let mut chain = vec![];
+    for candidate in (0..position as usize).rev().filter_map(compiled) {
+        if height <= 2 {
+            break;
+        }
+        if candidate.stack_height == Some(height - 1) {
+            chain.push(program_id(candidate.program_id_index));
+            height -= 1;
+        }
+    }
+    callers.extend(chain.into_iter().rev());
+    callers
+}
+
+/// 交易中的顶层和内部指令，按执行顺序
+///
+/// Inner instruction data is base58-decoded; instructions whose data doesn't
+/// decode are skipped.
+pub fn transaction_instructions(
+    versioned_tx: &solana_sdk::transaction::VersionedTransaction,
+    inner_instructions: &[solana_transaction_status::UiInnerInstructions],
+) -> Vec<(InstructionIndex, solana_sdk::instruction::CompiledInstruction)> {
+    use solana_sdk::instruction::CompiledInstruction;
+    use solana_transaction_status::UiInstruction;
+    let mut instructions: Vec<(InstructionIndex, CompiledInstruction)> = versioned_tx
+        .message
+        .instructions()
+        .iter()
+        .enumerate()
+        .map(|(index, ix)| (InstructionIndex::outer(index as u16), ix.clone()))
+        .collect();
+    for inner_instruction in inner_instructions {
+        for (index, instruction) in inner_instruction.instructions.iter().enumerate() {
+            if let UiInstruction::Compiled(compiled) = instruction {
+                let Ok(data) = super::codec::base58_decode(&compiled.data) else {
+                    continue;
+                };
+                instructions.push((
+                    InstructionIndex::inner(inner_instruction.index as u16, index as u16),
+                    CompiledInstruction {
+                        program_id_index: compiled.program_id_index,
+                        accounts: compiled.accounts.clone(),
+                        data,
+                    },
+                ));
+            }
+        }
+    }
+    instructions.sort_by_key(|(index, _)| *index);
+    instructions
+}
+
 /// 从日志中提取程序日志
 pub fn extract_program_log<'a>(log: &'a str, prefix: &str) -> Option<&'a str> {
     log.strip_prefix(prefix)
@@ -95,6 +286,12 @@ pub fn read_u8(data: &[u8], offset: usize) -> Option<u8> {
     data.get(offset).copied()
 }
 
+/// 安全地从字节数组中读取公钥
+pub fn read_pubkey(data: &[u8], offset: usize) -> Option<solana_sdk::pubkey::Pubkey> {
+    let bytes: [u8; 32] = data.get(offset..offset + 32)?.try_into().ok()?;
+    Some(solana_sdk::pubkey::Pubkey::new_from_array(bytes))
+}
+
 /// 验证账户索引的有效性
 pub fn validate_account_indices(indices: &[u8], account_count: usize) -> bool {
     indices.iter().all(|&idx| (idx as usize) < account_count)
@@ -0,0 +1,55 @@
+use solana_sdk::pubkey::Pubkey;
+
+/// 零拷贝视图的校验错误
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ViewError {
+    #[error("expected {expected} bytes, got {actual}")]
+    Length { expected: usize, actual: usize },
+    #[error("invalid bool {value} at offset {offset}")]
+    InvalidBool { offset: usize, value: u8 },
+}
+
+/// 校验定长布局的数据长度
+///
+/// Matches `borsh::from_slice`, which rejects both short data and trailing bytes.
+pub fn check_len(data: &[u8], expected: usize) -> Result<(), ViewError> {
+    if data.len() != expected {
+        return Err(ViewError::Length {
+            expected,
+            actual: data.len(),
+        });
+    }
+    Ok(())
+}
+
+/// 校验布尔字段只能是0或1
+///
+/// `offset` must be inside `data`, as after [`check_len`].
+pub(crate) fn check_bool(data: &[u8], offset: usize) -> Result<(), ViewError> {
+    match data[offset] {
+        0 | 1 => Ok(()),
+        value => Err(ViewError::InvalidBool { offset, value }),
+    }
+}
+
+// 以下读取函数不做边界检查，仅供视图在check_len之后调用
+
+#[inline]
+pub(crate) fn pubkey_at(data: &[u8], offset: usize) -> &Pubkey {
+    bytemuck::from_bytes(&data[offset..offset + 32])
+}
+
+#[inline]
+pub(crate) fn u64_at(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+#[inline]
+pub(crate) fn i64_at(data: &[u8], offset: usize) -> i64 {
+    i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+#[inline]
+pub(crate) fn bool_at(data: &[u8], offset: usize) -> bool {
+    data[offset] != 0
+}
@@ -0,0 +1,442 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::{UiInnerInstructions, UiInstruction};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+use std::time::Duration;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::{
+    EventMetadata, EventType, InstructionIndex, ProtocolType, RawInstruction,
+};
+use crate::streaming::event_parser::UnifiedEvent;
+
+#[derive(Debug, Default)]
+struct DecodeCounters {
+    attempts: AtomicU64,
+    failures: AtomicU64,
+}
+
+impl DecodeCounters {
+    fn record(&self, success: bool) {
+        self.attempts.fetch_add(1, Ordering::Relaxed);
+        if !success {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// 各协议的解码计数，进程内共享
+static DECODE_COUNTERS: LazyLock<RwLock<HashMap<ProtocolType, DecodeCounters>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// 记录一次解码尝试
+///
+/// Parsers call this when data matched one of their discriminators; `success` is
+/// false when the layout didn't decode, which would otherwise be a silent `None`.
+pub fn record_decode(protocol: &ProtocolType, success: bool) {
+    if let Some(counters) = DECODE_COUNTERS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(protocol)
+    {
+        counters.record(success);
+        return;
+    }
+    DECODE_COUNTERS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(protocol.clone())
+        .or_default()
+        .record(success);
+}
+
+/// 协议的累计解码统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParserHealthStats {
+    pub protocol: ProtocolType,
+    /// Data that matched a discriminator of the protocol
+    pub attempts: u64,
+    /// Matched data that failed to decode
+    pub failures: u64,
+}
+
+impl ParserHealthStats {
+    pub fn failure_ratio(&self) -> f64 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.failures as f64 / self.attempts as f64
+        }
+    }
+}
+
+/// Decode statistics of one protocol since the process started
+pub fn protocol_health(protocol: &ProtocolType) -> ParserHealthStats {
+    let counters = DECODE_COUNTERS.read().unwrap_or_else(|e| e.into_inner());
+    let (attempts, failures) = counters.get(protocol).map_or((0, 0), |counters| {
+        (
+            counters.attempts.load(Ordering::Relaxed),
+            counters.failures.load(Ordering::Relaxed),
+        )
+    });
+    ParserHealthStats {
+        protocol: protocol.clone(),
+        attempts,
+        failures,
+    }
+}
+
+/// Decode statistics of every protocol that attempted a decode, for metrics
+pub fn parser_health() -> Vec<ParserHealthStats> {
+    let protocols: Vec<ProtocolType> = DECODE_COUNTERS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .keys()
+        .cloned()
+        .collect();
+    protocols.iter().map(protocol_health).collect()
+}
+
+/// 解析器降级事件 - 协议解码失败率突增时由熔断器生成
+///
+/// Usually means a program upgrade changed a layout. Emitted once when the
+/// protocol trips, with the counts of the window that tripped it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParserDegradedEvent {
+    pub metadata: EventMetadata,
+    pub protocol: ProtocolType,
+    pub attempts: u64,
+    pub failures: u64,
+    pub failure_ratio: f64,
+    /// Whether the protocol now emits `RawInstructionEvent`s instead of typed events
+    pub passthrough: bool,
+}
+
+impl_unified_event!(ParserDegradedEvent,);
+
+/// 原始指令事件 - 降级协议在直通模式下输出
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawInstructionEvent {
+    pub metadata: EventMetadata,
+    pub instruction: RawInstruction,
+}
+
+impl_unified_event!(RawInstructionEvent,);
+
+/// 熔断器配置
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitBreakerConfig {
+    /// Length of the window failures are counted in
+    pub window: Duration,
+    /// Attempts a window needs before its failure ratio counts
+    pub min_attempts: u64,
+    /// Failure ratio (0.0-1.0) that trips the breaker
+    pub failure_ratio: f64,
+    /// Replace the typed events of a degraded protocol with `RawInstructionEvent`s
+    pub passthrough: bool,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CircuitBreakerConfig {
+    pub fn new() -> Self {
+        Self {
+            window: Duration::from_secs(60),
+            min_attempts: 50,
+            failure_ratio: 0.5,
+            passthrough: false,
+        }
+    }
+
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    pub fn with_min_attempts(mut self, min_attempts: u64) -> Self {
+        self.min_attempts = min_attempts.max(1);
+        self
+    }
+
+    pub fn with_failure_ratio(mut self, failure_ratio: f64) -> Self {
+        self.failure_ratio = failure_ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_passthrough(mut self, passthrough: bool) -> Self {
+        self.passthrough = passthrough;
+        self
+    }
+}
+
+#[derive(Debug)]
+struct BreakerState {
+    window_start_ms: i64,
+    // 窗口开始时的累计计数
+    attempts: u64,
+    failures: u64,
+    degraded: bool,
+    trips: u64,
+}
+
+struct BreakerInner {
+    config: CircuitBreakerConfig,
+    states: Mutex<HashMap<ProtocolType, BreakerState>>,
+}
+
+/// 解析失败熔断器
+///
+/// Watches the decode counters of [`record_decode`] per protocol. When a window
+/// of at least `min_attempts` decodes fails at `failure_ratio` or more, the
+/// protocol is marked degraded, a [`ParserDegradedEvent`] is emitted with the
+/// events of that transaction and, with `passthrough`, the protocol's
+/// instructions are emitted as [`RawInstructionEvent`]s instead of typed events
+/// that may be wrong. Parsing continues in the background, so a later window
+/// below the ratio restores the protocol.
+///
+/// # Usage Example
+/// ```ignore
+/// let breaker = ParserCircuitBreaker::new(CircuitBreakerConfig::new().with_passthrough(true));
+/// let options = ParseOptions::new().with_circuit_breaker(breaker.clone());
+/// // later, from a metrics endpoint
+/// for stats in parser_health() {
+///     gauge!("parse_failures", stats.failures, "protocol" => format!("{:?}", stats.protocol));
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ParserCircuitBreaker {
+    inner: Arc<BreakerInner>,
+}
+
+impl Default for ParserCircuitBreaker {
+    fn default() -> Self {
+        Self::new(CircuitBreakerConfig::default())
+    }
+}
+
+impl fmt::Debug for ParserCircuitBreaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParserCircuitBreaker")
+            .field("config", &self.inner.config)
+            .finish()
+    }
+}
+
+impl PartialEq for ParserCircuitBreaker {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for ParserCircuitBreaker {}
+
+impl ParserCircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            inner: Arc::new(BreakerInner {
+                config,
+                states: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.inner.config
+    }
+
+    pub fn is_degraded(&self, protocol: &ProtocolType) -> bool {
+        self.inner
+            .states
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(protocol)
+            .is_some_and(|state| state.degraded)
+    }
+
+    /// Whether `protocol` currently emits raw instructions
+    pub fn is_passthrough(&self, protocol: &ProtocolType) -> bool {
+        self.inner.config.passthrough && self.is_degraded(protocol)
+    }
+
+    pub fn degraded_protocols(&self) -> Vec<ProtocolType> {
+        self.inner
+            .states
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|(_, state)| state.degraded)
+            .map(|(protocol, _)| protocol.clone())
+            .collect()
+    }
+
+    /// Number of times `protocol` was marked degraded
+    pub fn trips(&self, protocol: &ProtocolType) -> u64 {
+        self.inner
+            .states
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(protocol)
+            .map_or(0, |state| state.trips)
+    }
+
+    /// Evaluate the current window of `protocol`, returning an event if it tripped
+    ///
+    /// The event's metadata only carries the event type and protocol.
+    pub fn check(&self, protocol: &ProtocolType, now_ms: i64) -> Option<ParserDegradedEvent> {
+        let config = &self.inner.config;
+        let stats = protocol_health(protocol);
+        let mut states = self.inner.states.lock().unwrap_or_else(|e| e.into_inner());
+        let state = states
+            .entry(protocol.clone())
+            .or_insert_with(|| BreakerState {
+                window_start_ms: now_ms,
+                attempts: stats.attempts,
+                failures: stats.failures,
+                degraded: false,
+                trips: 0,
+            });
+        let attempts = stats.attempts.saturating_sub(state.attempts);
+        let failures = stats.failures.saturating_sub(state.failures);
+        let failure_ratio = if attempts == 0 {
+            0.0
+        } else {
+            failures as f64 / attempts as f64
+        };
+        let window_ended = now_ms - state.window_start_ms >= config.window.as_millis() as i64;
+        let counts = attempts >= config.min_attempts;
+        let mut tripped = None;
+        if !state.degraded && counts && failure_ratio >= config.failure_ratio {
+            state.degraded = true;
+            state.trips += 1;
+            log::warn!(
+                "{:?} parser degraded: {} of {} decodes failed",
+                protocol,
+                failures,
+                attempts
+            );
+            let mut metadata = EventMetadata::default();
+            metadata.event_type = EventType::ParserDegraded;
+            metadata.protocol = protocol.clone();
+            tripped = Some(ParserDegradedEvent {
+                metadata,
+                protocol: protocol.clone(),
+                attempts,
+                failures,
+                failure_ratio,
+                passthrough: config.passthrough,
+            });
+        } else if window_ended && state.degraded && counts && failure_ratio < config.failure_ratio {
+            state.degraded = false;
+            log::info!(
+                "{:?} parser recovered: {} of {} decodes failed",
+                protocol,
+                failures,
+                attempts
+            );
+        }
+        if tripped.is_some() || window_ended {
+            state.window_start_ms = now_ms;
+            state.attempts = stats.attempts;
+            state.failures = stats.failures;
+        }
+        tripped
+    }
+
+    /// Apply the breaker to the events a parser produced for one transaction
+    ///
+    /// `template` is the metadata of the transaction; events created here copy it.
+    pub(crate) fn apply(
+        &self,
+        protocol: &ProtocolType,
+        program_ids: &[Pubkey],
+        mut events: Vec<Box<dyn UnifiedEvent>>,
+        template: &EventMetadata,
+        now_ms: i64,
+        transaction: RawTransaction<'_>,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        let tripped = self.check(protocol, now_ms);
+        if self.is_passthrough(protocol) {
+            events = transaction
+                .instructions_of(program_ids)
+                .into_iter()
+                .map(|(index, instruction)| {
+                    let mut metadata = EventMetadata {
+                        event_type: EventType::RawInstruction,
+                        program_id: instruction.program_id,
+                        index,
+                        ..template.clone()
+                    };
+                    metadata.set_instruction_id(&index);
+                    Box::new(RawInstructionEvent {
+                        metadata,
+                        instruction,
+                    }) as Box<dyn UnifiedEvent>
+                })
+                .collect();
+        }
+        if let Some(mut event) = tripped {
+            event.metadata = EventMetadata {
+                event_type: EventType::ParserDegraded,
+                ..template.clone()
+            };
+            event.metadata.set_id(format!("{:?}", protocol));
+            events.push(Box::new(event));
+        }
+        events
+    }
+}
+
+/// 熔断器直通模式读取的交易指令
+pub(crate) struct RawTransaction<'a> {
+    pub instructions: &'a [CompiledInstruction],
+    pub inner_instructions: &'a [UiInnerInstructions],
+    pub accounts: &'a [Pubkey],
+}
+
+impl RawTransaction<'_> {
+    /// Top-level and inner instructions of `program_ids`, in execution order
+    fn instructions_of(&self, program_ids: &[Pubkey]) -> Vec<(InstructionIndex, RawInstruction)> {
+        let mut indexes: Vec<InstructionIndex> = self
+            .instructions
+            .iter()
+            .enumerate()
+            .filter(|(_, instruction)| self.is_program(instruction.program_id_index, program_ids))
+            .map(|(index, _)| InstructionIndex::outer(index as u16))
+            .collect();
+        for inner in self.inner_instructions {
+            for (index, instruction) in inner.instructions.iter().enumerate() {
+                if let UiInstruction::Compiled(compiled) = instruction {
+                    if self.is_program(compiled.program_id_index, program_ids) {
+                        indexes.push(InstructionIndex::inner(inner.index as u16, index as u16));
+                    }
+                }
+            }
+        }
+        indexes.sort();
+        indexes
+            .into_iter()
+            .filter_map(|index| {
+                RawInstruction::at_index(
+                    &index,
+                    self.instructions,
+                    self.inner_instructions,
+                    self.accounts,
+                )
+                .map(|instruction| (index, instruction))
+            })
+            .collect()
+    }
+
+    fn is_program(&self, program_id_index: u8, program_ids: &[Pubkey]) -> bool {
+        self.accounts
+            .get(program_id_index as usize)
+            .is_some_and(|program_id| program_ids.contains(program_id))
+    }
+}
@@ -0,0 +1,35 @@
+use crate::streaming::event_parser::UnifiedEvent;
+
+/// 交易解析错误
+///
+/// Errors that stop a whole transaction from being parsed are returned as `Err`;
+/// errors scoped to one instruction are collected in [`ParseOutput::errors`] while
+/// the remaining instructions are still parsed.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseError {
+    #[error("missing transaction metadata")]
+    MissingMeta,
+    #[error("transaction could not be decoded from its encoding")]
+    UndecodableTransaction,
+    #[error("invalid loaded address {address}: {reason}")]
+    InvalidLoadedAddress { address: String, reason: String },
+    #[error("invalid base58 data in instruction {index}: {reason}")]
+    InvalidInstructionData { index: String, reason: String },
+    #[error("failed to parse instruction {index}: {reason}")]
+    Instruction { index: String, reason: String },
+    #[error("failed to parse logs: {reason}")]
+    Logs { reason: String },
+}
+
+/// 解析结果 - 成功解析的事件以及各指令的错误
+#[derive(Debug, Default)]
+pub struct ParseOutput {
+    pub events: Vec<Box<dyn UnifiedEvent>>,
+    pub errors: Vec<ParseError>,
+}
+
+impl ParseOutput {
+    pub fn is_partial(&self) -> bool {
+        !self.errors.is_empty()
+    }
+}
@@ -0,0 +1,187 @@
+use solana_sdk::{pubkey::Pubkey, transaction::VersionedTransaction};
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, EncodedTransactionWithStatusMeta, UiInnerInstructions,
+};
+use std::str::FromStr;
+
+use crate::streaming::event_parser::common::{
+    codec, decode_transaction, ui_loaded_addresses, TokenBalance,
+};
+use crate::streaming::event_parser::core::error::ParseError;
+
+/// 已解码的交易及其元数据，与来源编码无关
+///
+/// Everything the parsers read from a transaction. Built from RPC-encoded
+/// transactions with [`TransactionInput::from_encoded`] and, with the `grpc`
+/// feature, straight from yellowstone protobuf updates with
+/// [`TransactionInput::from_grpc`], so gRPC streams skip the RPC encoding round trip.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionInput {
+    /// None when the transaction could not be decoded
+    pub transaction: Option<VersionedTransaction>,
+    /// Accounts loaded from address lookup tables, writable first
+    pub loaded_addresses: Vec<Pubkey>,
+    pub inner_instructions: Vec<UiInnerInstructions>,
+    /// None when the node did not record logs
+    pub log_messages: Option<Vec<String>>,
+    pub pre_token_balances: Vec<TokenBalance>,
+    pub post_token_balances: Vec<TokenBalance>,
+    pub fee: u64,
+    pub error: Option<String>,
+    /// Problems found while decoding, reported with the parse errors
+    pub errors: Vec<ParseError>,
+}
+
+impl TransactionInput {
+    pub fn from_encoded(tx: EncodedTransactionWithStatusMeta) -> Result<Self, ParseError> {
+        let meta = tx.meta.ok_or(ParseError::MissingMeta)?;
+        let mut errors = vec![];
+        let loaded_addresses = match meta.loaded_addresses {
+            OptionSerializer::Some(loaded_addresses) => loaded_addresses
+                .writable
+                .iter()
+                .chain(&loaded_addresses.readonly)
+                .map(|lookup| {
+                    Pubkey::from_str(lookup).unwrap_or_else(|e| {
+                        errors.push(ParseError::InvalidLoadedAddress {
+                            address: lookup.clone(),
+                            reason: e.to_string(),
+                        });
+                        // 保持账户索引对齐
+                        Pubkey::default()
+                    })
+                })
+                .collect(),
+            _ => ui_loaded_addresses(&tx.transaction),
+        };
+        Ok(Self {
+            transaction: decode_transaction(&tx.transaction),
+            loaded_addresses,
+            inner_instructions: match meta.inner_instructions {
+                OptionSerializer::Some(inner_instructions) => inner_instructions,
+                _ => vec![],
+            },
+            log_messages: match meta.log_messages {
+                OptionSerializer::Some(log_messages) => Some(log_messages),
+                _ => None,
+            },
+            pre_token_balances: token_balances(meta.pre_token_balances),
+            post_token_balances: token_balances(meta.post_token_balances),
+            fee: meta.fee,
+            error: meta.err.map(|e| e.to_string()),
+            errors,
+        })
+    }
+
+    /// 从仅有交易本身的数据源构建 (ShredStream等)
+    ///
+    /// `accounts` is the full account list, static keys first. There is no status
+    /// meta, so the transaction counts as successful and has no inner
+    /// instructions, logs or balances.
+    pub fn from_versioned(transaction: VersionedTransaction, accounts: &[Pubkey]) -> Self {
+        let static_keys = transaction.message.static_account_keys().len();
+        Self {
+            loaded_addresses: accounts.get(static_keys..).unwrap_or_default().to_vec(),
+            transaction: Some(transaction),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(feature = "grpc")]
+impl TransactionInput {
+    /// 直接从yellowstone gRPC交易更新构建
+    ///
+    /// Copies the protobuf fields over without encoding the transaction for RPC and
+    /// decoding it again; only inner instruction data is base58 encoded.
+    pub fn from_grpc(
+        update: yellowstone_grpc_proto::geyser::SubscribeUpdateTransactionInfo,
+    ) -> Result<Self, ParseError> {
+        use solana_transaction_status::{UiCompiledInstruction, UiInstruction};
+        use yellowstone_grpc_proto::convert_from;
+
+        let meta = update.meta.ok_or(ParseError::MissingMeta)?;
+        let mut errors = vec![];
+        let loaded_addresses = meta
+            .loaded_writable_addresses
+            .iter()
+            .chain(&meta.loaded_readonly_addresses)
+            .map(|lookup| {
+                Pubkey::try_from(lookup.as_slice()).unwrap_or_else(|_| {
+                    errors.push(ParseError::InvalidLoadedAddress {
+                        address: bs58::encode(lookup).into_string(),
+                        reason: format!("expected 32 bytes, got {}", lookup.len()),
+                    });
+                    // 保持账户索引对齐
+                    Pubkey::default()
+                })
+            })
+            .collect();
+        let inner_instructions = meta
+            .inner_instructions
+            .into_iter()
+            .map(|inner| UiInnerInstructions {
+                index: inner.index as u8,
+                instructions: inner
+                    .instructions
+                    .into_iter()
+                    .map(|instruction| {
+                        UiInstruction::Compiled(UiCompiledInstruction {
+                            program_id_index: instruction.program_id_index as u8,
+                            accounts: instruction.accounts,
+                            data: bs58::encode(instruction.data).into_string(),
+                            stack_height: instruction.stack_height,
+                        })
+                    })
+                    .collect(),
+            })
+            .collect();
+        let error = match convert_from::create_tx_error(meta.err.as_ref()) {
+            Ok(error) => error.map(|e| e.to_string()),
+            Err(reason) => Some(reason.to_string()),
+        };
+        Ok(Self {
+            transaction: update
+                .transaction
+                .and_then(|tx| convert_from::create_tx_versioned(tx).ok()),
+            loaded_addresses,
+            inner_instructions,
+            log_messages: (!meta.log_messages_none).then_some(meta.log_messages),
+            pre_token_balances: grpc_token_balances(&meta.pre_token_balances),
+            post_token_balances: grpc_token_balances(&meta.post_token_balances),
+            fee: meta.fee,
+            error,
+            errors,
+        })
+    }
+}
+
+fn token_balances(
+    balances: OptionSerializer<Vec<solana_transaction_status::UiTransactionTokenBalance>>,
+) -> Vec<TokenBalance> {
+    match balances {
+        OptionSerializer::Some(balances) => {
+            balances.iter().filter_map(TokenBalance::from_ui).collect()
+        }
+        _ => vec![],
+    }
+}
+
+#[cfg(feature = "grpc")]
+fn grpc_token_balances(
+    balances: &[yellowstone_grpc_proto::prelude::TokenBalance],
+) -> Vec<TokenBalance> {
+    balances
+        .iter()
+        .filter_map(|balance| {
+            let amount = balance.ui_token_amount.as_ref()?;
+            Some(TokenBalance {
+                account_index: balance.account_index as u8,
+                mint: codec::decode_pubkey(&balance.mint)?,
+                owner: codec::decode_pubkey(&balance.owner),
+                decimals: amount.decimals as u8,
+                amount: amount.amount.parse().unwrap_or_default(),
+            })
+        })
+        .collect()
+}
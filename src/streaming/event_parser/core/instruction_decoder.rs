@@ -0,0 +1,213 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::common::AnyResult;
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::{EventMetadata, ProtocolType};
+use crate::streaming::simulation::idl::{Idl, IdlField, IdlInstruction, IdlType, IdlTypeDef};
+
+/// 按IDL名称标注的指令账户
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamedAccount {
+    pub name: String,
+    pub pubkey: Pubkey,
+}
+
+/// 按IDL完整解码的指令
+///
+/// Emitted next to the protocol events when `ParseOptions::decoded_instructions`
+/// is set. `args` is a JSON object keyed by IDL argument name: `u128`/`i128` are
+/// decimal strings, pubkeys base58 and `bytes` base64. Accounts beyond the IDL
+/// list are named `remaining_<n>`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DecodedInstructionEvent {
+    pub metadata: EventMetadata,
+    pub name: String,
+    pub args: Value,
+    pub accounts: Vec<NamedAccount>,
+}
+
+impl_unified_event!(DecodedInstructionEvent,);
+
+impl DecodedInstructionEvent {
+    /// Pubkey of the account with IDL name `name`
+    pub fn account(&self, name: &str) -> Option<Pubkey> {
+        self.accounts
+            .iter()
+            .find(|a| a.name == name)
+            .map(|a| a.pubkey)
+    }
+}
+
+/// 基于IDL的指令解码器
+pub struct InstructionDecoder {
+    program_id: Pubkey,
+    protocol_type: ProtocolType,
+    idl: Idl,
+}
+
+impl InstructionDecoder {
+    pub fn new(program_id: Pubkey, protocol_type: ProtocolType, idl: Idl) -> Self {
+        Self {
+            program_id,
+            protocol_type,
+            idl,
+        }
+    }
+
+    /// Build a decoder from a legacy or 0.30 Anchor IDL
+    pub fn from_idl_json(
+        program_id: Pubkey,
+        protocol_type: ProtocolType,
+        json: &str,
+    ) -> AnyResult<Self> {
+        Ok(Self::new(program_id, protocol_type, Idl::from_json(json)?))
+    }
+
+    pub fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    pub fn protocol_type(&self) -> ProtocolType {
+        self.protocol_type.clone()
+    }
+
+    pub fn idl(&self) -> &Idl {
+        &self.idl
+    }
+
+    /// IDL instruction matching the discriminator at the start of `data`
+    pub fn instruction(&self, data: &[u8]) -> Option<&IdlInstruction> {
+        self.idl
+            .instructions
+            .iter()
+            .find(|ix| data.len() >= 8 && data[..8] == ix.discriminator)
+    }
+
+    /// Decode instruction data and name its accounts
+    ///
+    /// Returns `None` for unknown discriminators and for data that does not match
+    /// the IDL arguments.
+    pub fn decode(
+        &self,
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<DecodedInstructionEvent> {
+        let ix = self.instruction(data)?;
+        let mut reader = Reader { data: &data[8..] };
+        let args = match self.decode_fields(&ix.args, &mut reader) {
+            Ok(args) => args,
+            Err(e) => {
+                log::debug!("failed to decode {} args: {}", ix.name, e);
+                return None;
+            }
+        };
+        let accounts = accounts
+            .iter()
+            .enumerate()
+            .map(|(i, pubkey)| NamedAccount {
+                name: ix
+                    .accounts
+                    .get(i)
+                    .cloned()
+                    .unwrap_or_else(|| format!("remaining_{}", i - ix.accounts.len())),
+                pubkey: *pubkey,
+            })
+            .collect();
+        Some(DecodedInstructionEvent {
+            metadata,
+            name: ix.name.clone(),
+            args,
+            accounts,
+        })
+    }
+
+    fn decode_fields(&self, fields: &[IdlField], reader: &mut Reader) -> AnyResult<Value> {
+        let mut object = Map::new();
+        for field in fields {
+            object.insert(field.name.clone(), self.decode_value(&field.ty, reader)?);
+        }
+        Ok(Value::Object(object))
+    }
+
+    fn decode_value(&self, ty: &IdlType, reader: &mut Reader) -> AnyResult<Value> {
+        Ok(match ty {
+            IdlType::Bool => Value::Bool(reader.take::<1>()?[0] != 0),
+            IdlType::U8 => reader.take::<1>()?[0].into(),
+            IdlType::U16 => u16::from_le_bytes(reader.take()?).into(),
+            IdlType::U32 => u32::from_le_bytes(reader.take()?).into(),
+            IdlType::U64 => u64::from_le_bytes(reader.take()?).into(),
+            IdlType::U128 => u128::from_le_bytes(reader.take()?).to_string().into(),
+            IdlType::I8 => (reader.take::<1>()?[0] as i8).into(),
+            IdlType::I16 => i16::from_le_bytes(reader.take()?).into(),
+            IdlType::I32 => i32::from_le_bytes(reader.take()?).into(),
+            IdlType::I64 => i64::from_le_bytes(reader.take()?).into(),
+            IdlType::I128 => i128::from_le_bytes(reader.take()?).to_string().into(),
+            IdlType::F32 => f32::from_le_bytes(reader.take()?).into(),
+            IdlType::F64 => f64::from_le_bytes(reader.take()?).into(),
+            IdlType::Pubkey => Pubkey::new_from_array(reader.take()?).to_string().into(),
+            IdlType::String => {
+                let len = u32::from_le_bytes(reader.take()?) as usize;
+                String::from_utf8(reader.take_slice(len)?.to_vec())?.into()
+            }
+            IdlType::Bytes => {
+                let len = u32::from_le_bytes(reader.take()?) as usize;
+                STANDARD.encode(reader.take_slice(len)?).into()
+            }
+            IdlType::Option(inner) => match reader.take::<1>()?[0] {
+                0 => Value::Null,
+                _ => self.decode_value(inner, reader)?,
+            },
+            IdlType::Vec(inner) => {
+                let len = u32::from_le_bytes(reader.take()?) as usize;
+                let mut items = Vec::with_capacity(len.min(reader.data.len()));
+                for _ in 0..len {
+                    items.push(self.decode_value(inner, reader)?);
+                }
+                Value::Array(items)
+            }
+            IdlType::Array(inner, len) => {
+                let mut items = Vec::with_capacity(*len);
+                for _ in 0..*len {
+                    items.push(self.decode_value(inner, reader)?);
+                }
+                Value::Array(items)
+            }
+            IdlType::Defined(name) => match self.idl.type_def(name) {
+                Some(IdlTypeDef::Struct(fields)) => self.decode_fields(fields, reader)?,
+                Some(IdlTypeDef::Enum(variants)) => {
+                    let variant = reader.take::<1>()?[0] as usize;
+                    variants
+                        .get(variant)
+                        .cloned()
+                        .ok_or_else(|| anyhow::anyhow!("{} has no variant {}", name, variant))?
+                        .into()
+                }
+                None => return Err(anyhow::anyhow!("undefined IDL type: {}", name)),
+            },
+        })
+    }
+}
+
+/// Borsh读取游标
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take_slice(&mut self, len: usize) -> AnyResult<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(anyhow::anyhow!("unexpected end of instruction data"));
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn take<const N: usize>(&mut self) -> AnyResult<[u8; N]> {
+        Ok(self.take_slice(N)?.try_into()?)
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use crate::streaming::event_parser::common::{
+    identity::stable_hash, EventType, IndexSource, InstructionIndex,
+};
+use crate::streaming::event_parser::core::traits::{GenericEventParseConfig, UnifiedEvent};
+
+/// 事件合并键 - 锚定指令 + 事件判别码 + 序号
+///
+/// One on-chain emission is one key: the `emit_cpi!` event and the `Program data:`
+/// log of a swap both anchor to the swap instruction and match the same event
+/// discriminator. The ordinal tells apart several emissions of one discriminator
+/// by the same instruction. It counts per source, so the n-th CPI event and the
+/// n-th log event of an instruction are the same action.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MergeKey {
+    pub instruction: InstructionIndex,
+    /// Hex discriminator of the matched parse config, or the event type for events
+    /// whose parser picks the type after decoding
+    pub discriminator: String,
+    pub ordinal: u32,
+}
+
+impl MergeKey {
+    pub fn new(
+        instruction: InstructionIndex,
+        discriminator: impl Into<String>,
+        ordinal: u32,
+    ) -> Self {
+        Self {
+            instruction: instruction.with_source(IndexSource::Instruction),
+            discriminator: discriminator.into(),
+            ordinal,
+        }
+    }
+}
+
+/// 合并指令事件与其CPI/日志事件，每个链上动作只输出一个事件
+///
+/// `emitted` pairs each `emit_cpi!` or log event with the instruction that emitted
+/// it. One emission can be decoded by several configs sharing its discriminator
+/// (a PumpFun `TradeEvent` is read as both a buy and a sell), and can appear both
+/// as CPI event and as log. All of these are grouped under one [`MergeKey`]; the
+/// group is merged into the instruction event whose type it contains, preferring
+/// the CPI event since logs can be truncated. Groups without an instruction event
+/// are dropped, or emitted as a single event when `emit_unmerged` is set (see
+/// `EventParser::emits_log_events`).
+pub fn merge_events(
+    mut instruction_events: Vec<Box<dyn UnifiedEvent>>,
+    emitted: Vec<(InstructionIndex, Box<dyn UnifiedEvent>)>,
+    inner_instruction_configs: &HashMap<&'static str, Vec<GenericEventParseConfig>>,
+    emit_unmerged: bool,
+) -> Vec<Box<dyn UnifiedEvent>> {
+    let discriminators: HashMap<EventType, &str> = inner_instruction_configs
+        .iter()
+        .filter(|(discriminator, _)| !discriminator.is_empty())
+        .flat_map(|(discriminator, configs)| {
+            configs
+                .iter()
+                .map(|config| (config.event_type.clone(), *discriminator))
+        })
+        .collect();
+
+    // 同一指令、同类型、同来源的事件按出现顺序编号
+    let mut ordinals: HashMap<(InstructionIndex, EventType, IndexSource), u32> = HashMap::new();
+    let mut positions: HashMap<MergeKey, usize> = HashMap::new();
+    let mut groups: Vec<(MergeKey, Vec<Box<dyn UnifiedEvent>>)> = Vec::new();
+    for (emitter, event) in emitted {
+        let emitter = emitter.with_source(IndexSource::Instruction);
+        let event_type = event.event_type();
+        let discriminator = match discriminators.get(&event_type) {
+            Some(discriminator) => discriminator.to_string(),
+            None => event_type.to_string(),
+        };
+        let ordinal = ordinals
+            .entry((emitter, event_type, event.index().source))
+            .or_default();
+        let key = MergeKey::new(emitter, discriminator, *ordinal);
+        *ordinal += 1;
+        match positions.get(&key) {
+            Some(&position) => groups[position].1.push(event),
+            None => {
+                positions.insert(key.clone(), groups.len());
+                groups.push((key, vec![event]));
+            }
+        }
+    }
+
+    let mut unmerged = Vec::new();
+    for (key, mut candidates) in groups {
+        // CPI事件优先于日志事件
+        candidates.sort_by_key(|event| event.index().source);
+        let target = instruction_events.iter_mut().find_map(|instruction_event| {
+            if !instruction_event.index().same_instruction(&key.instruction) {
+                return None;
+            }
+            let event_type = instruction_event.event_type();
+            let candidate = candidates
+                .iter()
+                .find(|event| event.event_type() == event_type)?;
+            Some((instruction_event, candidate))
+        });
+        match target {
+            Some((instruction_event, candidate)) => {
+                instruction_event.merge_from(candidate.as_ref())
+            }
+            None if emit_unmerged => {
+                let Some(mut event) = candidates.into_iter().next() else {
+                    continue;
+                };
+                // 同一指令内的多次发出按序号区分
+                if key.ordinal > 0 {
+                    let metadata = event.metadata_mut();
+                    metadata.id = format!(
+                        "{:016x}",
+                        stable_hash(format!("{}:{}", metadata.id, key.ordinal).as_bytes())
+                    );
+                }
+                unmerged.push(event);
+            }
+            None => {}
+        }
+    }
+    instruction_events.extend(unmerged);
+    instruction_events
+}
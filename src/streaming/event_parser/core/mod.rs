@@ -1,2 +1,22 @@
+pub mod circuit_breaker;
+pub mod error;
+pub mod input;
+pub mod instruction_decoder;
+pub mod merge;
+pub mod post_process;
 pub mod traits;
+pub mod unknown;
+pub use circuit_breaker::{
+    parser_health, protocol_health, record_decode, CircuitBreakerConfig, ParserCircuitBreaker,
+    ParserDegradedEvent, ParserHealthStats, RawInstructionEvent,
+};
+pub use error::{ParseError, ParseOutput};
+pub use input::TransactionInput;
+pub use instruction_decoder::{DecodedInstructionEvent, InstructionDecoder, NamedAccount};
+pub use merge::{merge_events, MergeKey};
+pub use post_process::{
+    BelieveDevTagger, BonkDevTagger, EventPostProcessor, PostProcessContext, PostProcessorChain,
+    PumpFunDevTagger, ReferralTagger,
+};
 pub use traits::{EventParser, UnifiedEvent};
+pub use unknown::UnknownProgramEvent;
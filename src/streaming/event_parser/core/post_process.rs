@@ -0,0 +1,347 @@
+use solana_sdk::pubkey::Pubkey;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::streaming::event_parser::{
+    common::DevAddressRegistry,
+    protocols::{
+        believe::{BelieveCreateTokenEvent, BelieveTradeEvent},
+        bonk::{BonkPoolCreateEvent, BonkTradeEvent},
+        pumpfun::{PumpFunCreateTokenEvent, PumpFunTradeEvent},
+    },
+    UnifiedEvent,
+};
+
+/// 后处理上下文
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PostProcessContext {
+    /// Wallet whose trades are tagged `is_bot`
+    pub bot_wallet: Option<Pubkey>,
+}
+
+/// 事件后处理器 - 在一笔交易的事件解析完成后执行
+///
+/// Receives all events parsed from one transaction, in instruction order, and may
+/// modify, drop or add events. The default chain tags PumpFun, Bonk and Believe
+/// dev and bot trades; replace or extend it per subscription with
+/// [`ParseOptions::with_post_processors`].
+///
+/// [`ParseOptions::with_post_processors`]: crate::streaming::event_parser::common::ParseOptions::with_post_processors
+pub trait EventPostProcessor: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn process(&self, events: &mut Vec<Box<dyn UnifiedEvent>>, context: &PostProcessContext);
+}
+
+/// 按顺序执行的后处理器链
+#[derive(Clone)]
+pub struct PostProcessorChain {
+    processors: Vec<Arc<dyn EventPostProcessor>>,
+}
+
+impl Default for PostProcessorChain {
+    /// PumpFun, Bonk and Believe dev/bot tagging with the global [`DevAddressRegistry`]
+    fn default() -> Self {
+        Self::empty()
+            .with(PumpFunDevTagger::new())
+            .with(BonkDevTagger::new())
+            .with(BelieveDevTagger::new())
+    }
+}
+
+impl PostProcessorChain {
+    /// Chain without any processors
+    pub fn empty() -> Self {
+        Self { processors: vec![] }
+    }
+
+    /// The default chain, recording and looking up creators in `registry` instead of
+    /// the global one, e.g. to keep subscriptions or tests apart
+    pub fn default_with_registry(registry: Arc<DevAddressRegistry>) -> Self {
+        Self::empty()
+            .with(PumpFunDevTagger::new().with_registry(registry.clone()))
+            .with(BonkDevTagger::new().with_registry(registry.clone()))
+            .with(BelieveDevTagger::new().with_registry(registry))
+    }
+
+    /// Append a processor
+    pub fn with<P: EventPostProcessor + 'static>(mut self, processor: P) -> Self {
+        self.processors.push(Arc::new(processor));
+        self
+    }
+
+    /// Remove the processors with the given name
+    pub fn without(mut self, name: &str) -> Self {
+        self.processors.retain(|processor| processor.name() != name);
+        self
+    }
+
+    /// Names of the processors in execution order
+    pub fn names(&self) -> Vec<&str> {
+        self.processors
+            .iter()
+            .map(|processor| processor.name())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.processors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.processors.is_empty()
+    }
+
+    pub fn run(&self, events: &mut Vec<Box<dyn UnifiedEvent>>, context: &PostProcessContext) {
+        for processor in &self.processors {
+            processor.process(events, context);
+        }
+    }
+}
+
+impl fmt::Debug for PostProcessorChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+impl PartialEq for PostProcessorChain {
+    fn eq(&self, other: &Self) -> bool {
+        self.processors.len() == other.processors.len()
+            && self
+                .processors
+                .iter()
+                .zip(&other.processors)
+                .all(|(a, b)| Arc::ptr_eq(a, b))
+    }
+}
+
+impl Eq for PostProcessorChain {}
+
+fn registry_or_global(registry: &Option<Arc<DevAddressRegistry>>) -> &DevAddressRegistry {
+    registry
+        .as_deref()
+        .unwrap_or_else(DevAddressRegistry::global)
+}
+
+/// PumpFun dev/bot标记
+///
+/// Marks trades by the token's creator (seen in the same transaction or recorded
+/// in the registry) with `is_dev_create_token_trade`, and trades by the bot wallet
+/// with `is_bot`.
+#[derive(Default)]
+pub struct PumpFunDevTagger {
+    registry: Option<Arc<DevAddressRegistry>>,
+}
+
+impl PumpFunDevTagger {
+    pub const NAME: &'static str = "pumpfun_dev_tagger";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record and look up creators in `registry` instead of the global one
+    pub fn with_registry(mut self, registry: Arc<DevAddressRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+}
+
+impl EventPostProcessor for PumpFunDevTagger {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn process(&self, events: &mut Vec<Box<dyn UnifiedEvent>>, context: &PostProcessContext) {
+        let mut dev_address = vec![];
+        // 跨交易的dev地址记录
+        let dev_registry = registry_or_global(&self.registry);
+        for event in events.iter_mut() {
+            if let Some(token_info) = event.as_any().downcast_ref::<PumpFunCreateTokenEvent>() {
+                dev_address.push(token_info.user);
+                dev_registry.record(token_info.mint, token_info.user);
+                if token_info.creator != Pubkey::default() && token_info.creator != token_info.user
+                {
+                    dev_address.push(token_info.creator);
+                    dev_registry.record(token_info.mint, token_info.creator);
+                }
+            } else if let Some(trade_info) = event.as_any_mut().downcast_mut::<PumpFunTradeEvent>()
+            {
+                if dev_address.contains(&trade_info.user)
+                    || dev_address.contains(&trade_info.creator)
+                    || dev_registry.is_dev(&trade_info.mint, &trade_info.user)
+                {
+                    trade_info.is_dev_create_token_trade = true;
+                } else if Some(trade_info.user) == context.bot_wallet {
+                    trade_info.is_bot = true;
+                } else {
+                    trade_info.is_dev_create_token_trade = false;
+                }
+            }
+        }
+    }
+}
+
+/// Bonk dev/bot标记
+///
+/// Marks trades by the pool's creator (seen in the same transaction or recorded in
+/// the registry) with `is_dev_create_token_trade`, and trades by the bot wallet
+/// with `is_bot`.
+#[derive(Default)]
+pub struct BonkDevTagger {
+    registry: Option<Arc<DevAddressRegistry>>,
+}
+
+impl BonkDevTagger {
+    pub const NAME: &'static str = "bonk_dev_tagger";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record and look up creators in `registry` instead of the global one
+    pub fn with_registry(mut self, registry: Arc<DevAddressRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+}
+
+impl EventPostProcessor for BonkDevTagger {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn process(&self, events: &mut Vec<Box<dyn UnifiedEvent>>, context: &PostProcessContext) {
+        let mut bonk_dev_address = None;
+        let dev_registry = registry_or_global(&self.registry);
+        for event in events.iter_mut() {
+            if let Some(pool_info) = event.as_any().downcast_ref::<BonkPoolCreateEvent>() {
+                bonk_dev_address = Some(pool_info.creator);
+                dev_registry.record(pool_info.pool_state, pool_info.creator);
+            } else if let Some(trade_info) = event.as_any_mut().downcast_mut::<BonkTradeEvent>() {
+                if Some(trade_info.payer) == bonk_dev_address
+                    || dev_registry.is_dev(&trade_info.pool_state, &trade_info.payer)
+                {
+                    trade_info.is_dev_create_token_trade = true;
+                } else if Some(trade_info.payer) == context.bot_wallet {
+                    trade_info.is_bot = true;
+                } else {
+                    trade_info.is_dev_create_token_trade = false;
+                }
+            }
+        }
+    }
+}
+
+/// Believe创建者归属与dev/bot标记
+///
+/// Sets `creator` on trades of tokens whose create was seen in the same
+/// transaction or recorded in the registry, marks trades by the creator with
+/// `is_dev_create_token_trade` and trades by the bot wallet with `is_bot`.
+#[derive(Default)]
+pub struct BelieveDevTagger {
+    registry: Option<Arc<DevAddressRegistry>>,
+}
+
+impl BelieveDevTagger {
+    pub const NAME: &'static str = "believe_dev_tagger";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record and look up creators in `registry` instead of the global one
+    pub fn with_registry(mut self, registry: Arc<DevAddressRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+}
+
+impl EventPostProcessor for BelieveDevTagger {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn process(&self, events: &mut Vec<Box<dyn UnifiedEvent>>, context: &PostProcessContext) {
+        let mut creators = vec![];
+        let dev_registry = registry_or_global(&self.registry);
+        for event in events.iter_mut() {
+            if let Some(token_info) = event.as_any().downcast_ref::<BelieveCreateTokenEvent>() {
+                creators.push((token_info.base_mint, token_info.creator));
+                dev_registry.record(token_info.base_mint, token_info.creator);
+            } else if let Some(trade_info) = event.as_any_mut().downcast_mut::<BelieveTradeEvent>()
+            {
+                let creator = creators
+                    .iter()
+                    .find(|(mint, _)| *mint == trade_info.base_mint)
+                    .map(|(_, creator)| *creator)
+                    .or_else(|| {
+                        dev_registry
+                            .dev_addresses(&trade_info.base_mint)
+                            .first()
+                            .copied()
+                    });
+                if let Some(creator) = creator {
+                    trade_info.creator = creator;
+                }
+                if creator == Some(trade_info.payer) {
+                    trade_info.is_dev_create_token_trade = true;
+                } else if Some(trade_info.payer) == context.bot_wallet {
+                    trade_info.is_bot = true;
+                } else {
+                    trade_info.is_dev_create_token_trade = false;
+                }
+            }
+        }
+    }
+}
+
+/// 基于memo的前端归因
+///
+/// Sets `metadata.referral` to the frontend of the first memo that starts with one
+/// of the configured prefixes. Not part of the default chain; add it with
+/// [`PostProcessorChain::with`].
+#[derive(Debug, Clone, Default)]
+pub struct ReferralTagger {
+    prefixes: Vec<(String, String)>,
+}
+
+impl ReferralTagger {
+    pub const NAME: &'static str = "referral_tagger";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attribute memos starting with `prefix` to `frontend`; earlier rules win
+    pub fn with_prefix(mut self, prefix: impl Into<String>, frontend: impl Into<String>) -> Self {
+        self.prefixes.push((prefix.into(), frontend.into()));
+        self
+    }
+
+    /// Frontend of the first memo matching a rule
+    pub fn frontend(&self, memos: &[String]) -> Option<&str> {
+        memos.iter().find_map(|memo| {
+            self.prefixes
+                .iter()
+                .find(|(prefix, _)| memo.starts_with(prefix.as_str()))
+                .map(|(_, frontend)| frontend.as_str())
+        })
+    }
+}
+
+impl EventPostProcessor for ReferralTagger {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn process(&self, events: &mut Vec<Box<dyn UnifiedEvent>>, _context: &PostProcessContext) {
+        for event in events.iter_mut() {
+            let metadata = event.metadata_mut();
+            if let Some(frontend) = self.frontend(&metadata.memos) {
+                metadata.referral = Some(frontend.to_string());
+            }
+        }
+    }
+}
@@ -7,17 +7,23 @@ use solana_transaction_status::{
     EncodedTransactionWithStatusMeta, UiCompiledInstruction, UiInnerInstructions, UiInstruction,
 };
 use std::fmt::Debug;
-use std::{collections::HashMap, str::FromStr};
+use std::collections::HashMap;
 
 use crate::streaming::event_parser::common::{
     parse_transfer_datas_from_next_instructions, TransferData,
 };
-use crate::streaming::event_parser::{
-    common::{utils::*, EventMetadata, EventType, ProtocolType},
-    protocols::{
-        bonk::{BonkPoolCreateEvent, BonkTradeEvent},
-        pumpfun::{PumpFunCreateTokenEvent, PumpFunTradeEvent},
-    },
+use crate::streaming::event_parser::core::circuit_breaker::{record_decode, RawTransaction};
+use crate::streaming::event_parser::core::error::{ParseError, ParseOutput};
+use crate::streaming::event_parser::core::input::TransactionInput;
+use crate::streaming::event_parser::core::instruction_decoder::InstructionDecoder;
+use crate::streaming::event_parser::core::merge::merge_events;
+use crate::streaming::event_parser::core::unknown::{unknown_discriminator, UnknownProgramEvent};
+use crate::streaming::event_parser::core::post_process::{PostProcessContext, PostProcessorChain};
+use crate::streaming::event_parser::common::{
+    codec, compile_inner_instructions, enrich_transfer_datas, extract_memos,
+    relevant_token_balance_changes, routed_via, token_account_mints, token_balance_changes,
+    utils::*, ClockHandle, EventMetadata, EventType, IndexSource, InstructionIndex, ParseOptions,
+    ProtocolType, RawInstruction,
 };
 
 /// Unified Event Interface - All protocol events must implement this trait
@@ -52,16 +58,37 @@ pub trait UnifiedEvent: Debug + Send + Sync {
     /// Clone the event
     fn clone_boxed(&self) -> Box<dyn UnifiedEvent>;
 
-    /// Merge events (optional implementation)
-    fn merge(&mut self, _other: Box<dyn UnifiedEvent>) {
+    /// Merge fields from another event of the same type without taking ownership
+    /// (optional implementation)
+    fn merge_from(&mut self, _other: &dyn UnifiedEvent) {
         // Default implementation: no merging operation
     }
 
+    /// Merge events
+    fn merge(&mut self, other: Box<dyn UnifiedEvent>) {
+        self.merge_from(other.as_ref());
+    }
+
     /// Set transfer datas
     fn set_transfer_datas(&mut self, transfer_datas: Vec<TransferData>);
 
-    /// Get index
-    fn index(&self) -> String;
+    /// Position of the event in the transaction
+    fn index(&self) -> InstructionIndex;
+
+    /// Get event metadata
+    fn metadata(&self) -> &EventMetadata;
+
+    /// Get mutable event metadata
+    fn metadata_mut(&mut self) -> &mut EventMetadata;
+
+    /// Serialize the full event to JSON
+    fn to_json(&self) -> String;
+
+    /// Stable hash of the event content, ignoring processing time fields
+    ///
+    /// FNV-1a of the JSON serialization, so it is the same across processes,
+    /// machines and Rust versions and can be persisted.
+    fn content_hash(&self) -> u64;
 }
 
 /// 事件解析器trait - 定义了事件解析的核心方法
@@ -75,7 +102,7 @@ pub trait EventParser: Send + Sync {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Vec<Box<dyn UnifiedEvent>>;
 
     /// 从指令中解析事件数据
@@ -87,10 +114,13 @@ pub trait EventParser: Send + Sync {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Vec<Box<dyn UnifiedEvent>>;
 
     /// 从VersionedTransaction中解析指令事件的通用方法
+    ///
+    /// Outer instructions that fail to parse are reported in the output errors
+    /// under their instruction index.
     async fn parse_instruction_events_from_versioned_transaction(
         &self,
         versioned_tx: &VersionedTransaction,
@@ -100,8 +130,9 @@ pub trait EventParser: Send + Sync {
         program_received_time_ms: i64,
         accounts: &[Pubkey],
         inner_instructions: &[UiInnerInstructions],
-    ) -> Result<Vec<Box<dyn UnifiedEvent>>> {
+    ) -> ParseOutput {
         let mut instruction_events = Vec::new();
+        let mut errors = Vec::new();
         // 获取交易的指令和账户
         let compiled_instructions = versioned_tx.message.instructions();
         let mut accounts: Vec<Pubkey> = accounts.to_vec();
@@ -120,7 +151,8 @@ pub trait EventParser: Send + Sync {
                                 accounts.push(Pubkey::default());
                             }
                         }
-                        if let Ok(mut events) = self
+                        let instruction_index = InstructionIndex::outer(index as u16);
+                        let mut events = match self
                             .parse_instruction(
                                 instruction,
                                 &accounts,
@@ -128,35 +160,45 @@ pub trait EventParser: Send + Sync {
                                 slot,
                                 block_time,
                                 program_received_time_ms,
-                                format!("{}", index),
+                                instruction_index,
                             )
                             .await
                         {
-                            if events.len() > 0 {
-                                if let Some(inn) =
-                                    inner_instructions.iter().find(|inner_instruction| {
-                                        inner_instruction.index == index as u8
-                                    })
-                                {
-                                    events.iter_mut().for_each(|event| {
-                                        let transfer_datas =
-                                            parse_transfer_datas_from_next_instructions(
-                                                &inn,
-                                                -1 as i8,
-                                                &accounts,
-                                                event.event_type(),
-                                            );
-                                        event.set_transfer_datas(transfer_datas.clone());
-                                    });
-                                }
-                                instruction_events.extend(events);
+                            Ok(events) => events,
+                            Err(e) => {
+                                errors.push(ParseError::Instruction {
+                                    index: instruction_index.to_string(),
+                                    reason: e.to_string(),
+                                });
+                                continue;
                             }
+                        };
+                        if events.len() > 0 {
+                            if let Some(inn) = inner_instructions
+                                .iter()
+                                .find(|inner_instruction| inner_instruction.index == index as u8)
+                            {
+                                events.iter_mut().for_each(|event| {
+                                    let transfer_datas =
+                                        parse_transfer_datas_from_next_instructions(
+                                            &inn,
+                                            -1 as i8,
+                                            &accounts,
+                                            event.event_type(),
+                                        );
+                                    event.set_transfer_datas(transfer_datas);
+                                });
+                            }
+                            instruction_events.extend(events);
                         }
                     }
                 }
             }
         }
-        Ok(instruction_events)
+        ParseOutput {
+            events: instruction_events,
+            errors,
+        }
     }
 
     async fn parse_versioned_transaction(
@@ -169,19 +211,54 @@ pub trait EventParser: Send + Sync {
         bot_wallet: Option<Pubkey>,
     ) -> Result<Vec<Box<dyn UnifiedEvent>>> {
         let accounts: Vec<Pubkey> = versioned_tx.message.static_account_keys().to_vec();
-        let events = self
-            .parse_instruction_events_from_versioned_transaction(
+        let output = self
+            .parse_versioned_transaction_with_accounts(
                 versioned_tx,
+                &accounts,
                 signature,
                 slot,
                 block_time,
                 program_received_time_ms,
-                &accounts,
-                &vec![],
+                bot_wallet,
+                ParseOptions::default(),
             )
-            .await
-            .unwrap_or_else(|_e| vec![]);
-        Ok(self.process_events(events, bot_wallet))
+            .await?;
+        for error in &output.errors {
+            log::debug!("Partial parse of {}: {}", signature, error);
+        }
+        Ok(output.events)
+    }
+
+    /// 使用完整账户列表解析VersionedTransaction
+    ///
+    /// `accounts` must include the addresses loaded from lookup tables (see
+    /// `AltResolver`), otherwise instructions referencing them get wrong accounts.
+    /// `options` apply as in [`EventParser::parse_transaction_with_errors`]; the
+    /// transaction has no status meta, so it counts as successful and
+    /// [`FailedTxPolicy::OnlyFailed`] yields no events.
+    ///
+    /// [`FailedTxPolicy::OnlyFailed`]: crate::streaming::event_parser::common::FailedTxPolicy::OnlyFailed
+    async fn parse_versioned_transaction_with_accounts(
+        &self,
+        versioned_tx: &VersionedTransaction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: Option<u64>,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        bot_wallet: Option<Pubkey>,
+        options: ParseOptions,
+    ) -> std::result::Result<ParseOutput, ParseError> {
+        self.parse_transaction_input(
+            TransactionInput::from_versioned(versioned_tx.clone(), accounts),
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            bot_wallet,
+            options,
+        )
+        .await
     }
 
     async fn parse_transaction(
@@ -193,37 +270,131 @@ pub trait EventParser: Send + Sync {
         program_received_time_ms: i64,
         bot_wallet: Option<Pubkey>,
     ) -> Result<Vec<Box<dyn UnifiedEvent>>> {
-        let transaction = tx.transaction;
+        let output = self
+            .parse_transaction_with_errors(
+                tx,
+                signature,
+                slot,
+                block_time,
+                program_received_time_ms,
+                bot_wallet,
+                ParseOptions::default(),
+            )
+            .await?;
+        for error in &output.errors {
+            log::debug!("Partial parse of {}: {}", signature, error);
+        }
+        Ok(output.events)
+    }
+
+    /// 解析交易，返回成功解析的事件以及每条指令的错误
+    ///
+    /// Never panics on pruned or legacy-encoded transactions: missing inner
+    /// instructions or loaded addresses are treated as empty, and instructions that
+    /// fail to decode are reported in `errors` while the rest are still parsed.
+    /// Failed transactions are handled according to `options.failed_tx_policy`; parsed
+    /// events of a failed transaction have `transaction_failed` set in their metadata.
+    async fn parse_transaction_with_errors(
+        &self,
+        tx: EncodedTransactionWithStatusMeta,
+        signature: &str,
+        slot: Option<u64>,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        bot_wallet: Option<Pubkey>,
+        options: ParseOptions,
+    ) -> std::result::Result<ParseOutput, ParseError> {
         // 检查交易元数据
-        let meta = tx
-            .meta
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Missing transaction metadata"))?;
-
-        let mut address_table_lookups: Vec<Pubkey> = vec![];
-        let mut inner_instructions: Vec<UiInnerInstructions> = vec![];
-        if meta.err.is_none() {
-            inner_instructions = meta.inner_instructions.as_ref().unwrap().clone();
-            let loaded_addresses = meta.loaded_addresses.as_ref().unwrap();
-            for lookup in &loaded_addresses.writable {
-                address_table_lookups.push(Pubkey::from_str(lookup).unwrap());
-            }
-            for lookup in &loaded_addresses.readonly {
-                address_table_lookups.push(Pubkey::from_str(lookup).unwrap());
-            }
+        let meta = tx.meta.as_ref().ok_or(ParseError::MissingMeta)?;
+        if !options.failed_tx_policy.accepts(meta.err.is_some()) {
+            return Ok(ParseOutput::default());
+        }
+        self.parse_transaction_input(
+            TransactionInput::from_encoded(tx)?,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            bot_wallet,
+            options,
+        )
+        .await
+    }
+
+    /// 直接解析yellowstone gRPC交易更新
+    ///
+    /// Same as [`EventParser::parse_transaction_with_errors`] but reads the protobuf
+    /// types as they come off the stream instead of an RPC-encoded transaction, and
+    /// sets `tx_index` from the update. `created_at` is the update's timestamp.
+    #[cfg(feature = "grpc")]
+    async fn parse_grpc_transaction(
+        &self,
+        update: yellowstone_grpc_proto::geyser::SubscribeUpdateTransactionInfo,
+        slot: u64,
+        created_at: Option<Timestamp>,
+        program_received_time_ms: i64,
+        bot_wallet: Option<Pubkey>,
+        options: ParseOptions,
+    ) -> std::result::Result<ParseOutput, ParseError> {
+        let signature = bs58::encode(&update.signature).into_string();
+        let tx_index = update.index;
+        let mut output = self
+            .parse_transaction_input(
+                TransactionInput::from_grpc(update)?,
+                &signature,
+                Some(slot),
+                created_at,
+                program_received_time_ms,
+                bot_wallet,
+                options,
+            )
+            .await?;
+        for event in &mut output.events {
+            event.metadata_mut().tx_index = Some(tx_index);
+        }
+        Ok(output)
+    }
+
+    /// 解析已解码的交易
+    ///
+    /// The encoding-independent core of the `parse_*transaction*` entry points.
+    async fn parse_transaction_input(
+        &self,
+        input: TransactionInput,
+        signature: &str,
+        slot: Option<u64>,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        bot_wallet: Option<Pubkey>,
+        options: ParseOptions,
+    ) -> std::result::Result<ParseOutput, ParseError> {
+        let TransactionInput {
+            transaction: versioned_tx,
+            loaded_addresses: address_table_lookups,
+            mut inner_instructions,
+            log_messages,
+            pre_token_balances,
+            post_token_balances,
+            fee,
+            error: transaction_error,
+            mut errors,
+        } = input;
+        if !options.failed_tx_policy.accepts(transaction_error.is_some()) {
+            return Ok(ParseOutput::default());
         }
         let mut accounts: Vec<Pubkey> = vec![];
 
         let mut instruction_events = Vec::new();
 
         // 解析指令事件
-        if let Some(versioned_tx) = transaction.decode() {
+        if let Some(versioned_tx) = &versioned_tx {
             accounts = versioned_tx.message.static_account_keys().to_vec();
             accounts.extend(address_table_lookups.clone());
+            compile_inner_instructions(&mut inner_instructions, &accounts);
 
-            instruction_events = self
+            let output = self
                 .parse_instruction_events_from_versioned_transaction(
-                    &versioned_tx,
+                    versioned_tx,
                     signature,
                     slot,
                     block_time,
@@ -231,38 +402,53 @@ pub trait EventParser: Send + Sync {
                     &accounts,
                     &inner_instructions,
                 )
-                .await
-                .unwrap_or_else(|_e| vec![]);
+                .await;
+            instruction_events = output.events;
+            errors.extend(output.errors);
         } else {
+            errors.push(ParseError::UndecodableTransaction);
             accounts.extend(address_table_lookups.clone());
         }
 
         // Parse inner instruction events
-        let mut inner_instruction_events = Vec::new();
-        // Check if transaction was successful
-        if meta.err.is_none() {
-            for inner_instruction in &inner_instructions {
-                for (index, instruction) in inner_instruction.instructions.iter().enumerate() {
-                    match instruction {
-                        UiInstruction::Compiled(compiled) => {
-                            // 解析嵌套指令
-                            let compiled_instruction = CompiledInstruction {
-                                program_id_index: compiled.program_id_index,
-                                accounts: compiled.accounts.clone(),
-                                data: bs58::decode(compiled.data.clone()).into_vec().unwrap(),
-                            };
-                            if let Ok(mut events) = self
-                                .parse_instruction(
-                                    &compiled_instruction,
-                                    &accounts,
-                                    signature,
-                                    slot,
-                                    block_time,
-                                    program_received_time_ms,
-                                    format!("{}.{}", inner_instruction.index, index),
-                                )
-                                .await
-                            {
+        // (emitting instruction, event) pairs
+        let mut inner_instruction_events: Vec<(InstructionIndex, Box<dyn UnifiedEvent>)> =
+            Vec::new();
+        for inner_instruction in &inner_instructions {
+            for (index, instruction) in inner_instruction.instructions.iter().enumerate() {
+                match instruction {
+                    UiInstruction::Compiled(compiled) => {
+                        let instruction_index =
+                            InstructionIndex::inner(inner_instruction.index as u16, index as u16);
+                        // 解析嵌套指令
+                        let data = match codec::base58_decode(&compiled.data) {
+                            Ok(data) => data,
+                            Err(e) => {
+                                errors.push(ParseError::InvalidInstructionData {
+                                    index: instruction_index.to_string(),
+                                    reason: e.to_string(),
+                                });
+                                continue;
+                            }
+                        };
+                        let compiled_instruction = CompiledInstruction {
+                            program_id_index: compiled.program_id_index,
+                            accounts: compiled.accounts.clone(),
+                            data,
+                        };
+                        match self
+                            .parse_instruction(
+                                &compiled_instruction,
+                                &accounts,
+                                signature,
+                                slot,
+                                block_time,
+                                program_received_time_ms,
+                                instruction_index,
+                            )
+                            .await
+                        {
+                            Ok(mut events) => {
                                 if events.len() > 0 {
                                     events.iter_mut().for_each(|event| {
                                         let transfer_datas =
@@ -272,23 +458,30 @@ pub trait EventParser: Send + Sync {
                                                 &accounts,
                                                 event.event_type(),
                                             );
-                                        event.set_transfer_datas(transfer_datas.clone());
+                                        event.set_transfer_datas(transfer_datas);
                                     });
                                     instruction_events.extend(events);
                                 }
                             }
-                            if let Ok(mut events) = self
-                                .parse_inner_instruction(
-                                    compiled,
-                                    signature,
-                                    slot,
-                                    block_time,
-                                    program_received_time_ms,
-                                    format!("{}.{}", inner_instruction.index, index),
-                                )
-                                .await
-                            {
+                            Err(e) => errors.push(ParseError::Instruction {
+                                index: instruction_index.to_string(),
+                                reason: e.to_string(),
+                            }),
+                        }
+                        match self
+                            .parse_inner_instruction(
+                                compiled,
+                                signature,
+                                slot,
+                                block_time,
+                                program_received_time_ms,
+                                instruction_index,
+                            )
+                            .await
+                        {
+                            Ok(mut events) => {
                                 if events.len() > 0 {
+                                    let invoker = invoking_instruction_index(inner_instruction, index);
                                     events.iter_mut().for_each(|event| {
                                         let transfer_datas =
                                             parse_transfer_datas_from_next_instructions(
@@ -297,22 +490,31 @@ pub trait EventParser: Send + Sync {
                                                 &accounts,
                                                 event.event_type(),
                                             );
-                                        event.set_transfer_datas(transfer_datas.clone());
+                                        event.set_transfer_datas(transfer_datas);
+                                        let metadata = event.metadata_mut();
+                                        metadata.index = instruction_index
+                                            .with_source(IndexSource::InnerInstruction);
+                                        metadata.set_instruction_id(&invoker);
                                     });
-                                    inner_instruction_events.extend(events);
+                                    inner_instruction_events
+                                        .extend(events.into_iter().map(|event| (invoker, event)));
                                 }
                             }
+                            Err(e) => errors.push(ParseError::Instruction {
+                                index: instruction_index.to_string(),
+                                reason: e.to_string(),
+                            }),
                         }
-                        _ => {}
                     }
+                    _ => {}
                 }
             }
         }
 
         // Parse events from transaction logs
         let mut log_events = Vec::new();
-        if let solana_transaction_status::option_serializer::OptionSerializer::Some(log_messages) = &meta.log_messages {
-            log_events = self
+        if let Some(log_messages) = &log_messages {
+            match self
                 .parse_events_from_logs(
                     log_messages,
                     signature,
@@ -321,100 +523,278 @@ pub trait EventParser: Send + Sync {
                     &inner_instructions,
                 )
                 .await
-                .unwrap_or_else(|_e| vec![]);
+            {
+                Ok(events) => log_events = events,
+                Err(e) => errors.push(ParseError::Logs { reason: e.to_string() }),
+            }
         }
 
-        // Merge log events with inner instruction events
-        inner_instruction_events.extend(log_events);
-
-        if instruction_events.len() > 0 && inner_instruction_events.len() > 0 {
-            for instruction_event in &mut instruction_events {
-                for inner_instruction_event in &inner_instruction_events {
-                    if instruction_event.id() == inner_instruction_event.id() {
-                        let i_index = instruction_event.index();
-                        let in_index = inner_instruction_event.index();
-                        
-                        // Handle log events specially - they should merge with matching ID
-                        if in_index == "log" {
-                            instruction_event.merge(inner_instruction_event.clone_boxed());
-                            continue; // Don't break, might have multiple matches
-                        }
-                        
-                        if !i_index.contains(".") && in_index.contains(".") {
-                            let in_index_parent_index = in_index.split(".").nth(0).unwrap();
-                            if in_index_parent_index == i_index {
-                                instruction_event.merge(inner_instruction_event.clone_boxed());
-                                break;
-                            }
-                        } else if i_index.contains(".") && in_index.contains(".") {
-                            // 嵌套指令
-                            let i_index_parent_index = i_index.split(".").nth(0).unwrap();
-                            let in_index_parent_index = in_index.split(".").nth(0).unwrap();
-                            if i_index_parent_index == in_index_parent_index {
-                                let i_index_child_index = i_index
-                                    .split(".")
-                                    .nth(1)
-                                    .unwrap()
-                                    .parse::<u32>()
-                                    .unwrap_or(0);
-                                let in_index_child_index = in_index
-                                    .split(".")
-                                    .nth(1)
-                                    .unwrap()
-                                    .parse::<u32>()
-                                    .unwrap_or(0);
-                                if in_index_child_index > i_index_child_index {
-                                    instruction_event.merge(inner_instruction_event.clone_boxed());
-                                    break;
-                                }
-                            }
-                        }
-                    }
+        // Log events carry the position of the instruction that emitted them
+        inner_instruction_events.extend(log_events.into_iter().map(|event| (event.index(), event)));
+        instruction_events = merge_events(
+            instruction_events,
+            inner_instruction_events,
+            self.get_inner_instruction_configs(),
+            self.emits_log_events(),
+        );
+        if options.unknown_instructions {
+            if let Some(versioned_tx) = &versioned_tx {
+                instruction_events.extend(self.capture_unknown_instructions(
+                    versioned_tx,
+                    &inner_instructions,
+                    &accounts,
+                    signature,
+                    slot.unwrap_or(0),
+                    block_time,
+                    program_received_time_ms,
+                ));
+            }
+        }
+        if options.decoded_instructions {
+            if let Some(versioned_tx) = &versioned_tx {
+                instruction_events.extend(self.decode_instructions(
+                    versioned_tx,
+                    &inner_instructions,
+                    &accounts,
+                    signature,
+                    slot.unwrap_or(0),
+                    block_time,
+                    program_received_time_ms,
+                ));
+            }
+        }
+        let instructions =
+            versioned_tx.as_ref().map(|tx| tx.message.instructions()).unwrap_or_default();
+        if let Some(breaker) = &options.circuit_breaker {
+            let timestamp = block_time.unwrap_or(Timestamp { seconds: 0, nanos: 0 });
+            let template = EventMetadata::new(
+                signature.to_string(),
+                signature.to_string(),
+                slot.unwrap_or(0),
+                timestamp.seconds,
+                timestamp.seconds * 1000 + (timestamp.nanos as i64) / 1_000_000,
+                self.get_protocol_type(),
+                EventType::RawInstruction,
+                self.get_program_id(),
+                InstructionIndex::default(),
+                program_received_time_ms,
+            );
+            instruction_events = breaker.apply(
+                &self.get_protocol_type(),
+                &self.supported_program_ids(),
+                instruction_events,
+                &template,
+                options.clock.now_ms(),
+                RawTransaction {
+                    instructions,
+                    inner_instructions: &inner_instructions,
+                    accounts: &accounts,
+                },
+            );
+        }
+        if transaction_error.is_some() {
+            for event in &mut instruction_events {
+                let metadata = event.metadata_mut();
+                metadata.transaction_failed = true;
+                metadata.transaction_error = transaction_error.clone();
+            }
+        }
+        let signers = versioned_tx
+            .as_ref()
+            .map(|tx| {
+                let keys = tx.message.static_account_keys();
+                let count = (tx.message.header().num_required_signatures as usize).min(keys.len());
+                keys[..count].to_vec()
+            })
+            .unwrap_or_default();
+        let memos = versioned_tx
+            .as_ref()
+            .map(|tx| extract_memos(tx.message.instructions(), &inner_instructions, &accounts))
+            .unwrap_or_default();
+        let mints = token_account_mints(&pre_token_balances, &post_token_balances, &accounts);
+        let balance_changes =
+            token_balance_changes(&pre_token_balances, &post_token_balances, &accounts);
+        for event in &mut instruction_events {
+            let metadata = event.metadata_mut();
+            enrich_transfer_datas(&mut metadata.transfer_datas, &mints);
+            metadata.fee = fee;
+            metadata.signers = signers.clone();
+            metadata.memos = memos.clone();
+            metadata.caller_program_ids = caller_program_ids(
+                instructions,
+                &inner_instructions,
+                &accounts,
+                &metadata.index,
+            );
+            metadata.routed_via = routed_via(&metadata.caller_program_ids);
+            if !balance_changes.is_empty() {
+                if let Some(raw) = RawInstruction::at_index(
+                    &metadata.index,
+                    instructions,
+                    &inner_instructions,
+                    &accounts,
+                ) {
+                    metadata.token_balance_changes =
+                        relevant_token_balance_changes(&balance_changes, &raw.accounts);
                 }
             }
         }
-        Ok(self.process_events(instruction_events, bot_wallet))
+        if options.include_raw {
+            for event in &mut instruction_events {
+                let raw = RawInstruction::at_index(
+                    &event.index(),
+                    instructions,
+                    &inner_instructions,
+                    &accounts,
+                );
+                event.metadata_mut().raw = raw;
+            }
+        }
+        Ok(ParseOutput {
+            events: self.process_events(
+                instruction_events,
+                bot_wallet,
+                &options.post_processors,
+                &options.clock,
+            ),
+            errors,
+        })
     }
 
+    /// 按IDL解码交易中属于本协议的全部指令
+    ///
+    /// Covers top-level and inner instructions; returns nothing when the parser has
+    /// no [`InstructionDecoder`].
+    fn decode_instructions(
+        &self,
+        versioned_tx: &VersionedTransaction,
+        inner_instructions: &[UiInnerInstructions],
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        let Some(decoder) = self.instruction_decoder() else {
+            return Vec::new();
+        };
+        let instructions = transaction_instructions(versioned_tx, inner_instructions);
+
+        let timestamp = block_time.unwrap_or(Timestamp { seconds: 0, nanos: 0 });
+        let block_time_ms = timestamp.seconds * 1000 + (timestamp.nanos as i64) / 1_000_000;
+        let mut events: Vec<Box<dyn UnifiedEvent>> = Vec::new();
+        for (index, instruction) in instructions {
+            if accounts.get(instruction.program_id_index as usize) != Some(&decoder.program_id()) {
+                continue;
+            }
+            if !validate_account_indices(&instruction.accounts, accounts.len()) {
+                continue;
+            }
+            let account_pubkeys: Vec<Pubkey> =
+                instruction.accounts.iter().map(|&idx| accounts[idx as usize]).collect();
+            let mut metadata = EventMetadata::new(
+                signature.to_string(),
+                signature.to_string(),
+                slot,
+                timestamp.seconds,
+                block_time_ms,
+                decoder.protocol_type(),
+                EventType::DecodedInstruction,
+                decoder.program_id(),
+                index,
+                program_received_time_ms,
+            );
+            metadata.set_instruction_id(&index);
+            if let Some(event) = decoder.decode(&instruction.data, &account_pubkeys, metadata) {
+                events.push(Box::new(event));
+            }
+        }
+        events
+    }
+
+    /// 输出本协议程序中不匹配任何鉴别器的指令
+    ///
+    /// Covers top-level and inner instructions of `supported_program_ids`; inner
+    /// instructions matching a configured event discriminator are events, not
+    /// unknown instructions.
+    fn capture_unknown_instructions(
+        &self,
+        versioned_tx: &VersionedTransaction,
+        inner_instructions: &[UiInnerInstructions],
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        let configs = self.get_instruction_configs();
+        let Some(discriminator_len) = configs.keys().map(Vec::len).max() else {
+            return Vec::new();
+        };
+        let program_ids = self.supported_program_ids();
+        let message_instructions = versioned_tx.message.instructions();
+        let timestamp = block_time.unwrap_or(Timestamp { seconds: 0, nanos: 0 });
+        let block_time_ms = timestamp.seconds * 1000 + (timestamp.nanos as i64) / 1_000_000;
+        let mut events: Vec<Box<dyn UnifiedEvent>> = Vec::new();
+        for (index, instruction) in transaction_instructions(versioned_tx, inner_instructions) {
+            let Some(program_id) = accounts.get(instruction.program_id_index as usize) else {
+                continue;
+            };
+            if !program_ids.contains(program_id) {
+                continue;
+            }
+            let data = &instruction.data;
+            if configs.keys().any(|disc| data.starts_with(disc)) {
+                continue;
+            }
+            if index.inner.is_some()
+                && self
+                    .get_inner_instruction_configs()
+                    .keys()
+                    .any(|disc| !disc.is_empty() && discriminator_hex_matches(data, disc))
+            {
+                continue;
+            }
+            let Some(raw) =
+                RawInstruction::at_index(&index, message_instructions, inner_instructions, accounts)
+            else {
+                continue;
+            };
+            let mut metadata = EventMetadata::new(
+                signature.to_string(),
+                signature.to_string(),
+                slot,
+                timestamp.seconds,
+                block_time_ms,
+                self.get_protocol_type(),
+                EventType::UnknownProgram,
+                *program_id,
+                index,
+                program_received_time_ms,
+            );
+            metadata.set_instruction_id(&index);
+            events.push(Box::new(UnknownProgramEvent {
+                metadata,
+                discriminator: unknown_discriminator(data, discriminator_len),
+                instruction: raw,
+            }));
+        }
+        events
+    }
+
+    /// 执行后处理器链并记录处理耗时
     fn process_events(
         &self,
         mut events: Vec<Box<dyn UnifiedEvent>>,
         bot_wallet: Option<Pubkey>,
+        post_processors: &PostProcessorChain,
+        clock: &ClockHandle,
     ) -> Vec<Box<dyn UnifiedEvent>> {
-        let mut dev_address = vec![];
-        let mut bonk_dev_address = None;
+        post_processors.run(&mut events, &PostProcessContext { bot_wallet });
+        let now = clock.now_ms();
         for event in &mut events {
-            if let Some(token_info) = event.as_any().downcast_ref::<PumpFunCreateTokenEvent>() {
-                dev_address.push(token_info.user);
-                if token_info.creator != Pubkey::default() && token_info.creator != token_info.user
-                {
-                    dev_address.push(token_info.creator);
-                }
-            } else if let Some(trade_info) = event.as_any_mut().downcast_mut::<PumpFunTradeEvent>()
-            {
-                if dev_address.contains(&trade_info.user)
-                    || dev_address.contains(&trade_info.creator)
-                {
-                    trade_info.is_dev_create_token_trade = true;
-                } else if Some(trade_info.user) == bot_wallet {
-                    trade_info.is_bot = true;
-                } else {
-                    trade_info.is_dev_create_token_trade = false;
-                }
-            }
-            if let Some(pool_info) = event.as_any().downcast_ref::<BonkPoolCreateEvent>() {
-                bonk_dev_address = Some(pool_info.creator);
-            } else if let Some(trade_info) = event.as_any_mut().downcast_mut::<BonkTradeEvent>() {
-                if Some(trade_info.payer) == bonk_dev_address {
-                    trade_info.is_dev_create_token_trade = true;
-                } else if Some(trade_info.payer) == bot_wallet {
-                    trade_info.is_bot = true;
-                } else {
-                    trade_info.is_dev_create_token_trade = false;
-                }
-            }
-            let now = chrono::Utc::now().timestamp_millis();
             event.set_program_handle_time_consuming_ms(now - event.program_received_time_ms());
+            event.metadata_mut().parse_end_time_ms = now;
         }
         events
     }
@@ -426,7 +806,7 @@ pub trait EventParser: Send + Sync {
         slot: Option<u64>,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Result<Vec<Box<dyn UnifiedEvent>>> {
         let slot = slot.unwrap_or(0);
         let events = self.parse_events_from_inner_instruction(
@@ -448,7 +828,7 @@ pub trait EventParser: Send + Sync {
         slot: Option<u64>,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Result<Vec<Box<dyn UnifiedEvent>>> {
         let slot = slot.unwrap_or(0);
         let events = self.parse_events_from_instruction(
@@ -472,75 +852,66 @@ pub trait EventParser: Send + Sync {
         block_time: Option<Timestamp>,
         _inner_instructions: &[UiInnerInstructions],
     ) -> Result<Vec<Box<dyn UnifiedEvent>>> {
-        use crate::streaming::event_parser::common::utils::{decode_base64, extract_program_data};
-        
+        use crate::streaming::event_parser::common::utils::{
+            extract_program_data, log_instruction_indices,
+        };
+
         let mut events = Vec::new();
-        
-        for log in logs {
+        let indices = log_instruction_indices(logs);
+
+        for (log, index) in logs.iter().zip(indices) {
+            // 日志被截断时无法确定所属指令
+            let Some(index) = index else {
+                continue;
+            };
             if let Some(data_str) = extract_program_data(log) {
-                if let Ok(decoded) = decode_base64(data_str) {
+                if let Ok(decoded) = codec::base64_decode(data_str) {
                     if decoded.len() >= 16 {
-                        let hex_str = format!("0x{}", hex::encode(&decoded));
-                        
                         let discriminators = self.get_inner_instruction_configs();
-                        
+
                         // Check both full 16-byte and 8-byte discriminators for log events
                         for (discriminator, configs) in discriminators {
-                            // Try full discriminator match first
-                            if hex_str.starts_with(discriminator) {
-                                let data = &decoded[16..]; // Skip full 16-byte discriminator
-                                
-                                for config in configs {
-                                    if let Some(event) = (config.inner_instruction_parser)(
-                                        data,
-                                        EventMetadata::new(
-                                            signature.to_string(),
-                                            signature.to_string(),
-                                            slot.unwrap_or(0),
-                                            block_time.map(|bt| bt.seconds).unwrap_or(0),
-                                            block_time.map(|bt| bt.seconds * 1000 + (bt.nanos as i64) / 1_000_000).unwrap_or(0),
-                                            self.get_protocol_type(),
-                                            config.event_type.clone(),
-                                            self.get_program_id(),
-                                            "log".to_string(),
-                                            0, // program_received_time_ms
-                                        ),
-                                    ) {
-                                        events.push(event);
-                                    }
-                                }
+                            let discriminator_hex =
+                                discriminator.strip_prefix("0x").unwrap_or(discriminator);
+                            // Try full discriminator match first, then the 8-byte
+                            // discriminator (second half) for log events
+                            let skip = if discriminator_hex.is_empty() {
+                                // 没有内部事件的指令配置
+                                continue;
+                            } else if discriminator_hex_matches(&decoded, discriminator_hex) {
+                                16
+                            } else if discriminator_hex.len() > 16
+                                && discriminator_hex_matches(&decoded, &discriminator_hex[16..])
+                            {
+                                8
                             } else {
-                                // Try 8-byte discriminator (second half) for log events
-                                let discriminator_without_prefix = discriminator.strip_prefix("0x").unwrap_or(discriminator);
-                                if discriminator_without_prefix.len() >= 16 {
-                                    let second_half = &discriminator_without_prefix[16..]; // Take last 8 bytes
-                                    let second_half_with_prefix = format!("0x{}", second_half);
-                                    
-                                    if hex_str.starts_with(&second_half_with_prefix) {
-                                        let data = &decoded[8..]; // Skip 8-byte discriminator
-                                        
-                                        for config in configs {
-                                            if let Some(event) = (config.inner_instruction_parser)(
-                                                data,
-                                                EventMetadata::new(
-                                                    signature.to_string(),
-                                                    signature.to_string(),
-                                                    slot.unwrap_or(0),
-                                                    block_time.map(|bt| bt.seconds).unwrap_or(0),
-                                                    block_time.map(|bt| bt.seconds * 1000 + (bt.nanos as i64) / 1_000_000).unwrap_or(0),
-                                                    self.get_protocol_type(),
-                                                    config.event_type.clone(),
-                                                    self.get_program_id(),
-                                                    "log".to_string(),
-                                                    0, // program_received_time_ms
-                                                ),
-                                            ) {
-                                                events.push(event);
-                                            }
-                                        }
-                                    }
+                                continue;
+                            };
+                            let data = &decoded[skip..];
+
+                            let before = events.len();
+                            for config in configs {
+                                if let Some(event) = config.parse_inner_instruction(
+                                    data,
+                                    EventMetadata::new(
+                                        signature.to_string(),
+                                        signature.to_string(),
+                                        slot.unwrap_or(0),
+                                        block_time.map(|bt| bt.seconds).unwrap_or(0),
+                                        block_time.map(|bt| bt.seconds * 1000 + (bt.nanos as i64) / 1_000_000).unwrap_or(0),
+                                        self.get_protocol_type(),
+                                        config.event_type.clone(),
+                                        self.get_program_id(),
+                                        index.with_source(IndexSource::Log),
+                                        0, // program_received_time_ms
+                                    ),
+                                ) {
+                                    let mut event = event;
+                                    event.metadata_mut().set_instruction_id(&index);
+                                    events.push(event);
                                 }
                             }
+                            record_decode(&self.get_protocol_type(), events.len() > before);
                         }
                     }
                 }
@@ -556,6 +927,17 @@ pub trait EventParser: Send + Sync {
         static EMPTY_MAP: LazyLock<std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>>> = LazyLock::new(|| std::collections::HashMap::new());
         &EMPTY_MAP
     }
+
+    /// 获取指令配置，键为指令鉴别器
+    ///
+    /// Used to tell unknown instructions apart; parsers returning an empty map
+    /// never report `UnknownProgramEvent`s.
+    fn get_instruction_configs(&self) -> &HashMap<Vec<u8>, Vec<GenericEventParseConfig>> {
+        use std::sync::LazyLock;
+        static EMPTY_MAP: LazyLock<HashMap<Vec<u8>, Vec<GenericEventParseConfig>>> =
+            LazyLock::new(HashMap::new);
+        &EMPTY_MAP
+    }
     
     /// 获取协议类型（需要实现）
     fn get_protocol_type(&self) -> ProtocolType {
@@ -569,6 +951,21 @@ pub trait EventParser: Send + Sync {
         Pubkey::default()
     }
 
+    /// 获取指令解码器，未提供IDL的解析器返回None
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        None
+    }
+
+    /// 日志事件是否作为独立事件输出
+    ///
+    /// Log events normally only fill in the instruction event they belong to.
+    /// Programs that report solely through `emit!` logs, like Drift, have no
+    /// instruction event to merge into; their parsers return `true` so every log
+    /// event is emitted on its own.
+    fn emits_log_events(&self) -> bool {
+        false
+    }
+
     /// 检查是否应该处理此程序ID
     fn should_handle(&self, program_id: &Pubkey) -> bool;
 
@@ -589,8 +986,128 @@ pub struct GenericEventParseConfig {
     pub inner_instruction_discriminator: &'static str,
     pub instruction_discriminator: &'static [u8],
     pub event_type: EventType,
+    /// Parser of the current layout
     pub inner_instruction_parser: InnerInstructionEventParser,
     pub instruction_parser: InstructionEventParser,
+    /// Earlier layouts, tried in order when the current parser returns `None`
+    pub layouts: Vec<LayoutVersion>,
+}
+
+impl GenericEventParseConfig {
+    /// Add an earlier layout of the same discriminator
+    pub fn with_layout(mut self, layout: LayoutVersion) -> Self {
+        self.layouts.push(layout);
+        self
+    }
+
+    /// Decode inner instruction or log data with the first layout that accepts it
+    pub fn parse_inner_instruction(
+        &self,
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if self.layouts.is_empty() {
+            return (self.inner_instruction_parser)(data, metadata);
+        }
+        let slot = metadata.slot;
+        (self.inner_instruction_parser)(data, metadata.clone()).or_else(|| {
+            self.layouts
+                .iter()
+                .filter(|layout| layout.contains_slot(slot))
+                .filter_map(|layout| layout.inner_instruction_parser)
+                .find_map(|parser| parser(data, metadata.clone()))
+        })
+    }
+
+    /// Decode instruction data with the first layout that accepts it
+    pub fn parse_instruction(
+        &self,
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if self.layouts.is_empty() {
+            return (self.instruction_parser)(data, accounts, metadata);
+        }
+        let slot = metadata.slot;
+        (self.instruction_parser)(data, accounts, metadata.clone()).or_else(|| {
+            self.layouts
+                .iter()
+                .filter(|layout| layout.contains_slot(slot))
+                .filter_map(|layout| layout.instruction_parser)
+                .find_map(|parser| parser(data, accounts, metadata.clone()))
+        })
+    }
+}
+
+/// 协议事件或指令的历史布局
+///
+/// Programs such as Bonk and Raydium have added fields to events and instruction
+/// arguments over time. The current layout stays the config's own parser, so live
+/// data decodes on the first try; data it rejects (borsh rejects both missing and
+/// leftover bytes) falls through to the earlier layouts in the order they were
+/// added. A slot range keeps a layout from being applied outside the slots it was
+/// live, for layouts whose data would also fit another version.
+///
+/// # Usage Example
+/// ```ignore
+/// GenericEventParseConfig {
+///     inner_instruction_discriminator: discriminators::TRADE_EVENT,
+///     instruction_discriminator: discriminators::BUY_EXACT_IN,
+///     event_type: EventType::BonkBuyExactIn,
+///     inner_instruction_parser: Self::parse_trade_inner_instruction,
+///     instruction_parser: Self::parse_buy_exact_in_instruction,
+///     layouts: Vec::new(),
+/// }
+/// .with_layout(
+///     LayoutVersion::new("v1")
+///         .with_slots(0, Some(UPGRADE_SLOT))
+///         .with_inner_instruction_parser(Self::parse_trade_v1_inner_instruction),
+/// )
+/// ```
+#[derive(Debug, Clone)]
+pub struct LayoutVersion {
+    pub name: &'static str,
+    /// First slot the layout applies to
+    pub from_slot: u64,
+    /// Slot the layout was replaced at (exclusive), `None` if unbounded
+    pub until_slot: Option<u64>,
+    pub inner_instruction_parser: Option<InnerInstructionEventParser>,
+    pub instruction_parser: Option<InstructionEventParser>,
+}
+
+impl LayoutVersion {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            from_slot: 0,
+            until_slot: None,
+            inner_instruction_parser: None,
+            instruction_parser: None,
+        }
+    }
+
+    pub fn with_slots(mut self, from_slot: u64, until_slot: Option<u64>) -> Self {
+        self.from_slot = from_slot;
+        self.until_slot = until_slot;
+        self
+    }
+
+    pub fn with_inner_instruction_parser(mut self, parser: InnerInstructionEventParser) -> Self {
+        self.inner_instruction_parser = Some(parser);
+        self
+    }
+
+    pub fn with_instruction_parser(mut self, parser: InstructionEventParser) -> Self {
+        self.instruction_parser = Some(parser);
+        self
+    }
+
+    /// Whether the layout may have produced data of `slot`; slot 0 (unknown) always matches
+    pub fn contains_slot(&self, slot: u64) -> bool {
+        slot == 0
+            || (slot >= self.from_slot && !matches!(self.until_slot, Some(until) if slot >= until))
+    }
 }
 
 /// 内联指令事件解析器
@@ -607,6 +1124,7 @@ pub struct GenericEventParser {
     protocol_type: ProtocolType,
     inner_instruction_configs: HashMap<&'static str, Vec<GenericEventParseConfig>>,
     instruction_configs: HashMap<Vec<u8>, Vec<GenericEventParseConfig>>,
+    instruction_decoder: Option<InstructionDecoder>,
 }
 
 impl GenericEventParser {
@@ -635,9 +1153,16 @@ impl GenericEventParser {
             protocol_type,
             inner_instruction_configs,
             instruction_configs,
+            instruction_decoder: None,
         }
     }
 
+    /// 设置用于输出完整解码指令的IDL解码器
+    pub fn with_instruction_decoder(mut self, instruction_decoder: InstructionDecoder) -> Self {
+        self.instruction_decoder = Some(instruction_decoder);
+        self
+    }
+
     /// 通用的内联指令解析方法
     fn parse_inner_instruction_event(
         &self,
@@ -647,14 +1172,14 @@ impl GenericEventParser {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Option<Box<dyn UnifiedEvent>> {
         let timestamp = block_time.unwrap_or(Timestamp {
             seconds: 0,
             nanos: 0,
         });
         let block_time_ms = timestamp.seconds * 1000 + (timestamp.nanos as i64) / 1_000_000;
-        let metadata = EventMetadata::new(
+        let mut metadata = EventMetadata::new(
             signature.to_string(),
             signature.to_string(),
             slot,
@@ -666,7 +1191,8 @@ impl GenericEventParser {
             index,
             program_received_time_ms,
         );
-        (config.inner_instruction_parser)(data, metadata)
+        metadata.set_instruction_id(&index);
+        config.parse_inner_instruction(data, metadata)
     }
 
     /// 通用的指令解析方法
@@ -679,14 +1205,14 @@ impl GenericEventParser {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Option<Box<dyn UnifiedEvent>> {
         let timestamp = block_time.unwrap_or(Timestamp {
             seconds: 0,
             nanos: 0,
         });
         let block_time_ms = timestamp.seconds * 1000 + (timestamp.nanos as i64) / 1_000_000;
-        let metadata = EventMetadata::new(
+        let mut metadata = EventMetadata::new(
             signature.to_string(),
             signature.to_string(),
             slot,
@@ -698,7 +1224,8 @@ impl GenericEventParser {
             index,
             program_received_time_ms,
         );
-        (config.instruction_parser)(data, account_pubkeys, metadata)
+        metadata.set_instruction_id(&index);
+        config.parse_instruction(data, account_pubkeys, metadata)
     }
 }
 
@@ -712,20 +1239,20 @@ impl EventParser for GenericEventParser {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Vec<Box<dyn UnifiedEvent>> {
-        let inner_instruction_data = inner_instruction.data.clone();
-        let inner_instruction_data_decoded =
-            bs58::decode(inner_instruction_data).into_vec().unwrap();
+        let Ok(inner_instruction_data_decoded) = codec::base58_decode(&inner_instruction.data)
+        else {
+            return Vec::new();
+        };
         if inner_instruction_data_decoded.len() < 16 {
             return Vec::new();
         }
-        let inner_instruction_data_decoded_str =
-            format!("0x{}", hex::encode(&inner_instruction_data_decoded));
         let data = &inner_instruction_data_decoded[16..];
         let mut events = Vec::new();
         for (disc, configs) in &self.inner_instruction_configs {
-            if discriminator_matches(&inner_instruction_data_decoded_str, disc) {
+            if discriminator_hex_matches(&inner_instruction_data_decoded, disc) {
+                let before = events.len();
                 for config in configs {
                     if let Some(event) = self.parse_inner_instruction_event(
                         config,
@@ -734,11 +1261,15 @@ impl EventParser for GenericEventParser {
                         slot,
                         block_time,
                         program_received_time_ms,
-                        index.clone(),
+                        index,
                     ) {
                         events.push(event);
                     }
                 }
+                // 空鉴别器表示该指令没有内部事件
+                if !disc.is_empty() {
+                    record_decode(&self.protocol_type, events.len() > before);
+                }
             }
         }
         events
@@ -753,7 +1284,7 @@ impl EventParser for GenericEventParser {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Vec<Box<dyn UnifiedEvent>> {
         // Check bounds for program_id_index
         if instruction.program_id_index as usize >= accounts.len() {
@@ -781,6 +1312,7 @@ impl EventParser for GenericEventParser {
                     .iter()
                     .map(|&idx| accounts[idx as usize])
                     .collect();
+                let before = events.len();
                 for config in configs {
                     if let Some(event) = self.parse_instruction_event(
                         config,
@@ -790,11 +1322,12 @@ impl EventParser for GenericEventParser {
                         slot,
                         block_time,
                         program_received_time_ms,
-                        index.clone(),
+                        index,
                     ) {
                         events.push(event);
                     }
                 }
+                record_decode(&self.protocol_type, events.len() > before);
             }
         }
 
@@ -804,6 +1337,10 @@ impl EventParser for GenericEventParser {
     fn get_inner_instruction_configs(&self) -> &std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>> {
         &self.inner_instruction_configs
     }
+
+    fn get_instruction_configs(&self) -> &HashMap<Vec<u8>, Vec<GenericEventParseConfig>> {
+        &self.instruction_configs
+    }
     
     fn get_protocol_type(&self) -> ProtocolType {
         self.protocol_type.clone()
@@ -813,6 +1350,10 @@ impl EventParser for GenericEventParser {
         self.program_id
     }
 
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.instruction_decoder.as_ref()
+    }
+
     fn should_handle(&self, program_id: &Pubkey) -> bool {
         *program_id == self.program_id
     }
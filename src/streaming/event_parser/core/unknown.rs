@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::{
+    discriminator::EVENT_IX_TAG, EventMetadata, RawInstruction,
+};
+
+/// 未知指令事件 - 指令属于支持的程序但不匹配任何已配置的鉴别器
+///
+/// Emitted when `ParseOptions::unknown_instructions` is set, so instructions added
+/// by a program upgrade (PumpFun `set_creator` before it had a parser, say) show
+/// up in the stream instead of being dropped silently. Anchor event self-CPIs
+/// without a configured event are reported too, with the 16-byte event
+/// discriminator.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnknownProgramEvent {
+    pub metadata: EventMetadata,
+    /// Leading bytes of the data, as long as the program's configured discriminators
+    pub discriminator: Vec<u8>,
+    pub instruction: RawInstruction,
+}
+
+impl_unified_event!(UnknownProgramEvent,);
+
+impl UnknownProgramEvent {
+    /// Discriminator as lowercase hex, the form discriminator constants are looked up by
+    pub fn discriminator_hex(&self) -> String {
+        hex::encode(&self.discriminator)
+    }
+
+    /// Whether the instruction is an Anchor event self-CPI
+    pub fn is_event(&self) -> bool {
+        self.discriminator.starts_with(&EVENT_IX_TAG)
+    }
+}
+
+/// Discriminator of `data` given the length of the program's instruction discriminators
+pub(crate) fn unknown_discriminator(data: &[u8], len: usize) -> Vec<u8> {
+    let len = if data.len() >= 16 && data.starts_with(&EVENT_IX_TAG) {
+        16
+    } else {
+        len.min(data.len())
+    };
+    data[..len].to_vec()
+}
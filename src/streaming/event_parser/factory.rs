@@ -1,6 +1,6 @@
 use anyhow::{anyhow, Result};
 use solana_sdk::pubkey::Pubkey;
-use std::{collections::HashMap, sync::{Arc, LazyLock}};
+use std::{collections::HashMap, sync::{Arc, LazyLock, RwLock}};
 
 use crate::streaming::event_parser::protocols::{
     bonk::parser::BONK_PROGRAM_ID, pumpfun::parser::PUMPFUN_PROGRAM_ID,
@@ -8,9 +8,54 @@ use crate::streaming::event_parser::protocols::{
     raydium_clmm::parser::RAYDIUM_CLMM_PROGRAM_ID, BonkEventParser, RaydiumCpmmEventParser,
     RaydiumClmmEventParser,
 };
+use crate::streaming::event_parser::protocols::{
+    stake::parser::STAKE_PROGRAM_ID, vote::parser::VOTE_PROGRAM_ID,
+};
+#[cfg(feature = "validator-ops")]
+use crate::streaming::event_parser::protocols::{StakeEventParser, VoteEventParser};
+use crate::streaming::event_parser::protocols::{
+    metaplex::parser::TOKEN_METADATA_PROGRAM_ID, MetaplexEventParser,
+};
+use crate::streaming::event_parser::protocols::{
+    magic_eden::parser::{MAGIC_EDEN_M2_PROGRAM_ID, MAGIC_EDEN_MMM_PROGRAM_ID},
+    MagicEdenEventParser,
+};
+use crate::streaming::event_parser::protocols::{
+    tensor::parser::{TENSOR_COMP_PROGRAM_ID, TENSOR_SWAP_PROGRAM_ID},
+    TensorEventParser,
+};
+use crate::streaming::event_parser::protocols::{
+    kamino::parser::KAMINO_LEND_PROGRAM_ID, KaminoEventParser,
+};
+use crate::streaming::event_parser::protocols::{
+    marginfi::parser::MARGINFI_PROGRAM_ID, MarginfiEventParser,
+};
+use crate::streaming::event_parser::protocols::{drift::parser::DRIFT_PROGRAM_ID, DriftEventParser};
+use crate::streaming::event_parser::protocols::{
+    sanctum::parser::{
+        SANCTUM_ROUTER_PROGRAM_ID, SANCTUM_SPL_MULTI_STAKE_POOL_PROGRAM_ID,
+        SANCTUM_SPL_STAKE_POOL_PROGRAM_ID, SPL_STAKE_POOL_PROGRAM_ID,
+    },
+    SanctumEventParser,
+};
+use crate::streaming::event_parser::protocols::{
+    phoenix::parser::PHOENIX_PROGRAM_ID, PhoenixEventParser,
+};
+use crate::streaming::event_parser::protocols::{
+    meteora_dbc::parser::METEORA_DBC_PROGRAM_ID, MeteoraDbcEventParser,
+};
+use crate::streaming::event_parser::protocols::{
+    meteora_damm::parser::METEORA_DAMM_PROGRAM_ID, MeteoraDammEventParser,
+};
+use crate::streaming::event_parser::protocols::{
+    believe::parser::BELIEVE_PROGRAM_ID, BelieveEventParser,
+};
+use crate::streaming::event_parser::protocols::{boop::parser::BOOP_PROGRAM_ID, BoopEventParser};
 
 use super::{
+    common::EventType,
     core::traits::EventParser,
+    prefilter::ProgramIdFilter,
     protocols::{pumpfun::PumpFunEventParser, pumpswap::PumpSwapEventParser},
 };
 
@@ -22,6 +67,22 @@ pub enum Protocol {
     Bonk,
     RaydiumCpmm,
     RaydiumClmm,
+    Boop,
+    Believe,
+    MeteoraDamm,
+    MeteoraDbc,
+    Phoenix,
+    Sanctum,
+    Drift,
+    Marginfi,
+    Kamino,
+    Tensor,
+    MagicEden,
+    Metaplex,
+    Vote,
+    Stake,
+    /// Protocol parsed by a parser added with [`EventParserFactory::register`]
+    Custom(String),
 }
 
 impl Protocol {
@@ -32,6 +93,115 @@ impl Protocol {
             Protocol::Bonk => vec![BONK_PROGRAM_ID],
             Protocol::RaydiumCpmm => vec![RAYDIUM_CPMM_PROGRAM_ID],
             Protocol::RaydiumClmm => vec![RAYDIUM_CLMM_PROGRAM_ID],
+            Protocol::Boop => vec![BOOP_PROGRAM_ID],
+            Protocol::Believe => vec![BELIEVE_PROGRAM_ID],
+            Protocol::MeteoraDamm => vec![METEORA_DAMM_PROGRAM_ID],
+            Protocol::MeteoraDbc => vec![METEORA_DBC_PROGRAM_ID],
+            Protocol::Phoenix => vec![PHOENIX_PROGRAM_ID],
+            Protocol::Sanctum => vec![
+                SANCTUM_ROUTER_PROGRAM_ID,
+                SPL_STAKE_POOL_PROGRAM_ID,
+                SANCTUM_SPL_STAKE_POOL_PROGRAM_ID,
+                SANCTUM_SPL_MULTI_STAKE_POOL_PROGRAM_ID,
+            ],
+            Protocol::Drift => vec![DRIFT_PROGRAM_ID],
+            Protocol::Marginfi => vec![MARGINFI_PROGRAM_ID],
+            Protocol::Kamino => vec![KAMINO_LEND_PROGRAM_ID],
+            Protocol::Tensor => vec![TENSOR_COMP_PROGRAM_ID, TENSOR_SWAP_PROGRAM_ID],
+            Protocol::MagicEden => vec![MAGIC_EDEN_M2_PROGRAM_ID, MAGIC_EDEN_MMM_PROGRAM_ID],
+            Protocol::Metaplex => vec![TOKEN_METADATA_PROGRAM_ID],
+            Protocol::Vote => vec![VOTE_PROGRAM_ID],
+            Protocol::Stake => vec![STAKE_PROGRAM_ID],
+            Protocol::Custom(_) => EventParserFactory::get_parser(self)
+                .map(|parser| parser.supported_program_ids())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Protocol {
+    /// Built-in protocol that emits `event_type`
+    pub fn from_event_type(event_type: &EventType) -> Option<Protocol> {
+        match event_type {
+            EventType::PumpSwapBuy
+            | EventType::PumpSwapSell
+            | EventType::PumpSwapCreatePool
+            | EventType::PumpSwapDeposit
+            | EventType::PumpSwapWithdraw
+            | EventType::PumpSwapUpdateFeeConfig
+            | EventType::PumpSwapSetCoinCreator => Some(Protocol::PumpSwap),
+            EventType::PumpFunCreateToken
+            | EventType::PumpFunBuy
+            | EventType::PumpFunSell
+            | EventType::PumpFunSetParams
+            | EventType::PumpFunSetCreator
+            | EventType::PumpFunCollectCreatorFee
+            | EventType::PumpFunWithdraw => Some(Protocol::PumpFun),
+            EventType::BonkBuyExactIn
+            | EventType::BonkBuyExactOut
+            | EventType::BonkSellExactIn
+            | EventType::BonkSellExactOut
+            | EventType::BonkInitialize => Some(Protocol::Bonk),
+            EventType::RaydiumCpmmSwapBaseInput | EventType::RaydiumCpmmSwapBaseOutput => {
+                Some(Protocol::RaydiumCpmm)
+            }
+            EventType::RaydiumClmmSwap | EventType::RaydiumClmmSwapV2 => {
+                Some(Protocol::RaydiumClmm)
+            }
+            EventType::BoopCreateToken
+            | EventType::BoopBuy
+            | EventType::BoopSell
+            | EventType::BoopGraduate => Some(Protocol::Boop),
+            EventType::BelieveCreateToken
+            | EventType::BelieveTrade => Some(Protocol::Believe),
+            EventType::MeteoraDammSwap
+            | EventType::MeteoraDammAddLiquidity
+            | EventType::MeteoraDammRemoveLiquidity
+            | EventType::MeteoraDammBootstrapLiquidity => Some(Protocol::MeteoraDamm),
+            EventType::MeteoraDbcInitializePool
+            | EventType::MeteoraDbcSwap
+            | EventType::MeteoraDbcMigrate => Some(Protocol::MeteoraDbc),
+            EventType::PhoenixFill
+            | EventType::PhoenixPlace
+            | EventType::PhoenixCancel
+            | EventType::PhoenixFillSummary
+            | EventType::PhoenixFee => Some(Protocol::Phoenix),
+            EventType::SanctumDepositSol
+            | EventType::SanctumDepositStake
+            | EventType::SanctumWithdrawSol
+            | EventType::SanctumWithdrawStake
+            | EventType::SanctumSwap => Some(Protocol::Sanctum),
+            EventType::DriftPerpFill
+            | EventType::DriftSpotFill
+            | EventType::DriftPlaceOrder
+            | EventType::DriftLiquidation => Some(Protocol::Drift),
+            EventType::MarginfiDeposit
+            | EventType::MarginfiRepay
+            | EventType::MarginfiWithdraw
+            | EventType::MarginfiBorrow
+            | EventType::MarginfiLiquidate => Some(Protocol::Marginfi),
+            EventType::KaminoDeposit
+            | EventType::KaminoWithdraw
+            | EventType::KaminoBorrow
+            | EventType::KaminoRepay
+            | EventType::KaminoLiquidate => Some(Protocol::Kamino),
+            EventType::TensorList
+            | EventType::TensorDelist
+            | EventType::TensorBuy
+            | EventType::TensorBidFill
+            | EventType::TensorSwapTrade => Some(Protocol::Tensor),
+            EventType::MagicEdenList
+            | EventType::MagicEdenDelist
+            | EventType::MagicEdenSale
+            | EventType::MagicEdenBidFill => Some(Protocol::MagicEden),
+            EventType::MetaplexCreateMetadata
+            | EventType::MetaplexUpdateMetadata
+            | EventType::MetaplexVerify => Some(Protocol::Metaplex),
+            EventType::VoteCast | EventType::VoteWithdraw => Some(Protocol::Vote),
+            EventType::StakeDelegate
+            | EventType::StakeDeactivate
+            | EventType::StakeWithdraw => Some(Protocol::Stake),
+            _ => None,
         }
     }
 }
@@ -44,6 +214,21 @@ impl std::fmt::Display for Protocol {
             Protocol::Bonk => write!(f, "Bonk"),
             Protocol::RaydiumCpmm => write!(f, "RaydiumCpmm"),
             Protocol::RaydiumClmm => write!(f, "RaydiumClmm"),
+            Protocol::Boop => write!(f, "Boop"),
+            Protocol::Believe => write!(f, "Believe"),
+            Protocol::MeteoraDamm => write!(f, "MeteoraDamm"),
+            Protocol::MeteoraDbc => write!(f, "MeteoraDbc"),
+            Protocol::Phoenix => write!(f, "Phoenix"),
+            Protocol::Sanctum => write!(f, "Sanctum"),
+            Protocol::Drift => write!(f, "Drift"),
+            Protocol::Marginfi => write!(f, "Marginfi"),
+            Protocol::Kamino => write!(f, "Kamino"),
+            Protocol::Tensor => write!(f, "Tensor"),
+            Protocol::MagicEden => write!(f, "MagicEden"),
+            Protocol::Metaplex => write!(f, "Metaplex"),
+            Protocol::Vote => write!(f, "Vote"),
+            Protocol::Stake => write!(f, "Stake"),
+            Protocol::Custom(name) => write!(f, "{}", name),
         }
     }
 }
@@ -51,6 +236,8 @@ impl std::fmt::Display for Protocol {
 impl std::str::FromStr for Protocol {
     type Err = anyhow::Error;
 
+    /// Built-in protocols by case-insensitive name, custom protocols by their
+    /// registered name
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
             "pumpswap" => Ok(Protocol::PumpSwap),
@@ -58,30 +245,195 @@ impl std::str::FromStr for Protocol {
             "bonk" => Ok(Protocol::Bonk),
             "raydiumcpmm" => Ok(Protocol::RaydiumCpmm),
             "raydiumclmm" => Ok(Protocol::RaydiumClmm),
-            _ => Err(anyhow!("Unsupported protocol: {}", s)),
+            "boop" => Ok(Protocol::Boop),
+            "believe" => Ok(Protocol::Believe),
+            "meteoradamm" => Ok(Protocol::MeteoraDamm),
+            "meteoradbc" => Ok(Protocol::MeteoraDbc),
+            "phoenix" => Ok(Protocol::Phoenix),
+            "sanctum" => Ok(Protocol::Sanctum),
+            "drift" => Ok(Protocol::Drift),
+            "marginfi" => Ok(Protocol::Marginfi),
+            "kamino" => Ok(Protocol::Kamino),
+            "tensor" => Ok(Protocol::Tensor),
+            "magiceden" => Ok(Protocol::MagicEden),
+            "metaplex" => Ok(Protocol::Metaplex),
+            "vote" => Ok(Protocol::Vote),
+            "stake" => Ok(Protocol::Stake),
+            _ => {
+                let custom = Protocol::Custom(s.to_string());
+                if EventParserFactory::is_supported(&custom) {
+                    Ok(custom)
+                } else {
+                    Err(anyhow!("Unsupported protocol: {}", s))
+                }
+            }
         }
     }
 }
 
-static EVENT_PARSERS: LazyLock<HashMap<Protocol, Arc<dyn EventParser>>> = LazyLock::new(|| {
-    let mut parsers: HashMap<Protocol, Arc<dyn EventParser>> = HashMap::new();
-    parsers.insert(Protocol::PumpSwap, Arc::new(PumpSwapEventParser::new()));
-    parsers.insert(Protocol::PumpFun, Arc::new(PumpFunEventParser::new()));
-    parsers.insert(Protocol::Bonk, Arc::new(BonkEventParser::new()));
-    parsers.insert(Protocol::RaydiumCpmm, Arc::new(RaydiumCpmmEventParser::new()));
-    parsers.insert(Protocol::RaydiumClmm, Arc::new(RaydiumClmmEventParser::new()));
-    parsers
-});
+/// 注册表中的解析器，内置解析器在首次使用时构造
+enum ParserEntry {
+    Lazy(fn() -> Arc<dyn EventParser>),
+    Built(Arc<dyn EventParser>),
+}
 
+static EVENT_PARSERS: LazyLock<RwLock<HashMap<Protocol, ParserEntry>>> = LazyLock::new(|| {
+    let mut parsers: HashMap<Protocol, ParserEntry> = HashMap::new();
+    parsers.insert(
+        Protocol::PumpSwap,
+        ParserEntry::Lazy(|| Arc::new(PumpSwapEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::PumpFun,
+        ParserEntry::Lazy(|| Arc::new(PumpFunEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::Bonk,
+        ParserEntry::Lazy(|| Arc::new(BonkEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::RaydiumCpmm,
+        ParserEntry::Lazy(|| Arc::new(RaydiumCpmmEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::RaydiumClmm,
+        ParserEntry::Lazy(|| Arc::new(RaydiumClmmEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::Boop,
+        ParserEntry::Lazy(|| Arc::new(BoopEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::Believe,
+        ParserEntry::Lazy(|| Arc::new(BelieveEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::MeteoraDamm,
+        ParserEntry::Lazy(|| Arc::new(MeteoraDammEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::MeteoraDbc,
+        ParserEntry::Lazy(|| Arc::new(MeteoraDbcEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::Phoenix,
+        ParserEntry::Lazy(|| Arc::new(PhoenixEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::Sanctum,
+        ParserEntry::Lazy(|| Arc::new(SanctumEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::Drift,
+        ParserEntry::Lazy(|| Arc::new(DriftEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::Marginfi,
+        ParserEntry::Lazy(|| Arc::new(MarginfiEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::Kamino,
+        ParserEntry::Lazy(|| Arc::new(KaminoEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::Tensor,
+        ParserEntry::Lazy(|| Arc::new(TensorEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::MagicEden,
+        ParserEntry::Lazy(|| Arc::new(MagicEdenEventParser::new())),
+    );
+    parsers.insert(
+        Protocol::Metaplex,
+        ParserEntry::Lazy(|| Arc::new(MetaplexEventParser::new())),
+    );
+    // 投票交易量极大，仅在启用 validator-ops 时注册
+    #[cfg(feature = "validator-ops")]
+    {
+        parsers.insert(
+            Protocol::Vote,
+            ParserEntry::Lazy(|| Arc::new(VoteEventParser::new())),
+        );
+        parsers.insert(
+            Protocol::Stake,
+            ParserEntry::Lazy(|| Arc::new(StakeEventParser::new())),
+        );
+    }
+    RwLock::new(parsers)
+});
 
 /// 事件解析器工厂 - 用于创建不同协议的事件解析器
+///
+/// Holds one shared parser per protocol. Downstream crates add parsers for
+/// private or unreleased programs with [`register`](Self::register), usually
+/// under a [`Protocol::Custom`] name, and subscribe to them like built-in
+/// protocols.
+///
+/// ```ignore
+/// EventParserFactory::register(Protocol::Custom("my_dex".into()), MyDexEventParser::new());
+/// grpc.subscribe_events_v2(vec![Protocol::Custom("my_dex".into())], ...).await?;
+/// ```
 pub struct EventParserFactory;
 
 impl EventParserFactory {
+    /// Register the parser of `protocol`, replacing and returning the previous one
+    pub fn register<P: EventParser + 'static>(
+        protocol: Protocol,
+        parser: P,
+    ) -> Option<Arc<dyn EventParser>> {
+        EVENT_PARSERS
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(protocol, ParserEntry::Built(Arc::new(parser)))
+            .and_then(ParserEntry::into_built)
+    }
+
+    /// Remove the parser of `protocol`
+    pub fn unregister(protocol: &Protocol) -> Option<Arc<dyn EventParser>> {
+        EVENT_PARSERS
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(protocol)
+            .and_then(ParserEntry::into_built)
+    }
+
+    /// Parser of `protocol`, `None` if none is registered
+    ///
+    /// Built-in parsers are constructed on first use and reused afterwards.
+    pub fn get_parser(protocol: &Protocol) -> Option<Arc<dyn EventParser>> {
+        match EVENT_PARSERS
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(protocol)?
+        {
+            ParserEntry::Built(parser) => return Some(parser.clone()),
+            ParserEntry::Lazy(_) => {}
+        }
+        let mut parsers = EVENT_PARSERS.write().unwrap_or_else(|e| e.into_inner());
+        let entry = parsers.get_mut(protocol)?;
+        if let ParserEntry::Lazy(build) = *entry {
+            *entry = ParserEntry::Built(build());
+        }
+        match entry {
+            ParserEntry::Built(parser) => Some(parser.clone()),
+            ParserEntry::Lazy(_) => None,
+        }
+    }
+
+    /// Whether the parser of `protocol` has been constructed
+    pub fn is_built(protocol: &Protocol) -> bool {
+        matches!(
+            EVENT_PARSERS
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(protocol),
+            Some(ParserEntry::Built(_))
+        )
+    }
 
     /// 创建指定协议的事件解析器
     pub fn create_parser(protocol: Protocol) -> Arc<dyn EventParser> {
-        EVENT_PARSERS.get(&protocol).cloned().unwrap_or_else(|| {
+        Self::get_parser(&protocol).unwrap_or_else(|| {
             panic!("Parser for protocol {} not found", protocol);
         })
     }
@@ -94,13 +446,164 @@ impl EventParserFactory {
             .collect()
     }
 
-    /// 获取所有支持的协议
+    /// 获取所有支持的协议，内置协议在前，自定义协议按名称排序
     pub fn supported_protocols() -> Vec<Protocol> {
-        vec![Protocol::PumpSwap]
+        let mut protocols: Vec<Protocol> = EVENT_PARSERS
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .keys()
+            .cloned()
+            .collect();
+        protocols.sort_by_key(|protocol| match protocol {
+            Protocol::PumpSwap => (0, String::new()),
+            Protocol::PumpFun => (1, String::new()),
+            Protocol::Bonk => (2, String::new()),
+            Protocol::RaydiumCpmm => (3, String::new()),
+            Protocol::RaydiumClmm => (4, String::new()),
+            Protocol::Boop => (5, String::new()),
+            Protocol::Believe => (6, String::new()),
+            Protocol::MeteoraDamm => (7, String::new()),
+            Protocol::MeteoraDbc => (8, String::new()),
+            Protocol::Phoenix => (9, String::new()),
+            Protocol::Sanctum => (10, String::new()),
+            Protocol::Drift => (11, String::new()),
+            Protocol::Marginfi => (12, String::new()),
+            Protocol::Kamino => (13, String::new()),
+            Protocol::Tensor => (14, String::new()),
+            Protocol::MagicEden => (15, String::new()),
+            Protocol::Metaplex => (16, String::new()),
+            Protocol::Vote => (17, String::new()),
+            Protocol::Stake => (18, String::new()),
+            Protocol::Custom(name) => (19, name.clone()),
+        });
+        protocols
     }
 
     /// 检查协议是否支持
     pub fn is_supported(protocol: &Protocol) -> bool {
-        Self::supported_protocols().contains(protocol)
+        EVENT_PARSERS
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains_key(protocol)
+    }
+}
+
+impl ParserEntry {
+    fn into_built(self) -> Option<Arc<dyn EventParser>> {
+        match self {
+            ParserEntry::Built(parser) => Some(parser),
+            ParserEntry::Lazy(_) => None,
+        }
+    }
+}
+
+/// 运行时可切换的协议集合
+///
+/// Cloned handles share the same set, so a stream started with one handle parses
+/// the protocols enabled through any other handle from the next transaction on,
+/// without restarting the subscription. Parsers come from [`EventParserFactory`]
+/// and are shared, so re-enabling a protocol does not rebuild its parser.
+/// Transactions of a protocol are only received if the subscription filter
+/// includes its program. Streams skip transactions that reference none of the
+/// enabled protocols' programs before decoding them, so register custom protocols
+/// before enabling them.
+///
+/// ```ignore
+/// let protocols = ActiveProtocols::new(vec![Protocol::PumpFun, Protocol::Bonk]);
+/// tokio::spawn(grpc.subscribe_events_v2(protocols.clone(), None, include, vec![], vec![], None, callback));
+/// protocols.disable(&Protocol::Bonk);
+/// protocols.enable(Protocol::RaydiumCpmm);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ActiveProtocols {
+    inner: Arc<RwLock<ActiveState>>,
+}
+
+#[derive(Debug, Default)]
+struct ActiveState {
+    protocols: Vec<Protocol>,
+    /// Program IDs of `protocols`, recomputed on every change
+    filter: Arc<ProgramIdFilter>,
+}
+
+impl ActiveState {
+    fn changed(&mut self) {
+        self.filter = Arc::new(ProgramIdFilter::from_protocols(&self.protocols));
+    }
+}
+
+impl ActiveProtocols {
+    pub fn new(protocols: Vec<Protocol>) -> Self {
+        let set = Self::default();
+        set.set(protocols);
+        set
+    }
+
+    /// Start parsing `protocol`, returns false if it was already enabled
+    pub fn enable(&self, protocol: Protocol) -> bool {
+        let mut state = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        if state.protocols.contains(&protocol) {
+            return false;
+        }
+        state.protocols.push(protocol);
+        state.changed();
+        true
+    }
+
+    /// Stop parsing `protocol`, returns false if it was not enabled
+    pub fn disable(&self, protocol: &Protocol) -> bool {
+        let mut state = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        let len = state.protocols.len();
+        state.protocols.retain(|p| p != protocol);
+        if state.protocols.len() == len {
+            return false;
+        }
+        state.changed();
+        true
+    }
+
+    /// Replace the enabled protocols
+    pub fn set(&self, protocols: Vec<Protocol>) {
+        let mut deduped: Vec<Protocol> = Vec::with_capacity(protocols.len());
+        for protocol in protocols {
+            if !deduped.contains(&protocol) {
+                deduped.push(protocol);
+            }
+        }
+        let mut state = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        state.protocols = deduped;
+        state.changed();
+    }
+
+    pub fn is_enabled(&self, protocol: &Protocol) -> bool {
+        self.inner
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .protocols
+            .contains(protocol)
+    }
+
+    /// Enabled protocols in the order they were enabled
+    pub fn protocols(&self) -> Vec<Protocol> {
+        self.inner
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .protocols
+            .clone()
+    }
+
+    /// Pre-filter matching the program IDs of the enabled protocols
+    pub fn program_filter(&self) -> Arc<ProgramIdFilter> {
+        self.inner
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .filter
+            .clone()
+    }
+}
+
+impl From<Vec<Protocol>> for ActiveProtocols {
+    fn from(protocols: Vec<Protocol>) -> Self {
+        Self::new(protocols)
     }
 }
@@ -1,10 +1,20 @@
 pub mod common;
 pub mod core;
 pub mod factory;
+pub mod prefilter;
 pub mod protocols;
 
+pub use core::circuit_breaker::{
+    parser_health, CircuitBreakerConfig, ParserCircuitBreaker, ParserDegradedEvent,
+    ParserHealthStats, RawInstructionEvent,
+};
+pub use core::error::{ParseError, ParseOutput};
+pub use core::instruction_decoder::{DecodedInstructionEvent, InstructionDecoder, NamedAccount};
+pub use core::post_process::{EventPostProcessor, PostProcessContext, PostProcessorChain};
 pub use core::traits::{EventParser, UnifiedEvent};
-pub use factory::{EventParserFactory, Protocol};
+pub use core::unknown::UnknownProgramEvent;
+pub use factory::{ActiveProtocols, EventParserFactory, Protocol};
+pub use prefilter::ProgramIdFilter;
 
 /// Macro: Simplify downcast_ref pattern matching
 /// 
@@ -0,0 +1,57 @@
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::event_parser::Protocol;
+
+/// 交易预过滤 - 解码前按程序ID筛选交易
+///
+/// Matches transactions whose account keys (static keys plus addresses loaded from
+/// lookup tables) include one of the program IDs, comparing raw key bytes so
+/// irrelevant transactions are skipped before any base58, base64 or borsh work.
+/// An empty filter matches nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProgramIdFilter {
+    program_ids: Vec<Pubkey>,
+}
+
+impl ProgramIdFilter {
+    pub fn new(program_ids: Vec<Pubkey>) -> Self {
+        let mut deduped: Vec<Pubkey> = Vec::with_capacity(program_ids.len());
+        for program_id in program_ids {
+            if !deduped.contains(&program_id) {
+                deduped.push(program_id);
+            }
+        }
+        Self {
+            program_ids: deduped,
+        }
+    }
+
+    /// Program IDs of the parsers of `protocols`
+    pub fn from_protocols(protocols: &[Protocol]) -> Self {
+        Self::new(protocols.iter().flat_map(|p| p.get_program_id()).collect())
+    }
+
+    pub fn program_ids(&self) -> &[Pubkey] {
+        &self.program_ids
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.program_ids.is_empty()
+    }
+
+    pub fn matches_key(&self, key: &[u8]) -> bool {
+        self.program_ids
+            .iter()
+            .any(|program_id| program_id.as_ref() == key)
+    }
+
+    /// Whether any of the raw 32-byte keys is one of the program IDs
+    pub fn matches_keys<'a>(&self, keys: impl IntoIterator<Item = &'a [u8]>) -> bool {
+        keys.into_iter().any(|key| self.matches_key(key))
+    }
+
+    /// Whether the resolved account keys of a transaction include a program ID
+    pub fn matches_accounts(&self, accounts: &[Pubkey]) -> bool {
+        accounts.iter().any(|key| self.program_ids.contains(key))
+    }
+}
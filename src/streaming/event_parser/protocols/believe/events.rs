@@ -0,0 +1,126 @@
+use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::EventMetadata;
+
+/// 创建代币
+///
+/// Believe launches a token as a Meteora dynamic bonding curve pool under one of
+/// its pool configs. `creator` is the wallet the pool's creator fees are paid to.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct BelieveCreateTokenEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub pool: Pubkey,
+    pub config: Pubkey,
+    pub creator: Pubkey,
+    pub base_mint: Pubkey,
+    pub pool_type: u8,
+    pub activation_point: u64,
+    #[borsh(skip)]
+    pub name: String,
+    #[borsh(skip)]
+    pub symbol: String,
+    #[borsh(skip)]
+    pub uri: String,
+    #[borsh(skip)]
+    pub quote_mint: Pubkey,
+    #[borsh(skip)]
+    pub base_vault: Pubkey,
+    #[borsh(skip)]
+    pub quote_vault: Pubkey,
+    #[borsh(skip)]
+    pub payer: Pubkey,
+}
+
+impl_unified_event!(
+    BelieveCreateTokenEvent,
+    pool,
+    config,
+    creator,
+    base_mint,
+    pool_type,
+    activation_point
+);
+
+/// 交易
+///
+/// Unlike PumpFun, fees are reported per recipient: `trading_fee` stays in the
+/// pool and is split between Believe and the token creator, `protocol_fee` goes to
+/// Meteora and `referral_fee` to the referral account if one was passed. The
+/// creator is attributed from the create event by the `BelieveDevTagger`
+/// post-processor.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct BelieveTradeEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub pool: Pubkey,
+    pub config: Pubkey,
+    /// 0 = sell (base to quote), 1 = buy (quote to base)
+    pub trade_direction: u8,
+    pub has_referral: bool,
+    /// Amount in as requested by the instruction
+    pub requested_amount_in: u64,
+    pub minimum_amount_out: u64,
+    pub actual_input_amount: u64,
+    pub output_amount: u64,
+    pub next_sqrt_price: u128,
+    pub trading_fee: u64,
+    pub protocol_fee: u64,
+    pub referral_fee: u64,
+    pub amount_in: u64,
+    pub current_timestamp: u64,
+    #[borsh(skip)]
+    pub is_buy: bool,
+    #[borsh(skip)]
+    pub payer: Pubkey,
+    #[borsh(skip)]
+    pub base_mint: Pubkey,
+    #[borsh(skip)]
+    pub quote_mint: Pubkey,
+    #[borsh(skip)]
+    pub input_token_account: Pubkey,
+    #[borsh(skip)]
+    pub output_token_account: Pubkey,
+    #[borsh(skip)]
+    pub base_vault: Pubkey,
+    #[borsh(skip)]
+    pub quote_vault: Pubkey,
+    #[borsh(skip)]
+    pub referral_token_account: Pubkey,
+    /// Token creator, if the create was seen
+    #[borsh(skip)]
+    pub creator: Pubkey,
+    #[borsh(skip)]
+    pub is_dev_create_token_trade: bool,
+    #[borsh(skip)]
+    pub is_bot: bool,
+}
+
+impl_unified_event!(
+    BelieveTradeEvent,
+    pool,
+    config,
+    trade_direction,
+    has_referral,
+    requested_amount_in,
+    minimum_amount_out,
+    actual_input_amount,
+    output_amount,
+    next_sqrt_price,
+    trading_fee,
+    protocol_fee,
+    referral_fee,
+    amount_in,
+    current_timestamp,
+    is_buy
+);
+
+/// 事件鉴别器常量 (Meteora DBC)
+pub mod discriminators {
+    pub use crate::streaming::event_parser::protocols::meteora_dbc::discriminators::{
+        INITIALIZE_POOL_EVENT, INITIALIZE_POOL_IX, SWAP_EVENT, SWAP_IX,
+    };
+}
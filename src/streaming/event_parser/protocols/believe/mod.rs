@@ -0,0 +1,5 @@
+pub mod events;
+pub mod parser;
+
+pub use events::*;
+pub use parser::{is_pool_config, register_pool_config, BelieveEventParser};
@@ -0,0 +1,258 @@
+use std::collections::HashSet;
+use std::sync::{LazyLock, RwLock};
+
+use borsh::BorshDeserialize;
+use prost_types::Timestamp;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::UiCompiledInstruction;
+
+use crate::streaming::event_parser::{
+    common::{read_u64_le, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
+    core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
+    protocols::believe::{discriminators, BelieveCreateTokenEvent, BelieveTradeEvent},
+    protocols::meteora_dbc::parser::METEORA_DBC_PROGRAM_ID,
+};
+
+/// Believe程序ID
+///
+/// Believe (formerly Launchcoin) launches through the Meteora dynamic bonding
+/// curve program.
+pub const BELIEVE_PROGRAM_ID: Pubkey = METEORA_DBC_PROGRAM_ID;
+
+/// 指令解码所用的IDL子集
+const IDL: &str = include_str!("idl.json");
+
+/// Believe使用的池配置
+static POOL_CONFIGS: LazyLock<RwLock<HashSet<Pubkey>>> =
+    LazyLock::new(|| RwLock::new(HashSet::new()));
+
+/// 注册Believe的池配置
+///
+/// The bonding curve program is shared with other launchpads; only pools created
+/// under a registered config are parsed as Believe.
+pub fn register_pool_config(config: Pubkey) {
+    POOL_CONFIGS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(config);
+}
+
+/// Whether `config` is a registered Believe pool config
+pub fn is_pool_config(config: &Pubkey) -> bool {
+    POOL_CONFIGS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains(config)
+}
+
+/// initialize_virtual_pool_with_spl_token 指令参数
+#[derive(BorshDeserialize)]
+struct InitializePoolArgs {
+    name: String,
+    symbol: String,
+    uri: String,
+}
+
+/// Believe事件解析器
+pub struct BelieveEventParser {
+    inner: GenericEventParser,
+}
+
+impl BelieveEventParser {
+    pub fn new() -> Self {
+        // 配置所有事件类型
+        let configs = vec![
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::INITIALIZE_POOL_EVENT,
+                instruction_discriminator: discriminators::INITIALIZE_POOL_IX,
+                event_type: EventType::BelieveCreateToken,
+                inner_instruction_parser: Self::parse_create_token_inner_instruction,
+                instruction_parser: Self::parse_create_token_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::SWAP_EVENT,
+                instruction_discriminator: discriminators::SWAP_IX,
+                event_type: EventType::BelieveTrade,
+                inner_instruction_parser: Self::parse_trade_inner_instruction,
+                instruction_parser: Self::parse_trade_instruction,
+                layouts: Vec::new(),
+            },
+        ];
+
+        let instruction_decoder =
+            InstructionDecoder::from_idl_json(BELIEVE_PROGRAM_ID, ProtocolType::Believe, IDL)
+                .expect("bundled IDL is valid");
+        let inner = GenericEventParser::new(BELIEVE_PROGRAM_ID, ProtocolType::Believe, configs)
+            .with_instruction_decoder(instruction_decoder);
+
+        Self { inner }
+    }
+
+    /// 解析创建代币事件
+    fn parse_create_token_inner_instruction(
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let event = borsh::from_slice::<BelieveCreateTokenEvent>(data).ok()?;
+        if !is_pool_config(&event.config) {
+            return None;
+        }
+        Some(Box::new(BelieveCreateTokenEvent { metadata, ..event }))
+    }
+
+    /// 解析交易事件
+    fn parse_trade_inner_instruction(
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let event = borsh::from_slice::<BelieveTradeEvent>(data).ok()?;
+        if !is_pool_config(&event.config) {
+            return None;
+        }
+        let is_buy = event.trade_direction == 1;
+        Some(Box::new(BelieveTradeEvent {
+            metadata,
+            is_buy,
+            ..event
+        }))
+    }
+
+    /// 解析创建代币指令事件
+    fn parse_create_token_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 11 || !is_pool_config(&accounts[0]) {
+            return None;
+        }
+        let args = InitializePoolArgs::deserialize(&mut &data[..]).ok()?;
+        Some(Box::new(BelieveCreateTokenEvent {
+            metadata,
+            name: args.name,
+            symbol: args.symbol,
+            uri: args.uri,
+            config: accounts[0],
+            creator: accounts[2],
+            base_mint: accounts[3],
+            quote_mint: accounts[4],
+            pool: accounts[5],
+            base_vault: accounts[6],
+            quote_vault: accounts[7],
+            payer: accounts[10],
+            ..Default::default()
+        }))
+    }
+
+    /// 解析交易指令事件
+    fn parse_trade_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 13 || !is_pool_config(&accounts[1]) {
+            return None;
+        }
+        let requested_amount_in = read_u64_le(data, 0)?;
+        let minimum_amount_out = read_u64_le(data, 8)?;
+        // 未传推荐账户时该位置为程序ID
+        let referral_token_account = if accounts[12] == BELIEVE_PROGRAM_ID {
+            Pubkey::default()
+        } else {
+            accounts[12]
+        };
+        Some(Box::new(BelieveTradeEvent {
+            metadata,
+            config: accounts[1],
+            pool: accounts[2],
+            input_token_account: accounts[3],
+            output_token_account: accounts[4],
+            base_vault: accounts[5],
+            quote_vault: accounts[6],
+            base_mint: accounts[7],
+            quote_mint: accounts[8],
+            payer: accounts[9],
+            referral_token_account,
+            requested_amount_in,
+            minimum_amount_out,
+            ..Default::default()
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl EventParser for BelieveEventParser {
+    fn parse_events_from_inner_instruction(
+        &self,
+        inner_instruction: &UiCompiledInstruction,
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_inner_instruction(
+            inner_instruction,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        instruction: &CompiledInstruction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_instruction(
+            instruction,
+            accounts,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn get_inner_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>> {
+        self.inner.get_inner_instruction_configs()
+    }
+
+    fn get_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<Vec<u8>, Vec<GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        self.inner.get_protocol_type()
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        self.inner.get_program_id()
+    }
+
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.inner.should_handle(program_id)
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        self.inner.supported_program_ids()
+    }
+}
@@ -0,0 +1,155 @@
+use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::event_parser::common::{
+    discriminator::account_discriminator, read_pubkey, read_u64_le,
+};
+use crate::streaming::event_parser::protocols::bonk::PoolStatus;
+
+/// Denominator of LaunchLab fee rates (`trade_fee_rate`, `fee_rate`, `share_fee_rate`)
+pub const LAUNCHLAB_FEE_RATE_DENOMINATOR: u64 = 1_000_000;
+
+/// 账户鉴别器常量
+pub mod account_discriminators {
+    use super::account_discriminator;
+
+    pub const GLOBAL_CONFIG: [u8; 8] = account_discriminator("GlobalConfig");
+    pub const PLATFORM_CONFIG: [u8; 8] = account_discriminator("PlatformConfig");
+    pub const POOL_STATE: [u8; 8] = account_discriminator("PoolState");
+}
+
+/// LaunchLab全局配置账户 - 交易手续费和迁移门槛
+///
+/// One per curve type and quote mint; every pool created under it trades with its
+/// `trade_fee_rate` and must satisfy its minimums to migrate.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct LaunchLabGlobalConfig {
+    pub epoch: u64,
+    pub curve_type: u8,
+    pub index: u16,
+    pub migrate_fee: u64,
+    pub trade_fee_rate: u64,
+    pub max_share_fee_rate: u64,
+    pub min_base_supply: u64,
+    pub max_lock_rate: u64,
+    pub min_base_sell_rate: u64,
+    pub min_base_migrate_rate: u64,
+    pub min_quote_fund_raising: u64,
+    pub quote_mint: Pubkey,
+    pub protocol_fee_owner: Pubkey,
+    pub migrate_fee_owner: Pubkey,
+    pub migrate_to_amm_wallet: Pubkey,
+    pub migrate_to_cpswap_wallet: Pubkey,
+}
+
+/// LaunchLab平台配置账户 - 启动平台（如 bonk.fun）的手续费
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchLabPlatformConfig {
+    pub epoch: u64,
+    pub platform_fee_wallet: Pubkey,
+    pub platform_nft_wallet: Pubkey,
+    pub platform_scale: u64,
+    pub creator_scale: u64,
+    pub burn_scale: u64,
+    pub fee_rate: u64,
+    pub name: String,
+    pub web: String,
+    pub img: String,
+}
+
+impl LaunchLabPlatformConfig {
+    /// Decode the account data after the discriminator
+    ///
+    /// Name, web and image are fixed-size, zero padded byte arrays.
+    fn from_bytes(data: &[u8]) -> Option<Self> {
+        let text = |offset: usize, len: usize| {
+            data.get(offset..offset + len)
+                .map(|bytes| {
+                    let end = bytes.iter().position(|b| *b == 0).unwrap_or(len);
+                    String::from_utf8_lossy(&bytes[..end]).into_owned()
+                })
+                .unwrap_or_default()
+        };
+        Some(Self {
+            epoch: read_u64_le(data, 0)?,
+            platform_fee_wallet: read_pubkey(data, 8)?,
+            platform_nft_wallet: read_pubkey(data, 40)?,
+            platform_scale: read_u64_le(data, 72)?,
+            creator_scale: read_u64_le(data, 80)?,
+            burn_scale: read_u64_le(data, 88)?,
+            fee_rate: read_u64_le(data, 96)?,
+            name: text(104, 64),
+            web: text(168, 256),
+            img: text(424, 256),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct VestingSchedule {
+    pub total_locked_amount: u64,
+    pub cliff_period: u64,
+    pub unlock_period: u64,
+    pub start_time: u64,
+    pub allocated_share_amount: u64,
+}
+
+/// LaunchLab池子账户 - 曲线参数、储备和所属配置
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct LaunchLabPoolState {
+    pub epoch: u64,
+    pub auth_bump: u8,
+    pub status: PoolStatus,
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+    pub migrate_type: u8,
+    pub supply: u64,
+    pub total_base_sell: u64,
+    pub virtual_base: u64,
+    pub virtual_quote: u64,
+    pub real_base: u64,
+    pub real_quote: u64,
+    pub total_quote_fund_raising: u64,
+    pub quote_protocol_fee: u64,
+    pub platform_fee: u64,
+    pub migrate_fee: u64,
+    pub vesting_schedule: VestingSchedule,
+    pub global_config: Pubkey,
+    pub platform_config: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub creator: Pubkey,
+}
+
+/// 解码后的LaunchLab账户
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LaunchLabAccount {
+    GlobalConfig(LaunchLabGlobalConfig),
+    PlatformConfig(LaunchLabPlatformConfig),
+    PoolState(LaunchLabPoolState),
+}
+
+impl LaunchLabAccount {
+    /// Decode a LaunchLab account by its Anchor discriminator
+    ///
+    /// Trailing bytes (the programs' reserved padding) are ignored, so accounts keep
+    /// decoding when fields are appended there.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let (discriminator, mut body) = data.split_first_chunk::<8>()?;
+        match *discriminator {
+            account_discriminators::GLOBAL_CONFIG => LaunchLabGlobalConfig::deserialize(&mut body)
+                .ok()
+                .map(Self::GlobalConfig),
+            account_discriminators::PLATFORM_CONFIG => {
+                LaunchLabPlatformConfig::from_bytes(body).map(Self::PlatformConfig)
+            }
+            account_discriminators::POOL_STATE => LaunchLabPoolState::deserialize(&mut body)
+                .ok()
+                .map(Self::PoolState),
+            _ => None,
+        }
+    }
+}
@@ -48,6 +48,12 @@ pub struct BonkTradeEvent {
     #[borsh(skip)]
     pub quote_token_mint: Pubkey,
     #[borsh(skip)]
+    #[serde(default)]
+    pub global_config: Pubkey,
+    #[borsh(skip)]
+    #[serde(default)]
+    pub platform_config: Pubkey,
+    #[borsh(skip)]
     pub is_dev_create_token_trade: bool,
     #[borsh(skip)]
     pub is_bot: bool,
@@ -113,14 +119,17 @@ impl_unified_event!(
 
 /// 事件鉴别器常量
 pub mod discriminators {
+    use crate::anchor_event_discriminator;
+    use crate::streaming::event_parser::common::discriminator::instruction_discriminator;
+
     // 事件鉴别器
-    pub const TRADE_EVENT: &str = "0xe445a52e51cb9a1dbddb7fd34ee661ee";
-    pub const POOL_CREATE_EVENT: &str = "0xe445a52e51cb9a1d97d7e20976a173ae";
+    pub const TRADE_EVENT: &str = anchor_event_discriminator!("TradeEvent");
+    pub const POOL_CREATE_EVENT: &str = anchor_event_discriminator!("PoolCreateEvent");
 
     // 指令鉴别器
-    pub const BUY_EXACT_IN: &[u8] = &[250, 234, 13, 123, 213, 156, 19, 236];
-    pub const BUY_EXACT_OUT: &[u8] = &[24, 211, 116, 40, 105, 3, 153, 56];
-    pub const SELL_EXACT_IN: &[u8] = &[149, 39, 222, 155, 211, 124, 152, 26];
-    pub const SELL_EXACT_OUT: &[u8] = &[95, 200, 71, 34, 8, 9, 11, 166];
-    pub const INITIALIZE: &[u8] = &[175, 175, 109, 31, 13, 152, 155, 237];
+    pub const BUY_EXACT_IN: &[u8] = &instruction_discriminator("buy_exact_in");
+    pub const BUY_EXACT_OUT: &[u8] = &instruction_discriminator("buy_exact_out");
+    pub const SELL_EXACT_IN: &[u8] = &instruction_discriminator("sell_exact_in");
+    pub const SELL_EXACT_OUT: &[u8] = &instruction_discriminator("sell_exact_out");
+    pub const INITIALIZE: &[u8] = &instruction_discriminator("initialize");
 }
@@ -1,7 +1,9 @@
+pub mod accounts;
 pub mod events;
 pub mod parser;
 pub mod types;
 
+pub use accounts::*;
 pub use events::*;
 pub use parser::BonkEventParser;
 pub use types::*;
\ No newline at end of file
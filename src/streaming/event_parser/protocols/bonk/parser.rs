@@ -3,7 +3,8 @@ use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
 use solana_transaction_status::UiCompiledInstruction;
 
 use crate::streaming::event_parser::{
-    common::{utils::*, EventMetadata, EventType, ProtocolType},
+    common::{utils::*, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
     core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
     protocols::bonk::{
         discriminators, BonkPoolCreateEvent, BonkTradeEvent, ConstantCurve, CurveParams,
@@ -15,6 +16,9 @@ use crate::streaming::event_parser::{
 pub const BONK_PROGRAM_ID: Pubkey =
     solana_sdk::pubkey!("LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj");
 
+/// 指令解码所用的IDL子集
+const IDL: &str = include_str!("idl.json");
+
 /// Bonk事件解析器
 pub struct BonkEventParser {
     inner: GenericEventParser,
@@ -30,6 +34,7 @@ impl BonkEventParser {
                 event_type: EventType::BonkBuyExactIn,
                 inner_instruction_parser: Self::parse_trade_inner_instruction,
                 instruction_parser: Self::parse_buy_exact_in_instruction,
+                layouts: Vec::new(),
             },
             GenericEventParseConfig {
                 inner_instruction_discriminator: discriminators::TRADE_EVENT,
@@ -37,6 +42,7 @@ impl BonkEventParser {
                 event_type: EventType::BonkBuyExactOut,
                 inner_instruction_parser: Self::parse_trade_inner_instruction,
                 instruction_parser: Self::parse_buy_exact_out_instruction,
+                layouts: Vec::new(),
             },
             GenericEventParseConfig {
                 inner_instruction_discriminator: discriminators::TRADE_EVENT,
@@ -44,6 +50,7 @@ impl BonkEventParser {
                 event_type: EventType::BonkSellExactIn,
                 inner_instruction_parser: Self::parse_trade_inner_instruction,
                 instruction_parser: Self::parse_sell_exact_in_instruction,
+                layouts: Vec::new(),
             },
             GenericEventParseConfig {
                 inner_instruction_discriminator: discriminators::TRADE_EVENT,
@@ -51,6 +58,7 @@ impl BonkEventParser {
                 event_type: EventType::BonkSellExactOut,
                 inner_instruction_parser: Self::parse_trade_inner_instruction,
                 instruction_parser: Self::parse_sell_exact_out_instruction,
+                layouts: Vec::new(),
             },
             GenericEventParseConfig {
                 inner_instruction_discriminator: discriminators::POOL_CREATE_EVENT,
@@ -58,10 +66,15 @@ impl BonkEventParser {
                 event_type: EventType::BonkInitialize,
                 inner_instruction_parser: Self::parse_pool_create_inner_instruction,
                 instruction_parser: Self::parse_initialize_instruction,
+                layouts: Vec::new(),
             },
         ];
 
-        let inner = GenericEventParser::new(BONK_PROGRAM_ID, ProtocolType::Bonk, configs);
+        let instruction_decoder =
+            InstructionDecoder::from_idl_json(BONK_PROGRAM_ID, ProtocolType::Bonk, IDL)
+                .expect("bundled IDL is valid");
+        let inner = GenericEventParser::new(BONK_PROGRAM_ID, ProtocolType::Bonk, configs)
+            .with_instruction_decoder(instruction_decoder);
 
         Self { inner }
     }
@@ -72,8 +85,6 @@ impl BonkEventParser {
         metadata: EventMetadata,
     ) -> Option<Box<dyn UnifiedEvent>> {
         if let Ok(event) = borsh::from_slice::<BonkPoolCreateEvent>(data) {
-            let mut metadata = metadata;
-            metadata.set_id(format!("{}", metadata.signature,));
             Some(Box::new(BonkPoolCreateEvent {
                 metadata: metadata,
                 ..event
@@ -89,12 +100,6 @@ impl BonkEventParser {
         metadata: EventMetadata,
     ) -> Option<Box<dyn UnifiedEvent>> {
         if let Ok(event) = borsh::from_slice::<BonkTradeEvent>(data) {
-            let mut metadata = metadata;
-            metadata.set_id(format!(
-                "{}-{}",
-                metadata.signature,
-                event.pool_state.to_string()
-            ));
             if metadata.event_type == EventType::BonkBuyExactIn
                 || metadata.event_type == EventType::BonkBuyExactOut
             {
@@ -131,15 +136,14 @@ impl BonkEventParser {
         let minimum_amount_out = read_u64_le(data, 8)?;
         let share_fee_rate = read_u64_le(data, 16)?;
 
-        let mut metadata = metadata;
-        metadata.set_id(format!("{}-{}", metadata.signature, accounts[4]));
-
         Some(Box::new(BonkTradeEvent {
             metadata,
             amount_in,
             minimum_amount_out,
             share_fee_rate,
             payer: accounts[0],
+            global_config: accounts[2],
+            platform_config: accounts[3],
             pool_state: accounts[4],
             user_base_token: accounts[5],
             user_quote_token: accounts[6],
@@ -165,15 +169,14 @@ impl BonkEventParser {
         let maximum_amount_in = read_u64_le(data, 8)?;
         let share_fee_rate = read_u64_le(data, 16)?;
 
-        let mut metadata = metadata;
-        metadata.set_id(format!("{}-{}", metadata.signature, accounts[4]));
-
         Some(Box::new(BonkTradeEvent {
             metadata,
             amount_out,
             maximum_amount_in,
             share_fee_rate,
             payer: accounts[0],
+            global_config: accounts[2],
+            platform_config: accounts[3],
             pool_state: accounts[4],
             user_base_token: accounts[5],
             user_quote_token: accounts[6],
@@ -199,15 +202,14 @@ impl BonkEventParser {
         let minimum_amount_out = read_u64_le(data, 8)?;
         let share_fee_rate = read_u64_le(data, 16)?;
 
-        let mut metadata = metadata;
-        metadata.set_id(format!("{}-{}", metadata.signature, accounts[4]));
-
         Some(Box::new(BonkTradeEvent {
             metadata,
             amount_in,
             minimum_amount_out,
             share_fee_rate,
             payer: accounts[0],
+            global_config: accounts[2],
+            platform_config: accounts[3],
             pool_state: accounts[4],
             user_base_token: accounts[5],
             user_quote_token: accounts[6],
@@ -233,15 +235,14 @@ impl BonkEventParser {
         let maximum_amount_in = read_u64_le(data, 8)?;
         let share_fee_rate = read_u64_le(data, 16)?;
 
-        let mut metadata = metadata;
-        metadata.set_id(format!("{}-{}", metadata.signature, accounts[4]));
-
         Some(Box::new(BonkTradeEvent {
             metadata,
             amount_out,
             maximum_amount_in,
             share_fee_rate,
             payer: accounts[0],
+            global_config: accounts[2],
+            platform_config: accounts[3],
             pool_state: accounts[4],
             user_base_token: accounts[5],
             user_quote_token: accounts[6],
@@ -269,9 +270,6 @@ impl BonkEventParser {
         let curve_param = Self::parse_curve_params(data, &mut offset)?;
         let vesting_param = Self::parse_vesting_params(data, &mut offset)?;
 
-        let mut metadata = metadata;
-        metadata.set_id(format!("{}", metadata.signature));
-
         Some(Box::new(BonkPoolCreateEvent {
             metadata,
             payer: accounts[0],
@@ -422,7 +420,7 @@ impl EventParser for BonkEventParser {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Vec<Box<dyn UnifiedEvent>> {
         self.inner.parse_events_from_inner_instruction(
             inner_instruction,
@@ -442,7 +440,7 @@ impl EventParser for BonkEventParser {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Vec<Box<dyn UnifiedEvent>> {
         self.inner.parse_events_from_instruction(
             instruction,
@@ -455,6 +453,10 @@ impl EventParser for BonkEventParser {
         )
     }
 
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
     fn should_handle(&self, program_id: &Pubkey) -> bool {
         self.inner.should_handle(program_id)
     }
@@ -466,6 +468,10 @@ impl EventParser for BonkEventParser {
     fn get_inner_instruction_configs(&self) -> &std::collections::HashMap<&'static str, Vec<crate::streaming::event_parser::core::traits::GenericEventParseConfig>> {
         self.inner.get_inner_instruction_configs()
     }
+
+    fn get_instruction_configs(&self) -> &std::collections::HashMap<Vec<u8>, Vec<crate::streaming::event_parser::core::traits::GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
     
     fn get_protocol_type(&self) -> ProtocolType {
         ProtocolType::Bonk
@@ -0,0 +1,106 @@
+use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::EventMetadata;
+
+/// 创建代币
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct BoopCreateTokenEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    #[borsh(skip)]
+    pub salt: u64,
+    #[borsh(skip)]
+    pub mint: Pubkey,
+    #[borsh(skip)]
+    pub creator: Pubkey,
+    #[borsh(skip)]
+    pub config: Pubkey,
+}
+
+impl_unified_event!(BoopCreateTokenEvent, name, symbol, uri);
+
+/// 买入/卖出
+///
+/// `TokenBought` and `TokenSold` share this layout; for buys `amount_in` is SOL and
+/// `amount_out` tokens, for sells the other way round.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct BoopTradeEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub swap_fee: u64,
+    /// Buyer or seller
+    pub user: Pubkey,
+    pub recipient: Pubkey,
+    #[borsh(skip)]
+    pub is_buy: bool,
+    #[borsh(skip)]
+    pub bonding_curve: Pubkey,
+    #[borsh(skip)]
+    pub bonding_curve_vault: Pubkey,
+    #[borsh(skip)]
+    pub bonding_curve_sol_vault: Pubkey,
+    #[borsh(skip)]
+    pub trading_fees_vault: Pubkey,
+    #[borsh(skip)]
+    pub user_token_account: Pubkey,
+    /// SOL to spend on buys, tokens to sell on sells
+    #[borsh(skip)]
+    pub amount: u64,
+    #[borsh(skip)]
+    pub amount_out_min: u64,
+}
+
+impl_unified_event!(
+    BoopTradeEvent,
+    mint,
+    amount_in,
+    amount_out,
+    swap_fee,
+    user,
+    recipient
+);
+
+/// 毕业 - 联合曲线完成并迁移流动性
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct BoopGraduateEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub mint: Pubkey,
+    pub sol_for_liquidity: u64,
+    pub graduation_fee: u64,
+    #[borsh(skip)]
+    pub bonding_curve: Pubkey,
+    #[borsh(skip)]
+    pub bonding_curve_sol_vault: Pubkey,
+    #[borsh(skip)]
+    pub operator: Pubkey,
+}
+
+impl_unified_event!(BoopGraduateEvent, mint, sol_for_liquidity, graduation_fee);
+
+/// 事件鉴别器常量
+pub mod discriminators {
+    use crate::anchor_event_discriminator;
+    use crate::streaming::event_parser::common::discriminator::instruction_discriminator;
+
+    // 事件鉴别器
+    pub const TOKEN_CREATED_EVENT: &str = anchor_event_discriminator!("TokenCreated");
+    pub const TOKEN_BOUGHT_EVENT: &str = anchor_event_discriminator!("TokenBought");
+    pub const TOKEN_SOLD_EVENT: &str = anchor_event_discriminator!("TokenSold");
+    pub const TOKEN_GRADUATED_EVENT: &str = anchor_event_discriminator!("TokenGraduated");
+
+    // 指令鉴别器
+    pub const CREATE_TOKEN_IX: &[u8] = &instruction_discriminator("create_token");
+    pub const BUY_TOKEN_IX: &[u8] = &instruction_discriminator("buy_token");
+    pub const SELL_TOKEN_IX: &[u8] = &instruction_discriminator("sell_token");
+    pub const GRADUATE_IX: &[u8] = &instruction_discriminator("graduate");
+}
@@ -0,0 +1,284 @@
+use borsh::BorshDeserialize;
+use prost_types::Timestamp;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::UiCompiledInstruction;
+
+use crate::streaming::event_parser::{
+    common::{read_u64_le, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
+    core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
+    protocols::boop::{discriminators, BoopCreateTokenEvent, BoopGraduateEvent, BoopTradeEvent},
+};
+
+/// Boop程序ID
+pub const BOOP_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("boop8hVGQGqehUK2iVEMEnMrL5RbjywRzHKBmBE7ry4");
+
+/// 指令解码所用的IDL子集
+const IDL: &str = include_str!("idl.json");
+
+/// create_token 指令参数
+#[derive(BorshDeserialize)]
+struct CreateTokenArgs {
+    salt: u64,
+    name: String,
+    symbol: String,
+    uri: String,
+}
+
+/// Boop事件解析器
+pub struct BoopEventParser {
+    inner: GenericEventParser,
+}
+
+impl BoopEventParser {
+    pub fn new() -> Self {
+        // 配置所有事件类型
+        let configs = vec![
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::TOKEN_CREATED_EVENT,
+                instruction_discriminator: discriminators::CREATE_TOKEN_IX,
+                event_type: EventType::BoopCreateToken,
+                inner_instruction_parser: Self::parse_create_token_inner_instruction,
+                instruction_parser: Self::parse_create_token_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::TOKEN_BOUGHT_EVENT,
+                instruction_discriminator: discriminators::BUY_TOKEN_IX,
+                event_type: EventType::BoopBuy,
+                inner_instruction_parser: Self::parse_trade_inner_instruction,
+                instruction_parser: Self::parse_buy_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::TOKEN_SOLD_EVENT,
+                instruction_discriminator: discriminators::SELL_TOKEN_IX,
+                event_type: EventType::BoopSell,
+                inner_instruction_parser: Self::parse_trade_inner_instruction,
+                instruction_parser: Self::parse_sell_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::TOKEN_GRADUATED_EVENT,
+                instruction_discriminator: discriminators::GRADUATE_IX,
+                event_type: EventType::BoopGraduate,
+                inner_instruction_parser: Self::parse_graduate_inner_instruction,
+                instruction_parser: Self::parse_graduate_instruction,
+                layouts: Vec::new(),
+            },
+        ];
+
+        let instruction_decoder =
+            InstructionDecoder::from_idl_json(BOOP_PROGRAM_ID, ProtocolType::Boop, IDL)
+                .expect("bundled IDL is valid");
+        let inner = GenericEventParser::new(BOOP_PROGRAM_ID, ProtocolType::Boop, configs)
+            .with_instruction_decoder(instruction_decoder);
+
+        Self { inner }
+    }
+
+    /// 解析创建代币事件
+    fn parse_create_token_inner_instruction(
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let event = borsh::from_slice::<BoopCreateTokenEvent>(data).ok()?;
+        Some(Box::new(BoopCreateTokenEvent { metadata, ..event }))
+    }
+
+    /// 解析买入/卖出事件
+    fn parse_trade_inner_instruction(
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let event = borsh::from_slice::<BoopTradeEvent>(data).ok()?;
+        let is_buy = metadata.event_type == EventType::BoopBuy;
+        Some(Box::new(BoopTradeEvent {
+            metadata,
+            is_buy,
+            ..event
+        }))
+    }
+
+    /// 解析毕业事件
+    fn parse_graduate_inner_instruction(
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let event = borsh::from_slice::<BoopGraduateEvent>(data).ok()?;
+        Some(Box::new(BoopGraduateEvent { metadata, ..event }))
+    }
+
+    /// 解析创建代币指令事件
+    fn parse_create_token_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 8 {
+            return None;
+        }
+        let args = CreateTokenArgs::deserialize(&mut &data[..]).ok()?;
+        Some(Box::new(BoopCreateTokenEvent {
+            metadata,
+            name: args.name,
+            symbol: args.symbol,
+            uri: args.uri,
+            salt: args.salt,
+            mint: accounts[2],
+            creator: accounts[3],
+            config: accounts[0],
+        }))
+    }
+
+    /// 解析买入指令事件
+    fn parse_buy_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 13 {
+            return None;
+        }
+        let buy_amount = read_u64_le(data, 0)?;
+        let amount_out_min = read_u64_le(data, 8)?;
+        Some(Box::new(BoopTradeEvent {
+            metadata,
+            mint: accounts[0],
+            bonding_curve: accounts[1],
+            trading_fees_vault: accounts[2],
+            bonding_curve_vault: accounts[3],
+            bonding_curve_sol_vault: accounts[4],
+            user_token_account: accounts[5],
+            user: accounts[6],
+            recipient: accounts[6],
+            amount: buy_amount,
+            amount_out_min,
+            is_buy: true,
+            ..Default::default()
+        }))
+    }
+
+    /// 解析卖出指令事件
+    fn parse_sell_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 12 {
+            return None;
+        }
+        let sell_amount = read_u64_le(data, 0)?;
+        let amount_out_min = read_u64_le(data, 8)?;
+        Some(Box::new(BoopTradeEvent {
+            metadata,
+            mint: accounts[0],
+            bonding_curve: accounts[1],
+            trading_fees_vault: accounts[2],
+            bonding_curve_vault: accounts[3],
+            bonding_curve_sol_vault: accounts[4],
+            user_token_account: accounts[5],
+            user: accounts[6],
+            recipient: accounts[7],
+            amount: sell_amount,
+            amount_out_min,
+            is_buy: false,
+            ..Default::default()
+        }))
+    }
+
+    /// 解析毕业指令事件
+    fn parse_graduate_instruction(
+        _data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 12 {
+            return None;
+        }
+        Some(Box::new(BoopGraduateEvent {
+            metadata,
+            mint: accounts[0],
+            bonding_curve_sol_vault: accounts[6],
+            bonding_curve: accounts[7],
+            operator: accounts[10],
+            ..Default::default()
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl EventParser for BoopEventParser {
+    fn parse_events_from_inner_instruction(
+        &self,
+        inner_instruction: &UiCompiledInstruction,
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_inner_instruction(
+            inner_instruction,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        instruction: &CompiledInstruction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_instruction(
+            instruction,
+            accounts,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn get_inner_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>> {
+        self.inner.get_inner_instruction_configs()
+    }
+
+    fn get_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<Vec<u8>, Vec<GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        self.inner.get_protocol_type()
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        self.inner.get_program_id()
+    }
+
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.inner.should_handle(program_id)
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        self.inner.supported_program_ids()
+    }
+}
@@ -0,0 +1,122 @@
+use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::protocols::drift::types::{
+    LiquidatePerpRecord, LiquidateSpotRecord, LiquidationType, MarketType, OrderAction,
+    OrderStatus, OrderTriggerCondition, OrderType, PositionDirection,
+};
+
+/// 成交 (OrderActionRecord, action = Fill)
+///
+/// One record per fill; a single fill instruction can produce several. Amounts are
+/// in Drift precision: base in 1e9 for perps or the token's decimals for spot,
+/// quote and prices in 1e6. `action_explanation` is the raw
+/// `OrderActionExplanation` variant, e.g. 6 for a fill against the AMM and 8 for a
+/// fill against a maker order.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct DriftFillEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub ts: i64,
+    pub action: OrderAction,
+    pub action_explanation: u8,
+    pub market_index: u16,
+    pub market_type: MarketType,
+    pub filler: Option<Pubkey>,
+    pub filler_reward: Option<u64>,
+    pub fill_record_id: Option<u64>,
+    pub base_asset_amount_filled: Option<u64>,
+    pub quote_asset_amount_filled: Option<u64>,
+    pub taker_fee: Option<u64>,
+    /// Negative for a maker rebate
+    pub maker_fee: Option<i64>,
+    pub referrer_reward: Option<u32>,
+    pub quote_asset_amount_surplus: Option<i64>,
+    pub spot_fulfillment_method_fee: Option<u64>,
+    pub taker: Option<Pubkey>,
+    pub taker_order_id: Option<u32>,
+    pub taker_order_direction: Option<PositionDirection>,
+    pub taker_order_base_asset_amount: Option<u64>,
+    pub taker_order_cumulative_base_asset_amount_filled: Option<u64>,
+    pub taker_order_cumulative_quote_asset_amount_filled: Option<u64>,
+    /// None when the taker was filled by the AMM
+    pub maker: Option<Pubkey>,
+    pub maker_order_id: Option<u32>,
+    pub maker_order_direction: Option<PositionDirection>,
+    pub maker_order_base_asset_amount: Option<u64>,
+    pub maker_order_cumulative_base_asset_amount_filled: Option<u64>,
+    pub maker_order_cumulative_quote_asset_amount_filled: Option<u64>,
+    pub oracle_price: i64,
+}
+
+impl_unified_event!(DriftFillEvent,);
+
+/// 下单 (OrderRecord)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct DriftOrderEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub ts: i64,
+    pub user: Pubkey,
+    pub slot: u64,
+    pub price: u64,
+    pub base_asset_amount: u64,
+    pub base_asset_amount_filled: u64,
+    pub quote_asset_amount_filled: u64,
+    pub trigger_price: u64,
+    pub auction_start_price: i64,
+    pub auction_end_price: i64,
+    pub max_ts: i64,
+    pub oracle_price_offset: i32,
+    pub order_id: u32,
+    pub market_index: u16,
+    pub status: OrderStatus,
+    pub order_type: OrderType,
+    pub market_type: MarketType,
+    pub user_order_id: u8,
+    pub existing_position_direction: PositionDirection,
+    pub direction: PositionDirection,
+    pub reduce_only: bool,
+    pub post_only: bool,
+    pub immediate_or_cancel: bool,
+    pub trigger_condition: OrderTriggerCondition,
+    pub auction_duration: u8,
+}
+
+impl_unified_event!(DriftOrderEvent,);
+
+/// 清算 (LiquidationRecord)
+///
+/// Only the detail record matching `liquidation_type` is filled in; the others
+/// are zeroed by the program.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct DriftLiquidationEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub ts: i64,
+    pub liquidation_type: LiquidationType,
+    pub user: Pubkey,
+    pub liquidator: Pubkey,
+    pub margin_requirement: u128,
+    pub total_collateral: i128,
+    pub margin_freed: u64,
+    pub liquidation_id: u16,
+    pub bankrupt: bool,
+    pub canceled_order_ids: Vec<u32>,
+    pub liquidate_perp: LiquidatePerpRecord,
+    pub liquidate_spot: LiquidateSpotRecord,
+}
+
+impl_unified_event!(DriftLiquidationEvent,);
+
+/// 事件鉴别器常量
+pub mod discriminators {
+    use crate::anchor_event_discriminator;
+
+    pub const ORDER_ACTION_RECORD_EVENT: &str = anchor_event_discriminator!("OrderActionRecord");
+    pub const ORDER_RECORD_EVENT: &str = anchor_event_discriminator!("OrderRecord");
+    pub const LIQUIDATION_RECORD_EVENT: &str = anchor_event_discriminator!("LiquidationRecord");
+}
@@ -0,0 +1,170 @@
+use borsh::BorshDeserialize;
+use prost_types::Timestamp;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::UiCompiledInstruction;
+
+use crate::streaming::event_parser::{
+    common::{EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
+    core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
+    protocols::drift::{
+        discriminators, DriftFillEvent, DriftLiquidationEvent, DriftOrderEvent, MarketType,
+        OrderAction,
+    },
+};
+
+/// Drift v2程序ID
+pub const DRIFT_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("dRiftyHA39MWEi3m9aunc5MzRF1JYuBsbn6VPcn33UH");
+
+/// Drift事件解析器
+///
+/// Drift reports fills, orders and liquidations only through `emit!` log records,
+/// so every event comes from the transaction logs and is emitted on its own.
+/// Records are decoded up to the fields listed here; fields appended by newer
+/// program versions are ignored.
+pub struct DriftEventParser {
+    inner: GenericEventParser,
+}
+
+impl DriftEventParser {
+    pub fn new() -> Self {
+        let configs = vec![
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::ORDER_ACTION_RECORD_EVENT,
+                instruction_discriminator: &[],
+                event_type: EventType::DriftPerpFill,
+                inner_instruction_parser: Self::parse_order_action_record,
+                instruction_parser: Self::parse_none_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::ORDER_RECORD_EVENT,
+                instruction_discriminator: &[],
+                event_type: EventType::DriftPlaceOrder,
+                inner_instruction_parser: Self::parse_order_record,
+                instruction_parser: Self::parse_none_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::LIQUIDATION_RECORD_EVENT,
+                instruction_discriminator: &[],
+                event_type: EventType::DriftLiquidation,
+                inner_instruction_parser: Self::parse_liquidation_record,
+                instruction_parser: Self::parse_none_instruction,
+                layouts: Vec::new(),
+            },
+        ];
+
+        let inner = GenericEventParser::new(DRIFT_PROGRAM_ID, ProtocolType::Drift, configs);
+
+        Self { inner }
+    }
+
+    /// 解析成交记录，非成交动作忽略
+    fn parse_order_action_record(
+        data: &[u8],
+        mut metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let mut event = DriftFillEvent::deserialize(&mut &data[..]).ok()?;
+        if event.action != OrderAction::Fill {
+            return None;
+        }
+        metadata.event_type = match event.market_type {
+            MarketType::Perp => EventType::DriftPerpFill,
+            MarketType::Spot => EventType::DriftSpotFill,
+        };
+        event.metadata = metadata;
+        Some(Box::new(event))
+    }
+
+    /// 解析下单记录
+    fn parse_order_record(data: &[u8], metadata: EventMetadata) -> Option<Box<dyn UnifiedEvent>> {
+        let mut event = DriftOrderEvent::deserialize(&mut &data[..]).ok()?;
+        event.metadata = metadata;
+        Some(Box::new(event))
+    }
+
+    /// 解析清算记录
+    fn parse_liquidation_record(
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let mut event = DriftLiquidationEvent::deserialize(&mut &data[..]).ok()?;
+        event.metadata = metadata;
+        Some(Box::new(event))
+    }
+
+    /// 事件只来自日志
+    fn parse_none_instruction(
+        _data: &[u8],
+        _accounts: &[Pubkey],
+        _metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        None
+    }
+}
+
+#[async_trait::async_trait]
+impl EventParser for DriftEventParser {
+    fn parse_events_from_inner_instruction(
+        &self,
+        _inner_instruction: &UiCompiledInstruction,
+        _signature: &str,
+        _slot: u64,
+        _block_time: Option<Timestamp>,
+        _program_received_time_ms: i64,
+        _index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        vec![]
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        _instruction: &CompiledInstruction,
+        _accounts: &[Pubkey],
+        _signature: &str,
+        _slot: u64,
+        _block_time: Option<Timestamp>,
+        _program_received_time_ms: i64,
+        _index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        vec![]
+    }
+
+    fn get_inner_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>> {
+        self.inner.get_inner_instruction_configs()
+    }
+
+    fn get_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<Vec<u8>, Vec<GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        self.inner.get_protocol_type()
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        self.inner.get_program_id()
+    }
+
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
+    fn emits_log_events(&self) -> bool {
+        true
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.inner.should_handle(program_id)
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        self.inner.supported_program_ids()
+    }
+}
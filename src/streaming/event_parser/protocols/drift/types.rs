@@ -0,0 +1,95 @@
+use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub enum MarketType {
+    #[default]
+    Spot,
+    Perp,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub enum PositionDirection {
+    #[default]
+    Long,
+    Short,
+}
+
+/// 订单动作
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub enum OrderAction {
+    #[default]
+    Place,
+    Cancel,
+    Fill,
+    Trigger,
+    Expire,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub enum OrderStatus {
+    #[default]
+    Init,
+    Open,
+    Filled,
+    Canceled,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub enum OrderType {
+    #[default]
+    Market,
+    Limit,
+    TriggerMarket,
+    TriggerLimit,
+    /// Market order with an auction priced off the oracle
+    Oracle,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub enum OrderTriggerCondition {
+    #[default]
+    Above,
+    Below,
+    TriggeredAbove,
+    TriggeredBelow,
+}
+
+/// 清算类型
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub enum LiquidationType {
+    #[default]
+    LiquidatePerp,
+    LiquidateSpot,
+    LiquidateBorrowForPerpPnl,
+    LiquidatePerpPnlForDeposit,
+    PerpBankruptcy,
+    SpotBankruptcy,
+}
+
+/// 永续合约清算明细，仅在 `LiquidatePerp` 时有值
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct LiquidatePerpRecord {
+    pub market_index: u16,
+    pub oracle_price: i64,
+    pub base_asset_amount: i64,
+    pub quote_asset_amount: i64,
+    pub lp_shares: u64,
+    pub fill_record_id: u64,
+    pub user_order_id: u32,
+    pub liquidator_order_id: u32,
+    pub liquidator_fee: u64,
+    pub if_fee: u64,
+}
+
+/// 现货清算明细，仅在 `LiquidateSpot` 时有值
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct LiquidateSpotRecord {
+    pub asset_market_index: u16,
+    pub asset_price: i64,
+    pub asset_transfer: u128,
+    pub liability_market_index: u16,
+    pub liability_price: i64,
+    pub liability_transfer: u128,
+    pub if_fee: u64,
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::EventMetadata;
+
+/// 存款/取款/借款/还款
+///
+/// The event type tells the action apart. `amount` is in native units of
+/// `liquidity_mint`, except for withdrawals where it is the amount of reserve
+/// collateral tokens redeemed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KaminoLendingEvent {
+    pub metadata: EventMetadata,
+    pub owner: Pubkey,
+    pub obligation: Pubkey,
+    pub lending_market: Pubkey,
+    pub reserve: Pubkey,
+    pub liquidity_mint: Pubkey,
+    /// Token account paying for deposits and repayments or receiving withdrawals
+    /// and borrows
+    pub user_token_account: Pubkey,
+    pub amount: u64,
+}
+
+impl_unified_event!(KaminoLendingEvent,);
+
+/// 清算
+///
+/// The liquidator repays up to `liquidity_amount` of the obligation's debt in
+/// `repay_reserve` and receives collateral of `withdraw_reserve`, redeemed to its
+/// liquidity.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KaminoLiquidationEvent {
+    pub metadata: EventMetadata,
+    pub liquidator: Pubkey,
+    pub obligation: Pubkey,
+    pub lending_market: Pubkey,
+    pub repay_reserve: Pubkey,
+    pub repay_mint: Pubkey,
+    pub withdraw_reserve: Pubkey,
+    pub withdraw_mint: Pubkey,
+    pub user_source_liquidity: Pubkey,
+    pub user_destination_liquidity: Pubkey,
+    pub liquidity_amount: u64,
+    pub min_acceptable_received_liquidity_amount: u64,
+    pub max_allowed_ltv_override_percent: u64,
+}
+
+impl_unified_event!(KaminoLiquidationEvent,);
+
+/// 指令鉴别器常量
+///
+/// The `_v2` instructions add farm accounts after the accounts of the original
+/// instruction and share its layout otherwise.
+pub mod discriminators {
+    use crate::streaming::event_parser::common::discriminator::instruction_discriminator;
+
+    pub const DEPOSIT_IX: &[u8] =
+        &instruction_discriminator("deposit_reserve_liquidity_and_obligation_collateral");
+    pub const DEPOSIT_V2_IX: &[u8] =
+        &instruction_discriminator("deposit_reserve_liquidity_and_obligation_collateral_v2");
+    pub const WITHDRAW_IX: &[u8] =
+        &instruction_discriminator("withdraw_obligation_collateral_and_redeem_reserve_collateral");
+    pub const WITHDRAW_V2_IX: &[u8] = &instruction_discriminator(
+        "withdraw_obligation_collateral_and_redeem_reserve_collateral_v2",
+    );
+    pub const BORROW_IX: &[u8] = &instruction_discriminator("borrow_obligation_liquidity");
+    pub const BORROW_V2_IX: &[u8] = &instruction_discriminator("borrow_obligation_liquidity_v2");
+    pub const REPAY_IX: &[u8] = &instruction_discriminator("repay_obligation_liquidity");
+    pub const REPAY_V2_IX: &[u8] = &instruction_discriminator("repay_obligation_liquidity_v2");
+    pub const LIQUIDATE_IX: &[u8] =
+        &instruction_discriminator("liquidate_obligation_and_redeem_reserve_collateral");
+    pub const LIQUIDATE_V2_IX: &[u8] =
+        &instruction_discriminator("liquidate_obligation_and_redeem_reserve_collateral_v2");
+}
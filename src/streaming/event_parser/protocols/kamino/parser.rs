@@ -0,0 +1,276 @@
+use prost_types::Timestamp;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::UiCompiledInstruction;
+
+use crate::streaming::event_parser::{
+    common::{read_u64_le, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
+    core::traits::{
+        EventParser, GenericEventParseConfig, GenericEventParser, InstructionEventParser,
+        UnifiedEvent,
+    },
+    protocols::kamino::{discriminators, KaminoLendingEvent, KaminoLiquidationEvent},
+};
+
+/// Kamino Lend程序ID
+pub const KAMINO_LEND_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("KLend2g3cP87fffoy8q1mQqGKjrxjC8boSyAYavgmjD");
+
+/// 指令解码所用的IDL子集
+const IDL: &str = include_str!("idl.json");
+
+/// Kamino Lend事件解析器
+pub struct KaminoEventParser {
+    inner: GenericEventParser,
+}
+
+impl KaminoEventParser {
+    pub fn new() -> Self {
+        let instructions: [(&'static [u8], EventType, InstructionEventParser); 10] = [
+            (
+                discriminators::DEPOSIT_IX,
+                EventType::KaminoDeposit,
+                Self::parse_deposit_instruction,
+            ),
+            (
+                discriminators::DEPOSIT_V2_IX,
+                EventType::KaminoDeposit,
+                Self::parse_deposit_instruction,
+            ),
+            (
+                discriminators::WITHDRAW_IX,
+                EventType::KaminoWithdraw,
+                Self::parse_deposit_instruction,
+            ),
+            (
+                discriminators::WITHDRAW_V2_IX,
+                EventType::KaminoWithdraw,
+                Self::parse_deposit_instruction,
+            ),
+            (
+                discriminators::BORROW_IX,
+                EventType::KaminoBorrow,
+                Self::parse_borrow_instruction,
+            ),
+            (
+                discriminators::BORROW_V2_IX,
+                EventType::KaminoBorrow,
+                Self::parse_borrow_instruction,
+            ),
+            (
+                discriminators::REPAY_IX,
+                EventType::KaminoRepay,
+                Self::parse_repay_instruction,
+            ),
+            (
+                discriminators::REPAY_V2_IX,
+                EventType::KaminoRepay,
+                Self::parse_repay_instruction,
+            ),
+            (
+                discriminators::LIQUIDATE_IX,
+                EventType::KaminoLiquidate,
+                Self::parse_liquidate_instruction,
+            ),
+            (
+                discriminators::LIQUIDATE_V2_IX,
+                EventType::KaminoLiquidate,
+                Self::parse_liquidate_instruction,
+            ),
+        ];
+        let configs = instructions
+            .into_iter()
+            .map(
+                |(instruction_discriminator, event_type, instruction_parser)| {
+                    GenericEventParseConfig {
+                        inner_instruction_discriminator: "",
+                        instruction_discriminator,
+                        event_type,
+                        inner_instruction_parser: Self::parse_none_inner_instruction,
+                        instruction_parser,
+                        layouts: Vec::new(),
+                    }
+                },
+            )
+            .collect();
+
+        let instruction_decoder =
+            InstructionDecoder::from_idl_json(KAMINO_LEND_PROGRAM_ID, ProtocolType::Kamino, IDL)
+                .expect("bundled IDL is valid");
+        let inner = GenericEventParser::new(KAMINO_LEND_PROGRAM_ID, ProtocolType::Kamino, configs)
+            .with_instruction_decoder(instruction_decoder);
+
+        Self { inner }
+    }
+
+    /// 事件只来自指令
+    fn parse_none_inner_instruction(
+        _data: &[u8],
+        _metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        None
+    }
+
+    /// 解析存款/取款指令事件，两者账户布局相同
+    fn parse_deposit_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 10 {
+            return None;
+        }
+        Some(Box::new(KaminoLendingEvent {
+            metadata,
+            owner: accounts[0],
+            obligation: accounts[1],
+            lending_market: accounts[2],
+            reserve: accounts[4],
+            liquidity_mint: accounts[5],
+            user_token_account: accounts[9],
+            amount: read_u64_le(data, 0)?,
+        }))
+    }
+
+    /// 解析借款指令事件
+    fn parse_borrow_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 9 {
+            return None;
+        }
+        Some(Box::new(KaminoLendingEvent {
+            metadata,
+            owner: accounts[0],
+            obligation: accounts[1],
+            lending_market: accounts[2],
+            reserve: accounts[4],
+            liquidity_mint: accounts[5],
+            user_token_account: accounts[8],
+            amount: read_u64_le(data, 0)?,
+        }))
+    }
+
+    /// 解析还款指令事件
+    fn parse_repay_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 7 {
+            return None;
+        }
+        Some(Box::new(KaminoLendingEvent {
+            metadata,
+            owner: accounts[0],
+            obligation: accounts[1],
+            lending_market: accounts[2],
+            reserve: accounts[3],
+            liquidity_mint: accounts[4],
+            user_token_account: accounts[6],
+            amount: read_u64_le(data, 0)?,
+        }))
+    }
+
+    /// 解析清算指令事件
+    fn parse_liquidate_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 16 {
+            return None;
+        }
+        Some(Box::new(KaminoLiquidationEvent {
+            metadata,
+            liquidator: accounts[0],
+            obligation: accounts[1],
+            lending_market: accounts[2],
+            repay_reserve: accounts[4],
+            repay_mint: accounts[5],
+            withdraw_reserve: accounts[7],
+            withdraw_mint: accounts[8],
+            user_source_liquidity: accounts[13],
+            user_destination_liquidity: accounts[15],
+            liquidity_amount: read_u64_le(data, 0)?,
+            min_acceptable_received_liquidity_amount: read_u64_le(data, 8)?,
+            max_allowed_ltv_override_percent: read_u64_le(data, 16)?,
+        }))
+    }
+}
+#[async_trait::async_trait]
+impl EventParser for KaminoEventParser {
+    fn parse_events_from_inner_instruction(
+        &self,
+        inner_instruction: &UiCompiledInstruction,
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_inner_instruction(
+            inner_instruction,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        instruction: &CompiledInstruction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_instruction(
+            instruction,
+            accounts,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn get_inner_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>> {
+        self.inner.get_inner_instruction_configs()
+    }
+
+    fn get_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<Vec<u8>, Vec<GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        self.inner.get_protocol_type()
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        self.inner.get_program_id()
+    }
+
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.inner.should_handle(program_id)
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        self.inner.supported_program_ids()
+    }
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::EventMetadata;
+
+/// M2挂单/撤单
+///
+/// `price` is in lamports for `token_size` tokens of `token_mint`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MagicEdenListEvent {
+    pub metadata: EventMetadata,
+    pub seller: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_account: Pubkey,
+    pub auction_house: Pubkey,
+    pub seller_trade_state: Pubkey,
+    pub price: u64,
+    pub token_size: u64,
+    /// Unix timestamp, -1 for no expiry
+    pub expiry: i64,
+}
+
+impl_unified_event!(MagicEdenListEvent,);
+
+/// M2成交
+///
+/// Settles both instant buys and accepted offers. Fees are charged on `price`:
+/// the taker pays `taker_fee`, the maker pays `maker_fee`, which is negative for a
+/// maker rebate. Creator royalties are paid by the buyer on top of `price`
+/// according to the token metadata and are not part of the instruction.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MagicEdenSaleEvent {
+    pub metadata: EventMetadata,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub token_mint: Pubkey,
+    pub auction_house: Pubkey,
+    pub price: u64,
+    pub token_size: u64,
+    pub maker_fee_bp: i16,
+    pub taker_fee_bp: u16,
+    pub maker_fee: i64,
+    pub taker_fee: u64,
+}
+
+impl_unified_event!(MagicEdenSaleEvent,);
+
+/// 集合出价成交 (MMM池子买入NFT)
+///
+/// Collection offers on Magic Eden are MMM pools; selling into one fills the
+/// offer. The seller receives at least `min_payment_amount` lamports for
+/// `asset_amount` tokens.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MagicEdenBidFillEvent {
+    pub metadata: EventMetadata,
+    pub seller: Pubkey,
+    pub pool_owner: Pubkey,
+    pub pool: Pubkey,
+    pub asset_mint: Pubkey,
+    pub asset_amount: u64,
+    pub min_payment_amount: u64,
+    pub maker_fee_bp: i16,
+    pub taker_fee_bp: i16,
+}
+
+impl_unified_event!(MagicEdenBidFillEvent,);
+
+/// 指令鉴别器常量
+pub mod discriminators {
+    use crate::streaming::event_parser::common::discriminator::instruction_discriminator;
+
+    // M2
+    pub const SELL_IX: &[u8] = &instruction_discriminator("sell");
+    pub const CANCEL_SELL_IX: &[u8] = &instruction_discriminator("cancel_sell");
+    pub const EXECUTE_SALE_V2_IX: &[u8] = &instruction_discriminator("execute_sale_v2");
+
+    // MMM
+    pub const SOL_FULFILL_BUY_IX: &[u8] = &instruction_discriminator("sol_fulfill_buy");
+    pub const SOL_MIP1_FULFILL_BUY_IX: &[u8] = &instruction_discriminator("sol_mip1_fulfill_buy");
+}
@@ -0,0 +1,318 @@
+use borsh::BorshDeserialize;
+use prost_types::Timestamp;
+use solana_sdk::{
+    instruction::CompiledInstruction, pubkey::Pubkey, transaction::VersionedTransaction,
+};
+use solana_transaction_status::{UiCompiledInstruction, UiInnerInstructions};
+
+use crate::streaming::event_parser::{
+    common::{read_u64_le, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
+    core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
+    protocols::magic_eden::{
+        discriminators, MagicEdenBidFillEvent, MagicEdenListEvent, MagicEdenSaleEvent,
+    },
+};
+
+/// Magic Eden M2程序ID
+pub const MAGIC_EDEN_M2_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("M2mx93ekt1fmXSVkTrUL9xVFHkmME8HTUi5Cyc5aF7K");
+
+/// Magic Eden MMM (集合出价池) 程序ID
+pub const MAGIC_EDEN_MMM_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("mmm3XBJg5gk8XJxEKBvdgptZz6SgK4tXvn36sodowMc");
+
+/// execute_sale_v2 指令参数
+#[derive(BorshDeserialize)]
+struct ExecuteSaleV2Args {
+    _escrow_payment_bump: u8,
+    _program_as_signer_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+    _buyer_state_expiry: i64,
+    _seller_state_expiry: i64,
+    maker_fee_bp: i16,
+    taker_fee_bp: u16,
+}
+
+/// sol_fulfill_buy / sol_mip1_fulfill_buy 指令参数
+#[derive(BorshDeserialize)]
+struct SolFulfillBuyArgs {
+    asset_amount: u64,
+    min_payment_amount: u64,
+    _allowlist_aux: Option<String>,
+    maker_fee_bp: i16,
+    taker_fee_bp: i16,
+}
+
+/// Magic Eden事件解析器
+///
+/// Covers the M2 marketplace (listings and sales) and the MMM pools behind
+/// collection offers. Neither program emits events, so everything comes from the
+/// instructions.
+pub struct MagicEdenEventParser {
+    m2: GenericEventParser,
+    mmm: GenericEventParser,
+}
+
+impl MagicEdenEventParser {
+    pub fn new() -> Self {
+        let m2_configs = vec![
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::SELL_IX,
+                event_type: EventType::MagicEdenList,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_sell_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::CANCEL_SELL_IX,
+                event_type: EventType::MagicEdenDelist,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_cancel_sell_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::EXECUTE_SALE_V2_IX,
+                event_type: EventType::MagicEdenSale,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_execute_sale_instruction,
+                layouts: Vec::new(),
+            },
+        ];
+        let mmm_configs = vec![
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::SOL_FULFILL_BUY_IX,
+                event_type: EventType::MagicEdenBidFill,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_fulfill_buy_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::SOL_MIP1_FULFILL_BUY_IX,
+                event_type: EventType::MagicEdenBidFill,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_fulfill_buy_instruction,
+                layouts: Vec::new(),
+            },
+        ];
+
+        Self {
+            m2: GenericEventParser::new(
+                MAGIC_EDEN_M2_PROGRAM_ID,
+                ProtocolType::MagicEden,
+                m2_configs,
+            ),
+            mmm: GenericEventParser::new(
+                MAGIC_EDEN_MMM_PROGRAM_ID,
+                ProtocolType::MagicEden,
+                mmm_configs,
+            ),
+        }
+    }
+
+    fn parse_none_inner_instruction(
+        _data: &[u8],
+        _metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        None
+    }
+
+    /// 解析挂单指令事件
+    fn parse_sell_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 9 {
+            return None;
+        }
+        // 跳过 seller_state_bump, program_as_signer_bump
+        Some(Box::new(MagicEdenListEvent {
+            metadata,
+            seller: accounts[0],
+            token_account: accounts[2],
+            token_mint: accounts[4],
+            auction_house: accounts[7],
+            seller_trade_state: accounts[8],
+            price: read_u64_le(data, 2)?,
+            token_size: read_u64_le(data, 10)?,
+            expiry: read_u64_le(data, 18)? as i64,
+        }))
+    }
+
+    /// 解析撤单指令事件
+    fn parse_cancel_sell_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 8 {
+            return None;
+        }
+        Some(Box::new(MagicEdenListEvent {
+            metadata,
+            seller: accounts[0],
+            token_account: accounts[2],
+            token_mint: accounts[4],
+            auction_house: accounts[6],
+            seller_trade_state: accounts[7],
+            price: read_u64_le(data, 0)?,
+            token_size: read_u64_le(data, 8)?,
+            expiry: read_u64_le(data, 16)? as i64,
+        }))
+    }
+
+    /// 解析成交指令事件
+    fn parse_execute_sale_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 10 {
+            return None;
+        }
+        let args = ExecuteSaleV2Args::deserialize(&mut &data[..]).ok()?;
+        let fee = |bp: i64| (args.buyer_price as i128 * bp as i128 / 10_000) as i64;
+        Some(Box::new(MagicEdenSaleEvent {
+            metadata,
+            buyer: accounts[0],
+            seller: accounts[1],
+            token_mint: accounts[4],
+            auction_house: accounts[9],
+            price: args.buyer_price,
+            token_size: args.token_size,
+            maker_fee_bp: args.maker_fee_bp,
+            taker_fee_bp: args.taker_fee_bp,
+            maker_fee: fee(args.maker_fee_bp as i64),
+            taker_fee: fee(args.taker_fee_bp as i64) as u64,
+        }))
+    }
+
+    /// 解析集合出价成交指令事件
+    fn parse_fulfill_buy_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 8 {
+            return None;
+        }
+        let args = SolFulfillBuyArgs::deserialize(&mut &data[..]).ok()?;
+        Some(Box::new(MagicEdenBidFillEvent {
+            metadata,
+            seller: accounts[0],
+            pool_owner: accounts[1],
+            pool: accounts[4],
+            asset_mint: accounts[7],
+            asset_amount: args.asset_amount,
+            min_payment_amount: args.min_payment_amount,
+            maker_fee_bp: args.maker_fee_bp,
+            taker_fee_bp: args.taker_fee_bp,
+        }))
+    }
+
+    fn parsers(&self) -> impl Iterator<Item = &GenericEventParser> {
+        [&self.m2, &self.mmm].into_iter()
+    }
+}
+
+#[async_trait::async_trait]
+impl EventParser for MagicEdenEventParser {
+    fn parse_events_from_inner_instruction(
+        &self,
+        _inner_instruction: &UiCompiledInstruction,
+        _signature: &str,
+        _slot: u64,
+        _block_time: Option<Timestamp>,
+        _program_received_time_ms: i64,
+        _index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        vec![]
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        instruction: &CompiledInstruction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.parsers()
+            .flat_map(|parser| {
+                parser.parse_events_from_instruction(
+                    instruction,
+                    accounts,
+                    signature,
+                    slot,
+                    block_time,
+                    program_received_time_ms,
+                    index,
+                )
+            })
+            .collect()
+    }
+
+    fn get_inner_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>> {
+        self.m2.get_inner_instruction_configs()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        ProtocolType::MagicEden
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        MAGIC_EDEN_M2_PROGRAM_ID
+    }
+
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        None
+    }
+
+    fn capture_unknown_instructions(
+        &self,
+        versioned_tx: &VersionedTransaction,
+        inner_instructions: &[UiInnerInstructions],
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        [&self.m2, &self.mmm]
+            .into_iter()
+            .flat_map(|parser| {
+                parser.capture_unknown_instructions(
+                    versioned_tx,
+                    inner_instructions,
+                    accounts,
+                    signature,
+                    slot,
+                    block_time,
+                    program_received_time_ms,
+                )
+            })
+            .collect()
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.parsers()
+            .any(|parser| parser.should_handle(program_id))
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        self.parsers()
+            .flat_map(|parser| parser.supported_program_ids())
+            .collect()
+    }
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::EventMetadata;
+
+/// 存款/还款/取款/借款
+///
+/// The event type tells the action apart. `amount` is in native units of the
+/// bank's mint; with `all` set (`deposit_up_to_limit`, `repay_all` or
+/// `withdraw_all`) the program moves the whole balance or up to the deposit limit
+/// instead and `amount` is only an upper bound.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarginfiLendingEvent {
+    pub metadata: EventMetadata,
+    pub group: Pubkey,
+    pub marginfi_account: Pubkey,
+    pub authority: Pubkey,
+    pub bank: Pubkey,
+    /// Token account paying for deposits and repayments or receiving withdrawals
+    /// and borrows
+    pub user_token_account: Pubkey,
+    pub liquidity_vault: Pubkey,
+    pub amount: u64,
+    pub all: bool,
+}
+
+impl_unified_event!(MarginfiLendingEvent,);
+
+/// 清算
+///
+/// The liquidator takes `asset_amount` of the liquidatee's `asset_bank` deposit at
+/// a discount and assumes the matching liability in `liab_bank`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarginfiLiquidationEvent {
+    pub metadata: EventMetadata,
+    pub group: Pubkey,
+    pub asset_bank: Pubkey,
+    pub liab_bank: Pubkey,
+    pub liquidator_marginfi_account: Pubkey,
+    pub liquidator: Pubkey,
+    pub liquidatee_marginfi_account: Pubkey,
+    pub asset_amount: u64,
+}
+
+impl_unified_event!(MarginfiLiquidationEvent,);
+
+/// 指令鉴别器常量
+pub mod discriminators {
+    use crate::streaming::event_parser::common::discriminator::instruction_discriminator;
+
+    pub const DEPOSIT_IX: &[u8] = &instruction_discriminator("lending_account_deposit");
+    pub const REPAY_IX: &[u8] = &instruction_discriminator("lending_account_repay");
+    pub const WITHDRAW_IX: &[u8] = &instruction_discriminator("lending_account_withdraw");
+    pub const BORROW_IX: &[u8] = &instruction_discriminator("lending_account_borrow");
+    pub const LIQUIDATE_IX: &[u8] = &instruction_discriminator("lending_account_liquidate");
+}
@@ -0,0 +1,229 @@
+use prost_types::Timestamp;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::UiCompiledInstruction;
+
+use crate::streaming::event_parser::{
+    common::{read_u64_le, read_u8, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
+    core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
+    protocols::marginfi::{discriminators, MarginfiLendingEvent, MarginfiLiquidationEvent},
+};
+
+/// marginfi v2程序ID
+pub const MARGINFI_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("MFv2hWf31Z9kbCa1snEPYctwafyhdvnV7FZnsebVacA");
+
+/// 指令解码所用的IDL子集
+const IDL: &str = include_str!("idl.json");
+
+/// marginfi事件解析器
+pub struct MarginfiEventParser {
+    inner: GenericEventParser,
+}
+
+impl MarginfiEventParser {
+    pub fn new() -> Self {
+        let configs = vec![
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::DEPOSIT_IX,
+                event_type: EventType::MarginfiDeposit,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_deposit_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::REPAY_IX,
+                event_type: EventType::MarginfiRepay,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_deposit_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::WITHDRAW_IX,
+                event_type: EventType::MarginfiWithdraw,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_withdraw_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::BORROW_IX,
+                event_type: EventType::MarginfiBorrow,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_withdraw_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::LIQUIDATE_IX,
+                event_type: EventType::MarginfiLiquidate,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_liquidate_instruction,
+                layouts: Vec::new(),
+            },
+        ];
+
+        let instruction_decoder =
+            InstructionDecoder::from_idl_json(MARGINFI_PROGRAM_ID, ProtocolType::Marginfi, IDL)
+                .expect("bundled IDL is valid");
+        let inner = GenericEventParser::new(MARGINFI_PROGRAM_ID, ProtocolType::Marginfi, configs)
+            .with_instruction_decoder(instruction_decoder);
+
+        Self { inner }
+    }
+
+    /// 事件只来自指令
+    fn parse_none_inner_instruction(
+        _data: &[u8],
+        _metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        None
+    }
+
+    /// `Option<bool>` 参数，缺省为false
+    fn read_flag(data: &[u8], offset: usize) -> bool {
+        read_u8(data, offset) == Some(1) && read_u8(data, offset + 1) == Some(1)
+    }
+
+    /// 解析存款/还款指令事件
+    fn parse_deposit_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 6 {
+            return None;
+        }
+        Some(Box::new(MarginfiLendingEvent {
+            metadata,
+            group: accounts[0],
+            marginfi_account: accounts[1],
+            authority: accounts[2],
+            bank: accounts[3],
+            user_token_account: accounts[4],
+            liquidity_vault: accounts[5],
+            amount: read_u64_le(data, 0)?,
+            all: Self::read_flag(data, 8),
+        }))
+    }
+
+    /// 解析取款/借款指令事件
+    fn parse_withdraw_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 7 {
+            return None;
+        }
+        Some(Box::new(MarginfiLendingEvent {
+            metadata,
+            group: accounts[0],
+            marginfi_account: accounts[1],
+            authority: accounts[2],
+            bank: accounts[3],
+            user_token_account: accounts[4],
+            liquidity_vault: accounts[6],
+            amount: read_u64_le(data, 0)?,
+            all: Self::read_flag(data, 8),
+        }))
+    }
+
+    /// 解析清算指令事件
+    fn parse_liquidate_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 6 {
+            return None;
+        }
+        Some(Box::new(MarginfiLiquidationEvent {
+            metadata,
+            group: accounts[0],
+            asset_bank: accounts[1],
+            liab_bank: accounts[2],
+            liquidator_marginfi_account: accounts[3],
+            liquidator: accounts[4],
+            liquidatee_marginfi_account: accounts[5],
+            asset_amount: read_u64_le(data, 0)?,
+        }))
+    }
+}
+#[async_trait::async_trait]
+impl EventParser for MarginfiEventParser {
+    fn parse_events_from_inner_instruction(
+        &self,
+        inner_instruction: &UiCompiledInstruction,
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_inner_instruction(
+            inner_instruction,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        instruction: &CompiledInstruction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_instruction(
+            instruction,
+            accounts,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn get_inner_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>> {
+        self.inner.get_inner_instruction_configs()
+    }
+
+    fn get_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<Vec<u8>, Vec<GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        self.inner.get_protocol_type()
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        self.inner.get_program_id()
+    }
+
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.inner.should_handle(program_id)
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        self.inner.supported_program_ids()
+    }
+}
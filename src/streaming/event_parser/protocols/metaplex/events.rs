@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::protocols::metaplex::types::{
+    MetaplexCreator, MetaplexVerification,
+};
+
+/// 创建元数据
+///
+/// `metadata_account` is the metadata PDA of `mint`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetaplexCreateMetadataEvent {
+    pub metadata: EventMetadata,
+    pub metadata_account: Pubkey,
+    pub mint: Pubkey,
+    pub mint_authority: Pubkey,
+    pub payer: Pubkey,
+    pub update_authority: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<MetaplexCreator>,
+    /// Whether the update authority can still change the metadata
+    pub is_mutable: bool,
+}
+
+impl_unified_event!(MetaplexCreateMetadataEvent,);
+
+/// 更新元数据
+///
+/// Only the changed values are set. The instruction carries the new values only,
+/// so telling whether the URI actually changed takes the previous value, e.g.
+/// from the create event. `mint` is not an account of `UpdateMetadataAccountV2`
+/// and left empty for it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetaplexUpdateMetadataEvent {
+    pub metadata: EventMetadata,
+    pub metadata_account: Pubkey,
+    pub mint: Pubkey,
+    /// Signing update authority
+    pub update_authority: Pubkey,
+    /// Update authority is handed over to this account
+    pub new_update_authority: Option<Pubkey>,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+    pub seller_fee_basis_points: Option<u16>,
+    pub creators: Option<Vec<MetaplexCreator>>,
+    pub primary_sale_happened: Option<bool>,
+    /// `Some(false)` locks the metadata for good
+    pub is_mutable: Option<bool>,
+}
+
+impl_unified_event!(MetaplexUpdateMetadataEvent,);
+
+/// 验证创作者或集合
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetaplexVerifyEvent {
+    pub metadata: EventMetadata,
+    pub metadata_account: Pubkey,
+    /// Creator or collection authority
+    pub authority: Pubkey,
+    pub verification: MetaplexVerification,
+    /// Collection mint, empty for creator verification
+    pub collection_mint: Pubkey,
+}
+
+impl_unified_event!(MetaplexVerifyEvent,);
+
+/// 指令标签常量
+///
+/// Token Metadata is not an Anchor program; instructions start with a one byte
+/// variant tag.
+pub mod discriminators {
+    pub const CREATE_METADATA_ACCOUNT_V3_IX: &[u8] = &[33];
+    pub const CREATE_IX: &[u8] = &[42];
+    pub const UPDATE_METADATA_ACCOUNT_V2_IX: &[u8] = &[15];
+    pub const UPDATE_IX: &[u8] = &[50];
+    pub const SIGN_METADATA_IX: &[u8] = &[7];
+    pub const VERIFY_COLLECTION_IX: &[u8] = &[18];
+    pub const SET_AND_VERIFY_COLLECTION_IX: &[u8] = &[25];
+    pub const VERIFY_SIZED_COLLECTION_ITEM_IX: &[u8] = &[30];
+    pub const SET_AND_VERIFY_SIZED_COLLECTION_ITEM_IX: &[u8] = &[32];
+    pub const VERIFY_IX: &[u8] = &[52];
+}
@@ -0,0 +1,7 @@
+pub mod events;
+pub mod parser;
+pub mod types;
+
+pub use events::*;
+pub use parser::MetaplexEventParser;
+pub use types::*;
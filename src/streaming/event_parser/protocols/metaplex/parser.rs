@@ -0,0 +1,462 @@
+use borsh::BorshDeserialize;
+use prost_types::Timestamp;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::UiCompiledInstruction;
+
+use crate::streaming::event_parser::{
+    common::{EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
+    core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
+    protocols::metaplex::{
+        discriminators, MetaplexCreateMetadataEvent, MetaplexData, MetaplexUpdateMetadataEvent,
+        MetaplexVerification, MetaplexVerifyEvent,
+    },
+};
+
+/// Metaplex Token Metadata程序ID
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+#[derive(BorshDeserialize)]
+struct Collection {
+    _verified: bool,
+    _key: Pubkey,
+}
+
+#[derive(BorshDeserialize)]
+struct Uses {
+    _use_method: u8,
+    _remaining: u64,
+    _total: u64,
+}
+
+/// `DataV2` - `Data` 加上集合与用途
+#[derive(BorshDeserialize)]
+struct DataV2 {
+    data: MetaplexData,
+    _collection: Option<Collection>,
+    _uses: Option<Uses>,
+}
+
+/// CreateMetadataAccountV3 指令参数，忽略之后的 collection_details
+#[derive(BorshDeserialize)]
+struct CreateMetadataAccountV3Args {
+    data: DataV2,
+    is_mutable: bool,
+}
+
+/// Create 指令 `CreateArgs::V1` 的 `AssetData` 开头
+#[derive(BorshDeserialize)]
+struct CreateV1Args {
+    data: MetaplexData,
+    _primary_sale_happened: bool,
+    is_mutable: bool,
+}
+
+/// UpdateMetadataAccountV2 指令参数
+#[derive(BorshDeserialize)]
+struct UpdateMetadataAccountV2Args {
+    data: Option<DataV2>,
+    update_authority: Option<Pubkey>,
+    primary_sale_happened: Option<bool>,
+    is_mutable: Option<bool>,
+}
+
+/// Update 指令 `UpdateArgs::V1` / `AsUpdateAuthorityV2` 的开头
+#[derive(BorshDeserialize)]
+struct UpdateV1Args {
+    new_update_authority: Option<Pubkey>,
+    data: Option<MetaplexData>,
+    primary_sale_happened: Option<bool>,
+    is_mutable: Option<bool>,
+}
+
+/// `UpdateArgs` 中由更新权限签名的变体
+const UPDATE_V1: u8 = 0;
+const UPDATE_AS_UPDATE_AUTHORITY_V2: u8 = 1;
+
+/// `VerificationArgs` 变体
+const VERIFY_CREATOR_V1: u8 = 0;
+const VERIFY_COLLECTION_V1: u8 = 1;
+
+/// Metaplex Token Metadata事件解析器
+///
+/// Parses metadata creation, updates and verification from the instructions,
+/// including the CPIs launchpads make when creating a token. Delegated updates
+/// are not covered.
+pub struct MetaplexEventParser {
+    inner: GenericEventParser,
+}
+
+impl MetaplexEventParser {
+    pub fn new() -> Self {
+        let configs = vec![
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::CREATE_METADATA_ACCOUNT_V3_IX,
+                event_type: EventType::MetaplexCreateMetadata,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_create_metadata_account_v3_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::CREATE_IX,
+                event_type: EventType::MetaplexCreateMetadata,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_create_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::UPDATE_METADATA_ACCOUNT_V2_IX,
+                event_type: EventType::MetaplexUpdateMetadata,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_update_metadata_account_v2_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::UPDATE_IX,
+                event_type: EventType::MetaplexUpdateMetadata,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_update_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::SIGN_METADATA_IX,
+                event_type: EventType::MetaplexVerify,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_sign_metadata_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::VERIFY_COLLECTION_IX,
+                event_type: EventType::MetaplexVerify,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_verify_collection_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::VERIFY_SIZED_COLLECTION_ITEM_IX,
+                event_type: EventType::MetaplexVerify,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_verify_collection_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::SET_AND_VERIFY_COLLECTION_IX,
+                event_type: EventType::MetaplexVerify,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_set_and_verify_collection_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::SET_AND_VERIFY_SIZED_COLLECTION_ITEM_IX,
+                event_type: EventType::MetaplexVerify,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_set_and_verify_collection_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::VERIFY_IX,
+                event_type: EventType::MetaplexVerify,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_verify_instruction,
+                layouts: Vec::new(),
+            },
+        ];
+
+        let inner =
+            GenericEventParser::new(TOKEN_METADATA_PROGRAM_ID, ProtocolType::Metaplex, configs);
+
+        Self { inner }
+    }
+
+    fn parse_none_inner_instruction(
+        _data: &[u8],
+        _metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        None
+    }
+
+    /// 解析 CreateMetadataAccountV3 指令事件
+    fn parse_create_metadata_account_v3_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 5 {
+            return None;
+        }
+        let args = CreateMetadataAccountV3Args::deserialize(&mut &data[..]).ok()?;
+        let data = args.data.data;
+        Some(Box::new(MetaplexCreateMetadataEvent {
+            metadata,
+            metadata_account: accounts[0],
+            mint: accounts[1],
+            mint_authority: accounts[2],
+            payer: accounts[3],
+            update_authority: accounts[4],
+            name: data.name,
+            symbol: data.symbol,
+            uri: data.uri,
+            seller_fee_basis_points: data.seller_fee_basis_points,
+            creators: data.creators.unwrap_or_default(),
+            is_mutable: args.is_mutable,
+        }))
+    }
+
+    /// 解析 Create (V1) 指令事件
+    fn parse_create_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 6 {
+            return None;
+        }
+        // CreateArgs::V1
+        let (&0, mut reader) = data.split_first()? else {
+            return None;
+        };
+        let args = CreateV1Args::deserialize(&mut reader).ok()?;
+        Some(Box::new(MetaplexCreateMetadataEvent {
+            metadata,
+            metadata_account: accounts[0],
+            mint: accounts[2],
+            mint_authority: accounts[3],
+            payer: accounts[4],
+            update_authority: accounts[5],
+            name: args.data.name,
+            symbol: args.data.symbol,
+            uri: args.data.uri,
+            seller_fee_basis_points: args.data.seller_fee_basis_points,
+            creators: args.data.creators.unwrap_or_default(),
+            is_mutable: args.is_mutable,
+        }))
+    }
+
+    /// 解析 UpdateMetadataAccountV2 指令事件
+    fn parse_update_metadata_account_v2_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 2 {
+            return None;
+        }
+        let args = UpdateMetadataAccountV2Args::deserialize(&mut &data[..]).ok()?;
+        let mut event = MetaplexUpdateMetadataEvent {
+            metadata,
+            metadata_account: accounts[0],
+            update_authority: accounts[1],
+            new_update_authority: args.update_authority,
+            primary_sale_happened: args.primary_sale_happened,
+            is_mutable: args.is_mutable,
+            ..Default::default()
+        };
+        if let Some(data) = args.data {
+            Self::set_update_data(&mut event, data.data);
+        }
+        Some(Box::new(event))
+    }
+
+    /// 解析 Update 指令事件
+    fn parse_update_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 5 {
+            return None;
+        }
+        let (&variant, mut reader) = data.split_first()?;
+        if variant != UPDATE_V1 && variant != UPDATE_AS_UPDATE_AUTHORITY_V2 {
+            return None;
+        }
+        let args = UpdateV1Args::deserialize(&mut reader).ok()?;
+        let mut event = MetaplexUpdateMetadataEvent {
+            metadata,
+            update_authority: accounts[0],
+            mint: accounts[3],
+            metadata_account: accounts[4],
+            new_update_authority: args.new_update_authority,
+            primary_sale_happened: args.primary_sale_happened,
+            is_mutable: args.is_mutable,
+            ..Default::default()
+        };
+        if let Some(data) = args.data {
+            Self::set_update_data(&mut event, data);
+        }
+        Some(Box::new(event))
+    }
+
+    fn set_update_data(event: &mut MetaplexUpdateMetadataEvent, data: MetaplexData) {
+        event.name = Some(data.name);
+        event.symbol = Some(data.symbol);
+        event.uri = Some(data.uri);
+        event.seller_fee_basis_points = Some(data.seller_fee_basis_points);
+        event.creators = data.creators;
+    }
+
+    /// 解析 SignMetadata (创作者验证) 指令事件
+    fn parse_sign_metadata_instruction(
+        _data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 2 {
+            return None;
+        }
+        Some(Box::new(MetaplexVerifyEvent {
+            metadata,
+            metadata_account: accounts[0],
+            authority: accounts[1],
+            verification: MetaplexVerification::Creator,
+            ..Default::default()
+        }))
+    }
+
+    /// 解析 VerifyCollection / VerifySizedCollectionItem 指令事件
+    fn parse_verify_collection_instruction(
+        _data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 4 {
+            return None;
+        }
+        Some(Box::new(MetaplexVerifyEvent {
+            metadata,
+            metadata_account: accounts[0],
+            authority: accounts[1],
+            verification: MetaplexVerification::Collection,
+            collection_mint: accounts[3],
+        }))
+    }
+
+    /// 解析 SetAndVerifyCollection / SetAndVerifySizedCollectionItem 指令事件
+    ///
+    /// These take the item's update authority before the collection mint, so the
+    /// mint is the fifth account.
+    fn parse_set_and_verify_collection_instruction(
+        _data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 5 {
+            return None;
+        }
+        Some(Box::new(MetaplexVerifyEvent {
+            metadata,
+            metadata_account: accounts[0],
+            authority: accounts[1],
+            verification: MetaplexVerification::Collection,
+            collection_mint: accounts[4],
+        }))
+    }
+
+    /// 解析 Verify 指令事件
+    fn parse_verify_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 3 {
+            return None;
+        }
+        let (verification, collection_mint) = match *data.first()? {
+            VERIFY_CREATOR_V1 => (MetaplexVerification::Creator, Pubkey::default()),
+            VERIFY_COLLECTION_V1 => (MetaplexVerification::Collection, *accounts.get(3)?),
+            _ => return None,
+        };
+        Some(Box::new(MetaplexVerifyEvent {
+            metadata,
+            authority: accounts[0],
+            metadata_account: accounts[2],
+            verification,
+            collection_mint,
+        }))
+    }
+}
+#[async_trait::async_trait]
+impl EventParser for MetaplexEventParser {
+    fn parse_events_from_inner_instruction(
+        &self,
+        inner_instruction: &UiCompiledInstruction,
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_inner_instruction(
+            inner_instruction,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        instruction: &CompiledInstruction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_instruction(
+            instruction,
+            accounts,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn get_inner_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>> {
+        self.inner.get_inner_instruction_configs()
+    }
+
+    fn get_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<Vec<u8>, Vec<GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        self.inner.get_protocol_type()
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        self.inner.get_program_id()
+    }
+
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.inner.should_handle(program_id)
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        self.inner.supported_program_ids()
+    }
+}
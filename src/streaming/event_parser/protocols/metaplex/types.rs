@@ -0,0 +1,32 @@
+use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+/// 创作者及版税份额
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct MetaplexCreator {
+    pub address: Pubkey,
+    pub verified: bool,
+    /// Share of the royalties in percent
+    pub share: u8,
+}
+
+/// 元数据内容
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct MetaplexData {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<MetaplexCreator>>,
+}
+
+/// 验证对象
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MetaplexVerification {
+    /// A creator signs the metadata
+    #[default]
+    Creator,
+    /// The collection authority confirms the item belongs to the collection
+    Collection,
+}
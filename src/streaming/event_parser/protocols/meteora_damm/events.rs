@@ -0,0 +1,152 @@
+use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::EventMetadata;
+
+/// 兑换
+///
+/// Amounts are in the pool's underlying tokens, not vault LP. The swap direction
+/// follows from the unwound vault transfers in `metadata.transfer_datas`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct MeteoraDammSwapEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub in_amount: u64,
+    pub out_amount: u64,
+    pub trade_fee: u64,
+    pub protocol_fee: u64,
+    pub host_fee: u64,
+    #[borsh(skip)]
+    pub minimum_out_amount: u64,
+    #[borsh(skip)]
+    pub pool: Pubkey,
+    #[borsh(skip)]
+    pub user: Pubkey,
+    #[borsh(skip)]
+    pub user_source_token: Pubkey,
+    #[borsh(skip)]
+    pub user_destination_token: Pubkey,
+    #[borsh(skip)]
+    pub a_vault: Pubkey,
+    #[borsh(skip)]
+    pub b_vault: Pubkey,
+    #[borsh(skip)]
+    pub a_token_vault: Pubkey,
+    #[borsh(skip)]
+    pub b_token_vault: Pubkey,
+    #[borsh(skip)]
+    pub a_vault_lp: Pubkey,
+    #[borsh(skip)]
+    pub b_vault_lp: Pubkey,
+    #[borsh(skip)]
+    pub protocol_token_fee: Pubkey,
+}
+
+impl_unified_event!(
+    MeteoraDammSwapEvent,
+    in_amount,
+    out_amount,
+    trade_fee,
+    protocol_fee,
+    host_fee
+);
+
+/// 添加/移除平衡流动性
+///
+/// `AddLiquidity` and `RemoveLiquidity` share this layout; `lp_amount` is minted on
+/// add and burned on remove, the token amounts go in on add and out on remove.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct MeteoraDammLiquidityEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub lp_amount: u64,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    /// Pool LP requested by the instruction
+    #[borsh(skip)]
+    pub pool_token_amount: u64,
+    /// Maximum in on add, minimum out on remove
+    #[borsh(skip)]
+    pub token_a_limit: u64,
+    #[borsh(skip)]
+    pub token_b_limit: u64,
+    #[borsh(skip)]
+    pub pool: Pubkey,
+    #[borsh(skip)]
+    pub lp_mint: Pubkey,
+    #[borsh(skip)]
+    pub user: Pubkey,
+    #[borsh(skip)]
+    pub user_pool_lp: Pubkey,
+    #[borsh(skip)]
+    pub user_a_token: Pubkey,
+    #[borsh(skip)]
+    pub user_b_token: Pubkey,
+    #[borsh(skip)]
+    pub a_vault: Pubkey,
+    #[borsh(skip)]
+    pub b_vault: Pubkey,
+    #[borsh(skip)]
+    pub a_token_vault: Pubkey,
+    #[borsh(skip)]
+    pub b_token_vault: Pubkey,
+}
+
+impl_unified_event!(
+    MeteoraDammLiquidityEvent,
+    lp_amount,
+    token_a_amount,
+    token_b_amount
+);
+
+/// 初始流动性
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct MeteoraDammBootstrapLiquidityEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub lp_mint_amount: u64,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub pool: Pubkey,
+    #[borsh(skip)]
+    pub lp_mint: Pubkey,
+    #[borsh(skip)]
+    pub user: Pubkey,
+    #[borsh(skip)]
+    pub user_a_token: Pubkey,
+    #[borsh(skip)]
+    pub user_b_token: Pubkey,
+    #[borsh(skip)]
+    pub a_vault: Pubkey,
+    #[borsh(skip)]
+    pub b_vault: Pubkey,
+}
+
+impl_unified_event!(
+    MeteoraDammBootstrapLiquidityEvent,
+    lp_mint_amount,
+    token_a_amount,
+    token_b_amount,
+    pool
+);
+
+/// 事件鉴别器常量
+pub mod discriminators {
+    use crate::anchor_event_discriminator;
+    use crate::streaming::event_parser::common::discriminator::instruction_discriminator;
+
+    // 事件鉴别器 (程序日志)
+    pub const SWAP_EVENT: &str = anchor_event_discriminator!("Swap");
+    pub const ADD_LIQUIDITY_EVENT: &str = anchor_event_discriminator!("AddLiquidity");
+    pub const REMOVE_LIQUIDITY_EVENT: &str = anchor_event_discriminator!("RemoveLiquidity");
+    pub const BOOTSTRAP_LIQUIDITY_EVENT: &str = anchor_event_discriminator!("BootstrapLiquidity");
+
+    // 指令鉴别器
+    pub const SWAP_IX: &[u8] = &instruction_discriminator("swap");
+    pub const ADD_BALANCE_LIQUIDITY_IX: &[u8] = &instruction_discriminator("add_balance_liquidity");
+    pub const REMOVE_BALANCE_LIQUIDITY_IX: &[u8] =
+        &instruction_discriminator("remove_balance_liquidity");
+    pub const BOOTSTRAP_LIQUIDITY_IX: &[u8] = &instruction_discriminator("bootstrap_liquidity");
+}
@@ -0,0 +1,6 @@
+pub mod events;
+pub mod parser;
+pub mod vault;
+
+pub use events::*;
+pub use parser::MeteoraDammEventParser;
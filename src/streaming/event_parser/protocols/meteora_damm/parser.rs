@@ -0,0 +1,273 @@
+use prost_types::Timestamp;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::UiCompiledInstruction;
+
+use crate::streaming::event_parser::{
+    common::{read_u64_le, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
+    core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
+    protocols::meteora_damm::{
+        discriminators, MeteoraDammBootstrapLiquidityEvent, MeteoraDammLiquidityEvent,
+        MeteoraDammSwapEvent,
+    },
+};
+
+/// Meteora Dynamic AMM程序ID
+pub const METEORA_DAMM_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB");
+
+/// 指令解码所用的IDL子集
+const IDL: &str = include_str!("idl.json");
+
+/// Meteora Dynamic AMM事件解析器
+///
+/// Events are emitted as program logs and merged into the instruction events;
+/// token movements are unwound from the vault CPIs, see
+/// [`unwind_vault_transfers`](super::vault::unwind_vault_transfers).
+pub struct MeteoraDammEventParser {
+    inner: GenericEventParser,
+}
+
+impl MeteoraDammEventParser {
+    pub fn new() -> Self {
+        // 配置所有事件类型
+        let configs = vec![
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::SWAP_EVENT,
+                instruction_discriminator: discriminators::SWAP_IX,
+                event_type: EventType::MeteoraDammSwap,
+                inner_instruction_parser: Self::parse_swap_inner_instruction,
+                instruction_parser: Self::parse_swap_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::ADD_LIQUIDITY_EVENT,
+                instruction_discriminator: discriminators::ADD_BALANCE_LIQUIDITY_IX,
+                event_type: EventType::MeteoraDammAddLiquidity,
+                inner_instruction_parser: Self::parse_liquidity_inner_instruction,
+                instruction_parser: Self::parse_liquidity_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::REMOVE_LIQUIDITY_EVENT,
+                instruction_discriminator: discriminators::REMOVE_BALANCE_LIQUIDITY_IX,
+                event_type: EventType::MeteoraDammRemoveLiquidity,
+                inner_instruction_parser: Self::parse_liquidity_inner_instruction,
+                instruction_parser: Self::parse_liquidity_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::BOOTSTRAP_LIQUIDITY_EVENT,
+                instruction_discriminator: discriminators::BOOTSTRAP_LIQUIDITY_IX,
+                event_type: EventType::MeteoraDammBootstrapLiquidity,
+                inner_instruction_parser: Self::parse_bootstrap_liquidity_inner_instruction,
+                instruction_parser: Self::parse_bootstrap_liquidity_instruction,
+                layouts: Vec::new(),
+            },
+        ];
+
+        let instruction_decoder = InstructionDecoder::from_idl_json(
+            METEORA_DAMM_PROGRAM_ID,
+            ProtocolType::MeteoraDamm,
+            IDL,
+        )
+        .expect("bundled IDL is valid");
+        let inner =
+            GenericEventParser::new(METEORA_DAMM_PROGRAM_ID, ProtocolType::MeteoraDamm, configs)
+                .with_instruction_decoder(instruction_decoder);
+
+        Self { inner }
+    }
+
+    /// 解析兑换事件
+    fn parse_swap_inner_instruction(
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let event = borsh::from_slice::<MeteoraDammSwapEvent>(data).ok()?;
+        Some(Box::new(MeteoraDammSwapEvent { metadata, ..event }))
+    }
+
+    /// 解析添加/移除流动性事件
+    fn parse_liquidity_inner_instruction(
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let event = borsh::from_slice::<MeteoraDammLiquidityEvent>(data).ok()?;
+        Some(Box::new(MeteoraDammLiquidityEvent { metadata, ..event }))
+    }
+
+    /// 解析初始流动性事件
+    fn parse_bootstrap_liquidity_inner_instruction(
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let event = borsh::from_slice::<MeteoraDammBootstrapLiquidityEvent>(data).ok()?;
+        Some(Box::new(MeteoraDammBootstrapLiquidityEvent {
+            metadata,
+            ..event
+        }))
+    }
+
+    /// 解析兑换指令事件
+    fn parse_swap_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 15 {
+            return None;
+        }
+        let in_amount = read_u64_le(data, 0)?;
+        let minimum_out_amount = read_u64_le(data, 8)?;
+        Some(Box::new(MeteoraDammSwapEvent {
+            metadata,
+            in_amount,
+            minimum_out_amount,
+            pool: accounts[0],
+            user_source_token: accounts[1],
+            user_destination_token: accounts[2],
+            a_vault: accounts[3],
+            b_vault: accounts[4],
+            a_token_vault: accounts[5],
+            b_token_vault: accounts[6],
+            a_vault_lp: accounts[9],
+            b_vault_lp: accounts[10],
+            protocol_token_fee: accounts[11],
+            user: accounts[12],
+            ..Default::default()
+        }))
+    }
+
+    /// 解析添加/移除流动性指令事件
+    fn parse_liquidity_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 16 {
+            return None;
+        }
+        let pool_token_amount = read_u64_le(data, 0)?;
+        let token_a_limit = read_u64_le(data, 8)?;
+        let token_b_limit = read_u64_le(data, 16)?;
+        Some(Box::new(MeteoraDammLiquidityEvent {
+            metadata,
+            pool_token_amount,
+            token_a_limit,
+            token_b_limit,
+            pool: accounts[0],
+            lp_mint: accounts[1],
+            user_pool_lp: accounts[2],
+            a_vault: accounts[5],
+            b_vault: accounts[6],
+            a_token_vault: accounts[9],
+            b_token_vault: accounts[10],
+            user_a_token: accounts[11],
+            user_b_token: accounts[12],
+            user: accounts[13],
+            ..Default::default()
+        }))
+    }
+
+    /// 解析初始流动性指令事件
+    fn parse_bootstrap_liquidity_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 16 {
+            return None;
+        }
+        let token_a_amount = read_u64_le(data, 0)?;
+        let token_b_amount = read_u64_le(data, 8)?;
+        Some(Box::new(MeteoraDammBootstrapLiquidityEvent {
+            metadata,
+            token_a_amount,
+            token_b_amount,
+            pool: accounts[0],
+            lp_mint: accounts[1],
+            a_vault: accounts[5],
+            b_vault: accounts[6],
+            user_a_token: accounts[11],
+            user_b_token: accounts[12],
+            user: accounts[13],
+            ..Default::default()
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl EventParser for MeteoraDammEventParser {
+    fn parse_events_from_inner_instruction(
+        &self,
+        inner_instruction: &UiCompiledInstruction,
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_inner_instruction(
+            inner_instruction,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        instruction: &CompiledInstruction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_instruction(
+            instruction,
+            accounts,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn get_inner_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>> {
+        self.inner.get_inner_instruction_configs()
+    }
+
+    fn get_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<Vec<u8>, Vec<GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        self.inner.get_protocol_type()
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        self.inner.get_program_id()
+    }
+
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.inner.should_handle(program_id)
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        self.inner.supported_program_ids()
+    }
+}
@@ -0,0 +1,94 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::{UiCompiledInstruction, UiInnerInstructions, UiInstruction};
+
+use crate::streaming::event_parser::common::TransferData;
+use crate::streaming::event_parser::common::codec;
+
+/// Meteora Vault程序ID
+pub const METEORA_VAULT_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi");
+
+const TOKEN_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const TOKEN_2022_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// 展开vault CPI，提取底层代币转账
+///
+/// Dynamic AMM pools hold vault LP instead of tokens, so every swap and liquidity
+/// change runs through vault deposit and withdraw CPIs that also mint and burn
+/// vault LP. This walks the instructions invoked by the AMM instruction at
+/// `current_index` (-1 for a top-level instruction) and keeps only the SPL token
+/// transfers, which are the token movements of the user and the pool.
+pub fn unwind_vault_transfers(
+    inner_instruction: &UiInnerInstructions,
+    current_index: i8,
+    accounts: &[Pubkey],
+) -> Vec<TransferData> {
+    let start = (current_index + 1) as usize;
+    // 顶层指令的内联指令全部属于它; 否则只取调用栈更深的指令
+    let caller_height = if current_index < 0 {
+        None
+    } else {
+        match inner_instruction.instructions.get(current_index as usize) {
+            Some(UiInstruction::Compiled(compiled)) => compiled.stack_height,
+            _ => None,
+        }
+    };
+
+    let mut transfer_datas = vec![];
+    for instruction in inner_instruction.instructions.iter().skip(start) {
+        let UiInstruction::Compiled(compiled) = instruction else {
+            continue;
+        };
+        if let (Some(caller), Some(height)) = (caller_height, compiled.stack_height) {
+            if height <= caller {
+                break;
+            }
+        }
+        if let Some(transfer_data) = decode_token_transfer(compiled, accounts) {
+            transfer_datas.push(transfer_data);
+        }
+    }
+    transfer_datas
+}
+
+/// 解析 transfer / transferChecked
+fn decode_token_transfer(
+    compiled: &UiCompiledInstruction,
+    accounts: &[Pubkey],
+) -> Option<TransferData> {
+    let token_program = *accounts.get(compiled.program_id_index as usize)?;
+    if token_program != TOKEN_PROGRAM_ID && token_program != TOKEN_2022_PROGRAM_ID {
+        return None;
+    }
+    let data = codec::base58_decode(&compiled.data).ok()?;
+    let account = |i: usize| {
+        compiled
+            .accounts
+            .get(i)
+            .and_then(|index| accounts.get(*index as usize))
+            .copied()
+    };
+    let amount = u64::from_le_bytes(data.get(1..9)?.try_into().ok()?);
+    match data[0] {
+        3 => Some(TransferData {
+            token_program,
+            source: account(0)?,
+            destination: account(1)?,
+            authority: account(2),
+            amount,
+            decimals: None,
+            mint: None,
+        }),
+        12 => Some(TransferData {
+            token_program,
+            source: account(0)?,
+            mint: account(1),
+            destination: account(2)?,
+            authority: account(3),
+            amount,
+            decimals: data.get(9).copied(),
+        }),
+        _ => None,
+    }
+}
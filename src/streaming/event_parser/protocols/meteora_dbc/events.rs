@@ -0,0 +1,153 @@
+use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::protocols::meteora_dbc::types::{
+    MigrationTarget, TradeDirection,
+};
+
+/// 创建虚拟池
+///
+/// `config` is the curve config account shared by all pools of a launchpad; it
+/// holds the curve, fees and migration settings.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct MeteoraDbcInitializePoolEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub pool: Pubkey,
+    pub config: Pubkey,
+    pub creator: Pubkey,
+    pub base_mint: Pubkey,
+    pub pool_type: u8,
+    pub activation_point: u64,
+    #[borsh(skip)]
+    pub name: String,
+    #[borsh(skip)]
+    pub symbol: String,
+    #[borsh(skip)]
+    pub uri: String,
+    #[borsh(skip)]
+    pub quote_mint: Pubkey,
+    #[borsh(skip)]
+    pub base_vault: Pubkey,
+    #[borsh(skip)]
+    pub quote_vault: Pubkey,
+    #[borsh(skip)]
+    pub payer: Pubkey,
+    #[borsh(skip)]
+    pub is_token2022: bool,
+}
+
+impl_unified_event!(
+    MeteoraDbcInitializePoolEvent,
+    pool,
+    config,
+    creator,
+    base_mint,
+    pool_type,
+    activation_point
+);
+
+/// 兑换
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct MeteoraDbcSwapEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub pool: Pubkey,
+    pub config: Pubkey,
+    pub trade_direction: TradeDirection,
+    pub has_referral: bool,
+    /// Amount in as requested by the instruction
+    pub requested_amount_in: u64,
+    pub minimum_amount_out: u64,
+    pub actual_input_amount: u64,
+    pub output_amount: u64,
+    pub next_sqrt_price: u128,
+    pub trading_fee: u64,
+    pub protocol_fee: u64,
+    pub referral_fee: u64,
+    pub amount_in: u64,
+    pub current_timestamp: u64,
+    #[borsh(skip)]
+    pub payer: Pubkey,
+    #[borsh(skip)]
+    pub base_mint: Pubkey,
+    #[borsh(skip)]
+    pub quote_mint: Pubkey,
+    #[borsh(skip)]
+    pub input_token_account: Pubkey,
+    #[borsh(skip)]
+    pub output_token_account: Pubkey,
+    #[borsh(skip)]
+    pub base_vault: Pubkey,
+    #[borsh(skip)]
+    pub quote_vault: Pubkey,
+    #[borsh(skip)]
+    pub referral_token_account: Pubkey,
+}
+
+impl_unified_event!(
+    MeteoraDbcSwapEvent,
+    pool,
+    config,
+    trade_direction,
+    has_referral,
+    requested_amount_in,
+    minimum_amount_out,
+    actual_input_amount,
+    output_amount,
+    next_sqrt_price,
+    trading_fee,
+    protocol_fee,
+    referral_fee,
+    amount_in,
+    current_timestamp
+);
+
+/// 迁移 - 完成的联合曲线迁移到DAMM池
+///
+/// Parsed from the instruction only. `target_pool` is the new DAMM pool; for
+/// DAMM v1 `target_lp_mint` is its LP mint, for DAMM v2 `first_position` and
+/// `second_position` are the liquidity positions created for the pool.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MeteoraDbcMigrateEvent {
+    pub metadata: EventMetadata,
+    pub target: MigrationTarget,
+    /// Bonding curve pool
+    pub pool: Pubkey,
+    pub config: Pubkey,
+    pub migration_metadata: Pubkey,
+    pub target_program: Pubkey,
+    pub target_pool: Pubkey,
+    pub target_lp_mint: Pubkey,
+    pub first_position: Pubkey,
+    pub second_position: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub payer: Pubkey,
+}
+
+impl_unified_event!(MeteoraDbcMigrateEvent,);
+
+/// 事件鉴别器常量
+pub mod discriminators {
+    use crate::anchor_event_discriminator;
+    use crate::streaming::event_parser::common::discriminator::instruction_discriminator;
+
+    // 事件鉴别器
+    pub const INITIALIZE_POOL_EVENT: &str = anchor_event_discriminator!("EvtInitializePool");
+    pub const SWAP_EVENT: &str = anchor_event_discriminator!("EvtSwap");
+
+    // 指令鉴别器
+    pub const INITIALIZE_POOL_IX: &[u8] =
+        &instruction_discriminator("initialize_virtual_pool_with_spl_token");
+    pub const INITIALIZE_POOL_TOKEN2022_IX: &[u8] =
+        &instruction_discriminator("initialize_virtual_pool_with_token2022");
+    pub const SWAP_IX: &[u8] = &instruction_discriminator("swap");
+    pub const MIGRATE_DAMM_IX: &[u8] = &instruction_discriminator("migrate_meteora_damm");
+    pub const MIGRATE_DAMM_V2_IX: &[u8] = &instruction_discriminator("migration_damm_v2");
+}
@@ -0,0 +1,341 @@
+use borsh::BorshDeserialize;
+use prost_types::Timestamp;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::UiCompiledInstruction;
+
+use crate::streaming::event_parser::{
+    common::{read_u64_le, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
+    core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
+    protocols::meteora_dbc::{
+        discriminators, MeteoraDbcInitializePoolEvent, MeteoraDbcMigrateEvent, MeteoraDbcSwapEvent,
+        MigrationTarget,
+    },
+};
+
+/// Meteora DBC程序ID
+pub const METEORA_DBC_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("dbcij3LWUppWqq96dh6gJWwBifmcGfLSB5D4DuSMaqN");
+
+/// 指令解码所用的IDL子集
+const IDL: &str = include_str!("idl.json");
+
+/// initialize_virtual_pool_* 指令参数
+#[derive(BorshDeserialize)]
+struct InitializePoolArgs {
+    name: String,
+    symbol: String,
+    uri: String,
+}
+
+/// Meteora DBC事件解析器
+///
+/// Covers every launchpad built on the dynamic bonding curve, including the
+/// pools also reported by [`BelieveEventParser`](crate::streaming::event_parser::protocols::BelieveEventParser).
+pub struct MeteoraDbcEventParser {
+    inner: GenericEventParser,
+}
+
+impl MeteoraDbcEventParser {
+    pub fn new() -> Self {
+        // 配置所有事件类型
+        let configs = vec![
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::INITIALIZE_POOL_EVENT,
+                instruction_discriminator: discriminators::INITIALIZE_POOL_IX,
+                event_type: EventType::MeteoraDbcInitializePool,
+                inner_instruction_parser: Self::parse_initialize_pool_inner_instruction,
+                instruction_parser: Self::parse_initialize_pool_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::INITIALIZE_POOL_TOKEN2022_IX,
+                event_type: EventType::MeteoraDbcInitializePool,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_initialize_pool_token2022_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::SWAP_EVENT,
+                instruction_discriminator: discriminators::SWAP_IX,
+                event_type: EventType::MeteoraDbcSwap,
+                inner_instruction_parser: Self::parse_swap_inner_instruction,
+                instruction_parser: Self::parse_swap_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::MIGRATE_DAMM_IX,
+                event_type: EventType::MeteoraDbcMigrate,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_migrate_damm_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::MIGRATE_DAMM_V2_IX,
+                event_type: EventType::MeteoraDbcMigrate,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_migrate_damm_v2_instruction,
+                layouts: Vec::new(),
+            },
+        ];
+
+        let instruction_decoder = InstructionDecoder::from_idl_json(
+            METEORA_DBC_PROGRAM_ID,
+            ProtocolType::MeteoraDbc,
+            IDL,
+        )
+        .expect("bundled IDL is valid");
+        let inner =
+            GenericEventParser::new(METEORA_DBC_PROGRAM_ID, ProtocolType::MeteoraDbc, configs)
+                .with_instruction_decoder(instruction_decoder);
+
+        Self { inner }
+    }
+
+    /// 没有对应事件的指令
+    fn parse_none_inner_instruction(
+        _data: &[u8],
+        _metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        None
+    }
+
+    /// 解析创建虚拟池事件
+    fn parse_initialize_pool_inner_instruction(
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let event = borsh::from_slice::<MeteoraDbcInitializePoolEvent>(data).ok()?;
+        Some(Box::new(MeteoraDbcInitializePoolEvent {
+            metadata,
+            ..event
+        }))
+    }
+
+    /// 解析兑换事件
+    fn parse_swap_inner_instruction(
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let event = borsh::from_slice::<MeteoraDbcSwapEvent>(data).ok()?;
+        Some(Box::new(MeteoraDbcSwapEvent { metadata, ..event }))
+    }
+
+    fn initialize_pool_event(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+        payer: Pubkey,
+        is_token2022: bool,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let args = InitializePoolArgs::deserialize(&mut &data[..]).ok()?;
+        Some(Box::new(MeteoraDbcInitializePoolEvent {
+            metadata,
+            name: args.name,
+            symbol: args.symbol,
+            uri: args.uri,
+            config: accounts[0],
+            creator: accounts[2],
+            base_mint: accounts[3],
+            quote_mint: accounts[4],
+            pool: accounts[5],
+            base_vault: accounts[6],
+            quote_vault: accounts[7],
+            payer,
+            is_token2022,
+            ..Default::default()
+        }))
+    }
+
+    /// 解析创建虚拟池指令事件 (SPL Token)
+    fn parse_initialize_pool_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 11 {
+            return None;
+        }
+        Self::initialize_pool_event(data, accounts, metadata, accounts[10], false)
+    }
+
+    /// 解析创建虚拟池指令事件 (Token 2022)
+    fn parse_initialize_pool_token2022_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 9 {
+            return None;
+        }
+        Self::initialize_pool_event(data, accounts, metadata, accounts[8], true)
+    }
+
+    /// 解析兑换指令事件
+    fn parse_swap_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 13 {
+            return None;
+        }
+        let requested_amount_in = read_u64_le(data, 0)?;
+        let minimum_amount_out = read_u64_le(data, 8)?;
+        // 未传推荐账户时该位置为程序ID
+        let referral_token_account = if accounts[12] == METEORA_DBC_PROGRAM_ID {
+            Pubkey::default()
+        } else {
+            accounts[12]
+        };
+        Some(Box::new(MeteoraDbcSwapEvent {
+            metadata,
+            config: accounts[1],
+            pool: accounts[2],
+            input_token_account: accounts[3],
+            output_token_account: accounts[4],
+            base_vault: accounts[5],
+            quote_vault: accounts[6],
+            base_mint: accounts[7],
+            quote_mint: accounts[8],
+            payer: accounts[9],
+            referral_token_account,
+            requested_amount_in,
+            minimum_amount_out,
+            ..Default::default()
+        }))
+    }
+
+    /// 解析迁移到DAMM v1指令事件
+    fn parse_migrate_damm_instruction(
+        _data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 27 {
+            return None;
+        }
+        Some(Box::new(MeteoraDbcMigrateEvent {
+            metadata,
+            target: MigrationTarget::Damm,
+            pool: accounts[0],
+            migration_metadata: accounts[1],
+            config: accounts[2],
+            target_pool: accounts[4],
+            target_lp_mint: accounts[6],
+            base_mint: accounts[7],
+            quote_mint: accounts[8],
+            base_vault: accounts[17],
+            quote_vault: accounts[18],
+            payer: accounts[22],
+            target_program: accounts[26],
+            ..Default::default()
+        }))
+    }
+
+    /// 解析迁移到DAMM v2指令事件
+    fn parse_migrate_damm_v2_instruction(
+        _data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 20 {
+            return None;
+        }
+        Some(Box::new(MeteoraDbcMigrateEvent {
+            metadata,
+            target: MigrationTarget::DammV2,
+            pool: accounts[0],
+            migration_metadata: accounts[1],
+            config: accounts[2],
+            target_pool: accounts[4],
+            first_position: accounts[7],
+            second_position: accounts[10],
+            target_program: accounts[12],
+            base_mint: accounts[13],
+            quote_mint: accounts[14],
+            base_vault: accounts[17],
+            quote_vault: accounts[18],
+            payer: accounts[19],
+            ..Default::default()
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl EventParser for MeteoraDbcEventParser {
+    fn parse_events_from_inner_instruction(
+        &self,
+        inner_instruction: &UiCompiledInstruction,
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_inner_instruction(
+            inner_instruction,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        instruction: &CompiledInstruction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_instruction(
+            instruction,
+            accounts,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn get_inner_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>> {
+        self.inner.get_inner_instruction_configs()
+    }
+
+    fn get_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<Vec<u8>, Vec<GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        self.inner.get_protocol_type()
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        self.inner.get_program_id()
+    }
+
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.inner.should_handle(program_id)
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        self.inner.supported_program_ids()
+    }
+}
@@ -0,0 +1,20 @@
+use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub enum TradeDirection {
+    /// Sell base for quote
+    #[default]
+    BaseToQuote,
+    /// Buy base with quote
+    QuoteToBase,
+}
+
+/// 迁移目标
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub enum MigrationTarget {
+    /// Meteora Dynamic AMM (v1)
+    #[default]
+    Damm,
+    DammV2,
+}
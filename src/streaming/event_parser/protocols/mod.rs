@@ -3,9 +3,37 @@ pub mod pumpswap;
 pub mod bonk;
 pub mod raydium_cpmm;
 pub mod raydium_clmm;
+pub mod boop;
+pub mod believe;
+pub mod meteora_damm;
+pub mod meteora_dbc;
+pub mod phoenix;
+pub mod sanctum;
+pub mod drift;
+pub mod marginfi;
+pub mod kamino;
+pub mod tensor;
+pub mod magic_eden;
+pub mod metaplex;
+pub mod vote;
+pub mod stake;
 
 pub use pumpfun::PumpFunEventParser;
 pub use pumpswap::PumpSwapEventParser;
 pub use bonk::BonkEventParser;
 pub use raydium_cpmm::RaydiumCpmmEventParser;
-pub use raydium_clmm::RaydiumClmmEventParser;
\ No newline at end of file
+pub use raydium_clmm::RaydiumClmmEventParser;
+pub use boop::BoopEventParser;
+pub use believe::BelieveEventParser;
+pub use meteora_damm::MeteoraDammEventParser;
+pub use meteora_dbc::MeteoraDbcEventParser;
+pub use phoenix::PhoenixEventParser;
+pub use sanctum::SanctumEventParser;
+pub use drift::DriftEventParser;
+pub use marginfi::MarginfiEventParser;
+pub use kamino::KaminoEventParser;
+pub use tensor::TensorEventParser;
+pub use magic_eden::MagicEdenEventParser;
+pub use metaplex::MetaplexEventParser;
+pub use vote::VoteEventParser;
+pub use stake::StakeEventParser;
\ No newline at end of file
@@ -0,0 +1,187 @@
+use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::EventMetadata;
+
+/// 市场参数 - 用于把lots/ticks换算为atoms和代币单位
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhoenixMarketParams {
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+    /// Base atoms per base lot
+    pub base_lot_size: u64,
+    /// Quote atoms per quote lot
+    pub quote_lot_size: u64,
+    pub tick_size_in_quote_atoms_per_base_unit: u64,
+}
+
+impl PhoenixMarketParams {
+    pub fn base_lots_to_atoms(&self, base_lots: u64) -> u64 {
+        base_lots.saturating_mul(self.base_lot_size)
+    }
+
+    pub fn quote_lots_to_atoms(&self, quote_lots: u64) -> u64 {
+        quote_lots.saturating_mul(self.quote_lot_size)
+    }
+
+    /// Price in quote atoms per whole base unit
+    pub fn ticks_to_quote_atoms_per_base_unit(&self, price_in_ticks: u64) -> u64 {
+        price_in_ticks.saturating_mul(self.tick_size_in_quote_atoms_per_base_unit)
+    }
+
+    pub fn base_atoms_to_units(&self, base_atoms: u64) -> f64 {
+        base_atoms as f64 / 10f64.powi(self.base_decimals as i32)
+    }
+
+    pub fn quote_atoms_to_units(&self, quote_atoms: u64) -> f64 {
+        quote_atoms as f64 / 10f64.powi(self.quote_decimals as i32)
+    }
+}
+
+/// 成交 - 一个挂单被吃单成交
+///
+/// Raw fields are in lots and ticks. The `*_atoms` fields and `price` / `size`
+/// (in whole tokens) are only filled for markets registered with
+/// [`register_market`](super::register_market).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, BorshDeserialize)]
+pub struct PhoenixFillEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    /// Position of the event within the instruction
+    pub index: u16,
+    pub maker: Pubkey,
+    pub order_sequence_number: u64,
+    pub price_in_ticks: u64,
+    pub base_lots_filled: u64,
+    pub base_lots_remaining: u64,
+    #[borsh(skip)]
+    pub market: Pubkey,
+    /// Taker
+    #[borsh(skip)]
+    pub signer: Pubkey,
+    #[borsh(skip)]
+    pub sequence_number: u64,
+    #[borsh(skip)]
+    pub timestamp: i64,
+    #[borsh(skip)]
+    pub base_atoms_filled: u64,
+    #[borsh(skip)]
+    pub quote_atoms_filled: u64,
+    #[borsh(skip)]
+    pub price_in_quote_atoms_per_base_unit: u64,
+    #[borsh(skip)]
+    pub price: Option<f64>,
+    #[borsh(skip)]
+    pub size: Option<f64>,
+}
+
+impl_unified_event!(PhoenixFillEvent,);
+
+/// 挂单
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, BorshDeserialize)]
+pub struct PhoenixPlaceEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub index: u16,
+    pub order_sequence_number: u64,
+    pub client_order_id: u128,
+    pub price_in_ticks: u64,
+    pub base_lots_placed: u64,
+    #[borsh(skip)]
+    pub market: Pubkey,
+    #[borsh(skip)]
+    pub signer: Pubkey,
+    #[borsh(skip)]
+    pub sequence_number: u64,
+    #[borsh(skip)]
+    pub timestamp: i64,
+    #[borsh(skip)]
+    pub base_atoms_placed: u64,
+    #[borsh(skip)]
+    pub price_in_quote_atoms_per_base_unit: u64,
+    #[borsh(skip)]
+    pub price: Option<f64>,
+    #[borsh(skip)]
+    pub size: Option<f64>,
+}
+
+impl_unified_event!(PhoenixPlaceEvent,);
+
+/// 撤单 (含部分撤单)
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, BorshDeserialize)]
+pub struct PhoenixCancelEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub index: u16,
+    pub order_sequence_number: u64,
+    pub price_in_ticks: u64,
+    pub base_lots_removed: u64,
+    pub base_lots_remaining: u64,
+    #[borsh(skip)]
+    pub market: Pubkey,
+    #[borsh(skip)]
+    pub signer: Pubkey,
+    #[borsh(skip)]
+    pub sequence_number: u64,
+    #[borsh(skip)]
+    pub timestamp: i64,
+    #[borsh(skip)]
+    pub base_atoms_removed: u64,
+    #[borsh(skip)]
+    pub price: Option<f64>,
+    #[borsh(skip)]
+    pub size: Option<f64>,
+}
+
+impl_unified_event!(PhoenixCancelEvent,);
+
+/// 吃单汇总 - 一笔订单的全部成交与taker手续费
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, BorshDeserialize)]
+pub struct PhoenixFillSummaryEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub index: u16,
+    pub client_order_id: u128,
+    pub total_base_lots_filled: u64,
+    pub total_quote_lots_filled: u64,
+    pub total_fee_in_quote_lots: u64,
+    #[borsh(skip)]
+    pub market: Pubkey,
+    #[borsh(skip)]
+    pub signer: Pubkey,
+    #[borsh(skip)]
+    pub sequence_number: u64,
+    #[borsh(skip)]
+    pub timestamp: i64,
+    #[borsh(skip)]
+    pub total_base_atoms_filled: u64,
+    #[borsh(skip)]
+    pub total_quote_atoms_filled: u64,
+    #[borsh(skip)]
+    pub total_fee_in_quote_atoms: u64,
+}
+
+impl_unified_event!(PhoenixFillSummaryEvent,);
+
+/// 手续费提取
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, BorshDeserialize)]
+pub struct PhoenixFeeEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub index: u16,
+    pub fees_collected_in_quote_lots: u64,
+    #[borsh(skip)]
+    pub market: Pubkey,
+    #[borsh(skip)]
+    pub signer: Pubkey,
+    #[borsh(skip)]
+    pub sequence_number: u64,
+    #[borsh(skip)]
+    pub timestamp: i64,
+    #[borsh(skip)]
+    pub fees_collected_in_quote_atoms: u64,
+}
+
+impl_unified_event!(PhoenixFeeEvent,);
@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+use borsh::BorshDeserialize;
+use prost_types::Timestamp;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::UiCompiledInstruction;
+
+use crate::streaming::event_parser::{
+    common::{identity::stable_hash, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
+    core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
+    protocols::phoenix::{
+        PhoenixCancelEvent, PhoenixFeeEvent, PhoenixFillEvent, PhoenixFillSummaryEvent,
+        PhoenixMarketParams, PhoenixPlaceEvent,
+    },
+};
+
+/// Phoenix程序ID
+pub const PHOENIX_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY");
+
+/// 事件记录指令 (PhoenixInstruction::Log)
+pub const LOG_IX_TAG: u8 = 15;
+
+/// 已注册的市场参数
+static MARKETS: LazyLock<RwLock<HashMap<Pubkey, PhoenixMarketParams>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// 注册市场参数
+///
+/// The lot and tick sizes live in the market account, not in the events; fills,
+/// places and cancels of registered markets also carry atoms and whole-token
+/// prices and sizes.
+pub fn register_market(market: Pubkey, params: PhoenixMarketParams) {
+    MARKETS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(market, params);
+}
+
+pub fn market_params(market: &Pubkey) -> Option<PhoenixMarketParams> {
+    MARKETS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(market)
+        .copied()
+}
+
+/// 事件记录的头部
+///
+/// Recorded as the `PhoenixMarketEvent::Header` variant, so the data of a `Log`
+/// instruction starts with the `HEADER` variant byte before it.
+#[derive(BorshDeserialize)]
+struct AuditLogHeader {
+    _instruction: u8,
+    sequence_number: u64,
+    timestamp: i64,
+    _slot: u64,
+    market: Pubkey,
+    signer: Pubkey,
+    _total_events: u16,
+}
+
+// PhoenixMarketEvent 变体
+const UNINITIALIZED: u8 = 0;
+const HEADER: u8 = 1;
+const FILL: u8 = 2;
+const PLACE: u8 = 3;
+const REDUCE: u8 = 4;
+const EVICT: u8 = 5;
+const FILL_SUMMARY: u8 = 6;
+const FEE: u8 = 7;
+const TIME_IN_FORCE: u8 = 8;
+const EXPIRED_ORDER: u8 = 9;
+
+/// Phoenix事件解析器
+///
+/// Phoenix is not an Anchor program: it records the market events of every
+/// instruction in `Log` instructions it invokes on itself. Each `Log` holds a
+/// `Header` event with the market and signer followed by the other events, so one
+/// instruction yields one event per fill, place, cancel and fee.
+pub struct PhoenixEventParser {
+    inner: GenericEventParser,
+}
+
+impl PhoenixEventParser {
+    pub fn new() -> Self {
+        let inner = GenericEventParser::new(PHOENIX_PROGRAM_ID, ProtocolType::Phoenix, vec![]);
+        Self { inner }
+    }
+
+    /// 解析Log指令中的全部事件
+    fn parse_log(data: &[u8], base: &EventMetadata) -> Vec<Box<dyn UnifiedEvent>> {
+        // 第一个事件必须是Header
+        let Some((&HEADER, mut reader)) = data.split_first() else {
+            return vec![];
+        };
+        let Ok(header) = AuditLogHeader::deserialize(&mut reader) else {
+            return vec![];
+        };
+        let params = market_params(&header.market);
+        let mut events: Vec<Box<dyn UnifiedEvent>> = vec![];
+        while let Some((&variant, rest)) = reader.split_first() {
+            reader = rest;
+            let metadata = |event_type: EventType, index: u16| {
+                let mut metadata = base.clone();
+                metadata.event_type = event_type;
+                let instruction_index = metadata.index;
+                metadata.set_instruction_id(&instruction_index);
+                // 同一指令内的多个事件按序号区分
+                metadata.id = format!(
+                    "{:016x}",
+                    stable_hash(format!("{}:{}", metadata.id, index).as_bytes())
+                );
+                metadata
+            };
+            match variant {
+                FILL => {
+                    let Ok(event) = PhoenixFillEvent::deserialize(&mut reader) else {
+                        break;
+                    };
+                    let mut event = PhoenixFillEvent {
+                        metadata: metadata(EventType::PhoenixFill, event.index),
+                        market: header.market,
+                        signer: header.signer,
+                        sequence_number: header.sequence_number,
+                        timestamp: header.timestamp,
+                        ..event
+                    };
+                    if let Some(params) = &params {
+                        let price = params.ticks_to_quote_atoms_per_base_unit(event.price_in_ticks);
+                        let base_atoms = params.base_lots_to_atoms(event.base_lots_filled);
+                        event.base_atoms_filled = base_atoms;
+                        event.price_in_quote_atoms_per_base_unit = price;
+                        event.quote_atoms_filled = (base_atoms as u128 * price as u128
+                            / 10u128.pow(params.base_decimals as u32))
+                            as u64;
+                        event.price = Some(params.quote_atoms_to_units(price));
+                        event.size = Some(params.base_atoms_to_units(base_atoms));
+                    }
+                    events.push(Box::new(event));
+                }
+                PLACE => {
+                    let Ok(event) = PhoenixPlaceEvent::deserialize(&mut reader) else {
+                        break;
+                    };
+                    let mut event = PhoenixPlaceEvent {
+                        metadata: metadata(EventType::PhoenixPlace, event.index),
+                        market: header.market,
+                        signer: header.signer,
+                        sequence_number: header.sequence_number,
+                        timestamp: header.timestamp,
+                        ..event
+                    };
+                    if let Some(params) = &params {
+                        let price = params.ticks_to_quote_atoms_per_base_unit(event.price_in_ticks);
+                        let base_atoms = params.base_lots_to_atoms(event.base_lots_placed);
+                        event.base_atoms_placed = base_atoms;
+                        event.price_in_quote_atoms_per_base_unit = price;
+                        event.price = Some(params.quote_atoms_to_units(price));
+                        event.size = Some(params.base_atoms_to_units(base_atoms));
+                    }
+                    events.push(Box::new(event));
+                }
+                REDUCE => {
+                    let Ok(event) = PhoenixCancelEvent::deserialize(&mut reader) else {
+                        break;
+                    };
+                    let mut event = PhoenixCancelEvent {
+                        metadata: metadata(EventType::PhoenixCancel, event.index),
+                        market: header.market,
+                        signer: header.signer,
+                        sequence_number: header.sequence_number,
+                        timestamp: header.timestamp,
+                        ..event
+                    };
+                    if let Some(params) = &params {
+                        let price = params.ticks_to_quote_atoms_per_base_unit(event.price_in_ticks);
+                        let base_atoms = params.base_lots_to_atoms(event.base_lots_removed);
+                        event.base_atoms_removed = base_atoms;
+                        event.price = Some(params.quote_atoms_to_units(price));
+                        event.size = Some(params.base_atoms_to_units(base_atoms));
+                    }
+                    events.push(Box::new(event));
+                }
+                FILL_SUMMARY => {
+                    let Ok(event) = PhoenixFillSummaryEvent::deserialize(&mut reader) else {
+                        break;
+                    };
+                    let mut event = PhoenixFillSummaryEvent {
+                        metadata: metadata(EventType::PhoenixFillSummary, event.index),
+                        market: header.market,
+                        signer: header.signer,
+                        sequence_number: header.sequence_number,
+                        timestamp: header.timestamp,
+                        ..event
+                    };
+                    if let Some(params) = &params {
+                        event.total_base_atoms_filled =
+                            params.base_lots_to_atoms(event.total_base_lots_filled);
+                        event.total_quote_atoms_filled =
+                            params.quote_lots_to_atoms(event.total_quote_lots_filled);
+                        event.total_fee_in_quote_atoms =
+                            params.quote_lots_to_atoms(event.total_fee_in_quote_lots);
+                    }
+                    events.push(Box::new(event));
+                }
+                FEE => {
+                    let Ok(event) = PhoenixFeeEvent::deserialize(&mut reader) else {
+                        break;
+                    };
+                    let mut event = PhoenixFeeEvent {
+                        metadata: metadata(EventType::PhoenixFee, event.index),
+                        market: header.market,
+                        signer: header.signer,
+                        sequence_number: header.sequence_number,
+                        timestamp: header.timestamp,
+                        ..event
+                    };
+                    if let Some(params) = &params {
+                        event.fees_collected_in_quote_atoms =
+                            params.quote_lots_to_atoms(event.fees_collected_in_quote_lots);
+                    }
+                    events.push(Box::new(event));
+                }
+                // 不输出的事件，按固定长度跳过
+                UNINITIALIZED | HEADER | EVICT | TIME_IN_FORCE | EXPIRED_ORDER => {
+                    let len = match variant {
+                        UNINITIALIZED => 0,
+                        HEADER => 91,
+                        TIME_IN_FORCE => 26,
+                        _ => 58,
+                    };
+                    if reader.len() < len {
+                        break;
+                    }
+                    reader = &reader[len..];
+                }
+                _ => break,
+            }
+        }
+        events
+    }
+}
+
+#[async_trait::async_trait]
+impl EventParser for PhoenixEventParser {
+    /// Log指令需要账户表判断程序，由 [`Self::parse_events_from_instruction`] 处理
+    fn parse_events_from_inner_instruction(
+        &self,
+        _inner_instruction: &UiCompiledInstruction,
+        _signature: &str,
+        _slot: u64,
+        _block_time: Option<Timestamp>,
+        _program_received_time_ms: i64,
+        _index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        vec![]
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        instruction: &CompiledInstruction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        let Some(program_id) = accounts.get(instruction.program_id_index as usize) else {
+            return vec![];
+        };
+        if !self.should_handle(program_id) {
+            return vec![];
+        }
+        let Some((&LOG_IX_TAG, data)) = instruction.data.split_first() else {
+            return vec![];
+        };
+        let timestamp = block_time.unwrap_or(Timestamp {
+            seconds: 0,
+            nanos: 0,
+        });
+        let block_time_ms = timestamp.seconds * 1000 + (timestamp.nanos as i64) / 1_000_000;
+        let base = EventMetadata::new(
+            signature.to_string(),
+            signature.to_string(),
+            slot,
+            timestamp.seconds,
+            block_time_ms,
+            ProtocolType::Phoenix,
+            EventType::PhoenixFill,
+            PHOENIX_PROGRAM_ID,
+            index,
+            program_received_time_ms,
+        );
+        Self::parse_log(data, &base)
+    }
+
+    fn get_inner_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>> {
+        self.inner.get_inner_instruction_configs()
+    }
+
+    fn get_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<Vec<u8>, Vec<GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        self.inner.get_protocol_type()
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        self.inner.get_program_id()
+    }
+
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.inner.should_handle(program_id)
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        self.inner.supported_program_ids()
+    }
+}
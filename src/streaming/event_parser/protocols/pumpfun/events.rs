@@ -100,14 +100,94 @@ impl_unified_event!(
     creator_fee
 );
 
+/// 全局参数更新事件 - 来自 `set_params` 指令
+///
+/// Bonding curve parameters and fees of new launches. Fields the decoded layout
+/// doesn't carry keep their defaults: `fee_recipient` only exists in the original
+/// layout, the withdraw/migration/creator-fee fields only in later ones, and
+/// `fee_recipients` and `set_creator_authority` only in the current one.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PumpFunSetParamsEvent {
+    pub metadata: EventMetadata,
+    pub global: Pubkey,
+    pub authority: Pubkey,
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub token_total_supply: u64,
+    pub fee_basis_points: u64,
+    pub fee_recipient: Pubkey,
+    pub withdraw_authority: Pubkey,
+    pub enable_migrate: bool,
+    pub pool_migration_fee: u64,
+    pub creator_fee_basis_points: u64,
+    pub fee_recipients: Vec<Pubkey>,
+    pub set_creator_authority: Pubkey,
+}
+
+impl_unified_event!(PumpFunSetParamsEvent,);
+
+/// 设置代币创建者事件
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct PumpFunSetCreatorEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub timestamp: i64,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub creator: Pubkey,
+    #[borsh(skip)]
+    pub set_creator_authority: Pubkey,
+}
+
+impl_unified_event!(PumpFunSetCreatorEvent, timestamp, mint, bonding_curve, creator);
+
+/// 领取创建者手续费事件
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct PumpFunCollectCreatorFeeEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub timestamp: i64,
+    pub creator: Pubkey,
+    /// Lamports moved out of the creator vault
+    pub creator_fee: u64,
+    #[borsh(skip)]
+    pub creator_vault: Pubkey,
+}
+
+impl_unified_event!(PumpFunCollectCreatorFeeEvent, timestamp, creator, creator_fee);
+
+/// 提取事件 - 来自 `withdraw` 指令，完成的曲线流动性被取出用于迁移
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PumpFunWithdrawEvent {
+    pub metadata: EventMetadata,
+    pub global: Pubkey,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub associated_bonding_curve: Pubkey,
+    pub user: Pubkey,
+}
+
+impl_unified_event!(PumpFunWithdrawEvent,);
+
 /// 事件鉴别器常量
 pub mod discriminators {
+    use crate::anchor_event_discriminator;
+    use crate::streaming::event_parser::common::discriminator::instruction_discriminator;
+
     // 事件鉴别器
-    pub const CREATE_TOKEN_EVENT: &str = "0xe445a52e51cb9a1d1b72a94ddeeb6376";
-    pub const TRADE_EVENT: &str = "0xe445a52e51cb9a1dbddb7fd34ee661ee";
+    pub const CREATE_TOKEN_EVENT: &str = anchor_event_discriminator!("CreateEvent");
+    pub const TRADE_EVENT: &str = anchor_event_discriminator!("TradeEvent");
+    pub const SET_CREATOR_EVENT: &str = anchor_event_discriminator!("SetCreatorEvent");
+    pub const COLLECT_CREATOR_FEE_EVENT: &str =
+        anchor_event_discriminator!("CollectCreatorFeeEvent");
 
     // 指令鉴别器
-    pub const CREATE_TOKEN_IX: &[u8] = &[24, 30, 200, 40, 5, 28, 7, 119];
-    pub const BUY_IX: &[u8] = &[102, 6, 61, 18, 1, 218, 235, 234];
-    pub const SELL_IX: &[u8] = &[51, 230, 133, 164, 1, 127, 131, 173];
+    pub const CREATE_TOKEN_IX: &[u8] = &instruction_discriminator("create");
+    pub const BUY_IX: &[u8] = &instruction_discriminator("buy");
+    pub const SELL_IX: &[u8] = &instruction_discriminator("sell");
+    pub const SET_PARAMS_IX: &[u8] = &instruction_discriminator("set_params");
+    pub const SET_CREATOR_IX: &[u8] = &instruction_discriminator("set_creator");
+    pub const COLLECT_CREATOR_FEE_IX: &[u8] = &instruction_discriminator("collect_creator_fee");
+    pub const WITHDRAW_IX: &[u8] = &instruction_discriminator("withdraw");
 }
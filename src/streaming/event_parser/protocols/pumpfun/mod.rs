@@ -1,5 +1,7 @@
 pub mod events;
 pub mod parser;
+pub mod view;
 
 pub use events::*;
-pub use parser::PumpFunEventParser; 
\ No newline at end of file
+pub use parser::PumpFunEventParser;
+pub use view::PumpFunTradeEventView;
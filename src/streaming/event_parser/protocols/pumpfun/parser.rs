@@ -1,17 +1,28 @@
+use borsh::BorshDeserialize;
 use prost_types::Timestamp;
 use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
 use solana_transaction_status::UiCompiledInstruction;
 
 use crate::streaming::event_parser::{
-    common::{EventMetadata, EventType, ProtocolType},
-    core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
-    protocols::pumpfun::{discriminators, PumpFunCreateTokenEvent, PumpFunTradeEvent},
+    common::{read_pubkey, read_u64_le, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
+    core::traits::{
+        EventParser, GenericEventParseConfig, GenericEventParser, LayoutVersion, UnifiedEvent,
+    },
+    protocols::pumpfun::{
+        discriminators, PumpFunCollectCreatorFeeEvent, PumpFunCreateTokenEvent,
+        PumpFunSetCreatorEvent, PumpFunSetParamsEvent, PumpFunTradeEvent, PumpFunTradeEventView,
+        PumpFunWithdrawEvent,
+    },
 };
 
 /// PumpFun程序ID
 pub const PUMPFUN_PROGRAM_ID: Pubkey =
     solana_sdk::pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
 
+/// 指令解码所用的IDL子集
+const IDL: &str = include_str!("idl.json");
+
 /// PumpFun事件解析器
 pub struct PumpFunEventParser {
     inner: GenericEventParser,
@@ -27,6 +38,7 @@ impl PumpFunEventParser {
                 event_type: EventType::PumpFunCreateToken,
                 inner_instruction_parser: Self::parse_create_token_inner_instruction,
                 instruction_parser: Self::parse_create_token_instruction,
+                layouts: Vec::new(),
             },
             GenericEventParseConfig {
                 inner_instruction_discriminator: discriminators::TRADE_EVENT,
@@ -34,6 +46,7 @@ impl PumpFunEventParser {
                 event_type: EventType::PumpFunBuy,
                 inner_instruction_parser: Self::parse_trade_inner_instruction,
                 instruction_parser: Self::parse_buy_instruction,
+                layouts: Vec::new(),
             },
             GenericEventParseConfig {
                 inner_instruction_discriminator: discriminators::TRADE_EVENT,
@@ -41,10 +54,51 @@ impl PumpFunEventParser {
                 event_type: EventType::PumpFunSell,
                 inner_instruction_parser: Self::parse_trade_inner_instruction,
                 instruction_parser: Self::parse_sell_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::SET_PARAMS_IX,
+                event_type: EventType::PumpFunSetParams,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_set_params_instruction,
+                layouts: Vec::new(),
+            }
+            .with_layout(
+                LayoutVersion::new("fee_recipient")
+                    .with_instruction_parser(Self::parse_set_params_v1_instruction),
+            ),
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::SET_CREATOR_EVENT,
+                instruction_discriminator: discriminators::SET_CREATOR_IX,
+                event_type: EventType::PumpFunSetCreator,
+                inner_instruction_parser: Self::parse_set_creator_inner_instruction,
+                instruction_parser: Self::parse_set_creator_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::COLLECT_CREATOR_FEE_EVENT,
+                instruction_discriminator: discriminators::COLLECT_CREATOR_FEE_IX,
+                event_type: EventType::PumpFunCollectCreatorFee,
+                inner_instruction_parser: Self::parse_collect_creator_fee_inner_instruction,
+                instruction_parser: Self::parse_collect_creator_fee_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::WITHDRAW_IX,
+                event_type: EventType::PumpFunWithdraw,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_withdraw_instruction,
+                layouts: Vec::new(),
             },
         ];
 
-        let inner = GenericEventParser::new(PUMPFUN_PROGRAM_ID, ProtocolType::PumpFun, configs);
+        let instruction_decoder =
+            InstructionDecoder::from_idl_json(PUMPFUN_PROGRAM_ID, ProtocolType::PumpFun, IDL)
+                .expect("bundled IDL is valid");
+        let inner = GenericEventParser::new(PUMPFUN_PROGRAM_ID, ProtocolType::PumpFun, configs)
+            .with_instruction_decoder(instruction_decoder);
 
         Self { inner }
     }
@@ -55,14 +109,6 @@ impl PumpFunEventParser {
         metadata: EventMetadata,
     ) -> Option<Box<dyn UnifiedEvent>> {
         if let Ok(event) = borsh::from_slice::<PumpFunCreateTokenEvent>(data) {
-            let mut metadata = metadata;
-            metadata.set_id(format!(
-                "{}-{}-{}-{}",
-                metadata.signature,
-                event.name,
-                event.symbol,
-                event.mint.to_string()
-            ));
             Some(Box::new(PumpFunCreateTokenEvent {
                 metadata: metadata,
                 ..event
@@ -77,22 +123,8 @@ impl PumpFunEventParser {
         data: &[u8],
         metadata: EventMetadata,
     ) -> Option<Box<dyn UnifiedEvent>> {
-        if let Ok(event) = borsh::from_slice::<PumpFunTradeEvent>(data) {
-            let mut metadata = metadata;
-            metadata.set_id(format!(
-                "{}-{}-{}-{}",
-                metadata.signature,
-                event.mint.to_string(),
-                event.user.to_string(),
-                event.is_buy.to_string()
-            ));
-            Some(Box::new(PumpFunTradeEvent {
-                metadata: metadata,
-                ..event
-            }))
-        } else {
-            None
-        }
+        let view = PumpFunTradeEventView::new(data).ok()?;
+        Some(Box::new(view.to_event(metadata)))
     }
 
     /// 解析创建代币指令事件
@@ -123,15 +155,6 @@ impl PumpFunEventParser {
             Pubkey::default()
         };
 
-        let mut metadata = metadata;
-        metadata.set_id(format!(
-            "{}-{}-{}-{}",
-            metadata.signature,
-            name,
-            symbol,
-            accounts[0].to_string()
-        ));
-
         Some(Box::new(PumpFunCreateTokenEvent {
             metadata,
             name: name.to_string(),
@@ -158,14 +181,6 @@ impl PumpFunEventParser {
         }
         let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
         let max_sol_cost = u64::from_le_bytes(data[8..16].try_into().unwrap());
-        let mut metadata = metadata;
-        metadata.set_id(format!(
-            "{}-{}-{}-{}",
-            metadata.signature,
-            accounts[2].to_string(),
-            accounts[6].to_string(),
-            true.to_string()
-        ));
         Some(Box::new(PumpFunTradeEvent {
             metadata,
             fee_recipient: accounts[1],
@@ -193,14 +208,6 @@ impl PumpFunEventParser {
         }
         let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
         let min_sol_output = u64::from_le_bytes(data[8..16].try_into().unwrap());
-        let mut metadata = metadata;
-        metadata.set_id(format!(
-            "{}-{}-{}-{}",
-            metadata.signature,
-            accounts[2].to_string(),
-            accounts[6].to_string(),
-            false.to_string()
-        ));
         Some(Box::new(PumpFunTradeEvent {
             metadata,
             fee_recipient: accounts[1],
@@ -216,6 +223,152 @@ impl PumpFunEventParser {
             ..Default::default()
         }))
     }
+
+    /// 没有内部事件的指令
+    fn parse_none_inner_instruction(
+        _data: &[u8],
+        _metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        None
+    }
+
+    /// 解析设置创建者事件
+    fn parse_set_creator_inner_instruction(
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        // 不要求读完数据，程序升级可能在末尾追加字段
+        let event = PumpFunSetCreatorEvent::deserialize(&mut &data[..]).ok()?;
+        Some(Box::new(PumpFunSetCreatorEvent { metadata, ..event }))
+    }
+
+    /// 解析领取创建者手续费事件
+    fn parse_collect_creator_fee_inner_instruction(
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let event = PumpFunCollectCreatorFeeEvent::deserialize(&mut &data[..]).ok()?;
+        Some(Box::new(PumpFunCollectCreatorFeeEvent { metadata, ..event }))
+    }
+
+    /// 解析set_params指令
+    ///
+    /// Five reserve/fee u64s, withdraw_authority, enable_migrate and
+    /// pool_migration_fee, then creator_fee_basis_points and set_creator_authority
+    /// on programs new enough to have creator fees.
+    fn parse_set_params_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if data.len() < 81 || accounts.len() < 2 {
+            return None;
+        }
+        let enable_migrate = match data[72] {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+        Some(Box::new(PumpFunSetParamsEvent {
+            metadata,
+            global: accounts[0],
+            authority: accounts[1],
+            initial_virtual_token_reserves: read_u64_le(data, 0)?,
+            initial_virtual_sol_reserves: read_u64_le(data, 8)?,
+            initial_real_token_reserves: read_u64_le(data, 16)?,
+            token_total_supply: read_u64_le(data, 24)?,
+            fee_basis_points: read_u64_le(data, 32)?,
+            withdraw_authority: read_pubkey(data, 40)?,
+            enable_migrate,
+            pool_migration_fee: read_u64_le(data, 73)?,
+            creator_fee_basis_points: read_u64_le(data, 81).unwrap_or_default(),
+            // 创建者手续费之后是七个手续费接收账户，再之后才是set_creator_authority
+            fee_recipients: (0..7)
+                .map(|i| read_pubkey(data, 89 + i * 32))
+                .collect::<Option<Vec<_>>>()
+                .unwrap_or_default(),
+            set_creator_authority: read_pubkey(data, 313).unwrap_or_default(),
+            ..Default::default()
+        }))
+    }
+
+    /// 解析原始布局的set_params指令：fee_recipient后接五个u64
+    fn parse_set_params_v1_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if data.len() != 72 || accounts.len() < 2 {
+            return None;
+        }
+        Some(Box::new(PumpFunSetParamsEvent {
+            metadata,
+            global: accounts[0],
+            authority: accounts[1],
+            fee_recipient: read_pubkey(data, 0)?,
+            initial_virtual_token_reserves: read_u64_le(data, 32)?,
+            initial_virtual_sol_reserves: read_u64_le(data, 40)?,
+            initial_real_token_reserves: read_u64_le(data, 48)?,
+            token_total_supply: read_u64_le(data, 56)?,
+            fee_basis_points: read_u64_le(data, 64)?,
+            ..Default::default()
+        }))
+    }
+
+    /// 解析设置创建者指令
+    fn parse_set_creator_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 5 {
+            return None;
+        }
+        Some(Box::new(PumpFunSetCreatorEvent {
+            metadata,
+            creator: read_pubkey(data, 0)?,
+            set_creator_authority: accounts[0],
+            mint: accounts[2],
+            bonding_curve: accounts[4],
+            ..Default::default()
+        }))
+    }
+
+    /// 解析领取创建者手续费指令
+    fn parse_collect_creator_fee_instruction(
+        _data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 2 {
+            return None;
+        }
+        Some(Box::new(PumpFunCollectCreatorFeeEvent {
+            metadata,
+            creator: accounts[0],
+            creator_vault: accounts[1],
+            ..Default::default()
+        }))
+    }
+
+    /// 解析提取指令
+    fn parse_withdraw_instruction(
+        _data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 7 {
+            return None;
+        }
+        Some(Box::new(PumpFunWithdrawEvent {
+            metadata,
+            global: accounts[0],
+            mint: accounts[2],
+            bonding_curve: accounts[3],
+            associated_bonding_curve: accounts[4],
+            user: accounts[6],
+        }))
+    }
 }
 
 #[async_trait::async_trait]
@@ -227,7 +380,7 @@ impl EventParser for PumpFunEventParser {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Vec<Box<dyn UnifiedEvent>> {
         self.inner.parse_events_from_inner_instruction(
             inner_instruction,
@@ -247,7 +400,7 @@ impl EventParser for PumpFunEventParser {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Vec<Box<dyn UnifiedEvent>> {
         self.inner.parse_events_from_instruction(
             instruction,
@@ -263,6 +416,10 @@ impl EventParser for PumpFunEventParser {
     fn get_inner_instruction_configs(&self) -> &std::collections::HashMap<&'static str, Vec<crate::streaming::event_parser::core::traits::GenericEventParseConfig>> {
         self.inner.get_inner_instruction_configs()
     }
+
+    fn get_instruction_configs(&self) -> &std::collections::HashMap<Vec<u8>, Vec<crate::streaming::event_parser::core::traits::GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
     
     fn get_protocol_type(&self) -> crate::streaming::event_parser::common::ProtocolType {
         self.inner.get_protocol_type()
@@ -272,6 +429,10 @@ impl EventParser for PumpFunEventParser {
         self.inner.get_program_id()
     }
 
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
     fn should_handle(&self, program_id: &Pubkey) -> bool {
         self.inner.should_handle(program_id)
     }
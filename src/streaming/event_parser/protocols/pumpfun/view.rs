@@ -0,0 +1,120 @@
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::event_parser::common::{
+    view::{bool_at, check_bool, check_len, i64_at, pubkey_at, u64_at},
+    EventMetadata, ViewError,
+};
+use crate::streaming::event_parser::protocols::pumpfun::PumpFunTradeEvent;
+
+/// PumpFun交易日志事件的零拷贝视图
+///
+/// Reads the fields of a trade event straight from the log bytes (discriminator
+/// stripped) after checking its length and `is_buy` flag once. Use it to filter
+/// trades without building a [`PumpFunTradeEvent`]; [`Self::to_event`] produces
+/// the owned struct, identical to what `borsh::from_slice` returns.
+#[derive(Debug, Clone, Copy)]
+pub struct PumpFunTradeEventView<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> PumpFunTradeEventView<'a> {
+    pub const LEN: usize = 217;
+
+    pub fn new(data: &'a [u8]) -> Result<Self, ViewError> {
+        check_len(data, Self::LEN)?;
+        check_bool(data, 48)?;
+        Ok(Self { data })
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn mint(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 0)
+    }
+
+    pub fn sol_amount(&self) -> u64 {
+        u64_at(self.data, 32)
+    }
+
+    pub fn token_amount(&self) -> u64 {
+        u64_at(self.data, 40)
+    }
+
+    pub fn is_buy(&self) -> bool {
+        bool_at(self.data, 48)
+    }
+
+    pub fn user(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 49)
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        i64_at(self.data, 81)
+    }
+
+    pub fn virtual_sol_reserves(&self) -> u64 {
+        u64_at(self.data, 89)
+    }
+
+    pub fn virtual_token_reserves(&self) -> u64 {
+        u64_at(self.data, 97)
+    }
+
+    pub fn real_sol_reserves(&self) -> u64 {
+        u64_at(self.data, 105)
+    }
+
+    pub fn real_token_reserves(&self) -> u64 {
+        u64_at(self.data, 113)
+    }
+
+    pub fn fee_recipient(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 121)
+    }
+
+    pub fn fee_basis_points(&self) -> u64 {
+        u64_at(self.data, 153)
+    }
+
+    pub fn fee(&self) -> u64 {
+        u64_at(self.data, 161)
+    }
+
+    pub fn creator(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 169)
+    }
+
+    pub fn creator_fee_basis_points(&self) -> u64 {
+        u64_at(self.data, 201)
+    }
+
+    pub fn creator_fee(&self) -> u64 {
+        u64_at(self.data, 209)
+    }
+
+    /// Owned event carrying `metadata`
+    pub fn to_event(&self, metadata: EventMetadata) -> PumpFunTradeEvent {
+        PumpFunTradeEvent {
+            metadata,
+            mint: *self.mint(),
+            sol_amount: self.sol_amount(),
+            token_amount: self.token_amount(),
+            is_buy: self.is_buy(),
+            user: *self.user(),
+            timestamp: self.timestamp(),
+            virtual_sol_reserves: self.virtual_sol_reserves(),
+            virtual_token_reserves: self.virtual_token_reserves(),
+            real_sol_reserves: self.real_sol_reserves(),
+            real_token_reserves: self.real_token_reserves(),
+            fee_recipient: *self.fee_recipient(),
+            fee_basis_points: self.fee_basis_points(),
+            fee: self.fee(),
+            creator: *self.creator(),
+            creator_fee_basis_points: self.creator_fee_basis_points(),
+            creator_fee: self.creator_fee(),
+            ..Default::default()
+        }
+    }
+}
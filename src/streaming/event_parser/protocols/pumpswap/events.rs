@@ -304,19 +304,87 @@ impl_unified_event!(
     user_pool_token_account
 );
 
+/// 手续费配置更新事件 - 来自 `update_fee_config` 指令
+///
+/// Fees of every pool, so quotes follow admin changes instead of constants.
+/// `coin_creator_fee_basis_points` and `admin_set_coin_creator_authority` are zero
+/// for updates made before creator fees existed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PumpSwapUpdateFeeConfigEvent {
+    pub metadata: EventMetadata,
+    pub timestamp: i64,
+    pub admin: Pubkey,
+    pub global_config: Pubkey,
+    pub lp_fee_basis_points: u64,
+    pub protocol_fee_basis_points: u64,
+    pub protocol_fee_recipients: [Pubkey; 8],
+    pub coin_creator_fee_basis_points: u64,
+    pub admin_set_coin_creator_authority: Pubkey,
+}
+
+impl_unified_event!(
+    PumpSwapUpdateFeeConfigEvent,
+    timestamp,
+    lp_fee_basis_points,
+    protocol_fee_basis_points,
+    protocol_fee_recipients,
+    coin_creator_fee_basis_points,
+    admin_set_coin_creator_authority
+);
+
+impl PumpSwapUpdateFeeConfigEvent {
+    /// Fee charged on a swap in total: LP, protocol and coin creator
+    pub fn total_fee_basis_points(&self) -> u64 {
+        self.lp_fee_basis_points + self.protocol_fee_basis_points + self.coin_creator_fee_basis_points
+    }
+}
+
+/// 设置池子币创建者事件 - 来自 `set_coin_creator` 指令
+///
+/// The creator is read from the PumpFun bonding curve of the mint or, for coins
+/// that didn't launch there, its Metaplex metadata; `source` is that account.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct PumpSwapSetCoinCreatorEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub timestamp: i64,
+    pub base_mint: Pubkey,
+    pub pool: Pubkey,
+    pub source: Pubkey,
+    pub coin_creator: Pubkey,
+}
+
+impl_unified_event!(
+    PumpSwapSetCoinCreatorEvent,
+    timestamp,
+    base_mint,
+    source,
+    coin_creator
+);
+
 /// 事件鉴别器常量
 pub mod discriminators {
+    use crate::anchor_event_discriminator;
+    use crate::streaming::event_parser::common::discriminator::instruction_discriminator;
+
     // 事件鉴别器
-    pub const BUY_EVENT: &str = "0xe445a52e51cb9a1d67f4521f2cf57777";
-    pub const SELL_EVENT: &str = "0xe445a52e51cb9a1d3e2f370aa503dc2a";
-    pub const CREATE_POOL_EVENT: &str = "0xe445a52e51cb9a1db1310cd2a076a774";
-    pub const DEPOSIT_EVENT: &str = "0xe445a52e51cb9a1d78f83d531f8e6b90";
-    pub const WITHDRAW_EVENT: &str = "0xe445a52e51cb9a1d1609851aa02c47c0";
+    pub const BUY_EVENT: &str = anchor_event_discriminator!("BuyEvent");
+    pub const SELL_EVENT: &str = anchor_event_discriminator!("SellEvent");
+    pub const CREATE_POOL_EVENT: &str = anchor_event_discriminator!("CreatePoolEvent");
+    pub const DEPOSIT_EVENT: &str = anchor_event_discriminator!("DepositEvent");
+    pub const WITHDRAW_EVENT: &str = anchor_event_discriminator!("WithdrawEvent");
+    pub const UPDATE_FEE_CONFIG_EVENT: &str = anchor_event_discriminator!("UpdateFeeConfigEvent");
+    pub const SET_BONDING_CURVE_COIN_CREATOR_EVENT: &str =
+        anchor_event_discriminator!("SetBondingCurveCoinCreatorEvent");
+    pub const SET_METAPLEX_COIN_CREATOR_EVENT: &str =
+        anchor_event_discriminator!("SetMetaplexCoinCreatorEvent");
 
     // 指令鉴别器
-    pub const BUY_IX: &[u8] = &[102, 6, 61, 18, 1, 218, 235, 234];
-    pub const SELL_IX: &[u8] = &[51, 230, 133, 164, 1, 127, 131, 173];
-    pub const CREATE_POOL_IX: &[u8] = &[233, 146, 209, 142, 207, 104, 64, 188];
-    pub const DEPOSIT_IX: &[u8] = &[242, 35, 198, 137, 82, 225, 242, 182];
-    pub const WITHDRAW_IX: &[u8] = &[183, 18, 70, 156, 148, 109, 161, 34];
+    pub const BUY_IX: &[u8] = &instruction_discriminator("buy");
+    pub const SELL_IX: &[u8] = &instruction_discriminator("sell");
+    pub const CREATE_POOL_IX: &[u8] = &instruction_discriminator("create_pool");
+    pub const DEPOSIT_IX: &[u8] = &instruction_discriminator("deposit");
+    pub const WITHDRAW_IX: &[u8] = &instruction_discriminator("withdraw");
+    pub const UPDATE_FEE_CONFIG_IX: &[u8] = &instruction_discriminator("update_fee_config");
+    pub const SET_COIN_CREATOR_IX: &[u8] = &instruction_discriminator("set_coin_creator");
 }
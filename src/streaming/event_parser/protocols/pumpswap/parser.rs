@@ -1,13 +1,16 @@
+use borsh::BorshDeserialize;
 use prost_types::Timestamp;
 use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
 use solana_transaction_status::UiCompiledInstruction;
 
 use crate::streaming::event_parser::{
-    common::{read_u64_le, EventMetadata, EventType, ProtocolType},
+    common::{read_pubkey, read_u64_le, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
     core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
     protocols::pumpswap::{
-        discriminators, PumpSwapBuyEvent, PumpSwapCreatePoolEvent, PumpSwapDepositEvent,
-        PumpSwapSellEvent, PumpSwapWithdrawEvent,
+        discriminators, PumpSwapBuyEvent, PumpSwapBuyEventView, PumpSwapCreatePoolEvent,
+        PumpSwapDepositEvent, PumpSwapSellEvent, PumpSwapSellEventView,
+        PumpSwapSetCoinCreatorEvent, PumpSwapUpdateFeeConfigEvent, PumpSwapWithdrawEvent,
     },
 };
 
@@ -15,6 +18,9 @@ use crate::streaming::event_parser::{
 pub const PUMPSWAP_PROGRAM_ID: Pubkey =
     solana_sdk::pubkey!("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
 
+/// 指令解码所用的IDL子集
+const IDL: &str = include_str!("idl.json");
+
 /// PumpSwap事件解析器
 pub struct PumpSwapEventParser {
     inner: GenericEventParser,
@@ -30,6 +36,7 @@ impl PumpSwapEventParser {
                 event_type: EventType::PumpSwapBuy,
                 inner_instruction_parser: Self::parse_buy_inner_instruction,
                 instruction_parser: Self::parse_buy_instruction,
+                layouts: Vec::new(),
             },
             GenericEventParseConfig {
                 inner_instruction_discriminator: discriminators::SELL_EVENT,
@@ -37,6 +44,7 @@ impl PumpSwapEventParser {
                 event_type: EventType::PumpSwapSell,
                 inner_instruction_parser: Self::parse_sell_inner_instruction,
                 instruction_parser: Self::parse_sell_instruction,
+                layouts: Vec::new(),
             },
             GenericEventParseConfig {
                 inner_instruction_discriminator: discriminators::CREATE_POOL_EVENT,
@@ -44,6 +52,7 @@ impl PumpSwapEventParser {
                 event_type: EventType::PumpSwapCreatePool,
                 inner_instruction_parser: Self::parse_create_pool_inner_instruction,
                 instruction_parser: Self::parse_create_pool_instruction,
+                layouts: Vec::new(),
             },
             GenericEventParseConfig {
                 inner_instruction_discriminator: discriminators::DEPOSIT_EVENT,
@@ -51,6 +60,7 @@ impl PumpSwapEventParser {
                 event_type: EventType::PumpSwapDeposit,
                 inner_instruction_parser: Self::parse_deposit_inner_instruction,
                 instruction_parser: Self::parse_deposit_instruction,
+                layouts: Vec::new(),
             },
             GenericEventParseConfig {
                 inner_instruction_discriminator: discriminators::WITHDRAW_EVENT,
@@ -58,10 +68,40 @@ impl PumpSwapEventParser {
                 event_type: EventType::PumpSwapWithdraw,
                 inner_instruction_parser: Self::parse_withdraw_inner_instruction,
                 instruction_parser: Self::parse_withdraw_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::UPDATE_FEE_CONFIG_EVENT,
+                instruction_discriminator: discriminators::UPDATE_FEE_CONFIG_IX,
+                event_type: EventType::PumpSwapUpdateFeeConfig,
+                inner_instruction_parser: Self::parse_update_fee_config_inner_instruction,
+                instruction_parser: Self::parse_update_fee_config_instruction,
+                layouts: Vec::new(),
+            },
+            // set_coin_creator按创建者来源发出两种事件之一，指令只解析一次
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::SET_BONDING_CURVE_COIN_CREATOR_EVENT,
+                instruction_discriminator: discriminators::SET_COIN_CREATOR_IX,
+                event_type: EventType::PumpSwapSetCoinCreator,
+                inner_instruction_parser: Self::parse_set_coin_creator_inner_instruction,
+                instruction_parser: Self::parse_set_coin_creator_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: discriminators::SET_METAPLEX_COIN_CREATOR_EVENT,
+                instruction_discriminator: discriminators::SET_COIN_CREATOR_IX,
+                event_type: EventType::PumpSwapSetCoinCreator,
+                inner_instruction_parser: Self::parse_set_coin_creator_inner_instruction,
+                instruction_parser: Self::parse_none_instruction,
+                layouts: Vec::new(),
             },
         ];
 
-        let inner = GenericEventParser::new(PUMPSWAP_PROGRAM_ID, ProtocolType::PumpSwap, configs);
+        let instruction_decoder =
+            InstructionDecoder::from_idl_json(PUMPSWAP_PROGRAM_ID, ProtocolType::PumpSwap, IDL)
+                .expect("bundled IDL is valid");
+        let inner = GenericEventParser::new(PUMPSWAP_PROGRAM_ID, ProtocolType::PumpSwap, configs)
+            .with_instruction_decoder(instruction_decoder);
 
         Self { inner }
     }
@@ -71,19 +111,8 @@ impl PumpSwapEventParser {
         data: &[u8],
         metadata: EventMetadata,
     ) -> Option<Box<dyn UnifiedEvent>> {
-        if let Ok(event) = borsh::from_slice::<PumpSwapBuyEvent>(data) {
-            let mut metadata = metadata;
-            metadata.set_id(format!(
-                "{}-{}-{}-{}",
-                metadata.signature, event.user, event.pool, event.base_amount_out
-            ));
-            Some(Box::new(PumpSwapBuyEvent {
-                metadata: metadata,
-                ..event
-            }))
-        } else {
-            None
-        }
+        let view = PumpSwapBuyEventView::new(data).ok()?;
+        Some(Box::new(view.to_event(metadata)))
     }
 
     /// 解析卖出日志事件
@@ -91,19 +120,8 @@ impl PumpSwapEventParser {
         data: &[u8],
         metadata: EventMetadata,
     ) -> Option<Box<dyn UnifiedEvent>> {
-        if let Ok(event) = borsh::from_slice::<PumpSwapSellEvent>(data) {
-            let mut metadata = metadata;
-            metadata.set_id(format!(
-                "{}-{}-{}-{}",
-                metadata.signature, event.user, event.pool, event.base_amount_in
-            ));
-            Some(Box::new(PumpSwapSellEvent {
-                metadata: metadata,
-                ..event
-            }))
-        } else {
-            None
-        }
+        let view = PumpSwapSellEventView::new(data).ok()?;
+        Some(Box::new(view.to_event(metadata)))
     }
 
     /// 解析创建池子日志事件
@@ -112,11 +130,6 @@ impl PumpSwapEventParser {
         metadata: EventMetadata,
     ) -> Option<Box<dyn UnifiedEvent>> {
         if let Ok(event) = borsh::from_slice::<PumpSwapCreatePoolEvent>(data) {
-            let mut metadata = metadata;
-            metadata.set_id(format!(
-                "{}-{}-{}-{}",
-                metadata.signature, event.pool, event.creator, event.base_amount_in
-            ));
             Some(Box::new(PumpSwapCreatePoolEvent {
                 metadata: metadata,
                 ..event
@@ -132,11 +145,6 @@ impl PumpSwapEventParser {
         metadata: EventMetadata,
     ) -> Option<Box<dyn UnifiedEvent>> {
         if let Ok(event) = borsh::from_slice::<PumpSwapDepositEvent>(data) {
-            let mut metadata = metadata;
-            metadata.set_id(format!(
-                "{}-{}-{}-{}",
-                metadata.signature, event.pool, event.user, event.lp_token_amount_out
-            ));
             Some(Box::new(PumpSwapDepositEvent {
                 metadata: metadata,
                 ..event
@@ -152,11 +160,6 @@ impl PumpSwapEventParser {
         metadata: EventMetadata,
     ) -> Option<Box<dyn UnifiedEvent>> {
         if let Ok(event) = borsh::from_slice::<PumpSwapWithdrawEvent>(data) {
-            let mut metadata = metadata;
-            metadata.set_id(format!(
-                "{}-{}-{}-{}",
-                metadata.signature, event.pool, event.user, event.lp_token_amount_in
-            ));
             Some(Box::new(PumpSwapWithdrawEvent {
                 metadata: metadata,
                 ..event
@@ -166,6 +169,93 @@ impl PumpSwapEventParser {
         }
     }
 
+    /// 解析手续费配置更新日志事件
+    ///
+    /// Layout: timestamp, admin, lp and protocol fees, eight protocol fee
+    /// recipients, then coin_creator_fee_basis_points and
+    /// admin_set_coin_creator_authority, which older events don't have
+    fn parse_update_fee_config_inner_instruction(
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let timestamp = read_u64_le(data, 0)? as i64;
+        let admin = read_pubkey(data, 8)?;
+        Some(Box::new(PumpSwapUpdateFeeConfigEvent {
+            metadata,
+            timestamp,
+            admin,
+            ..Self::fee_config_from(data, 40)?
+        }))
+    }
+
+    /// 解析设置币创建者日志事件
+    fn parse_set_coin_creator_inner_instruction(
+        data: &[u8],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        // 不要求读完数据，程序升级可能在末尾追加字段
+        let event = PumpSwapSetCoinCreatorEvent::deserialize(&mut &data[..]).ok()?;
+        Some(Box::new(PumpSwapSetCoinCreatorEvent { metadata, ..event }))
+    }
+
+    /// 从`offset`处读取手续费配置，事件和指令参数共用同一布局
+    fn fee_config_from(data: &[u8], offset: usize) -> Option<PumpSwapUpdateFeeConfigEvent> {
+        let mut protocol_fee_recipients = [Pubkey::default(); 8];
+        for (i, recipient) in protocol_fee_recipients.iter_mut().enumerate() {
+            *recipient = read_pubkey(data, offset + 16 + i * 32)?;
+        }
+        Some(PumpSwapUpdateFeeConfigEvent {
+            lp_fee_basis_points: read_u64_le(data, offset)?,
+            protocol_fee_basis_points: read_u64_le(data, offset + 8)?,
+            protocol_fee_recipients,
+            coin_creator_fee_basis_points: read_u64_le(data, offset + 272).unwrap_or_default(),
+            admin_set_coin_creator_authority: read_pubkey(data, offset + 280)
+                .unwrap_or_default(),
+            ..Default::default()
+        })
+    }
+
+    /// 解析手续费配置更新指令事件
+    fn parse_update_fee_config_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 2 {
+            return None;
+        }
+        Some(Box::new(PumpSwapUpdateFeeConfigEvent {
+            metadata,
+            admin: accounts[0],
+            global_config: accounts[1],
+            ..Self::fee_config_from(data, 0)?
+        }))
+    }
+
+    /// 解析设置币创建者指令事件
+    fn parse_set_coin_creator_instruction(
+        _data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 3 {
+            return None;
+        }
+        Some(Box::new(PumpSwapSetCoinCreatorEvent {
+            metadata,
+            pool: accounts[0],
+            ..Default::default()
+        }))
+    }
+
+    fn parse_none_instruction(
+        _data: &[u8],
+        _accounts: &[Pubkey],
+        _metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        None
+    }
+
     /// 解析买入指令事件
     fn parse_buy_instruction(
         data: &[u8],
@@ -179,12 +269,6 @@ impl PumpSwapEventParser {
         let base_amount_out = read_u64_le(data, 0)?;
         let max_quote_amount_in = read_u64_le(data, 8)?;
 
-        let mut metadata = metadata;
-        metadata.set_id(format!(
-            "{}-{}-{}-{}",
-            metadata.signature, accounts[1], accounts[0], base_amount_out
-        ));
-
         Some(Box::new(PumpSwapBuyEvent {
             metadata,
             base_amount_out,
@@ -218,12 +302,6 @@ impl PumpSwapEventParser {
         let base_amount_in = read_u64_le(data, 0)?;
         let min_quote_amount_out = read_u64_le(data, 8)?;
 
-        let mut metadata = metadata;
-        metadata.set_id(format!(
-            "{}-{}-{}-{}",
-            metadata.signature, accounts[1], accounts[0], base_amount_in
-        ));
-
         Some(Box::new(PumpSwapSellEvent {
             metadata,
             base_amount_in,
@@ -263,12 +341,6 @@ impl PumpSwapEventParser {
             Pubkey::default()
         };
 
-        let mut metadata = metadata;
-        metadata.set_id(format!(
-            "{}-{}-{}-{}",
-            metadata.signature, accounts[0], accounts[2], base_amount_in
-        ));
-
         Some(Box::new(PumpSwapCreatePoolEvent {
             metadata,
             index,
@@ -303,12 +375,6 @@ impl PumpSwapEventParser {
         let max_base_amount_in = u64::from_le_bytes(data[8..16].try_into().ok()?);
         let max_quote_amount_in = u64::from_le_bytes(data[16..24].try_into().ok()?);
 
-        let mut metadata = metadata;
-        metadata.set_id(format!(
-            "{}-{}-{}-{}",
-            metadata.signature, accounts[0], accounts[2], lp_token_amount_out
-        ));
-
         Some(Box::new(PumpSwapDepositEvent {
             metadata,
             lp_token_amount_out,
@@ -341,12 +407,6 @@ impl PumpSwapEventParser {
         let min_base_amount_out = u64::from_le_bytes(data[8..16].try_into().ok()?);
         let min_quote_amount_out = u64::from_le_bytes(data[16..24].try_into().ok()?);
 
-        let mut metadata = metadata;
-        metadata.set_id(format!(
-            "{}-{}-{}-{}",
-            metadata.signature, accounts[0], accounts[2], lp_token_amount_in
-        ));
-
         Some(Box::new(PumpSwapWithdrawEvent {
             metadata,
             lp_token_amount_in,
@@ -375,7 +435,7 @@ impl EventParser for PumpSwapEventParser {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Vec<Box<dyn UnifiedEvent>> {
         self.inner.parse_events_from_inner_instruction(
             inner_instruction,
@@ -395,7 +455,7 @@ impl EventParser for PumpSwapEventParser {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Vec<Box<dyn UnifiedEvent>> {
         self.inner.parse_events_from_instruction(
             instruction,
@@ -408,6 +468,10 @@ impl EventParser for PumpSwapEventParser {
         )
     }
 
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
     fn should_handle(&self, program_id: &Pubkey) -> bool {
         self.inner.should_handle(program_id)
     }
@@ -419,6 +483,10 @@ impl EventParser for PumpSwapEventParser {
     fn get_inner_instruction_configs(&self) -> &std::collections::HashMap<&'static str, Vec<crate::streaming::event_parser::core::traits::GenericEventParseConfig>> {
         self.inner.get_inner_instruction_configs()
     }
+
+    fn get_instruction_configs(&self) -> &std::collections::HashMap<Vec<u8>, Vec<crate::streaming::event_parser::core::traits::GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
     
     fn get_protocol_type(&self) -> ProtocolType {
         ProtocolType::PumpSwap
@@ -0,0 +1,298 @@
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::event_parser::common::{
+    view::{check_len, i64_at, pubkey_at, u64_at},
+    EventMetadata, ViewError,
+};
+use crate::streaming::event_parser::protocols::pumpswap::{PumpSwapBuyEvent, PumpSwapSellEvent};
+
+// 买入和卖出事件布局相同，只是部分字段含义不同
+const LEN: usize = 352;
+
+/// PumpSwap买入日志事件的零拷贝视图
+///
+/// See [`PumpSwapSellEventView`] for the sell side; both have the same layout.
+/// [`Self::to_event`] produces the owned struct, identical to what
+/// `borsh::from_slice` returns.
+#[derive(Debug, Clone, Copy)]
+pub struct PumpSwapBuyEventView<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> PumpSwapBuyEventView<'a> {
+    pub const LEN: usize = LEN;
+
+    pub fn new(data: &'a [u8]) -> Result<Self, ViewError> {
+        check_len(data, LEN)?;
+        Ok(Self { data })
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        i64_at(self.data, 0)
+    }
+
+    pub fn base_amount_out(&self) -> u64 {
+        u64_at(self.data, 8)
+    }
+
+    pub fn max_quote_amount_in(&self) -> u64 {
+        u64_at(self.data, 16)
+    }
+
+    pub fn user_base_token_reserves(&self) -> u64 {
+        u64_at(self.data, 24)
+    }
+
+    pub fn user_quote_token_reserves(&self) -> u64 {
+        u64_at(self.data, 32)
+    }
+
+    pub fn pool_base_token_reserves(&self) -> u64 {
+        u64_at(self.data, 40)
+    }
+
+    pub fn pool_quote_token_reserves(&self) -> u64 {
+        u64_at(self.data, 48)
+    }
+
+    pub fn quote_amount_in(&self) -> u64 {
+        u64_at(self.data, 56)
+    }
+
+    pub fn lp_fee_basis_points(&self) -> u64 {
+        u64_at(self.data, 64)
+    }
+
+    pub fn lp_fee(&self) -> u64 {
+        u64_at(self.data, 72)
+    }
+
+    pub fn protocol_fee_basis_points(&self) -> u64 {
+        u64_at(self.data, 80)
+    }
+
+    pub fn protocol_fee(&self) -> u64 {
+        u64_at(self.data, 88)
+    }
+
+    pub fn quote_amount_in_with_lp_fee(&self) -> u64 {
+        u64_at(self.data, 96)
+    }
+
+    pub fn user_quote_amount_in(&self) -> u64 {
+        u64_at(self.data, 104)
+    }
+
+    pub fn pool(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 112)
+    }
+
+    pub fn user(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 144)
+    }
+
+    pub fn user_base_token_account(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 176)
+    }
+
+    pub fn user_quote_token_account(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 208)
+    }
+
+    pub fn protocol_fee_recipient(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 240)
+    }
+
+    pub fn protocol_fee_recipient_token_account(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 272)
+    }
+
+    pub fn coin_creator(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 304)
+    }
+
+    pub fn coin_creator_fee_basis_points(&self) -> u64 {
+        u64_at(self.data, 336)
+    }
+
+    pub fn coin_creator_fee(&self) -> u64 {
+        u64_at(self.data, 344)
+    }
+
+    /// Owned event carrying `metadata`
+    pub fn to_event(&self, metadata: EventMetadata) -> PumpSwapBuyEvent {
+        PumpSwapBuyEvent {
+            metadata,
+            timestamp: self.timestamp(),
+            base_amount_out: self.base_amount_out(),
+            max_quote_amount_in: self.max_quote_amount_in(),
+            user_base_token_reserves: self.user_base_token_reserves(),
+            user_quote_token_reserves: self.user_quote_token_reserves(),
+            pool_base_token_reserves: self.pool_base_token_reserves(),
+            pool_quote_token_reserves: self.pool_quote_token_reserves(),
+            quote_amount_in: self.quote_amount_in(),
+            lp_fee_basis_points: self.lp_fee_basis_points(),
+            lp_fee: self.lp_fee(),
+            protocol_fee_basis_points: self.protocol_fee_basis_points(),
+            protocol_fee: self.protocol_fee(),
+            quote_amount_in_with_lp_fee: self.quote_amount_in_with_lp_fee(),
+            user_quote_amount_in: self.user_quote_amount_in(),
+            pool: *self.pool(),
+            user: *self.user(),
+            user_base_token_account: *self.user_base_token_account(),
+            user_quote_token_account: *self.user_quote_token_account(),
+            protocol_fee_recipient: *self.protocol_fee_recipient(),
+            protocol_fee_recipient_token_account: *self.protocol_fee_recipient_token_account(),
+            coin_creator: *self.coin_creator(),
+            coin_creator_fee_basis_points: self.coin_creator_fee_basis_points(),
+            coin_creator_fee: self.coin_creator_fee(),
+            ..Default::default()
+        }
+    }
+}
+
+/// PumpSwap卖出日志事件的零拷贝视图
+#[derive(Debug, Clone, Copy)]
+pub struct PumpSwapSellEventView<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> PumpSwapSellEventView<'a> {
+    pub const LEN: usize = LEN;
+
+    pub fn new(data: &'a [u8]) -> Result<Self, ViewError> {
+        check_len(data, LEN)?;
+        Ok(Self { data })
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        i64_at(self.data, 0)
+    }
+
+    pub fn base_amount_in(&self) -> u64 {
+        u64_at(self.data, 8)
+    }
+
+    pub fn min_quote_amount_out(&self) -> u64 {
+        u64_at(self.data, 16)
+    }
+
+    pub fn user_base_token_reserves(&self) -> u64 {
+        u64_at(self.data, 24)
+    }
+
+    pub fn user_quote_token_reserves(&self) -> u64 {
+        u64_at(self.data, 32)
+    }
+
+    pub fn pool_base_token_reserves(&self) -> u64 {
+        u64_at(self.data, 40)
+    }
+
+    pub fn pool_quote_token_reserves(&self) -> u64 {
+        u64_at(self.data, 48)
+    }
+
+    pub fn quote_amount_out(&self) -> u64 {
+        u64_at(self.data, 56)
+    }
+
+    pub fn lp_fee_basis_points(&self) -> u64 {
+        u64_at(self.data, 64)
+    }
+
+    pub fn lp_fee(&self) -> u64 {
+        u64_at(self.data, 72)
+    }
+
+    pub fn protocol_fee_basis_points(&self) -> u64 {
+        u64_at(self.data, 80)
+    }
+
+    pub fn protocol_fee(&self) -> u64 {
+        u64_at(self.data, 88)
+    }
+
+    pub fn quote_amount_out_without_lp_fee(&self) -> u64 {
+        u64_at(self.data, 96)
+    }
+
+    pub fn user_quote_amount_out(&self) -> u64 {
+        u64_at(self.data, 104)
+    }
+
+    pub fn pool(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 112)
+    }
+
+    pub fn user(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 144)
+    }
+
+    pub fn user_base_token_account(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 176)
+    }
+
+    pub fn user_quote_token_account(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 208)
+    }
+
+    pub fn protocol_fee_recipient(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 240)
+    }
+
+    pub fn protocol_fee_recipient_token_account(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 272)
+    }
+
+    pub fn coin_creator(&self) -> &'a Pubkey {
+        pubkey_at(self.data, 304)
+    }
+
+    pub fn coin_creator_fee_basis_points(&self) -> u64 {
+        u64_at(self.data, 336)
+    }
+
+    pub fn coin_creator_fee(&self) -> u64 {
+        u64_at(self.data, 344)
+    }
+
+    /// Owned event carrying `metadata`
+    pub fn to_event(&self, metadata: EventMetadata) -> PumpSwapSellEvent {
+        PumpSwapSellEvent {
+            metadata,
+            timestamp: self.timestamp(),
+            base_amount_in: self.base_amount_in(),
+            min_quote_amount_out: self.min_quote_amount_out(),
+            user_base_token_reserves: self.user_base_token_reserves(),
+            user_quote_token_reserves: self.user_quote_token_reserves(),
+            pool_base_token_reserves: self.pool_base_token_reserves(),
+            pool_quote_token_reserves: self.pool_quote_token_reserves(),
+            quote_amount_out: self.quote_amount_out(),
+            lp_fee_basis_points: self.lp_fee_basis_points(),
+            lp_fee: self.lp_fee(),
+            protocol_fee_basis_points: self.protocol_fee_basis_points(),
+            protocol_fee: self.protocol_fee(),
+            quote_amount_out_without_lp_fee: self.quote_amount_out_without_lp_fee(),
+            user_quote_amount_out: self.user_quote_amount_out(),
+            pool: *self.pool(),
+            user: *self.user(),
+            user_base_token_account: *self.user_base_token_account(),
+            user_quote_token_account: *self.user_quote_token_account(),
+            protocol_fee_recipient: *self.protocol_fee_recipient(),
+            protocol_fee_recipient_token_account: *self.protocol_fee_recipient_token_account(),
+            coin_creator: *self.coin_creator(),
+            coin_creator_fee_basis_points: self.coin_creator_fee_basis_points(),
+            coin_creator_fee: self.coin_creator_fee(),
+            ..Default::default()
+        }
+    }
+}
@@ -53,7 +53,9 @@ impl_unified_event!(RaydiumClmmSwapV2Event,);
 
 /// 事件鉴别器常量
 pub mod discriminators {
+    use crate::streaming::event_parser::common::discriminator::instruction_discriminator;
+
     // 指令鉴别器
-    pub const SWAP: &[u8] = &[248, 198, 158, 145, 225, 117, 135, 200];
-    pub const SWAP_V2: &[u8] = &[43, 4, 237, 11, 26, 201, 30, 98];
+    pub const SWAP: &[u8] = &instruction_discriminator("swap");
+    pub const SWAP_V2: &[u8] = &instruction_discriminator("swap_v2");
 }
@@ -3,7 +3,11 @@ use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
 use solana_transaction_status::UiCompiledInstruction;
 
 use crate::streaming::event_parser::{
-    common::{read_u128_le, read_u64_le, read_u8_le, EventMetadata, EventType, ProtocolType},
+    common::{
+        read_u128_le, read_u64_le, read_u8_le, EventMetadata, EventType, InstructionIndex,
+        ProtocolType,
+    },
+    core::instruction_decoder::InstructionDecoder,
     core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
     protocols::raydium_clmm::{discriminators, RaydiumClmmSwapEvent, RaydiumClmmSwapV2Event},
 };
@@ -12,6 +16,9 @@ use crate::streaming::event_parser::{
 pub const RAYDIUM_CLMM_PROGRAM_ID: Pubkey =
     solana_sdk::pubkey!("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK");
 
+/// 指令解码所用的IDL子集
+const IDL: &str = include_str!("idl.json");
+
 /// Raydium CLMM事件解析器
 pub struct RaydiumClmmEventParser {
     inner: GenericEventParser,
@@ -27,6 +34,7 @@ impl RaydiumClmmEventParser {
                 event_type: EventType::RaydiumClmmSwap,
                 inner_instruction_parser: Self::parse_trade_inner_instruction,
                 instruction_parser: Self::parse_swap_instruction,
+                layouts: Vec::new(),
             },
             GenericEventParseConfig {
                 inner_instruction_discriminator: "",
@@ -34,11 +42,15 @@ impl RaydiumClmmEventParser {
                 event_type: EventType::RaydiumClmmSwapV2,
                 inner_instruction_parser: Self::parse_trade_inner_instruction,
                 instruction_parser: Self::parse_swap_v2_instruction,
+                layouts: Vec::new(),
             },
         ];
 
-        let inner =
-            GenericEventParser::new(RAYDIUM_CLMM_PROGRAM_ID, ProtocolType::RaydiumClmm, configs);
+        let instruction_decoder =
+            InstructionDecoder::from_idl_json(RAYDIUM_CLMM_PROGRAM_ID, ProtocolType::RaydiumClmm, IDL)
+                .expect("bundled IDL is valid");
+        let inner = GenericEventParser::new(RAYDIUM_CLMM_PROGRAM_ID, ProtocolType::RaydiumClmm, configs)
+            .with_instruction_decoder(instruction_decoder);
 
         Self { inner }
     }
@@ -66,12 +78,6 @@ impl RaydiumClmmEventParser {
         let sqrt_price_limit_x64 = read_u128_le(data, 16)?;
         let is_base_input = read_u8_le(data, 32)?;
 
-        let mut metadata = metadata;
-        metadata.set_id(format!(
-            "{}-{}-{}-{}",
-            metadata.signature, accounts[2], accounts[3], accounts[4]
-        ));
-
         Some(Box::new(RaydiumClmmSwapEvent {
             metadata,
             amount,
@@ -107,12 +113,6 @@ impl RaydiumClmmEventParser {
         let sqrt_price_limit_x64 = read_u128_le(data, 16)?;
         let is_base_input = read_u8_le(data, 32)?;
 
-        let mut metadata = metadata;
-        metadata.set_id(format!(
-            "{}-{}-{}-{}",
-            metadata.signature, accounts[2], accounts[3], accounts[4]
-        ));
-
         Some(Box::new(RaydiumClmmSwapV2Event {
             metadata,
             amount,
@@ -147,7 +147,7 @@ impl EventParser for RaydiumClmmEventParser {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Vec<Box<dyn UnifiedEvent>> {
         self.inner.parse_events_from_inner_instruction(
             inner_instruction,
@@ -167,7 +167,7 @@ impl EventParser for RaydiumClmmEventParser {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Vec<Box<dyn UnifiedEvent>> {
         self.inner.parse_events_from_instruction(
             instruction,
@@ -180,10 +180,24 @@ impl EventParser for RaydiumClmmEventParser {
         )
     }
 
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
     fn should_handle(&self, program_id: &Pubkey) -> bool {
         self.inner.should_handle(program_id)
     }
 
+    fn get_protocol_type(&self) -> ProtocolType {
+        self.inner.get_protocol_type()
+    }
+
+    fn get_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<Vec<u8>, Vec<GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
+
     fn supported_program_ids(&self) -> Vec<Pubkey> {
         self.inner.supported_program_ids()
     }
@@ -29,7 +29,9 @@ impl_unified_event!(RaydiumCpmmSwapEvent,);
 
 /// 事件鉴别器常量
 pub mod discriminators {
+    use crate::streaming::event_parser::common::discriminator::instruction_discriminator;
+
     // 指令鉴别器
-    pub const SWAP_BASE_IN: &[u8] = &[143, 190, 90, 218, 196, 30, 51, 222];
-    pub const SWAP_BASE_OUT: &[u8] = &[55, 217, 98, 86, 163, 74, 180, 173];
+    pub const SWAP_BASE_IN: &[u8] = &instruction_discriminator("swap_base_input");
+    pub const SWAP_BASE_OUT: &[u8] = &instruction_discriminator("swap_base_output");
 }
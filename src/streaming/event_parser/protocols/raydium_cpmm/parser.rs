@@ -3,7 +3,8 @@ use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
 use solana_transaction_status::UiCompiledInstruction;
 
 use crate::streaming::event_parser::{
-    common::{read_u64_le, EventMetadata, EventType, ProtocolType},
+    common::{read_u64_le, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
     core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
     protocols::raydium_cpmm::{discriminators, RaydiumCpmmSwapEvent},
 };
@@ -12,6 +13,9 @@ use crate::streaming::event_parser::{
 pub const RAYDIUM_CPMM_PROGRAM_ID: Pubkey =
     solana_sdk::pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
 
+/// 指令解码所用的IDL子集
+const IDL: &str = include_str!("idl.json");
+
 /// Raydium CPMM事件解析器
 pub struct RaydiumCpmmEventParser {
     inner: GenericEventParser,
@@ -27,6 +31,7 @@ impl RaydiumCpmmEventParser {
                 event_type: EventType::RaydiumCpmmSwapBaseInput,
                 inner_instruction_parser: Self::parse_trade_inner_instruction,
                 instruction_parser: Self::parse_swap_base_input_instruction,
+                layouts: Vec::new(),
             },
             GenericEventParseConfig {
                 inner_instruction_discriminator: "",
@@ -34,11 +39,15 @@ impl RaydiumCpmmEventParser {
                 event_type: EventType::RaydiumCpmmSwapBaseOutput,
                 inner_instruction_parser: Self::parse_trade_inner_instruction,
                 instruction_parser: Self::parse_swap_base_output_instruction,
+                layouts: Vec::new(),
             },
         ];
 
-        let inner =
-            GenericEventParser::new(RAYDIUM_CPMM_PROGRAM_ID, ProtocolType::RaydiumCpmm, configs);
+        let instruction_decoder =
+            InstructionDecoder::from_idl_json(RAYDIUM_CPMM_PROGRAM_ID, ProtocolType::RaydiumCpmm, IDL)
+                .expect("bundled IDL is valid");
+        let inner = GenericEventParser::new(RAYDIUM_CPMM_PROGRAM_ID, ProtocolType::RaydiumCpmm, configs)
+            .with_instruction_decoder(instruction_decoder);
 
         Self { inner }
     }
@@ -64,12 +73,6 @@ impl RaydiumCpmmEventParser {
         let amount_in = read_u64_le(data, 0)?;
         let minimum_amount_out = read_u64_le(data, 8)?;
 
-        let mut metadata = metadata;
-        metadata.set_id(format!(
-            "{}-{}-{}-{}",
-            metadata.signature, accounts[3], accounts[10], accounts[11]
-        ));
-
         Some(Box::new(RaydiumCpmmSwapEvent {
             metadata,
             amount_in,
@@ -101,12 +104,6 @@ impl RaydiumCpmmEventParser {
         let max_amount_in = read_u64_le(data, 0)?;
         let amount_out = read_u64_le(data, 8)?;
 
-        let mut metadata = metadata;
-        metadata.set_id(format!(
-            "{}-{}-{}-{}",
-            metadata.signature, accounts[3], accounts[10], accounts[11]
-        ));
-
         Some(Box::new(RaydiumCpmmSwapEvent {
             metadata,
             max_amount_in,
@@ -136,7 +133,7 @@ impl EventParser for RaydiumCpmmEventParser {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Vec<Box<dyn UnifiedEvent>> {
         self.inner.parse_events_from_inner_instruction(
             inner_instruction,
@@ -156,7 +153,7 @@ impl EventParser for RaydiumCpmmEventParser {
         slot: u64,
         block_time: Option<Timestamp>,
         program_received_time_ms: i64,
-        index: String,
+        index: InstructionIndex,
     ) -> Vec<Box<dyn UnifiedEvent>> {
         self.inner.parse_events_from_instruction(
             instruction,
@@ -169,10 +166,24 @@ impl EventParser for RaydiumCpmmEventParser {
         )
     }
 
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
     fn should_handle(&self, program_id: &Pubkey) -> bool {
         self.inner.should_handle(program_id)
     }
 
+    fn get_protocol_type(&self) -> ProtocolType {
+        self.inner.get_protocol_type()
+    }
+
+    fn get_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<Vec<u8>, Vec<GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
+
     fn supported_program_ids(&self) -> Vec<Pubkey> {
         self.inner.supported_program_ids()
     }
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::EventMetadata;
+
+/// 质押池存入/取出
+///
+/// Deposits turn SOL or a stake account into the pool's LST, withdrawals the
+/// other way round. `amount` is lamports for SOL deposits and pool tokens
+/// otherwise; stake deposits carry no amount, it is the stake account's balance.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SanctumStakePoolEvent {
+    pub metadata: EventMetadata,
+    pub stake_pool: Pubkey,
+    /// LST mint
+    pub pool_mint: Pubkey,
+    /// Wallet paying or receiving SOL, or the transfer authority of the pool tokens
+    pub user: Pubkey,
+    pub user_pool_token_account: Pubkey,
+    pub reserve_stake: Pubkey,
+    pub validator_list: Pubkey,
+    pub manager_fee_account: Pubkey,
+    /// Validator stake account the stake is merged into or split from
+    pub validator_stake: Pubkey,
+    /// Stake account deposited or received
+    pub user_stake_account: Pubkey,
+    pub amount: u64,
+    /// Slippage limit of the `*WithSlippage` instructions
+    pub minimum_out: u64,
+    pub is_deposit: bool,
+}
+
+impl_unified_event!(SanctumStakePoolEvent,);
+
+/// Sanctum Router 兑换
+///
+/// The router swaps between LSTs, and between wSOL and LSTs, through a bridge
+/// stake account that is withdrawn from the source pool and deposited into the
+/// destination pool.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SanctumSwapEvent {
+    pub metadata: EventMetadata,
+    pub user: Pubkey,
+    pub src_token_account: Pubkey,
+    pub dest_token_account: Pubkey,
+    pub src_mint: Pubkey,
+    pub dest_mint: Pubkey,
+    pub amount: u64,
+    /// Bridge stake account, default for wSOL deposits which go through SOL
+    pub bridge_stake: Pubkey,
+    pub bridge_stake_seed: u32,
+    pub fee_token_account: Pubkey,
+}
+
+impl_unified_event!(SanctumSwapEvent,);
+
+/// 指令鉴别器常量 (单字节指令标签)
+pub mod discriminators {
+    // SPL Stake Pool
+    pub const DEPOSIT_STAKE_IX: &[u8] = &[9];
+    pub const WITHDRAW_STAKE_IX: &[u8] = &[10];
+    pub const DEPOSIT_SOL_IX: &[u8] = &[14];
+    pub const WITHDRAW_SOL_IX: &[u8] = &[16];
+    pub const DEPOSIT_STAKE_WITH_SLIPPAGE_IX: &[u8] = &[23];
+    pub const WITHDRAW_STAKE_WITH_SLIPPAGE_IX: &[u8] = &[24];
+    pub const DEPOSIT_SOL_WITH_SLIPPAGE_IX: &[u8] = &[25];
+    pub const WITHDRAW_SOL_WITH_SLIPPAGE_IX: &[u8] = &[26];
+
+    // Sanctum Router
+    pub const STAKE_WRAPPED_SOL_IX: &[u8] = &[0];
+    pub const SWAP_VIA_STAKE_IX: &[u8] = &[1];
+    pub const PREFUND_SWAP_VIA_STAKE_IX: &[u8] = &[7];
+}
@@ -0,0 +1,409 @@
+use prost_types::Timestamp;
+use solana_sdk::{
+    instruction::CompiledInstruction, pubkey::Pubkey, transaction::VersionedTransaction,
+};
+use solana_transaction_status::{UiCompiledInstruction, UiInnerInstructions};
+
+use crate::streaming::event_parser::{
+    common::{read_u32_le, read_u64_le, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
+    core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
+    protocols::sanctum::{discriminators, SanctumStakePoolEvent, SanctumSwapEvent},
+};
+
+/// Sanctum Router程序ID
+pub const SANCTUM_ROUTER_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("stkitrT1Uoy18Dk1fTrgPw8W6MVzoCfrDGZc8HmdqTA");
+
+/// SPL Stake Pool程序ID
+pub const SPL_STAKE_POOL_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+/// Sanctum部署的SPL Stake Pool (单验证者)
+pub const SANCTUM_SPL_STAKE_POOL_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("SP12tWFxD9oJsVWNavTTBZvMbA6gkAmxtVgxdqvyvhY");
+
+/// Sanctum部署的SPL Stake Pool (多验证者)
+pub const SANCTUM_SPL_MULTI_STAKE_POOL_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("SPMBzsVUuoHA4Jm6KunbsotaahvVikZs1JyTW6iJvbn");
+
+/// 与SPL Stake Pool指令布局相同的程序
+pub const STAKE_POOL_PROGRAM_IDS: [Pubkey; 3] = [
+    SPL_STAKE_POOL_PROGRAM_ID,
+    SANCTUM_SPL_STAKE_POOL_PROGRAM_ID,
+    SANCTUM_SPL_MULTI_STAKE_POOL_PROGRAM_ID,
+];
+
+/// Sanctum事件解析器
+///
+/// Parses the Sanctum Router and the stake pool programs that share the SPL
+/// stake pool instruction layout. None of them emit events, so everything comes
+/// from the instructions.
+pub struct SanctumEventParser {
+    router: GenericEventParser,
+    stake_pools: Vec<GenericEventParser>,
+}
+
+impl SanctumEventParser {
+    pub fn new() -> Self {
+        let router_configs = vec![
+            Self::config(
+                discriminators::STAKE_WRAPPED_SOL_IX,
+                EventType::SanctumSwap,
+                Self::parse_stake_wrapped_sol_instruction,
+            ),
+            Self::config(
+                discriminators::SWAP_VIA_STAKE_IX,
+                EventType::SanctumSwap,
+                Self::parse_swap_via_stake_instruction,
+            ),
+            Self::config(
+                discriminators::PREFUND_SWAP_VIA_STAKE_IX,
+                EventType::SanctumSwap,
+                Self::parse_swap_via_stake_instruction,
+            ),
+        ];
+        let stake_pool_configs = vec![
+            Self::config(
+                discriminators::DEPOSIT_STAKE_IX,
+                EventType::SanctumDepositStake,
+                Self::parse_deposit_stake_instruction,
+            ),
+            Self::config(
+                discriminators::DEPOSIT_STAKE_WITH_SLIPPAGE_IX,
+                EventType::SanctumDepositStake,
+                Self::parse_deposit_stake_with_slippage_instruction,
+            ),
+            Self::config(
+                discriminators::WITHDRAW_STAKE_IX,
+                EventType::SanctumWithdrawStake,
+                Self::parse_withdraw_stake_instruction,
+            ),
+            Self::config(
+                discriminators::WITHDRAW_STAKE_WITH_SLIPPAGE_IX,
+                EventType::SanctumWithdrawStake,
+                Self::parse_withdraw_stake_instruction,
+            ),
+            Self::config(
+                discriminators::DEPOSIT_SOL_IX,
+                EventType::SanctumDepositSol,
+                Self::parse_deposit_sol_instruction,
+            ),
+            Self::config(
+                discriminators::DEPOSIT_SOL_WITH_SLIPPAGE_IX,
+                EventType::SanctumDepositSol,
+                Self::parse_deposit_sol_instruction,
+            ),
+            Self::config(
+                discriminators::WITHDRAW_SOL_IX,
+                EventType::SanctumWithdrawSol,
+                Self::parse_withdraw_sol_instruction,
+            ),
+            Self::config(
+                discriminators::WITHDRAW_SOL_WITH_SLIPPAGE_IX,
+                EventType::SanctumWithdrawSol,
+                Self::parse_withdraw_sol_instruction,
+            ),
+        ];
+
+        let router = GenericEventParser::new(
+            SANCTUM_ROUTER_PROGRAM_ID,
+            ProtocolType::Sanctum,
+            router_configs,
+        );
+        let stake_pools = STAKE_POOL_PROGRAM_IDS
+            .iter()
+            .map(|program_id| {
+                GenericEventParser::new(
+                    *program_id,
+                    ProtocolType::Sanctum,
+                    stake_pool_configs.clone(),
+                )
+            })
+            .collect();
+
+        Self {
+            router,
+            stake_pools,
+        }
+    }
+
+    fn config(
+        instruction_discriminator: &'static [u8],
+        event_type: EventType,
+        instruction_parser: fn(&[u8], &[Pubkey], EventMetadata) -> Option<Box<dyn UnifiedEvent>>,
+    ) -> GenericEventParseConfig {
+        GenericEventParseConfig {
+            inner_instruction_discriminator: "",
+            instruction_discriminator,
+            event_type,
+            inner_instruction_parser: Self::parse_none_inner_instruction,
+            instruction_parser,
+            layouts: Vec::new(),
+        }
+    }
+
+    /// 这些程序不发出事件
+    fn parse_none_inner_instruction(
+        _data: &[u8],
+        _metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        None
+    }
+
+    fn parsers(&self) -> impl Iterator<Item = &GenericEventParser> {
+        std::iter::once(&self.router).chain(&self.stake_pools)
+    }
+
+    /// 解析质押账户存入指令事件
+    fn parse_deposit_stake_instruction(
+        _data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 11 {
+            return None;
+        }
+        Some(Box::new(SanctumStakePoolEvent {
+            metadata,
+            stake_pool: accounts[0],
+            validator_list: accounts[1],
+            user_stake_account: accounts[4],
+            validator_stake: accounts[5],
+            reserve_stake: accounts[6],
+            user_pool_token_account: accounts[7],
+            manager_fee_account: accounts[8],
+            pool_mint: accounts[10],
+            is_deposit: true,
+            ..Default::default()
+        }))
+    }
+
+    /// 解析带滑点的质押账户存入指令事件
+    fn parse_deposit_stake_with_slippage_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        let minimum_out = read_u64_le(data, 0)?;
+        let mut event = Self::parse_deposit_stake_instruction(data, accounts, metadata)?;
+        if let Some(event) = event.as_any_mut().downcast_mut::<SanctumStakePoolEvent>() {
+            event.minimum_out = minimum_out;
+        }
+        Some(event)
+    }
+
+    /// 解析质押账户取出指令事件
+    fn parse_withdraw_stake_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 10 {
+            return None;
+        }
+        Some(Box::new(SanctumStakePoolEvent {
+            metadata,
+            stake_pool: accounts[0],
+            validator_list: accounts[1],
+            validator_stake: accounts[3],
+            user_stake_account: accounts[4],
+            user: accounts[6],
+            user_pool_token_account: accounts[7],
+            manager_fee_account: accounts[8],
+            pool_mint: accounts[9],
+            amount: read_u64_le(data, 0)?,
+            minimum_out: read_u64_le(data, 8).unwrap_or_default(),
+            is_deposit: false,
+            ..Default::default()
+        }))
+    }
+
+    /// 解析SOL存入指令事件
+    fn parse_deposit_sol_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 8 {
+            return None;
+        }
+        Some(Box::new(SanctumStakePoolEvent {
+            metadata,
+            stake_pool: accounts[0],
+            reserve_stake: accounts[2],
+            user: accounts[3],
+            user_pool_token_account: accounts[4],
+            manager_fee_account: accounts[5],
+            pool_mint: accounts[7],
+            amount: read_u64_le(data, 0)?,
+            minimum_out: read_u64_le(data, 8).unwrap_or_default(),
+            is_deposit: true,
+            ..Default::default()
+        }))
+    }
+
+    /// 解析SOL取出指令事件
+    fn parse_withdraw_sol_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 8 {
+            return None;
+        }
+        Some(Box::new(SanctumStakePoolEvent {
+            metadata,
+            stake_pool: accounts[0],
+            user: accounts[2],
+            user_pool_token_account: accounts[3],
+            reserve_stake: accounts[4],
+            manager_fee_account: accounts[6],
+            pool_mint: accounts[7],
+            amount: read_u64_le(data, 0)?,
+            minimum_out: read_u64_le(data, 8).unwrap_or_default(),
+            is_deposit: false,
+            ..Default::default()
+        }))
+    }
+
+    /// 解析wSOL换LST指令事件
+    fn parse_stake_wrapped_sol_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 8 {
+            return None;
+        }
+        Some(Box::new(SanctumSwapEvent {
+            metadata,
+            user: accounts[0],
+            src_token_account: accounts[1],
+            dest_token_account: accounts[2],
+            fee_token_account: accounts[5],
+            dest_mint: accounts[6],
+            src_mint: accounts[7],
+            amount: read_u64_le(data, 0)?,
+            ..Default::default()
+        }))
+    }
+
+    /// 解析LST互换指令事件
+    fn parse_swap_via_stake_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 7 {
+            return None;
+        }
+        Some(Box::new(SanctumSwapEvent {
+            metadata,
+            user: accounts[0],
+            src_token_account: accounts[1],
+            dest_token_account: accounts[2],
+            bridge_stake: accounts[3],
+            fee_token_account: accounts[4],
+            src_mint: accounts[5],
+            dest_mint: accounts[6],
+            amount: read_u64_le(data, 0)?,
+            bridge_stake_seed: read_u32_le(data, 8)?,
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl EventParser for SanctumEventParser {
+    fn parse_events_from_inner_instruction(
+        &self,
+        _inner_instruction: &UiCompiledInstruction,
+        _signature: &str,
+        _slot: u64,
+        _block_time: Option<Timestamp>,
+        _program_received_time_ms: i64,
+        _index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        vec![]
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        instruction: &CompiledInstruction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.parsers()
+            .flat_map(|parser| {
+                parser.parse_events_from_instruction(
+                    instruction,
+                    accounts,
+                    signature,
+                    slot,
+                    block_time,
+                    program_received_time_ms,
+                    index,
+                )
+            })
+            .collect()
+    }
+
+    fn get_inner_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>> {
+        self.router.get_inner_instruction_configs()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        ProtocolType::Sanctum
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        SANCTUM_ROUTER_PROGRAM_ID
+    }
+
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        None
+    }
+
+    fn capture_unknown_instructions(
+        &self,
+        versioned_tx: &VersionedTransaction,
+        inner_instructions: &[UiInnerInstructions],
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        std::iter::once(&self.router)
+            .chain(&self.stake_pools)
+            .flat_map(|parser| {
+                parser.capture_unknown_instructions(
+                    versioned_tx,
+                    inner_instructions,
+                    accounts,
+                    signature,
+                    slot,
+                    block_time,
+                    program_received_time_ms,
+                )
+            })
+            .collect()
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.parsers()
+            .any(|parser| parser.should_handle(program_id))
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        self.parsers()
+            .flat_map(|parser| parser.supported_program_ids())
+            .collect()
+    }
+}
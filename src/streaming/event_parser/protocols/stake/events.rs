@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::EventMetadata;
+
+/// 质押委托
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeDelegateEvent {
+    pub metadata: EventMetadata,
+    pub stake_account: Pubkey,
+    pub vote_account: Pubkey,
+    pub stake_authority: Pubkey,
+}
+
+impl_unified_event!(StakeDelegateEvent,);
+
+/// 质押取消委托
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeDeactivateEvent {
+    pub metadata: EventMetadata,
+    pub stake_account: Pubkey,
+    pub stake_authority: Pubkey,
+}
+
+impl_unified_event!(StakeDeactivateEvent,);
+
+/// 质押账户提取
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeWithdrawEvent {
+    pub metadata: EventMetadata,
+    pub stake_account: Pubkey,
+    pub recipient: Pubkey,
+    pub withdraw_authority: Pubkey,
+    pub lamports: u64,
+}
+
+impl_unified_event!(StakeWithdrawEvent,);
+
+/// 指令鉴别器常量 (bincode u32 枚举标签)
+pub mod discriminators {
+    pub const DELEGATE_STAKE_IX: &[u8] = &[2, 0, 0, 0];
+    pub const WITHDRAW_IX: &[u8] = &[4, 0, 0, 0];
+    pub const DEACTIVATE_IX: &[u8] = &[5, 0, 0, 0];
+}
@@ -0,0 +1,193 @@
+use prost_types::Timestamp;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::UiCompiledInstruction;
+
+use crate::streaming::event_parser::{
+    common::{read_u64_le, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
+    core::traits::{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent},
+    protocols::stake::{
+        discriminators, StakeDeactivateEvent, StakeDelegateEvent, StakeWithdrawEvent,
+    },
+};
+
+/// Stake程序ID
+pub const STAKE_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("Stake11111111111111111111111111111111111111");
+
+/// Stake事件解析器
+///
+/// Parses delegations, deactivations and withdrawals of stake accounts, e.g. to
+/// follow the stake moving to and away from a validator. Registered in
+/// [`EventParserFactory`](crate::streaming::event_parser::EventParserFactory)
+/// with the `validator-ops` feature.
+pub struct StakeEventParser {
+    inner: GenericEventParser,
+}
+
+impl StakeEventParser {
+    pub fn new() -> Self {
+        let configs = vec![
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::DELEGATE_STAKE_IX,
+                event_type: EventType::StakeDelegate,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_delegate_stake_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::DEACTIVATE_IX,
+                event_type: EventType::StakeDeactivate,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_deactivate_instruction,
+                layouts: Vec::new(),
+            },
+            GenericEventParseConfig {
+                inner_instruction_discriminator: "",
+                instruction_discriminator: discriminators::WITHDRAW_IX,
+                event_type: EventType::StakeWithdraw,
+                inner_instruction_parser: Self::parse_none_inner_instruction,
+                instruction_parser: Self::parse_withdraw_instruction,
+                layouts: Vec::new(),
+            },
+        ];
+        let inner = GenericEventParser::new(STAKE_PROGRAM_ID, ProtocolType::Stake, configs);
+        Self { inner }
+    }
+
+    /// Stake程序不发出事件
+    fn parse_none_inner_instruction(
+        _data: &[u8],
+        _metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        None
+    }
+
+    /// 解析质押委托指令事件
+    fn parse_delegate_stake_instruction(
+        _data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 6 {
+            return None;
+        }
+        Some(Box::new(StakeDelegateEvent {
+            metadata,
+            stake_account: accounts[0],
+            vote_account: accounts[1],
+            stake_authority: accounts[5],
+        }))
+    }
+
+    /// 解析取消委托指令事件
+    fn parse_deactivate_instruction(
+        _data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 3 {
+            return None;
+        }
+        Some(Box::new(StakeDeactivateEvent {
+            metadata,
+            stake_account: accounts[0],
+            stake_authority: accounts[2],
+        }))
+    }
+
+    /// 解析质押账户提取指令事件
+    fn parse_withdraw_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 5 {
+            return None;
+        }
+        Some(Box::new(StakeWithdrawEvent {
+            metadata,
+            stake_account: accounts[0],
+            recipient: accounts[1],
+            withdraw_authority: accounts[4],
+            lamports: read_u64_le(data, 0)?,
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl EventParser for StakeEventParser {
+    fn parse_events_from_inner_instruction(
+        &self,
+        inner_instruction: &UiCompiledInstruction,
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_inner_instruction(
+            inner_instruction,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        instruction: &CompiledInstruction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_instruction(
+            instruction,
+            accounts,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn get_inner_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>> {
+        self.inner.get_inner_instruction_configs()
+    }
+
+    fn get_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<Vec<u8>, Vec<GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        self.inner.get_protocol_type()
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        self.inner.get_program_id()
+    }
+
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.inner.should_handle(program_id)
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        self.inner.supported_program_ids()
+    }
+}
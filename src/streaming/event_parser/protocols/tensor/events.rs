@@ -0,0 +1,109 @@
+use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::protocols::tensor::types::{TensorField, TensorTarget};
+
+/// 挂单 (tcomp MakeEvent)
+///
+/// `amount` is the asking price in lamports, or in `currency` when set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct TensorListEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub maker: Pubkey,
+    pub bid_id: Option<Pubkey>,
+    pub target: TensorTarget,
+    pub target_id: Pubkey,
+    pub field: Option<TensorField>,
+    pub field_id: Option<Pubkey>,
+    pub amount: u64,
+    pub quantity: u32,
+    pub currency: Option<Pubkey>,
+    pub expiry: i64,
+    pub private_taker: Option<Pubkey>,
+    pub asset_id: Option<Pubkey>,
+}
+
+impl_unified_event!(TensorListEvent,);
+
+/// 成交 (tcomp TakeEvent)
+///
+/// Emitted as `TensorBuy` when a listing is bought and as `TensorBidFill` when a
+/// bid is sold into; `target` tells a collection bid (`Whitelist`) from a bid on a
+/// single asset. `amount` is the price paid by the buyer; the fees and creator
+/// royalties are paid out of it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct TensorTakeEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub taker: Pubkey,
+    pub bid_id: Option<Pubkey>,
+    pub target: TensorTarget,
+    pub target_id: Pubkey,
+    pub field: Option<TensorField>,
+    pub field_id: Option<Pubkey>,
+    pub amount: u64,
+    pub quantity: u32,
+    /// Marketplace fee
+    pub tcomp_fee: u64,
+    pub taker_broker_fee: u64,
+    pub maker_broker_fee: u64,
+    /// Creator royalties
+    pub creator_fee: u64,
+    pub currency: Option<Pubkey>,
+    pub asset_id: Option<Pubkey>,
+}
+
+impl_unified_event!(TensorTakeEvent,);
+
+/// 撤销挂单
+///
+/// `asset` is the mint or core asset; it is not an account of compressed NFT
+/// delistings and left empty for them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TensorDelistEvent {
+    pub metadata: EventMetadata,
+    pub owner: Pubkey,
+    pub list_state: Pubkey,
+    pub asset: Pubkey,
+}
+
+impl_unified_event!(TensorDelistEvent,);
+
+/// TensorSwap池子买卖 (BuySellEvent)
+///
+/// Pool trades only log the price breakdown; the pool, mint and trader are in the
+/// instruction.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct TensorSwapTradeEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    pub current_price: u64,
+    pub tswap_fee: u64,
+    /// Market making fee kept by trade pools
+    pub mm_fee: u64,
+    pub creators_fee: u64,
+}
+
+impl_unified_event!(TensorSwapTradeEvent,);
+
+/// 事件与指令鉴别器常量
+pub mod discriminators {
+    use crate::anchor_event_discriminator;
+    use crate::streaming::event_parser::common::discriminator::instruction_discriminator;
+
+    // 事件鉴别器
+    pub const BUY_SELL_EVENT: &str = anchor_event_discriminator!("BuySellEvent");
+
+    // 指令鉴别器
+    /// Self-CPI carrying a borsh `TcompEvent`
+    pub const TCOMP_NOOP_IX: &[u8] = &instruction_discriminator("tcomp_noop");
+    pub const DELIST_IX: &[u8] = &instruction_discriminator("delist");
+    pub const DELIST_LEGACY_IX: &[u8] = &instruction_discriminator("delist_legacy");
+    pub const DELIST_T22_IX: &[u8] = &instruction_discriminator("delist_t22");
+    pub const DELIST_WNS_IX: &[u8] = &instruction_discriminator("delist_wns");
+    pub const DELIST_CORE_IX: &[u8] = &instruction_discriminator("delist_core");
+}
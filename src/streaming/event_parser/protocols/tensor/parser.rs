@@ -0,0 +1,327 @@
+use borsh::BorshDeserialize;
+use prost_types::Timestamp;
+use solana_sdk::{
+    instruction::CompiledInstruction, pubkey::Pubkey, transaction::VersionedTransaction,
+};
+use solana_transaction_status::{UiCompiledInstruction, UiInnerInstructions};
+
+use crate::streaming::event_parser::{
+    common::{EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
+    core::traits::{
+        EventParser, GenericEventParseConfig, GenericEventParser, InstructionEventParser,
+        UnifiedEvent,
+    },
+    protocols::tensor::{
+        discriminators, TensorDelistEvent, TensorListEvent, TensorSwapTradeEvent, TensorTakeEvent,
+    },
+};
+
+/// Tensor Compressed (tcomp) 程序ID
+pub const TENSOR_COMP_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("TCMPhJdwDryooaGtiocG1u3xcYbRpiJzb283XfCZsDp");
+
+/// TensorSwap程序ID
+pub const TENSOR_SWAP_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("TSWAPaqyCSx2KABk68Shruf4rp7CxcNi8hAsbdwmHbN");
+
+/// `TcompEvent` 变体
+const MAKER_EVENT: u8 = 0;
+const TAKER_EVENT: u8 = 1;
+
+/// Tensor事件解析器
+///
+/// tcomp reports listings and sales in a `tcomp_noop` self-CPI carrying the event;
+/// TensorSwap pool trades are `emit!` logs, emitted on their own. Delistings carry
+/// no event and are read from the instructions.
+pub struct TensorEventParser {
+    tcomp: GenericEventParser,
+    tswap: GenericEventParser,
+}
+
+impl TensorEventParser {
+    pub fn new() -> Self {
+        let delist_instructions: [(&'static [u8], InstructionEventParser); 5] = [
+            (
+                discriminators::DELIST_IX,
+                Self::parse_delist_compressed_instruction,
+            ),
+            (
+                discriminators::DELIST_LEGACY_IX,
+                Self::parse_delist_token_instruction,
+            ),
+            (
+                discriminators::DELIST_T22_IX,
+                Self::parse_delist_token_instruction,
+            ),
+            (
+                discriminators::DELIST_WNS_IX,
+                Self::parse_delist_token_instruction,
+            ),
+            (
+                discriminators::DELIST_CORE_IX,
+                Self::parse_delist_core_instruction,
+            ),
+        ];
+        let delist_configs = delist_instructions
+            .into_iter()
+            .map(
+                |(instruction_discriminator, instruction_parser)| GenericEventParseConfig {
+                    inner_instruction_discriminator: "",
+                    instruction_discriminator,
+                    event_type: EventType::TensorDelist,
+                    inner_instruction_parser: Self::parse_none_inner_instruction,
+                    instruction_parser,
+                    layouts: Vec::new(),
+                },
+            )
+            .collect();
+        let tswap_configs = vec![GenericEventParseConfig {
+            inner_instruction_discriminator: discriminators::BUY_SELL_EVENT,
+            instruction_discriminator: &[],
+            event_type: EventType::TensorSwapTrade,
+            inner_instruction_parser: Self::parse_buy_sell_log,
+            instruction_parser: Self::parse_none_instruction,
+            layouts: Vec::new(),
+        }];
+
+        Self {
+            tcomp: GenericEventParser::new(
+                TENSOR_COMP_PROGRAM_ID,
+                ProtocolType::Tensor,
+                delist_configs,
+            ),
+            tswap: GenericEventParser::new(
+                TENSOR_SWAP_PROGRAM_ID,
+                ProtocolType::Tensor,
+                tswap_configs,
+            ),
+        }
+    }
+
+    /// 解析 `tcomp_noop` 携带的事件，出价本身不输出
+    fn parse_tcomp_event(data: &[u8], base: EventMetadata) -> Option<Box<dyn UnifiedEvent>> {
+        let (&variant, mut reader) = data.split_first()?;
+        let mut metadata = base;
+        match variant {
+            MAKER_EVENT => {
+                let mut event = TensorListEvent::deserialize(&mut reader).ok()?;
+                if event.bid_id.is_some() {
+                    return None;
+                }
+                metadata.event_type = EventType::TensorList;
+                event.metadata = metadata;
+                Some(Box::new(event))
+            }
+            TAKER_EVENT => {
+                let mut event = TensorTakeEvent::deserialize(&mut reader).ok()?;
+                metadata.event_type = if event.bid_id.is_some() {
+                    EventType::TensorBidFill
+                } else {
+                    EventType::TensorBuy
+                };
+                event.metadata = metadata;
+                Some(Box::new(event))
+            }
+            _ => None,
+        }
+    }
+
+    /// 解析TensorSwap买卖日志
+    fn parse_buy_sell_log(data: &[u8], metadata: EventMetadata) -> Option<Box<dyn UnifiedEvent>> {
+        let mut event = TensorSwapTradeEvent::deserialize(&mut &data[..]).ok()?;
+        event.metadata = metadata;
+        event.metadata.program_id = TENSOR_SWAP_PROGRAM_ID;
+        Some(Box::new(event))
+    }
+
+    /// 解析压缩NFT撤单指令事件
+    fn parse_delist_compressed_instruction(
+        _data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 9 {
+            return None;
+        }
+        Some(Box::new(TensorDelistEvent {
+            metadata,
+            owner: accounts[1],
+            list_state: accounts[8],
+            ..Default::default()
+        }))
+    }
+
+    /// 解析SPL/Token-2022/WNS撤单指令事件
+    fn parse_delist_token_instruction(
+        _data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 5 {
+            return None;
+        }
+        Some(Box::new(TensorDelistEvent {
+            metadata,
+            owner: accounts[0],
+            list_state: accounts[2],
+            asset: accounts[4],
+        }))
+    }
+
+    /// 解析Metaplex Core撤单指令事件
+    fn parse_delist_core_instruction(
+        _data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 4 {
+            return None;
+        }
+        Some(Box::new(TensorDelistEvent {
+            metadata,
+            asset: accounts[0],
+            list_state: accounts[2],
+            owner: accounts[3],
+        }))
+    }
+
+    fn parse_none_inner_instruction(
+        _data: &[u8],
+        _metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        None
+    }
+
+    fn parse_none_instruction(
+        _data: &[u8],
+        _accounts: &[Pubkey],
+        _metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        None
+    }
+}
+
+#[async_trait::async_trait]
+impl EventParser for TensorEventParser {
+    /// `tcomp_noop` 需要账户表判断程序，由 [`Self::parse_events_from_instruction`] 处理
+    fn parse_events_from_inner_instruction(
+        &self,
+        _inner_instruction: &UiCompiledInstruction,
+        _signature: &str,
+        _slot: u64,
+        _block_time: Option<Timestamp>,
+        _program_received_time_ms: i64,
+        _index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        vec![]
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        instruction: &CompiledInstruction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        let Some(program_id) = accounts.get(instruction.program_id_index as usize) else {
+            return vec![];
+        };
+        if *program_id != TENSOR_COMP_PROGRAM_ID {
+            return vec![];
+        }
+        let Some(data) = instruction.data.strip_prefix(discriminators::TCOMP_NOOP_IX) else {
+            return self.tcomp.parse_events_from_instruction(
+                instruction,
+                accounts,
+                signature,
+                slot,
+                block_time,
+                program_received_time_ms,
+                index,
+            );
+        };
+        let timestamp = block_time.unwrap_or(Timestamp {
+            seconds: 0,
+            nanos: 0,
+        });
+        let block_time_ms = timestamp.seconds * 1000 + (timestamp.nanos as i64) / 1_000_000;
+        let metadata = EventMetadata::new(
+            signature.to_string(),
+            signature.to_string(),
+            slot,
+            timestamp.seconds,
+            block_time_ms,
+            ProtocolType::Tensor,
+            EventType::TensorBuy,
+            TENSOR_COMP_PROGRAM_ID,
+            index,
+            program_received_time_ms,
+        );
+        let Some(mut event) = Self::parse_tcomp_event(data, metadata) else {
+            return vec![];
+        };
+        // 事件类型在解码后才确定
+        event.metadata_mut().set_instruction_id(&index);
+        vec![event]
+    }
+
+    fn get_inner_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>> {
+        self.tswap.get_inner_instruction_configs()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        ProtocolType::Tensor
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        TENSOR_COMP_PROGRAM_ID
+    }
+
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        None
+    }
+
+    fn capture_unknown_instructions(
+        &self,
+        versioned_tx: &VersionedTransaction,
+        inner_instructions: &[UiInnerInstructions],
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        [&self.tcomp, &self.tswap]
+            .into_iter()
+            .flat_map(|parser| {
+                parser.capture_unknown_instructions(
+                    versioned_tx,
+                    inner_instructions,
+                    accounts,
+                    signature,
+                    slot,
+                    block_time,
+                    program_received_time_ms,
+                )
+            })
+            .collect()
+    }
+
+    fn emits_log_events(&self) -> bool {
+        true
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.tcomp.should_handle(program_id) || self.tswap.should_handle(program_id)
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        vec![TENSOR_COMP_PROGRAM_ID, TENSOR_SWAP_PROGRAM_ID]
+    }
+}
@@ -0,0 +1,19 @@
+use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+
+/// 挂单或出价的目标
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub enum TensorTarget {
+    /// A single asset, `target_id` is the asset
+    #[default]
+    AssetId,
+    /// Any asset of a collection, `target_id` is its whitelist
+    Whitelist,
+}
+
+/// 出价的附加条件
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub enum TensorField {
+    #[default]
+    Name,
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::EventMetadata;
+
+/// 验证者投票
+///
+/// One vote transaction of a validator. `vote_latency` is the distance between
+/// the slot the vote landed in and the newest slot it votes for, `credits` the
+/// vote credits that slot earns under timely vote credits once it is rooted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoteEvent {
+    pub metadata: EventMetadata,
+    pub vote_account: Pubkey,
+    pub vote_authority: Pubkey,
+    /// Newest slot voted for
+    pub last_voted_slot: u64,
+    /// Root of the proposed tower, `None` for legacy votes
+    pub root: Option<u64>,
+    pub vote_latency: u64,
+    pub credits: u64,
+    pub timestamp: Option<i64>,
+}
+
+impl_unified_event!(VoteEvent,);
+
+/// 投票账户提取
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoteWithdrawEvent {
+    pub metadata: EventMetadata,
+    pub vote_account: Pubkey,
+    pub recipient: Pubkey,
+    pub withdraw_authority: Pubkey,
+    pub lamports: u64,
+}
+
+impl_unified_event!(VoteWithdrawEvent,);
+
+/// 指令鉴别器常量 (bincode u32 枚举标签)
+pub mod discriminators {
+    pub const VOTE_IX: &[u8] = &[2, 0, 0, 0];
+    pub const WITHDRAW_IX: &[u8] = &[3, 0, 0, 0];
+    pub const VOTE_SWITCH_IX: &[u8] = &[6, 0, 0, 0];
+    pub const UPDATE_VOTE_STATE_IX: &[u8] = &[8, 0, 0, 0];
+    pub const UPDATE_VOTE_STATE_SWITCH_IX: &[u8] = &[9, 0, 0, 0];
+    pub const COMPACT_UPDATE_VOTE_STATE_IX: &[u8] = &[12, 0, 0, 0];
+    pub const COMPACT_UPDATE_VOTE_STATE_SWITCH_IX: &[u8] = &[13, 0, 0, 0];
+    pub const TOWER_SYNC_IX: &[u8] = &[14, 0, 0, 0];
+    pub const TOWER_SYNC_SWITCH_IX: &[u8] = &[15, 0, 0, 0];
+}
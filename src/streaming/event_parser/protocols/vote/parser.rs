@@ -0,0 +1,355 @@
+use prost_types::Timestamp;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::UiCompiledInstruction;
+
+use crate::streaming::event_parser::{
+    common::{read_u64_le, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    core::instruction_decoder::InstructionDecoder,
+    core::traits::{
+        EventParser, GenericEventParseConfig, GenericEventParser, InstructionEventParser,
+        UnifiedEvent,
+    },
+    protocols::vote::{discriminators, VoteEvent, VoteWithdrawEvent},
+};
+
+/// Vote程序ID
+pub const VOTE_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("Vote111111111111111111111111111111111111111");
+
+/// 投票后可获得满额积分的延迟槽数
+pub const VOTE_CREDITS_GRACE_SLOTS: u64 = 2;
+
+/// 每个槽位的最大投票积分
+pub const VOTE_CREDITS_MAXIMUM_PER_SLOT: u64 = 16;
+
+/// 按投票延迟计算积分 (timely vote credits)
+pub fn timely_vote_credits(vote_latency: u64) -> u64 {
+    let late_by = vote_latency.saturating_sub(VOTE_CREDITS_GRACE_SLOTS);
+    VOTE_CREDITS_MAXIMUM_PER_SLOT.saturating_sub(late_by).max(1)
+}
+
+/// 解析出的投票塔
+struct Tower {
+    last_voted_slot: u64,
+    root: Option<u64>,
+    timestamp: Option<i64>,
+}
+
+/// Vote事件解析器
+///
+/// Parses votes and vote account withdrawals for validator monitoring. Every
+/// validator votes on every slot, so the Vote protocol is only registered in
+/// [`EventParserFactory`](crate::streaming::event_parser::EventParserFactory)
+/// with the `validator-ops` feature and has to be subscribed explicitly.
+pub struct VoteEventParser {
+    inner: GenericEventParser,
+}
+
+impl VoteEventParser {
+    pub fn new() -> Self {
+        let instructions: [(&'static [u8], EventType, InstructionEventParser); 9] = [
+            (
+                discriminators::VOTE_IX,
+                EventType::VoteCast,
+                Self::parse_vote_instruction,
+            ),
+            (
+                discriminators::VOTE_SWITCH_IX,
+                EventType::VoteCast,
+                Self::parse_vote_instruction,
+            ),
+            (
+                discriminators::UPDATE_VOTE_STATE_IX,
+                EventType::VoteCast,
+                Self::parse_update_vote_state_instruction,
+            ),
+            (
+                discriminators::UPDATE_VOTE_STATE_SWITCH_IX,
+                EventType::VoteCast,
+                Self::parse_update_vote_state_instruction,
+            ),
+            (
+                discriminators::COMPACT_UPDATE_VOTE_STATE_IX,
+                EventType::VoteCast,
+                Self::parse_compact_vote_instruction,
+            ),
+            (
+                discriminators::COMPACT_UPDATE_VOTE_STATE_SWITCH_IX,
+                EventType::VoteCast,
+                Self::parse_compact_vote_instruction,
+            ),
+            (
+                discriminators::TOWER_SYNC_IX,
+                EventType::VoteCast,
+                Self::parse_compact_vote_instruction,
+            ),
+            (
+                discriminators::TOWER_SYNC_SWITCH_IX,
+                EventType::VoteCast,
+                Self::parse_compact_vote_instruction,
+            ),
+            (
+                discriminators::WITHDRAW_IX,
+                EventType::VoteWithdraw,
+                Self::parse_withdraw_instruction,
+            ),
+        ];
+        let configs = instructions
+            .into_iter()
+            .map(
+                |(instruction_discriminator, event_type, instruction_parser)| {
+                    GenericEventParseConfig {
+                        inner_instruction_discriminator: "",
+                        instruction_discriminator,
+                        event_type,
+                        inner_instruction_parser: Self::parse_none_inner_instruction,
+                        instruction_parser,
+                        layouts: Vec::new(),
+                    }
+                },
+            )
+            .collect();
+        let inner = GenericEventParser::new(VOTE_PROGRAM_ID, ProtocolType::Vote, configs);
+        Self { inner }
+    }
+
+    /// Vote程序不发出事件
+    fn parse_none_inner_instruction(
+        _data: &[u8],
+        _metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        None
+    }
+
+    /// bincode `Option<u64>` / `Option<i64>`，返回值与读取的字节数
+    fn read_option_u64(data: &[u8], offset: usize) -> Option<(Option<u64>, usize)> {
+        match data.get(offset)? {
+            0 => Some((None, 1)),
+            1 => Some((Some(read_u64_le(data, offset + 1)?), 9)),
+            _ => None,
+        }
+    }
+
+    /// LEB128 变长整数 (serde_varint 与 short_vec 长度)
+    fn read_varint(data: &[u8], offset: &mut usize) -> Option<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *data.get(*offset)?;
+            *offset += 1;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    /// `Vote { slots: Vec<Slot>, hash: Hash, timestamp: Option<UnixTimestamp> }`
+    fn decode_vote(data: &[u8]) -> Option<Tower> {
+        let len = usize::try_from(read_u64_le(data, 0)?).ok()?;
+        if len == 0 || len > data.len() / 8 {
+            return None;
+        }
+        let last_voted_slot = read_u64_le(data, len * 8)?;
+        let (timestamp, _) = Self::read_option_u64(data, 8 + len * 8 + 32)?;
+        Some(Tower {
+            last_voted_slot,
+            root: None,
+            timestamp: timestamp.map(|t| t as i64),
+        })
+    }
+
+    /// `VoteStateUpdate { lockouts: VecDeque<Lockout>, root: Option<Slot>, hash, timestamp }`
+    fn decode_vote_state_update(data: &[u8]) -> Option<Tower> {
+        const LOCKOUT_LEN: usize = 12;
+        let len = usize::try_from(read_u64_le(data, 0)?).ok()?;
+        if len == 0 || len > data.len() / LOCKOUT_LEN {
+            return None;
+        }
+        let last_voted_slot = read_u64_le(data, 8 + (len - 1) * LOCKOUT_LEN)?;
+        let offset = 8 + len * LOCKOUT_LEN;
+        let (root, read) = Self::read_option_u64(data, offset)?;
+        let (timestamp, _) = Self::read_option_u64(data, offset + read + 32)?;
+        Some(Tower {
+            last_voted_slot,
+            root,
+            timestamp: timestamp.map(|t| t as i64),
+        })
+    }
+
+    /// 压缩格式 (CompactUpdateVoteState / TowerSync)
+    ///
+    /// The root is `u64::MAX` when there is none and the lockouts are a short_vec
+    /// of slot offsets from the previous slot, starting at the root.
+    fn decode_compact_vote(data: &[u8]) -> Option<Tower> {
+        let root = read_u64_le(data, 0).map(|root| (root != u64::MAX).then_some(root))?;
+        let mut offset = 8;
+        let count = Self::read_varint(data, &mut offset)?;
+        if count == 0 {
+            return None;
+        }
+        let mut last_voted_slot = root.unwrap_or_default();
+        for _ in 0..count {
+            last_voted_slot = last_voted_slot.checked_add(Self::read_varint(data, &mut offset)?)?;
+            // confirmation_count: u8
+            offset += 1;
+        }
+        let (timestamp, _) = Self::read_option_u64(data, offset + 32)?;
+        Some(Tower {
+            last_voted_slot,
+            root,
+            timestamp: timestamp.map(|t| t as i64),
+        })
+    }
+
+    fn vote_event(
+        tower: Tower,
+        vote_account: Pubkey,
+        vote_authority: Pubkey,
+        metadata: EventMetadata,
+    ) -> Box<dyn UnifiedEvent> {
+        let vote_latency = metadata.slot.saturating_sub(tower.last_voted_slot);
+        Box::new(VoteEvent {
+            metadata,
+            vote_account,
+            vote_authority,
+            last_voted_slot: tower.last_voted_slot,
+            root: tower.root,
+            vote_latency,
+            credits: timely_vote_credits(vote_latency),
+            timestamp: tower.timestamp,
+        })
+    }
+
+    /// 解析 Vote / VoteSwitch 指令事件
+    fn parse_vote_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 4 {
+            return None;
+        }
+        let tower = Self::decode_vote(data)?;
+        Some(Self::vote_event(tower, accounts[0], accounts[3], metadata))
+    }
+
+    /// 解析 UpdateVoteState 指令事件
+    fn parse_update_vote_state_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 2 {
+            return None;
+        }
+        let tower = Self::decode_vote_state_update(data)?;
+        Some(Self::vote_event(tower, accounts[0], accounts[1], metadata))
+    }
+
+    /// 解析 CompactUpdateVoteState / TowerSync 指令事件
+    fn parse_compact_vote_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 2 {
+            return None;
+        }
+        let tower = Self::decode_compact_vote(data)?;
+        Some(Self::vote_event(tower, accounts[0], accounts[1], metadata))
+    }
+
+    /// 解析投票账户提取指令事件
+    fn parse_withdraw_instruction(
+        data: &[u8],
+        accounts: &[Pubkey],
+        metadata: EventMetadata,
+    ) -> Option<Box<dyn UnifiedEvent>> {
+        if accounts.len() < 3 {
+            return None;
+        }
+        Some(Box::new(VoteWithdrawEvent {
+            metadata,
+            vote_account: accounts[0],
+            recipient: accounts[1],
+            withdraw_authority: accounts[2],
+            lamports: read_u64_le(data, 0)?,
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl EventParser for VoteEventParser {
+    fn parse_events_from_inner_instruction(
+        &self,
+        inner_instruction: &UiCompiledInstruction,
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_inner_instruction(
+            inner_instruction,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        instruction: &CompiledInstruction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.inner.parse_events_from_instruction(
+            instruction,
+            accounts,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn get_inner_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<&'static str, Vec<GenericEventParseConfig>> {
+        self.inner.get_inner_instruction_configs()
+    }
+
+    fn get_instruction_configs(
+        &self,
+    ) -> &std::collections::HashMap<Vec<u8>, Vec<GenericEventParseConfig>> {
+        self.inner.get_instruction_configs()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        self.inner.get_protocol_type()
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        self.inner.get_program_id()
+    }
+
+    fn instruction_decoder(&self) -> Option<&InstructionDecoder> {
+        self.inner.instruction_decoder()
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.inner.should_handle(program_id)
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        self.inner.supported_program_ids()
+    }
+}
@@ -0,0 +1,283 @@
+//! 交易夹具与黄金测试工具
+//!
+//! Regression tests for a transaction take one call: the transaction is fetched
+//! from RPC on first use and stored under `tests/fixtures/<signature>.json`, then
+//! parsed offline with every supported protocol.
+//!
+//! ```ignore
+//! use solana_streamer_sdk::streaming::fixtures;
+//!
+//! #[tokio::test]
+//! async fn test_pumpfun_buy() {
+//!     // Partial match against the redacted event JSON
+//!     fixtures::assert_events(SIGNATURE, &json!([{"sol_amount": 98019, "is_buy": true}])).await;
+//!     // Full match against tests/fixtures/<signature>.events.json, written on
+//!     // first run or when UPDATE_GOLDEN=1
+//!     fixtures::FixtureStore::default().assert_golden(SIGNATURE).await;
+//! }
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use prost_types::Timestamp;
+use serde_json::Value;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::{commitment_config::CommitmentConfig, signature::Signature};
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::{EventParserFactory, UnifiedEvent};
+
+/// Default directory of fixtures and golden files
+pub const DEFAULT_FIXTURE_DIR: &str = "tests/fixtures";
+/// RPC used to capture missing fixtures
+pub const DEFAULT_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+/// Set to `1` to rewrite golden files instead of comparing against them
+pub const UPDATE_GOLDEN_ENV: &str = "UPDATE_GOLDEN";
+
+/// Event fields that change between runs and are removed before comparing
+pub const DEFAULT_REDACTED_FIELDS: &[&str] = &[
+    "metadata.program_received_time_ms",
+    "metadata.program_handle_time_consuming_ms",
+    "metadata.grpc_received_time_ms",
+    "metadata.parse_start_time_ms",
+    "metadata.parse_end_time_ms",
+];
+
+/// 夹具存储
+#[derive(Debug, Clone)]
+pub struct FixtureStore {
+    dir: PathBuf,
+    rpc_url: String,
+    redacted_fields: Vec<String>,
+}
+
+impl Default for FixtureStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_FIXTURE_DIR)
+    }
+}
+
+impl FixtureStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            rpc_url: DEFAULT_RPC_URL.to_string(),
+            redacted_fields: DEFAULT_REDACTED_FIELDS
+                .iter()
+                .map(|field| field.to_string())
+                .collect(),
+        }
+    }
+
+    pub fn with_rpc_url(mut self, rpc_url: impl Into<String>) -> Self {
+        self.rpc_url = rpc_url.into();
+        self
+    }
+
+    /// Also remove a dot separated field path, e.g. `metadata.token_badges`
+    pub fn with_redacted_field(mut self, field: impl Into<String>) -> Self {
+        self.redacted_fields.push(field.into());
+        self
+    }
+
+    pub fn fixture_path(&self, signature: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", signature))
+    }
+
+    pub fn golden_path(&self, signature: &str) -> PathBuf {
+        self.dir.join(format!("{}.events.json", signature))
+    }
+
+    /// Load the stored transaction, fetching and storing it if missing
+    pub async fn capture(
+        &self,
+        signature: &str,
+    ) -> AnyResult<EncodedConfirmedTransactionWithStatusMeta> {
+        self.capture_at(signature, &self.fixture_path(signature))
+            .await
+    }
+
+    /// Like [`FixtureStore::capture`] with an explicit fixture file
+    pub async fn capture_at(
+        &self,
+        signature: &str,
+        path: &Path,
+    ) -> AnyResult<EncodedConfirmedTransactionWithStatusMeta> {
+        if path.exists() {
+            return Ok(serde_json::from_str(&fs::read_to_string(path)?)?);
+        }
+
+        let client =
+            RpcClient::new_with_commitment(self.rpc_url.clone(), CommitmentConfig::confirmed());
+        let tx = client
+            .get_transaction_with_config(
+                &Signature::from_str(signature)?,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .await?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&tx)?)?;
+        Ok(tx)
+    }
+
+    /// Parse the fixture with every supported protocol
+    pub async fn parse(&self, signature: &str) -> AnyResult<Vec<Box<dyn UnifiedEvent>>> {
+        let tx = self.capture(signature).await?;
+        let block_time = tx.block_time.map(|seconds| Timestamp { seconds, nanos: 0 });
+        let mut events = Vec::new();
+        for protocol in EventParserFactory::supported_protocols() {
+            let parser = EventParserFactory::create_parser(protocol);
+            events.extend(
+                parser
+                    .parse_transaction(
+                        tx.transaction.clone(),
+                        signature,
+                        Some(tx.slot),
+                        block_time,
+                        0,
+                        None,
+                    )
+                    .await?,
+            );
+        }
+        Ok(events)
+    }
+
+    /// Event JSON without the redacted fields
+    pub fn redact(&self, events: &[Box<dyn UnifiedEvent>]) -> Value {
+        Value::Array(
+            events
+                .iter()
+                .map(|event| {
+                    let mut value = serde_json::from_str(&event.to_json()).unwrap_or_default();
+                    for field in &self.redacted_fields {
+                        remove_path(&mut value, field);
+                    }
+                    value
+                })
+                .collect(),
+        )
+    }
+
+    /// Assert the parsed events contain `expected`
+    ///
+    /// `expected` is an array with one entry per event; objects only need to list
+    /// the fields to check.
+    pub async fn assert_events(&self, signature: &str, expected: &Value) {
+        let events = self
+            .parse(signature)
+            .await
+            .expect("failed to parse fixture");
+        let actual = self.redact(&events);
+        assert!(
+            json_contains(&actual, expected),
+            "events of {} do not match\nexpected: {}\nactual: {}",
+            signature,
+            to_golden_json(expected),
+            to_golden_json(&actual)
+        );
+    }
+
+    /// Assert the parsed events equal the golden file of the signature
+    ///
+    /// The golden file is written when missing or when `UPDATE_GOLDEN=1`.
+    pub async fn assert_golden(&self, signature: &str) {
+        let events = self
+            .parse(signature)
+            .await
+            .expect("failed to parse fixture");
+        let actual = to_golden_json(&self.redact(&events));
+        let path = self.golden_path(signature);
+        let update = std::env::var(UPDATE_GOLDEN_ENV).is_ok_and(|value| value == "1");
+        if update || !path.exists() {
+            fs::write(&path, &actual).expect("failed to write golden file");
+            return;
+        }
+        let expected = fs::read_to_string(&path).expect("failed to read golden file");
+        assert_eq!(
+            expected,
+            actual,
+            "events of {} differ from {}; rerun with {}=1 to update",
+            signature,
+            path.display(),
+            UPDATE_GOLDEN_ENV
+        );
+    }
+}
+
+/// [`FixtureStore::capture`] on the default store
+pub async fn capture(signature: &str) -> AnyResult<EncodedConfirmedTransactionWithStatusMeta> {
+    FixtureStore::default().capture(signature).await
+}
+
+/// [`FixtureStore::capture_at`] on the default store
+pub async fn capture_at(
+    signature: &str,
+    path: impl AsRef<Path>,
+) -> AnyResult<EncodedConfirmedTransactionWithStatusMeta> {
+    FixtureStore::default()
+        .capture_at(signature, path.as_ref())
+        .await
+}
+
+/// [`FixtureStore::assert_events`] on the default store
+pub async fn assert_events(signature: &str, expected: &Value) {
+    FixtureStore::default()
+        .assert_events(signature, expected)
+        .await
+}
+
+/// Deterministic JSON: sorted keys, two-space indent, trailing newline
+pub fn to_golden_json(value: &Value) -> String {
+    // serde_json的Map按键排序
+    let mut json = serde_json::to_string_pretty(value).unwrap_or_default();
+    json.push('\n');
+    json
+}
+
+/// Whether `actual` has every field of `expected` with the same value
+pub fn json_contains(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => expected.iter().all(|(key, value)| {
+            actual
+                .get(key)
+                .is_some_and(|actual| json_contains(actual, value))
+        }),
+        (Value::Array(actual), Value::Array(expected)) => {
+            actual.len() == expected.len()
+                && actual
+                    .iter()
+                    .zip(expected)
+                    .all(|(actual, expected)| json_contains(actual, expected))
+        }
+        _ => actual == expected,
+    }
+}
+
+fn remove_path(value: &mut Value, path: &str) {
+    let mut parts: Vec<&str> = path.split('.').collect();
+    let Some(last) = parts.pop() else {
+        return;
+    };
+    let mut current = value;
+    for part in parts {
+        match current.get_mut(part) {
+            Some(next) => current = next,
+            None => return,
+        }
+    }
+    if let Value::Object(object) = current {
+        object.remove(last);
+    }
+}
@@ -0,0 +1,226 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tonic::codec::CompressionEncoding;
+use tonic::metadata::{Ascii, MetadataKey, MetadataValue};
+use tonic::service::Interceptor;
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint};
+use tonic::{Request, Status};
+
+use crate::common::AnyResult;
+
+/// Default connect timeout
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Default timeout for a request to be answered
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+/// Default largest accepted message
+pub const DEFAULT_MAX_DECODING_MESSAGE_SIZE: usize = 1024 * 1024 * 10;
+
+/// gRPC消息压缩方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GrpcCompression {
+    Gzip,
+    Zstd,
+}
+
+impl From<GrpcCompression> for CompressionEncoding {
+    fn from(compression: GrpcCompression) -> Self {
+        match compression {
+            GrpcCompression::Gzip => CompressionEncoding::Gzip,
+            GrpcCompression::Zstd => CompressionEncoding::Zstd,
+        }
+    }
+}
+
+/// gRPC客户端连接配置
+///
+/// Covers authentication headers, message compression, TLS, keepalive and
+/// timeouts of the Yellowstone connection. The defaults match the previous
+/// hard-coded settings: TLS with native roots, 10s connect and 60s request
+/// timeout, no compression.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GrpcConfig {
+    /// Sent as the `x-token` header
+    pub x_token: Option<String>,
+    /// Sent as `authorization: Bearer <token>`
+    pub bearer_token: Option<String>,
+    /// Extra ASCII metadata sent with every request
+    pub headers: Vec<(String, String)>,
+    /// Compression of requests and accepted compression of responses
+    pub compression: Option<GrpcCompression>,
+    /// Use TLS for `https` endpoints
+    pub tls: bool,
+    /// PEM CA certificate to trust instead of the native roots
+    pub ca_certificate: Option<String>,
+    pub tcp_keepalive: Option<Duration>,
+    pub tcp_nodelay: bool,
+    /// Interval of HTTP/2 keepalive pings, also sent while idle
+    pub http2_keep_alive_interval: Option<Duration>,
+    /// How long to wait for a keepalive ping to be acknowledged
+    pub keep_alive_timeout: Option<Duration>,
+    pub connect_timeout: Duration,
+    /// Timeout for the server to answer a request; does not limit a running stream
+    pub request_timeout: Duration,
+    pub max_decoding_message_size: usize,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GrpcConfig {
+    pub fn new() -> Self {
+        Self {
+            x_token: None,
+            bearer_token: None,
+            headers: vec![],
+            compression: None,
+            tls: true,
+            ca_certificate: None,
+            tcp_keepalive: None,
+            tcp_nodelay: true,
+            http2_keep_alive_interval: None,
+            keep_alive_timeout: None,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            max_decoding_message_size: DEFAULT_MAX_DECODING_MESSAGE_SIZE,
+        }
+    }
+
+    pub fn with_x_token(mut self, x_token: impl Into<String>) -> Self {
+        self.x_token = Some(x_token.into());
+        self
+    }
+
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// Add a metadata header; names are lowercased by gRPC
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn with_compression(mut self, compression: GrpcCompression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    pub fn with_tls(mut self, tls: bool) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    pub fn with_ca_certificate(mut self, pem: impl Into<String>) -> Self {
+        self.ca_certificate = Some(pem.into());
+        self
+    }
+
+    pub fn with_tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = Some(interval);
+        self
+    }
+
+    pub fn with_tcp_nodelay(mut self, tcp_nodelay: bool) -> Self {
+        self.tcp_nodelay = tcp_nodelay;
+        self
+    }
+
+    pub fn with_http2_keep_alive(mut self, interval: Duration, timeout: Duration) -> Self {
+        self.http2_keep_alive_interval = Some(interval);
+        self.keep_alive_timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    pub fn with_max_decoding_message_size(mut self, size: usize) -> Self {
+        self.max_decoding_message_size = size;
+        self
+    }
+
+    /// Transport endpoint for `url` with the TLS, keepalive and timeout settings
+    pub fn endpoint(&self, url: &str) -> AnyResult<Endpoint> {
+        let mut endpoint = Endpoint::from_shared(url.to_string())?
+            .connect_timeout(self.connect_timeout)
+            .timeout(self.request_timeout)
+            .tcp_keepalive(self.tcp_keepalive)
+            .tcp_nodelay(self.tcp_nodelay);
+        if let Some(interval) = self.http2_keep_alive_interval {
+            endpoint = endpoint
+                .http2_keep_alive_interval(interval)
+                .keep_alive_while_idle(true);
+        }
+        if let Some(timeout) = self.keep_alive_timeout {
+            endpoint = endpoint.keep_alive_timeout(timeout);
+        }
+        if self.tls {
+            let tls = match &self.ca_certificate {
+                Some(pem) => ClientTlsConfig::new().ca_certificate(Certificate::from_pem(pem)),
+                None => ClientTlsConfig::new().with_native_roots(),
+            };
+            endpoint = endpoint.tls_config(tls)?;
+        }
+        Ok(endpoint)
+    }
+
+    /// Interceptor adding the authentication and custom headers
+    ///
+    /// Fails on header names or values that are not valid ASCII metadata.
+    pub fn interceptor(&self) -> AnyResult<HeaderInterceptor> {
+        let mut headers = Vec::new();
+        let mut push = |name: &str, value: &str| -> AnyResult<()> {
+            let name = MetadataKey::<Ascii>::from_bytes(name.as_bytes())
+                .map_err(|_| anyhow::anyhow!("invalid gRPC header name: {}", name))?;
+            let value: MetadataValue<Ascii> = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("invalid value of gRPC header {}", name))?;
+            headers.push((name, value));
+            Ok(())
+        };
+        if let Some(x_token) = &self.x_token {
+            push("x-token", x_token)?;
+        }
+        if let Some(token) = &self.bearer_token {
+            push("authorization", &format!("Bearer {}", token))?;
+        }
+        for (name, value) in &self.headers {
+            push(name, value)?;
+        }
+        Ok(HeaderInterceptor { headers })
+    }
+}
+
+/// 为每个请求添加固定metadata的拦截器
+#[derive(Debug, Clone, Default)]
+pub struct HeaderInterceptor {
+    headers: Vec<(MetadataKey<Ascii>, MetadataValue<Ascii>)>,
+}
+
+impl HeaderInterceptor {
+    /// Header names in the order they are added
+    pub fn header_names(&self) -> Vec<&str> {
+        self.headers.iter().map(|(name, _)| name.as_str()).collect()
+    }
+}
+
+impl Interceptor for HeaderInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        for (name, value) in &self.headers {
+            request.metadata_mut().insert(name.clone(), value.clone());
+        }
+        Ok(request)
+    }
+}
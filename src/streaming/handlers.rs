@@ -0,0 +1,172 @@
+use std::any::{Any, TypeId};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::streaming::event_parser::UnifiedEvent;
+
+type ErasedHandler = Box<dyn Fn(&dyn UnifiedEvent) + Send + Sync>;
+
+struct Registration {
+    priority: i32,
+    // 注册顺序，同优先级按注册顺序执行
+    order: usize,
+    handler: ErasedHandler,
+}
+
+impl Registration {
+    // 优先级高的在前，同优先级先注册的在前
+    fn runs_before(&self, other: &Registration) -> bool {
+        (Reverse(self.priority), self.order) < (Reverse(other.priority), other.order)
+    }
+}
+
+/// 按事件类型注册的处理器
+///
+/// Routes each event to the handlers registered for its concrete type, replacing a
+/// `match_event!` block. Handlers registered with `on_any` see every event, and
+/// `on_unhandled` handlers see the events no typed handler was registered for.
+/// Typed and `on_any` handlers run together ordered by priority (higher first),
+/// then by registration order.
+///
+/// # Usage Example
+/// ```ignore
+/// let handlers = HandlerRegistry::new()
+///     .on(|e: &PumpFunTradeEvent| println!("trade {}", e.mint))
+///     .on_with_priority(10, |e: &PumpFunTradeEvent| risk.check(e))
+///     .on(|e: &PumpSwapBuyEvent| println!("buy {}", e.pool))
+///     .on_unhandled(|e| println!("other {:?}", e.event_type()));
+/// grpc.subscribe_events_v2(protocols, None, include, vec![], vec![], None, handlers.into_callback()).await?;
+/// ```
+#[derive(Default)]
+pub struct HandlerRegistry {
+    typed: HashMap<TypeId, Vec<Registration>>,
+    any: Vec<Registration>,
+    unhandled: Vec<Registration>,
+    registered: usize,
+}
+
+impl HandlerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn registration(&mut self, priority: i32, handler: ErasedHandler) -> Registration {
+        self.registered += 1;
+        Registration {
+            priority,
+            order: self.registered,
+            handler,
+        }
+    }
+
+    fn insert(list: &mut Vec<Registration>, registration: Registration) {
+        let at = list.partition_point(|existing| existing.runs_before(&registration));
+        list.insert(at, registration);
+    }
+
+    /// Handle events of type `T`
+    pub fn on<T, F>(self, handler: F) -> Self
+    where
+        T: UnifiedEvent + Any,
+        F: Fn(&T) + Send + Sync + 'static,
+    {
+        self.on_with_priority(0, handler)
+    }
+
+    /// Handle events of type `T`; higher priorities run first
+    pub fn on_with_priority<T, F>(mut self, priority: i32, handler: F) -> Self
+    where
+        T: UnifiedEvent + Any,
+        F: Fn(&T) + Send + Sync + 'static,
+    {
+        let handler: ErasedHandler = Box::new(move |event: &dyn UnifiedEvent| {
+            if let Some(event) = event.as_any().downcast_ref::<T>() {
+                handler(event);
+            }
+        });
+        let registration = self.registration(priority, handler);
+        Self::insert(
+            self.typed.entry(TypeId::of::<T>()).or_default(),
+            registration,
+        );
+        self
+    }
+
+    /// Handle every event
+    pub fn on_any<F>(self, handler: F) -> Self
+    where
+        F: Fn(&dyn UnifiedEvent) + Send + Sync + 'static,
+    {
+        self.on_any_with_priority(0, handler)
+    }
+
+    /// Handle every event; higher priorities run first
+    pub fn on_any_with_priority<F>(mut self, priority: i32, handler: F) -> Self
+    where
+        F: Fn(&dyn UnifiedEvent) + Send + Sync + 'static,
+    {
+        let registration = self.registration(priority, Box::new(handler));
+        Self::insert(&mut self.any, registration);
+        self
+    }
+
+    /// Handle events without a typed handler
+    pub fn on_unhandled<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&dyn UnifiedEvent) + Send + Sync + 'static,
+    {
+        let registration = self.registration(0, Box::new(handler));
+        Self::insert(&mut self.unhandled, registration);
+        self
+    }
+
+    /// Number of registered handlers
+    pub fn len(&self) -> usize {
+        self.registered
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.registered == 0
+    }
+
+    /// Whether a typed handler is registered for `T`
+    pub fn handles<T: Any>(&self) -> bool {
+        self.typed.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Run the handlers for an event, returns the number of handlers called
+    pub fn dispatch(&self, event: &dyn UnifiedEvent) -> usize {
+        let typed = match self.typed.get(&event.as_any().type_id()) {
+            Some(typed) => typed.as_slice(),
+            None => self.unhandled.as_slice(),
+        };
+        // 合并两个已排序的列表
+        let (mut i, mut j) = (0, 0);
+        loop {
+            let next = match (typed.get(i), self.any.get(j)) {
+                (Some(a), Some(b)) if b.runs_before(a) => {
+                    j += 1;
+                    b
+                }
+                (Some(a), _) => {
+                    i += 1;
+                    a
+                }
+                (None, Some(b)) => {
+                    j += 1;
+                    b
+                }
+                (None, None) => break,
+            };
+            (next.handler)(event);
+        }
+        typed.len() + self.any.len()
+    }
+
+    /// Callback dispatching every event, for use with the subscribe methods
+    pub fn into_callback(self) -> impl Fn(Box<dyn UnifiedEvent>) + Send + Sync {
+        move |event: Box<dyn UnifiedEvent>| {
+            self.dispatch(event.as_ref());
+        }
+    }
+}
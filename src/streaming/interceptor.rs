@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+/// 数据源类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StreamSource {
+    YellowstoneGrpc,
+    ShredStream,
+}
+
+/// 原始消息上下文 - 提供给拦截器的数据源信息
+#[derive(Debug, Clone)]
+pub struct RawMessageContext {
+    pub source: StreamSource,
+    pub endpoint: String,
+    /// Local time the message was read from the stream (milliseconds)
+    pub received_at_ms: i64,
+}
+
+/// 拦截器处理结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterceptAction {
+    /// Pass the (possibly mutated) message on to parsing
+    Continue,
+    /// Drop the message before parsing
+    Drop,
+}
+
+/// 原始消息拦截器 - 在解析之前检查、修改或丢弃上游消息
+///
+/// `M` is the raw message type of the source: `SubscribeUpdate` for Yellowstone gRPC
+/// and the shredstream `Entry` (whose `entries` field holds the raw serialized bytes).
+/// Closures with the matching signature implement this trait.
+pub trait StreamInterceptor<M>: Send + Sync {
+    fn intercept(&self, message: &mut M, ctx: &RawMessageContext) -> InterceptAction;
+}
+
+impl<M, F> StreamInterceptor<M> for F
+where
+    F: Fn(&mut M, &RawMessageContext) -> InterceptAction + Send + Sync,
+{
+    fn intercept(&self, message: &mut M, ctx: &RawMessageContext) -> InterceptAction {
+        self(message, ctx)
+    }
+}
+
+/// Shared interceptor handle stored on the sources
+pub type SharedInterceptor<M> = Arc<dyn StreamInterceptor<M>>;
+
+/// Run an optional interceptor, returning false if the message should be dropped
+pub(crate) fn run_interceptor<M>(
+    interceptor: &Option<SharedInterceptor<M>>,
+    message: &mut M,
+    source: StreamSource,
+    endpoint: &str,
+) -> bool {
+    match interceptor {
+        Some(interceptor) => {
+            let ctx = RawMessageContext {
+                source,
+                endpoint: endpoint.to_string(),
+                received_at_ms: chrono::Utc::now().timestamp_millis(),
+            };
+            interceptor.intercept(message, &ctx) == InterceptAction::Continue
+        }
+        None => true,
+    }
+}
@@ -0,0 +1,565 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::{common::event_from_json, UnifiedEvent};
+use crate::streaming::pipeline::EventMiddleware;
+
+/// Default size at which a segment is closed and a new one started
+pub const DEFAULT_SEGMENT_BYTES: u64 = 64 * 1024 * 1024;
+
+const SEGMENT_EXTENSION: &str = "journal";
+/// Record header: payload length and CRC32 of the payload, little endian
+const RECORD_HEADER_LEN: usize = 8;
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// CRC-32 (IEEE) of `data`, the checksum of journal records
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+/// 事件日志配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JournalConfig {
+    /// A segment is closed once it reaches this size
+    pub max_segment_bytes: u64,
+    /// fsync after this many appends; 0 syncs only on [`EventJournal::flush`] and
+    /// segment rotation
+    pub sync_every: u64,
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JournalConfig {
+    pub fn new() -> Self {
+        Self {
+            max_segment_bytes: DEFAULT_SEGMENT_BYTES,
+            sync_every: 0,
+        }
+    }
+
+    pub fn with_max_segment_bytes(mut self, max_segment_bytes: u64) -> Self {
+        self.max_segment_bytes = max_segment_bytes.max(1);
+        self
+    }
+
+    pub fn with_sync_every(mut self, sync_every: u64) -> Self {
+        self.sync_every = sync_every;
+        self
+    }
+}
+
+/// 从日志读出的事件
+#[derive(Debug)]
+pub struct JournalEntry {
+    /// Offset of the event; a cursor at `offset + 1` resumes after it
+    pub offset: u64,
+    pub event: Box<dyn UnifiedEvent>,
+}
+
+/// Length of the complete, intact record at the start of `data`
+fn valid_record_len(data: &[u8]) -> Option<usize> {
+    let (header, rest) = data.split_first_chunk::<RECORD_HEADER_LEN>()?;
+    let len = u32::from_le_bytes(header[0..4].try_into().ok()?) as usize;
+    let crc = u32::from_le_bytes(header[4..8].try_into().ok()?);
+    let payload = rest.get(..len)?;
+    (crc32(payload) == crc).then_some(RECORD_HEADER_LEN + len)
+}
+
+/// Payload: event type name length (u16), name, JSON of the event
+fn encode_record(event: &dyn UnifiedEvent) -> Vec<u8> {
+    let event_type = event.event_type().to_string();
+    let json = event.to_json();
+    let payload_len = 2 + event_type.len() + json.len();
+    let mut record = Vec::with_capacity(RECORD_HEADER_LEN + payload_len);
+    record.extend_from_slice(&(payload_len as u32).to_le_bytes());
+    record.extend_from_slice(&[0u8; 4]);
+    record.extend_from_slice(&(event_type.len() as u16).to_le_bytes());
+    record.extend_from_slice(event_type.as_bytes());
+    record.extend_from_slice(json.as_bytes());
+    let crc = crc32(&record[RECORD_HEADER_LEN..]);
+    record[4..8].copy_from_slice(&crc.to_le_bytes());
+    record
+}
+
+fn decode_payload(payload: &[u8]) -> AnyResult<Box<dyn UnifiedEvent>> {
+    let (len, rest) = payload
+        .split_first_chunk::<2>()
+        .ok_or_else(|| anyhow::anyhow!("journal record without event type"))?;
+    let len = u16::from_le_bytes(*len) as usize;
+    if rest.len() < len {
+        return Err(anyhow::anyhow!("journal record with truncated event type"));
+    }
+    let event_type = std::str::from_utf8(&rest[..len])?;
+    event_from_json(event_type, std::str::from_utf8(&rest[len..])?)
+}
+
+fn segment_path(dir: &Path, base_offset: u64) -> PathBuf {
+    dir.join(format!("{:020}.{}", base_offset, SEGMENT_EXTENSION))
+}
+
+/// Segments of `dir` as (offset of the first record, path), oldest first
+fn list_segments(dir: &Path) -> AnyResult<Vec<(u64, PathBuf)>> {
+    let mut segments = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|extension| extension.to_str()) != Some(SEGMENT_EXTENSION) {
+            continue;
+        }
+        if let Some(base) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse::<u64>().ok())
+        {
+            segments.push((base, path));
+        }
+    }
+    segments.sort();
+    Ok(segments)
+}
+
+struct JournalWriter {
+    file: File,
+    base_offset: u64,
+    segment_bytes: u64,
+    next_offset: u64,
+    unsynced: u64,
+    // 写入失败且无法回滚，拒绝后续追加
+    poisoned: bool,
+}
+
+/// 持久化事件日志 - 追加写入的分段文件，支持从任意游标重放
+///
+/// Parsed events are appended to segment files in a directory, each record framed
+/// with its length and a CRC32, and addressed by an offset counting up from 0.
+/// Segments are named after the offset of their first record and closed once they
+/// reach [`JournalConfig::max_segment_bytes`].
+///
+/// Opening a journal after a crash truncates the active segment at the first torn
+/// or corrupt record, so the journal always ends at the last complete append. With
+/// the journal written before delivery (e.g. as the first pipeline stage) and the
+/// offsets a sink has durably written kept in a [`JournalCursor`], a restarted
+/// consumer replays from the cursor with [`EventJournal::read_from`] and gets
+/// every event at least once without a message broker. Records are only
+/// guaranteed to survive a power loss once synced, see
+/// [`JournalConfig::sync_every`].
+///
+/// A failed append is rolled back to the end of the last complete record, so
+/// later appends never land behind a torn one. If the rollback or an fsync fails
+/// the journal is poisoned and refuses further appends until it is reopened. Used
+/// as a pipeline stage, an event that can't be journaled is dropped instead of
+/// delivered, and counted in [`EventJournal::append_failures`].
+///
+/// # Usage Example
+/// ```ignore
+/// let journal = Arc::new(EventJournal::open("journal", JournalConfig::new())?);
+/// let cursor = JournalCursor::load("journal/sink.cursor")?;
+/// for entry in journal.read_from(cursor.offset())? {
+///     let entry = entry?;
+///     sink.write_event(entry.event.as_ref())?;
+///     cursor.commit(entry.offset + 1)?;
+/// }
+/// let pipeline = EventPipeline::new().with_stage(journal.clone());
+/// ```
+pub struct EventJournal {
+    dir: PathBuf,
+    config: JournalConfig,
+    writer: Mutex<JournalWriter>,
+    append_failures: AtomicU64,
+}
+
+impl std::fmt::Debug for EventJournal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventJournal")
+            .field("dir", &self.dir)
+            .field("config", &self.config)
+            .field("next_offset", &self.next_offset())
+            .field("append_failures", &self.append_failures())
+            .finish()
+    }
+}
+
+impl EventJournal {
+    /// Open the journal in `dir`, creating it if needed and recovering the tail of
+    /// the active segment
+    pub fn open(dir: impl AsRef<Path>, config: JournalConfig) -> AnyResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let writer = match list_segments(&dir)?.pop() {
+            Some((base_offset, path)) => {
+                let data = fs::read(&path)?;
+                let (mut records, mut valid_len) = (0u64, 0usize);
+                while let Some(len) = valid_record_len(&data[valid_len..]) {
+                    records += 1;
+                    valid_len += len;
+                }
+                let file = OpenOptions::new().append(true).open(&path)?;
+                if valid_len < data.len() {
+                    warn!(
+                        "truncating {} bytes of torn or corrupt records at the end of {}",
+                        data.len() - valid_len,
+                        path.display()
+                    );
+                    file.set_len(valid_len as u64)?;
+                    file.sync_all()?;
+                }
+                JournalWriter {
+                    file,
+                    base_offset,
+                    segment_bytes: valid_len as u64,
+                    next_offset: base_offset + records,
+                    unsynced: 0,
+                    poisoned: false,
+                }
+            }
+            None => JournalWriter {
+                file: Self::create_segment(&dir, 0)?,
+                base_offset: 0,
+                segment_bytes: 0,
+                next_offset: 0,
+                unsynced: 0,
+                poisoned: false,
+            },
+        };
+        Ok(Self {
+            dir,
+            config,
+            writer: Mutex::new(writer),
+            append_failures: AtomicU64::new(0),
+        })
+    }
+
+    fn create_segment(dir: &Path, base_offset: u64) -> AnyResult<File> {
+        Ok(OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(segment_path(dir, base_offset))?)
+    }
+
+    fn writer(&self) -> std::sync::MutexGuard<'_, JournalWriter> {
+        self.writer.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Append an event, returning its offset
+    pub fn append(&self, event: &dyn UnifiedEvent) -> AnyResult<u64> {
+        let record = encode_record(event);
+        let mut writer = self.writer();
+        if writer.poisoned {
+            return Err(anyhow::anyhow!(
+                "journal {} is poisoned by a failed write, reopen it",
+                self.dir.display()
+            ));
+        }
+        if writer.segment_bytes > 0
+            && writer.segment_bytes + record.len() as u64 > self.config.max_segment_bytes
+        {
+            Self::sync(&mut writer, true)?;
+            let base_offset = writer.next_offset;
+            writer.file = Self::create_segment(&self.dir, base_offset)?;
+            writer.base_offset = base_offset;
+            writer.segment_bytes = 0;
+            writer.unsynced = 0;
+        }
+        if let Err(e) = writer.file.write_all(&record) {
+            // 截掉写了一半的记录，后续追加才不会落在它后面
+            let segment_bytes = writer.segment_bytes;
+            if let Err(rollback) = writer.file.set_len(segment_bytes) {
+                error!(
+                    "failed to roll back a partial journal write in {}: {}",
+                    self.dir.display(),
+                    rollback
+                );
+                writer.poisoned = true;
+            }
+            return Err(e.into());
+        }
+        writer.segment_bytes += record.len() as u64;
+        let offset = writer.next_offset;
+        writer.next_offset += 1;
+        writer.unsynced += 1;
+        if self.config.sync_every > 0 && writer.unsynced >= self.config.sync_every {
+            Self::sync(&mut writer, false)?;
+        }
+        Ok(offset)
+    }
+
+    /// fsync the active segment; a failed fsync leaves the state of the written
+    /// records unknown, so it poisons the journal
+    fn sync(writer: &mut JournalWriter, all: bool) -> AnyResult<()> {
+        let result = if all {
+            writer.file.sync_all()
+        } else {
+            writer.file.sync_data()
+        };
+        if let Err(e) = result {
+            writer.poisoned = true;
+            return Err(e.into());
+        }
+        writer.unsynced = 0;
+        Ok(())
+    }
+
+    /// Sync the active segment to disk
+    pub fn flush(&self) -> AnyResult<()> {
+        let mut writer = self.writer();
+        if writer.poisoned {
+            return Err(anyhow::anyhow!(
+                "journal {} is poisoned by a failed write, reopen it",
+                self.dir.display()
+            ));
+        }
+        Self::sync(&mut writer, false)
+    }
+
+    /// Whether a failed write stopped the journal from accepting appends
+    pub fn is_poisoned(&self) -> bool {
+        self.writer().poisoned
+    }
+
+    /// Events the pipeline stage dropped because they couldn't be journaled
+    pub fn append_failures(&self) -> u64 {
+        self.append_failures.load(Ordering::Relaxed)
+    }
+
+    /// Offset the next appended event gets
+    pub fn next_offset(&self) -> u64 {
+        self.writer().next_offset
+    }
+
+    /// Offset of the oldest event still on disk
+    pub fn first_offset(&self) -> AnyResult<u64> {
+        Ok(list_segments(&self.dir)?
+            .first()
+            .map(|(base, _)| *base)
+            .unwrap_or_default())
+    }
+
+    /// Replay the events from `offset` on, up to the end of the journal at the time
+    /// each segment is read
+    pub fn read_from(&self, offset: u64) -> AnyResult<JournalReader> {
+        JournalReader::new(&self.dir, offset)
+    }
+
+    /// Delete the segments that only hold events before `offset`, e.g. the lowest
+    /// cursor of all consumers; returns the number of deleted segments
+    pub fn remove_before(&self, offset: u64) -> AnyResult<usize> {
+        // 持有写锁，避免与分段切换交错
+        let _writer = self.writer();
+        let segments = list_segments(&self.dir)?;
+        let mut removed = 0;
+        for pair in segments.windows(2) {
+            let ((_, path), (next_base, _)) = (&pair[0], &pair[1]);
+            if *next_base > offset {
+                break;
+            }
+            fs::remove_file(path)?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+}
+
+impl EventMiddleware for EventJournal {
+    fn name(&self) -> &str {
+        "event_journal"
+    }
+
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        // 未写入日志的事件不交付，否则重启后无法重放
+        if let Err(e) = self.append(event.as_ref()) {
+            self.append_failures.fetch_add(1, Ordering::Relaxed);
+            error!("dropping event {}, failed to journal it: {:?}", event.id(), e);
+            return vec![];
+        }
+        vec![event]
+    }
+}
+
+/// 日志重放迭代器，见 [`EventJournal::read_from`]
+///
+/// Stops at the current end of the journal; a torn record at the end of the
+/// active segment is treated as the end, a corrupt record elsewhere is an error.
+pub struct JournalReader {
+    dir: PathBuf,
+    file: Option<BufReader<File>>,
+    next_offset: u64,
+    // 游标之前的记录不解码直接跳过
+    from: u64,
+}
+
+impl JournalReader {
+    fn new(dir: &Path, offset: u64) -> AnyResult<Self> {
+        let segments = list_segments(dir)?;
+        let first = segments.first().map(|(base, _)| *base).unwrap_or_default();
+        if offset < first {
+            return Err(anyhow::anyhow!(
+                "journal offset {} was removed, the journal starts at {}",
+                offset,
+                first
+            ));
+        }
+        let mut reader = Self {
+            dir: dir.to_path_buf(),
+            file: None,
+            next_offset: offset,
+            from: offset,
+        };
+        if let Some((base, path)) = segments.iter().rev().find(|(base, _)| *base <= offset) {
+            reader.file = Some(BufReader::new(File::open(path)?));
+            reader.next_offset = *base;
+        }
+        Ok(reader)
+    }
+
+    /// Next payload of the open segment, `None` at its end
+    fn read_payload(&mut self) -> AnyResult<Option<Vec<u8>>> {
+        let Some(file) = self.file.as_mut() else {
+            return Ok(None);
+        };
+        let position = file.stream_position()?;
+        let mut header = [0u8; RECORD_HEADER_LEN];
+        let mut payload = Vec::new();
+        let complete = match file.read_exact(&mut header) {
+            Ok(()) => {
+                payload.resize(u32::from_le_bytes(header[0..4].try_into()?) as usize, 0);
+                match file.read_exact(&mut payload) {
+                    Ok(()) => true,
+                    Err(e) if e.kind() == ErrorKind::UnexpectedEof => false,
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => false,
+            Err(e) => return Err(e.into()),
+        };
+        if !complete {
+            // 记录尚未写完，回到记录开头
+            file.seek(SeekFrom::Start(position))?;
+            return Ok(None);
+        }
+        if crc32(&payload) != u32::from_le_bytes(header[4..8].try_into()?) {
+            return Err(anyhow::anyhow!(
+                "corrupt journal record at offset {}",
+                self.next_offset
+            ));
+        }
+        Ok(Some(payload))
+    }
+
+    fn next_entry(&mut self) -> AnyResult<Option<JournalEntry>> {
+        loop {
+            if let Some(payload) = self.read_payload()? {
+                let offset = self.next_offset;
+                self.next_offset += 1;
+                if offset < self.from {
+                    continue;
+                }
+                let event = decode_payload(&payload)?;
+                return Ok(Some(JournalEntry { offset, event }));
+            }
+            // 当前分段读完，继续下一个分段
+            let path = segment_path(&self.dir, self.next_offset);
+            if !path.exists() {
+                return Ok(None);
+            }
+            self.file = Some(BufReader::new(File::open(path)?));
+        }
+    }
+
+    /// Offset of the next event the reader returns
+    pub fn next_offset(&self) -> u64 {
+        self.next_offset.max(self.from)
+    }
+}
+
+impl Iterator for JournalReader {
+    type Item = AnyResult<JournalEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry().transpose()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CursorFile {
+    offset: u64,
+}
+
+/// 日志消费游标 - 下游已持久化处理到的日志位置
+///
+/// Commit `entry.offset + 1` after the sink durably wrote an entry; a restarted
+/// consumer replays from [`JournalCursor::offset`], so events written after the
+/// last commit are delivered again (at-least-once). The file is replaced
+/// atomically on every commit, so commit in batches on busy streams.
+#[derive(Debug)]
+pub struct JournalCursor {
+    path: PathBuf,
+    offset: AtomicU64,
+}
+
+impl JournalCursor {
+    /// Load the cursor file; a missing file starts at offset 0
+    pub fn load(path: impl AsRef<Path>) -> AnyResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let offset = if path.exists() {
+            serde_json::from_str::<CursorFile>(&fs::read_to_string(&path)?)?.offset
+        } else {
+            0
+        };
+        Ok(Self {
+            path,
+            offset: AtomicU64::new(offset),
+        })
+    }
+
+    /// Offset of the next event to deliver
+    pub fn offset(&self) -> u64 {
+        self.offset.load(Ordering::Acquire)
+    }
+
+    /// Persist `offset` as the next event to deliver; never moves backwards
+    pub fn commit(&self, offset: u64) -> AnyResult<()> {
+        let offset = self.offset.fetch_max(offset, Ordering::AcqRel).max(offset);
+        // 先写临时文件再重命名，避免崩溃时留下不完整的文件
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec(&CursorFile { offset })?)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// 单个缓存的内存统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryStats {
+    pub name: String,
+    /// Estimated bytes held
+    pub bytes: usize,
+    pub entries: usize,
+    /// Entries dropped to stay within the limits
+    pub evictions: u64,
+}
+
+#[derive(Debug)]
+struct BudgetInner {
+    // 0表示不限制
+    limit: AtomicUsize,
+    used: AtomicUsize,
+    accounts: Mutex<Vec<Arc<AccountInner>>>,
+}
+
+#[derive(Debug)]
+struct AccountInner {
+    name: String,
+    bytes: AtomicUsize,
+    entries: AtomicUsize,
+    evictions: AtomicU64,
+}
+
+/// 全局内存预算
+///
+/// Caches and trackers keep per-mint, per-pool or per-table state that a
+/// long-running process otherwise accumulates forever. Each of them charges its
+/// estimated size to a named [`MemoryAccount`] of a shared budget; while the
+/// total is over the limit, whichever cache inserts next evicts its least
+/// recently used entries, and [`Self::stats`] reports size and evictions per
+/// cache for metrics. Sizes are shallow estimates (keys, values and table
+/// overhead), so the limit bounds growth rather than the exact heap size.
+///
+/// # Usage Example
+/// ```ignore
+/// let budget = MemoryBudget::new(512 * 1024 * 1024);
+/// let curves = BondingCurveTracker::new().with_memory_budget(&budget);
+/// let alts = AltResolver::new(loader).with_memory_budget(&budget);
+/// for stats in budget.stats() {
+///     log::info!("{} {} bytes, {} evicted", stats.name, stats.bytes, stats.evictions);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MemoryBudget {
+    inner: Arc<BudgetInner>,
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+impl MemoryBudget {
+    pub fn new(limit_bytes: usize) -> Self {
+        Self {
+            inner: Arc::new(BudgetInner {
+                limit: AtomicUsize::new(limit_bytes),
+                used: AtomicUsize::new(0),
+                accounts: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Budget that only accounts, never evicts
+    pub fn unlimited() -> Self {
+        Self::new(0)
+    }
+
+    /// Limit in bytes, 0 if unlimited
+    pub fn limit(&self) -> usize {
+        self.inner.limit.load(Ordering::Relaxed)
+    }
+
+    /// Change the limit; caches shrink on their next inserts
+    pub fn set_limit(&self, limit_bytes: usize) {
+        self.inner.limit.store(limit_bytes, Ordering::Relaxed);
+    }
+
+    /// Estimated bytes held by all accounts
+    pub fn used(&self) -> usize {
+        self.inner.used.load(Ordering::Relaxed)
+    }
+
+    pub fn is_over(&self) -> bool {
+        let limit = self.limit();
+        limit > 0 && self.used() > limit
+    }
+
+    /// Account charged by one cache; the same name returns the same account
+    pub fn account(&self, name: &str) -> MemoryAccount {
+        let mut accounts = self
+            .inner
+            .accounts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let account = match accounts.iter().find(|account| account.name == name) {
+            Some(account) => account.clone(),
+            None => {
+                let account = Arc::new(AccountInner {
+                    name: name.to_string(),
+                    bytes: AtomicUsize::new(0),
+                    entries: AtomicUsize::new(0),
+                    evictions: AtomicU64::new(0),
+                });
+                accounts.push(account.clone());
+                account
+            }
+        };
+        MemoryAccount {
+            account,
+            budget: self.inner.clone(),
+        }
+    }
+
+    /// Statistics of every account, in registration order
+    pub fn stats(&self) -> Vec<MemoryStats> {
+        self.inner
+            .accounts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|account| MemoryStats {
+                name: account.name.clone(),
+                bytes: account.bytes.load(Ordering::Relaxed),
+                entries: account.entries.load(Ordering::Relaxed),
+                evictions: account.evictions.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    /// Total evictions across accounts
+    pub fn evictions(&self) -> u64 {
+        self.stats().iter().map(|stats| stats.evictions).sum()
+    }
+}
+
+/// 缓存在内存预算中的账户
+#[derive(Debug, Clone)]
+pub struct MemoryAccount {
+    account: Arc<AccountInner>,
+    budget: Arc<BudgetInner>,
+}
+
+impl MemoryAccount {
+    pub fn name(&self) -> &str {
+        &self.account.name
+    }
+
+    /// Record `entries` new entries taking `bytes`
+    pub fn charge(&self, bytes: usize, entries: usize) {
+        self.account.bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.account.entries.fetch_add(entries, Ordering::Relaxed);
+        self.budget.used.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Record `entries` entries taking `bytes` being dropped
+    pub fn release(&self, bytes: usize, entries: usize) {
+        saturating_sub(&self.account.bytes, bytes);
+        saturating_sub(&self.account.entries, entries);
+        saturating_sub(&self.budget.used, bytes);
+    }
+
+    /// Record entries dropped to stay within the limits, after releasing them
+    pub fn record_evictions(&self, evictions: usize) {
+        self.account
+            .evictions
+            .fetch_add(evictions as u64, Ordering::Relaxed);
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.account.bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn entries(&self) -> usize {
+        self.account.entries.load(Ordering::Relaxed)
+    }
+
+    pub fn evictions(&self) -> u64 {
+        self.account.evictions.load(Ordering::Relaxed)
+    }
+
+    /// Whether the whole budget is over its limit
+    pub fn over_budget(&self) -> bool {
+        let limit = self.budget.limit.load(Ordering::Relaxed);
+        limit > 0 && self.budget.used.load(Ordering::Relaxed) > limit
+    }
+}
+
+fn saturating_sub(counter: &AtomicUsize, value: usize) {
+    let _ = counter.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+        Some(current.saturating_sub(value))
+    });
+}
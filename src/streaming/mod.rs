@@ -1,8 +1,102 @@
+#[cfg(feature = "grpc")]
 pub mod yellowstone_grpc;
-pub mod yellowstone_sub_system;    
+#[cfg(feature = "grpc")]
+pub mod grpc_config;
+#[cfg(feature = "grpc")]
+pub mod multi_endpoint;
+#[cfg(feature = "grpc")]
+pub mod subscribe_request;
+#[cfg(feature = "grpc")]
+pub mod yellowstone_sub_system;
+#[cfg(feature = "shredstream")]
 pub mod shred_stream;
+#[cfg(any(feature = "grpc", feature = "shredstream"))]
+pub mod interceptor;
+#[cfg(feature = "rpc")]
+pub mod backfill;
+#[cfg(feature = "rpc")]
+pub mod fixtures;
+#[cfg(feature = "runtime")]
+pub mod async_handlers;
+#[cfg(feature = "runtime")]
+pub mod replay;
+#[cfg(feature = "runtime")]
+pub mod runtime;
+#[cfg(feature = "runtime")]
+pub mod shutdown;
+#[cfg(feature = "runtime")]
+pub mod stream;
+#[cfg(feature = "plugins")]
+pub mod plugin;
+#[cfg(feature = "server")]
+pub mod bridge;
+#[cfg(feature = "server")]
+pub mod security;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod alt_resolver;
+pub mod block_events;
+pub mod checkpoint;
+pub mod config;
+pub mod enrichment;
 pub mod event_parser;
+pub mod handlers;
+pub mod journal;
+pub mod memory;
+pub mod multiplex;
+pub mod normalize;
+pub mod ordering;
+pub mod pipeline;
+pub mod ring;
+pub mod simulation;
+pub mod sink;
+pub mod slot_gaps;
+pub mod trackers;
 
+#[cfg(feature = "grpc")]
+pub use grpc_config::{GrpcCompression, GrpcConfig};
+#[cfg(feature = "grpc")]
+pub use multi_endpoint::{EndpointHealth, EndpointMode, MultiEndpointGrpc};
+#[cfg(feature = "grpc")]
+pub use subscribe_request::SubscribeRequestBuilder;
+#[cfg(feature = "grpc")]
 pub use yellowstone_grpc::YellowstoneGrpc;
+#[cfg(feature = "grpc")]
 pub use yellowstone_sub_system::{SystemEvent, TransferInfo};
-pub use shred_stream::ShredStreamGrpc;
\ No newline at end of file
+#[cfg(feature = "shredstream")]
+pub use shred_stream::ShredStreamGrpc;
+#[cfg(any(feature = "grpc", feature = "shredstream"))]
+pub use interceptor::{InterceptAction, RawMessageContext, StreamInterceptor, StreamSource};
+#[cfg(feature = "runtime")]
+pub use async_handlers::{AsyncHandlerDispatcher, AsyncHandlerRegistry};
+#[cfg(feature = "runtime")]
+pub use runtime::{RuntimeConfig, StreamerRuntime};
+#[cfg(feature = "runtime")]
+pub use shutdown::{ShutdownReport, ShutdownSignal, StreamHandle};
+#[cfg(feature = "runtime")]
+pub use stream::ParsedEventStream;
+pub use ordering::{EventSequencer, OrderingConfig};
+#[cfg(feature = "server")]
+pub use bridge::EventBridge;
+#[cfg(feature = "server")]
+pub use security::{AuthError, ServerAuth, ServerTls};
+#[cfg(feature = "server")]
+pub use server::EventServer;
+pub use alt_resolver::{AltResolver, LookupTableLoader};
+pub use block_events::{
+    BlockEventAggregator, BlockEvents, BlockMeta, BlockMetaEvent, SlotStatus, SlotUpdateEvent,
+};
+pub use config::{
+    CheckpointConfig, EndpointConfig, EndpointKind, FilterConfig, PerformanceConfig, SinkConfig,
+    StreamerConfig,
+};
+pub use handlers::HandlerRegistry;
+pub use journal::{EventJournal, JournalConfig, JournalCursor, JournalEntry, JournalReader};
+pub use memory::{MemoryAccount, MemoryBudget, MemoryStats};
+pub use multiplex::{SubscriptionId, SubscriptionMux, SubscriptionSpec, SubscriptionStats};
+pub use normalize::{
+    LiquidityNormalizer, NormalizedLiquidityEvent, NormalizedTrade, TradeNormalizer,
+};
+pub use pipeline::{EventMiddleware, EventPipeline};
+pub use ring::{ring_buffer, RingBufferConfig, RingConsumer, RingProducer};
+pub use slot_gaps::{SlotGapDetectedEvent, SlotGapDetector};
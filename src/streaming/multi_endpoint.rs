@@ -0,0 +1,424 @@
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use futures::{channel::mpsc, SinkExt, StreamExt};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use yellowstone_grpc_proto::geyser::{
+    subscribe_update::UpdateOneof, SubscribeRequest, SubscribeRequestPing,
+};
+
+use crate::common::AnyResult;
+use crate::streaming::enrichment::{BlockTimeChecker, BlockTimeHealth};
+use crate::streaming::event_parser::{ActiveProtocols, UnifiedEvent};
+use crate::streaming::interceptor::{run_interceptor, StreamSource};
+use crate::streaming::memory::{MemoryAccount, MemoryBudget};
+use crate::streaming::yellowstone_grpc::{update_matches, TransactionPretty, YellowstoneGrpc};
+
+/// Default number of recent signatures remembered to drop duplicate deliveries
+pub const DEFAULT_DEDUP_WINDOW: usize = 100_000;
+/// Default delay before reconnecting a failed endpoint
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+const CHANNEL_SIZE: usize = 1000;
+
+/// 多端点工作模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EndpointMode {
+    /// Stream from one endpoint at a time in priority order. When it fails the
+    /// next endpoint resubscribes from the last seen slot.
+    #[default]
+    Failover,
+    /// Stream from all endpoints at once; the first delivery of a transaction wins
+    /// and later copies are dropped.
+    Race,
+}
+
+/// 单个端点的健康指标
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EndpointHealth {
+    pub endpoint: String,
+    pub connected: bool,
+    /// Transactions received from the endpoint, duplicates included
+    pub messages: u64,
+    /// Transactions this endpoint delivered before any other endpoint
+    pub first_deliveries: u64,
+    pub errors: u64,
+    pub reconnects: u64,
+    /// Highest slot received from the endpoint
+    pub last_slot: Option<u64>,
+    pub last_message_ms: Option<i64>,
+    pub last_error: Option<String>,
+    /// Drift and anomalies of the block times sent by the endpoint
+    pub block_time: BlockTimeHealth,
+}
+
+type SharedHealth = Arc<RwLock<Vec<EndpointHealth>>>;
+
+/// 多端点Yellowstone gRPC订阅
+///
+/// Wraps several configured [`YellowstoneGrpc`] clients of different providers.
+/// Transactions are deduplicated by signature before parsing, so a failover replay
+/// or a race never emits the same event twice. Parsing uses the options of the
+/// first client. The block times sent by each endpoint are checked with a
+/// [`BlockTimeChecker`] and reported in [`EndpointHealth::block_time`].
+pub struct MultiEndpointGrpc {
+    clients: Vec<Arc<YellowstoneGrpc>>,
+    mode: EndpointMode,
+    dedup_window: usize,
+    retry_delay: Duration,
+    health: SharedHealth,
+    // 每个端点的区块时间检查
+    block_time_checkers: Arc<Vec<BlockTimeChecker>>,
+    dedup_account: Option<MemoryAccount>,
+}
+
+impl MultiEndpointGrpc {
+    /// Clients are in priority order, the first one is the primary
+    pub fn new(clients: Vec<YellowstoneGrpc>) -> AnyResult<Self> {
+        if clients.is_empty() {
+            return Err(anyhow::anyhow!("at least one endpoint is required"));
+        }
+        let health = clients
+            .iter()
+            .map(|client| EndpointHealth {
+                endpoint: client.endpoint.clone(),
+                ..Default::default()
+            })
+            .collect();
+        let block_time_checkers = clients.iter().map(|_| BlockTimeChecker::new()).collect();
+        Ok(Self {
+            clients: clients.into_iter().map(Arc::new).collect(),
+            mode: EndpointMode::default(),
+            dedup_window: DEFAULT_DEDUP_WINDOW,
+            retry_delay: DEFAULT_RETRY_DELAY,
+            health: Arc::new(RwLock::new(health)),
+            block_time_checkers: Arc::new(block_time_checkers),
+            dedup_account: None,
+        })
+    }
+
+    pub fn with_mode(mut self, mode: EndpointMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_dedup_window(mut self, dedup_window: usize) -> Self {
+        self.dedup_window = dedup_window.max(1);
+        self
+    }
+
+    pub fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Charge the dedup window to `budget`; while it is exceeded the window drops
+    /// its oldest signatures early
+    pub fn with_memory_budget(mut self, budget: &MemoryBudget) -> Self {
+        self.dedup_account = Some(budget.account("multi_endpoint.dedup"));
+        self
+    }
+
+    pub fn mode(&self) -> EndpointMode {
+        self.mode
+    }
+
+    /// Snapshot of the per-endpoint metrics, in client order
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        let mut health = self
+            .health
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        for (endpoint, checker) in health.iter_mut().zip(self.block_time_checkers.iter()) {
+            endpoint.block_time = checker.health();
+        }
+        health
+    }
+
+    /// Subscribe on all endpoints with the same request
+    ///
+    /// Takes the same arguments as [`YellowstoneGrpc::subscribe_events_with_request`];
+    /// build the request with [`YellowstoneGrpc::subscribe_request_builder`].
+    pub async fn subscribe_events_with_request<F>(
+        &self,
+        protocols: impl Into<ActiveProtocols>,
+        bot_wallet: Option<Pubkey>,
+        request: SubscribeRequest,
+        callback: F,
+    ) -> AnyResult<()>
+    where
+        F: Fn(Box<dyn UnifiedEvent>) + Send + Sync + 'static,
+    {
+        let protocols: ActiveProtocols = protocols.into();
+        let (tx, mut rx) = mpsc::channel::<(usize, TransactionPretty)>(CHANNEL_SIZE);
+
+        match self.mode {
+            EndpointMode::Race => {
+                for index in 0..self.clients.len() {
+                    let clients = self.clients.clone();
+                    let health = self.health.clone();
+                    let checkers = self.block_time_checkers.clone();
+                    let request = request.clone();
+                    let protocols = protocols.clone();
+                    let mut tx = tx.clone();
+                    let retry_delay = self.retry_delay;
+                    tokio::spawn(async move {
+                        loop {
+                            let result = stream_endpoint(
+                                &clients[index],
+                                index,
+                                request.clone(),
+                                &protocols,
+                                &mut tx,
+                                &health,
+                                &checkers[index],
+                            )
+                            .await;
+                            if tx.is_closed() {
+                                break;
+                            }
+                            record_failure(&health, index, result);
+                            tokio::time::sleep(retry_delay).await;
+                        }
+                    });
+                }
+            }
+            EndpointMode::Failover => {
+                let clients = self.clients.clone();
+                let health = self.health.clone();
+                let checkers = self.block_time_checkers.clone();
+                let protocols = protocols.clone();
+                let mut tx = tx.clone();
+                let retry_delay = self.retry_delay;
+                tokio::spawn(async move {
+                    let mut request = request;
+                    let mut index = 0;
+                    loop {
+                        let result = stream_endpoint(
+                            &clients[index],
+                            index,
+                            request.clone(),
+                            &protocols,
+                            &mut tx,
+                            &health,
+                            &checkers[index],
+                        )
+                        .await;
+                        if tx.is_closed() {
+                            break;
+                        }
+                        // 从最后收到的slot重新订阅，重复的交易在去重时丢弃
+                        let last_slot = last_slot(&health);
+                        record_failure(&health, index, result);
+                        index = (index + 1) % clients.len();
+                        request.from_slot = last_slot.or(request.from_slot);
+                        warn!(
+                            "failing over to {} from slot {:?}",
+                            clients[index].endpoint, request.from_slot
+                        );
+                        if index == 0 {
+                            tokio::time::sleep(retry_delay).await;
+                        }
+                    }
+                });
+            }
+        }
+        drop(tx);
+
+        let parse_options = self.clients[0].parse_options.clone();
+        let health = self.health.clone();
+        let mut seen = SignatureWindow::new(self.dedup_window, self.dedup_account.clone());
+        tokio::spawn(async move {
+            while let Some((index, transaction_pretty)) = rx.next().await {
+                if !seen.insert(transaction_pretty.signature) {
+                    continue;
+                }
+                if let Some(endpoint) = health
+                    .write()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .get_mut(index)
+                {
+                    endpoint.first_deliveries += 1;
+                }
+                let events = YellowstoneGrpc::parse_event_transaction(
+                    transaction_pretty,
+                    bot_wallet,
+                    protocols.protocols(),
+                    parse_options.clone(),
+                )
+                .await;
+                for event in events {
+                    callback(event);
+                }
+            }
+        });
+
+        tokio::signal::ctrl_c().await?;
+        Ok(())
+    }
+}
+
+/// 订阅一个端点直到连接断开
+async fn stream_endpoint(
+    client: &YellowstoneGrpc,
+    index: usize,
+    request: SubscribeRequest,
+    protocols: &ActiveProtocols,
+    tx: &mut mpsc::Sender<(usize, TransactionPretty)>,
+    health: &SharedHealth,
+    block_time_checker: &BlockTimeChecker,
+) -> AnyResult<()> {
+    let mut grpc = client.connect().await?;
+    let (mut subscribe_tx, mut stream) = grpc.subscribe_with_request(Some(request)).await?;
+    update_health(health, index, |endpoint| endpoint.connected = true);
+    info!("connected to {}", client.endpoint);
+
+    while let Some(message) = stream.next().await {
+        let mut msg = message?;
+        if !run_interceptor(
+            &client.interceptor,
+            &mut msg,
+            StreamSource::YellowstoneGrpc,
+            &client.endpoint,
+        ) {
+            continue;
+        }
+        if let Some(UpdateOneof::Transaction(sut)) = &msg.update_oneof {
+            let slot = sut.slot;
+            update_health(health, index, |endpoint| {
+                endpoint.messages += 1;
+                endpoint.last_slot = endpoint.last_slot.max(Some(slot));
+                endpoint.last_message_ms = Some(chrono::Utc::now().timestamp_millis());
+            });
+        }
+        if !update_matches(&msg, &protocols.program_filter()) {
+            continue;
+        }
+        let created_at = msg.created_at;
+        match msg.update_oneof {
+            Some(UpdateOneof::Transaction(sut)) => {
+                let mut transaction_pretty = TransactionPretty::from((sut, created_at));
+                transaction_pretty.received_time_ms = client.parse_options.clock.now_ms();
+                if let Some(block_time) = &transaction_pretty.block_time {
+                    block_time_checker.check(
+                        transaction_pretty.slot,
+                        block_time.seconds * 1000 + block_time.nanos as i64 / 1_000_000,
+                        transaction_pretty.received_time_ms,
+                    );
+                }
+                // 处理任务退出时发送失败
+                tx.send((index, transaction_pretty)).await?;
+            }
+            Some(UpdateOneof::Ping(_)) => {
+                subscribe_tx
+                    .send(SubscribeRequest {
+                        ping: Some(SubscribeRequestPing { id: 1 }),
+                        ..Default::default()
+                    })
+                    .await?;
+            }
+            _ => {}
+        }
+    }
+    Err(anyhow::anyhow!("stream closed by {}", client.endpoint))
+}
+
+fn record_failure(health: &SharedHealth, index: usize, result: AnyResult<()>) {
+    let error = match result {
+        Ok(()) => "stream ended".to_string(),
+        Err(e) => e.to_string(),
+    };
+    update_health(health, index, |endpoint| {
+        error!("endpoint {} failed: {}", endpoint.endpoint, error);
+        endpoint.connected = false;
+        endpoint.errors += 1;
+        endpoint.reconnects += 1;
+        endpoint.last_error = Some(error.clone());
+    });
+}
+
+fn update_health(health: &SharedHealth, index: usize, update: impl FnOnce(&mut EndpointHealth)) {
+    if let Some(endpoint) = health
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_mut(index)
+    {
+        update(endpoint);
+    }
+}
+
+/// Highest slot delivered by any endpoint
+fn last_slot(health: &SharedHealth) -> Option<u64> {
+    health
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .filter_map(|endpoint| endpoint.last_slot)
+        .max()
+}
+
+// 窗口中每个签名的估算大小：集合和队列各一份
+const SIGNATURE_ENTRY_SIZE: usize = 2 * std::mem::size_of::<Signature>() + 9;
+
+/// 最近交易签名的有界集合
+struct SignatureWindow {
+    seen: HashSet<Signature>,
+    order: VecDeque<Signature>,
+    capacity: usize,
+    account: Option<MemoryAccount>,
+}
+
+impl SignatureWindow {
+    fn new(capacity: usize, account: Option<MemoryAccount>) -> Self {
+        Self {
+            seen: HashSet::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+            capacity,
+            account,
+        }
+    }
+
+    /// Returns false if `signature` was already seen
+    fn insert(&mut self, signature: Signature) -> bool {
+        if !self.seen.insert(signature) {
+            return false;
+        }
+        self.order.push_back(signature);
+        if let Some(account) = &self.account {
+            account.charge(SIGNATURE_ENTRY_SIZE, 1);
+        }
+        if self.order.len() > self.capacity {
+            self.pop_oldest();
+        }
+        while self.order.len() > 1
+            && self.account.as_ref().is_some_and(|account| account.over_budget())
+        {
+            self.pop_oldest();
+            if let Some(account) = &self.account {
+                account.record_evictions(1);
+            }
+        }
+        true
+    }
+
+    fn pop_oldest(&mut self) {
+        if let Some(oldest) = self.order.pop_front() {
+            self.seen.remove(&oldest);
+            if let Some(account) = &self.account {
+                account.release(SIGNATURE_ENTRY_SIZE, 1);
+            }
+        }
+    }
+}
+
+impl Drop for SignatureWindow {
+    fn drop(&mut self) {
+        if let Some(account) = &self.account {
+            account.release(SIGNATURE_ENTRY_SIZE * self.order.len(), self.order.len());
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::streaming::event_parser::{ActiveProtocols, Protocol, UnifiedEvent};
+use crate::streaming::pipeline::{SharedEvent, SharedHandler, Watchlist};
+
+/// 逻辑订阅ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubscriptionId(pub u64);
+
+/// 逻辑订阅的过滤条件
+///
+/// An event is routed to the subscription when it comes from one of `protocols`
+/// (any protocol when empty) and matches the watchlist. Events that belong to no
+/// built-in protocol are only checked against the watchlist. A subscription without
+/// protocols adds none to the stream; it sees what the other subscriptions parse.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubscriptionSpec {
+    pub name: String,
+    pub protocols: Vec<Protocol>,
+    pub watchlist: Watchlist,
+}
+
+impl SubscriptionSpec {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_protocols(mut self, protocols: Vec<Protocol>) -> Self {
+        self.protocols = protocols;
+        self
+    }
+
+    pub fn with_watchlist(mut self, watchlist: Watchlist) -> Self {
+        self.watchlist = watchlist;
+        self
+    }
+
+    pub fn matches(&self, event: &dyn UnifiedEvent) -> bool {
+        if !self.protocols.is_empty() {
+            if let Some(protocol) = Protocol::from_event_type(&event.event_type()) {
+                if !self.protocols.contains(&protocol) {
+                    return false;
+                }
+            }
+        }
+        self.watchlist.matches(event)
+    }
+}
+
+/// 逻辑订阅统计
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriptionStats {
+    pub id: SubscriptionId,
+    pub name: String,
+    /// Events routed to the subscription
+    pub delivered: u64,
+}
+
+struct Subscription {
+    id: SubscriptionId,
+    spec: SubscriptionSpec,
+    handler: SharedHandler,
+    delivered: AtomicU64,
+}
+
+struct MuxInner {
+    subscriptions: RwLock<Vec<Arc<Subscription>>>,
+    protocols: ActiveProtocols,
+    next_id: AtomicU64,
+    unrouted: AtomicU64,
+}
+
+/// 订阅多路复用 - 多个逻辑订阅共享一个连接和一次解析
+///
+/// Each logical subscription has its own filter and handler. The stream parses the
+/// union of the subscriptions' protocols once, and every event is moved into one
+/// [`SharedEvent`] and handed to each subscription it matches, so consumers in one
+/// process no longer open a stream each and parse everything again.
+///
+/// The parsed protocols follow the subscriptions as they are added and removed.
+/// The upstream filter is fixed when the stream starts, though: register the
+/// subscriptions first, or start the stream with a request that also covers the
+/// programs of subscriptions added later.
+///
+/// # Usage Example
+/// ```ignore
+/// let mux = SubscriptionMux::new();
+/// mux.add(
+///     SubscriptionSpec::new("sniper").with_protocols(vec![Protocol::PumpFun]),
+///     |event: SharedEvent| sniper.on_event(&*event),
+/// );
+/// mux.add(
+///     SubscriptionSpec::new("copy").with_watchlist(Watchlist::new().with_wallet(leader)),
+///     move |event: SharedEvent| { let _ = tx.send(event); },
+/// );
+/// let handle = grpc.spawn_multiplexed(&mux, None).await?;
+/// ```
+#[derive(Clone)]
+pub struct SubscriptionMux {
+    inner: Arc<MuxInner>,
+}
+
+impl Default for SubscriptionMux {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for SubscriptionMux {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubscriptionMux")
+            .field("subscriptions", &self.stats())
+            .field("protocols", &self.inner.protocols.protocols())
+            .finish()
+    }
+}
+
+impl SubscriptionMux {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(MuxInner {
+                subscriptions: RwLock::new(Vec::new()),
+                protocols: ActiveProtocols::default(),
+                next_id: AtomicU64::new(1),
+                unrouted: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Register a logical subscription
+    pub fn add<F>(&self, spec: SubscriptionSpec, handler: F) -> SubscriptionId
+    where
+        F: Fn(SharedEvent) + Send + Sync + 'static,
+    {
+        let id = SubscriptionId(self.inner.next_id.fetch_add(1, Ordering::Relaxed));
+        let mut subscriptions = self
+            .inner
+            .subscriptions
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        subscriptions.push(Arc::new(Subscription {
+            id,
+            spec,
+            handler: Arc::new(handler),
+            delivered: AtomicU64::new(0),
+        }));
+        self.inner.protocols.set(union_protocols(&subscriptions));
+        id
+    }
+
+    /// Remove a subscription, returns false if it was not registered
+    pub fn remove(&self, id: SubscriptionId) -> bool {
+        let mut subscriptions = self
+            .inner
+            .subscriptions
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        let len = subscriptions.len();
+        subscriptions.retain(|subscription| subscription.id != id);
+        if subscriptions.len() == len {
+            return false;
+        }
+        self.inner.protocols.set(union_protocols(&subscriptions));
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner
+            .subscriptions
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Protocols parsed for the current subscriptions; the handle is updated when
+    /// subscriptions change
+    pub fn protocols(&self) -> ActiveProtocols {
+        self.inner.protocols.clone()
+    }
+
+    /// Route one parsed event to every matching subscription, returns the number
+    /// of subscriptions it was delivered to
+    pub fn dispatch(&self, event: SharedEvent) -> usize {
+        // 在回调期间不持有锁，处理器可以增删订阅
+        let subscriptions = self
+            .inner
+            .subscriptions
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        let mut routed = 0;
+        for subscription in subscriptions
+            .iter()
+            .filter(|subscription| subscription.spec.matches(event.as_ref()))
+        {
+            subscription.delivered.fetch_add(1, Ordering::Relaxed);
+            (subscription.handler)(event.clone());
+            routed += 1;
+        }
+        if routed == 0 {
+            self.inner.unrouted.fetch_add(1, Ordering::Relaxed);
+        }
+        routed
+    }
+
+    /// Callback accepting boxed events as produced by the stream sources
+    pub fn callback(&self) -> impl Fn(Box<dyn UnifiedEvent>) + Send + Sync + 'static {
+        let mux = self.clone();
+        move |event: Box<dyn UnifiedEvent>| {
+            mux.dispatch(SharedEvent::from(event));
+        }
+    }
+
+    pub fn stats(&self) -> Vec<SubscriptionStats> {
+        self.inner
+            .subscriptions
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|subscription| SubscriptionStats {
+                id: subscription.id,
+                name: subscription.spec.name.clone(),
+                delivered: subscription.delivered.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    /// Events that matched no subscription
+    pub fn unrouted(&self) -> u64 {
+        self.inner.unrouted.load(Ordering::Relaxed)
+    }
+}
+
+/// Protocols of all subscriptions; a subscription without protocols receives
+/// whatever the others parse and adds none
+fn union_protocols(subscriptions: &[Arc<Subscription>]) -> Vec<Protocol> {
+    subscriptions
+        .iter()
+        .flat_map(|subscription| subscription.spec.protocols.iter().cloned())
+        .collect()
+}
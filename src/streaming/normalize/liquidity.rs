@@ -0,0 +1,237 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::{
+    common::{EventMetadata, EventType, ProtocolType, TransferData},
+    protocols::{
+        boop::BoopGraduateEvent,
+        meteora_damm::{MeteoraDammBootstrapLiquidityEvent, MeteoraDammLiquidityEvent},
+        meteora_dbc::MeteoraDbcMigrateEvent,
+        pumpswap::{PumpSwapCreatePoolEvent, PumpSwapDepositEvent, PumpSwapWithdrawEvent},
+    },
+    UnifiedEvent,
+};
+use crate::streaming::normalize::is_quote_side;
+use crate::streaming::pipeline::EventMiddleware;
+use crate::streaming::trackers::{derived_metadata, pool::WSOL_MINT};
+
+/// 流动性操作类型
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LiquidityAction {
+    #[default]
+    CreatePool,
+    Deposit,
+    Withdraw,
+    /// Liquidity of a completed bonding curve moved into an AMM pool
+    Migrate,
+}
+
+/// 统一的流动性事件 - 由LiquidityNormalizer从各协议的建池/存取/迁移事件生成
+///
+/// Deltas are the change of the pool's reserves in raw units of each mint,
+/// negative on withdrawals, so summing them per pool tracks TVL without knowing
+/// the protocol. For pools without a base/quote order (Meteora DAMM) the side
+/// registered as quote mint is the quote side, see [`is_quote_side`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NormalizedLiquidityEvent {
+    pub metadata: EventMetadata,
+    pub venue: ProtocolType,
+    pub action: LiquidityAction,
+    /// Pool receiving or releasing the liquidity, default if the event does not
+    /// name it (e.g. Boop graduations, whose pool is created by a later instruction)
+    pub pool: Pubkey,
+    /// Bonding curve the liquidity migrated from
+    pub from_pool: Option<Pubkey>,
+    /// Wallet adding or removing the liquidity
+    pub provider: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_delta: i128,
+    pub quote_delta: i128,
+    pub lp_mint: Pubkey,
+    /// LP tokens minted, negative when burned
+    pub lp_delta: i128,
+}
+
+impl_unified_event!(NormalizedLiquidityEvent,);
+
+impl NormalizedLiquidityEvent {
+    /// 从协议事件构造统一流动性事件，不是流动性事件时返回None
+    pub fn from_event(event: &dyn UnifiedEvent) -> Option<Self> {
+        let any = event.as_any();
+        if let Some(e) = any.downcast_ref::<PumpSwapCreatePoolEvent>() {
+            return Some(Self {
+                pool: e.pool,
+                provider: e.creator,
+                base_mint: e.base_mint,
+                quote_mint: e.quote_mint,
+                base_delta: e.base_amount_in as i128,
+                quote_delta: e.quote_amount_in as i128,
+                lp_mint: e.lp_mint,
+                lp_delta: e.lp_token_amount_out as i128,
+                ..Self::new(&e.metadata, LiquidityAction::CreatePool)
+            });
+        }
+        if let Some(e) = any.downcast_ref::<PumpSwapDepositEvent>() {
+            return Some(Self {
+                pool: e.pool,
+                provider: e.user,
+                base_mint: e.base_mint,
+                quote_mint: e.quote_mint,
+                base_delta: e.base_amount_in as i128,
+                quote_delta: e.quote_amount_in as i128,
+                lp_delta: e.lp_token_amount_out as i128,
+                ..Self::new(&e.metadata, LiquidityAction::Deposit)
+            });
+        }
+        if let Some(e) = any.downcast_ref::<PumpSwapWithdrawEvent>() {
+            return Some(Self {
+                pool: e.pool,
+                provider: e.user,
+                base_mint: e.base_mint,
+                quote_mint: e.quote_mint,
+                base_delta: -(e.base_amount_out as i128),
+                quote_delta: -(e.quote_amount_out as i128),
+                lp_delta: -(e.lp_token_amount_in as i128),
+                ..Self::new(&e.metadata, LiquidityAction::Withdraw)
+            });
+        }
+        if let Some(e) = any.downcast_ref::<MeteoraDammLiquidityEvent>() {
+            let (action, sign) = match e.metadata.event_type {
+                EventType::MeteoraDammAddLiquidity => (LiquidityAction::Deposit, 1),
+                EventType::MeteoraDammRemoveLiquidity => (LiquidityAction::Withdraw, -1),
+                _ => return None,
+            };
+            let transfers = &e.metadata.transfer_datas;
+            return Some(Self {
+                pool: e.pool,
+                provider: e.user,
+                lp_mint: e.lp_mint,
+                lp_delta: sign * e.lp_amount as i128,
+                ..Self::new(&e.metadata, action).with_pair(
+                    (
+                        mint_of(transfers, &e.user_a_token)?,
+                        sign * e.token_a_amount as i128,
+                    ),
+                    (
+                        mint_of(transfers, &e.user_b_token)?,
+                        sign * e.token_b_amount as i128,
+                    ),
+                )
+            });
+        }
+        if let Some(e) = any.downcast_ref::<MeteoraDammBootstrapLiquidityEvent>() {
+            let transfers = &e.metadata.transfer_datas;
+            return Some(Self {
+                pool: e.pool,
+                provider: e.user,
+                lp_mint: e.lp_mint,
+                lp_delta: e.lp_mint_amount as i128,
+                ..Self::new(&e.metadata, LiquidityAction::Deposit).with_pair(
+                    (
+                        mint_of(transfers, &e.user_a_token)?,
+                        e.token_a_amount as i128,
+                    ),
+                    (
+                        mint_of(transfers, &e.user_b_token)?,
+                        e.token_b_amount as i128,
+                    ),
+                )
+            });
+        }
+        if let Some(e) = any.downcast_ref::<MeteoraDbcMigrateEvent>() {
+            // 仅解析指令，迁移的数量未知
+            return Some(Self {
+                pool: e.target_pool,
+                from_pool: Some(e.pool),
+                provider: e.payer,
+                base_mint: e.base_mint,
+                quote_mint: e.quote_mint,
+                lp_mint: e.target_lp_mint,
+                ..Self::new(&e.metadata, LiquidityAction::Migrate)
+            });
+        }
+        if let Some(e) = any.downcast_ref::<BoopGraduateEvent>() {
+            return Some(Self {
+                from_pool: Some(e.bonding_curve),
+                provider: e.operator,
+                base_mint: e.mint,
+                quote_mint: WSOL_MINT,
+                quote_delta: e.sol_for_liquidity as i128,
+                ..Self::new(&e.metadata, LiquidityAction::Migrate)
+            });
+        }
+        None
+    }
+
+    fn new(source: &EventMetadata, action: LiquidityAction) -> Self {
+        Self {
+            metadata: derived_metadata(source, EventType::NormalizedLiquidity, source.id.clone()),
+            venue: source.protocol.clone(),
+            action,
+            ..Default::default()
+        }
+    }
+
+    /// 没有base/quote顺序的池子，以注册的报价代币一侧作为quote
+    fn with_pair(self, a: (Pubkey, i128), b: (Pubkey, i128)) -> Self {
+        let (base, quote) = if is_quote_side(&a.0, &b.0) {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        Self {
+            base_mint: base.0,
+            base_delta: base.1,
+            quote_mint: quote.0,
+            quote_delta: quote.1,
+            ..self
+        }
+    }
+}
+
+/// 用户代币账户参与的转账中的mint
+fn mint_of(transfers: &[TransferData], token_account: &Pubkey) -> Option<Pubkey> {
+    transfers
+        .iter()
+        .find(|t| t.source == *token_account || t.destination == *token_account)
+        .and_then(|t| t.mint)
+}
+
+/// 流动性归一化阶段
+///
+/// Emits a [`NormalizedLiquidityEvent`] after every PumpSwap pool creation,
+/// deposit and withdrawal, Meteora DAMM liquidity change and Meteora DBC or Boop
+/// migration, for protocol-agnostic TVL tracking. The protocol event is passed on
+/// unchanged in front of it.
+///
+/// # Usage Example
+/// ```ignore
+/// let pipeline = EventPipeline::new()
+///     .with_stage(TradeNormalizer::new())
+///     .with_stage(LiquidityNormalizer::new());
+/// ```
+#[derive(Debug, Default)]
+pub struct LiquidityNormalizer;
+
+impl LiquidityNormalizer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl EventMiddleware for LiquidityNormalizer {
+    fn name(&self) -> &str {
+        "liquidity_normalizer"
+    }
+
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        let liquidity = NormalizedLiquidityEvent::from_event(event.as_ref());
+        let mut events = vec![event];
+        if let Some(liquidity) = liquidity {
+            events.push(Box::new(liquidity));
+        }
+        events
+    }
+}
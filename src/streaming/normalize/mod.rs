@@ -0,0 +1,10 @@
+pub mod liquidity;
+pub mod quote;
+pub mod trade;
+
+pub use liquidity::{LiquidityAction, LiquidityNormalizer, NormalizedLiquidityEvent};
+pub use quote::{
+    is_quote_mint, is_quote_side, quote_mint, register_quote_mint, set_quote_usd_price,
+    unregister_quote_mint, QuoteMint,
+};
+pub use trade::{NormalizedTrade, TradeNormalizer};
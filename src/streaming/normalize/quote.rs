@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::trackers::pool::{USDC_MINT, USDT_MINT, WSOL_MINT};
+
+/// 报价代币
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuoteMint {
+    pub mint: Pubkey,
+    pub symbol: String,
+    pub decimals: u8,
+    /// USD price of one whole token, fixed for stablecoins and kept current with
+    /// [`set_quote_usd_price`] for the others
+    pub usd_price: Option<f64>,
+    /// Pairs of two quote mints are quoted in the one with the higher priority,
+    /// e.g. SOL/USDC pools price SOL in USDC
+    pub priority: u8,
+}
+
+impl QuoteMint {
+    pub fn new(mint: Pubkey, symbol: impl Into<String>, decimals: u8) -> Self {
+        Self {
+            mint,
+            symbol: symbol.into(),
+            decimals,
+            usd_price: None,
+            priority: 0,
+        }
+    }
+
+    /// Stablecoin pegged to `usd_price`
+    pub fn with_usd_price(mut self, usd_price: f64) -> Self {
+        self.usd_price = Some(usd_price);
+        self
+    }
+
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Raw amount in whole tokens
+    pub fn ui_amount(&self, amount: u64) -> f64 {
+        amount as f64 / 10f64.powi(self.decimals as i32)
+    }
+}
+
+/// 报价代币注册表，默认包含WSOL、USDC和USDT
+static QUOTE_MINTS: LazyLock<RwLock<HashMap<Pubkey, QuoteMint>>> = LazyLock::new(|| {
+    let quote_mints = [
+        QuoteMint::new(WSOL_MINT, "SOL", 9),
+        QuoteMint::new(USDC_MINT, "USDC", 6)
+            .with_usd_price(1.0)
+            .with_priority(10),
+        QuoteMint::new(USDT_MINT, "USDT", 6)
+            .with_usd_price(1.0)
+            .with_priority(10),
+    ];
+    RwLock::new(
+        quote_mints
+            .into_iter()
+            .map(|quote| (quote.mint, quote))
+            .collect(),
+    )
+});
+
+/// 注册报价代币，替换同一mint之前的注册
+///
+/// Normalized trades and liquidity events and the pool tracker treat registered
+/// mints as the quote side of pools without a base/quote order.
+pub fn register_quote_mint(quote: QuoteMint) {
+    QUOTE_MINTS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(quote.mint, quote);
+}
+
+pub fn unregister_quote_mint(mint: &Pubkey) -> Option<QuoteMint> {
+    QUOTE_MINTS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(mint)
+}
+
+pub fn quote_mint(mint: &Pubkey) -> Option<QuoteMint> {
+    QUOTE_MINTS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(mint)
+        .cloned()
+}
+
+/// 更新报价代币的USD价格 (例如SOL)，未注册的mint返回false
+pub fn set_quote_usd_price(mint: &Pubkey, usd_price: f64) -> bool {
+    match QUOTE_MINTS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_mut(mint)
+    {
+        Some(quote) => {
+            quote.usd_price = Some(usd_price);
+            true
+        }
+        None => false,
+    }
+}
+
+pub fn is_quote_mint(mint: &Pubkey) -> bool {
+    QUOTE_MINTS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains_key(mint)
+}
+
+/// `mint` 是否是与 `other` 组成的交易对的quote一侧
+///
+/// A registered mint is the quote side against an unregistered one, and against
+/// another registered mint if its priority is higher. Ties go to the larger
+/// pubkey so both directions of a pair agree.
+pub fn is_quote_side(mint: &Pubkey, other: &Pubkey) -> bool {
+    let quote_mints = QUOTE_MINTS.read().unwrap_or_else(|e| e.into_inner());
+    match (quote_mints.get(mint), quote_mints.get(other)) {
+        (Some(quote), Some(other_quote)) => {
+            (quote.priority, mint) > (other_quote.priority, other)
+        }
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
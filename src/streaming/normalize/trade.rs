@@ -0,0 +1,345 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::{
+    common::{EventMetadata, EventType, ProtocolType, TransferData},
+    protocols::{
+        believe::BelieveTradeEvent,
+        bonk::{BonkTradeEvent, TradeDirection},
+        boop::BoopTradeEvent,
+        meteora_damm::MeteoraDammSwapEvent,
+        meteora_dbc::{MeteoraDbcSwapEvent, TradeDirection as DbcTradeDirection},
+        pumpfun::PumpFunTradeEvent,
+        pumpswap::{PumpSwapBuyEvent, PumpSwapSellEvent},
+        raydium_clmm::{RaydiumClmmSwapEvent, RaydiumClmmSwapV2Event},
+        raydium_cpmm::RaydiumCpmmSwapEvent,
+    },
+    UnifiedEvent,
+};
+use crate::streaming::normalize::{is_quote_side, quote_mint};
+use crate::streaming::pipeline::EventMiddleware;
+use crate::streaming::trackers::{
+    bonding_curve::PUMPFUN_TOKEN_DECIMALS, derived_metadata, pool::WSOL_MINT,
+};
+
+/// 统一的成交事件 - 由TradeNormalizer从各协议的买卖/兑换事件生成
+///
+/// Amounts are in raw units of their mint. The quote side of the pair is the
+/// launchpad's or pool's quote mint, and for pools without a base/quote order
+/// (Raydium, Meteora DAMM) the side registered as quote mint, see
+/// [`is_quote_side`]. The whole-token and USD fields use the decimals and USD
+/// price of the quote mint from the registry, so USDC and USDT quoted pools are
+/// valued correctly next to SOL quoted ones.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct NormalizedTrade {
+    pub metadata: EventMetadata,
+    pub venue: ProtocolType,
+    /// Pool, bonding curve or market traded against
+    pub pool: Pubkey,
+    pub trader: Pubkey,
+    pub mint_in: Pubkey,
+    pub mint_out: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    /// Whether the trader bought the base mint
+    pub is_buy: bool,
+    /// Quote per base in raw units, `None` if the pair has no known quote side
+    pub price: Option<f64>,
+    /// Whole quote tokens per whole base token, needs the base decimals from a
+    /// `transferChecked` of the trade or the launchpad
+    pub ui_price: Option<f64>,
+    /// USD per whole base token
+    pub usd_price: Option<f64>,
+    /// USD value of the quote side of the trade
+    pub usd_value: Option<f64>,
+}
+
+impl_unified_event!(NormalizedTrade,);
+
+impl NormalizedTrade {
+    /// 从协议事件构造统一成交，不是成交事件或缺少mint时返回None
+    pub fn from_event(event: &dyn UnifiedEvent) -> Option<Self> {
+        let any = event.as_any();
+        let trade = if let Some(e) = any.downcast_ref::<PumpFunTradeEvent>() {
+            let fees = e.fee.saturating_add(e.creator_fee);
+            let sol = if e.is_buy {
+                e.sol_amount.saturating_add(fees)
+            } else {
+                e.sol_amount.saturating_sub(fees)
+            };
+            Self::base_quote(
+                &e.metadata,
+                e.bonding_curve,
+                e.user,
+                (e.mint, e.token_amount),
+                (WSOL_MINT, sol),
+                e.is_buy,
+            )
+            .with_base_decimals(Some(PUMPFUN_TOKEN_DECIMALS))
+        } else if let Some(e) = any.downcast_ref::<PumpSwapBuyEvent>() {
+            Self::base_quote(
+                &e.metadata,
+                e.pool,
+                e.user,
+                (e.base_mint, e.base_amount_out),
+                (e.quote_mint, e.user_quote_amount_in),
+                true,
+            )
+        } else if let Some(e) = any.downcast_ref::<PumpSwapSellEvent>() {
+            Self::base_quote(
+                &e.metadata,
+                e.pool,
+                e.user,
+                (e.base_mint, e.base_amount_in),
+                (e.quote_mint, e.user_quote_amount_out),
+                false,
+            )
+        } else if let Some(e) = any.downcast_ref::<BonkTradeEvent>() {
+            let is_buy = e.trade_direction == TradeDirection::Buy;
+            let (base_amount, quote_amount) = if is_buy {
+                (e.amount_out, e.amount_in)
+            } else {
+                (e.amount_in, e.amount_out)
+            };
+            Self::base_quote(
+                &e.metadata,
+                e.pool_state,
+                e.payer,
+                (e.base_token_mint, base_amount),
+                (e.quote_token_mint, quote_amount),
+                is_buy,
+            )
+        } else if let Some(e) = any.downcast_ref::<BoopTradeEvent>() {
+            let (base_amount, quote_amount) = if e.is_buy {
+                (e.amount_out, e.amount_in)
+            } else {
+                (e.amount_in, e.amount_out)
+            };
+            Self::base_quote(
+                &e.metadata,
+                e.bonding_curve,
+                e.user,
+                (e.mint, base_amount),
+                (WSOL_MINT, quote_amount),
+                e.is_buy,
+            )
+        } else if let Some(e) = any.downcast_ref::<BelieveTradeEvent>() {
+            let (base_amount, quote_amount) = if e.is_buy {
+                (e.output_amount, e.actual_input_amount)
+            } else {
+                (e.actual_input_amount, e.output_amount)
+            };
+            Self::base_quote(
+                &e.metadata,
+                e.pool,
+                e.payer,
+                (e.base_mint, base_amount),
+                (e.quote_mint, quote_amount),
+                e.is_buy,
+            )
+        } else if let Some(e) = any.downcast_ref::<MeteoraDbcSwapEvent>() {
+            let is_buy = e.trade_direction == DbcTradeDirection::QuoteToBase;
+            let (base_amount, quote_amount) = if is_buy {
+                (e.output_amount, e.actual_input_amount)
+            } else {
+                (e.actual_input_amount, e.output_amount)
+            };
+            Self::base_quote(
+                &e.metadata,
+                e.pool,
+                e.payer,
+                (e.base_mint, base_amount),
+                (e.quote_mint, quote_amount),
+                is_buy,
+            )
+        } else if let Some(e) = any.downcast_ref::<RaydiumCpmmSwapEvent>() {
+            let transfers = &e.metadata.transfer_datas;
+            Self::swap(
+                &e.metadata,
+                e.pool_state,
+                e.payer,
+                (
+                    e.input_token_mint,
+                    received_by(transfers, &e.input_vault).unwrap_or(e.amount_in),
+                ),
+                (
+                    e.output_token_mint,
+                    sent_by(transfers, &e.output_vault).unwrap_or(e.amount_out),
+                ),
+            )
+        } else if let Some(e) = any.downcast_ref::<RaydiumClmmSwapV2Event>() {
+            let transfers = &e.metadata.transfer_datas;
+            Self::swap(
+                &e.metadata,
+                e.pool_state,
+                e.payer,
+                (e.input_vault_mint, received_by(transfers, &e.input_vault)?),
+                (e.output_vault_mint, sent_by(transfers, &e.output_vault)?),
+            )
+        } else if let Some(e) = any.downcast_ref::<RaydiumClmmSwapEvent>() {
+            // swap v1不携带mint，从转账数据中获取
+            let transfers = &e.metadata.transfer_datas;
+            let transfer_in = transfers.iter().find(|t| t.destination == e.input_vault)?;
+            let transfer_out = transfers.iter().find(|t| t.source == e.output_vault)?;
+            Self::swap(
+                &e.metadata,
+                e.pool_state,
+                e.payer,
+                (transfer_in.mint?, transfer_in.amount),
+                (transfer_out.mint?, transfer_out.amount),
+            )
+        } else if let Some(e) = any.downcast_ref::<MeteoraDammSwapEvent>() {
+            // 展开的vault转账中，用户转出与收到的代币即为兑换的两侧
+            let transfers = &e.metadata.transfer_datas;
+            let transfer_in = transfers.iter().find(|t| t.source == e.user_source_token)?;
+            let transfer_out = transfers
+                .iter()
+                .find(|t| t.destination == e.user_destination_token)?;
+            Self::swap(
+                &e.metadata,
+                e.pool,
+                e.user,
+                (transfer_in.mint?, e.in_amount),
+                (transfer_out.mint?, e.out_amount),
+            )
+        } else {
+            return None;
+        };
+        (trade.mint_in != Pubkey::default() && trade.mint_out != Pubkey::default()).then_some(trade)
+    }
+
+    /// 有明确base/quote的池子与bonding curve
+    fn base_quote(
+        source: &EventMetadata,
+        pool: Pubkey,
+        trader: Pubkey,
+        (base_mint, base_amount): (Pubkey, u64),
+        (quote_mint, quote_amount): (Pubkey, u64),
+        is_buy: bool,
+    ) -> Self {
+        let ((mint_in, amount_in), (mint_out, amount_out)) = if is_buy {
+            ((quote_mint, quote_amount), (base_mint, base_amount))
+        } else {
+            ((base_mint, base_amount), (quote_mint, quote_amount))
+        };
+        let quote = quote_mint(&quote_mint);
+        let usd_value = quote.as_ref().and_then(|quote| {
+            quote
+                .usd_price
+                .map(|usd_price| quote.ui_amount(quote_amount) * usd_price)
+        });
+        let base_decimals = source
+            .transfer_datas
+            .iter()
+            .find(|t| t.mint == Some(base_mint))
+            .and_then(|t| t.decimals);
+        Self {
+            metadata: derived_metadata(source, EventType::NormalizedTrade, source.id.clone()),
+            venue: source.protocol.clone(),
+            pool,
+            trader,
+            mint_in,
+            mint_out,
+            amount_in,
+            amount_out,
+            base_mint,
+            quote_mint,
+            is_buy,
+            price: (base_amount > 0).then(|| quote_amount as f64 / base_amount as f64),
+            ui_price: None,
+            usd_price: None,
+            usd_value,
+        }
+        .with_base_decimals(base_decimals)
+    }
+
+    /// 根据base精度与报价代币注册信息计算整币价格与USD价格
+    fn with_base_decimals(mut self, base_decimals: Option<u8>) -> Self {
+        let (Some(price), Some(base_decimals), Some(quote)) =
+            (self.price, base_decimals, quote_mint(&self.quote_mint))
+        else {
+            return self;
+        };
+        let ui_price = price * 10f64.powi(base_decimals as i32 - quote.decimals as i32);
+        self.ui_price = Some(ui_price);
+        self.usd_price = quote.usd_price.map(|usd_price| ui_price * usd_price);
+        self
+    }
+
+    /// 没有base/quote顺序的池子，以注册的报价代币一侧作为quote
+    fn swap(
+        source: &EventMetadata,
+        pool: Pubkey,
+        trader: Pubkey,
+        input: (Pubkey, u64),
+        output: (Pubkey, u64),
+    ) -> Self {
+        if is_quote_side(&input.0, &output.0) {
+            return Self::base_quote(source, pool, trader, output, input, true);
+        }
+        if is_quote_side(&output.0, &input.0) {
+            return Self::base_quote(source, pool, trader, input, output, false);
+        }
+        Self {
+            price: None,
+            ui_price: None,
+            usd_price: None,
+            usd_value: None,
+            ..Self::base_quote(source, pool, trader, input, output, false)
+        }
+    }
+}
+
+/// 转入指定vault的数量
+fn received_by(transfers: &[TransferData], vault: &Pubkey) -> Option<u64> {
+    transfers
+        .iter()
+        .find(|t| t.destination == *vault)
+        .map(|t| t.amount)
+}
+
+/// 从指定vault转出的数量
+fn sent_by(transfers: &[TransferData], vault: &Pubkey) -> Option<u64> {
+    transfers
+        .iter()
+        .find(|t| t.source == *vault)
+        .map(|t| t.amount)
+}
+
+/// 成交归一化阶段
+///
+/// Emits a [`NormalizedTrade`] after every buy, sell or swap event it can map,
+/// so strategies can consume one trade shape across PumpFun, PumpSwap, Bonk,
+/// Boop, Believe, Meteora DBC and DAMM and Raydium CPMM and CLMM. The protocol
+/// event is passed on unchanged in front of it.
+///
+/// # Usage Example
+/// ```ignore
+/// let pipeline = EventPipeline::new().with_stage(TradeNormalizer::new());
+/// ```
+#[derive(Debug, Default)]
+pub struct TradeNormalizer;
+
+impl TradeNormalizer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl EventMiddleware for TradeNormalizer {
+    fn name(&self) -> &str {
+        "trade_normalizer"
+    }
+
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        let trade = NormalizedTrade::from_event(event.as_ref());
+        let mut events = vec![event];
+        if let Some(trade) = trade {
+            events.push(Box::new(trade));
+        }
+        events
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use log::warn;
+
+use crate::streaming::event_parser::{common::InstructionIndex, UnifiedEvent};
+
+/// Default number of extra slots a slot waits for its late transactions
+pub const DEFAULT_ORDERING_SLOT_LAG: u64 = 1;
+/// Default bound of the events buffered across pending slots
+pub const DEFAULT_MAX_BUFFERED_EVENTS: usize = 100_000;
+
+/// 事件排序配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrderingConfig {
+    /// A slot is released once an event of a slot more than `slot_lag` slots later
+    /// arrives; 0 releases it at the first event of any later slot
+    pub slot_lag: u64,
+    /// Release the oldest pending slot early while more events are buffered
+    pub max_buffered_events: usize,
+}
+
+impl Default for OrderingConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrderingConfig {
+    pub fn new() -> Self {
+        Self {
+            slot_lag: DEFAULT_ORDERING_SLOT_LAG,
+            max_buffered_events: DEFAULT_MAX_BUFFERED_EVENTS,
+        }
+    }
+
+    pub fn with_slot_lag(mut self, slot_lag: u64) -> Self {
+        self.slot_lag = slot_lag;
+        self
+    }
+
+    pub fn with_max_buffered_events(mut self, max_buffered_events: usize) -> Self {
+        self.max_buffered_events = max_buffered_events.max(1);
+        self
+    }
+}
+
+/// Position an event is ordered by
+type OrderKey = (u64, Option<u64>, InstructionIndex);
+
+fn order_key(event: &dyn UnifiedEvent) -> OrderKey {
+    (event.slot(), event.metadata().tx_index, event.index())
+}
+
+#[derive(Default)]
+struct SequencerState {
+    pending: BTreeMap<u64, Vec<Box<dyn UnifiedEvent>>>,
+    buffered: usize,
+    highest_slot: u64,
+    // 已释放的最高slot，之后到达的事件直接交付
+    released_slot: Option<u64>,
+    last_key: Option<OrderKey>,
+    next_sequence: u64,
+    out_of_order_events: u64,
+}
+
+/// 事件排序器 - 按 (slot, 交易位置, 指令位置) 顺序交付事件
+///
+/// The ordering mode for stateful consumers. Transactions of a slot don't arrive in
+/// block order, and a transaction's events are produced protocol by protocol, so
+/// events are buffered per slot and a slot is released, sorted by transaction and
+/// instruction index, once the stream has moved more than
+/// [`OrderingConfig::slot_lag`] slots past it. Released events get
+/// `metadata.sequence`, counting up from 1 without gaps, so consumers can also
+/// detect lost events downstream.
+///
+/// An event of a slot that was already released (a transaction the source sent
+/// too late, or a slot released early because the buffer was full) can't be put in
+/// place: it is delivered immediately with the next sequence number and, if it
+/// sorts before the last delivered event, counted in [`Self::out_of_order_events`]
+/// and logged. Raise the lag if this happens regularly; each slot of lag adds about
+/// 400ms of latency. Sources without a transaction index keep arrival order within
+/// a slot.
+///
+/// The handle is cheap to clone; keep a clone to read the counters of a sequencer
+/// passed to a stream.
+#[derive(Clone)]
+pub struct EventSequencer {
+    config: OrderingConfig,
+    state: Arc<Mutex<SequencerState>>,
+}
+
+impl Default for EventSequencer {
+    fn default() -> Self {
+        Self::new(OrderingConfig::default())
+    }
+}
+
+impl std::fmt::Debug for EventSequencer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventSequencer")
+            .field("config", &self.config)
+            .field("buffered", &self.buffered())
+            .field("out_of_order_events", &self.out_of_order_events())
+            .finish()
+    }
+}
+
+impl EventSequencer {
+    pub fn new(config: OrderingConfig) -> Self {
+        Self {
+            config,
+            state: Arc::new(Mutex::new(SequencerState {
+                next_sequence: 1,
+                ..Default::default()
+            })),
+        }
+    }
+
+    pub fn config(&self) -> &OrderingConfig {
+        &self.config
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, SequencerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Add an event, returning the events that are ready, in order
+    pub fn push(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        let mut state = self.state();
+        let slot = event.slot();
+        if state.released_slot.is_some_and(|released| slot <= released) {
+            let mut ready = vec![event];
+            Self::sequence(&mut state, &mut ready);
+            return ready;
+        }
+        state.highest_slot = state.highest_slot.max(slot);
+        state.pending.entry(slot).or_default().push(event);
+        state.buffered += 1;
+
+        let mut ready = Vec::new();
+        let release_below = state.highest_slot.saturating_sub(self.config.slot_lag);
+        while let Some(&oldest) = state.pending.keys().next() {
+            if oldest >= release_below && state.buffered <= self.config.max_buffered_events {
+                break;
+            }
+            Self::release_oldest(&mut state, &mut ready);
+        }
+        Self::sequence(&mut state, &mut ready);
+        ready
+    }
+
+    /// Release all buffered events, e.g. when the stream ends
+    pub fn flush(&self) -> Vec<Box<dyn UnifiedEvent>> {
+        let mut state = self.state();
+        let mut ready = Vec::new();
+        while !state.pending.is_empty() {
+            Self::release_oldest(&mut state, &mut ready);
+        }
+        Self::sequence(&mut state, &mut ready);
+        ready
+    }
+
+    fn release_oldest(state: &mut SequencerState, ready: &mut Vec<Box<dyn UnifiedEvent>>) {
+        let Some((slot, mut events)) = state.pending.pop_first() else {
+            return;
+        };
+        state.buffered -= events.len();
+        // 稳定排序，没有交易位置的事件保持到达顺序
+        events.sort_by_key(|event| (event.metadata().tx_index, event.index()));
+        state.released_slot = Some(state.released_slot.map_or(slot, |r| r.max(slot)));
+        ready.extend(events);
+    }
+
+    fn sequence(state: &mut SequencerState, events: &mut [Box<dyn UnifiedEvent>]) {
+        for event in events.iter_mut() {
+            let key = order_key(event.as_ref());
+            if state.last_key.as_ref().is_some_and(|last| key < *last) {
+                state.out_of_order_events += 1;
+                warn!(
+                    "event {} of {} delivered out of order: {:?} after {:?}",
+                    event.id(),
+                    event.signature(),
+                    key,
+                    state.last_key
+                );
+            } else {
+                state.last_key = Some(key);
+            }
+            event.metadata_mut().sequence = state.next_sequence;
+            state.next_sequence += 1;
+        }
+    }
+
+    /// Sequence number the next released event gets
+    pub fn next_sequence(&self) -> u64 {
+        self.state().next_sequence
+    }
+
+    /// Events currently waiting for their slot to be released
+    pub fn buffered(&self) -> usize {
+        self.state().buffered
+    }
+
+    /// Events delivered after an event they sort before, see above
+    pub fn out_of_order_events(&self) -> u64 {
+        self.state().out_of_order_events
+    }
+
+    /// Wrap `callback` so it receives events in order; buffered events are flushed
+    /// to it when the returned callback is dropped
+    pub fn wrap<F>(&self, callback: F) -> SequencedCallback<F>
+    where
+        F: Fn(Box<dyn UnifiedEvent>),
+    {
+        SequencedCallback::new(Some(self.clone()), callback)
+    }
+}
+
+/// 按顺序交付事件的回调，见 [`EventSequencer::wrap`]
+///
+/// Without a sequencer events are passed through as they come.
+pub struct SequencedCallback<F: Fn(Box<dyn UnifiedEvent>)> {
+    sequencer: Option<EventSequencer>,
+    callback: F,
+}
+
+impl<F: Fn(Box<dyn UnifiedEvent>)> SequencedCallback<F> {
+    pub(crate) fn new(sequencer: Option<EventSequencer>, callback: F) -> Self {
+        Self {
+            sequencer,
+            callback,
+        }
+    }
+
+    pub fn call(&self, event: Box<dyn UnifiedEvent>) {
+        match &self.sequencer {
+            Some(sequencer) => {
+                for event in sequencer.push(event) {
+                    (self.callback)(event);
+                }
+            }
+            None => (self.callback)(event),
+        }
+    }
+}
+
+impl<F: Fn(Box<dyn UnifiedEvent>)> Drop for SequencedCallback<F> {
+    fn drop(&mut self) {
+        if let Some(sequencer) = &self.sequencer {
+            for event in sequencer.flush() {
+                (self.callback)(event);
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::UnifiedEvent;
+use crate::streaming::pipeline::{EventPipeline, PipelineMetrics};
+use crate::streaming::shutdown::{ShutdownReport, StreamHandle};
+
+/// Callback handed to a pipeline's source
+pub type PipelineCallback = Box<dyn Fn(Box<dyn UnifiedEvent>) + Send + Sync>;
+
+/// 单个管道的运行句柄
+pub struct PipelineHandle {
+    name: String,
+    metrics: Arc<PipelineMetrics>,
+    stream: StreamHandle,
+}
+
+impl PipelineHandle {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn metrics(&self) -> Arc<PipelineMetrics> {
+        self.metrics.clone()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.stream.is_finished()
+    }
+
+    /// Stop the pipeline's stream immediately, without draining it
+    pub fn abort(&self) {
+        self.stream.abort();
+    }
+
+    /// Stop the pipeline's stream, delivering what it already received, see
+    /// [`StreamHandle::shutdown`]
+    pub async fn shutdown(self, grace: Duration) -> AnyResult<ShutdownReport> {
+        self.stream.shutdown(grace).await
+    }
+
+    /// Wait for the stream to finish and return its result
+    pub async fn join(self) -> AnyResult<()> {
+        self.stream.join().await
+    }
+}
+
+/// 多管道管理 - 在同一进程中运行多个相互独立的管道
+///
+/// Every pipeline has its own source, protocols, stages and sink callback and runs
+/// as its own stream on the shared tokio runtime. State that is safe to share, such
+/// as a tracker wrapped in an `Arc` or the global dev address registry, can be added
+/// to several pipelines; everything else stays per pipeline. The group owns the
+/// [`StreamHandle`] of every source, so stopping a pipeline stops its connection
+/// and parsing tasks too.
+///
+/// # Usage Example
+/// ```ignore
+/// let group = PipelineGroup::new();
+/// let grpc = Arc::new(YellowstoneGrpc::new(endpoint, None)?);
+/// group.spawn("pumpfun", EventPipeline::new().with_stage(curves.clone()), pumpfun_sink, move |callback| async move {
+///     grpc.spawn_events_with_request(vec![Protocol::PumpFun], None, request, callback).await
+/// }).await?;
+/// // later
+/// group.stop("pumpfun", Duration::from_secs(5)).await;
+/// ```
+#[derive(Default)]
+pub struct PipelineGroup {
+    pipelines: Mutex<BTreeMap<String, PipelineHandle>>,
+}
+
+impl PipelineGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn pipelines(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, PipelineHandle>> {
+        self.pipelines.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Start a pipeline
+    ///
+    /// `source` receives the pipeline callback and starts the stream, typically
+    /// with one of the `spawn_*` methods, returning its handle. Errors starting the
+    /// source are returned here.
+    pub async fn spawn<F, S, Fut>(
+        &self,
+        name: impl Into<String>,
+        pipeline: EventPipeline,
+        sink: F,
+        source: S,
+    ) -> AnyResult<()>
+    where
+        F: Fn(Box<dyn UnifiedEvent>) + Send + Sync + 'static,
+        S: FnOnce(PipelineCallback) -> Fut,
+        Fut: Future<Output = AnyResult<StreamHandle>>,
+    {
+        let name = name.into();
+        if self.is_running(&name) {
+            return Err(anyhow::anyhow!("pipeline {} is already running", name));
+        }
+        let metrics = pipeline.metrics();
+        let callback: PipelineCallback = Box::new(pipeline.into_callback(sink));
+        let stream = source(callback).await?;
+
+        let mut pipelines = self.pipelines();
+        // 启动期间可能有同名管道被启动
+        if pipelines
+            .get(&name)
+            .is_some_and(|handle| !handle.is_finished())
+        {
+            stream.abort();
+            return Err(anyhow::anyhow!("pipeline {} is already running", name));
+        }
+        pipelines.insert(
+            name.clone(),
+            PipelineHandle {
+                name,
+                metrics,
+                stream,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.pipelines().keys().cloned().collect()
+    }
+
+    pub fn metrics(&self, name: &str) -> Option<Arc<PipelineMetrics>> {
+        self.pipelines().get(name).map(|handle| handle.metrics())
+    }
+
+    pub fn is_running(&self, name: &str) -> bool {
+        self.pipelines()
+            .get(name)
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    /// Remove a pipeline from the group, returning its handle
+    pub fn take(&self, name: &str) -> Option<PipelineHandle> {
+        self.pipelines().remove(name)
+    }
+
+    /// Shut down and remove a pipeline, giving its stream `grace` to drain; None if
+    /// no pipeline has that name
+    pub async fn stop(&self, name: &str, grace: Duration) -> Option<AnyResult<ShutdownReport>> {
+        let handle = self.take(name)?;
+        Some(handle.shutdown(grace).await)
+    }
+
+    /// Shut down and remove every pipeline, returning each result by name
+    pub async fn stop_all(&self, grace: Duration) -> Vec<(String, AnyResult<ShutdownReport>)> {
+        let pipelines = std::mem::take(&mut *self.pipelines());
+        let shutdowns = pipelines
+            .into_iter()
+            .map(|(name, handle)| async move { (name, handle.shutdown(grace).await) });
+        futures::future::join_all(shutdowns).await
+    }
+
+    /// Wait for every pipeline to finish, returning each result by name
+    pub async fn join_all(&self) -> Vec<(String, AnyResult<()>)> {
+        let pipelines = std::mem::take(&mut *self.pipelines());
+        let mut results = Vec::with_capacity(pipelines.len());
+        for (name, handle) in pipelines {
+            results.push((name, handle.join().await));
+        }
+        results
+    }
+}
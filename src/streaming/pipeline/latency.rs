@@ -0,0 +1,256 @@
+use std::sync::Mutex;
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::impl_unified_event;
+use crate::streaming::block_events::chain_metadata;
+use crate::streaming::event_parser::{
+    common::{ClockHandle, EventMetadata, EventType},
+    UnifiedEvent,
+};
+use crate::streaming::pipeline::EventMiddleware;
+
+/// Default p99 latency budget from block time to delivery (milliseconds)
+pub const DEFAULT_LATENCY_BUDGET_MS: i64 = 2_000;
+/// Default number of samples per evaluation window
+pub const DEFAULT_LATENCY_WINDOW: usize = 1_000;
+
+/// 延迟超标的主要来源
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LatencyCause {
+    /// Most of the latency was spent before the message reached this process
+    #[default]
+    Provider,
+    /// Most of the latency was spent parsing and in the pipeline
+    Local,
+}
+
+/// 延迟统计 - 最近一个完整窗口的百分位数（毫秒）
+///
+/// `provider` covers block time to the arrival of the source message, `local`
+/// arrival to delivery; their p99s don't add up to the total p99.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencyStats {
+    pub samples: usize,
+    pub p50_ms: i64,
+    pub p99_ms: i64,
+    pub max_ms: i64,
+    pub provider_p99_ms: i64,
+    pub local_p99_ms: i64,
+}
+
+impl LatencyStats {
+    pub fn cause(&self) -> LatencyCause {
+        if self.provider_p99_ms >= self.local_p99_ms {
+            LatencyCause::Provider
+        } else {
+            LatencyCause::Local
+        }
+    }
+}
+
+/// 延迟超标告警事件
+///
+/// Emitted by [`LatencyWatchdog`] when the p99 of a window goes over the budget.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencyBudgetExceededEvent {
+    pub metadata: EventMetadata,
+    pub budget_ms: i64,
+    pub stats: LatencyStats,
+    pub cause: LatencyCause,
+}
+
+impl_unified_event!(LatencyBudgetExceededEvent,);
+
+impl LatencyBudgetExceededEvent {
+    pub fn new(slot: u64, budget_ms: i64, stats: LatencyStats, now_ms: i64) -> Self {
+        let mut metadata = chain_metadata(slot, None, EventType::LatencyBudgetExceeded, now_ms);
+        metadata.set_id(format!("latency_budget:{}:{}", slot, now_ms));
+        let cause = stats.cause();
+        Self {
+            metadata,
+            budget_ms,
+            stats,
+            cause,
+        }
+    }
+}
+
+#[derive(Default)]
+struct WatchdogState {
+    // 当前窗口的 (总延迟, 数据源延迟, 本地延迟)
+    total: Vec<i64>,
+    provider: Vec<i64>,
+    local: Vec<i64>,
+    last_slot: u64,
+    stats: LatencyStats,
+    exceeded: bool,
+    alerts: u64,
+}
+
+/// 端到端延迟看门狗 - 测量区块时间到交付的延迟
+///
+/// Add it as the last pipeline stage so the measurement includes every stage
+/// before the user callback. Each event with a block time contributes a sample;
+/// every `window` samples the percentiles are computed and published through
+/// [`Self::stats`], and when the p99 goes over the budget a
+/// [`LatencyBudgetExceededEvent`] is emitted after the event. The alert is raised
+/// once per excursion and re-armed by a window back within the budget.
+///
+/// The split into provider and local latency uses the arrival time of the source
+/// message (`grpc_received_time_ms`, or the parse start when the source doesn't
+/// set it): a provider p99 near the total points at the node or network, a local
+/// p99 near the total at parsing or slow stages. Block times have second
+/// resolution, so keep the budget well above a second.
+///
+/// # Usage Example
+/// ```ignore
+/// let watchdog = Arc::new(LatencyWatchdog::new().with_budget_ms(1_500));
+/// let pipeline = EventPipeline::new().with_stage(watchdog.clone());
+/// // later, from a metrics endpoint
+/// gauge!("delivery_latency_p99_ms", watchdog.stats().p99_ms as f64);
+/// ```
+pub struct LatencyWatchdog {
+    budget_ms: i64,
+    window: usize,
+    clock: ClockHandle,
+    state: Mutex<WatchdogState>,
+}
+
+impl Default for LatencyWatchdog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LatencyWatchdog {
+    pub fn new() -> Self {
+        Self {
+            budget_ms: DEFAULT_LATENCY_BUDGET_MS,
+            window: DEFAULT_LATENCY_WINDOW,
+            clock: ClockHandle::default(),
+            state: Mutex::new(WatchdogState::default()),
+        }
+    }
+
+    /// p99 latency above which an alert is raised
+    pub fn with_budget_ms(mut self, budget_ms: i64) -> Self {
+        self.budget_ms = budget_ms;
+        self
+    }
+
+    /// Samples per evaluation window
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window.max(1);
+        self
+    }
+
+    /// Clock the delivery time is read from
+    pub fn with_clock(mut self, clock: ClockHandle) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn budget_ms(&self) -> i64 {
+        self.budget_ms
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, WatchdogState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record the latency of an event delivered now, returning the alert it raises
+    ///
+    /// Events without a block time are ignored.
+    pub fn record(&self, metadata: &EventMetadata) -> Option<LatencyBudgetExceededEvent> {
+        if metadata.block_time_ms <= 0 {
+            return None;
+        }
+        let now_ms = self.clock.now_ms();
+        let received_ms = if metadata.grpc_received_time_ms > 0 {
+            metadata.grpc_received_time_ms
+        } else {
+            metadata.program_received_time_ms
+        };
+        let mut state = self.state();
+        state.total.push(now_ms - metadata.block_time_ms);
+        state.provider.push(received_ms - metadata.block_time_ms);
+        state.local.push(now_ms - received_ms);
+        state.last_slot = state.last_slot.max(metadata.slot);
+        if state.total.len() < self.window {
+            return None;
+        }
+
+        let stats = LatencyStats {
+            samples: state.total.len(),
+            p50_ms: percentile(&mut state.total, 0.50),
+            p99_ms: percentile(&mut state.total, 0.99),
+            max_ms: state.total.iter().copied().max().unwrap_or_default(),
+            provider_p99_ms: percentile(&mut state.provider, 0.99),
+            local_p99_ms: percentile(&mut state.local, 0.99),
+        };
+        state.total.clear();
+        state.provider.clear();
+        state.local.clear();
+        state.stats = stats.clone();
+
+        let exceeded = stats.p99_ms > self.budget_ms;
+        let raise = exceeded && !state.exceeded;
+        state.exceeded = exceeded;
+        if !raise {
+            return None;
+        }
+        state.alerts += 1;
+        warn!(
+            "delivery latency p99 {}ms over the {}ms budget (provider p99 {}ms, local p99 {}ms)",
+            stats.p99_ms, self.budget_ms, stats.provider_p99_ms, stats.local_p99_ms
+        );
+        Some(LatencyBudgetExceededEvent::new(
+            state.last_slot,
+            self.budget_ms,
+            stats,
+            now_ms,
+        ))
+    }
+
+    /// Percentiles of the last complete window, for a metrics gauge
+    pub fn stats(&self) -> LatencyStats {
+        self.state().stats.clone()
+    }
+
+    /// Whether the last complete window was over the budget
+    pub fn is_exceeded(&self) -> bool {
+        self.state().exceeded
+    }
+
+    /// Alerts raised so far
+    pub fn alerts(&self) -> u64 {
+        self.state().alerts
+    }
+}
+
+/// Nearest-rank percentile, sorts `samples`
+fn percentile(samples: &mut [i64], p: f64) -> i64 {
+    if samples.is_empty() {
+        return 0;
+    }
+    samples.sort_unstable();
+    let rank = (p * samples.len() as f64).ceil() as usize;
+    samples[rank.clamp(1, samples.len()) - 1]
+}
+
+impl EventMiddleware for LatencyWatchdog {
+    fn name(&self) -> &str {
+        "latency_watchdog"
+    }
+
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        let alert = self.record(event.metadata());
+        let mut events = vec![event];
+        if let Some(alert) = alert {
+            events.push(Box::new(alert));
+        }
+        events
+    }
+}
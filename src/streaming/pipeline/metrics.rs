@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// 单个阶段的耗时统计
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StageStats {
+    /// Number of events the stage processed
+    pub calls: u64,
+    /// Total time spent in the stage (microseconds)
+    pub total_us: u64,
+    /// Slowest single call (microseconds)
+    pub max_us: u64,
+    /// Number of events whose deadline was crossed while in this stage
+    pub deadline_exceeded: u64,
+}
+
+impl StageStats {
+    pub fn avg_us(&self) -> u64 {
+        if self.calls == 0 {
+            0
+        } else {
+            self.total_us / self.calls
+        }
+    }
+}
+
+/// Pipeline运行指标
+#[derive(Debug, Default)]
+pub struct PipelineMetrics {
+    events_processed: AtomicU64,
+    deadline_exceeded: AtomicU64,
+    stages: Mutex<HashMap<String, StageStats>>,
+}
+
+impl PipelineMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record_event(&self) {
+        self.events_processed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_stage(&self, stage: &str, elapsed: Duration, exceeded: bool) {
+        let elapsed_us = elapsed.as_micros() as u64;
+        let mut stages = self.stages.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = stages.entry(stage.to_string()).or_default();
+        stats.calls += 1;
+        stats.total_us += elapsed_us;
+        stats.max_us = stats.max_us.max(elapsed_us);
+        if exceeded {
+            stats.deadline_exceeded += 1;
+            self.deadline_exceeded.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Total number of events that entered the pipeline
+    pub fn events_processed(&self) -> u64 {
+        self.events_processed.load(Ordering::Relaxed)
+    }
+
+    /// Total number of events that exceeded the processing deadline
+    pub fn deadline_exceeded(&self) -> u64 {
+        self.deadline_exceeded.load(Ordering::Relaxed)
+    }
+
+    /// Per-stage statistics, keyed by stage name
+    pub fn stage_stats(&self) -> HashMap<String, StageStats> {
+        self.stages.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// The stage in which deadlines were crossed most often
+    pub fn slowest_stage(&self) -> Option<(String, StageStats)> {
+        self.stage_stats()
+            .into_iter()
+            .filter(|(_, stats)| stats.deadline_exceeded > 0)
+            .max_by_key(|(_, stats)| stats.deadline_exceeded)
+    }
+
+    pub fn reset(&self) {
+        self.events_processed.store(0, Ordering::Relaxed);
+        self.deadline_exceeded.store(0, Ordering::Relaxed);
+        self.stages.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
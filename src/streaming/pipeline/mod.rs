@@ -0,0 +1,193 @@
+#[cfg(feature = "runtime")]
+pub mod group;
+pub mod latency;
+pub mod metrics;
+pub mod sampling;
+pub mod shared;
+pub mod watchlist;
+
+#[cfg(feature = "runtime")]
+pub use group::{PipelineCallback, PipelineGroup, PipelineHandle};
+pub use latency::{LatencyBudgetExceededEvent, LatencyCause, LatencyStats, LatencyWatchdog};
+pub use metrics::{PipelineMetrics, StageStats};
+pub use sampling::{SamplingStage, SamplingStats};
+pub use shared::{FanOut, SharedEvent, SharedHandler};
+pub use watchlist::{Watchlist, WatchlistFilter};
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::streaming::event_parser::UnifiedEvent;
+
+/// Stage name used in metrics for the user callback
+pub const HANDLER_STAGE: &str = "handler";
+
+/// 事件中间件 - 在事件到达用户回调之前依次执行的处理阶段
+///
+/// A stage receives one event and returns the events to pass on: an empty vec drops
+/// the event, extra entries are synthetic events emitted by the stage.
+pub trait EventMiddleware: Send + Sync {
+    /// Stage name, used to identify the stage in metrics
+    fn name(&self) -> &str;
+
+    /// Process an event
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>>;
+}
+
+impl<T: EventMiddleware + ?Sized> EventMiddleware for Arc<T> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        (**self).handle(event)
+    }
+}
+
+/// 基于闭包的中间件
+pub struct FnMiddleware<F> {
+    name: String,
+    f: F,
+}
+
+impl<F> FnMiddleware<F>
+where
+    F: Fn(Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> + Send + Sync,
+{
+    pub fn new(name: impl Into<String>, f: F) -> Self {
+        Self { name: name.into(), f }
+    }
+}
+
+impl<F> EventMiddleware for FnMiddleware<F>
+where
+    F: Fn(Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> + Send + Sync,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        (self.f)(event)
+    }
+}
+
+/// 事件处理管道
+///
+/// Runs each parsed event through the configured stages and then the user callback.
+/// When a deadline is set, events that take longer than it are flagged with
+/// `metadata.deadline_exceeded` and the stage in which the deadline was crossed is
+/// counted in [`PipelineMetrics`].
+///
+/// # Usage Example
+/// ```ignore
+/// let pipeline = EventPipeline::new()
+///     .with_deadline(Duration::from_millis(5))
+///     .with_stage(FnMiddleware::new("enrich", |event| vec![event]));
+/// let metrics = pipeline.metrics();
+/// grpc.subscribe_events_v2(protocols, None, include, vec![], vec![], None, pipeline.into_callback(callback)).await?;
+/// ```
+#[derive(Default)]
+pub struct EventPipeline {
+    stages: Vec<Arc<dyn EventMiddleware>>,
+    deadline: Option<Duration>,
+    metrics: Arc<PipelineMetrics>,
+}
+
+impl EventPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a stage to the end of the pipeline
+    pub fn with_stage(mut self, stage: impl EventMiddleware + 'static) -> Self {
+        self.stages.push(Arc::new(stage));
+        self
+    }
+
+    /// Set the per-event processing deadline
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub fn deadline(&self) -> Option<Duration> {
+        self.deadline
+    }
+
+    /// Shared handle to the pipeline metrics
+    pub fn metrics(&self) -> Arc<PipelineMetrics> {
+        self.metrics.clone()
+    }
+
+    pub fn stage_names(&self) -> Vec<String> {
+        self.stages.iter().map(|s| s.name().to_string()).collect()
+    }
+
+    fn is_past_deadline(&self, started: Instant) -> bool {
+        self.deadline.map(|d| started.elapsed() > d).unwrap_or(false)
+    }
+
+    /// Run an event through all stages
+    pub fn process(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        self.run(event, Instant::now()).0
+    }
+
+    fn run(
+        &self,
+        event: Box<dyn UnifiedEvent>,
+        started: Instant,
+    ) -> (Vec<Box<dyn UnifiedEvent>>, bool) {
+        self.metrics.record_event();
+        let mut exceeded = false;
+        let mut events = vec![event];
+        for stage in &self.stages {
+            let stage_started = Instant::now();
+            let mut next = Vec::with_capacity(events.len());
+            for event in events {
+                next.extend(stage.handle(event));
+            }
+            let crossed = !exceeded && self.is_past_deadline(started);
+            exceeded |= crossed;
+            self.metrics.record_stage(stage.name(), stage_started.elapsed(), crossed);
+            events = next;
+            if events.is_empty() {
+                break;
+            }
+        }
+        if exceeded {
+            for event in &mut events {
+                event.metadata_mut().deadline_exceeded = true;
+            }
+        }
+        (events, exceeded)
+    }
+
+    /// Wrap a user callback so every event runs through the pipeline first
+    pub fn into_callback<F>(self, callback: F) -> impl Fn(Box<dyn UnifiedEvent>) + Send + Sync
+    where
+        F: Fn(Box<dyn UnifiedEvent>) + Send + Sync,
+    {
+        move |event: Box<dyn UnifiedEvent>| {
+            let started = Instant::now();
+            let (events, mut exceeded) = self.run(event, started);
+            for event in events {
+                let handler_started = Instant::now();
+                callback(event);
+                let crossed = !exceeded && self.is_past_deadline(started);
+                exceeded |= crossed;
+                self.metrics
+                    .record_stage(HANDLER_STAGE, handler_started.elapsed(), crossed);
+            }
+        }
+    }
+
+    /// Like [`EventPipeline::into_callback`], but delivers each event as a
+    /// [`SharedEvent`] so handlers can keep or fan it out without cloning it
+    pub fn into_shared_callback<F>(self, callback: F) -> impl Fn(Box<dyn UnifiedEvent>) + Send + Sync
+    where
+        F: Fn(SharedEvent) + Send + Sync,
+    {
+        self.into_callback(move |event: Box<dyn UnifiedEvent>| callback(SharedEvent::from(event)))
+    }
+}
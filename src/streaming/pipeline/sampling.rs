@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::streaming::event_parser::common::{EventType, ProtocolType};
+use crate::streaming::event_parser::UnifiedEvent;
+use crate::streaming::pipeline::EventMiddleware;
+
+/// 采样与限流统计
+#[derive(Debug, Default)]
+pub struct SamplingStats {
+    /// Events passed on
+    pub passed: AtomicU64,
+    /// Events dropped by a sample rate
+    pub sampled_out: AtomicU64,
+    /// Events dropped by a rate limit
+    pub rate_limited: AtomicU64,
+}
+
+/// 令牌桶，容量为每秒事件数
+#[derive(Debug)]
+struct TokenBucket {
+    per_second: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(per_second: u32) -> Self {
+        Self {
+            per_second: per_second as f64,
+            tokens: per_second as f64,
+            updated: Instant::now(),
+        }
+    }
+
+    fn try_take(&mut self) -> bool {
+        let now = Instant::now();
+        let refill = now.duration_since(self.updated).as_secs_f64() * self.per_second;
+        self.tokens = (self.tokens + refill).min(self.per_second);
+        self.updated = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// 采样与限流阶段
+///
+/// Thins out the event stream for consumers that cannot absorb the full volume,
+/// e.g. keep every create but only 10% of trades. The sample rate of an event is
+/// the rate of its event type, else the rate of its protocol, else the default of
+/// 1.0. Rate limits cap events per second with a burst of one second and apply per
+/// event type and per protocol; an event has to pass both. Sampling runs before
+/// rate limiting, so sampled out events do not use up the limit.
+///
+/// # Usage Example
+/// ```ignore
+/// let sampling = SamplingStage::new()
+///     .with_event_type_rate(EventType::PumpFunBuy, 0.1)
+///     .with_event_type_rate(EventType::PumpFunSell, 0.1)
+///     .with_protocol_limit(ProtocolType::PumpSwap, 500);
+/// let pipeline = EventPipeline::new().with_stage(sampling);
+/// ```
+pub struct SamplingStage {
+    default_rate: f64,
+    event_type_rates: HashMap<EventType, f64>,
+    protocol_rates: HashMap<ProtocolType, f64>,
+    event_type_limits: HashMap<EventType, Mutex<TokenBucket>>,
+    protocol_limits: HashMap<ProtocolType, Mutex<TokenBucket>>,
+    rng: Mutex<StdRng>,
+    stats: Arc<SamplingStats>,
+}
+
+impl Default for SamplingStage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SamplingStage {
+    pub fn new() -> Self {
+        Self {
+            default_rate: 1.0,
+            event_type_rates: HashMap::new(),
+            protocol_rates: HashMap::new(),
+            event_type_limits: HashMap::new(),
+            protocol_limits: HashMap::new(),
+            rng: Mutex::new(StdRng::seed_from_u64(rand::random())),
+            stats: Arc::new(SamplingStats::default()),
+        }
+    }
+
+    /// Share of events without a more specific rule to keep, clamped to 0..=1
+    pub fn with_default_rate(mut self, rate: f64) -> Self {
+        self.default_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_event_type_rate(mut self, event_type: EventType, rate: f64) -> Self {
+        self.event_type_rates
+            .insert(event_type, rate.clamp(0.0, 1.0));
+        self
+    }
+
+    pub fn with_protocol_rate(mut self, protocol: ProtocolType, rate: f64) -> Self {
+        self.protocol_rates.insert(protocol, rate.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Pass at most `per_second` events of `event_type` per second
+    pub fn with_event_type_limit(mut self, event_type: EventType, per_second: u32) -> Self {
+        self.event_type_limits
+            .insert(event_type, Mutex::new(TokenBucket::new(per_second)));
+        self
+    }
+
+    /// Pass at most `per_second` events of `protocol` per second
+    pub fn with_protocol_limit(mut self, protocol: ProtocolType, per_second: u32) -> Self {
+        self.protocol_limits
+            .insert(protocol, Mutex::new(TokenBucket::new(per_second)));
+        self
+    }
+
+    /// Use a fixed seed so the sampled events are reproducible
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Mutex::new(StdRng::seed_from_u64(seed));
+        self
+    }
+
+    /// Shared handle to the counters
+    pub fn stats(&self) -> Arc<SamplingStats> {
+        self.stats.clone()
+    }
+
+    /// Share of events of this type and protocol that is kept
+    pub fn sample_rate(&self, event_type: &EventType, protocol: &ProtocolType) -> f64 {
+        self.event_type_rates
+            .get(event_type)
+            .or_else(|| self.protocol_rates.get(protocol))
+            .copied()
+            .unwrap_or(self.default_rate)
+    }
+
+    fn sample(&self, rate: f64) -> bool {
+        if rate >= 1.0 {
+            return true;
+        }
+        if rate <= 0.0 {
+            return false;
+        }
+        self.rng
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .random_bool(rate)
+    }
+
+    fn take<K: Eq + std::hash::Hash>(limits: &HashMap<K, Mutex<TokenBucket>>, key: &K) -> bool {
+        limits.get(key).map_or(true, |bucket| {
+            bucket.lock().unwrap_or_else(|e| e.into_inner()).try_take()
+        })
+    }
+
+    /// Whether an event of this type and protocol passes
+    pub fn accepts(&self, event_type: &EventType, protocol: &ProtocolType) -> bool {
+        if !self.sample(self.sample_rate(event_type, protocol)) {
+            self.stats.sampled_out.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        if !Self::take(&self.event_type_limits, event_type)
+            || !Self::take(&self.protocol_limits, protocol)
+        {
+            self.stats.rate_limited.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        self.stats.passed.fetch_add(1, Ordering::Relaxed);
+        true
+    }
+}
+
+impl EventMiddleware for SamplingStage {
+    fn name(&self) -> &str {
+        "sampling"
+    }
+
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        let metadata = event.metadata();
+        if self.accepts(&metadata.event_type, &metadata.protocol) {
+            vec![event]
+        } else {
+            vec![]
+        }
+    }
+}
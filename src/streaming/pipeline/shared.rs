@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use crate::streaming::event_parser::UnifiedEvent;
+
+/// 共享事件 - 多个处理器之间共享同一个事件对象
+pub type SharedEvent = Arc<dyn UnifiedEvent>;
+
+/// Handler receiving shared events
+pub type SharedHandler = Arc<dyn Fn(SharedEvent) + Send + Sync>;
+
+/// 事件分发 - 把同一个事件交给多个处理器而不复制事件
+///
+/// Each parsed event is moved into one `Arc` and every handler receives a reference
+/// counted handle to it, instead of each handler getting its own `clone_boxed` copy.
+/// Handlers may keep the event (e.g. queue it for another task) without cloning.
+///
+/// # Usage Example
+/// ```ignore
+/// let fan_out = FanOut::new()
+///     .with_handler(|event: SharedEvent| metrics.record(&*event))
+///     .with_handler(move |event: SharedEvent| { let _ = tx.send(event); });
+/// grpc.subscribe_events_v2(protocols, None, include, vec![], vec![], None, fan_out.into_callback()).await?;
+/// ```
+#[derive(Default, Clone)]
+pub struct FanOut {
+    handlers: Vec<SharedHandler>,
+}
+
+impl FanOut {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(SharedEvent) + Send + Sync + 'static,
+    {
+        self.handlers.push(Arc::new(handler));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    /// Deliver one event to every handler
+    pub fn dispatch(&self, event: SharedEvent) {
+        for handler in &self.handlers {
+            handler(event.clone());
+        }
+    }
+
+    /// Callback accepting boxed events as produced by the stream sources
+    pub fn into_callback(self) -> impl Fn(Box<dyn UnifiedEvent>) + Send + Sync {
+        move |event: Box<dyn UnifiedEvent>| self.dispatch(SharedEvent::from(event))
+    }
+}
@@ -0,0 +1,325 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+
+use crate::common::AnyResult;
+use crate::streaming::enrichment::event_mints;
+use crate::streaming::event_parser::{
+    common::{EventType, ProtocolType},
+    UnifiedEvent,
+};
+use crate::streaming::normalize::NormalizedTrade;
+use crate::streaming::pipeline::EventMiddleware;
+
+/// 关注列表文件格式
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct WatchlistFile {
+    wallets: Vec<String>,
+    mints: Vec<String>,
+    event_types: Vec<EventType>,
+    protocols: Vec<ProtocolType>,
+}
+
+/// 钱包/mint关注列表和事件过滤条件
+///
+/// Every non-empty set has to match for an event to pass; an empty watchlist
+/// passes everything. Files list keys as base58 strings:
+///
+/// ```json
+/// { "wallets": ["..."], "mints": ["..."], "event_types": ["PumpFunBuy"] }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Watchlist {
+    /// Matched against the signers of the transaction and the trader of trades
+    pub wallets: HashSet<Pubkey>,
+    pub mints: HashSet<Pubkey>,
+    pub event_types: HashSet<EventType>,
+    pub protocols: HashSet<ProtocolType>,
+}
+
+impl Watchlist {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_wallet(mut self, wallet: Pubkey) -> Self {
+        self.wallets.insert(wallet);
+        self
+    }
+
+    pub fn with_mint(mut self, mint: Pubkey) -> Self {
+        self.mints.insert(mint);
+        self
+    }
+
+    pub fn with_event_type(mut self, event_type: EventType) -> Self {
+        self.event_types.insert(event_type);
+        self
+    }
+
+    pub fn with_protocol(mut self, protocol: ProtocolType) -> Self {
+        self.protocols.insert(protocol);
+        self
+    }
+
+    pub fn from_json(watchlist: &str) -> AnyResult<Self> {
+        Self::from_file_format(serde_json::from_str(watchlist)?)
+    }
+
+    pub fn from_toml(watchlist: &str) -> AnyResult<Self> {
+        Self::from_file_format(toml::from_str(watchlist)?)
+    }
+
+    /// Load a `.toml` file, or a JSON file for any other extension
+    pub fn load(path: impl AsRef<Path>) -> AnyResult<Self> {
+        let path = path.as_ref();
+        let watchlist = std::fs::read_to_string(path)?;
+        let is_toml = path
+            .extension()
+            .is_some_and(|extension| extension == "toml");
+        if is_toml {
+            Self::from_toml(&watchlist)
+        } else {
+            Self::from_json(&watchlist)
+        }
+        .map_err(|e| anyhow::anyhow!("invalid watchlist {}: {}", path.display(), e))
+    }
+
+    fn from_file_format(file: WatchlistFile) -> AnyResult<Self> {
+        let keys = |name: &str, keys: Vec<String>| -> AnyResult<HashSet<Pubkey>> {
+            keys.iter()
+                .enumerate()
+                .map(|(i, key)| {
+                    Pubkey::from_str(key.trim())
+                        .map_err(|_| anyhow::anyhow!("{}[{}]: invalid pubkey {:?}", name, i, key))
+                })
+                .collect()
+        };
+        Ok(Self {
+            wallets: keys("wallets", file.wallets)?,
+            mints: keys("mints", file.mints)?,
+            event_types: file.event_types.into_iter().collect(),
+            protocols: file.protocols.into_iter().collect(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wallets.is_empty()
+            && self.mints.is_empty()
+            && self.event_types.is_empty()
+            && self.protocols.is_empty()
+    }
+
+    /// Whether the event passes every non-empty set
+    pub fn matches(&self, event: &dyn UnifiedEvent) -> bool {
+        let metadata = event.metadata();
+        if !self.event_types.is_empty() && !self.event_types.contains(&metadata.event_type) {
+            return false;
+        }
+        if !self.protocols.is_empty() && !self.protocols.contains(&metadata.protocol) {
+            return false;
+        }
+        if self.wallets.is_empty() && self.mints.is_empty() {
+            return true;
+        }
+        let trade = match event.as_any().downcast_ref::<NormalizedTrade>() {
+            Some(trade) => Some(trade.clone()),
+            None => NormalizedTrade::from_event(event),
+        };
+        if !self.wallets.is_empty() {
+            let signer = metadata
+                .signers
+                .iter()
+                .any(|signer| self.wallets.contains(signer));
+            let trader = trade
+                .as_ref()
+                .is_some_and(|trade| self.wallets.contains(&trade.trader));
+            if !signer && !trader {
+                return false;
+            }
+        }
+        if !self.mints.is_empty() {
+            let mint = event_mints(event)
+                .iter()
+                .any(|mint| self.mints.contains(mint))
+                || trade
+                    .as_ref()
+                    .is_some_and(|trade| self.mints.contains(&trade.base_mint));
+            if !mint {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// 可热更新的关注列表过滤阶段
+///
+/// Drops events that don't match the current [`Watchlist`]. The list is swapped
+/// atomically, by API call or by reloading its file, while the subscription keeps
+/// running, so target lists can be rotated without reconnecting. A file that
+/// fails to load leaves the previous list in place.
+///
+/// # Usage Example
+/// ```ignore
+/// let filter = Arc::new(WatchlistFilter::from_file("watchlist.json")?);
+/// filter.clone().spawn_file_watch(Duration::from_secs(1));
+/// let pipeline = EventPipeline::new().with_stage(filter.clone());
+/// // later, from an admin endpoint
+/// filter.add_wallet(wallet);
+/// ```
+pub struct WatchlistFilter {
+    watchlist: RwLock<Arc<Watchlist>>,
+    path: Option<PathBuf>,
+    modified: Mutex<Option<SystemTime>>,
+    version: AtomicU64,
+}
+
+impl Default for WatchlistFilter {
+    fn default() -> Self {
+        Self::new(Watchlist::default())
+    }
+}
+
+impl WatchlistFilter {
+    pub fn new(watchlist: Watchlist) -> Self {
+        Self {
+            watchlist: RwLock::new(Arc::new(watchlist)),
+            path: None,
+            modified: Mutex::new(None),
+            version: AtomicU64::new(0),
+        }
+    }
+
+    /// Filter backed by a watchlist file, see [`Watchlist::load`]
+    pub fn from_file(path: impl AsRef<Path>) -> AnyResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let filter = Self {
+            path: Some(path),
+            ..Self::default()
+        };
+        filter.reload()?;
+        Ok(filter)
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Current watchlist
+    pub fn watchlist(&self) -> Arc<Watchlist> {
+        self.watchlist
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Number of times the watchlist was replaced
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
+    /// Replace the whole watchlist
+    pub fn replace(&self, watchlist: Watchlist) -> u64 {
+        *self.watchlist.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(watchlist);
+        self.version.fetch_add(1, Ordering::AcqRel) + 1
+    }
+
+    /// Change a copy of the watchlist and swap it in
+    pub fn update(&self, f: impl FnOnce(&mut Watchlist)) -> u64 {
+        let mut watchlist = self.watchlist.write().unwrap_or_else(|e| e.into_inner());
+        let mut updated = Watchlist::clone(&watchlist);
+        f(&mut updated);
+        *watchlist = Arc::new(updated);
+        self.version.fetch_add(1, Ordering::AcqRel) + 1
+    }
+
+    pub fn add_wallet(&self, wallet: Pubkey) -> u64 {
+        self.update(|watchlist| {
+            watchlist.wallets.insert(wallet);
+        })
+    }
+
+    pub fn remove_wallet(&self, wallet: &Pubkey) -> u64 {
+        self.update(|watchlist| {
+            watchlist.wallets.remove(wallet);
+        })
+    }
+
+    pub fn add_mint(&self, mint: Pubkey) -> u64 {
+        self.update(|watchlist| {
+            watchlist.mints.insert(mint);
+        })
+    }
+
+    pub fn remove_mint(&self, mint: &Pubkey) -> u64 {
+        self.update(|watchlist| {
+            watchlist.mints.remove(mint);
+        })
+    }
+
+    /// Reload the watchlist file
+    pub fn reload(&self) -> AnyResult<u64> {
+        let Some(path) = &self.path else {
+            return Err(anyhow::anyhow!("watchlist filter has no file"));
+        };
+        // 先记录修改时间，损坏的文件只报告一次，直到再次修改
+        *self.modified.lock().unwrap_or_else(|e| e.into_inner()) =
+            std::fs::metadata(path)?.modified().ok();
+        Ok(self.replace(Watchlist::load(path)?))
+    }
+
+    /// Reload the watchlist file if it was modified since the last load
+    pub fn reload_if_changed(&self) -> AnyResult<bool> {
+        let Some(path) = &self.path else {
+            return Ok(false);
+        };
+        let modified = std::fs::metadata(path)?.modified().ok();
+        if modified.is_some()
+            && modified == *self.modified.lock().unwrap_or_else(|e| e.into_inner())
+        {
+            return Ok(false);
+        }
+        self.reload()?;
+        Ok(true)
+    }
+
+    /// Check the file for changes every `interval` in the background
+    #[cfg(feature = "runtime")]
+    pub fn spawn_file_watch(
+        self: Arc<Self>,
+        interval: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match self.reload_if_changed() {
+                    Ok(true) => log::info!("Reloaded watchlist, version {}", self.version()),
+                    Ok(false) => {}
+                    Err(e) => log::warn!("Failed to reload watchlist: {:?}", e),
+                }
+            }
+        })
+    }
+}
+
+impl EventMiddleware for WatchlistFilter {
+    fn name(&self) -> &str {
+        "watchlist"
+    }
+
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        if self.watchlist().matches(event.as_ref()) {
+            vec![event]
+        } else {
+            vec![]
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! 插件ABI - 插件动态库与streamer之间的C接口
+//!
+//! A plugin is a `cdylib` exporting a static [`PluginDeclaration`] under the symbol
+//! [`PLUGIN_DECLARATION_SYMBOL`]. Only `#[repr(C)]` types and `extern "C"` functions
+//! cross the boundary, so plugins don't have to be built with the same compiler as
+//! the streamer. Events and sink input are exchanged as UTF-8 JSON.
+//!
+//! Bump [`PLUGIN_ABI_VERSION`] on every incompatible change to these types; the
+//! loader rejects plugins declaring another version.
+//!
+//! # Plugin Example
+//! ```ignore
+//! use solana_streamer_sdk::streaming::plugin::abi::*;
+//!
+//! static PROGRAM_IDS: [[u8; 32]; 1] = [MY_PROGRAM_ID];
+//!
+//! unsafe extern "C" fn parse_instruction(
+//!     program_id: *const [u8; 32],
+//!     data: *const u8,
+//!     data_len: usize,
+//!     accounts: *const [u8; 32],
+//!     accounts_len: usize,
+//!     out: *mut PluginBuffer,
+//! ) -> i32 {
+//!     // decode, then write `[{"name": "Swap", "fields": {...}}]` into `out`
+//!     *out = PluginBuffer::from_vec(json.into_bytes());
+//!     PLUGIN_OK
+//! }
+//!
+//! #[no_mangle]
+//! pub static solana_streamer_plugin: PluginDeclaration = PluginDeclaration {
+//!     abi_version: PLUGIN_ABI_VERSION,
+//!     name: c"my-protocol".as_ptr(),
+//!     program_ids: PROGRAM_IDS.as_ptr(),
+//!     program_ids_len: PROGRAM_IDS.len(),
+//!     parse_instruction: Some(parse_instruction),
+//!     write_event: None,
+//!     flush: None,
+//!     free_buffer: Some(free_buffer),
+//! };
+//! ```
+
+use std::ffi::c_char;
+
+/// Current plugin ABI version
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Name of the exported [`PluginDeclaration`] static
+pub const PLUGIN_DECLARATION_SYMBOL: &[u8] = b"solana_streamer_plugin\0";
+
+/// Return code of a successful plugin call; any other value is an error code
+pub const PLUGIN_OK: i32 = 0;
+
+/// 插件分配的字节缓冲区，由插件的 `free_buffer` 释放
+#[repr(C)]
+#[derive(Debug)]
+pub struct PluginBuffer {
+    pub ptr: *mut u8,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl PluginBuffer {
+    pub const fn empty() -> Self {
+        Self {
+            ptr: std::ptr::null_mut(),
+            len: 0,
+            capacity: 0,
+        }
+    }
+
+    /// Hand a vec to the host; release it with [`free_buffer`] in the same library
+    pub fn from_vec(bytes: Vec<u8>) -> Self {
+        let mut bytes = std::mem::ManuallyDrop::new(bytes);
+        Self {
+            ptr: bytes.as_mut_ptr(),
+            len: bytes.len(),
+            capacity: bytes.capacity(),
+        }
+    }
+}
+
+/// Default `free_buffer` implementation for buffers created with [`PluginBuffer::from_vec`]
+///
+/// # Safety
+/// `buffer` must come from [`PluginBuffer::from_vec`] of the library exporting this
+/// function and must not be freed twice.
+pub unsafe extern "C" fn free_buffer(buffer: PluginBuffer) {
+    if !buffer.ptr.is_null() {
+        drop(Vec::from_raw_parts(buffer.ptr, buffer.len, buffer.capacity));
+    }
+}
+
+/// Parse one instruction of a supported program into a JSON array of
+/// `{"name": string, "fields": object}`; an empty buffer means no events
+pub type ParseInstructionFn = unsafe extern "C" fn(
+    program_id: *const [u8; 32],
+    data: *const u8,
+    data_len: usize,
+    accounts: *const [u8; 32],
+    accounts_len: usize,
+    out: *mut PluginBuffer,
+) -> i32;
+
+/// Receive one event as its type name and JSON fields
+pub type WriteEventFn = unsafe extern "C" fn(
+    event_type: *const u8,
+    event_type_len: usize,
+    json: *const u8,
+    json_len: usize,
+) -> i32;
+
+/// Flush buffered sink output
+pub type FlushFn = unsafe extern "C" fn() -> i32;
+
+/// Release a buffer returned by the plugin
+pub type FreeBufferFn = unsafe extern "C" fn(buffer: PluginBuffer);
+
+/// 插件声明 - 插件导出的入口
+///
+/// A plugin providing `parse_instruction` is a parser for `program_ids` and must
+/// provide `free_buffer`; a plugin providing `write_event` is a sink. A plugin may
+/// be both.
+#[repr(C)]
+pub struct PluginDeclaration {
+    pub abi_version: u32,
+    /// NUL terminated plugin name
+    pub name: *const c_char,
+    pub program_ids: *const [u8; 32],
+    pub program_ids_len: usize,
+    pub parse_instruction: Option<ParseInstructionFn>,
+    pub write_event: Option<WriteEventFn>,
+    pub flush: Option<FlushFn>,
+    pub free_buffer: Option<FreeBufferFn>,
+}
+
+// 声明中的指针只指向插件内的静态数据
+unsafe impl Sync for PluginDeclaration {}
@@ -0,0 +1,458 @@
+pub mod abi;
+
+use prost_types::Timestamp;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::UiCompiledInstruction;
+use std::ffi::CStr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::common::AnyResult;
+use crate::impl_unified_event;
+use crate::streaming::event_parser::{
+    common::{
+        validate_account_indices, EventMetadata, EventType, InstructionIndex, ProtocolType,
+    },
+    core::traits::EventParser,
+    UnifiedEvent,
+};
+use crate::streaming::sink::EventSink;
+
+use abi::{
+    PluginBuffer, PluginDeclaration, PLUGIN_ABI_VERSION, PLUGIN_DECLARATION_SYMBOL, PLUGIN_OK,
+};
+
+/// 插件加载或调用失败的原因
+#[derive(Debug, thiserror::Error)]
+pub enum PluginError {
+    #[error("failed to load plugin library: {0}")]
+    Load(#[from] libloading::Error),
+    #[error("plugin ABI version {found} is not supported (expected {expected})")]
+    AbiMismatch { expected: u32, found: u32 },
+    #[error("plugin declaration is invalid: {0}")]
+    InvalidDeclaration(&'static str),
+    #[error("plugin {plugin} does not provide {capability}")]
+    Unsupported {
+        plugin: String,
+        capability: &'static str,
+    },
+    #[error("plugin {plugin} returned error code {code}")]
+    Call { plugin: String, code: i32 },
+    #[error("plugin {plugin} returned invalid output: {reason}")]
+    InvalidOutput { plugin: String, reason: String },
+}
+
+/// 插件解析出的事件
+///
+/// Plugin events carry the plugin's event name and its JSON fields; match them
+/// by `plugin` and `name` rather than by downcasting to a concrete type.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PluginEvent {
+    pub metadata: EventMetadata,
+    pub plugin: String,
+    pub name: String,
+    pub fields: Value,
+}
+
+impl_unified_event!(PluginEvent,);
+
+#[derive(Deserialize)]
+struct PluginOutputEvent {
+    name: String,
+    #[serde(default)]
+    fields: Value,
+}
+
+/// 已加载的插件
+///
+/// Keeps its library loaded for as long as the plugin, or any parser or sink
+/// created from it, is alive.
+pub struct Plugin {
+    name: String,
+    path: PathBuf,
+    program_ids: Vec<Pubkey>,
+    declaration: *const PluginDeclaration,
+    _library: libloading::Library,
+}
+
+// 声明指向库内的静态数据，库与Plugin同生命周期；插件函数必须是线程安全的
+unsafe impl Send for Plugin {}
+unsafe impl Sync for Plugin {}
+
+impl std::fmt::Debug for Plugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Plugin")
+            .field("name", &self.name)
+            .field("path", &self.path)
+            .field("program_ids", &self.program_ids)
+            .finish()
+    }
+}
+
+impl Plugin {
+    /// Load a plugin library and validate its declaration
+    ///
+    /// # Safety
+    /// Loading a library runs its initialisers, and the declared functions are
+    /// called as declared; only load plugins from trusted locations.
+    pub unsafe fn load(path: impl AsRef<Path>) -> Result<Arc<Self>, PluginError> {
+        let path = path.as_ref().to_path_buf();
+        let library = libloading::Library::new(&path)?;
+        let declaration: *const PluginDeclaration =
+            *library.get::<*const PluginDeclaration>(PLUGIN_DECLARATION_SYMBOL)?;
+        let decl = declaration
+            .as_ref()
+            .ok_or(PluginError::InvalidDeclaration("null declaration"))?;
+        if decl.abi_version != PLUGIN_ABI_VERSION {
+            return Err(PluginError::AbiMismatch {
+                expected: PLUGIN_ABI_VERSION,
+                found: decl.abi_version,
+            });
+        }
+        if decl.name.is_null() {
+            return Err(PluginError::InvalidDeclaration("missing name"));
+        }
+        let name = CStr::from_ptr(decl.name).to_string_lossy().into_owned();
+        if decl.parse_instruction.is_some() && decl.free_buffer.is_none() {
+            return Err(PluginError::InvalidDeclaration(
+                "parser without free_buffer",
+            ));
+        }
+        let program_ids = if decl.program_ids.is_null() || decl.program_ids_len == 0 {
+            vec![]
+        } else {
+            std::slice::from_raw_parts(decl.program_ids, decl.program_ids_len)
+                .iter()
+                .map(|bytes| Pubkey::new_from_array(*bytes))
+                .collect()
+        };
+        Ok(Arc::new(Self {
+            name,
+            path,
+            program_ids,
+            declaration,
+            _library: library,
+        }))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Programs the plugin parses
+    pub fn program_ids(&self) -> &[Pubkey] {
+        &self.program_ids
+    }
+
+    pub fn is_parser(&self) -> bool {
+        self.declaration().parse_instruction.is_some()
+    }
+
+    pub fn is_sink(&self) -> bool {
+        self.declaration().write_event.is_some()
+    }
+
+    fn declaration(&self) -> &PluginDeclaration {
+        // 在load中已校验非空
+        unsafe { &*self.declaration }
+    }
+
+    fn check(&self, code: i32) -> Result<(), PluginError> {
+        if code == PLUGIN_OK {
+            Ok(())
+        } else {
+            Err(PluginError::Call {
+                plugin: self.name.clone(),
+                code,
+            })
+        }
+    }
+
+    fn unsupported(&self, capability: &'static str) -> PluginError {
+        PluginError::Unsupported {
+            plugin: self.name.clone(),
+            capability,
+        }
+    }
+
+    /// Parse one instruction into `(event name, fields)` pairs
+    pub fn parse_instruction(
+        &self,
+        program_id: &Pubkey,
+        data: &[u8],
+        accounts: &[Pubkey],
+    ) -> Result<Vec<(String, Value)>, PluginError> {
+        let decl = self.declaration();
+        let parse = decl
+            .parse_instruction
+            .ok_or_else(|| self.unsupported("parse_instruction"))?;
+        let accounts: Vec<[u8; 32]> = accounts.iter().map(|key| key.to_bytes()).collect();
+        let mut out = PluginBuffer::empty();
+        let code = unsafe {
+            parse(
+                &program_id.to_bytes(),
+                data.as_ptr(),
+                data.len(),
+                accounts.as_ptr(),
+                accounts.len(),
+                &mut out,
+            )
+        };
+        let bytes = if out.ptr.is_null() {
+            Vec::new()
+        } else {
+            let bytes = unsafe { std::slice::from_raw_parts(out.ptr, out.len) }.to_vec();
+            if let Some(free) = decl.free_buffer {
+                unsafe { free(out) };
+            }
+            bytes
+        };
+        self.check(code)?;
+        if bytes.is_empty() {
+            return Ok(vec![]);
+        }
+        let events: Vec<PluginOutputEvent> =
+            serde_json::from_slice(&bytes).map_err(|e| PluginError::InvalidOutput {
+                plugin: self.name.clone(),
+                reason: e.to_string(),
+            })?;
+        Ok(events.into_iter().map(|e| (e.name, e.fields)).collect())
+    }
+
+    /// Hand one event to the plugin's sink
+    pub fn write_event(&self, event_type: &str, fields: &Value) -> Result<(), PluginError> {
+        let write = self
+            .declaration()
+            .write_event
+            .ok_or_else(|| self.unsupported("write_event"))?;
+        let json = serde_json::to_vec(fields).map_err(|e| PluginError::InvalidOutput {
+            plugin: self.name.clone(),
+            reason: e.to_string(),
+        })?;
+        let code = unsafe {
+            write(
+                event_type.as_ptr(),
+                event_type.len(),
+                json.as_ptr(),
+                json.len(),
+            )
+        };
+        self.check(code)
+    }
+
+    pub fn flush(&self) -> Result<(), PluginError> {
+        match self.declaration().flush {
+            Some(flush) => self.check(unsafe { flush() }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// 插件目录扫描结果
+#[derive(Debug, Default)]
+pub struct PluginSet {
+    pub plugins: Vec<Arc<Plugin>>,
+    /// Libraries in the directory that failed to load
+    pub failed: Vec<(PathBuf, PluginError)>,
+}
+
+impl PluginSet {
+    /// Load every shared library (`.so`, `.dylib` or `.dll` for the platform) in `dir`
+    ///
+    /// A library that fails to load is recorded in `failed` and does not stop the
+    /// others from loading.
+    ///
+    /// # Safety
+    /// See [`Plugin::load`].
+    pub unsafe fn discover(dir: impl AsRef<Path>) -> AnyResult<Self> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && path.extension().and_then(|ext| ext.to_str())
+                        == Some(std::env::consts::DLL_EXTENSION)
+            })
+            .collect();
+        paths.sort();
+        let mut set = Self::default();
+        for path in paths {
+            match Plugin::load(&path) {
+                Ok(plugin) => set.plugins.push(plugin),
+                Err(e) => {
+                    log::warn!("Failed to load plugin {}: {}", path.display(), e);
+                    set.failed.push((path, e));
+                }
+            }
+        }
+        Ok(set)
+    }
+
+    /// Event parsers of all parser plugins
+    pub fn parsers(&self) -> Vec<Box<dyn EventParser>> {
+        self.plugins
+            .iter()
+            .filter(|plugin| plugin.is_parser())
+            .map(|plugin| Box::new(PluginParser::new(plugin.clone())) as Box<dyn EventParser>)
+            .collect()
+    }
+
+    /// Sinks of all sink plugins
+    pub fn sinks(&self) -> Vec<PluginSink> {
+        self.plugins
+            .iter()
+            .filter(|plugin| plugin.is_sink())
+            .map(|plugin| PluginSink::new(plugin.clone()))
+            .collect()
+    }
+}
+
+/// 插件事件解析器
+///
+/// Parses the outer and CPI instructions of the plugin's programs into
+/// [`PluginEvent`]s. Plugins receive the instruction data and the instruction's
+/// account keys; log and self-CPI event data is not passed to plugins.
+pub struct PluginParser {
+    plugin: Arc<Plugin>,
+}
+
+impl PluginParser {
+    pub fn new(plugin: Arc<Plugin>) -> Self {
+        Self { plugin }
+    }
+
+    pub fn plugin(&self) -> &Arc<Plugin> {
+        &self.plugin
+    }
+}
+
+#[async_trait::async_trait]
+impl EventParser for PluginParser {
+    fn parse_events_from_inner_instruction(
+        &self,
+        _inner_instruction: &UiCompiledInstruction,
+        _signature: &str,
+        _slot: u64,
+        _block_time: Option<Timestamp>,
+        _program_received_time_ms: i64,
+        _index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        Vec::new()
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        instruction: &CompiledInstruction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        let Some(program_id) = accounts.get(instruction.program_id_index as usize) else {
+            return Vec::new();
+        };
+        if !self.should_handle(program_id)
+            || !validate_account_indices(&instruction.accounts, accounts.len())
+        {
+            return Vec::new();
+        }
+        let account_pubkeys: Vec<Pubkey> = instruction
+            .accounts
+            .iter()
+            .map(|&idx| accounts[idx as usize])
+            .collect();
+        let events =
+            match self
+                .plugin
+                .parse_instruction(program_id, &instruction.data, &account_pubkeys)
+            {
+                Ok(events) => events,
+                Err(e) => {
+                    log::debug!("Plugin parse of {} failed: {}", signature, e);
+                    return Vec::new();
+                }
+            };
+        let timestamp = block_time.unwrap_or(Timestamp {
+            seconds: 0,
+            nanos: 0,
+        });
+        let block_time_ms = timestamp.seconds * 1000 + (timestamp.nanos as i64) / 1_000_000;
+        events
+            .into_iter()
+            .map(|(name, fields)| {
+                let mut metadata = EventMetadata::new(
+                    signature.to_string(),
+                    signature.to_string(),
+                    slot,
+                    timestamp.seconds,
+                    block_time_ms,
+                    ProtocolType::Plugin,
+                    EventType::Plugin,
+                    *program_id,
+                    index,
+                    program_received_time_ms,
+                );
+                // 同一指令可产生多个插件事件，按名称区分
+                metadata.set_id(format!("{}-{}", index, name));
+                Box::new(PluginEvent {
+                    metadata,
+                    plugin: self.plugin.name().to_string(),
+                    name,
+                    fields,
+                }) as Box<dyn UnifiedEvent>
+            })
+            .collect()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        ProtocolType::Plugin
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        self.plugin
+            .program_ids()
+            .first()
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.plugin.program_ids().contains(program_id)
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        self.plugin.program_ids().to_vec()
+    }
+}
+
+/// 插件输出端
+pub struct PluginSink {
+    plugin: Arc<Plugin>,
+}
+
+impl PluginSink {
+    pub fn new(plugin: Arc<Plugin>) -> Self {
+        Self { plugin }
+    }
+}
+
+impl EventSink for PluginSink {
+    fn name(&self) -> &str {
+        self.plugin.name()
+    }
+
+    fn write(&self, event_type: &str, fields: &Value) -> AnyResult<()> {
+        Ok(self.plugin.write_event(event_type, fields)?)
+    }
+
+    fn flush(&self) -> AnyResult<()> {
+        Ok(self.plugin.flush()?)
+    }
+}
@@ -0,0 +1,171 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+use crate::streaming::replay::ArchivedTransaction;
+
+/// 回放断点
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Pause before the first transaction at or after this slot
+    Slot(u64),
+    /// Pause before the transaction with this signature
+    Signature(String),
+}
+
+impl Breakpoint {
+    fn hit(&self, previous_slot: Option<u64>, tx: &ArchivedTransaction) -> bool {
+        match self {
+            // 只在跨过断点slot的第一笔交易处暂停
+            Breakpoint::Slot(slot) => {
+                tx.slot >= *slot && previous_slot.map_or(true, |previous| previous < *slot)
+            }
+            Breakpoint::Signature(signature) => tx.signature == *signature,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ControlState {
+    paused: AtomicBool,
+    single_step: AtomicBool,
+    // 暂停状态下允许继续处理的交易数
+    steps: AtomicU64,
+    speed: Mutex<Option<f64>>,
+    breakpoints: Mutex<Vec<Breakpoint>>,
+    position: Mutex<Option<(u64, String)>>,
+    notify: Notify,
+}
+
+/// 回放控制句柄
+///
+/// Cloned handles control the same replay, so a debugger UI or test can pause,
+/// step and change speed while `ReplayEngine::run` is executing.
+#[derive(Clone, Default)]
+pub struct ReplayControl {
+    state: Arc<ControlState>,
+}
+
+impl ReplayControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Speed multiplier of the original pace; `None` replays as fast as possible
+    pub fn speed(&self) -> Option<f64> {
+        *self.state.speed.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set_speed(&self, speed: Option<f64>) {
+        *self.state.speed.lock().unwrap_or_else(|e| e.into_inner()) =
+            speed.filter(|speed| *speed > 0.0);
+    }
+
+    pub fn pause(&self) {
+        self.state.paused.store(true, Ordering::Release);
+    }
+
+    pub fn resume(&self) {
+        self.state.paused.store(false, Ordering::Release);
+        self.state.notify.notify_waiters();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.paused.load(Ordering::Acquire)
+    }
+
+    /// Process one more transaction while paused
+    pub fn step(&self) {
+        self.state.steps.fetch_add(1, Ordering::AcqRel);
+        self.state.notify.notify_waiters();
+    }
+
+    /// Pause after every transaction
+    pub fn set_single_step(&self, enabled: bool) {
+        self.state.single_step.store(enabled, Ordering::Release);
+        if enabled {
+            self.pause();
+        }
+    }
+
+    pub fn add_breakpoint(&self, breakpoint: Breakpoint) {
+        self.state
+            .breakpoints
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(breakpoint);
+    }
+
+    pub fn remove_breakpoint(&self, breakpoint: &Breakpoint) {
+        self.state
+            .breakpoints
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|b| b != breakpoint);
+    }
+
+    pub fn clear_breakpoints(&self) {
+        self.state
+            .breakpoints
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Slot and signature of the last replayed transaction
+    pub fn position(&self) -> Option<(u64, String)> {
+        self.state
+            .position
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Called before a transaction is replayed; waits while paused
+    pub(crate) async fn before(&self, tx: &ArchivedTransaction) {
+        let previous_slot = self.position().map(|(slot, _)| slot);
+        let hit = self
+            .state
+            .breakpoints
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .any(|breakpoint| breakpoint.hit(previous_slot, tx));
+        if hit {
+            log::info!(
+                "Replay breakpoint hit at slot {} ({})",
+                tx.slot,
+                tx.signature
+            );
+            self.pause();
+        }
+        loop {
+            let notified = self.state.notify.notified();
+            if !self.is_paused() || self.take_step() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Called after a transaction was replayed
+    pub(crate) fn after(&self, tx: &ArchivedTransaction) {
+        *self
+            .state
+            .position
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some((tx.slot, tx.signature.clone()));
+        if self.state.single_step.load(Ordering::Acquire) {
+            self.pause();
+        }
+    }
+
+    fn take_step(&self) -> bool {
+        self.state
+            .steps
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |steps| {
+                steps.checked_sub(1)
+            })
+            .is_ok()
+    }
+}
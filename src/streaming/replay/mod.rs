@@ -0,0 +1,182 @@
+pub mod control;
+pub mod source;
+
+pub use control::{Breakpoint, ReplayControl};
+
+#[cfg(feature = "rpc")]
+pub use source::RpcBlockSource;
+pub use source::{ArchivedTransaction, FixtureSource, ReplaySource};
+
+use prost_types::Timestamp;
+use solana_sdk::pubkey::Pubkey;
+use std::time::Duration;
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::{
+    common::{FailedTxPolicy, ParseOptions},
+    core::post_process::PostProcessorChain,
+    EventParserFactory, Protocol, UnifiedEvent,
+};
+
+/// Slot时长，区块时间缺失时用于估算回放间隔
+const SLOT_DURATION_MS: u64 = 400;
+
+/// 回放统计
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayStats {
+    pub transactions: u64,
+    pub events: u64,
+    pub parse_errors: u64,
+    pub first_slot: Option<u64>,
+    pub last_slot: Option<u64>,
+}
+
+/// 回放引擎 - 将归档交易按原始节奏推送给解析器
+///
+/// Archived transactions go through the same `parse_transaction` path as the live
+/// gRPC stream, so a strategy backtested on a replay sees exactly the events it
+/// would have seen live. `speed` scales the original gaps between blocks (1.0 is
+/// real time); `None` replays as fast as possible. A [`ReplayControl`] handle
+/// changes the speed, pauses at breakpoints and single-steps while running.
+pub struct ReplayEngine {
+    protocols: Vec<Protocol>,
+    bot_wallet: Option<Pubkey>,
+    parse_options: ParseOptions,
+    control: ReplayControl,
+}
+
+impl ReplayEngine {
+    pub fn new(protocols: Vec<Protocol>) -> Self {
+        Self {
+            protocols,
+            bot_wallet: None,
+            parse_options: ParseOptions::default(),
+            control: ReplayControl::new(),
+        }
+    }
+
+    pub fn with_bot_wallet(mut self, bot_wallet: Pubkey) -> Self {
+        self.bot_wallet = Some(bot_wallet);
+        self
+    }
+
+    /// Set how failed transactions are handled (skipped by default)
+    pub fn with_failed_tx_policy(mut self, failed_tx_policy: FailedTxPolicy) -> Self {
+        self.parse_options.failed_tx_policy = failed_tx_policy;
+        self
+    }
+
+    /// Attach the raw instruction data, accounts and stack height to every event
+    pub fn with_include_raw(mut self, include_raw: bool) -> Self {
+        self.parse_options.include_raw = include_raw;
+        self
+    }
+
+    /// Post-processors run on each transaction's events, replacing the default
+    /// dev/bot tagging chain
+    pub fn with_post_processors(mut self, post_processors: PostProcessorChain) -> Self {
+        self.parse_options.post_processors = post_processors;
+        self
+    }
+
+    /// Replay at `speed` times the original pace
+    pub fn with_speed(self, speed: f64) -> Self {
+        self.control.set_speed(Some(speed));
+        self
+    }
+
+    /// Replay without waiting between blocks
+    pub fn as_fast_as_possible(self) -> Self {
+        self.control.set_speed(None);
+        self
+    }
+
+    /// Share a control handle, e.g. to set breakpoints before the replay starts
+    pub fn with_control(mut self, control: ReplayControl) -> Self {
+        self.control = control;
+        self
+    }
+
+    pub fn control(&self) -> &ReplayControl {
+        &self.control
+    }
+
+    pub async fn run<S, F>(&self, mut source: S, callback: F) -> AnyResult<ReplayStats>
+    where
+        S: ReplaySource,
+        F: Fn(Box<dyn UnifiedEvent>) + Send + Sync,
+    {
+        let mut stats = ReplayStats::default();
+        let mut previous: Option<(u64, Option<i64>)> = None;
+        while let Some(tx) = source.next().await? {
+            self.control.before(&tx).await;
+            if let Some(delay) = self.delay(previous, &tx) {
+                tokio::time::sleep(delay).await;
+            }
+            previous = Some((tx.slot, tx.block_time));
+            stats.first_slot.get_or_insert(tx.slot);
+            stats.last_slot = Some(tx.slot);
+            stats.transactions += 1;
+            match self.parse(tx.clone()).await {
+                Ok(events) => {
+                    stats.events += events.len() as u64;
+                    for event in events {
+                        callback(event);
+                    }
+                }
+                Err(_) => stats.parse_errors += 1,
+            }
+            self.control.after(&tx);
+        }
+        Ok(stats)
+    }
+
+    /// Parse one archived transaction with every configured protocol
+    pub async fn parse(&self, tx: ArchivedTransaction) -> AnyResult<Vec<Box<dyn UnifiedEvent>>> {
+        let program_received_time_ms = chrono::Utc::now().timestamp_millis();
+        let block_time = tx.block_time.map(|seconds| Timestamp { seconds, nanos: 0 });
+        let mut events = vec![];
+        for protocol in &self.protocols {
+            let parser = EventParserFactory::create_parser(protocol.clone());
+            events.extend(
+                parser
+                    .parse_transaction_with_errors(
+                        tx.tx.clone(),
+                        &tx.signature,
+                        Some(tx.slot),
+                        block_time,
+                        program_received_time_ms,
+                        self.bot_wallet,
+                        self.parse_options.clone(),
+                    )
+                    .await?
+                    .events,
+            );
+        }
+        if let Some(tx_index) = tx.tx_index {
+            for event in &mut events {
+                event.metadata_mut().tx_index = Some(tx_index);
+            }
+        }
+        Ok(events)
+    }
+
+    fn delay(
+        &self,
+        previous: Option<(u64, Option<i64>)>,
+        tx: &ArchivedTransaction,
+    ) -> Option<Duration> {
+        let speed = self.control.speed()?;
+        let (previous_slot, previous_time) = previous?;
+        let gap_ms = match (previous_time, tx.block_time) {
+            (Some(previous_time), Some(block_time)) => {
+                block_time.saturating_sub(previous_time).max(0) as u64 * 1000
+            }
+            _ => tx.slot.saturating_sub(previous_slot) * SLOT_DURATION_MS,
+        };
+        if gap_ms == 0 {
+            return None;
+        }
+        Some(Duration::from_secs_f64(gap_ms as f64 / 1000.0 / speed))
+    }
+}
@@ -0,0 +1,267 @@
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransactionWithStatusMeta,
+};
+use std::collections::VecDeque;
+use std::path::Path;
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::common::decode_transaction;
+
+/// 归档的交易
+#[derive(Debug, Clone)]
+pub struct ArchivedTransaction {
+    pub signature: String,
+    pub slot: u64,
+    /// Block time in seconds
+    pub block_time: Option<i64>,
+    /// Index of the transaction within its block, if known
+    pub tx_index: Option<u64>,
+    pub tx: EncodedTransactionWithStatusMeta,
+}
+
+impl ArchivedTransaction {
+    pub fn from_confirmed(confirmed: EncodedConfirmedTransactionWithStatusMeta) -> Self {
+        let signature = decode_transaction(&confirmed.transaction.transaction)
+            .and_then(|tx| tx.signatures.first().map(|s| s.to_string()))
+            .unwrap_or_default();
+        Self {
+            signature,
+            slot: confirmed.slot,
+            block_time: confirmed.block_time,
+            tx_index: None,
+            tx: confirmed.transaction,
+        }
+    }
+}
+
+/// 回放数据源
+///
+/// Yields archived transactions in replay order. Other archive formats (e.g. Parquet
+/// exports) plug in by implementing this trait.
+#[async_trait::async_trait]
+pub trait ReplaySource: Send {
+    async fn next(&mut self) -> AnyResult<Option<ArchivedTransaction>>;
+}
+
+/// JSON fixture数据源
+///
+/// Loads `EncodedConfirmedTransactionWithStatusMeta` documents, as written by the
+/// parser tests and `getTransaction`, from `.json` files (one transaction or an
+/// array) and `.jsonl` files (one transaction per line). Transactions are replayed
+/// in slot order.
+#[derive(Debug, Default)]
+pub struct FixtureSource {
+    transactions: VecDeque<ArchivedTransaction>,
+}
+
+impl FixtureSource {
+    pub fn from_transactions(mut transactions: Vec<ArchivedTransaction>) -> Self {
+        transactions.sort_by_key(|tx| tx.slot);
+        Self {
+            transactions: transactions.into(),
+        }
+    }
+
+    /// Load a fixture file, or every `.json`/`.jsonl` file of a directory
+    pub fn load(path: impl AsRef<Path>) -> AnyResult<Self> {
+        let path = path.as_ref();
+        let mut transactions = vec![];
+        if path.is_dir() {
+            let mut files: Vec<_> = std::fs::read_dir(path)?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| {
+                    matches!(
+                        p.extension().and_then(|e| e.to_str()),
+                        Some("json" | "jsonl")
+                    )
+                })
+                .collect();
+            files.sort();
+            for file in files {
+                transactions.extend(Self::load_file(&file)?);
+            }
+        } else {
+            transactions = Self::load_file(path)?;
+        }
+        Ok(Self::from_transactions(transactions))
+    }
+
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    fn load_file(path: &Path) -> AnyResult<Vec<ArchivedTransaction>> {
+        let data = std::fs::read_to_string(path)?;
+        let confirmed: Vec<EncodedConfirmedTransactionWithStatusMeta> =
+            if path.extension().and_then(|e| e.to_str()) == Some("jsonl") {
+                data.lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(serde_json::from_str)
+                    .collect::<Result<_, _>>()?
+            } else if data.trim_start().starts_with('[') {
+                serde_json::from_str(&data)?
+            } else {
+                vec![serde_json::from_str(&data)?]
+            };
+        Ok(confirmed
+            .into_iter()
+            .map(ArchivedTransaction::from_confirmed)
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl ReplaySource for FixtureSource {
+    async fn next(&mut self) -> AnyResult<Option<ArchivedTransaction>> {
+        Ok(self.transactions.pop_front())
+    }
+}
+
+/// Attempts to fetch a block the node reports as not available yet
+#[cfg(feature = "rpc")]
+pub const BLOCK_NOT_AVAILABLE_ATTEMPTS: u32 = 5;
+/// Delay before the first retry of a block that is not available, doubled per retry
+#[cfg(feature = "rpc")]
+const BLOCK_NOT_AVAILABLE_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(400);
+
+/// RPC slot区间数据源 - 通过getBlock逐个slot拉取交易
+#[cfg(feature = "rpc")]
+pub struct RpcBlockSource {
+    client: std::sync::Arc<crate::common::SolanaRpcClient>,
+    next_slot: u64,
+    end_slot: u64,
+    buffer: VecDeque<ArchivedTransaction>,
+}
+
+#[cfg(feature = "rpc")]
+impl RpcBlockSource {
+    /// Replay the inclusive slot range `start_slot..=end_slot`
+    pub fn new(
+        client: std::sync::Arc<crate::common::SolanaRpcClient>,
+        start_slot: u64,
+        end_slot: u64,
+    ) -> Self {
+        Self {
+            client,
+            next_slot: start_slot,
+            end_slot,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    /// Next slot that has not been fetched yet
+    pub fn next_slot(&self) -> u64 {
+        self.next_slot
+    }
+
+    /// Fetch all transactions of a block; skipped slots yield an empty list
+    ///
+    /// A block the node doesn't have yet (not confirmed, or not in its ledger) is
+    /// retried with backoff and then returned as an error, so callers never mistake
+    /// it for a skipped slot and move past transactions they never saw.
+    pub async fn fetch_block(
+        client: &crate::common::SolanaRpcClient,
+        slot: u64,
+    ) -> AnyResult<Vec<ArchivedTransaction>> {
+        use solana_client::rpc_config::RpcBlockConfig;
+        use solana_sdk::commitment_config::CommitmentConfig;
+        use solana_transaction_status::{TransactionDetails, UiTransactionEncoding};
+
+        let config = RpcBlockConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            transaction_details: Some(TransactionDetails::Full),
+            rewards: Some(false),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
+        let mut delay = BLOCK_NOT_AVAILABLE_RETRY_DELAY;
+        let mut attempt = 1;
+        let block = loop {
+            match client.get_block_with_config(slot, config).await {
+                Ok(block) => break block,
+                Err(e) if Self::is_skipped_slot(&e) => return Ok(vec![]),
+                Err(e)
+                    if Self::is_block_not_available(&e)
+                        && attempt < BLOCK_NOT_AVAILABLE_ATTEMPTS =>
+                {
+                    log::debug!("block {} not available yet, retrying in {:?}", slot, delay);
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    return Err(anyhow::Error::from(e)
+                        .context(format!("failed to fetch block {}", slot)))
+                }
+            }
+        };
+        Ok(block
+            .transactions
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(tx_index, tx)| {
+                let signature = decode_transaction(&tx.transaction)
+                    .and_then(|t| t.signatures.first().map(|s| s.to_string()))
+                    .unwrap_or_default();
+                ArchivedTransaction {
+                    signature,
+                    slot,
+                    block_time: block.block_time,
+                    tx_index: Some(tx_index as u64),
+                    tx,
+                }
+            })
+            .collect())
+    }
+
+    /// Whether the error says the leader skipped the slot, so it has no block
+    pub fn is_skipped_slot(error: &solana_client::client_error::ClientError) -> bool {
+        use solana_rpc_client_api::custom_error::{
+            JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_SLOT_SKIPPED,
+            JSON_RPC_SERVER_ERROR_SLOT_SKIPPED,
+        };
+        matches!(
+            Self::rpc_error_code(error),
+            Some(code) if code == JSON_RPC_SERVER_ERROR_SLOT_SKIPPED
+                || code == JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_SLOT_SKIPPED
+        )
+    }
+
+    /// Whether the error says the node doesn't have the block (yet); the slot may
+    /// still have transactions
+    pub fn is_block_not_available(error: &solana_client::client_error::ClientError) -> bool {
+        use solana_rpc_client_api::custom_error::JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE;
+        Self::rpc_error_code(error) == Some(JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE)
+    }
+
+    fn rpc_error_code(error: &solana_client::client_error::ClientError) -> Option<i64> {
+        use solana_client::client_error::ClientErrorKind;
+        use solana_rpc_client_api::request::RpcError;
+        match error.kind() {
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => Some(*code),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "rpc")]
+#[async_trait::async_trait]
+impl ReplaySource for RpcBlockSource {
+    async fn next(&mut self) -> AnyResult<Option<ArchivedTransaction>> {
+        while self.buffer.is_empty() {
+            if self.next_slot > self.end_slot {
+                return Ok(None);
+            }
+            let slot = self.next_slot;
+            self.buffer
+                .extend(Self::fetch_block(&self.client, slot).await?);
+            self.next_slot += 1;
+        }
+        Ok(self.buffer.pop_front())
+    }
+}
@@ -0,0 +1,282 @@
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{fence, AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::Thread;
+
+/// Default number of slots of the parser ring buffer
+pub const DEFAULT_RING_CAPACITY: usize = 4096;
+/// Default busy-wait iterations before an idle consumer yields its time slice
+pub const DEFAULT_SPIN_LIMIT: u32 = 1_000;
+/// Time slices an idle consumer yields after spinning before it parks its thread
+const YIELD_LIMIT: u32 = 100;
+
+/// 独占一条缓存行，避免生产者和消费者的索引伪共享
+#[repr(align(64))]
+struct CachePadded<T>(T);
+
+struct Ring<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    mask: usize,
+    // 下一个读取位置，只由消费者写入
+    head: CachePadded<AtomicUsize>,
+    // 下一个写入位置，只由生产者写入
+    tail: CachePadded<AtomicUsize>,
+    closed: AtomicBool,
+    // 消费者线程是否已休眠，生产者写入后需要唤醒
+    parked: AtomicBool,
+    waiter: Mutex<Option<Thread>>,
+}
+
+impl<T> Ring<T> {
+    /// Unpark the consumer if it sleeps; called after publishing a value or closing
+    fn wake(&self) {
+        // 与消费者休眠前的fence配对：要么生产者看到parked，要么消费者看到新的tail
+        fence(Ordering::SeqCst);
+        if self.parked.load(Ordering::Relaxed) {
+            if let Some(thread) = self
+                .waiter
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .as_ref()
+            {
+                thread.unpark();
+            }
+        }
+    }
+}
+
+// SAFETY: a slot is only accessed by the producer while it is outside
+// `head..tail` and by the consumer while it is inside, and the Release/Acquire
+// pairs on `head` and `tail` order these accesses.
+unsafe impl<T: Send> Sync for Ring<T> {}
+unsafe impl<T: Send> Send for Ring<T> {}
+
+impl<T> Drop for Ring<T> {
+    fn drop(&mut self) {
+        let tail = *self.tail.0.get_mut();
+        let mut head = *self.head.0.get_mut();
+        while head != tail {
+            // SAFETY: slots in head..tail hold initialized values
+            unsafe { self.slots[head & self.mask].get_mut().assume_init_drop() };
+            head = head.wrapping_add(1);
+        }
+    }
+}
+
+/// 创建单生产者单消费者环形缓冲区
+///
+/// The capacity is rounded up to a power of two. Both ends are lock-free and never
+/// touch the async runtime, so a value handed from the gRPC reader to a dedicated
+/// parser thread costs no channel wakeup.
+pub fn ring_buffer<T>(capacity: usize) -> (RingProducer<T>, RingConsumer<T>) {
+    let capacity = capacity.max(2).next_power_of_two();
+    let ring = Arc::new(Ring {
+        slots: (0..capacity)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect(),
+        mask: capacity - 1,
+        head: CachePadded(AtomicUsize::new(0)),
+        tail: CachePadded(AtomicUsize::new(0)),
+        closed: AtomicBool::new(false),
+        parked: AtomicBool::new(false),
+        waiter: Mutex::new(None),
+    });
+    (
+        RingProducer {
+            ring: ring.clone(),
+            cached_head: 0,
+        },
+        RingConsumer {
+            ring,
+            cached_tail: 0,
+        },
+    )
+}
+
+/// 环形缓冲区的写入端
+pub struct RingProducer<T> {
+    ring: Arc<Ring<T>>,
+    // 上次读到的消费者位置，减少跨核读取
+    cached_head: usize,
+}
+
+impl<T> RingProducer<T> {
+    /// Add a value, handing it back if the ring is full
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        let ring = &*self.ring;
+        let tail = ring.tail.0.load(Ordering::Relaxed);
+        if tail.wrapping_sub(self.cached_head) > ring.mask {
+            self.cached_head = ring.head.0.load(Ordering::Acquire);
+            if tail.wrapping_sub(self.cached_head) > ring.mask {
+                return Err(value);
+            }
+        }
+        // SAFETY: the slot is outside head..tail, so the consumer doesn't read it
+        unsafe { (*ring.slots[tail & ring.mask].get()).write(value) };
+        ring.tail.0.store(tail.wrapping_add(1), Ordering::Release);
+        ring.wake();
+        Ok(())
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.ring.mask + 1
+    }
+
+    /// Values waiting to be consumed
+    pub fn len(&self) -> usize {
+        let tail = self.ring.tail.0.load(Ordering::Relaxed);
+        tail.wrapping_sub(self.ring.head.0.load(Ordering::Acquire))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether the consumer was dropped
+    pub fn is_closed(&self) -> bool {
+        self.ring.closed.load(Ordering::Acquire)
+    }
+
+    /// Whether the consumer's thread is parked in `pop_wait`
+    pub fn is_consumer_parked(&self) -> bool {
+        self.ring.parked.load(Ordering::Acquire)
+    }
+}
+
+impl<T> Drop for RingProducer<T> {
+    fn drop(&mut self) {
+        self.ring.closed.store(true, Ordering::Release);
+        self.ring.wake();
+    }
+}
+
+/// 环形缓冲区的读取端
+pub struct RingConsumer<T> {
+    ring: Arc<Ring<T>>,
+    // 上次读到的生产者位置
+    cached_tail: usize,
+}
+
+impl<T> RingConsumer<T> {
+    /// Take the oldest value, None if the ring is empty
+    pub fn pop(&mut self) -> Option<T> {
+        let ring = &*self.ring;
+        let head = ring.head.0.load(Ordering::Relaxed);
+        if head == self.cached_tail {
+            self.cached_tail = ring.tail.0.load(Ordering::Acquire);
+            if head == self.cached_tail {
+                return None;
+            }
+        }
+        // SAFETY: the slot is inside head..tail, so the producer initialized it
+        // and doesn't write it until head moves past
+        let value = unsafe { (*ring.slots[head & ring.mask].get()).assume_init_read() };
+        ring.head.0.store(head.wrapping_add(1), Ordering::Release);
+        Some(value)
+    }
+
+    /// Wait for the next value, None once the producer is dropped and the ring is
+    /// drained
+    ///
+    /// Busy-waits `spin_limit` iterations, so a consumer on a pinned core picks
+    /// values up within nanoseconds, then yields its time slice for a while and
+    /// finally parks the thread until the producer pushes or is dropped, so an idle
+    /// stream doesn't keep a core busy.
+    pub fn pop_wait(&mut self, spin_limit: u32) -> Option<T> {
+        let mut idle: u32 = 0;
+        loop {
+            if let Some(value) = self.pop() {
+                return Some(value);
+            }
+            if self.ring.closed.load(Ordering::Acquire) {
+                // 关闭前写入的值
+                return self.pop();
+            }
+            if idle < spin_limit {
+                std::hint::spin_loop();
+            } else if idle < spin_limit.saturating_add(YIELD_LIMIT) {
+                std::thread::yield_now();
+            } else {
+                self.park();
+            }
+            idle = idle.saturating_add(1);
+        }
+    }
+
+    /// Sleep until the producer wakes the thread; returns early if a value or the
+    /// close arrived while registering
+    fn park(&self) {
+        let ring = &*self.ring;
+        *ring.waiter.lock().unwrap_or_else(|e| e.into_inner()) = Some(std::thread::current());
+        ring.parked.store(true, Ordering::Release);
+        fence(Ordering::SeqCst);
+        // 设置标志后再检查一次，避免错过休眠前的写入
+        let ready = ring.tail.0.load(Ordering::Relaxed) != ring.head.0.load(Ordering::Relaxed)
+            || ring.closed.load(Ordering::Relaxed);
+        if !ready {
+            std::thread::park();
+        }
+        ring.parked.store(false, Ordering::Release);
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.ring.mask + 1
+    }
+
+    /// Whether the producer was dropped
+    pub fn is_closed(&self) -> bool {
+        self.ring.closed.load(Ordering::Acquire)
+    }
+}
+
+impl<T> Drop for RingConsumer<T> {
+    fn drop(&mut self) {
+        self.ring.closed.store(true, Ordering::Release);
+    }
+}
+
+/// 专用解析线程的环形缓冲区配置
+///
+/// Used with `YellowstoneGrpc::with_ring_buffer`: the gRPC reader pushes updates
+/// into a ring of `capacity` slots and a dedicated OS thread, optionally pinned
+/// to `core`, parses them, bypassing the tokio channel and its wakeups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RingBufferConfig {
+    pub capacity: usize,
+    /// Core the parser thread is pinned to (requires the `affinity` feature)
+    pub core: Option<usize>,
+    /// Busy-wait iterations before the idle parser thread yields and then parks
+    pub spin_limit: u32,
+}
+
+impl Default for RingBufferConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RingBufferConfig {
+    pub fn new() -> Self {
+        Self {
+            capacity: DEFAULT_RING_CAPACITY,
+            core: None,
+            spin_limit: DEFAULT_SPIN_LIMIT,
+        }
+    }
+
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn with_core(mut self, core: usize) -> Self {
+        self.core = Some(core);
+        self
+    }
+
+    pub fn with_spin_limit(mut self, spin_limit: u32) -> Self {
+        self.spin_limit = spin_limit;
+        self
+    }
+}
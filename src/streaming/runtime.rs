@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::runtime::{Builder, Handle, Runtime};
+
+use crate::common::affinity::{available_cores, pin_current_thread};
+use crate::common::AnyResult;
+
+/// 线程拓扑配置
+///
+/// Controls how many threads do network IO and parsing, whether they share one
+/// tokio runtime and which cores their threads are pinned to. Threads of a
+/// runtime, blocking pool included, take its listed cores round-robin as they
+/// start; an empty list leaves them to the OS scheduler. Pinning requires the
+/// `affinity` feature.
+///
+/// # Usage Example
+/// ```ignore
+/// let runtime = RuntimeConfig::new()
+///     .with_io_threads(1)
+///     .with_io_cores(vec![2])
+///     .with_parser_threads(4)
+///     .with_parser_cores(vec![4, 5, 6, 7])
+///     .build()?;
+/// let grpc = YellowstoneGrpc::new(endpoint, None)?.with_runtime(&runtime);
+/// runtime.block_on(grpc.subscribe_events_v2(...))?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuntimeConfig {
+    /// Worker threads of the runtime reading the streams
+    pub io_threads: usize,
+    /// Worker threads of the runtime parsing transactions
+    pub parser_threads: usize,
+    /// Run IO and parsing on one runtime of `io_threads + parser_threads` workers
+    pub shared_runtime: bool,
+    pub io_cores: Vec<usize>,
+    pub parser_cores: Vec<usize>,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RuntimeConfig {
+    /// Separate runtimes: one IO thread and the other cores for parsing
+    pub fn new() -> Self {
+        Self {
+            io_threads: 1,
+            parser_threads: available_cores().saturating_sub(1).max(1),
+            shared_runtime: false,
+            io_cores: vec![],
+            parser_cores: vec![],
+        }
+    }
+
+    pub fn with_io_threads(mut self, threads: usize) -> Self {
+        self.io_threads = threads.max(1);
+        self
+    }
+
+    pub fn with_parser_threads(mut self, threads: usize) -> Self {
+        self.parser_threads = threads.max(1);
+        self
+    }
+
+    pub fn with_shared_runtime(mut self, shared_runtime: bool) -> Self {
+        self.shared_runtime = shared_runtime;
+        self
+    }
+
+    pub fn with_io_cores(mut self, cores: Vec<usize>) -> Self {
+        self.io_cores = cores;
+        self
+    }
+
+    pub fn with_parser_cores(mut self, cores: Vec<usize>) -> Self {
+        self.parser_cores = cores;
+        self
+    }
+
+    pub fn validate(&self) -> AnyResult<()> {
+        let cores = available_cores();
+        if let Some(core) = self
+            .io_cores
+            .iter()
+            .chain(&self.parser_cores)
+            .find(|core| **core >= cores)
+        {
+            return Err(anyhow::anyhow!(
+                "core {} does not exist, this machine has {} cores",
+                core,
+                cores
+            ));
+        }
+        Ok(())
+    }
+
+    /// Start the runtimes
+    pub fn build(&self) -> AnyResult<StreamerRuntime> {
+        self.validate()?;
+        if self.shared_runtime {
+            let cores: Vec<usize> = self
+                .io_cores
+                .iter()
+                .chain(&self.parser_cores)
+                .copied()
+                .collect();
+            let runtime = build_runtime(
+                "solana-streamer",
+                self.io_threads + self.parser_threads,
+                cores,
+            )?;
+            return Ok(StreamerRuntime {
+                io: runtime,
+                parser: None,
+            });
+        }
+        Ok(StreamerRuntime {
+            io: build_runtime("solana-streamer-io", self.io_threads, self.io_cores.clone())?,
+            parser: Some(build_runtime(
+                "solana-streamer-parser",
+                self.parser_threads,
+                self.parser_cores.clone(),
+            )?),
+        })
+    }
+}
+
+fn build_runtime(name: &str, threads: usize, cores: Vec<usize>) -> AnyResult<Runtime> {
+    let mut builder = Builder::new_multi_thread();
+    builder
+        .worker_threads(threads.max(1))
+        .thread_name(name)
+        .enable_all();
+    if !cores.is_empty() {
+        let next = Arc::new(AtomicUsize::new(0));
+        builder.on_thread_start(move || {
+            // 按启动顺序轮流分配核心
+            let core = cores[next.fetch_add(1, Ordering::Relaxed) % cores.len()];
+            pin_current_thread(core);
+        });
+    }
+    Ok(builder.build()?)
+}
+
+/// 按 `RuntimeConfig` 启动的运行时
+pub struct StreamerRuntime {
+    io: Runtime,
+    // 与IO共享运行时时为None
+    parser: Option<Runtime>,
+}
+
+impl StreamerRuntime {
+    /// Runtime reading the streams; subscriptions are started on it
+    pub fn io_handle(&self) -> Handle {
+        self.io.handle().clone()
+    }
+
+    /// Runtime parsing transactions, the IO runtime when they share one
+    pub fn parser_handle(&self) -> Handle {
+        self.parser.as_ref().unwrap_or(&self.io).handle().clone()
+    }
+
+    pub fn is_shared(&self) -> bool {
+        self.parser.is_none()
+    }
+
+    /// Run a future to completion on the IO runtime
+    pub fn block_on<F: std::future::Future>(&self, future: F) -> F::Output {
+        self.io.block_on(future)
+    }
+}
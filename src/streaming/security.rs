@@ -0,0 +1,240 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::common::codec;
+
+/// Header carrying `Bearer <token>` for token auth
+pub const AUTHORIZATION_HEADER: &str = "authorization";
+/// Header carrying the unix timestamp (seconds) signed for HMAC auth
+pub const TIMESTAMP_HEADER: &str = "x-auth-timestamp";
+/// Header carrying a random value that makes every signed request unique
+pub const NONCE_HEADER: &str = "x-auth-nonce";
+/// Header carrying the hex encoded HMAC-SHA256 of the signed message, see
+/// [`ServerAuth::sign`]
+pub const SIGNATURE_HEADER: &str = "x-auth-signature";
+/// Maximum length of a nonce
+const MAX_NONCE_LEN: usize = 64;
+
+/// Default tolerated clock difference between client and server for HMAC auth
+pub const DEFAULT_MAX_SKEW_SECS: i64 = 30;
+
+/// 认证失败原因
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AuthError {
+    #[error("missing {0} header")]
+    MissingHeader(&'static str),
+    #[error("invalid token")]
+    InvalidToken,
+    #[error("invalid signature")]
+    InvalidSignature,
+    #[error("timestamp outside the allowed window")]
+    ExpiredTimestamp,
+    #[error("invalid nonce")]
+    InvalidNonce,
+    #[error("nonce was already used")]
+    ReplayedNonce,
+}
+
+/// 已使用的nonce，在时间窗口内拒绝重放
+#[derive(Debug, Default)]
+pub struct NonceCache {
+    // nonce -> 请求时间戳
+    seen: Mutex<HashMap<String, i64>>,
+}
+
+impl NonceCache {
+    /// Record `nonce`, returning false if it was seen within `max_skew_secs` of now
+    fn insert(&self, nonce: &str, timestamp: i64, now_secs: i64, max_skew_secs: i64) -> bool {
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        // 时间窗口之外的nonce会因时间戳过期被拒绝，无需保留
+        seen.retain(|_, seen_at| now_secs.abs_diff(*seen_at) <= max_skew_secs.max(0) as u64);
+        if seen.contains_key(nonce) {
+            return false;
+        }
+        seen.insert(nonce.to_string(), timestamp);
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// 服务端认证方式
+///
+/// Shared by the built-in servers. Token auth expects `authorization: Bearer <token>`;
+/// HMAC auth expects `x-auth-timestamp` (unix seconds), `x-auth-nonce` and
+/// `x-auth-signature`, the hex HMAC-SHA256 over the timestamp, nonce and gRPC
+/// method path, see [`ServerAuth::sign`]. A signature is only valid for the
+/// method it was made for, and each nonce is accepted once within the clock skew
+/// window, so a captured header can't be replayed.
+#[derive(Clone, Default)]
+pub enum ServerAuth {
+    #[default]
+    None,
+    Token(String),
+    Hmac {
+        secret: Vec<u8>,
+        max_skew_secs: i64,
+        nonces: Arc<NonceCache>,
+    },
+}
+
+impl std::fmt::Debug for ServerAuth {
+    // 不输出密钥
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServerAuth::None => write!(f, "None"),
+            ServerAuth::Token(_) => write!(f, "Token(..)"),
+            ServerAuth::Hmac { max_skew_secs, .. } => {
+                write!(f, "Hmac {{ max_skew_secs: {} }}", max_skew_secs)
+            }
+        }
+    }
+}
+
+impl ServerAuth {
+    pub fn token(token: impl Into<String>) -> Self {
+        ServerAuth::Token(token.into())
+    }
+
+    pub fn hmac(secret: impl Into<Vec<u8>>) -> Self {
+        ServerAuth::Hmac {
+            secret: secret.into(),
+            max_skew_secs: DEFAULT_MAX_SKEW_SECS,
+            nonces: Arc::new(NonceCache::default()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, ServerAuth::None)
+    }
+
+    fn signed_mac(secret: &[u8], timestamp: &str, nonce: &str, path: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key size");
+        mac.update(format!("{}\n{}\n{}", timestamp, nonce, path).as_bytes());
+        mac
+    }
+
+    /// Signature a client sends in `x-auth-signature`: HMAC-SHA256 of
+    /// `"{timestamp}\n{nonce}\n{path}"`, where `path` is the gRPC method path, e.g.
+    /// `/events.EventStream/SubscribeEvents`
+    pub fn sign(secret: &[u8], timestamp: i64, nonce: &str, path: &str) -> String {
+        let mac = Self::signed_mac(secret, &timestamp.to_string(), nonce, path);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    /// Headers a client sends to call the method at `path` at `now_secs`
+    pub fn client_headers(&self, path: &str, now_secs: i64) -> Vec<(&'static str, String)> {
+        match self {
+            ServerAuth::None => vec![],
+            ServerAuth::Token(token) => vec![(AUTHORIZATION_HEADER, format!("Bearer {}", token))],
+            ServerAuth::Hmac { secret, .. } => {
+                let nonce = format!("{:032x}", rand::random::<u128>());
+                vec![
+                    (TIMESTAMP_HEADER, now_secs.to_string()),
+                    (SIGNATURE_HEADER, Self::sign(secret, now_secs, &nonce, path)),
+                    (NONCE_HEADER, nonce),
+                ]
+            }
+        }
+    }
+
+    /// Verify a call of the method at `path` given a header lookup and the current
+    /// unix time in seconds
+    pub fn verify<'a, H>(&self, header: H, path: &str, now_secs: i64) -> Result<(), AuthError>
+    where
+        H: Fn(&str) -> Option<&'a str>,
+    {
+        match self {
+            ServerAuth::None => Ok(()),
+            ServerAuth::Token(token) => {
+                let value = header(AUTHORIZATION_HEADER)
+                    .ok_or(AuthError::MissingHeader(AUTHORIZATION_HEADER))?;
+                let presented = value.strip_prefix("Bearer ").unwrap_or(value);
+                if constant_time_eq(presented.as_bytes(), token.as_bytes()) {
+                    Ok(())
+                } else {
+                    Err(AuthError::InvalidToken)
+                }
+            }
+            ServerAuth::Hmac {
+                secret,
+                max_skew_secs,
+                nonces,
+            } => {
+                let timestamp =
+                    header(TIMESTAMP_HEADER).ok_or(AuthError::MissingHeader(TIMESTAMP_HEADER))?;
+                let nonce = header(NONCE_HEADER).ok_or(AuthError::MissingHeader(NONCE_HEADER))?;
+                let signature =
+                    header(SIGNATURE_HEADER).ok_or(AuthError::MissingHeader(SIGNATURE_HEADER))?;
+                let seconds = timestamp
+                    .parse::<i64>()
+                    .map_err(|_| AuthError::InvalidSignature)?;
+                // 时间戳由客户端提供，不能用减法，极值会溢出
+                if now_secs.abs_diff(seconds) > (*max_skew_secs).max(0) as u64 {
+                    return Err(AuthError::ExpiredTimestamp);
+                }
+                if nonce.is_empty() || nonce.len() > MAX_NONCE_LEN {
+                    return Err(AuthError::InvalidNonce);
+                }
+                let signature =
+                    codec::hex_decode(signature).map_err(|_| AuthError::InvalidSignature)?;
+                Self::signed_mac(secret, timestamp, nonce, path)
+                    .verify_slice(&signature)
+                    .map_err(|_| AuthError::InvalidSignature)?;
+                // 签名有效后才记录nonce，伪造的请求不能占用它
+                if !nonces.insert(nonce, seconds, now_secs, *max_skew_secs) {
+                    return Err(AuthError::ReplayedNonce);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// 服务端TLS证书
+#[derive(Clone)]
+pub struct ServerTls {
+    pub cert_pem: Vec<u8>,
+    pub key_pem: Vec<u8>,
+}
+
+impl ServerTls {
+    pub fn new(cert_pem: impl Into<Vec<u8>>, key_pem: impl Into<Vec<u8>>) -> Self {
+        Self {
+            cert_pem: cert_pem.into(),
+            key_pem: key_pem.into(),
+        }
+    }
+
+    /// Load a PEM certificate chain and private key
+    pub fn from_files(cert_path: impl AsRef<Path>, key_path: impl AsRef<Path>) -> AnyResult<Self> {
+        Ok(Self::new(
+            std::fs::read(cert_path)?,
+            std::fs::read(key_path)?,
+        ))
+    }
+
+    pub(crate) fn tonic_config(&self) -> tonic::transport::ServerTlsConfig {
+        tonic::transport::ServerTlsConfig::new().identity(tonic::transport::Identity::from_pem(
+            &self.cert_pem,
+            &self.key_pem,
+        ))
+    }
+}
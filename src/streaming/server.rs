@@ -0,0 +1,256 @@
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use futures::Stream;
+use log::{info, warn};
+use tokio::sync::broadcast;
+use tonic::{Request, Response, Status};
+
+use crate::common::AnyResult;
+use crate::protos::events::event_stream_server::{EventStream, EventStreamServer};
+use crate::protos::events::{
+    EventMetadata as WireEventMetadata, StreamedEvent, SubscribeEventsRequest,
+    TokenBalanceChange as WireTokenBalanceChange,
+};
+use crate::streaming::event_parser::{
+    common::{reflect::get_field, EventMetadata},
+    UnifiedEvent,
+};
+use crate::streaming::security::{ServerAuth, ServerTls};
+use crate::streaming::sink::RedactionConfig;
+
+const DEFAULT_BROADCAST_CAPACITY: usize = 10_000;
+/// gRPC method path of `SubscribeEvents`, signed by HMAC authenticated clients
+pub const SUBSCRIBE_EVENTS_PATH: &str = "/solana_streamer.events.EventStream/SubscribeEvents";
+
+/// 事件广播服务 - 通过gRPC将解析后的事件转发给多个下游客户端
+///
+/// Events are published into a broadcast channel; every `SubscribeEvents` call gets
+/// its own receiver. Clients that fall more than the channel capacity behind skip
+/// the missed events instead of slowing down the publisher. The schema is in
+/// `proto/events.proto`. When the server is reachable beyond localhost, enable TLS
+/// with [`EventServer::with_tls`] and client auth with [`EventServer::with_auth`].
+#[derive(Clone)]
+pub struct EventServer {
+    sender: broadcast::Sender<Arc<StreamedEvent>>,
+    redaction: Arc<RedactionConfig>,
+    auth: Arc<ServerAuth>,
+    tls: Option<ServerTls>,
+}
+
+impl Default for EventServer {
+    fn default() -> Self {
+        Self::new(DEFAULT_BROADCAST_CAPACITY)
+    }
+}
+
+impl EventServer {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self {
+            sender,
+            redaction: Arc::new(RedactionConfig::default()),
+            auth: Arc::new(ServerAuth::None),
+            tls: None,
+        }
+    }
+
+    /// Redact fields of the JSON payload before it is sent to clients
+    ///
+    /// The top-level `id` and `signature` of the wire message follow the rules for
+    /// `metadata.id` and `metadata.signature`; a removed field is sent empty.
+    pub fn with_redaction(mut self, redaction: RedactionConfig) -> Self {
+        self.redaction = Arc::new(redaction);
+        self
+    }
+
+    /// Require clients to authenticate with a token or HMAC signature
+    pub fn with_auth(mut self, auth: ServerAuth) -> Self {
+        self.auth = Arc::new(auth);
+        self
+    }
+
+    /// Serve over TLS
+    pub fn with_tls(mut self, tls: ServerTls) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Number of connected clients
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    /// Publish an event to all connected clients
+    pub fn publish(&self, event: &dyn UnifiedEvent) {
+        if self.sender.receiver_count() == 0 {
+            return;
+        }
+        let streamed = Self::to_redacted_streamed_event(event, &self.redaction);
+        let _ = self.sender.send(Arc::new(streamed));
+    }
+
+    /// Callback that publishes every event, for use with the subscribe methods
+    pub fn callback(&self) -> impl Fn(Box<dyn UnifiedEvent>) + Send + Sync + 'static {
+        let server = self.clone();
+        move |event: Box<dyn UnifiedEvent>| server.publish(event.as_ref())
+    }
+
+    /// Serve the `EventStream` service until the future is dropped
+    pub async fn serve(&self, addr: SocketAddr) -> AnyResult<()> {
+        info!(
+            "Event server listening on {} (tls: {}, auth: {:?})",
+            addr,
+            self.tls.is_some(),
+            self.auth
+        );
+        let mut builder = tonic::transport::Server::builder();
+        if let Some(tls) = &self.tls {
+            builder = builder.tls_config(tls.tonic_config())?;
+        }
+        builder
+            .add_service(EventStreamServer::new(self.clone()))
+            .serve(addr)
+            .await?;
+        Ok(())
+    }
+
+    /// Check the credentials of a call of the method at `path`
+    fn authorize<T>(&self, request: &Request<T>, path: &str) -> Result<(), Status> {
+        let metadata = request.metadata();
+        self.auth
+            .verify(
+                |name| metadata.get(name).and_then(|value| value.to_str().ok()),
+                path,
+                chrono::Utc::now().timestamp(),
+            )
+            .map_err(|e| Status::unauthenticated(e.to_string()))
+    }
+
+    /// Wire message of an event with the redaction rules applied
+    pub fn to_redacted_streamed_event(
+        event: &dyn UnifiedEvent,
+        redaction: &RedactionConfig,
+    ) -> StreamedEvent {
+        let mut streamed = Self::to_streamed_event(event);
+        if redaction.is_empty() {
+            return streamed;
+        }
+        let fields = redaction.redact_event(event);
+        // 顶层字段与载荷中的元数据使用相同的规则
+        let redacted = |path: &str| match get_field(&fields, path) {
+            Some(serde_json::Value::String(value)) => value.clone(),
+            _ => String::new(),
+        };
+        streamed.id = redacted("metadata.id");
+        streamed.signature = redacted("metadata.signature");
+        streamed.payload_json = fields.to_string();
+        // 类型化元数据不经过脱敏规则
+        streamed.metadata = None;
+        streamed
+    }
+
+    pub fn to_streamed_event(event: &dyn UnifiedEvent) -> StreamedEvent {
+        let metadata = event.metadata();
+        StreamedEvent {
+            id: metadata.id.clone(),
+            signature: metadata.signature.clone(),
+            slot: metadata.slot,
+            block_time_ms: metadata.block_time_ms,
+            protocol: format!("{:?}", metadata.protocol),
+            event_type: metadata.event_type.to_string(),
+            program_id: metadata.program_id.to_string(),
+            index: metadata.index.to_string(),
+            program_received_time_ms: metadata.program_received_time_ms,
+            payload_json: event.to_json(),
+            schema_version: metadata.schema_version,
+            metadata: Some(metadata.into()),
+        }
+    }
+}
+
+impl From<&EventMetadata> for WireEventMetadata {
+    fn from(metadata: &EventMetadata) -> Self {
+        Self {
+            id: metadata.id.clone(),
+            signature: metadata.signature.clone(),
+            slot: metadata.slot,
+            block_time: metadata.block_time,
+            block_time_ms: metadata.block_time_ms,
+            protocol: format!("{:?}", metadata.protocol),
+            event_type: metadata.event_type.to_string(),
+            program_id: metadata.program_id.to_string(),
+            index: metadata.index.to_string(),
+            tx_index: metadata.tx_index,
+            program_received_time_ms: metadata.program_received_time_ms,
+            transaction_failed: metadata.transaction_failed,
+            transaction_error: metadata.transaction_error.clone(),
+            fee: metadata.fee,
+            signers: metadata.signers.iter().map(|signer| signer.to_string()).collect(),
+            memos: metadata.memos.clone(),
+            referral: metadata.referral.clone(),
+            schema_version: metadata.schema_version,
+            caller_program_ids: metadata
+                .caller_program_ids
+                .iter()
+                .map(|program_id| program_id.to_string())
+                .collect(),
+            routed_via: metadata.routed_via.as_ref().map(|router| router.to_string()),
+            token_balance_changes: metadata
+                .token_balance_changes
+                .iter()
+                .map(|change| WireTokenBalanceChange {
+                    account: change.account.to_string(),
+                    owner: change.owner.map(|owner| owner.to_string()),
+                    mint: change.mint.to_string(),
+                    decimals: change.decimals as u32,
+                    pre_amount: change.pre_amount,
+                    post_amount: change.post_amount,
+                })
+                .collect(),
+            risk_flags: metadata.risk.bits(),
+            sequence: metadata.sequence,
+        }
+    }
+}
+
+type EventStreamResult = Pin<Box<dyn Stream<Item = Result<StreamedEvent, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl EventStream for EventServer {
+    type SubscribeEventsStream = EventStreamResult;
+
+    async fn subscribe_events(
+        &self,
+        request: Request<SubscribeEventsRequest>,
+    ) -> Result<Response<Self::SubscribeEventsStream>, Status> {
+        // 在方法内校验，签名绑定到方法路径
+        self.authorize(&request, SUBSCRIBE_EVENTS_PATH)?;
+        let filter = request.into_inner();
+        let receiver = self.sender.subscribe();
+        let stream = futures::stream::unfold(receiver, move |mut receiver| {
+            let filter = filter.clone();
+            async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(event) => {
+                            let matches = (filter.event_types.is_empty()
+                                || filter.event_types.contains(&event.event_type))
+                                && (filter.protocols.is_empty()
+                                    || filter.protocols.contains(&event.protocol));
+                            if matches {
+                                return Some((Ok((*event).clone()), receiver));
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("Event server client lagged, skipped {} events", skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            }
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
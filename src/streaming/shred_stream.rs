@@ -4,20 +4,32 @@ use futures::{channel::mpsc, StreamExt};
 use solana_entry::entry::Entry;
 use tonic::transport::Channel;
 
-use log::error;
+use log::{error, warn};
 use solana_sdk::transaction::VersionedTransaction;
 
 use crate::common::AnyResult;
-use crate::streaming::event_parser::{EventParserFactory, Protocol, UnifiedEvent};
+use crate::streaming::alt_resolver::AltResolver;
+use crate::streaming::event_parser::{
+    common::{FailedTxPolicy, ParseOptions},
+    ActiveProtocols, EventParserFactory, PostProcessorChain, ProgramIdFilter, Protocol,
+    UnifiedEvent,
+};
+use crate::streaming::interceptor::{
+    run_interceptor, SharedInterceptor, StreamInterceptor, StreamSource,
+};
 
 use crate::protos::shredstream::shredstream_proxy_client::ShredstreamProxyClient;
-use crate::protos::shredstream::SubscribeEntriesRequest;
+use crate::protos::shredstream::{Entry as ShredstreamEntry, SubscribeEntriesRequest};
 use solana_sdk::pubkey::Pubkey;
 
 const CHANNEL_SIZE: usize = 1000;
 
 pub struct ShredStreamGrpc {
+    endpoint: String,
     shredstream_client: Arc<ShredstreamProxyClient<Channel>>,
+    interceptor: Option<SharedInterceptor<ShredstreamEntry>>,
+    alt_resolver: Option<Arc<AltResolver>>,
+    parse_options: ParseOptions,
 }
 
 struct TransactionWithSlot {
@@ -29,28 +41,98 @@ impl ShredStreamGrpc {
     pub async fn new(endpoint: String) -> AnyResult<Self> {
         let shredstream_client = ShredstreamProxyClient::connect(endpoint.clone()).await?;
         Ok(Self {
+            endpoint,
             shredstream_client: Arc::new(shredstream_client),
+            interceptor: None,
+            alt_resolver: None,
+            parse_options: ParseOptions::default(),
         })
     }
 
+    /// Set a hook that sees every raw shredstream entry batch before it is decoded
+    ///
+    /// `entries` holds the bincode-serialized `Vec<Entry>` as received from the proxy,
+    /// so the interceptor can unwrap or rewrite it, or drop the batch entirely.
+    pub fn with_interceptor(
+        mut self,
+        interceptor: impl StreamInterceptor<ShredstreamEntry> + 'static,
+    ) -> Self {
+        self.interceptor = Some(Arc::new(interceptor));
+        self
+    }
+
+    /// Resolve address lookup tables before parsing
+    ///
+    /// Without a resolver only the static account keys are known, so events from
+    /// transactions that load accounts from lookup tables get wrong accounts.
+    /// Transactions whose tables fail to resolve are skipped, see
+    /// [`AltResolver::resolve_for_parsing`].
+    pub fn with_alt_resolver(mut self, alt_resolver: Arc<AltResolver>) -> Self {
+        self.alt_resolver = Some(alt_resolver);
+        self
+    }
+
+    /// Set the options every transaction is parsed with
+    pub fn with_parse_options(mut self, parse_options: ParseOptions) -> Self {
+        self.parse_options = parse_options;
+        self
+    }
+
+    /// Entries carry no transaction status, so every transaction counts as
+    /// successful: `OnlyFailed` delivers nothing
+    pub fn with_failed_tx_policy(mut self, failed_tx_policy: FailedTxPolicy) -> Self {
+        self.parse_options.failed_tx_policy = failed_tx_policy;
+        self
+    }
+
+    /// Attach the raw instruction data and accounts to every event
+    pub fn with_include_raw(mut self, include_raw: bool) -> Self {
+        self.parse_options.include_raw = include_raw;
+        self
+    }
+
+    /// Post-processors run on each transaction's events, replacing the default
+    /// dev/bot tagging chain
+    pub fn with_post_processors(mut self, post_processors: PostProcessorChain) -> Self {
+        self.parse_options.post_processors = post_processors;
+        self
+    }
+
+    /// Subscribe to entries and parse their transactions
+    ///
+    /// Pass an [`ActiveProtocols`] handle as `protocols` to enable or disable
+    /// protocols while the stream runs.
     pub async fn shredstream_subscribe<F>(
         &self,
-        protocols: Vec<Protocol>,
+        protocols: impl Into<ActiveProtocols>,
         bot_wallet: Option<Pubkey>,
         callback: F,
     ) -> AnyResult<()>
     where
         F: Fn(Box<dyn UnifiedEvent>) + Send + Sync + 'static,
     {
+        let protocols: ActiveProtocols = protocols.into();
         let request = tonic::Request::new(SubscribeEntriesRequest {});
         let mut client = (*self.shredstream_client).clone();
         let mut stream = client.subscribe_entries(request).await?.into_inner();
         let (mut tx, mut rx) = mpsc::channel::<TransactionWithSlot>(CHANNEL_SIZE);
         let callback = Box::new(callback);
+        let interceptor = self.interceptor.clone();
+        let endpoint = self.endpoint.clone();
+        let alt_resolver = self.alt_resolver.clone();
+        let parse_options = self.parse_options.clone();
         tokio::spawn(async move {
             while let Some(message) = stream.next().await {
                 match message {
-                    Ok(msg) => {
+                    Ok(mut msg) => {
+                        if !run_interceptor(
+                            &interceptor,
+                            &mut msg,
+                            StreamSource::ShredStream,
+                            &endpoint,
+                        ) {
+                            continue;
+                        }
                         if let Ok(entries) = bincode::deserialize::<Vec<Entry>>(&msg.entries) {
                             for entry in entries {
                                 for transaction in entry.transactions {
@@ -73,8 +155,11 @@ impl ShredStreamGrpc {
         while let Some(transaction_with_slot) = rx.next().await {
             if let Err(e) = Self::process_transaction(
                 transaction_with_slot,
-                protocols.clone(),
+                protocols.protocols(),
+                &protocols.program_filter(),
                 bot_wallet,
+                alt_resolver.as_deref(),
+                &parse_options,
                 &*callback,
             )
             .await
@@ -89,7 +174,10 @@ impl ShredStreamGrpc {
     async fn process_transaction<F>(
         transaction_with_slot: TransactionWithSlot,
         protocols: Vec<Protocol>,
+        program_filter: &ProgramIdFilter,
         bot_wallet: Option<Pubkey>,
+        alt_resolver: Option<&AltResolver>,
+        parse_options: &ParseOptions,
         callback: &F,
     ) -> AnyResult<()>
     where
@@ -99,21 +187,44 @@ impl ShredStreamGrpc {
         let slot = transaction_with_slot.slot;
         let versioned_tx = transaction_with_slot.transaction;
         let signature = versioned_tx.signatures[0];
+        let accounts = match alt_resolver {
+            // 查找表无法解析时跳过交易，避免事件使用错误的账户
+            Some(alt_resolver) => match alt_resolver.resolve_for_parsing(&versioned_tx).await {
+                Some(accounts) => accounts,
+                None => return Ok(()),
+            },
+            None => versioned_tx.message.static_account_keys().to_vec(),
+        };
+        // 不涉及任何协议程序的交易无需解析
+        if !program_filter.matches_accounts(&accounts) {
+            return Ok(());
+        }
 
         for protocol in protocols {
             let parser = EventParserFactory::create_parser(protocol.clone());
-            let events = parser
-                .parse_versioned_transaction(
+            let output = match parser
+                .parse_versioned_transaction_with_accounts(
                     &versioned_tx,
+                    &accounts,
                     &signature.to_string(),
                     Some(slot),
                     None,
                     program_received_time_ms,
                     bot_wallet.clone(),
+                    parse_options.clone(),
                 )
                 .await
-                .unwrap_or_else(|_e| vec![]);
-            for event in events {
+            {
+                Ok(output) => output,
+                Err(e) => {
+                    warn!("Failed to parse {}: {}", signature, e);
+                    continue;
+                }
+            };
+            for error in &output.errors {
+                log::debug!("Partial parse of {}: {}", signature, error);
+            }
+            for event in output.events {
                 callback(event);
             }
         }
@@ -0,0 +1,159 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use log::{error, warn};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::common::AnyResult;
+use crate::streaming::checkpoint::ResumeCheckpoint;
+use crate::streaming::sink::SinkWriter;
+
+/// Default time a stream gets to drain on shutdown
+pub const DEFAULT_SHUTDOWN_GRACE: Duration = Duration::from_secs(10);
+
+type FlushHook = Box<dyn Fn() -> AnyResult<()> + Send + Sync>;
+
+/// 停机信号 - 由数据源监听，触发后停止读取新数据
+#[derive(Debug, Clone)]
+pub struct ShutdownSignal(watch::Receiver<bool>);
+
+impl ShutdownSignal {
+    pub fn is_shutdown(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    /// Resolves once shutdown was requested; never resolves if the handle was
+    /// dropped without shutting down
+    pub async fn wait(&self) {
+        let mut receiver = self.0.clone();
+        if receiver.wait_for(|shutdown| *shutdown).await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+/// Outcome of [`StreamHandle::shutdown`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Whether the source finished within the grace period; otherwise it was
+    /// aborted and queued transactions were lost
+    pub drained: bool,
+    pub elapsed: Duration,
+}
+
+/// 运行中的数据流句柄，支持有序停机
+///
+/// [`StreamHandle::shutdown`] tells the source to stop reading from the
+/// connection, waits for the transactions it already received to be parsed and
+/// delivered, then flushes the registered sinks and checkpoints, so a deploy does
+/// not cut off half-processed slots. Dropping the handle leaves the stream
+/// running.
+///
+/// # Usage Example
+/// ```ignore
+/// let handle = grpc
+///     .spawn_events_with_request(protocols, None, request, callback)
+///     .await?
+///     .with_sink(sink.clone())
+///     .with_checkpoint(checkpoint.clone());
+/// tokio::signal::ctrl_c().await?;
+/// let report = handle.shutdown(Duration::from_secs(5)).await?;
+/// ```
+pub struct StreamHandle {
+    trigger: watch::Sender<bool>,
+    task: JoinHandle<AnyResult<()>>,
+    flush_hooks: Vec<(String, FlushHook)>,
+}
+
+impl StreamHandle {
+    /// Run `source` as its own task
+    ///
+    /// The source must stop reading new data once the signal fires and return
+    /// after delivering what it already read.
+    pub fn spawn<S, Fut>(source: S) -> Self
+    where
+        S: FnOnce(ShutdownSignal) -> Fut,
+        Fut: Future<Output = AnyResult<()>> + Send + 'static,
+    {
+        let (trigger, receiver) = watch::channel(false);
+        let task = tokio::spawn(source(ShutdownSignal(receiver)));
+        Self {
+            trigger,
+            task,
+            flush_hooks: vec![],
+        }
+    }
+
+    /// Run `flush` after the stream stopped, in the order added
+    pub fn with_flush<F>(mut self, name: impl Into<String>, flush: F) -> Self
+    where
+        F: Fn() -> AnyResult<()> + Send + Sync + 'static,
+    {
+        self.flush_hooks.push((name.into(), Box::new(flush)));
+        self
+    }
+
+    pub fn with_sink(self, sink: Arc<SinkWriter>) -> Self {
+        self.with_flush("sink", move || sink.flush())
+    }
+
+    pub fn with_checkpoint(self, checkpoint: Arc<ResumeCheckpoint>) -> Self {
+        self.with_flush("checkpoint", move || checkpoint.save())
+    }
+
+    /// Signal observed by the source
+    pub fn signal(&self) -> ShutdownSignal {
+        ShutdownSignal(self.trigger.subscribe())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Stop immediately without draining or flushing
+    pub fn abort(&self) {
+        self.task.abort();
+    }
+
+    /// Wait for the source to finish on its own and return its result; the flush
+    /// hooks are not run
+    pub async fn join(self) -> AnyResult<()> {
+        self.task.await?
+    }
+
+    /// 有序停机
+    ///
+    /// Stops the source from reading, gives it `grace` to finish parsing and
+    /// delivering in-flight transactions and then runs the flush hooks, also when
+    /// the grace period ran out. Returns the error of the source or, failing that,
+    /// of the first failed flush.
+    pub async fn shutdown(mut self, grace: Duration) -> AnyResult<ShutdownReport> {
+        let started = Instant::now();
+        self.trigger.send_replace(true);
+        let (drained, result) = match tokio::time::timeout(grace, &mut self.task).await {
+            Ok(joined) => (true, joined.map_err(anyhow::Error::from).and_then(|r| r)),
+            Err(_) => {
+                warn!("Stream did not drain within {:?}, aborting", grace);
+                self.task.abort();
+                (false, Ok(()))
+            }
+        };
+        let mut flush_error = None;
+        for (name, flush) in &self.flush_hooks {
+            if let Err(e) = flush() {
+                error!("Failed to flush {} on shutdown: {:?}", name, e);
+                flush_error.get_or_insert(e);
+            }
+        }
+        result?;
+        if let Some(e) = flush_error {
+            return Err(e);
+        }
+        Ok(ShutdownReport {
+            drained,
+            elapsed: started.elapsed(),
+        })
+    }
+}
@@ -0,0 +1,263 @@
+use serde_json::Value;
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::common::discriminator;
+
+/// Anchor IDL中的字段类型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdlType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    F32,
+    F64,
+    Pubkey,
+    String,
+    Bytes,
+    Option(Box<IdlType>),
+    Vec(Box<IdlType>),
+    Array(Box<IdlType>, usize),
+    Defined(String),
+}
+
+impl IdlType {
+    /// Parse both the legacy (`publicKey`, `{"defined": "X"}`) and the 0.30
+    /// (`pubkey`, `{"defined": {"name": "X"}}`) type notations
+    pub fn parse(value: &Value) -> AnyResult<Self> {
+        if let Some(name) = value.as_str() {
+            return Ok(match name {
+                "bool" => Self::Bool,
+                "u8" => Self::U8,
+                "u16" => Self::U16,
+                "u32" => Self::U32,
+                "u64" => Self::U64,
+                "u128" => Self::U128,
+                "i8" => Self::I8,
+                "i16" => Self::I16,
+                "i32" => Self::I32,
+                "i64" => Self::I64,
+                "i128" => Self::I128,
+                "f32" => Self::F32,
+                "f64" => Self::F64,
+                "pubkey" | "publicKey" => Self::Pubkey,
+                "string" => Self::String,
+                "bytes" => Self::Bytes,
+                other => return Err(anyhow::anyhow!("unsupported IDL type: {}", other)),
+            });
+        }
+        if let Some(inner) = value.get("option") {
+            return Ok(Self::Option(Box::new(Self::parse(inner)?)));
+        }
+        if let Some(inner) = value.get("vec") {
+            return Ok(Self::Vec(Box::new(Self::parse(inner)?)));
+        }
+        if let Some(array) = value.get("array").and_then(|a| a.as_array()) {
+            let inner = array
+                .first()
+                .ok_or_else(|| anyhow::anyhow!("array without type"))?;
+            let len = array.get(1).and_then(|l| l.as_u64()).unwrap_or_default() as usize;
+            return Ok(Self::Array(Box::new(Self::parse(inner)?), len));
+        }
+        if let Some(defined) = value.get("defined") {
+            let name = defined
+                .as_str()
+                .or_else(|| defined.get("name").and_then(|n| n.as_str()))
+                .ok_or_else(|| anyhow::anyhow!("invalid defined type: {}", defined))?;
+            return Ok(Self::Defined(name.to_string()));
+        }
+        Err(anyhow::anyhow!("unsupported IDL type: {}", value))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdlField {
+    pub name: String,
+    pub ty: IdlType,
+}
+
+/// 自定义类型 - 结构体字段或枚举变体（仅支持无字段变体）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdlTypeDef {
+    Struct(Vec<IdlField>),
+    Enum(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdlInstruction {
+    pub name: String,
+    pub discriminator: [u8; 8],
+    pub accounts: Vec<String>,
+    pub args: Vec<IdlField>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdlEvent {
+    pub name: String,
+    pub discriminator: [u8; 8],
+    pub fields: Vec<IdlField>,
+}
+
+/// 精简的Anchor IDL - 仅包含生成模拟数据所需的部分
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Idl {
+    pub instructions: Vec<IdlInstruction>,
+    pub events: Vec<IdlEvent>,
+    pub types: Vec<(String, IdlTypeDef)>,
+}
+
+impl Idl {
+    pub fn from_json(json: &str) -> AnyResult<Self> {
+        let value: Value = serde_json::from_str(json)?;
+        let mut idl = Idl::default();
+
+        for ty in value
+            .get("types")
+            .and_then(|t| t.as_array())
+            .into_iter()
+            .flatten()
+        {
+            let name = str_field(ty, "name")?;
+            let def = &ty["type"];
+            let type_def = match def.get("kind").and_then(|k| k.as_str()) {
+                Some("struct") => IdlTypeDef::Struct(parse_fields(&def["fields"])?),
+                Some("enum") => IdlTypeDef::Enum(
+                    def["variants"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|v| v.get("name").and_then(|n| n.as_str()))
+                        .map(|n| n.to_string())
+                        .collect(),
+                ),
+                _ => continue,
+            };
+            idl.types.push((name, type_def));
+        }
+
+        for ix in value
+            .get("instructions")
+            .and_then(|i| i.as_array())
+            .into_iter()
+            .flatten()
+        {
+            let name = str_field(ix, "name")?;
+            let discriminator = match parse_discriminator(ix) {
+                Some(discriminator) => discriminator,
+                None => anchor_discriminator("global", &to_snake_case(&name)),
+            };
+            let accounts = ix["accounts"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|a| a.get("name").and_then(|n| n.as_str()))
+                .map(|n| n.to_string())
+                .collect();
+            idl.instructions.push(IdlInstruction {
+                name,
+                discriminator,
+                accounts,
+                args: parse_fields(&ix["args"])?,
+            });
+        }
+
+        for event in value
+            .get("events")
+            .and_then(|e| e.as_array())
+            .into_iter()
+            .flatten()
+        {
+            let name = str_field(event, "name")?;
+            let discriminator =
+                parse_discriminator(event).unwrap_or_else(|| anchor_discriminator("event", &name));
+            // 0.30格式的事件字段定义在types中
+            let fields = match event.get("fields") {
+                Some(fields) => parse_fields(fields)?,
+                None => match idl.type_def(&name) {
+                    Some(IdlTypeDef::Struct(fields)) => fields.clone(),
+                    _ => vec![],
+                },
+            };
+            idl.events.push(IdlEvent {
+                name,
+                discriminator,
+                fields,
+            });
+        }
+        Ok(idl)
+    }
+
+    pub fn instruction(&self, name: &str) -> Option<&IdlInstruction> {
+        self.instructions.iter().find(|ix| ix.name == name)
+    }
+
+    pub fn event(&self, name: &str) -> Option<&IdlEvent> {
+        self.events.iter().find(|event| event.name == name)
+    }
+
+    pub fn type_def(&self, name: &str) -> Option<&IdlTypeDef> {
+        self.types
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, def)| def)
+    }
+}
+
+/// Anchor discriminator: first 8 bytes of sha256("<namespace>:<name>")
+pub fn anchor_discriminator(namespace: &str, name: &str) -> [u8; 8] {
+    discriminator::anchor_discriminator(namespace.as_bytes(), name.as_bytes())
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len());
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+fn str_field(value: &Value, key: &str) -> AnyResult<String> {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string())
+        .ok_or_else(|| anyhow::anyhow!("IDL entry without {}: {}", key, value))
+}
+
+fn parse_fields(value: &Value) -> AnyResult<Vec<IdlField>> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|field| {
+            Ok(IdlField {
+                name: str_field(field, "name")?,
+                ty: IdlType::parse(&field["type"])?,
+            })
+        })
+        .collect()
+}
+
+fn parse_discriminator(value: &Value) -> Option<[u8; 8]> {
+    let bytes: Vec<u8> = value
+        .get("discriminator")?
+        .as_array()?
+        .iter()
+        .filter_map(|b| b.as_u64().map(|b| b as u8))
+        .collect();
+    bytes.try_into().ok()
+}
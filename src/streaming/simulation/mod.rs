@@ -0,0 +1,7 @@
+pub mod generator;
+pub mod idl;
+pub mod scaffold;
+
+pub use generator::{FixtureGenerator, SimulatedTransaction, EVENT_IX_TAG};
+pub use idl::{anchor_discriminator, Idl, IdlType};
+pub use scaffold::ProtocolScaffold;
@@ -0,0 +1,738 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, EncodedTransactionWithStatusMeta, UiInstruction,
+};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::common::decode_transaction;
+use crate::streaming::event_parser::common::discriminator::{
+    event_discriminator, instruction_discriminator, EVENT_IX_TAG,
+};
+
+/// 常见的Anchor指令名，用于反推鉴别器
+const COMMON_INSTRUCTIONS: &[&str] = &[
+    "buy",
+    "sell",
+    "swap",
+    "swap_v2",
+    "swap_base_in",
+    "swap_base_out",
+    "swap_base_input",
+    "swap_base_output",
+    "buy_exact_in",
+    "buy_exact_out",
+    "sell_exact_in",
+    "sell_exact_out",
+    "create",
+    "create_pool",
+    "initialize",
+    "initialize_pool",
+    "deposit",
+    "withdraw",
+    "add_liquidity",
+    "remove_liquidity",
+    "open_position",
+    "close_position",
+    "increase_liquidity",
+    "decrease_liquidity",
+    "collect_fees",
+    "claim",
+    "migrate",
+    "update_config",
+    "set_params",
+];
+
+/// 常见的Anchor事件名，用于反推鉴别器
+const COMMON_EVENTS: &[&str] = &[
+    "TradeEvent",
+    "SwapEvent",
+    "BuyEvent",
+    "SellEvent",
+    "CreateEvent",
+    "CreatePoolEvent",
+    "PoolCreateEvent",
+    "DepositEvent",
+    "WithdrawEvent",
+    "CompleteEvent",
+    "MigrateEvent",
+    "LiquidityEvent",
+    "AddLiquidityEvent",
+    "RemoveLiquidityEvent",
+    "ClaimEvent",
+];
+
+const KNOWN_ACCOUNTS: &[(&str, &str)] = &[
+    ("11111111111111111111111111111111", "system_program"),
+    (
+        "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+        "token_program",
+    ),
+    (
+        "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+        "token_2022_program",
+    ),
+    (
+        "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+        "associated_token_program",
+    ),
+    ("SysvarRent111111111111111111111111111111111", "rent"),
+    ("So11111111111111111111111111111111111111112", "wsol_mint"),
+    (
+        "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s",
+        "metadata_program",
+    ),
+];
+
+/// 指令中的账户
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountSample {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// 一次程序调用的样本
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionSample {
+    pub signature: String,
+    pub data: Vec<u8>,
+    pub accounts: Vec<AccountSample>,
+    /// All account keys of the transaction, used to recognize pubkey fields
+    pub transaction_keys: Vec<Pubkey>,
+}
+
+/// 推断的字段类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Pubkey,
+    U64,
+    Bool,
+    U8,
+}
+
+impl FieldKind {
+    pub fn size(&self) -> usize {
+        match self {
+            FieldKind::Pubkey => 32,
+            FieldKind::U64 => 8,
+            FieldKind::Bool | FieldKind::U8 => 1,
+        }
+    }
+
+    fn rust_type(&self) -> &'static str {
+        match self {
+            FieldKind::Pubkey => "Pubkey",
+            FieldKind::U64 => "u64",
+            FieldKind::Bool => "bool",
+            FieldKind::U8 => "u8",
+        }
+    }
+}
+
+/// 推断的字段，偏移量不含鉴别器
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldGuess {
+    pub name: String,
+    pub offset: usize,
+    pub kind: FieldKind,
+    /// Same value in every sample
+    pub constant: bool,
+}
+
+/// 推断的账户角色
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountRole {
+    pub index: usize,
+    pub name: String,
+    pub signer: bool,
+    pub writable: bool,
+    /// Same account in every sample (programs, sysvars, global configs)
+    pub fixed: Option<Pubkey>,
+}
+
+/// 按鉴别器归类的指令或事件候选
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub discriminator: [u8; 8],
+    /// Anchor name whose discriminator matches, if one of the common names does
+    pub anchor_name: Option<String>,
+    pub samples: usize,
+    pub min_data_len: usize,
+    pub max_data_len: usize,
+    pub fields: Vec<FieldGuess>,
+    /// Empty for events
+    pub accounts: Vec<AccountRole>,
+}
+
+impl Candidate {
+    /// Snake case name, the Anchor name or one derived from the discriminator
+    pub fn name(&self) -> String {
+        match &self.anchor_name {
+            Some(name) => to_snake_case(name.strip_suffix("Event").unwrap_or(name)),
+            None => format!("unknown_{}", hex::encode(&self.discriminator[..4])),
+        }
+    }
+}
+
+/// 生成的协议模块文件
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScaffoldFiles {
+    pub mod_rs: String,
+    pub events_rs: String,
+    pub parser_rs: String,
+    pub test_rs: String,
+}
+
+/// 协议脚手架 - 从抓取的交易推断协议模块骨架
+///
+/// Collects the calls of one program from sample transactions, groups them by
+/// 8-byte discriminator, infers field offsets and account roles, and renders a
+/// protocol module in the layout of `event_parser::protocols` (`mod.rs`,
+/// `events.rs`, `parser.rs`) plus a fixture-driven test. Anchor `emit_cpi!` events
+/// are recognized by their self-CPI tag. The output is a starting point: field
+/// names and types are guesses to review against the program's IDL or source.
+///
+/// Used by the `scaffold-protocol` binary:
+///
+/// ```text
+/// cargo run --bin scaffold-protocol --features rpc -- \
+///     --name my_dex --program <PROGRAM_ID> --rpc https://api.mainnet-beta.solana.com \
+///     --out scaffold <SIGNATURE>...
+/// ```
+pub struct ProtocolScaffold {
+    name: String,
+    program_id: Pubkey,
+    instructions: Vec<InstructionSample>,
+    events: Vec<InstructionSample>,
+    signatures: Vec<String>,
+}
+
+impl ProtocolScaffold {
+    /// `name` is the snake case module name, e.g. `my_dex`
+    pub fn new(name: impl Into<String>, program_id: Pubkey) -> Self {
+        Self {
+            name: name.into(),
+            program_id,
+            instructions: vec![],
+            events: vec![],
+            signatures: vec![],
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn signatures(&self) -> &[String] {
+        &self.signatures
+    }
+
+    /// Collect the program's instructions and events from a transaction;
+    /// returns the number of calls found
+    pub fn add_transaction(
+        &mut self,
+        signature: &str,
+        tx: &EncodedTransactionWithStatusMeta,
+    ) -> AnyResult<usize> {
+        let versioned = decode_transaction(&tx.transaction)
+            .ok_or_else(|| anyhow::anyhow!("undecodable transaction {}", signature))?;
+        let message = &versioned.message;
+        let header = message.header();
+        let static_keys = message.static_account_keys();
+        let signed = header.num_required_signatures as usize;
+        let mut keys: Vec<(Pubkey, bool, bool)> = static_keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| {
+                let writable = if i < signed {
+                    i < signed.saturating_sub(header.num_readonly_signed_accounts as usize)
+                } else {
+                    i < static_keys
+                        .len()
+                        .saturating_sub(header.num_readonly_unsigned_accounts as usize)
+                };
+                (*key, i < signed, writable)
+            })
+            .collect();
+        if let Some(meta) = &tx.meta {
+            if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+                for (addresses, writable) in [(&loaded.writable, true), (&loaded.readonly, false)] {
+                    for address in addresses {
+                        keys.push((Pubkey::from_str(address)?, false, writable));
+                    }
+                }
+            }
+        }
+        let transaction_keys: Vec<Pubkey> = keys.iter().map(|(key, _, _)| *key).collect();
+        let sample = |program_index: usize, accounts: &[u8], data: Vec<u8>| {
+            if transaction_keys.get(program_index) != Some(&self.program_id) {
+                return None;
+            }
+            let accounts = accounts
+                .iter()
+                .filter_map(|index| keys.get(*index as usize))
+                .map(|(pubkey, is_signer, is_writable)| AccountSample {
+                    pubkey: *pubkey,
+                    is_signer: *is_signer,
+                    is_writable: *is_writable,
+                })
+                .collect();
+            Some(InstructionSample {
+                signature: signature.to_string(),
+                data,
+                accounts,
+                transaction_keys: transaction_keys.clone(),
+            })
+        };
+
+        let mut found = vec![];
+        for instruction in message.instructions() {
+            found.extend(sample(
+                instruction.program_id_index as usize,
+                &instruction.accounts,
+                instruction.data.clone(),
+            ));
+        }
+        if let Some(meta) = &tx.meta {
+            if let OptionSerializer::Some(inner) = &meta.inner_instructions {
+                for instruction in inner.iter().flat_map(|inner| &inner.instructions) {
+                    if let UiInstruction::Compiled(compiled) = instruction {
+                        let data = bs58::decode(&compiled.data).into_vec()?;
+                        found.extend(sample(
+                            compiled.program_id_index as usize,
+                            &compiled.accounts,
+                            data,
+                        ));
+                    }
+                }
+            }
+        }
+
+        let count = found.len();
+        for sample in found {
+            if sample.data.len() >= 16 && sample.data[..8] == EVENT_IX_TAG {
+                self.events.push(sample);
+            } else if sample.data.len() >= 8 {
+                self.instructions.push(sample);
+            }
+        }
+        self.signatures.push(signature.to_string());
+        Ok(count)
+    }
+
+    /// Instruction candidates, most frequent first
+    pub fn instructions(&self) -> Vec<Candidate> {
+        analyze(&self.instructions, 0, instruction_name, true)
+    }
+
+    /// `emit_cpi!` event candidates, most frequent first
+    pub fn events(&self) -> Vec<Candidate> {
+        analyze(&self.events, 8, event_name, false)
+    }
+
+    /// Render the protocol module and its test
+    pub fn render(&self) -> ScaffoldFiles {
+        let instructions = self.instructions();
+        let events = self.events();
+        let type_prefix = to_pascal_case(&self.name);
+        let const_prefix = self.name.to_uppercase();
+        let mut files = ScaffoldFiles::default();
+
+        files.mod_rs = format!(
+            "pub mod events;\npub mod parser;\n\npub use events::*;\npub use parser::{type_prefix}EventParser;\n"
+        );
+
+        // events.rs
+        let out = &mut files.events_rs;
+        out.push_str("use crate::impl_unified_event;\nuse crate::streaming::event_parser::common::EventMetadata;\nuse serde::{Deserialize, Serialize};\nuse solana_sdk::pubkey::Pubkey;\n");
+        let structs = instructions
+            .iter()
+            .map(|candidate| (candidate, "Event"))
+            .chain(events.iter().map(|candidate| (candidate, "LogEvent")));
+        for (candidate, suffix) in structs {
+            let struct_name = format!(
+                "{}{}{}",
+                type_prefix,
+                to_pascal_case(&candidate.name()),
+                suffix
+            );
+            let _ = writeln!(
+                out,
+                "\n/// {} (seen {} times, {}-{} data bytes)",
+                candidate.name(),
+                candidate.samples,
+                candidate.min_data_len,
+                candidate.max_data_len
+            );
+            out.push_str(
+                "#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]\n",
+            );
+            let _ = writeln!(
+                out,
+                "pub struct {} {{\n    pub metadata: EventMetadata,",
+                struct_name
+            );
+            for field in &candidate.fields {
+                let _ = writeln!(out, "    pub {}: {},", field.name, field.kind.rust_type());
+            }
+            for account in &candidate.accounts {
+                let _ = writeln!(out, "    pub {}: Pubkey,", account.name);
+            }
+            let _ = writeln!(out, "}}\n\nimpl_unified_event!({},);", struct_name);
+        }
+        out.push_str("\n/// 事件鉴别器常量\npub mod discriminators {\n");
+        out.push_str("    use crate::anchor_event_discriminator;\n    use crate::streaming::event_parser::common::discriminator::instruction_discriminator;\n\n    // 事件鉴别器\n");
+        for candidate in &events {
+            let value = match &candidate.anchor_name {
+                Some(name) => format!("anchor_event_discriminator!(\"{}\")", name),
+                None => format!(
+                    "\"0x{}{}\"",
+                    hex::encode(EVENT_IX_TAG),
+                    hex::encode(candidate.discriminator)
+                ),
+            };
+            let _ = writeln!(
+                out,
+                "    pub const {}_EVENT: &str = {};",
+                candidate.name().to_uppercase(),
+                value
+            );
+        }
+        out.push_str("\n    // 指令鉴别器\n");
+        for candidate in &instructions {
+            let value = match &candidate.anchor_name {
+                Some(name) => format!("&instruction_discriminator(\"{}\")", name),
+                None => format!("&{:?}", candidate.discriminator),
+            };
+            let _ = writeln!(
+                out,
+                "    pub const {}_IX: &[u8] = {};",
+                candidate.name().to_uppercase(),
+                value
+            );
+        }
+        out.push_str("}\n");
+
+        // parser.rs
+        let out = &mut files.parser_rs;
+        let _ = write!(
+            out,
+            "use prost_types::Timestamp;\nuse solana_sdk::{{instruction::CompiledInstruction, pubkey::Pubkey}};\nuse solana_transaction_status::UiCompiledInstruction;\n\n\
+             use crate::streaming::event_parser::{{\n    common::{{\n        read_u64_le, read_u8, EventMetadata, EventType, InstructionIndex, ProtocolType,\n    }},\n    core::traits::{{EventParser, GenericEventParseConfig, GenericEventParser, UnifiedEvent}},\n    protocols::{name}::{{discriminators, *}},\n}};\n\n\
+             // TODO: add ProtocolType::{type_prefix} and the EventType variants used below\n\n\
+             /// {type_prefix}程序ID\npub const {const_prefix}_PROGRAM_ID: Pubkey =\n    solana_sdk::pubkey!(\"{program_id}\");\n\n\
+             /// {type_prefix}事件解析器\npub struct {type_prefix}EventParser {{\n    inner: GenericEventParser,\n}}\n\n\
+             impl {type_prefix}EventParser {{\n    pub fn new() -> Self {{\n        let configs = vec![\n",
+            name = self.name,
+            program_id = self.program_id,
+        );
+        for candidate in &instructions {
+            let name = candidate.name();
+            let pascal = to_pascal_case(&name);
+            // 同名事件（如 buy -> BuyEvent）作为内部指令合并
+            let event = events
+                .iter()
+                .find(|event| event.anchor_name.as_deref() == Some(&format!("{}Event", pascal)));
+            let (inner_discriminator, inner_parser) = match event {
+                Some(event) => (
+                    format!("discriminators::{}_EVENT", event.name().to_uppercase()),
+                    format!("Self::parse_{}_inner_instruction", event.name()),
+                ),
+                None => (
+                    "\"\"".to_string(),
+                    "Self::parse_unmatched_inner_instruction".to_string(),
+                ),
+            };
+            let _ = write!(
+                out,
+                "            GenericEventParseConfig {{\n                inner_instruction_discriminator: {inner_discriminator},\n                instruction_discriminator: discriminators::{upper}_IX,\n                event_type: EventType::{type_prefix}{pascal},\n                inner_instruction_parser: {inner_parser},\n                instruction_parser: Self::parse_{name}_instruction,\n                layouts: Vec::new(),\n            }},\n",
+                upper = name.to_uppercase(),
+            );
+        }
+        let _ = write!(
+            out,
+            "        ];\n\n        let inner =\n            GenericEventParser::new({const_prefix}_PROGRAM_ID, ProtocolType::{type_prefix}, configs);\n\n        Self {{ inner }}\n    }}\n\n\
+             fn parse_unmatched_inner_instruction(\n        _data: &[u8],\n        _metadata: EventMetadata,\n    ) -> Option<Box<dyn UnifiedEvent>> {{\n        None\n    }}\n"
+        );
+        for candidate in &events {
+            let struct_name = format!(
+                "{}{}LogEvent",
+                type_prefix,
+                to_pascal_case(&candidate.name())
+            );
+            let _ = write!(
+                out,
+                "\n    fn parse_{}_inner_instruction(\n        data: &[u8],\n        metadata: EventMetadata,\n    ) -> Option<Box<dyn UnifiedEvent>> {{\n",
+                candidate.name()
+            );
+            render_field_reads(out, candidate);
+            let _ = write!(
+                out,
+                "        Some(Box::new({} {{\n            metadata,\n",
+                struct_name
+            );
+            for field in &candidate.fields {
+                let _ = writeln!(out, "            {},", field.name);
+            }
+            out.push_str("        }))\n    }\n");
+        }
+        for candidate in &instructions {
+            let struct_name = format!("{}{}Event", type_prefix, to_pascal_case(&candidate.name()));
+            let _ = write!(
+                out,
+                "\n    fn parse_{}_instruction(\n        data: &[u8],\n        accounts: &[Pubkey],\n        metadata: EventMetadata,\n    ) -> Option<Box<dyn UnifiedEvent>> {{\n        if accounts.len() < {} {{\n            return None;\n        }}\n",
+                candidate.name(),
+                candidate.accounts.len()
+            );
+            render_field_reads(out, candidate);
+            let _ = write!(
+                out,
+                "        Some(Box::new({} {{\n            metadata,\n",
+                struct_name
+            );
+            for field in &candidate.fields {
+                let _ = writeln!(out, "            {},", field.name);
+            }
+            for account in &candidate.accounts {
+                let _ = writeln!(
+                    out,
+                    "            {}: accounts[{}],",
+                    account.name, account.index
+                );
+            }
+            out.push_str("        }))\n    }\n");
+        }
+        out.push_str("}\n\n#[async_trait::async_trait]\n");
+        let _ = write!(
+            out,
+            "impl EventParser for {type_prefix}EventParser {{\n\
+             \x20   fn parse_events_from_inner_instruction(\n        &self,\n        inner_instruction: &UiCompiledInstruction,\n        signature: &str,\n        slot: u64,\n        block_time: Option<Timestamp>,\n        program_received_time_ms: i64,\n        index: InstructionIndex,\n    ) -> Vec<Box<dyn UnifiedEvent>> {{\n        self.inner.parse_events_from_inner_instruction(\n            inner_instruction,\n            signature,\n            slot,\n            block_time,\n            program_received_time_ms,\n            index,\n        )\n    }}\n\n\
+             \x20   fn parse_events_from_instruction(\n        &self,\n        instruction: &CompiledInstruction,\n        accounts: &[Pubkey],\n        signature: &str,\n        slot: u64,\n        block_time: Option<Timestamp>,\n        program_received_time_ms: i64,\n        index: InstructionIndex,\n    ) -> Vec<Box<dyn UnifiedEvent>> {{\n        self.inner.parse_events_from_instruction(\n            instruction,\n            accounts,\n            signature,\n            slot,\n            block_time,\n            program_received_time_ms,\n            index,\n        )\n    }}\n\n\
+             \x20   fn should_handle(&self, program_id: &Pubkey) -> bool {{\n        self.inner.should_handle(program_id)\n    }}\n\n\
+             \x20   fn supported_program_ids(&self) -> Vec<Pubkey> {{\n        self.inner.supported_program_ids()\n    }}\n}}\n"
+        );
+
+        // tests/<name>_parsers.rs
+        let out = &mut files.test_rs;
+        let _ = write!(
+            out,
+            "use solana_streamer_sdk::streaming::event_parser::{{\n    protocols::{name}::parser::{type_prefix}EventParser, EventParser,\n}};\nuse solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;\nuse std::fs;\n\n\
+             #[tokio::test]\nasync fn test_{name}_fixtures_parse() -> anyhow::Result<()> {{\n    let parser = {type_prefix}EventParser::new();\n    for fixture in [\n",
+            name = self.name
+        );
+        for signature in &self.signatures {
+            let _ = writeln!(out, "        \"{}\",", fixture_path(&self.name, signature));
+        }
+        out.push_str(
+            "    ] {\n        let tx: EncodedConfirmedTransactionWithStatusMeta =\n            serde_json::from_str(&fs::read_to_string(fixture)?)?;\n        let events = parser\n            .parse_transaction(tx.transaction, fixture, Some(tx.slot), None, 0, None)\n            .await?;\n        assert!(!events.is_empty(), \"no events parsed from {}\", fixture);\n    }\n    Ok(())\n}\n",
+        );
+        files
+    }
+}
+
+/// Fixture path of a sample transaction, relative to the repository root
+pub fn fixture_path(name: &str, signature: &str) -> String {
+    let short: String = signature.chars().take(16).collect();
+    format!("tests/fixtures/{}_{}.json", name, short)
+}
+
+fn render_field_reads(out: &mut String, candidate: &Candidate) {
+    for field in &candidate.fields {
+        let read = match field.kind {
+            FieldKind::Pubkey => format!(
+                "Pubkey::new_from_array(data.get({}..{})?.try_into().ok()?)",
+                field.offset,
+                field.offset + 32
+            ),
+            FieldKind::U64 => format!("read_u64_le(data, {})?", field.offset),
+            FieldKind::Bool => format!("read_u8(data, {})? != 0", field.offset),
+            FieldKind::U8 => format!("read_u8(data, {})?", field.offset),
+        };
+        let _ = writeln!(out, "        let {} = {};", field.name, read);
+    }
+}
+
+fn instruction_name(discriminator: &[u8; 8]) -> Option<String> {
+    COMMON_INSTRUCTIONS
+        .iter()
+        .find(|name| instruction_discriminator(name) == *discriminator)
+        .map(|name| name.to_string())
+}
+
+fn event_name(discriminator: &[u8; 8]) -> Option<String> {
+    COMMON_EVENTS
+        .iter()
+        .find(|name| event_discriminator(name) == *discriminator)
+        .map(|name| name.to_string())
+}
+
+// 按鉴别器分组并推断字段；`skip` 为鉴别器之前的字节数（事件标签）
+fn analyze(
+    samples: &[InstructionSample],
+    skip: usize,
+    name: impl Fn(&[u8; 8]) -> Option<String>,
+    with_accounts: bool,
+) -> Vec<Candidate> {
+    let mut groups: BTreeMap<[u8; 8], Vec<&InstructionSample>> = BTreeMap::new();
+    for sample in samples {
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&sample.data[skip..skip + 8]);
+        groups.entry(discriminator).or_default().push(sample);
+    }
+    let mut candidates: Vec<Candidate> = groups
+        .into_iter()
+        .map(|(discriminator, group)| {
+            let payloads: Vec<&[u8]> = group.iter().map(|s| &s.data[skip + 8..]).collect();
+            let min_data_len = payloads.iter().map(|p| p.len()).min().unwrap_or(0);
+            let max_data_len = payloads.iter().map(|p| p.len()).max().unwrap_or(0);
+            Candidate {
+                discriminator,
+                anchor_name: name(&discriminator),
+                samples: group.len(),
+                min_data_len,
+                max_data_len,
+                fields: infer_fields(&group, &payloads, min_data_len),
+                accounts: if with_accounts {
+                    infer_accounts(&group)
+                } else {
+                    vec![]
+                },
+            }
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.samples
+            .cmp(&a.samples)
+            .then(a.discriminator.cmp(&b.discriminator))
+    });
+    candidates
+}
+
+fn infer_fields(group: &[&InstructionSample], payloads: &[&[u8]], len: usize) -> Vec<FieldGuess> {
+    let mut fields = vec![];
+    let mut offset = 0;
+    while offset < len {
+        let remaining = len - offset;
+        let is_pubkey = remaining >= 32
+            && group
+                .iter()
+                .zip(payloads)
+                .filter(|(sample, payload)| {
+                    let bytes = &payload[offset..offset + 32];
+                    sample
+                        .transaction_keys
+                        .iter()
+                        .any(|key| key.as_ref() == bytes)
+                })
+                .count()
+                * 2
+                > group.len();
+        let kind = if is_pubkey {
+            FieldKind::Pubkey
+        } else if remaining >= 8 {
+            FieldKind::U64
+        } else if payloads.iter().all(|payload| payload[offset] <= 1) {
+            FieldKind::Bool
+        } else {
+            FieldKind::U8
+        };
+        let size = kind.size();
+        let first = &payloads[0][offset..offset + size];
+        let constant = payloads
+            .iter()
+            .all(|payload| &payload[offset..offset + size] == first);
+        fields.push(FieldGuess {
+            name: format!("{}_{}", field_prefix(kind), offset),
+            offset,
+            kind,
+            constant,
+        });
+        offset += size;
+    }
+    fields
+}
+
+fn field_prefix(kind: FieldKind) -> &'static str {
+    match kind {
+        FieldKind::Pubkey => "pubkey",
+        FieldKind::U64 => "amount",
+        FieldKind::Bool => "flag",
+        FieldKind::U8 => "byte",
+    }
+}
+
+fn infer_accounts(group: &[&InstructionSample]) -> Vec<AccountRole> {
+    let count = group
+        .iter()
+        .map(|sample| sample.accounts.len())
+        .min()
+        .unwrap_or(0);
+    let mut used = std::collections::HashSet::new();
+    (0..count)
+        .map(|index| {
+            let first = &group[0].accounts[index];
+            let fixed = group
+                .iter()
+                .all(|sample| sample.accounts[index].pubkey == first.pubkey)
+                .then_some(first.pubkey);
+            let signer = group.iter().all(|sample| sample.accounts[index].is_signer);
+            let writable = group
+                .iter()
+                .all(|sample| sample.accounts[index].is_writable);
+            let known = fixed.and_then(|pubkey| {
+                let address = pubkey.to_string();
+                KNOWN_ACCOUNTS
+                    .iter()
+                    .find(|(known, _)| *known == address)
+                    .map(|(_, name)| name.to_string())
+            });
+            let name = match known {
+                Some(name) if used.insert(name.clone()) => name,
+                Some(name) => format!("{}_{}", name, index),
+                None if signer && index == 0 => "user".to_string(),
+                None if signer => format!("signer_{}", index),
+                None if fixed.is_some() => format!("fixed_account_{}", index),
+                None if writable => format!("writable_account_{}", index),
+                None => format!("account_{}", index),
+            };
+            AccountRole {
+                index,
+                name,
+                signer,
+                writable,
+                fixed,
+            }
+        })
+        .collect()
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn to_pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,268 @@
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::UnifiedEvent;
+use crate::streaming::journal::EventJournal;
+use crate::streaming::sink::RedactionConfig;
+
+/// Default number of records written per transaction
+pub const DEFAULT_SINK_BATCH_SIZE: usize = 500;
+
+/// 去重键 - 由稳定事件ID和事件在可重放数据源中的序号组成
+///
+/// The event ID is derived from the signature and instruction position, so it is
+/// the same on every run. The sequence is the position of the event in the source
+/// it is replayed from (the offset of an [`EventJournal`]), which is also stable
+/// on replay; `metadata.sequence` of a live stream restarts at 1 and is not.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DeliveryKey {
+    pub sequence: u64,
+    pub event_id: String,
+}
+
+impl DeliveryKey {
+    /// Key to store with the row (primary key, Kafka message key) so that a
+    /// repeated write of the same event is ignored
+    pub fn dedup_key(&self) -> String {
+        format!("{}:{}", self.sequence, self.event_id)
+    }
+}
+
+/// 待写入的一条记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct SinkRecord {
+    pub key: DeliveryKey,
+    pub event_type: String,
+    pub fields: Value,
+}
+
+/// 事务型输出端 - 记录和检查点在同一个事务中提交
+///
+/// Implemented by sinks that can make a batch of records and the delivery
+/// checkpoint visible atomically, e.g. one SQL transaction that inserts the rows
+/// with `ON CONFLICT (dedup_key) DO NOTHING` and updates a checkpoint table, or a
+/// transactional Kafka producer that writes the records and the checkpoint to a
+/// compacted topic. A crash between writing and checkpointing then can't leave
+/// rows whose checkpoint was lost, and the dedup key absorbs writes of a
+/// transaction whose commit outcome was unknown.
+pub trait TransactionalSink: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Sequence of the next record to deliver, as stored by the last committed
+    /// transaction; 0 if nothing was committed
+    fn committed_sequence(&self) -> AnyResult<u64>;
+
+    /// Write `records` and store `next_sequence` in one transaction
+    ///
+    /// Records whose dedup key was already written must be skipped, not fail the
+    /// transaction.
+    fn commit(&self, records: &[SinkRecord], next_sequence: u64) -> AnyResult<()>;
+}
+
+struct PendingBatch {
+    records: Vec<SinkRecord>,
+    // 待写入的下一个序号，包括未提交的批次
+    next_sequence: u64,
+    // 最近一次成功提交的下一个序号
+    committed_sequence: u64,
+}
+
+/// 恰好一次输出 - 批量写入事务型输出端并在同一事务中提交检查点
+///
+/// Events are identified by their sequence in a replayable source and must be
+/// written without gaps. Events below the next sequence are skipped, the rest are
+/// batched and committed together with the sequence after the last of them. After
+/// a crash, or a failed commit, [`Self::replay`] resumes from the committed
+/// sequence, so every event of the journal ends up in the sink exactly once.
+///
+/// The journal is synced before each commit, so the sink never checkpoints offsets
+/// a crash could take back from the journal. A sink whose checkpoint is ahead of
+/// the journal anyway (e.g. the journal directory was replaced) is an error: the
+/// journal would hand out those offsets again and the new events would be skipped.
+///
+/// # Usage Example
+/// ```ignore
+/// let journal = Arc::new(EventJournal::open("journal", JournalConfig::new())?);
+/// let writer = ExactlyOnceWriter::new(PostgresSink::connect(url)?)?;
+/// writer.replay(&journal)?;
+/// ```
+pub struct ExactlyOnceWriter<S: TransactionalSink> {
+    sink: S,
+    // 序号对应的日志，提交前先同步
+    journal: Option<Arc<EventJournal>>,
+    redaction: RedactionConfig,
+    batch_size: usize,
+    pending: Mutex<PendingBatch>,
+}
+
+impl<S: TransactionalSink> ExactlyOnceWriter<S> {
+    /// Wrap `sink`, resuming after its committed sequence
+    pub fn new(sink: S) -> AnyResult<Self> {
+        let next_sequence = sink.committed_sequence()?;
+        Ok(Self {
+            sink,
+            journal: None,
+            redaction: RedactionConfig::default(),
+            batch_size: DEFAULT_SINK_BATCH_SIZE,
+            pending: Mutex::new(PendingBatch {
+                records: Vec::new(),
+                next_sequence,
+                committed_sequence: next_sequence,
+            }),
+        })
+    }
+
+    /// Journal whose offsets are passed to [`Self::write`]; it is synced before
+    /// every commit. [`Self::replay`] uses the journal it is given.
+    pub fn with_journal(mut self, journal: Arc<EventJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    pub fn with_redaction(mut self, redaction: RedactionConfig) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    fn pending(&self) -> std::sync::MutexGuard<'_, PendingBatch> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Sequence of the next event to write, including the pending batch
+    pub fn next_sequence(&self) -> u64 {
+        self.pending().next_sequence
+    }
+
+    /// Sequence of the next event after the last committed batch
+    pub fn committed_sequence(&self) -> u64 {
+        self.pending().committed_sequence
+    }
+
+    /// Error if the sink committed sequences that `journal` doesn't hold
+    pub fn check_journal(&self, journal: &EventJournal) -> AnyResult<()> {
+        Self::check_offsets(&self.sink, self.committed_sequence(), journal)
+    }
+
+    fn check_offsets(sink: &S, committed_sequence: u64, journal: &EventJournal) -> AnyResult<()> {
+        let next_offset = journal.next_offset();
+        if committed_sequence > next_offset {
+            return Err(anyhow::anyhow!(
+                "{}: committed sequence {} is ahead of the journal, which ends at {}; \
+                 the journal lost events the sink already holds",
+                sink.name(),
+                committed_sequence,
+                next_offset
+            ));
+        }
+        Ok(())
+    }
+
+    /// Queue the event at `sequence`, committing when the batch is full
+    ///
+    /// Returns false for an event below the next sequence, which was already
+    /// written, and an error for an event after a gap, which needs a replay first.
+    pub fn write(&self, sequence: u64, event: &dyn UnifiedEvent) -> AnyResult<bool> {
+        self.write_to(sequence, event, self.journal.as_deref())
+    }
+
+    fn write_to(
+        &self,
+        sequence: u64,
+        event: &dyn UnifiedEvent,
+        journal: Option<&EventJournal>,
+    ) -> AnyResult<bool> {
+        let mut pending = self.pending();
+        if sequence < pending.next_sequence {
+            if let Some(journal) = journal {
+                Self::check_offsets(&self.sink, pending.committed_sequence, journal)?;
+            }
+            return Ok(false);
+        }
+        if sequence > pending.next_sequence {
+            return Err(anyhow::anyhow!(
+                "{}: sequence {} written before {}, replay the source first",
+                self.sink.name(),
+                sequence,
+                pending.next_sequence
+            ));
+        }
+        pending.records.push(SinkRecord {
+            key: DeliveryKey {
+                sequence,
+                event_id: event.id().to_string(),
+            },
+            event_type: event.event_type().to_string(),
+            fields: self.redaction.redact_event(event),
+        });
+        pending.next_sequence = sequence + 1;
+        if pending.records.len() >= self.batch_size {
+            self.commit_pending(&mut pending, journal)?;
+        }
+        Ok(true)
+    }
+
+    /// Commit the pending batch
+    pub fn flush(&self) -> AnyResult<()> {
+        self.flush_to(self.journal.as_deref())
+    }
+
+    fn flush_to(&self, journal: Option<&EventJournal>) -> AnyResult<()> {
+        let mut pending = self.pending();
+        self.commit_pending(&mut pending, journal)
+    }
+
+    fn commit_pending(
+        &self,
+        pending: &mut PendingBatch,
+        journal: Option<&EventJournal>,
+    ) -> AnyResult<()> {
+        if pending.records.is_empty() {
+            return Ok(());
+        }
+        // 日志先落盘，检查点才不会超过崩溃后日志的长度
+        let result = match journal {
+            Some(journal) => journal.flush(),
+            None => Ok(()),
+        }
+        .and_then(|()| self.sink.commit(&pending.records, pending.next_sequence));
+        pending.records.clear();
+        match result {
+            Ok(()) => {
+                pending.committed_sequence = pending.next_sequence;
+                Ok(())
+            }
+            Err(e) => {
+                // 回到已提交的位置，重放时重新写入这一批；
+                // 若提交其实已成功，去重键会忽略重复写入
+                pending.next_sequence = pending.committed_sequence;
+                Err(e)
+            }
+        }
+    }
+
+    /// Write the journal from the next sequence to its end and commit, returns the
+    /// number of events written
+    pub fn replay(&self, journal: &EventJournal) -> AnyResult<u64> {
+        self.check_journal(journal)?;
+        let mut written = 0;
+        for entry in journal.read_from(self.next_sequence())? {
+            let entry = entry?;
+            if self.write_to(entry.offset, entry.event.as_ref(), Some(journal))? {
+                written += 1;
+            }
+        }
+        self.flush_to(Some(journal))?;
+        Ok(written)
+    }
+}
@@ -0,0 +1,94 @@
+pub mod exactly_once;
+pub mod redaction;
+
+pub use exactly_once::{DeliveryKey, ExactlyOnceWriter, SinkRecord, TransactionalSink};
+pub use redaction::{RedactionAction, RedactionConfig, RedactionRule};
+
+use serde_json::Value;
+use std::io::Write;
+use std::sync::Mutex;
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::UnifiedEvent;
+
+/// 事件输出端 - 接收反射后的事件字段
+pub trait EventSink: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn write(&self, event_type: &str, fields: &Value) -> AnyResult<()>;
+
+    fn flush(&self) -> AnyResult<()> {
+        Ok(())
+    }
+}
+
+/// 带脱敏规则的输出端
+///
+/// Reflects each event, applies the sink's redaction rules and hands the result
+/// to the wrapped sink, so sensitive fields never reach it.
+pub struct SinkWriter {
+    sink: Box<dyn EventSink>,
+    redaction: RedactionConfig,
+}
+
+impl SinkWriter {
+    pub fn new(sink: impl EventSink + 'static) -> Self {
+        Self {
+            sink: Box::new(sink),
+            redaction: RedactionConfig::default(),
+        }
+    }
+
+    pub fn with_redaction(mut self, redaction: RedactionConfig) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    pub fn redaction(&self) -> &RedactionConfig {
+        &self.redaction
+    }
+
+    pub fn write_event(&self, event: &dyn UnifiedEvent) -> AnyResult<()> {
+        let fields = self.redaction.redact_event(event);
+        self.sink.write(&event.event_type().to_string(), &fields)
+    }
+
+    pub fn flush(&self) -> AnyResult<()> {
+        self.sink.flush()
+    }
+}
+
+/// JSON Lines输出端 - 每个事件一行
+pub struct JsonLinesSink<W: Write + Send> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> JsonLinesSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<W: Write + Send> EventSink for JsonLinesSink<W> {
+    fn name(&self) -> &str {
+        "json_lines"
+    }
+
+    fn write(&self, _event_type: &str, fields: &Value) -> AnyResult<()> {
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        serde_json::to_writer(&mut *writer, fields)?;
+        writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn flush(&self) -> AnyResult<()> {
+        self.writer.lock().unwrap_or_else(|e| e.into_inner()).flush()?;
+        Ok(())
+    }
+}
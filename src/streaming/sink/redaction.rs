@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::{
+    common::{
+        reflect::{event_fields, get_field_mut, visit_fields_named},
+        EventType,
+    },
+    UnifiedEvent,
+};
+
+const MASK: &str = "[redacted]";
+
+/// 字段脱敏方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionAction {
+    /// Drop the field from the output
+    Remove,
+    /// Replace the value with a fixed placeholder
+    Mask,
+    /// Replace the value with a salted SHA-256 hash, so records stay joinable
+    Hash,
+}
+
+/// 单条脱敏规则
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactionRule {
+    /// Field name matched at any depth (e.g. `user`), or a dot separated path from
+    /// the event root (e.g. `metadata.signature`)
+    pub field: String,
+    pub action: RedactionAction,
+    /// Event types the rule applies to; empty applies to all
+    #[serde(default)]
+    pub event_types: Vec<EventType>,
+}
+
+/// 输出端的字段脱敏配置
+///
+/// ```json
+/// {
+///   "salt": "analytics-2024",
+///   "rules": [
+///     { "field": "user", "action": "hash" },
+///     { "field": "payer", "action": "hash", "event_types": ["BonkBuyExactIn"] },
+///     { "field": "metadata.transfer_datas", "action": "remove" }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactionConfig {
+    #[serde(default)]
+    pub salt: String,
+    #[serde(default)]
+    pub rules: Vec<RedactionRule>,
+}
+
+impl RedactionConfig {
+    pub fn from_json(config: &str) -> AnyResult<Self> {
+        Ok(serde_json::from_str(config)?)
+    }
+
+    pub fn with_rule(mut self, rule: RedactionRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Reflect an event into JSON fields with the rules applied
+    pub fn redact_event(&self, event: &dyn UnifiedEvent) -> Value {
+        let mut fields = event_fields(event);
+        self.apply(&event.event_type(), &mut fields);
+        fields
+    }
+
+    /// Apply the rules to already reflected event fields
+    pub fn apply(&self, event_type: &EventType, fields: &mut Value) {
+        for rule in &self.rules {
+            if !rule.event_types.is_empty() && !rule.event_types.contains(event_type) {
+                continue;
+            }
+            if rule.field.contains('.') {
+                if rule.action == RedactionAction::Remove {
+                    Self::remove_path(fields, &rule.field);
+                } else if let Some(value) = get_field_mut(fields, &rule.field) {
+                    self.redact_value(rule.action, value);
+                }
+            } else if rule.action == RedactionAction::Remove {
+                Self::remove_named(fields, &rule.field);
+            } else {
+                visit_fields_named(fields, &rule.field, &mut |value| {
+                    self.redact_value(rule.action, value)
+                });
+            }
+        }
+    }
+
+    fn redact_value(&self, action: RedactionAction, value: &mut Value) {
+        *value = match action {
+            RedactionAction::Remove => Value::Null,
+            RedactionAction::Mask => Value::String(MASK.to_string()),
+            RedactionAction::Hash => {
+                let raw = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                let hash = solana_sdk::hash::hashv(&[self.salt.as_bytes(), raw.as_bytes()]);
+                Value::String(hash.to_string())
+            }
+        };
+    }
+
+    fn remove_path(fields: &mut Value, path: &str) {
+        let (parent, key) = match path.rsplit_once('.') {
+            Some((parent, key)) => (get_field_mut(fields, parent), key),
+            None => (Some(fields), path),
+        };
+        if let Some(Value::Object(object)) = parent {
+            object.remove(key);
+        }
+    }
+
+    fn remove_named(fields: &mut Value, name: &str) {
+        match fields {
+            Value::Object(object) => {
+                object.remove(name);
+                for value in object.values_mut() {
+                    Self::remove_named(value, name);
+                }
+            }
+            Value::Array(values) => {
+                for value in values {
+                    Self::remove_named(value, name);
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::sync::{Arc, Mutex};
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::impl_unified_event;
+use crate::streaming::block_events::{chain_metadata, BlockMetaEvent, SlotStatus, SlotUpdateEvent};
+use crate::streaming::event_parser::{
+    common::{ClockHandle, EventMetadata, EventType, ProtocolType},
+    UnifiedEvent,
+};
+use crate::streaming::pipeline::EventMiddleware;
+
+/// Default number of slots a slot may jump ahead without a known parent before the
+/// slots in between are reported, two leader windows
+pub const DEFAULT_MAX_SKIPPED_SLOTS: u64 = 8;
+
+/// slot缺口事件 - 流中缺少 `from..=to` 的slot
+///
+/// Emitted by [`SlotGapDetector`] for slots the stream never delivered although the
+/// chain produced (or may have produced) them, e.g. after a reconnect or a lagging
+/// provider. Backfill the range, e.g. with `streaming::backfill`; slots in it that
+/// the leader skipped simply have no transactions.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlotGapDetectedEvent {
+    pub metadata: EventMetadata,
+    /// First missing slot
+    pub from: u64,
+    /// Last missing slot, inclusive
+    pub to: u64,
+}
+
+impl_unified_event!(SlotGapDetectedEvent,);
+
+impl SlotGapDetectedEvent {
+    /// `slot` is the slot whose arrival revealed the gap
+    pub fn new(from: u64, to: u64, slot: u64, program_received_time_ms: i64) -> Self {
+        let mut metadata = chain_metadata(
+            slot,
+            None,
+            EventType::SlotGapDetected,
+            program_received_time_ms,
+        );
+        metadata.set_id(format!("slot_gap:{}:{}", from, to));
+        Self { metadata, from, to }
+    }
+
+    /// Number of missing slots
+    pub fn missing_slots(&self) -> u64 {
+        self.to - self.from + 1
+    }
+}
+
+#[derive(Default)]
+struct DetectorState {
+    highest_slot: Option<u64>,
+    gaps_detected: u64,
+    missing_slots: u64,
+}
+
+/// slot缺口检测器 - 跟踪流中出现的slot并报告缺失的区间
+///
+/// Slot updates and block meta carry the parent slot, which tells ledger skips from
+/// missed slots: when a slot arrives whose parent is newer than the highest slot
+/// seen, the slots from the one after the highest up to the parent were produced
+/// but never delivered, and a [`SlotGapDetectedEvent`] is emitted. Slots between
+/// the parent and the new slot were skipped by their leaders and are not reported.
+/// Dead slots, slots that don't move the stream forward and slot updates without a
+/// parent (first shred, created bank) are ignored.
+///
+/// Transaction events are only used with [`Self::with_event_slots`]. Without a
+/// parent a jump can't be told from skipped leaders, so only jumps of more than
+/// [`Self::with_max_skipped_slots`] slots are reported, and filtered streams
+/// legitimately miss slots without matching transactions; feed the detector the
+/// slot subscription (see `YellowstoneGrpc::with_slot_gap_detector`) instead where
+/// possible.
+///
+/// The handle is cheap to clone; keep a clone to read the counters.
+#[derive(Clone)]
+pub struct SlotGapDetector {
+    max_skipped_slots: u64,
+    event_slots: bool,
+    clock: ClockHandle,
+    state: Arc<Mutex<DetectorState>>,
+}
+
+impl Default for SlotGapDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for SlotGapDetector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SlotGapDetector")
+            .field("max_skipped_slots", &self.max_skipped_slots)
+            .field("event_slots", &self.event_slots)
+            .field("clock", &self.clock)
+            .field("highest_slot", &self.highest_slot())
+            .field("gaps_detected", &self.gaps_detected())
+            .finish()
+    }
+}
+
+impl SlotGapDetector {
+    pub fn new() -> Self {
+        Self {
+            max_skipped_slots: DEFAULT_MAX_SKIPPED_SLOTS,
+            event_slots: false,
+            clock: ClockHandle::default(),
+            state: Arc::new(Mutex::new(DetectorState::default())),
+        }
+    }
+
+    /// Slots a slot without a known parent may jump ahead before a gap is reported
+    pub fn with_max_skipped_slots(mut self, max_skipped_slots: u64) -> Self {
+        self.max_skipped_slots = max_skipped_slots;
+        self
+    }
+
+    /// Also track the slots of transaction events passed to [`EventMiddleware::handle`]
+    pub fn with_event_slots(mut self, event_slots: bool) -> Self {
+        self.event_slots = event_slots;
+        self
+    }
+
+    /// Clock the detection time of gap events is read from
+    pub fn with_clock(mut self, clock: ClockHandle) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, DetectorState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record a slot seen on the stream, returning the gap its arrival reveals
+    pub fn observe(&self, slot: u64, parent: Option<u64>) -> Option<SlotGapDetectedEvent> {
+        let mut state = self.state();
+        let Some(highest) = state.highest_slot else {
+            state.highest_slot = Some(slot);
+            return None;
+        };
+        if slot <= highest {
+            return None;
+        }
+        state.highest_slot = Some(slot);
+        let to = match parent {
+            Some(parent) if parent > highest => parent,
+            Some(_) => return None,
+            None if slot - highest - 1 > self.max_skipped_slots => slot - 1,
+            None => return None,
+        };
+        let gap = SlotGapDetectedEvent::new(highest + 1, to, slot, self.clock.now_ms());
+        state.gaps_detected += 1;
+        state.missing_slots += gap.missing_slots();
+        warn!(
+            "slots {}..={} missing from the stream, detected at slot {}",
+            gap.from, gap.to, slot
+        );
+        Some(gap)
+    }
+
+    /// Record a slot update or block meta event; other events are ignored
+    pub fn observe_event(&self, event: &dyn UnifiedEvent) -> Option<SlotGapDetectedEvent> {
+        let any = event.as_any();
+        if let Some(update) = any.downcast_ref::<SlotUpdateEvent>() {
+            if update.status == SlotStatus::Dead {
+                return None;
+            }
+            return update
+                .parent
+                .and_then(|parent| self.observe(update.metadata.slot, Some(parent)));
+        }
+        if let Some(meta) = any.downcast_ref::<BlockMetaEvent>() {
+            return self.observe(meta.block.slot, Some(meta.block.parent_slot));
+        }
+        None
+    }
+
+    /// Highest slot seen
+    pub fn highest_slot(&self) -> Option<u64> {
+        self.state().highest_slot
+    }
+
+    /// Gaps reported so far
+    pub fn gaps_detected(&self) -> u64 {
+        self.state().gaps_detected
+    }
+
+    /// Slots in the reported gaps
+    pub fn missing_slots(&self) -> u64 {
+        self.state().missing_slots
+    }
+}
+
+impl EventMiddleware for SlotGapDetector {
+    fn name(&self) -> &str {
+        "slot_gap_detector"
+    }
+
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        let gap = if event.metadata().protocol == ProtocolType::SDKSystem {
+            self.observe_event(event.as_ref())
+        } else if self.event_slots && event.slot() > 0 {
+            self.observe(event.slot(), None)
+        } else {
+            None
+        };
+        let mut events = vec![event];
+        if let Some(gap) = gap {
+            events.push(Box::new(gap));
+        }
+        events
+    }
+}
@@ -0,0 +1,108 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::UnifiedEvent;
+
+/// Default number of events buffered between the source and the consumer
+pub const DEFAULT_STREAM_CAPACITY: usize = 10_000;
+
+/// Callback handed to the source of a [`ParsedEventStream`]
+pub type StreamCallback = Box<dyn Fn(Box<dyn UnifiedEvent>) + Send + Sync>;
+
+/// 事件流 - 以 `futures::Stream` 的形式消费解析后的事件
+///
+/// Runs a callback based source (any of the `subscribe_*` methods) as its own task
+/// and yields its events, so they compose with `tokio::select!`, `StreamExt`
+/// combinators and `Sink`s. The stream ends when the source returns; its result is
+/// available from [`ParsedEventStream::join`].
+///
+/// Sources call back synchronously and must not be slowed down by a slow
+/// consumer, so events that arrive while the buffer is full are dropped and
+/// counted in [`ParsedEventStream::dropped`].
+///
+/// # Usage Example
+/// ```ignore
+/// let grpc = Arc::new(YellowstoneGrpc::new(endpoint, None)?);
+/// let mut events = ParsedEventStream::spawn(DEFAULT_STREAM_CAPACITY, move |callback| async move {
+///     grpc.subscribe_events_v2(protocols, None, include, vec![], vec![], None, callback).await
+/// });
+/// loop {
+///     tokio::select! {
+///         Some(event) = events.next() => handle(event),
+///         _ = shutdown.recv() => break,
+///     }
+/// }
+/// ```
+pub struct ParsedEventStream {
+    receiver: mpsc::Receiver<Box<dyn UnifiedEvent>>,
+    dropped: Arc<AtomicU64>,
+    task: JoinHandle<AnyResult<()>>,
+}
+
+impl ParsedEventStream {
+    /// Start `source` with a callback feeding this stream
+    pub fn spawn<S, Fut>(capacity: usize, source: S) -> Self
+    where
+        S: FnOnce(StreamCallback) -> Fut,
+        Fut: Future<Output = AnyResult<()>> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel(capacity.max(1));
+        let dropped = Arc::new(AtomicU64::new(0));
+        let counter = dropped.clone();
+        let callback: StreamCallback = Box::new(move |event: Box<dyn UnifiedEvent>| {
+            if sender.try_send(event).is_err() {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        let task = tokio::spawn(source(callback));
+        Self {
+            receiver,
+            dropped,
+            task,
+        }
+    }
+
+    /// Events dropped because the consumer fell behind
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Stop the source task
+    pub fn abort(&self) {
+        self.task.abort();
+    }
+
+    /// Stop reading and wait for the source to finish, returning its result
+    pub async fn join(self) -> AnyResult<()> {
+        drop(self.receiver);
+        self.task.await?
+    }
+
+    /// Send every event into `sink` until the stream or the sink ends
+    pub async fn forward<S>(self, mut sink: S) -> Result<(), S::Error>
+    where
+        S: Sink<Box<dyn UnifiedEvent>> + Unpin,
+    {
+        let mut events = self;
+        while let Some(event) = events.next().await {
+            sink.send(event).await?;
+        }
+        sink.close().await
+    }
+}
+
+impl Stream for ParsedEventStream {
+    type Item = Box<dyn UnifiedEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use yellowstone_grpc_proto::geyser::{
+    CommitmentLevel, SubscribeRequest, SubscribeRequestFilterBlocksMeta,
+    SubscribeRequestFilterTransactions,
+};
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::{
+    common::{EventType, FailedTxPolicy},
+    Protocol,
+};
+
+/// Name of the filters added by the builder
+pub const FILTER_NAME: &str = "client";
+
+/// Yellowstone订阅请求构造器
+///
+/// Derives the transaction filter from the protocols and event types to parse, so
+/// the programs of every parsed protocol are included, and adds user accounts on
+/// top. Votes and failed transactions are excluded by default.
+///
+/// ```ignore
+/// let request = SubscribeRequestBuilder::new()
+///     .with_protocols(&[Protocol::PumpFun])
+///     .with_event_types(&[EventType::RaydiumCpmmSwapBaseInput])
+///     .exclude_accounts([bot_wallet])
+///     .with_commitment(CommitmentLevel::Confirmed)
+///     .build()?;
+/// grpc.subscribe_events_with_request(protocols, None, request, callback).await?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscribeRequestBuilder {
+    account_include: Vec<String>,
+    account_exclude: Vec<String>,
+    account_required: Vec<String>,
+    vote: Option<bool>,
+    failed: Option<bool>,
+    commitment: CommitmentLevel,
+    blocks_meta: bool,
+}
+
+impl Default for SubscribeRequestBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SubscribeRequestBuilder {
+    pub fn new() -> Self {
+        Self {
+            account_include: vec![],
+            account_exclude: vec![],
+            account_required: vec![],
+            vote: Some(false),
+            failed: FailedTxPolicy::default().grpc_filter(),
+            commitment: CommitmentLevel::Processed,
+            blocks_meta: false,
+        }
+    }
+
+    /// Include transactions that touch the programs of `protocols`
+    pub fn with_protocols(mut self, protocols: &[Protocol]) -> Self {
+        for program_id in protocols.iter().flat_map(|p| p.get_program_id()) {
+            push_unique(&mut self.account_include, program_id.to_string());
+        }
+        self
+    }
+
+    /// Include the programs of the protocols emitting `event_types`
+    pub fn with_event_types(self, event_types: &[EventType]) -> Self {
+        let protocols: Vec<Protocol> = event_types
+            .iter()
+            .filter_map(Protocol::from_event_type)
+            .collect();
+        self.with_protocols(&protocols)
+    }
+
+    /// Include transactions that touch any of `accounts`
+    pub fn include_accounts(mut self, accounts: impl IntoIterator<Item = impl ToString>) -> Self {
+        for account in accounts {
+            push_unique(&mut self.account_include, account.to_string());
+        }
+        self
+    }
+
+    /// Drop transactions that touch any of `accounts`
+    pub fn exclude_accounts(mut self, accounts: impl IntoIterator<Item = impl ToString>) -> Self {
+        for account in accounts {
+            push_unique(&mut self.account_exclude, account.to_string());
+        }
+        self
+    }
+
+    /// Only keep transactions that touch all of `accounts`
+    pub fn require_accounts(mut self, accounts: impl IntoIterator<Item = impl ToString>) -> Self {
+        for account in accounts {
+            push_unique(&mut self.account_required, account.to_string());
+        }
+        self
+    }
+
+    /// `Some(false)` drops vote transactions (default), `None` keeps them
+    pub fn with_vote(mut self, vote: Option<bool>) -> Self {
+        self.vote = vote;
+        self
+    }
+
+    /// `Some(false)` drops failed transactions (default), `None` keeps them
+    pub fn with_failed(mut self, failed: Option<bool>) -> Self {
+        self.failed = failed;
+        self
+    }
+
+    /// Set the failed flag matching `policy`
+    pub fn with_failed_tx_policy(self, policy: FailedTxPolicy) -> Self {
+        self.with_failed(policy.grpc_filter())
+    }
+
+    pub fn with_commitment(mut self, commitment: CommitmentLevel) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    /// Also subscribe to block meta updates
+    pub fn with_blocks_meta(mut self, blocks_meta: bool) -> Self {
+        self.blocks_meta = blocks_meta;
+        self
+    }
+
+    /// Transaction filter of the request
+    pub fn transactions_filter(&self) -> SubscribeRequestFilterTransactions {
+        SubscribeRequestFilterTransactions {
+            vote: self.vote,
+            failed: self.failed,
+            signature: None,
+            account_include: self.account_include.clone(),
+            account_exclude: self.account_exclude.clone(),
+            account_required: self.account_required.clone(),
+        }
+    }
+
+    /// Build the request
+    ///
+    /// Fails without any account filter, since the request would stream every
+    /// transaction of the cluster.
+    pub fn build(&self) -> AnyResult<SubscribeRequest> {
+        if self.account_include.is_empty()
+            && self.account_exclude.is_empty()
+            && self.account_required.is_empty()
+        {
+            return Err(anyhow::anyhow!(
+                "account_include or account_exclude or account_required cannot be empty"
+            ));
+        }
+        let mut blocks_meta = HashMap::new();
+        if self.blocks_meta {
+            blocks_meta.insert(FILTER_NAME.to_string(), SubscribeRequestFilterBlocksMeta {});
+        }
+        Ok(SubscribeRequest {
+            transactions: HashMap::from([(FILTER_NAME.to_string(), self.transactions_filter())]),
+            blocks_meta,
+            commitment: Some(self.commitment as i32),
+            ..Default::default()
+        })
+    }
+}
+
+fn push_unique(values: &mut Vec<String>, value: String) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
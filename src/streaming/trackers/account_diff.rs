@@ -0,0 +1,345 @@
+use serde_json::{Map, Value};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+use crate::streaming::event_parser::{
+    common::{read_u64_le, read_u8, EventMetadata, EventType, InstructionIndex, ProtocolType},
+    protocols::pumpfun::parser::PUMPFUN_PROGRAM_ID,
+};
+use crate::streaming::trackers::{AccountDiffEvent, AccountFieldChange};
+
+/// 账户数据解码器 - 把原始账户数据解码为 (账户类型, 字段树)
+///
+/// Return `None` for accounts the decoder does not understand; they are skipped.
+pub type AccountDecoder = Arc<dyn Fn(&[u8]) -> Option<(String, Value)> + Send + Sync>;
+
+#[derive(Clone)]
+struct DecoderEntry {
+    protocol: ProtocolType,
+    decoder: AccountDecoder,
+}
+
+/// 上一次解码后的账户快照
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountSnapshot {
+    pub account_type: String,
+    pub slot: u64,
+    pub fields: Value,
+}
+
+/// 账户差异跟踪器
+///
+/// Keeps the last decoded state of every tracked account and, for each new account
+/// update, emits an [`AccountDiffEvent`] listing the fields that changed, so consumers
+/// don't have to retain previous snapshots themselves. Decoders are registered per
+/// owner program; PumpFun bonding curves are decoded out of the box.
+pub struct AccountDiffTracker {
+    decoders: RwLock<HashMap<Pubkey, DecoderEntry>>,
+    // 为空时跟踪所有有解码器的账户
+    tracked: RwLock<HashSet<Pubkey>>,
+    snapshots: RwLock<HashMap<Pubkey, AccountSnapshot>>,
+}
+
+impl Default for AccountDiffTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccountDiffTracker {
+    pub fn new() -> Self {
+        let tracker = Self {
+            decoders: RwLock::new(HashMap::new()),
+            tracked: RwLock::new(HashSet::new()),
+            snapshots: RwLock::new(HashMap::new()),
+        };
+        tracker.register_decoder(
+            PUMPFUN_PROGRAM_ID,
+            ProtocolType::PumpFun,
+            Arc::new(decode_pumpfun_bonding_curve),
+        );
+        tracker
+    }
+
+    /// Decode accounts owned by `owner` with `decoder`
+    pub fn with_decoder(
+        self,
+        owner: Pubkey,
+        protocol: ProtocolType,
+        decoder: AccountDecoder,
+    ) -> Self {
+        self.register_decoder(owner, protocol, decoder);
+        self
+    }
+
+    /// Only diff the given accounts instead of every account with a decoder
+    pub fn with_tracked_accounts(self, accounts: impl IntoIterator<Item = Pubkey>) -> Self {
+        self.tracked
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .extend(accounts);
+        self
+    }
+
+    pub fn register_decoder(&self, owner: Pubkey, protocol: ProtocolType, decoder: AccountDecoder) {
+        self.decoders
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(owner, DecoderEntry { protocol, decoder });
+    }
+
+    pub fn track(&self, account: Pubkey) {
+        self.tracked
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(account);
+    }
+
+    /// Stop tracking an account and drop its snapshot
+    pub fn untrack(&self, account: &Pubkey) -> Option<AccountSnapshot> {
+        self.tracked
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(account);
+        self.snapshots
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(account)
+    }
+
+    pub fn get_snapshot(&self, account: &Pubkey) -> Option<AccountSnapshot> {
+        self.snapshots
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(account)
+            .cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn is_tracked(&self, account: &Pubkey) -> bool {
+        let tracked = self.tracked.read().unwrap_or_else(|e| e.into_inner());
+        tracked.is_empty() || tracked.contains(account)
+    }
+
+    /// Apply a raw account update
+    ///
+    /// The first update of an account only records a snapshot. Later updates return
+    /// a diff event if at least one decoded field changed. Updates older than the
+    /// snapshot are ignored.
+    pub fn apply_update(
+        &self,
+        account: &Pubkey,
+        owner: &Pubkey,
+        data: &[u8],
+        slot: u64,
+        signature: Option<&str>,
+    ) -> Option<AccountDiffEvent> {
+        if !self.is_tracked(account) {
+            return None;
+        }
+        let entry = self
+            .decoders
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(owner)
+            .cloned()?;
+        let (account_type, fields) = (entry.decoder)(data)?;
+        self.apply_decoded(
+            account,
+            owner,
+            entry.protocol,
+            account_type,
+            fields,
+            slot,
+            signature,
+        )
+    }
+
+    /// Apply an account state that was already decoded by the caller
+    pub fn apply_decoded(
+        &self,
+        account: &Pubkey,
+        owner: &Pubkey,
+        protocol: ProtocolType,
+        account_type: String,
+        fields: Value,
+        slot: u64,
+        signature: Option<&str>,
+    ) -> Option<AccountDiffEvent> {
+        if !self.is_tracked(account) {
+            return None;
+        }
+        let mut snapshots = self.snapshots.write().unwrap_or_else(|e| e.into_inner());
+        let previous = snapshots.get(account);
+        if previous.is_some_and(|previous| slot < previous.slot) {
+            return None;
+        }
+        let event = previous
+            .filter(|previous| previous.account_type == account_type)
+            .map(|previous| (previous.slot, diff_fields(&previous.fields, &fields)))
+            .filter(|(_, changes)| !changes.is_empty())
+            .map(|(previous_slot, changes)| {
+                let signature = signature.unwrap_or_default().to_string();
+                let mut metadata = EventMetadata::new(
+                    String::new(),
+                    signature,
+                    slot,
+                    0,
+                    0,
+                    protocol,
+                    EventType::AccountDiff,
+                    *owner,
+                    InstructionIndex::account(),
+                    chrono::Utc::now().timestamp_millis(),
+                );
+                metadata.set_id(format!("{}-{}", account, slot));
+                AccountDiffEvent {
+                    metadata,
+                    account: *account,
+                    owner: *owner,
+                    account_type: account_type.clone(),
+                    previous_slot,
+                    changes,
+                }
+            });
+        snapshots.insert(
+            *account,
+            AccountSnapshot {
+                account_type,
+                slot,
+                fields,
+            },
+        );
+        event
+    }
+}
+
+/// Field level diff of two decoded account states, sorted by path
+pub fn diff_fields(old: &Value, new: &Value) -> Vec<AccountFieldChange> {
+    let (mut old_leaves, mut new_leaves) = (BTreeMap::new(), BTreeMap::new());
+    flatten("", old, &mut old_leaves);
+    flatten("", new, &mut new_leaves);
+
+    let mut paths: Vec<&String> = old_leaves.keys().chain(new_leaves.keys()).collect();
+    paths.sort();
+    paths.dedup();
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let old = old_leaves.get(path).cloned().unwrap_or(Value::Null);
+            let new = new_leaves.get(path).cloned().unwrap_or(Value::Null);
+            if old == new {
+                return None;
+            }
+            let delta = match (number_of(&old), number_of(&new)) {
+                (Some(old), Some(new)) => Some(new - old),
+                _ => None,
+            };
+            Some(AccountFieldChange {
+                path: path.clone(),
+                old,
+                new,
+                delta,
+            })
+        })
+        .collect()
+}
+
+fn number_of(value: &Value) -> Option<i128> {
+    value
+        .as_u64()
+        .map(i128::from)
+        .or_else(|| value.as_i64().map(i128::from))
+}
+
+fn flatten(prefix: &str, value: &Value, leaves: &mut BTreeMap<String, Value>) {
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        }
+    };
+    match value {
+        Value::Object(object) if !object.is_empty() => {
+            for (key, value) in object {
+                flatten(&join(key), value, leaves);
+            }
+        }
+        Value::Array(values) if !values.is_empty() => {
+            for (index, value) in values.iter().enumerate() {
+                flatten(&join(&index.to_string()), value, leaves);
+            }
+        }
+        _ => {
+            leaves.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+/// PumpFun bonding curve账户解码
+fn decode_pumpfun_bonding_curve(data: &[u8]) -> Option<(String, Value)> {
+    // 跳过8字节的anchor discriminator
+    let offset = 8;
+    let mut fields = Map::new();
+    fields.insert(
+        "virtual_token_reserves".into(),
+        read_u64_le(data, offset)?.into(),
+    );
+    fields.insert(
+        "virtual_sol_reserves".into(),
+        read_u64_le(data, offset + 8)?.into(),
+    );
+    fields.insert(
+        "real_token_reserves".into(),
+        read_u64_le(data, offset + 16)?.into(),
+    );
+    fields.insert(
+        "real_sol_reserves".into(),
+        read_u64_le(data, offset + 24)?.into(),
+    );
+    fields.insert(
+        "token_total_supply".into(),
+        read_u64_le(data, offset + 32)?.into(),
+    );
+    fields.insert("complete".into(), (read_u8(data, offset + 40)? == 1).into());
+    Some(("BondingCurve".to_string(), Value::Object(fields)))
+}
+
+#[cfg(feature = "grpc")]
+impl AccountDiffTracker {
+    /// Apply an account update from a Yellowstone account subscription
+    pub fn apply_grpc_update(
+        &self,
+        update: &yellowstone_grpc_proto::geyser::SubscribeUpdateAccount,
+    ) -> Option<AccountDiffEvent> {
+        let info = update.account.as_ref()?;
+        let account = Pubkey::try_from(info.pubkey.as_slice()).ok()?;
+        let owner = Pubkey::try_from(info.owner.as_slice()).ok()?;
+        let signature = info
+            .txn_signature
+            .as_ref()
+            .and_then(|signature| {
+                solana_sdk::signature::Signature::try_from(signature.as_slice()).ok()
+            })
+            .map(|signature| signature.to_string());
+        self.apply_update(
+            &account,
+            &owner,
+            &info.data,
+            update.slot,
+            signature.as_deref(),
+        )
+    }
+}
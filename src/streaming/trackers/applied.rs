@@ -0,0 +1,95 @@
+use std::collections::BTreeSet;
+use std::sync::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use serde::{Deserialize, Serialize};
+
+use crate::streaming::event_parser::common::EventMetadata;
+
+/// 跟踪器已应用到的位置 - 最高slot及该slot中已应用的事件ID
+///
+/// Events of earlier slots are assumed applied, so the position identifies every
+/// applied event as long as events are delivered in slot order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppliedPosition {
+    pub slot: u64,
+    pub event_ids: BTreeSet<String>,
+}
+
+impl AppliedPosition {
+    /// Whether the event at `slot` with `id` is covered by the position
+    pub fn contains(&self, slot: u64, id: &str) -> bool {
+        slot < self.slot || (slot == self.slot && self.event_ids.contains(id))
+    }
+
+    fn record(&mut self, slot: u64, id: &str) {
+        if slot > self.slot {
+            self.slot = slot;
+            self.event_ids.clear();
+        }
+        if slot == self.slot {
+            self.event_ids.insert(id.to_string());
+        }
+    }
+}
+
+#[derive(Default)]
+struct PositionState {
+    applied: AppliedPosition,
+    // 从快照恢复的位置，重放的事件若已包含在快照中则跳过
+    restored: Option<AppliedPosition>,
+}
+
+/// 跟踪器的应用守卫
+///
+/// Events are applied under a shared lock and snapshots are taken under the
+/// exclusive one, so a snapshot never sees an event half applied across the maps of
+/// a tracker. After a restore, events the snapshot already holds are skipped, so
+/// replaying the stream from before the snapshot doesn't apply them twice.
+#[derive(Default)]
+pub(crate) struct ApplyGuard {
+    gate: RwLock<()>,
+    position: Mutex<PositionState>,
+}
+
+impl ApplyGuard {
+    fn position(&self) -> std::sync::MutexGuard<'_, PositionState> {
+        self.position.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Start applying the event of `metadata`; None if the restored snapshot
+    /// already holds it
+    pub(crate) fn begin(&self, metadata: &EventMetadata) -> Option<RwLockReadGuard<'_, ()>> {
+        let gate = self.shared();
+        let mut position = self.position();
+        if position
+            .restored
+            .as_ref()
+            .is_some_and(|restored| restored.contains(metadata.slot, &metadata.id))
+        {
+            return None;
+        }
+        position.applied.record(metadata.slot, &metadata.id);
+        Some(gate)
+    }
+
+    /// Shared lock for updates that are idempotent, e.g. absolute account state
+    pub(crate) fn shared(&self) -> RwLockReadGuard<'_, ()> {
+        self.gate.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Exclusive lock, held while the state is copied or replaced
+    pub(crate) fn exclusive(&self) -> RwLockWriteGuard<'_, ()> {
+        self.gate.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub(crate) fn applied(&self) -> AppliedPosition {
+        self.position().applied.clone()
+    }
+
+    /// Continue from a restored position, skipping the events it covers
+    pub(crate) fn restore(&self, applied: AppliedPosition) {
+        let mut position = self.position();
+        position.restored = Some(applied.clone());
+        position.applied = applied;
+    }
+}
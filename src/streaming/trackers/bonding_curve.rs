@@ -0,0 +1,449 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::event_parser::{
+    common::{read_u64_le, read_u8, EventMetadata, EventType, ProtocolType},
+    protocols::{
+        bonk::{BonkPoolCreateEvent, BonkTradeEvent, PoolStatus},
+        pumpfun::{PumpFunCreateTokenEvent, PumpFunTradeEvent},
+    },
+    UnifiedEvent,
+};
+use crate::streaming::memory::MemoryBudget;
+use crate::streaming::pipeline::EventMiddleware;
+use crate::streaming::trackers::{
+    applied::{AppliedPosition, ApplyGuard},
+    derived_metadata, GraduationProgressEvent, PriceUpdatedEvent, ShardedMap,
+};
+
+/// PumpFun代币精度
+pub const PUMPFUN_TOKEN_DECIMALS: u8 = 6;
+/// PumpFun曲线初始的真实代币储备，耗尽时毕业
+pub const PUMPFUN_INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
+/// 默认的毕业进度阈值（百分比）
+pub const DEFAULT_GRADUATION_THRESHOLDS: [f64; 4] = [50.0, 75.0, 90.0, 100.0];
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+/// Size of the anchor account discriminator
+const ACCOUNT_DISCRIMINATOR_LEN: usize = 8;
+
+/// 单个mint的bonding curve状态
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BondingCurveState {
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub token_total_supply: u64,
+    pub complete: bool,
+    pub last_slot: u64,
+}
+
+impl BondingCurveState {
+    /// Price in SOL per whole token
+    pub fn price(&self) -> Option<f64> {
+        if self.virtual_token_reserves == 0 {
+            return None;
+        }
+        let sol = self.virtual_sol_reserves as f64 / LAMPORTS_PER_SOL;
+        let tokens =
+            self.virtual_token_reserves as f64 / 10f64.powi(PUMPFUN_TOKEN_DECIMALS as i32);
+        Some(sol / tokens)
+    }
+
+    /// Progress to graduation in percent (0-100)
+    pub fn progress_to_graduation(&self) -> f64 {
+        if self.complete {
+            return 100.0;
+        }
+        let remaining = self.real_token_reserves.min(PUMPFUN_INITIAL_REAL_TOKEN_RESERVES);
+        100.0 * (1.0 - remaining as f64 / PUMPFUN_INITIAL_REAL_TOKEN_RESERVES as f64)
+    }
+}
+
+/// Bonding curve 状态跟踪器
+///
+/// Maintains virtual/real reserves per PumpFun mint from trade and create events,
+/// and optionally from raw bonding curve account updates. Used as a pipeline stage
+/// it emits a [`PriceUpdatedEvent`] after every applied update, and a
+/// [`GraduationProgressEvent`] for every graduation threshold a PumpFun or Bonk
+/// trade moves a curve past. Moonshot has no parser in this crate, so its curves
+/// are not tracked.
+pub struct BondingCurveTracker {
+    curves: ShardedMap<Pubkey, BondingCurveState>,
+    // bonding curve账户 -> mint
+    curve_to_mint: ShardedMap<Pubkey, Pubkey>,
+    // mint -> 最近的毕业进度
+    progress: ShardedMap<Pubkey, f64>,
+    emit_price_updates: bool,
+    graduation_thresholds: Vec<f64>,
+    guard: ApplyGuard,
+    snapshot_name: String,
+}
+
+impl Default for BondingCurveTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BondingCurveTracker {
+    pub fn new() -> Self {
+        Self {
+            curves: ShardedMap::new(),
+            curve_to_mint: ShardedMap::new(),
+            progress: ShardedMap::new(),
+            emit_price_updates: true,
+            graduation_thresholds: DEFAULT_GRADUATION_THRESHOLDS.to_vec(),
+            guard: ApplyGuard::default(),
+            snapshot_name: "bonding_curve".to_string(),
+        }
+    }
+
+    /// Charge the bonding curves to `budget`, evicting least recently used entries while
+    /// it is exceeded
+    pub fn with_memory_budget(mut self, budget: &MemoryBudget) -> Self {
+        self.curves.set_memory_account(budget.account("bonding_curve.curves"));
+        self.curve_to_mint.set_memory_account(budget.account("bonding_curve.curve_to_mint"));
+        self.progress.set_memory_account(budget.account("bonding_curve.progress"));
+        self
+    }
+
+    /// Enable or disable emitting `PriceUpdatedEvent`s when used as a pipeline stage
+    pub fn with_price_updates(mut self, enabled: bool) -> Self {
+        self.emit_price_updates = enabled;
+        self
+    }
+
+    /// Progress thresholds in percent that emit a `GraduationProgressEvent`;
+    /// empty disables them. Defaults to [`DEFAULT_GRADUATION_THRESHOLDS`].
+    pub fn with_graduation_thresholds(mut self, thresholds: Vec<f64>) -> Self {
+        self.graduation_thresholds = thresholds;
+        self
+    }
+
+    /// Name the state is snapshotted under, "bonding_curve" by default; give each
+    /// tracker registered with the same snapshot manager its own name
+    pub fn with_snapshot_name(mut self, name: impl Into<String>) -> Self {
+        self.snapshot_name = name.into();
+        self
+    }
+
+    /// Position of the last applied event
+    pub fn applied_position(&self) -> AppliedPosition {
+        self.guard.applied()
+    }
+
+    pub fn get_state(&self, mint: &Pubkey) -> Option<BondingCurveState> {
+        self.curves.get(mint)
+    }
+
+    /// Current price in SOL per whole token
+    pub fn get_price(&self, mint: &Pubkey) -> Option<f64> {
+        self.get_state(mint).and_then(|state| state.price())
+    }
+
+    /// Progress to graduation in percent (0-100)
+    pub fn get_progress_to_graduation(&self, mint: &Pubkey) -> Option<f64> {
+        self.get_state(mint).map(|state| state.progress_to_graduation())
+    }
+
+    pub fn len(&self) -> usize {
+        self.curves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn remove(&self, mint: &Pubkey) -> Option<BondingCurveState> {
+        let _gate = self.guard.shared();
+        self.progress.remove(mint);
+        let state = self.curves.remove(mint)?;
+        self.curve_to_mint.remove(&state.bonding_curve);
+        Some(state)
+    }
+
+    /// Apply a PumpFun trade; returns the price update if the reserves changed
+    ///
+    /// Like the other event paths, trades already held by a restored snapshot are
+    /// ignored.
+    pub fn apply_trade(&self, trade: &PumpFunTradeEvent) -> Option<PriceUpdatedEvent> {
+        let _gate = self.guard.begin(&trade.metadata)?;
+        self.trade_update(trade)
+    }
+
+    fn trade_update(&self, trade: &PumpFunTradeEvent) -> Option<PriceUpdatedEvent> {
+        // 仅指令解析而未合并日志数据的事件没有储备信息
+        if trade.virtual_token_reserves == 0 {
+            return None;
+        }
+        let state = self.update(trade.mint, trade.bonding_curve, trade.metadata.slot, |state| {
+            state.virtual_sol_reserves = trade.virtual_sol_reserves;
+            state.virtual_token_reserves = trade.virtual_token_reserves;
+            state.real_sol_reserves = trade.real_sol_reserves;
+            state.real_token_reserves = trade.real_token_reserves;
+        })?;
+        Some(Self::price_updated(&trade.metadata, &state))
+    }
+
+    /// Seed the curve of a newly created token
+    pub fn apply_create(&self, create: &PumpFunCreateTokenEvent) -> Option<PriceUpdatedEvent> {
+        let _gate = self.guard.begin(&create.metadata)?;
+        self.create_update(create)
+    }
+
+    fn create_update(&self, create: &PumpFunCreateTokenEvent) -> Option<PriceUpdatedEvent> {
+        if create.virtual_token_reserves == 0 {
+            return None;
+        }
+        let state =
+            self.update(create.mint, create.bonding_curve, create.metadata.slot, |state| {
+                state.virtual_sol_reserves = create.virtual_sol_reserves;
+                state.virtual_token_reserves = create.virtual_token_reserves;
+                state.real_token_reserves = create.real_token_reserves;
+                state.token_total_supply = create.token_total_supply;
+            })?;
+        Some(Self::price_updated(&create.metadata, &state))
+    }
+
+    /// Apply a Bonk trade; returns a progress event per threshold it crossed, in
+    /// ascending order
+    ///
+    /// A LaunchLab pool migrates once all of `total_base_sell` is sold.
+    pub fn apply_bonk_trade(&self, trade: &BonkTradeEvent) -> Vec<GraduationProgressEvent> {
+        match self.guard.begin(&trade.metadata) {
+            Some(_gate) => self.bonk_progress(trade),
+            None => Vec::new(),
+        }
+    }
+
+    fn bonk_progress(&self, trade: &BonkTradeEvent) -> Vec<GraduationProgressEvent> {
+        // 仅指令解析而未合并日志数据的事件没有储备信息
+        if trade.total_base_sell == 0 {
+            return Vec::new();
+        }
+        let progress = if trade.pool_status == PoolStatus::Migrate {
+            100.0
+        } else {
+            100.0 * (trade.real_base_after as f64 / trade.total_base_sell as f64).min(1.0)
+        };
+        self.record_progress(
+            &trade.metadata,
+            ProtocolType::Bonk,
+            trade.base_token_mint,
+            trade.pool_state,
+            progress,
+        )
+    }
+
+    /// Record the progress of a mint, returning an event per threshold it crossed,
+    /// in ascending order
+    ///
+    /// Mints seen for the first time only record their progress, unless their
+    /// create event was seen, since the progress before is unknown. Progress that
+    /// falls back below a threshold and crosses it again emits again.
+    fn record_progress(
+        &self,
+        source: &EventMetadata,
+        protocol: ProtocolType,
+        mint: Pubkey,
+        bonding_curve: Pubkey,
+        progress: f64,
+    ) -> Vec<GraduationProgressEvent> {
+        let Some(previous_progress) = self.progress.insert(mint, progress) else {
+            return Vec::new();
+        };
+        let mut thresholds: Vec<f64> = self
+            .graduation_thresholds
+            .iter()
+            .copied()
+            .filter(|threshold| previous_progress < *threshold && progress >= *threshold)
+            .collect();
+        // 阈值可按任意顺序配置，一次跨过多个时按从低到高输出
+        thresholds.sort_by(f64::total_cmp);
+        thresholds.dedup();
+        thresholds
+            .into_iter()
+            .map(|threshold| GraduationProgressEvent {
+                metadata: derived_metadata(
+                    source,
+                    EventType::GraduationProgress,
+                    format!("{}:{}", mint, threshold),
+                ),
+                protocol: protocol.clone(),
+                mint,
+                bonding_curve,
+                threshold,
+                previous_progress,
+                progress,
+            })
+            .collect()
+    }
+
+    /// Apply a raw bonding curve account update
+    ///
+    /// The bonding curve must already be known from a trade or create event, since
+    /// the account itself does not store its mint.
+    pub fn update_from_account(
+        &self,
+        bonding_curve: &Pubkey,
+        data: &[u8],
+        slot: u64,
+    ) -> Option<BondingCurveState> {
+        let _gate = self.guard.shared();
+        let mint = self.curve_to_mint.get(bonding_curve)?;
+        let offset = ACCOUNT_DISCRIMINATOR_LEN;
+        let virtual_token_reserves = read_u64_le(data, offset)?;
+        let virtual_sol_reserves = read_u64_le(data, offset + 8)?;
+        let real_token_reserves = read_u64_le(data, offset + 16)?;
+        let real_sol_reserves = read_u64_le(data, offset + 24)?;
+        let token_total_supply = read_u64_le(data, offset + 32)?;
+        let complete = read_u8(data, offset + 40)? == 1;
+        self.update(mint, *bonding_curve, slot, |state| {
+            state.virtual_token_reserves = virtual_token_reserves;
+            state.virtual_sol_reserves = virtual_sol_reserves;
+            state.real_token_reserves = real_token_reserves;
+            state.real_sol_reserves = real_sol_reserves;
+            state.token_total_supply = token_total_supply;
+            state.complete = complete;
+        })
+    }
+
+    fn update<F>(
+        &self,
+        mint: Pubkey,
+        bonding_curve: Pubkey,
+        slot: u64,
+        apply: F,
+    ) -> Option<BondingCurveState>
+    where
+        F: FnOnce(&mut BondingCurveState),
+    {
+        let default = || BondingCurveState {
+            mint,
+            ..Default::default()
+        };
+        self.curves.upsert(mint, default, |state| {
+            // 忽略比当前状态更旧的更新
+            if slot < state.last_slot {
+                return None;
+            }
+            if bonding_curve != Pubkey::default() && state.bonding_curve != bonding_curve {
+                state.bonding_curve = bonding_curve;
+                self.curve_to_mint.insert(bonding_curve, mint);
+            }
+            apply(state);
+            state.last_slot = slot;
+            if state.real_token_reserves == 0 && state.virtual_token_reserves > 0 {
+                state.complete = true;
+            }
+            Some(state.clone())
+        })
+    }
+
+    /// Apply a supported event under the held guard, returning the derived events
+    fn apply_derived(&self, event: &dyn UnifiedEvent) -> Vec<Box<dyn UnifiedEvent>> {
+        let mut derived: Vec<Box<dyn UnifiedEvent>> = Vec::new();
+        if let Some(trade) = event.as_any().downcast_ref::<PumpFunTradeEvent>() {
+            if let Some(update) = self.trade_update(trade) {
+                let progress = self.record_progress(
+                    &trade.metadata,
+                    ProtocolType::PumpFun,
+                    trade.mint,
+                    update.bonding_curve,
+                    update.progress,
+                );
+                if self.emit_price_updates {
+                    derived.push(Box::new(update));
+                }
+                for progress in progress {
+                    derived.push(Box::new(progress));
+                }
+            }
+        } else if let Some(create) = event.as_any().downcast_ref::<PumpFunCreateTokenEvent>() {
+            if let Some(update) = self.create_update(create) {
+                self.progress.insert(create.mint, update.progress);
+                if self.emit_price_updates {
+                    derived.push(Box::new(update));
+                }
+            }
+        } else if let Some(trade) = event.as_any().downcast_ref::<BonkTradeEvent>() {
+            for progress in self.bonk_progress(trade) {
+                derived.push(Box::new(progress));
+            }
+        } else if let Some(create) = event.as_any().downcast_ref::<BonkPoolCreateEvent>() {
+            self.progress.insert(create.base_mint, 0.0);
+        }
+        derived
+    }
+
+    fn price_updated(source: &EventMetadata, state: &BondingCurveState) -> PriceUpdatedEvent {
+        PriceUpdatedEvent {
+            metadata: derived_metadata(source, EventType::PriceUpdated, state.mint.to_string()),
+            mint: state.mint,
+            bonding_curve: state.bonding_curve,
+            price: state.price().unwrap_or_default(),
+            virtual_sol_reserves: state.virtual_sol_reserves,
+            virtual_token_reserves: state.virtual_token_reserves,
+            real_sol_reserves: state.real_sol_reserves,
+            real_token_reserves: state.real_token_reserves,
+            progress: state.progress_to_graduation(),
+        }
+    }
+}
+
+#[cfg(feature = "snapshots")]
+#[derive(Serialize, Deserialize)]
+struct BondingCurveSnapshot {
+    curves: Vec<(Pubkey, BondingCurveState)>,
+    curve_to_mint: Vec<(Pubkey, Pubkey)>,
+    progress: Vec<(Pubkey, f64)>,
+    applied: AppliedPosition,
+}
+
+#[cfg(feature = "snapshots")]
+impl crate::streaming::trackers::SnapshotState for BondingCurveTracker {
+    fn snapshot_name(&self) -> &str {
+        &self.snapshot_name
+    }
+
+    fn snapshot_state(&self) -> crate::common::AnyResult<Vec<u8>> {
+        let _gate = self.guard.exclusive();
+        Ok(bincode::serialize(&BondingCurveSnapshot {
+            curves: self.curves.entries(),
+            curve_to_mint: self.curve_to_mint.entries(),
+            progress: self.progress.entries(),
+            applied: self.guard.applied(),
+        })?)
+    }
+
+    fn restore_state(&self, bytes: &[u8]) -> crate::common::AnyResult<()> {
+        let snapshot: BondingCurveSnapshot = bincode::deserialize(bytes)?;
+        let _gate = self.guard.exclusive();
+        self.curves.replace_all(snapshot.curves);
+        self.curve_to_mint.replace_all(snapshot.curve_to_mint);
+        self.progress.replace_all(snapshot.progress);
+        self.guard.restore(snapshot.applied);
+        Ok(())
+    }
+}
+
+impl EventMiddleware for BondingCurveTracker {
+    fn name(&self) -> &str {
+        "bonding_curve_tracker"
+    }
+
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        // 价格与进度在同一守卫下更新，快照不会只包含其中之一
+        let derived = match self.guard.begin(event.metadata()) {
+            Some(_gate) => self.apply_derived(event.as_ref()),
+            None => Vec::new(),
+        };
+        let mut events = vec![event];
+        events.extend(derived);
+        events
+    }
+}
@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::impl_unified_event;
+use crate::streaming::event_parser::common::{EventMetadata, ProtocolType};
+
+/// 价格更新事件 - 由BondingCurveTracker在曲线储备变化时生成
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PriceUpdatedEvent {
+    pub metadata: EventMetadata,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    /// Price in SOL per whole token
+    pub price: f64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    /// Progress to graduation in percent (0-100)
+    pub progress: f64,
+}
+
+impl_unified_event!(PriceUpdatedEvent,);
+
+/// 毕业进度事件 - 由BondingCurveTracker在交易使曲线越过进度阈值时生成
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraduationProgressEvent {
+    pub metadata: EventMetadata,
+    pub protocol: ProtocolType,
+    pub mint: Pubkey,
+    /// PumpFun bonding curve or Bonk pool state
+    pub bonding_curve: Pubkey,
+    /// Highest threshold crossed, in percent
+    pub threshold: f64,
+    /// Progress to migration in percent (0-100) before and after the trade
+    pub previous_progress: f64,
+    pub progress: f64,
+}
+
+impl_unified_event!(GraduationProgressEvent,);
+
+/// 新代币的一笔早期成交
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchTrade {
+    pub signature: String,
+    pub slot: u64,
+    pub trader: Pubkey,
+    pub is_buy: bool,
+    /// Raw amount of the launched token
+    pub base_amount: u64,
+    /// Raw amount of the quote mint
+    pub quote_amount: u64,
+    pub slots_since_creation: u64,
+    pub ms_since_creation: i64,
+}
+
+/// 首批成交快照事件 - 由LaunchSnapshotTracker在新代币成交满N笔或窗口结束时生成
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchSnapshotEvent {
+    pub metadata: EventMetadata,
+    /// Launchpad the token was created on
+    pub venue: ProtocolType,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub created_slot: u64,
+    pub created_at_ms: i64,
+    /// The first trades, in order
+    pub trades: Vec<LaunchTrade>,
+    /// False when the window passed before all trades were captured
+    pub complete: bool,
+}
+
+impl_unified_event!(LaunchSnapshotEvent,);
+
+/// 池子状态变化事件 - 由PoolTracker在储备变化时生成
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PoolStateChangedEvent {
+    pub metadata: EventMetadata,
+    pub pool: Pubkey,
+    pub protocol: ProtocolType,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_reserve: u64,
+    pub quote_reserve: u64,
+    /// Spot price in quote per whole base token, if known
+    pub price: Option<f64>,
+    /// Total value locked in whole quote tokens, if known
+    pub tvl: Option<f64>,
+    /// TVL in USD, if the quote mint has a USD price registered
+    #[serde(default)]
+    pub tvl_usd: Option<f64>,
+}
+
+impl_unified_event!(PoolStateChangedEvent,);
+
+/// 仓位平仓事件 - 由PnlTracker在钱包卖出全部持仓时生成
+///
+/// Amounts are in raw units: tokens in base units, cost and proceeds in the
+/// smallest unit of the quote mint (lamports for SOL pairs).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PositionClosedEvent {
+    pub metadata: EventMetadata,
+    pub wallet: Pubkey,
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub total_bought: u64,
+    pub total_sold: u64,
+    pub total_cost: u64,
+    pub total_proceeds: u64,
+    pub realized_pnl: i64,
+}
+
+impl_unified_event!(PositionClosedEvent,);
+
+/// 账户字段变化
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AccountFieldChange {
+    /// Dot separated field path, e.g. `virtual_sol_reserves` or `fee_config.lp_fee_bps`
+    pub path: String,
+    pub old: Value,
+    pub new: Value,
+    /// `new - old` when both values are integers
+    pub delta: Option<i128>,
+}
+
+/// 账户差异事件 - 由AccountDiffTracker在跟踪账户的解码状态变化时生成
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AccountDiffEvent {
+    pub metadata: EventMetadata,
+    pub account: Pubkey,
+    pub owner: Pubkey,
+    pub account_type: String,
+    /// Slot of the snapshot the changes are relative to
+    pub previous_slot: u64,
+    pub changes: Vec<AccountFieldChange>,
+}
+
+impl AccountDiffEvent {
+    pub fn change(&self, path: &str) -> Option<&AccountFieldChange> {
+        self.changes.iter().find(|change| change.path == path)
+    }
+}
+
+impl_unified_event!(AccountDiffEvent,);
+
+/// 钱包会话摘要
+///
+/// A burst of trading activity by one wallet. `net_sol_flow` is the SOL received
+/// minus the SOL spent in lamports, counting SOL-quoted trades only.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletSession {
+    pub wallet: Pubkey,
+    pub started_ms: i64,
+    pub last_activity_ms: i64,
+    pub first_slot: u64,
+    pub last_slot: u64,
+    pub trades: u32,
+    pub buys: u32,
+    pub sells: u32,
+    /// Mints traded in the session, in order of first trade
+    pub tokens: Vec<Pubkey>,
+    /// Protocols traded on in the session, in order of first trade
+    pub venues: Vec<ProtocolType>,
+    pub net_sol_flow: i64,
+}
+
+impl WalletSession {
+    pub fn duration_ms(&self) -> i64 {
+        self.last_activity_ms - self.started_ms
+    }
+}
+
+/// 钱包会话开始事件 - 由SessionTracker在钱包空闲后的第一笔交易时生成
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletSessionOpenedEvent {
+    pub metadata: EventMetadata,
+    pub wallet: Pubkey,
+    pub started_ms: i64,
+}
+
+impl_unified_event!(WalletSessionOpenedEvent,);
+
+/// 钱包会话结束事件 - 由SessionTracker在钱包空闲超过间隔后生成
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletSessionClosedEvent {
+    pub metadata: EventMetadata,
+    pub session: WalletSession,
+}
+
+impl_unified_event!(WalletSessionClosedEvent,);
@@ -0,0 +1,232 @@
+use solana_sdk::pubkey::Pubkey;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
+
+use crate::streaming::event_parser::{
+    common::{EventMetadata, EventType},
+    protocols::{
+        believe::BelieveCreateTokenEvent, bonk::BonkPoolCreateEvent, boop::BoopCreateTokenEvent,
+        meteora_dbc::MeteoraDbcInitializePoolEvent, pumpfun::PumpFunCreateTokenEvent,
+    },
+    UnifiedEvent,
+};
+use crate::streaming::memory::MemoryBudget;
+use crate::streaming::normalize::NormalizedTrade;
+use crate::streaming::pipeline::EventMiddleware;
+use crate::streaming::trackers::{derived_metadata, LaunchSnapshotEvent, LaunchTrade, ShardedMap};
+
+/// Default number of trades captured per launch
+pub const DEFAULT_LAUNCH_TRADES: usize = 20;
+/// Default time after creation at which an incomplete snapshot is emitted
+pub const DEFAULT_LAUNCH_WINDOW: Duration = Duration::from_secs(600);
+
+/// 新代币首批成交跟踪器
+///
+/// Captures the first N trades of every token created on PumpFun, Bonk, Boop,
+/// Meteora DBC or Believe, with their delay from the creation. Used as a pipeline
+/// stage it emits a [`LaunchSnapshotEvent`] once N trades are captured, or with
+/// fewer trades once the window after creation has passed, detected on the
+/// token's next trade or by a periodic sweep driven by the event stream.
+///
+/// Trades are read with [`NormalizedTrade::from_event`], so every venue the token
+/// trades on counts. Time is taken from the events like in `SessionTracker`.
+pub struct LaunchSnapshotTracker {
+    launches: ShardedMap<Pubkey, LaunchSnapshotEvent>,
+    trades_per_launch: usize,
+    window_ms: i64,
+    last_sweep_ms: AtomicI64,
+}
+
+impl Default for LaunchSnapshotTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LaunchSnapshotTracker {
+    pub fn new() -> Self {
+        Self {
+            launches: ShardedMap::new(),
+            trades_per_launch: DEFAULT_LAUNCH_TRADES,
+            window_ms: DEFAULT_LAUNCH_WINDOW.as_millis() as i64,
+            last_sweep_ms: AtomicI64::new(0),
+        }
+    }
+
+    /// Charge the launch snapshots to `budget`, evicting least recently used entries while
+    /// it is exceeded
+    pub fn with_memory_budget(mut self, budget: &MemoryBudget) -> Self {
+        self.launches.set_memory_account(budget.account("launch.launches"));
+        self
+    }
+
+    /// Number of trades after which the snapshot is emitted
+    pub fn with_trades_per_launch(mut self, trades: usize) -> Self {
+        self.trades_per_launch = trades.max(1);
+        self
+    }
+
+    /// Time after creation after which the snapshot is emitted with the trades so far
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window_ms = window.as_millis() as i64;
+        self
+    }
+
+    /// Snapshot in progress of a mint
+    pub fn get_launch(&self, mint: &Pubkey) -> Option<LaunchSnapshotEvent> {
+        self.launches.get(mint)
+    }
+
+    /// Number of launches still capturing trades
+    pub fn len(&self) -> usize {
+        self.launches.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Apply a create or trade event; returns the snapshot it completed
+    pub fn apply_event(&self, event: &dyn UnifiedEvent) -> Option<LaunchSnapshotEvent> {
+        if let Some((mint, creator)) = created_token(event) {
+            let metadata = event.metadata();
+            self.launches.insert(
+                mint,
+                LaunchSnapshotEvent {
+                    metadata: derived_metadata(
+                        metadata,
+                        EventType::LaunchSnapshot,
+                        format!("launch-{}", mint),
+                    ),
+                    venue: metadata.protocol.clone(),
+                    mint,
+                    creator,
+                    created_slot: metadata.slot,
+                    created_at_ms: event_time_ms(metadata),
+                    trades: Vec::with_capacity(self.trades_per_launch),
+                    complete: false,
+                },
+            );
+            return None;
+        }
+        let trade = match event.as_any().downcast_ref::<NormalizedTrade>() {
+            Some(trade) => trade.clone(),
+            None => NormalizedTrade::from_event(event)?,
+        };
+        let metadata = event.metadata();
+        let now = event_time_ms(metadata);
+        let finished = self.launches.update(&trade.base_mint, |launch| {
+            if now - launch.created_at_ms > self.window_ms {
+                return true;
+            }
+            launch.trades.push(LaunchTrade {
+                signature: metadata.signature.clone(),
+                slot: metadata.slot,
+                trader: trade.trader,
+                is_buy: trade.is_buy,
+                base_amount: if trade.is_buy {
+                    trade.amount_out
+                } else {
+                    trade.amount_in
+                },
+                quote_amount: if trade.is_buy {
+                    trade.amount_in
+                } else {
+                    trade.amount_out
+                },
+                slots_since_creation: metadata.slot.saturating_sub(launch.created_slot),
+                ms_since_creation: now - launch.created_at_ms,
+            });
+            launch.complete = launch.trades.len() >= self.trades_per_launch;
+            launch.complete
+        })?;
+        if !finished {
+            return None;
+        }
+        let mut snapshot = self.launches.remove(&trade.base_mint)?;
+        snapshot.metadata = Self::snapshot_metadata(metadata, &snapshot);
+        Some(snapshot)
+    }
+
+    /// Remove and return the launches whose window has passed at `now_ms`
+    pub fn close_expired(&self, now_ms: i64) -> Vec<LaunchSnapshotEvent> {
+        let mut expired = vec![];
+        self.launches.retain(|_, launch| {
+            if now_ms - launch.created_at_ms > self.window_ms {
+                expired.push(launch.clone());
+                return false;
+            }
+            true
+        });
+        expired
+    }
+
+    /// Metadata of the event that finished the snapshot
+    fn snapshot_metadata(source: &EventMetadata, snapshot: &LaunchSnapshotEvent) -> EventMetadata {
+        derived_metadata(
+            source,
+            EventType::LaunchSnapshot,
+            format!("launch-{}", snapshot.mint),
+        )
+    }
+}
+
+/// 创建代币事件的mint和创建者
+fn created_token(event: &dyn UnifiedEvent) -> Option<(Pubkey, Pubkey)> {
+    let any = event.as_any();
+    if let Some(e) = any.downcast_ref::<PumpFunCreateTokenEvent>() {
+        Some((e.mint, e.creator))
+    } else if let Some(e) = any.downcast_ref::<BonkPoolCreateEvent>() {
+        Some((e.base_mint, e.creator))
+    } else if let Some(e) = any.downcast_ref::<BoopCreateTokenEvent>() {
+        Some((e.mint, e.creator))
+    } else if let Some(e) = any.downcast_ref::<MeteoraDbcInitializePoolEvent>() {
+        Some((e.base_mint, e.creator))
+    } else if let Some(e) = any.downcast_ref::<BelieveCreateTokenEvent>() {
+        Some((e.base_mint, e.creator))
+    } else {
+        None
+    }
+}
+
+fn event_time_ms(metadata: &EventMetadata) -> i64 {
+    if metadata.block_time_ms > 0 {
+        metadata.block_time_ms
+    } else {
+        metadata.program_received_time_ms
+    }
+}
+
+impl EventMiddleware for LaunchSnapshotTracker {
+    fn name(&self) -> &str {
+        "launch_snapshot_tracker"
+    }
+
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        let completed = self.apply_event(event.as_ref());
+        let now = event_time_ms(event.metadata());
+        // 每个窗口最多扫描一次
+        let last_sweep = self.last_sweep_ms.load(Ordering::Relaxed);
+        let sweep = now - last_sweep >= self.window_ms
+            && self
+                .last_sweep_ms
+                .compare_exchange(last_sweep, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok();
+        let expired = if sweep {
+            self.close_expired(now)
+        } else {
+            vec![]
+        };
+
+        let mut events: Vec<Box<dyn UnifiedEvent>> = Vec::with_capacity(2 + expired.len());
+        for mut snapshot in expired {
+            snapshot.metadata = Self::snapshot_metadata(event.metadata(), &snapshot);
+            events.push(Box::new(snapshot));
+        }
+        events.push(event);
+        if let Some(snapshot) = completed {
+            events.push(Box::new(snapshot));
+        }
+        events
+    }
+}
@@ -0,0 +1,365 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::event_parser::{
+    protocols::bonk::{
+        parser::BONK_PROGRAM_ID, BonkPoolCreateEvent, BonkTradeEvent, CurveParams,
+        LaunchLabAccount, LaunchLabGlobalConfig, LaunchLabPlatformConfig, LaunchLabPoolState,
+        LAUNCHLAB_FEE_RATE_DENOMINATOR,
+    },
+    UnifiedEvent,
+};
+use crate::streaming::memory::MemoryBudget;
+use crate::streaming::pipeline::EventMiddleware;
+use crate::streaming::trackers::ShardedMap;
+
+/// 池子的曲线参数及其所属配置
+#[derive(Debug, Clone, Default)]
+struct PoolCurve {
+    global_config: Pubkey,
+    platform_config: Pubkey,
+    supply: u64,
+    total_base_sell: u64,
+    total_quote_fund_raising: u64,
+    migrate_type: u8,
+    last_slot: u64,
+}
+
+/// Bonk交易对应的曲线参数
+///
+/// Curve fields come from the pool account or its create event and are zero while
+/// neither was seen; configs are `None` until their accounts were applied.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BonkCurveParams {
+    pub pool_state: Pubkey,
+    pub global_config: Pubkey,
+    pub platform_config: Pubkey,
+    pub global: Option<LaunchLabGlobalConfig>,
+    pub platform: Option<LaunchLabPlatformConfig>,
+    pub supply: u64,
+    /// Base tokens sold on the curve before the pool migrates
+    pub total_base_sell: u64,
+    /// Quote raised at which the pool migrates
+    pub total_quote_fund_raising: u64,
+    /// 0 migrates to the Raydium AMM, 1 to CPMM
+    pub migrate_type: u8,
+}
+
+impl BonkCurveParams {
+    /// Protocol trade fee rate, in [`LAUNCHLAB_FEE_RATE_DENOMINATOR`] units
+    pub fn trade_fee_rate(&self) -> Option<u64> {
+        self.global.as_ref().map(|global| global.trade_fee_rate)
+    }
+
+    /// Platform fee rate, in [`LAUNCHLAB_FEE_RATE_DENOMINATOR`] units
+    pub fn platform_fee_rate(&self) -> Option<u64> {
+        self.platform.as_ref().map(|platform| platform.fee_rate)
+    }
+
+    /// Protocol and platform fee of a trade as a fraction of its quote amount
+    pub fn total_fee_ratio(&self) -> Option<f64> {
+        let rate = self.trade_fee_rate()? + self.platform_fee_rate()?;
+        Some(rate as f64 / LAUNCHLAB_FEE_RATE_DENOMINATOR as f64)
+    }
+
+    /// Progress to migration in percent (0-100) after `trade`, by quote raised
+    pub fn migration_progress(&self, trade: &BonkTradeEvent) -> Option<f64> {
+        if self.total_quote_fund_raising == 0 {
+            return None;
+        }
+        let raised = trade.real_quote_after as f64 / self.total_quote_fund_raising as f64;
+        Some(100.0 * raised.min(1.0))
+    }
+}
+
+/// LaunchLab配置跟踪器 - 把Bonk交易关联到手续费和迁移门槛
+///
+/// Bonk trade events carry amounts and fees paid but not the rates or thresholds
+/// they were computed with. Global config, platform config and pool state accounts
+/// are applied from an account subscription ([`Self::apply_account`]) or loaded
+/// over RPC ([`Self::load_from_rpc`]); used as a pipeline stage the tracker also
+/// learns each pool's configs and curve from create and trade events, and
+/// [`Self::missing_configs`] lists the configs still to load. Events pass through
+/// unchanged; look parameters up with [`Self::curve_params`].
+pub struct LaunchLabConfigTracker {
+    global_configs: RwLock<HashMap<Pubkey, LaunchLabGlobalConfig>>,
+    platform_configs: RwLock<HashMap<Pubkey, LaunchLabPlatformConfig>>,
+    pools: ShardedMap<Pubkey, PoolCurve>,
+}
+
+impl Default for LaunchLabConfigTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LaunchLabConfigTracker {
+    pub fn new() -> Self {
+        Self {
+            global_configs: RwLock::new(HashMap::new()),
+            platform_configs: RwLock::new(HashMap::new()),
+            pools: ShardedMap::new(),
+        }
+    }
+
+    /// Charge the pools to `budget`, evicting least recently used entries while it is
+    /// exceeded; configs are few and never evicted
+    pub fn with_memory_budget(mut self, budget: &MemoryBudget) -> Self {
+        self.pools
+            .set_memory_account(budget.account("launchlab.pools"));
+        self
+    }
+
+    pub fn get_global_config(&self, address: &Pubkey) -> Option<LaunchLabGlobalConfig> {
+        self.global_configs
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(address)
+            .cloned()
+    }
+
+    pub fn get_platform_config(&self, address: &Pubkey) -> Option<LaunchLabPlatformConfig> {
+        self.platform_configs
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(address)
+            .cloned()
+    }
+
+    /// Number of pools with known configs or curve
+    pub fn len(&self) -> usize {
+        self.pools.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Apply a raw account update, returning the decoded account
+    ///
+    /// Accounts not owned by the LaunchLab program are skipped, since other Anchor
+    /// programs share the `GlobalConfig` and `PoolState` discriminators. Pool updates
+    /// older than the pool's last update are ignored.
+    pub fn apply_account(
+        &self,
+        address: &Pubkey,
+        owner: &Pubkey,
+        data: &[u8],
+        slot: u64,
+    ) -> Option<LaunchLabAccount> {
+        if *owner != BONK_PROGRAM_ID {
+            return None;
+        }
+        let account = LaunchLabAccount::decode(data)?;
+        match &account {
+            LaunchLabAccount::GlobalConfig(config) => {
+                self.global_configs
+                    .write()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(*address, config.clone());
+            }
+            LaunchLabAccount::PlatformConfig(config) => {
+                self.platform_configs
+                    .write()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(*address, config.clone());
+            }
+            LaunchLabAccount::PoolState(pool) => self.apply_pool_state(address, pool, slot),
+        }
+        Some(account)
+    }
+
+    fn apply_pool_state(&self, address: &Pubkey, pool: &LaunchLabPoolState, slot: u64) {
+        self.pools.upsert(*address, PoolCurve::default, |curve| {
+            if slot < curve.last_slot {
+                return;
+            }
+            *curve = PoolCurve {
+                global_config: pool.global_config,
+                platform_config: pool.platform_config,
+                supply: pool.supply,
+                total_base_sell: pool.total_base_sell,
+                total_quote_fund_raising: pool.total_quote_fund_raising,
+                migrate_type: pool.migrate_type,
+                last_slot: slot,
+            };
+        });
+    }
+
+    /// Learn a pool's configs and curve from a Bonk create or trade event
+    pub fn apply_event(&self, event: &dyn UnifiedEvent) {
+        if let Some(create) = event.as_any().downcast_ref::<BonkPoolCreateEvent>() {
+            let (supply, total_base_sell, total_quote_fund_raising, migrate_type) =
+                match &create.curve_param {
+                    CurveParams::Constant { data } => (
+                        data.supply,
+                        data.total_base_sell,
+                        data.total_quote_fund_raising,
+                        data.migrate_type,
+                    ),
+                    CurveParams::Fixed { data } => (
+                        data.supply,
+                        0,
+                        data.total_quote_fund_raising,
+                        data.migrate_type,
+                    ),
+                    CurveParams::Linear { data } => (
+                        data.supply,
+                        0,
+                        data.total_quote_fund_raising,
+                        data.migrate_type,
+                    ),
+                };
+            self.pools
+                .upsert(create.pool_state, PoolCurve::default, |curve| {
+                    link(curve, create.global_config, create.platform_config);
+                    curve.supply = supply;
+                    if total_base_sell > 0 {
+                        curve.total_base_sell = total_base_sell;
+                    }
+                    curve.total_quote_fund_raising = total_quote_fund_raising;
+                    curve.migrate_type = migrate_type;
+                });
+        } else if let Some(trade) = event.as_any().downcast_ref::<BonkTradeEvent>() {
+            if trade.pool_state == Pubkey::default() {
+                return;
+            }
+            self.pools
+                .upsert(trade.pool_state, PoolCurve::default, |curve| {
+                    link(curve, trade.global_config, trade.platform_config);
+                    // 日志数据合并后交易带有total_base_sell
+                    if trade.total_base_sell > 0 {
+                        curve.total_base_sell = trade.total_base_sell;
+                    }
+                });
+        }
+    }
+
+    /// Curve parameters of the pool `trade` traded on
+    ///
+    /// Falls back to the configs named by the trade's own accounts for pools the
+    /// tracker hasn't seen.
+    pub fn curve_params(&self, trade: &BonkTradeEvent) -> Option<BonkCurveParams> {
+        let curve = self
+            .pools
+            .get(&trade.pool_state)
+            .unwrap_or_else(|| PoolCurve {
+                global_config: trade.global_config,
+                platform_config: trade.platform_config,
+                total_base_sell: trade.total_base_sell,
+                ..Default::default()
+            });
+        if curve.global_config == Pubkey::default() && curve.total_quote_fund_raising == 0 {
+            return None;
+        }
+        Some(BonkCurveParams {
+            pool_state: trade.pool_state,
+            global_config: curve.global_config,
+            platform_config: curve.platform_config,
+            global: self.get_global_config(&curve.global_config),
+            platform: self.get_platform_config(&curve.platform_config),
+            supply: curve.supply,
+            total_base_sell: curve.total_base_sell,
+            total_quote_fund_raising: curve.total_quote_fund_raising,
+            migrate_type: curve.migrate_type,
+        })
+    }
+
+    /// Global and platform configs referenced by known pools but not loaded yet
+    pub fn missing_configs(&self) -> Vec<Pubkey> {
+        let globals = self
+            .global_configs
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+        let platforms = self
+            .platform_configs
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+        let mut missing = Vec::new();
+        self.pools.for_each(|_, curve| {
+            if curve.global_config != Pubkey::default()
+                && !globals.contains_key(&curve.global_config)
+            {
+                missing.push(curve.global_config);
+            }
+            if curve.platform_config != Pubkey::default()
+                && !platforms.contains_key(&curve.platform_config)
+            {
+                missing.push(curve.platform_config);
+            }
+        });
+        missing.sort();
+        missing.dedup();
+        missing
+    }
+}
+
+/// 记录池子所属的配置，指令解析之外的事件没有配置账户
+fn link(curve: &mut PoolCurve, global_config: Pubkey, platform_config: Pubkey) {
+    if global_config != Pubkey::default() {
+        curve.global_config = global_config;
+    }
+    if platform_config != Pubkey::default() {
+        curve.platform_config = platform_config;
+    }
+}
+
+#[cfg(feature = "rpc")]
+impl LaunchLabConfigTracker {
+    /// Load LaunchLab accounts (configs or pools) over RPC, e.g. [`Self::missing_configs`]
+    ///
+    /// Returns the number of accounts applied; missing or non-LaunchLab accounts are
+    /// skipped.
+    pub async fn load_from_rpc(
+        &self,
+        client: &crate::common::SolanaRpcClient,
+        addresses: &[Pubkey],
+    ) -> crate::common::AnyResult<usize> {
+        let mut applied = 0;
+        // getMultipleAccounts最多接受100个地址
+        for chunk in addresses.chunks(100) {
+            let response = client
+                .get_multiple_accounts_with_commitment(chunk, client.commitment())
+                .await?;
+            let slot = response.context.slot;
+            for (address, account) in chunk.iter().zip(response.value) {
+                let Some(account) = account else { continue };
+                if self
+                    .apply_account(address, &account.owner, &account.data, slot)
+                    .is_some()
+                {
+                    applied += 1;
+                }
+            }
+        }
+        Ok(applied)
+    }
+}
+
+#[cfg(feature = "grpc")]
+impl LaunchLabConfigTracker {
+    /// Apply an account update from a Yellowstone account subscription
+    pub fn apply_grpc_update(
+        &self,
+        update: &yellowstone_grpc_proto::geyser::SubscribeUpdateAccount,
+    ) -> Option<LaunchLabAccount> {
+        let info = update.account.as_ref()?;
+        let address = Pubkey::try_from(info.pubkey.as_slice()).ok()?;
+        let owner = Pubkey::try_from(info.owner.as_slice()).ok()?;
+        self.apply_account(&address, &owner, &info.data, update.slot)
+    }
+}
+
+impl EventMiddleware for LaunchLabConfigTracker {
+    fn name(&self) -> &str {
+        "launchlab_config_tracker"
+    }
+
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        self.apply_event(event.as_ref());
+        vec![event]
+    }
+}
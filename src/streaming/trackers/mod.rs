@@ -0,0 +1,45 @@
+pub mod account_diff;
+pub mod applied;
+pub mod bonding_curve;
+pub mod events;
+pub mod launch;
+pub mod launchlab;
+pub mod pnl;
+pub mod pool;
+pub mod sessions;
+pub mod sharded;
+#[cfg(feature = "snapshots")]
+pub mod snapshot;
+
+pub use account_diff::{diff_fields, AccountDecoder, AccountDiffTracker, AccountSnapshot};
+pub use applied::AppliedPosition;
+pub use bonding_curve::{BondingCurveState, BondingCurveTracker, DEFAULT_GRADUATION_THRESHOLDS};
+pub use events::*;
+pub use launch::LaunchSnapshotTracker;
+pub use launchlab::{BonkCurveParams, LaunchLabConfigTracker};
+pub use pnl::{PnlTracker, Position};
+pub use pool::{PoolState, PoolTracker};
+pub use sessions::SessionTracker;
+pub use sharded::ShardedMap;
+#[cfg(feature = "snapshots")]
+pub use snapshot::{
+    FileSnapshotStore, SnapshotManager, SnapshotState, SnapshotStore, TrackerSnapshot,
+    DEFAULT_SNAPSHOTS_KEPT,
+};
+
+use crate::streaming::event_parser::common::{EventMetadata, EventType};
+
+/// 基于源事件元数据构造派生事件的元数据
+pub(crate) fn derived_metadata(
+    source: &EventMetadata,
+    event_type: EventType,
+    id: String,
+) -> EventMetadata {
+    let mut metadata = EventMetadata {
+        event_type,
+        transfer_datas: vec![],
+        ..source.clone()
+    };
+    metadata.set_id(id);
+    metadata
+}
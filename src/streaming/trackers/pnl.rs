@@ -0,0 +1,358 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+
+use crate::streaming::event_parser::{
+    common::{EventMetadata, EventType},
+    protocols::{
+        bonk::{BonkTradeEvent, TradeDirection},
+        pumpfun::PumpFunTradeEvent,
+        pumpswap::{PumpSwapBuyEvent, PumpSwapSellEvent},
+    },
+    UnifiedEvent,
+};
+use crate::streaming::memory::MemoryBudget;
+use crate::streaming::pipeline::EventMiddleware;
+use crate::streaming::trackers::{
+    applied::{AppliedPosition, ApplyGuard},
+    derived_metadata,
+    pool::WSOL_MINT,
+    PositionClosedEvent, ShardedMap,
+};
+
+/// 单个钱包在单个mint上的仓位
+///
+/// Amounts are in raw units: tokens in base units, cost and proceeds in the
+/// smallest unit of the quote mint.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+    pub wallet: Pubkey,
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    /// Tokens currently held, as far as the tracker has seen
+    pub balance: u64,
+    /// Cost basis of the remaining balance
+    pub cost_basis: u64,
+    pub total_bought: u64,
+    pub total_sold: u64,
+    pub total_cost: u64,
+    pub total_proceeds: u64,
+    pub realized_pnl: i64,
+    /// Tokens sold that were bought before tracking started (no known cost basis)
+    pub untracked_sold: u64,
+    pub last_slot: u64,
+}
+
+impl Position {
+    /// Unrealized PnL of the remaining balance at `price` (quote units per base unit)
+    pub fn unrealized_pnl(&self, price: f64) -> f64 {
+        self.balance as f64 * price - self.cost_basis as f64
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.balance > 0
+    }
+}
+
+/// 统一的买卖数据
+pub(crate) struct Fill {
+    pub(crate) wallet: Pubkey,
+    pub(crate) mint: Pubkey,
+    pub(crate) quote_mint: Pubkey,
+    pub(crate) is_buy: bool,
+    pub(crate) token_amount: u64,
+    pub(crate) quote_amount: u64,
+}
+
+impl Fill {
+    pub(crate) fn from_event(event: &dyn UnifiedEvent) -> Option<Self> {
+        let any = event.as_any();
+        if let Some(e) = any.downcast_ref::<PumpFunTradeEvent>() {
+            let fees = e.fee.saturating_add(e.creator_fee);
+            return Some(Self {
+                wallet: e.user,
+                mint: e.mint,
+                quote_mint: WSOL_MINT,
+                is_buy: e.is_buy,
+                token_amount: e.token_amount,
+                quote_amount: if e.is_buy {
+                    e.sol_amount.saturating_add(fees)
+                } else {
+                    e.sol_amount.saturating_sub(fees)
+                },
+            });
+        }
+        if let Some(e) = any.downcast_ref::<PumpSwapBuyEvent>() {
+            return Some(Self {
+                wallet: e.user,
+                mint: e.base_mint,
+                quote_mint: e.quote_mint,
+                is_buy: true,
+                token_amount: e.base_amount_out,
+                quote_amount: e.user_quote_amount_in,
+            });
+        }
+        if let Some(e) = any.downcast_ref::<PumpSwapSellEvent>() {
+            return Some(Self {
+                wallet: e.user,
+                mint: e.base_mint,
+                quote_mint: e.quote_mint,
+                is_buy: false,
+                token_amount: e.base_amount_in,
+                quote_amount: e.user_quote_amount_out,
+            });
+        }
+        if let Some(e) = any.downcast_ref::<BonkTradeEvent>() {
+            let is_buy = e.trade_direction == TradeDirection::Buy;
+            let (token_amount, quote_amount) =
+                if is_buy { (e.amount_out, e.amount_in) } else { (e.amount_in, e.amount_out) };
+            return Some(Self {
+                wallet: e.payer,
+                mint: e.base_token_mint,
+                quote_mint: e.quote_token_mint,
+                is_buy,
+                token_amount,
+                quote_amount,
+            });
+        }
+        None
+    }
+}
+
+/// 钱包盈亏跟踪器
+///
+/// Tracks average-cost positions per wallet and mint from PumpFun, PumpSwap and
+/// Bonk trades. Used as a pipeline stage it emits a [`PositionClosedEvent`] when a
+/// sell brings a wallet's balance back to zero.
+pub struct PnlTracker {
+    positions: ShardedMap<(Pubkey, Pubkey), Position>,
+    // 每个mint最近一次成交价格（quote最小单位 / token最小单位）
+    last_prices: ShardedMap<Pubkey, f64>,
+    emit_position_closed: bool,
+    guard: ApplyGuard,
+    snapshot_name: String,
+}
+
+impl Default for PnlTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PnlTracker {
+    pub fn new() -> Self {
+        Self {
+            positions: ShardedMap::new(),
+            last_prices: ShardedMap::new(),
+            emit_position_closed: true,
+            guard: ApplyGuard::default(),
+            snapshot_name: "pnl".to_string(),
+        }
+    }
+
+    /// Charge the positions and prices to `budget`, evicting least recently used entries while
+    /// it is exceeded
+    pub fn with_memory_budget(mut self, budget: &MemoryBudget) -> Self {
+        self.positions.set_memory_account(budget.account("pnl.positions"));
+        self.last_prices.set_memory_account(budget.account("pnl.last_prices"));
+        self
+    }
+
+    /// Enable or disable emitting `PositionClosedEvent`s when used as a pipeline stage
+    pub fn with_position_closed(mut self, enabled: bool) -> Self {
+        self.emit_position_closed = enabled;
+        self
+    }
+
+    /// Name the state is snapshotted under, "pnl" by default; give each tracker
+    /// registered with the same snapshot manager its own name
+    pub fn with_snapshot_name(mut self, name: impl Into<String>) -> Self {
+        self.snapshot_name = name.into();
+        self
+    }
+
+    /// Position of the last applied trade
+    pub fn applied_position(&self) -> AppliedPosition {
+        self.guard.applied()
+    }
+
+    pub fn get_position(&self, wallet: &Pubkey, mint: &Pubkey) -> Option<Position> {
+        self.positions.get(&(*wallet, *mint))
+    }
+
+    /// All positions of a wallet, open and closed
+    pub fn get_positions(&self, wallet: &Pubkey) -> Vec<Position> {
+        self.positions
+            .collect_values(|(position_wallet, _), _| position_wallet == wallet)
+    }
+
+    /// Last traded price of a mint in quote units per base unit
+    pub fn get_last_price(&self, mint: &Pubkey) -> Option<f64> {
+        self.last_prices.get(mint)
+    }
+
+    /// Unrealized PnL of a position marked at the mint's last traded price
+    pub fn get_unrealized_pnl(&self, wallet: &Pubkey, mint: &Pubkey) -> Option<f64> {
+        let position = self.get_position(wallet, mint)?;
+        let price = self.get_last_price(mint)?;
+        Some(position.unrealized_pnl(price))
+    }
+
+    /// Realized PnL of a wallet summed per quote mint
+    pub fn get_realized_pnl(&self, wallet: &Pubkey) -> HashMap<Pubkey, i64> {
+        let mut totals = HashMap::new();
+        for position in self.get_positions(wallet) {
+            *totals.entry(position.quote_mint).or_insert(0) += position.realized_pnl;
+        }
+        totals
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget all positions of a wallet
+    pub fn remove_wallet(&self, wallet: &Pubkey) {
+        let _gate = self.guard.shared();
+        self.positions
+            .retain(|(position_wallet, _), _| position_wallet != wallet);
+    }
+
+    /// Apply a trade event; returns the closed position if the trade emptied it
+    ///
+    /// Trades already held by a restored snapshot are ignored.
+    pub fn apply_event(&self, event: &dyn UnifiedEvent) -> Option<PositionClosedEvent> {
+        let fill = Fill::from_event(event)?;
+        if fill.wallet == Pubkey::default() || fill.token_amount == 0 {
+            return None;
+        }
+        let _gate = self.guard.begin(event.metadata())?;
+        let slot = event.slot();
+        self.last_prices.insert(
+            fill.mint,
+            fill.quote_amount as f64 / fill.token_amount as f64,
+        );
+
+        let default = || Position {
+            wallet: fill.wallet,
+            mint: fill.mint,
+            quote_mint: fill.quote_mint,
+            ..Default::default()
+        };
+        self.positions
+            .upsert((fill.wallet, fill.mint), default, |position| {
+                position.last_slot = position.last_slot.max(slot);
+                Self::apply_fill(position, &fill)
+                    .then(|| Self::position_closed(event.metadata(), position))
+            })
+    }
+
+    /// Apply a fill to a position; returns true if a sell emptied it
+    fn apply_fill(position: &mut Position, fill: &Fill) -> bool {
+        if fill.is_buy {
+            position.balance = position.balance.saturating_add(fill.token_amount);
+            position.cost_basis = position.cost_basis.saturating_add(fill.quote_amount);
+            position.total_bought = position.total_bought.saturating_add(fill.token_amount);
+            position.total_cost = position.total_cost.saturating_add(fill.quote_amount);
+            return false;
+        }
+
+        position.total_sold = position.total_sold.saturating_add(fill.token_amount);
+        position.total_proceeds = position.total_proceeds.saturating_add(fill.quote_amount);
+        if position.balance == 0 {
+            // 追踪开始之前买入的代币，没有成本信息
+            position.untracked_sold = position.untracked_sold.saturating_add(fill.token_amount);
+            return false;
+        }
+        // 只对已知成本的部分计算已实现盈亏
+        let sold = fill.token_amount.min(position.balance);
+        let proceeds = mul_div(fill.quote_amount, sold, fill.token_amount);
+        let cost = mul_div(position.cost_basis, sold, position.balance);
+        position.realized_pnl += proceeds as i64 - cost as i64;
+        position.cost_basis -= cost;
+        position.balance -= sold;
+        position.untracked_sold = position
+            .untracked_sold
+            .saturating_add(fill.token_amount - sold);
+
+        if position.balance > 0 {
+            return false;
+        }
+        position.cost_basis = 0;
+        true
+    }
+
+    fn position_closed(source: &EventMetadata, position: &Position) -> PositionClosedEvent {
+        PositionClosedEvent {
+            metadata: derived_metadata(
+                source,
+                EventType::PositionClosed,
+                format!("{}-{}", position.wallet, position.mint),
+            ),
+            wallet: position.wallet,
+            mint: position.mint,
+            quote_mint: position.quote_mint,
+            total_bought: position.total_bought,
+            total_sold: position.total_sold,
+            total_cost: position.total_cost,
+            total_proceeds: position.total_proceeds,
+            realized_pnl: position.realized_pnl,
+        }
+    }
+}
+
+fn mul_div(value: u64, numerator: u64, denominator: u64) -> u64 {
+    (value as u128 * numerator as u128 / denominator as u128) as u64
+}
+
+#[cfg(feature = "snapshots")]
+#[derive(Serialize, Deserialize)]
+struct PnlSnapshot {
+    positions: Vec<((Pubkey, Pubkey), Position)>,
+    last_prices: Vec<(Pubkey, f64)>,
+    applied: AppliedPosition,
+}
+
+#[cfg(feature = "snapshots")]
+impl crate::streaming::trackers::SnapshotState for PnlTracker {
+    fn snapshot_name(&self) -> &str {
+        &self.snapshot_name
+    }
+
+    fn snapshot_state(&self) -> crate::common::AnyResult<Vec<u8>> {
+        let _gate = self.guard.exclusive();
+        Ok(bincode::serialize(&PnlSnapshot {
+            positions: self.positions.entries(),
+            last_prices: self.last_prices.entries(),
+            applied: self.guard.applied(),
+        })?)
+    }
+
+    fn restore_state(&self, bytes: &[u8]) -> crate::common::AnyResult<()> {
+        let snapshot: PnlSnapshot = bincode::deserialize(bytes)?;
+        let _gate = self.guard.exclusive();
+        self.positions.replace_all(snapshot.positions);
+        self.last_prices.replace_all(snapshot.last_prices);
+        self.guard.restore(snapshot.applied);
+        Ok(())
+    }
+}
+
+impl EventMiddleware for PnlTracker {
+    fn name(&self) -> &str {
+        "pnl_tracker"
+    }
+
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        let closed = self.apply_event(event.as_ref());
+        let mut events = vec![event];
+        if let Some(closed) = closed.filter(|_| self.emit_position_closed) {
+            events.push(Box::new(closed));
+        }
+        events
+    }
+}
@@ -0,0 +1,567 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::event_parser::{
+    common::{EventMetadata, EventType, ProtocolType, TransferData},
+    protocols::{
+        meteora_damm::{MeteoraDammLiquidityEvent, MeteoraDammSwapEvent},
+        pumpswap::{
+            PumpSwapBuyEvent, PumpSwapCreatePoolEvent, PumpSwapDepositEvent, PumpSwapSellEvent,
+            PumpSwapWithdrawEvent,
+        },
+        raydium_clmm::{RaydiumClmmSwapEvent, RaydiumClmmSwapV2Event},
+        raydium_cpmm::RaydiumCpmmSwapEvent,
+    },
+    UnifiedEvent,
+};
+use crate::streaming::memory::MemoryBudget;
+use crate::streaming::normalize::{is_quote_side, quote_mint};
+use crate::streaming::pipeline::EventMiddleware;
+use crate::streaming::trackers::{
+    applied::{AppliedPosition, ApplyGuard},
+    derived_metadata, PoolStateChangedEvent, ShardedMap,
+};
+
+/// Wrapped SOL mint
+pub const WSOL_MINT: Pubkey = solana_sdk::pubkey!("So11111111111111111111111111111111111111112");
+/// USDC mint
+pub const USDC_MINT: Pubkey = solana_sdk::pubkey!("EPjFWdd5AufqSSqeM2qFxEwbTDsx8yHA3UUoqk5VPqQd");
+/// USDT mint
+pub const USDT_MINT: Pubkey = solana_sdk::pubkey!("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB");
+
+/// 单个池子的储备状态
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolState {
+    pub pool: Pubkey,
+    pub protocol: ProtocolType,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub base_reserve: u64,
+    pub quote_reserve: u64,
+    pub base_decimals: Option<u8>,
+    pub quote_decimals: Option<u8>,
+    /// Whether the absolute reserves are known (deltas alone are not enough)
+    pub reserves_known: bool,
+    /// Whether the base vault received an absolute balance
+    pub base_seeded: bool,
+    /// Whether the quote vault received an absolute balance
+    pub quote_seeded: bool,
+    /// Concentrated liquidity pools don't price off their vault balances
+    pub concentrated: bool,
+    pub last_slot: u64,
+}
+
+impl PoolState {
+    fn ui_amounts(&self) -> Option<(f64, f64)> {
+        if !self.reserves_known {
+            return None;
+        }
+        let base = self.base_reserve as f64 / 10f64.powi(self.base_decimals? as i32);
+        let quote = self.quote_reserve as f64 / 10f64.powi(self.quote_decimals? as i32);
+        Some((base, quote))
+    }
+
+    /// Spot price in quote per whole base token
+    pub fn price(&self) -> Option<f64> {
+        if self.concentrated {
+            return None;
+        }
+        let (base, quote) = self.ui_amounts()?;
+        if base == 0.0 {
+            return None;
+        }
+        Some(quote / base)
+    }
+
+    /// Total value locked in whole quote tokens
+    pub fn tvl(&self) -> Option<f64> {
+        let price = self.price()?;
+        let (base, quote) = self.ui_amounts()?;
+        Some(base * price + quote)
+    }
+
+    /// Total value locked in USD, if the quote mint has a USD price registered
+    pub fn tvl_usd(&self) -> Option<f64> {
+        let usd_price = quote_mint(&self.quote_mint)?.usd_price?;
+        Some(self.tvl()? * usd_price)
+    }
+}
+
+/// AMM池子储备与价格跟踪器
+///
+/// PumpSwap events carry the pool reserves, so PumpSwap pools are always exact.
+/// Raydium swap instructions don't, so those pools track vault deltas from the
+/// event transfer datas and only report reserves once both vault balances were
+/// seeded with [`PoolTracker::update_vault_balance`] (e.g. from account updates).
+/// Meteora DAMM pools keep their tokens in dynamic vaults shared by every pool of
+/// the mint, so a vault balance is not a pool reserve: swaps and liquidity changes
+/// track the deltas of the shared token vaults, and the reserves are only known
+/// once [`PoolTracker::apply_reserves`] set them (e.g. from the pool's vault LP
+/// share). Other AMMs can feed absolute reserves through `apply_reserves` as well.
+pub struct PoolTracker {
+    pools: ShardedMap<Pubkey, PoolState>,
+    // vault账户 -> pool
+    vault_to_pool: ShardedMap<Pubkey, Pubkey>,
+    emit_state_changes: bool,
+    guard: ApplyGuard,
+    snapshot_name: String,
+}
+
+impl Default for PoolTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PoolTracker {
+    pub fn new() -> Self {
+        Self {
+            pools: ShardedMap::new(),
+            vault_to_pool: ShardedMap::new(),
+            emit_state_changes: true,
+            guard: ApplyGuard::default(),
+            snapshot_name: "pool".to_string(),
+        }
+    }
+
+    /// Charge the pool states to `budget`, evicting least recently used entries while
+    /// it is exceeded
+    pub fn with_memory_budget(mut self, budget: &MemoryBudget) -> Self {
+        self.pools.set_memory_account(budget.account("pool.pools"));
+        self.vault_to_pool.set_memory_account(budget.account("pool.vault_to_pool"));
+        self
+    }
+
+    /// Enable or disable emitting `PoolStateChangedEvent`s when used as a pipeline stage
+    pub fn with_state_changes(mut self, enabled: bool) -> Self {
+        self.emit_state_changes = enabled;
+        self
+    }
+
+    /// Name the state is snapshotted under, "pool" by default; give each tracker
+    /// registered with the same snapshot manager its own name
+    pub fn with_snapshot_name(mut self, name: impl Into<String>) -> Self {
+        self.snapshot_name = name.into();
+        self
+    }
+
+    /// Position of the last applied event
+    pub fn applied_position(&self) -> AppliedPosition {
+        self.guard.applied()
+    }
+
+    pub fn get_state(&self, pool: &Pubkey) -> Option<PoolState> {
+        self.pools.get(pool)
+    }
+
+    /// Spot price in quote per whole base token
+    pub fn get_price(&self, pool: &Pubkey) -> Option<f64> {
+        self.get_state(pool)?.price()
+    }
+
+    /// Total value locked in whole quote tokens
+    pub fn get_tvl(&self, pool: &Pubkey) -> Option<f64> {
+        self.get_state(pool)?.tvl()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pools.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn remove(&self, pool: &Pubkey) -> Option<PoolState> {
+        let _gate = self.guard.shared();
+        let state = self.pools.remove(pool)?;
+        self.vault_to_pool.remove(&state.base_vault);
+        self.vault_to_pool.remove(&state.quote_vault);
+        Some(state)
+    }
+
+    /// Set absolute reserves for a pool
+    pub fn apply_reserves(
+        &self,
+        pool: Pubkey,
+        protocol: ProtocolType,
+        base_mint: Pubkey,
+        quote_mint: Pubkey,
+        base_reserve: u64,
+        quote_reserve: u64,
+        slot: u64,
+    ) -> Option<PoolState> {
+        let _gate = self.guard.shared();
+        self.update(pool, slot, |state| {
+            state.protocol = protocol;
+            Self::set_mints(state, base_mint, quote_mint);
+            state.base_reserve = base_reserve;
+            state.quote_reserve = quote_reserve;
+            state.reserves_known = true;
+        })
+    }
+
+    /// Set the absolute token balance of a known pool vault
+    ///
+    /// The reserves of a pool are known once both of its vaults were set; vault
+    /// deltas applied before that don't count.
+    pub fn update_vault_balance(&self, vault: &Pubkey, amount: u64, slot: u64) -> Option<PoolState> {
+        let _gate = self.guard.shared();
+        let pool = self.vault_to_pool.get(vault)?;
+        self.pools
+            .update(&pool, |state| {
+                if slot < state.last_slot {
+                    return None;
+                }
+                if state.base_vault == *vault {
+                    state.base_reserve = amount;
+                    state.base_seeded = true;
+                } else {
+                    state.quote_reserve = amount;
+                    state.quote_seeded = true;
+                }
+                state.last_slot = slot;
+                // 两个vault都收到过绝对余额后才认为储备已知，余额为0也算
+                state.reserves_known = state.base_seeded && state.quote_seeded;
+                Some(state.clone())
+            })
+            .flatten()
+    }
+
+    /// Apply a supported protocol event; returns the new pool state if it changed
+    ///
+    /// Events already held by a restored snapshot are ignored.
+    pub fn apply_event(&self, event: &dyn UnifiedEvent) -> Option<PoolState> {
+        let _gate = self.guard.begin(event.metadata())?;
+        self.apply_protocol_event(event)
+    }
+
+    fn apply_protocol_event(&self, event: &dyn UnifiedEvent) -> Option<PoolState> {
+        let any = event.as_any();
+        let slot = event.slot();
+        if let Some(e) = any.downcast_ref::<PumpSwapBuyEvent>() {
+            if e.pool_base_token_reserves == 0 {
+                return None;
+            }
+            return self.update(e.pool, slot, |state| {
+                Self::init_pumpswap(state, e.base_mint, e.quote_mint);
+                Self::set_vaults(state, e.pool_base_token_account, e.pool_quote_token_account);
+                state.base_reserve = e.pool_base_token_reserves.saturating_sub(e.base_amount_out);
+                state.quote_reserve = e
+                    .pool_quote_token_reserves
+                    .saturating_add(e.quote_amount_in_with_lp_fee);
+                state.reserves_known = true;
+            });
+        }
+        if let Some(e) = any.downcast_ref::<PumpSwapSellEvent>() {
+            if e.pool_base_token_reserves == 0 {
+                return None;
+            }
+            return self.update(e.pool, slot, |state| {
+                Self::init_pumpswap(state, e.base_mint, e.quote_mint);
+                Self::set_vaults(state, e.pool_base_token_account, e.pool_quote_token_account);
+                state.base_reserve = e.pool_base_token_reserves.saturating_add(e.base_amount_in);
+                state.quote_reserve = e
+                    .pool_quote_token_reserves
+                    .saturating_sub(e.quote_amount_out_without_lp_fee);
+                state.reserves_known = true;
+            });
+        }
+        if let Some(e) = any.downcast_ref::<PumpSwapCreatePoolEvent>() {
+            return self.update(e.pool, slot, |state| {
+                Self::init_pumpswap(state, e.base_mint, e.quote_mint);
+                Self::set_vaults(state, e.pool_base_token_account, e.pool_quote_token_account);
+                state.base_decimals = Some(e.base_mint_decimals);
+                state.quote_decimals = Some(e.quote_mint_decimals);
+                state.base_reserve = e.pool_base_amount;
+                state.quote_reserve = e.pool_quote_amount;
+                state.reserves_known = e.pool_base_amount > 0;
+            });
+        }
+        if let Some(e) = any.downcast_ref::<PumpSwapDepositEvent>() {
+            if e.pool_base_token_reserves == 0 {
+                return None;
+            }
+            return self.update(e.pool, slot, |state| {
+                Self::init_pumpswap(state, e.base_mint, e.quote_mint);
+                Self::set_vaults(state, e.pool_base_token_account, e.pool_quote_token_account);
+                state.base_reserve = e.pool_base_token_reserves.saturating_add(e.base_amount_in);
+                state.quote_reserve =
+                    e.pool_quote_token_reserves.saturating_add(e.quote_amount_in);
+                state.reserves_known = true;
+            });
+        }
+        if let Some(e) = any.downcast_ref::<PumpSwapWithdrawEvent>() {
+            if e.pool_base_token_reserves == 0 {
+                return None;
+            }
+            return self.update(e.pool, slot, |state| {
+                Self::init_pumpswap(state, e.base_mint, e.quote_mint);
+                Self::set_vaults(state, e.pool_base_token_account, e.pool_quote_token_account);
+                state.base_reserve = e.pool_base_token_reserves.saturating_sub(e.base_amount_out);
+                state.quote_reserve =
+                    e.pool_quote_token_reserves.saturating_sub(e.quote_amount_out);
+                state.reserves_known = true;
+            });
+        }
+        if let Some(e) = any.downcast_ref::<RaydiumCpmmSwapEvent>() {
+            return self.apply_vault_swap(
+                e.pool_state,
+                ProtocolType::RaydiumCpmm,
+                (e.input_token_mint, e.input_vault),
+                (e.output_token_mint, e.output_vault),
+                &e.metadata.transfer_datas,
+                slot,
+                false,
+            );
+        }
+        if let Some(e) = any.downcast_ref::<RaydiumClmmSwapV2Event>() {
+            return self.apply_vault_swap(
+                e.pool_state,
+                ProtocolType::RaydiumClmm,
+                (e.input_vault_mint, e.input_vault),
+                (e.output_vault_mint, e.output_vault),
+                &e.metadata.transfer_datas,
+                slot,
+                true,
+            );
+        }
+        if let Some(e) = any.downcast_ref::<RaydiumClmmSwapEvent>() {
+            // swap v1不携带mint，从转账数据中获取
+            let mint_of = |vault: &Pubkey| {
+                e.metadata
+                    .transfer_datas
+                    .iter()
+                    .find(|t| t.source == *vault || t.destination == *vault)
+                    .and_then(|t| t.mint)
+                    .unwrap_or_default()
+            };
+            return self.apply_vault_swap(
+                e.pool_state,
+                ProtocolType::RaydiumClmm,
+                (mint_of(&e.input_vault), e.input_vault),
+                (mint_of(&e.output_vault), e.output_vault),
+                &e.metadata.transfer_datas,
+                slot,
+                true,
+            );
+        }
+        if let Some(e) = any.downcast_ref::<MeteoraDammSwapEvent>() {
+            return self.apply_meteora_damm(
+                e.pool,
+                (e.a_token_vault, e.b_token_vault),
+                &e.metadata.transfer_datas,
+                slot,
+            );
+        }
+        if let Some(e) = any.downcast_ref::<MeteoraDammLiquidityEvent>() {
+            // 添加和移除共用事件结构，方向由vault转账决定
+            return self.apply_meteora_damm(
+                e.pool,
+                (e.a_token_vault, e.b_token_vault),
+                &e.metadata.transfer_datas,
+                slot,
+            );
+        }
+        None
+    }
+
+    fn apply_meteora_damm(
+        &self,
+        pool: Pubkey,
+        (a_token_vault, b_token_vault): (Pubkey, Pubkey),
+        transfer_datas: &[TransferData],
+        slot: u64,
+    ) -> Option<PoolState> {
+        // vault转账多为不带mint的transfer，mint在transferChecked时才可知
+        let mint_of = |vault: &Pubkey| {
+            transfer_datas
+                .iter()
+                .find(|t| t.source == *vault || t.destination == *vault)
+                .and_then(|t| t.mint)
+                .unwrap_or_default()
+        };
+        self.apply_vault_swap(
+            pool,
+            ProtocolType::MeteoraDamm,
+            (mint_of(&a_token_vault), a_token_vault),
+            (mint_of(&b_token_vault), b_token_vault),
+            transfer_datas,
+            slot,
+            false,
+        )
+    }
+
+    fn apply_vault_swap(
+        &self,
+        pool: Pubkey,
+        protocol: ProtocolType,
+        (input_mint, input_vault): (Pubkey, Pubkey),
+        (output_mint, output_vault): (Pubkey, Pubkey),
+        transfer_datas: &[TransferData],
+        slot: u64,
+        concentrated: bool,
+    ) -> Option<PoolState> {
+        self.update(pool, slot, |state| {
+            state.protocol = protocol;
+            state.concentrated = concentrated;
+            if state.base_vault == Pubkey::default() {
+                // 注册的报价代币作为quote，其余情况以输出代币作为base
+                let (base, quote) = if is_quote_side(&input_mint, &output_mint) {
+                    ((output_mint, output_vault), (input_mint, input_vault))
+                } else {
+                    ((input_mint, input_vault), (output_mint, output_vault))
+                };
+                Self::set_mints(state, base.0, quote.0);
+                Self::set_vaults(state, base.1, quote.1);
+            }
+            for transfer in transfer_datas {
+                for (vault, reserve, decimals) in [
+                    (state.base_vault, &mut state.base_reserve, &mut state.base_decimals),
+                    (state.quote_vault, &mut state.quote_reserve, &mut state.quote_decimals),
+                ] {
+                    if transfer.destination == vault {
+                        *reserve = reserve.saturating_add(transfer.amount);
+                    } else if transfer.source == vault {
+                        *reserve = reserve.saturating_sub(transfer.amount);
+                    } else {
+                        continue;
+                    }
+                    if transfer.decimals.is_some() {
+                        *decimals = transfer.decimals;
+                    }
+                }
+            }
+        })
+    }
+
+    fn init_pumpswap(state: &mut PoolState, base_mint: Pubkey, quote_mint: Pubkey) {
+        state.protocol = ProtocolType::PumpSwap;
+        Self::set_mints(state, base_mint, quote_mint);
+        // PumpSwap池子大多是pump.fun毕业代币(6位精度)与WSOL
+        if state.base_decimals.is_none() && state.quote_mint == WSOL_MINT {
+            state.base_decimals = Some(6);
+        }
+    }
+
+    fn set_mints(state: &mut PoolState, base_mint: Pubkey, quote_mint: Pubkey) {
+        if base_mint != Pubkey::default() {
+            state.base_mint = base_mint;
+        }
+        if quote_mint != Pubkey::default() {
+            state.quote_mint = quote_mint;
+        }
+        if state.quote_decimals.is_none() {
+            state.quote_decimals = self::quote_mint(&state.quote_mint).map(|quote| quote.decimals);
+        }
+    }
+
+    fn set_vaults(state: &mut PoolState, base_vault: Pubkey, quote_vault: Pubkey) {
+        if base_vault != Pubkey::default() {
+            state.base_vault = base_vault;
+        }
+        if quote_vault != Pubkey::default() {
+            state.quote_vault = quote_vault;
+        }
+    }
+
+    fn update<F>(&self, pool: Pubkey, slot: u64, apply: F) -> Option<PoolState>
+    where
+        F: FnOnce(&mut PoolState),
+    {
+        let default = || PoolState {
+            pool,
+            ..Default::default()
+        };
+        self.pools.upsert(pool, default, |state| {
+            if slot < state.last_slot {
+                return None;
+            }
+            let (base_vault, quote_vault) = (state.base_vault, state.quote_vault);
+            apply(state);
+            state.last_slot = slot;
+            // Meteora动态vault由多个池子共享，其余额不是单个池子的储备，不登记
+            let vaults_changed = state.base_vault != base_vault || state.quote_vault != quote_vault;
+            if vaults_changed && state.protocol != ProtocolType::MeteoraDamm {
+                for vault in [state.base_vault, state.quote_vault] {
+                    if vault != Pubkey::default() {
+                        self.vault_to_pool.insert(vault, pool);
+                    }
+                }
+            }
+            Some(state.clone())
+        })
+    }
+
+    fn state_changed(source: &EventMetadata, state: &PoolState) -> PoolStateChangedEvent {
+        PoolStateChangedEvent {
+            metadata: derived_metadata(
+                source,
+                EventType::PoolStateChanged,
+                state.pool.to_string(),
+            ),
+            pool: state.pool,
+            protocol: state.protocol.clone(),
+            base_mint: state.base_mint,
+            quote_mint: state.quote_mint,
+            base_reserve: state.base_reserve,
+            quote_reserve: state.quote_reserve,
+            price: state.price(),
+            tvl: state.tvl(),
+            tvl_usd: state.tvl_usd(),
+        }
+    }
+}
+
+#[cfg(feature = "snapshots")]
+#[derive(Serialize, Deserialize)]
+struct PoolSnapshot {
+    pools: Vec<(Pubkey, PoolState)>,
+    vault_to_pool: Vec<(Pubkey, Pubkey)>,
+    applied: AppliedPosition,
+}
+
+#[cfg(feature = "snapshots")]
+impl crate::streaming::trackers::SnapshotState for PoolTracker {
+    fn snapshot_name(&self) -> &str {
+        &self.snapshot_name
+    }
+
+    fn snapshot_state(&self) -> crate::common::AnyResult<Vec<u8>> {
+        let _gate = self.guard.exclusive();
+        Ok(bincode::serialize(&PoolSnapshot {
+            pools: self.pools.entries(),
+            vault_to_pool: self.vault_to_pool.entries(),
+            applied: self.guard.applied(),
+        })?)
+    }
+
+    fn restore_state(&self, bytes: &[u8]) -> crate::common::AnyResult<()> {
+        let snapshot: PoolSnapshot = bincode::deserialize(bytes)?;
+        let _gate = self.guard.exclusive();
+        self.pools.replace_all(snapshot.pools);
+        self.vault_to_pool.replace_all(snapshot.vault_to_pool);
+        self.guard.restore(snapshot.applied);
+        Ok(())
+    }
+}
+
+impl EventMiddleware for PoolTracker {
+    fn name(&self) -> &str {
+        "pool_tracker"
+    }
+
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        let changed = self
+            .apply_event(event.as_ref())
+            .filter(|_| self.emit_state_changes)
+            .map(|state| Self::state_changed(event.metadata(), &state));
+        let mut events: Vec<Box<dyn UnifiedEvent>> = vec![event];
+        if let Some(changed) = changed {
+            events.push(Box::new(changed));
+        }
+        events
+    }
+}
@@ -0,0 +1,211 @@
+use solana_sdk::pubkey::Pubkey;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
+
+use crate::streaming::event_parser::{
+    common::{EventMetadata, EventType},
+    UnifiedEvent,
+};
+use crate::streaming::memory::MemoryBudget;
+use crate::streaming::pipeline::EventMiddleware;
+use crate::streaming::trackers::{
+    derived_metadata, pnl::Fill, pool::WSOL_MINT, ShardedMap, WalletSession,
+    WalletSessionClosedEvent, WalletSessionOpenedEvent,
+};
+
+/// Default idle gap that ends a session
+pub const DEFAULT_SESSION_IDLE_GAP: Duration = Duration::from_secs(300);
+
+/// 钱包会话跟踪器
+///
+/// Groups each wallet's PumpFun, PumpSwap and Bonk trades into sessions: bursts of
+/// activity separated by more than the idle gap. Used as a pipeline stage it emits
+/// a [`WalletSessionOpenedEvent`] on a session's first trade and a
+/// [`WalletSessionClosedEvent`] once the wallet has been idle for longer than the
+/// gap, detected either on its next trade or by a periodic sweep driven by the
+/// event stream.
+///
+/// Time is taken from the events (`block_time_ms`, falling back to
+/// `program_received_time_ms`), so replays produce the same sessions as live
+/// streams.
+pub struct SessionTracker {
+    sessions: ShardedMap<Pubkey, WalletSession>,
+    idle_gap_ms: i64,
+    emit_opened: bool,
+    last_sweep_ms: AtomicI64,
+}
+
+impl Default for SessionTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionTracker {
+    pub fn new() -> Self {
+        Self {
+            sessions: ShardedMap::new(),
+            idle_gap_ms: DEFAULT_SESSION_IDLE_GAP.as_millis() as i64,
+            emit_opened: true,
+            last_sweep_ms: AtomicI64::new(0),
+        }
+    }
+
+    /// Charge the wallet sessions to `budget`, evicting least recently used entries while
+    /// it is exceeded
+    pub fn with_memory_budget(mut self, budget: &MemoryBudget) -> Self {
+        self.sessions.set_memory_account(budget.account("sessions.sessions"));
+        self
+    }
+
+    /// Inactivity after which a wallet's session is closed
+    pub fn with_idle_gap(mut self, idle_gap: Duration) -> Self {
+        self.idle_gap_ms = idle_gap.as_millis() as i64;
+        self
+    }
+
+    /// Enable or disable emitting `WalletSessionOpenedEvent`s when used as a pipeline stage
+    pub fn with_session_opened(mut self, enabled: bool) -> Self {
+        self.emit_opened = enabled;
+        self
+    }
+
+    /// Current session of a wallet
+    pub fn get_session(&self, wallet: &Pubkey) -> Option<WalletSession> {
+        self.sessions.get(wallet)
+    }
+
+    /// Number of open sessions
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Apply a trade event; returns the session events it produced
+    pub fn apply_event(&self, event: &dyn UnifiedEvent) -> Vec<Box<dyn UnifiedEvent>> {
+        let Some(fill) = Fill::from_event(event) else {
+            return vec![];
+        };
+        if fill.wallet == Pubkey::default() {
+            return vec![];
+        }
+        let metadata = event.metadata();
+        let now = event_time_ms(metadata);
+        let default = || WalletSession {
+            wallet: fill.wallet,
+            ..Default::default()
+        };
+        self.sessions.upsert(fill.wallet, default, |session| {
+            let mut events: Vec<Box<dyn UnifiedEvent>> = vec![];
+            if session.trades > 0 && now - session.last_activity_ms > self.idle_gap_ms {
+                let closed = std::mem::replace(session, default());
+                events.push(Box::new(Self::session_closed(metadata, closed)));
+            }
+            if session.trades == 0 {
+                session.started_ms = now;
+                session.first_slot = metadata.slot;
+                if self.emit_opened {
+                    events.push(Box::new(Self::session_opened(metadata, session)));
+                }
+            }
+            Self::record(session, metadata, &fill, now);
+            events
+        })
+    }
+
+    /// Close and return the sessions idle for longer than the gap at `now_ms`
+    pub fn close_idle(&self, now_ms: i64) -> Vec<WalletSession> {
+        let mut closed = vec![];
+        self.sessions.retain(|_, session| {
+            if now_ms - session.last_activity_ms > self.idle_gap_ms {
+                closed.push(session.clone());
+                return false;
+            }
+            true
+        });
+        closed
+    }
+
+    fn record(session: &mut WalletSession, metadata: &EventMetadata, fill: &Fill, now: i64) {
+        session.last_activity_ms = session.last_activity_ms.max(now);
+        session.last_slot = session.last_slot.max(metadata.slot);
+        session.trades += 1;
+        if fill.is_buy {
+            session.buys += 1;
+        } else {
+            session.sells += 1;
+        }
+        if !session.tokens.contains(&fill.mint) {
+            session.tokens.push(fill.mint);
+        }
+        if !session.venues.contains(&metadata.protocol) {
+            session.venues.push(metadata.protocol.clone());
+        }
+        // 只统计以SOL计价的交易
+        if fill.quote_mint == WSOL_MINT {
+            let amount = fill.quote_amount as i64;
+            session.net_sol_flow += if fill.is_buy { -amount } else { amount };
+        }
+    }
+
+    fn session_opened(source: &EventMetadata, session: &WalletSession) -> WalletSessionOpenedEvent {
+        WalletSessionOpenedEvent {
+            metadata: derived_metadata(
+                source,
+                EventType::WalletSessionOpened,
+                format!("{}-{}", session.wallet, session.started_ms),
+            ),
+            wallet: session.wallet,
+            started_ms: session.started_ms,
+        }
+    }
+
+    fn session_closed(source: &EventMetadata, session: WalletSession) -> WalletSessionClosedEvent {
+        WalletSessionClosedEvent {
+            metadata: derived_metadata(
+                source,
+                EventType::WalletSessionClosed,
+                format!("{}-{}", session.wallet, session.started_ms),
+            ),
+            session,
+        }
+    }
+}
+
+fn event_time_ms(metadata: &EventMetadata) -> i64 {
+    if metadata.block_time_ms > 0 {
+        metadata.block_time_ms
+    } else {
+        metadata.program_received_time_ms
+    }
+}
+
+impl EventMiddleware for SessionTracker {
+    fn name(&self) -> &str {
+        "session_tracker"
+    }
+
+    fn handle(&self, event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+        let derived = self.apply_event(event.as_ref());
+        let now = event_time_ms(event.metadata());
+        // 每个空闲间隔最多扫描一次
+        let last_sweep = self.last_sweep_ms.load(Ordering::Relaxed);
+        let sweep = now - last_sweep >= self.idle_gap_ms
+            && self
+                .last_sweep_ms
+                .compare_exchange(last_sweep, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok();
+        let closed = if sweep { self.close_idle(now) } else { vec![] };
+
+        let mut events: Vec<Box<dyn UnifiedEvent>> = Vec::with_capacity(1 + derived.len());
+        for session in closed {
+            events.push(Box::new(Self::session_closed(event.metadata(), session)));
+        }
+        events.push(event);
+        events.extend(derived);
+        events
+    }
+}
@@ -0,0 +1,366 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::streaming::memory::{MemoryAccount, MemoryBudget};
+
+/// Minimum number of shards of a [`ShardedMap`]
+pub const MIN_SHARDS: usize = 16;
+
+/// 带最近访问时间的值
+struct Slot<V> {
+    value: V,
+    touched: AtomicU64,
+}
+
+type SlotMap<K, V> = HashMap<K, Slot<V>>;
+
+struct Shard<K, V> {
+    map: RwLock<SlotMap<K, V>>,
+    // 分片内的访问计数，作为LRU时钟
+    clock: AtomicU64,
+}
+
+impl<K, V> Shard<K, V> {
+    fn touch(&self, slot: &Slot<V>) {
+        slot.touched.store(
+            self.clock.fetch_add(1, Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+    }
+}
+
+/// 分片锁哈希表 - 按key分散到多个独立加锁的分片
+///
+/// Trackers keep one entry per mint, pool or position, and at pump.fun scale a
+/// single `RwLock<HashMap>` serialises every update across hundreds of thousands of
+/// active mints. Keys are hashed to one of a power-of-two number of shards, so
+/// updates to different keys rarely contend. Closures passed to the accessors run
+/// while the key's shard is locked and must not access the same map again.
+///
+/// Maps are unbounded by default. With [`Self::with_max_entries`] or a
+/// [`MemoryAccount`] of an exceeded [`MemoryBudget`], inserting a new key first
+/// evicts the least recently used entries of its shard (an eighth of the shard at
+/// a time, so the scan is amortized over many inserts).
+pub struct ShardedMap<K, V> {
+    shards: Box<[Shard<K, V>]>,
+    hasher: RandomState,
+    mask: usize,
+    // 每个分片的最大条目数，0表示不限制
+    max_per_shard: usize,
+    account: Option<MemoryAccount>,
+}
+
+impl<K: Eq + Hash, V> Default for ShardedMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash, V> ShardedMap<K, V> {
+    /// Map with four shards per available CPU (at least [`MIN_SHARDS`])
+    pub fn new() -> Self {
+        let cpus = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Self::with_shards(cpus * 4)
+    }
+
+    /// Map with `shards` shards, rounded up to a power of two
+    pub fn with_shards(shards: usize) -> Self {
+        Self::with_shards_and_capacity(shards, 0)
+    }
+
+    /// Map pre-sized for `capacity` entries in total
+    pub fn with_capacity(capacity: usize) -> Self {
+        let map = Self::new();
+        let per_shard = capacity.div_ceil(map.shards.len());
+        Self::with_shards_and_capacity(map.shards.len(), per_shard)
+    }
+
+    fn with_shards_and_capacity(shards: usize, per_shard: usize) -> Self {
+        let shards = shards.max(MIN_SHARDS).next_power_of_two();
+        Self {
+            shards: (0..shards)
+                .map(|_| Shard {
+                    map: RwLock::new(HashMap::with_capacity(per_shard)),
+                    clock: AtomicU64::new(0),
+                })
+                .collect(),
+            hasher: RandomState::new(),
+            mask: shards - 1,
+            max_per_shard: 0,
+            account: None,
+        }
+    }
+
+    /// Bound the map to about `max_entries` entries, evicting least recently used
+    /// ones; the bound is split evenly across shards
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.set_max_entries(max_entries);
+        self
+    }
+
+    pub fn set_max_entries(&mut self, max_entries: usize) {
+        self.max_per_shard = if max_entries == 0 {
+            0
+        } else {
+            max_entries.div_ceil(self.shards.len())
+        };
+    }
+
+    /// Charge entries to `account` and evict while its budget is exceeded
+    pub fn with_memory_account(mut self, account: MemoryAccount) -> Self {
+        self.set_memory_account(account);
+        self
+    }
+
+    /// Charge entries to `account`, moving the entries already held to it
+    pub fn set_memory_account(&mut self, account: MemoryAccount) {
+        let len = self.len();
+        if let Some(previous) = self.account.take() {
+            previous.release(len * Self::entry_size(), len);
+        }
+        account.charge(len * Self::entry_size(), len);
+        self.account = Some(account);
+    }
+
+    /// Shorthand for an account named `name` in `budget`
+    pub fn with_memory_budget(self, budget: &MemoryBudget, name: &str) -> Self {
+        self.with_memory_account(budget.account(name))
+    }
+
+    pub fn memory_account(&self) -> Option<&MemoryAccount> {
+        self.account.as_ref()
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    fn shard(&self, key: &K) -> &Shard<K, V> {
+        // 低位用于HashMap内部的桶，取高位选择分片
+        &self.shards[(self.hasher.hash_one(key) >> 32) as usize & self.mask]
+    }
+
+    fn read_shard(&self, key: &K) -> (&Shard<K, V>, RwLockReadGuard<'_, SlotMap<K, V>>) {
+        let shard = self.shard(key);
+        (shard, shard.map.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn write_shard(&self, key: &K) -> (&Shard<K, V>, RwLockWriteGuard<'_, SlotMap<K, V>>) {
+        let shard = self.shard(key);
+        (shard, shard.map.write().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn charge(&self, entries: usize) {
+        if let Some(account) = &self.account {
+            account.charge(entries * Self::entry_size(), entries);
+        }
+    }
+
+    fn release(&self, entries: usize) {
+        if let Some(account) = &self.account {
+            account.release(entries * Self::entry_size(), entries);
+        }
+    }
+
+    /// Evict least recently used entries before a new key is added to `map`
+    fn make_room(&self, map: &mut SlotMap<K, V>) {
+        let full = self.max_per_shard > 0 && map.len() >= self.max_per_shard;
+        let over_budget = self
+            .account
+            .as_ref()
+            .is_some_and(|account| account.over_budget());
+        if map.is_empty() || (!full && !over_budget) {
+            return;
+        }
+        let mut evict = (map.len() / 8).max(1);
+        if full {
+            evict = evict.max(map.len() + 1 - self.max_per_shard);
+        }
+        let evict = evict.min(map.len());
+        let mut ticks: Vec<u64> = map
+            .values()
+            .map(|slot| slot.touched.load(Ordering::Relaxed))
+            .collect();
+        let (_, threshold, _) = ticks.select_nth_unstable(evict - 1);
+        let threshold = *threshold;
+        let before = map.len();
+        map.retain(|_, slot| slot.touched.load(Ordering::Relaxed) > threshold);
+        let evicted = before - map.len();
+        self.release(evicted);
+        if let Some(account) = &self.account {
+            account.record_evictions(evicted);
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.read(key, V::clone)
+    }
+
+    /// Read a value in place without cloning it
+    pub fn read<R>(&self, key: &K, f: impl FnOnce(&V) -> R) -> Option<R> {
+        let (shard, map) = self.read_shard(key);
+        map.get(key).map(|slot| {
+            shard.touch(slot);
+            f(&slot.value)
+        })
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.read_shard(key).1.contains_key(key)
+    }
+
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let (shard, mut map) = self.write_shard(&key);
+        if let Some(slot) = map.get_mut(&key) {
+            shard.touch(slot);
+            return Some(std::mem::replace(&mut slot.value, value));
+        }
+        self.make_room(&mut map);
+        let slot = Slot {
+            value,
+            touched: AtomicU64::new(0),
+        };
+        shard.touch(&slot);
+        map.insert(key, slot);
+        self.charge(1);
+        None
+    }
+
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let removed = self.write_shard(key).1.remove(key);
+        if removed.is_some() {
+            self.release(1);
+        }
+        removed.map(|slot| slot.value)
+    }
+
+    /// Mutate the value of `key`, inserting `default()` first if it is missing
+    pub fn upsert<R>(&self, key: K, default: impl FnOnce() -> V, f: impl FnOnce(&mut V) -> R) -> R {
+        let (shard, mut map) = self.write_shard(&key);
+        if !map.contains_key(&key) {
+            self.make_room(&mut map);
+            self.charge(1);
+        }
+        let slot = map.entry(key).or_insert_with(|| Slot {
+            value: default(),
+            touched: AtomicU64::new(0),
+        });
+        shard.touch(slot);
+        f(&mut slot.value)
+    }
+
+    /// Mutate the value of `key` if it exists
+    pub fn update<R>(&self, key: &K, f: impl FnOnce(&mut V) -> R) -> Option<R> {
+        let (shard, mut map) = self.write_shard(key);
+        map.get_mut(key).map(|slot| {
+            shard.touch(slot);
+            f(&mut slot.value)
+        })
+    }
+
+    /// Total number of entries; shards are counted one after another, so the result
+    /// is approximate while other threads are writing
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.map.read().unwrap_or_else(|e| e.into_inner()).len())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| {
+            shard
+                .map
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .is_empty()
+        })
+    }
+
+    pub fn clear(&self) {
+        for shard in self.shards.iter() {
+            let mut map = shard.map.write().unwrap_or_else(|e| e.into_inner());
+            self.release(map.len());
+            map.clear();
+        }
+    }
+
+    /// Keep only the entries for which `f` returns true, one shard at a time
+    pub fn retain(&self, mut f: impl FnMut(&K, &mut V) -> bool) {
+        for shard in self.shards.iter() {
+            let mut map = shard.map.write().unwrap_or_else(|e| e.into_inner());
+            let before = map.len();
+            map.retain(|key, slot| f(key, &mut slot.value));
+            self.release(before - map.len());
+        }
+    }
+
+    /// Visit every entry, one shard at a time
+    pub fn for_each(&self, mut f: impl FnMut(&K, &V)) {
+        for shard in self.shards.iter() {
+            for (key, slot) in shard.map.read().unwrap_or_else(|e| e.into_inner()).iter() {
+                f(key, &slot.value);
+            }
+        }
+    }
+
+    /// Clone every entry, e.g. for a snapshot
+    pub fn entries(&self) -> Vec<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        let mut entries = Vec::with_capacity(self.len());
+        self.for_each(|key, value| entries.push((key.clone(), value.clone())));
+        entries
+    }
+
+    /// Replace the contents with `entries`
+    pub fn replace_all(&self, entries: impl IntoIterator<Item = (K, V)>) {
+        self.clear();
+        for (key, value) in entries {
+            self.insert(key, value);
+        }
+    }
+
+    /// Clone the values matching `filter`
+    pub fn collect_values(&self, filter: impl Fn(&K, &V) -> bool) -> Vec<V>
+    where
+        V: Clone,
+    {
+        let mut values = Vec::new();
+        self.for_each(|key, value| {
+            if filter(key, value) {
+                values.push(value.clone());
+            }
+        });
+        values
+    }
+}
+
+impl<K, V> ShardedMap<K, V> {
+    /// Estimated bytes per entry: key, value, LRU tick and table control byte.
+    /// Heap data owned by values isn't included.
+    pub const fn entry_size() -> usize {
+        std::mem::size_of::<K>() + std::mem::size_of::<Slot<V>>() + 1
+    }
+}
+
+impl<K, V> Drop for ShardedMap<K, V> {
+    fn drop(&mut self) {
+        if let Some(account) = &self.account {
+            let len: usize = self
+                .shards
+                .iter_mut()
+                .map(|shard| shard.map.get_mut().unwrap_or_else(|e| e.into_inner()).len())
+                .sum();
+            account.release(len * Self::entry_size(), len);
+        }
+    }
+}
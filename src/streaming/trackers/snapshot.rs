@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::common::AnyResult;
+use crate::streaming::checkpoint::ResumeCheckpoint;
+
+/// Default number of snapshots kept by a [`FileSnapshotStore`]
+pub const DEFAULT_SNAPSHOTS_KEPT: usize = 3;
+
+const SNAPSHOT_PREFIX: &str = "snapshot-";
+const SNAPSHOT_EXTENSION: &str = "bin";
+
+/// 可快照的跟踪器状态
+///
+/// Implemented by the curve, pool and PnL trackers; the state is encoded with
+/// bincode together with the position of the last applied event.
+/// `restore_state` replaces the current state of the tracker, which then ignores
+/// replayed events up to that position.
+pub trait SnapshotState: Send + Sync {
+    /// Name the state is stored under, unique per registered tracker
+    fn snapshot_name(&self) -> &str;
+
+    fn snapshot_state(&self) -> AnyResult<Vec<u8>>;
+
+    fn restore_state(&self, bytes: &[u8]) -> AnyResult<()>;
+}
+
+impl<T: SnapshotState + ?Sized> SnapshotState for Arc<T> {
+    fn snapshot_name(&self) -> &str {
+        (**self).snapshot_name()
+    }
+
+    fn snapshot_state(&self) -> AnyResult<Vec<u8>> {
+        (**self).snapshot_state()
+    }
+
+    fn restore_state(&self, bytes: &[u8]) -> AnyResult<()> {
+        (**self).restore_state(bytes)
+    }
+}
+
+/// 跟踪器快照 - 某个slot时所有跟踪器的状态
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrackerSnapshot {
+    /// Slot to resume the stream after
+    ///
+    /// The trackers may have applied events past it; each records its own
+    /// position and skips those events when they are replayed.
+    pub slot: u64,
+    /// Encoded state per tracker name
+    pub trackers: BTreeMap<String, Vec<u8>>,
+}
+
+impl TrackerSnapshot {
+    pub fn encode(&self) -> AnyResult<Vec<u8>> {
+        Ok(bincode::serialize(self)?)
+    }
+
+    pub fn decode(bytes: &[u8]) -> AnyResult<Self> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+}
+
+/// 快照存储
+///
+/// [`FileSnapshotStore`] keeps them on disk; a Redis store only needs to `SET` the
+/// encoded snapshot under a key containing the slot and track the latest slot.
+pub trait SnapshotStore: Send + Sync {
+    fn save(&self, snapshot: &TrackerSnapshot) -> AnyResult<()>;
+
+    /// Latest snapshot, None if none was saved
+    fn load_latest(&self) -> AnyResult<Option<TrackerSnapshot>>;
+}
+
+/// 基于文件的快照存储 - 每个slot一个文件
+///
+/// Snapshots are written to `snapshot-{slot}.bin` through a temporary file and a
+/// rename, so a crash while saving leaves the previous snapshots intact. The
+/// newest `keep` snapshots are kept; a snapshot that fails to decode is skipped in
+/// favour of the one before it.
+#[derive(Debug, Clone)]
+pub struct FileSnapshotStore {
+    dir: PathBuf,
+    keep: usize,
+}
+
+impl FileSnapshotStore {
+    /// Store in `dir`, created if missing
+    pub fn new(dir: impl AsRef<Path>) -> AnyResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            keep: DEFAULT_SNAPSHOTS_KEPT,
+        })
+    }
+
+    pub fn with_keep(mut self, keep: usize) -> Self {
+        self.keep = keep.max(1);
+        self
+    }
+
+    pub fn path_for(&self, slot: u64) -> PathBuf {
+        self.dir.join(format!(
+            "{}{:020}.{}",
+            SNAPSHOT_PREFIX, slot, SNAPSHOT_EXTENSION
+        ))
+    }
+
+    /// Slots of the stored snapshots, oldest first
+    pub fn slots(&self) -> AnyResult<Vec<u64>> {
+        let mut slots = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SNAPSHOT_EXTENSION) {
+                continue;
+            }
+            let slot = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.strip_prefix(SNAPSHOT_PREFIX))
+                .and_then(|slot| slot.parse::<u64>().ok());
+            if let Some(slot) = slot {
+                slots.push(slot);
+            }
+        }
+        slots.sort_unstable();
+        Ok(slots)
+    }
+}
+
+impl SnapshotStore for FileSnapshotStore {
+    fn save(&self, snapshot: &TrackerSnapshot) -> AnyResult<()> {
+        let path = self.path_for(snapshot.slot);
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, snapshot.encode()?)?;
+        std::fs::rename(&tmp, &path)?;
+
+        let slots = self.slots()?;
+        for slot in &slots[..slots.len().saturating_sub(self.keep)] {
+            std::fs::remove_file(self.path_for(*slot))?;
+        }
+        Ok(())
+    }
+
+    fn load_latest(&self) -> AnyResult<Option<TrackerSnapshot>> {
+        for slot in self.slots()?.into_iter().rev() {
+            let path = self.path_for(slot);
+            match std::fs::read(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|bytes| TrackerSnapshot::decode(&bytes))
+            {
+                Ok(snapshot) => return Ok(Some(snapshot)),
+                Err(e) => warn!("skipping unreadable snapshot {}: {}", path.display(), e),
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// 跟踪器快照管理 - 按slot保存和恢复跟踪器状态
+///
+/// Save a snapshot at the slot of the stream checkpoint. The trackers keep
+/// applying events while the stream runs, so their state is usually ahead of the
+/// checkpoint; each tracker blocks its event path while its state is copied and
+/// stores the position of the last event it applied. On restart
+/// [`Self::restore`] loads the latest snapshot into the trackers and returns its
+/// slot; resume the stream from the slot after it, e.g. by seeding the
+/// [`ResumeCheckpoint`] with it. Replayed events the trackers already applied
+/// before the snapshot are skipped, the rest are applied, so none are lost or
+/// applied twice.
+///
+/// Every registered tracker needs its own snapshot name, set with the
+/// `with_snapshot_name` builder of the tracker when two of the same type are
+/// registered.
+///
+/// # Usage Example
+/// ```ignore
+/// let curves = Arc::new(BondingCurveTracker::new());
+/// let snapshots = SnapshotManager::new(FileSnapshotStore::new("snapshots")?)
+///     .with_tracker(curves.clone());
+/// let checkpoint = ResumeCheckpoint::new(snapshots.restore()?.unwrap_or(0));
+/// // periodically, once a slot is fully processed
+/// checkpoint.advance(slot)?;
+/// snapshots.save_checkpoint(&checkpoint)?;
+/// ```
+pub struct SnapshotManager {
+    store: Box<dyn SnapshotStore>,
+    trackers: Vec<Arc<dyn SnapshotState>>,
+}
+
+impl SnapshotManager {
+    pub fn new(store: impl SnapshotStore + 'static) -> Self {
+        Self {
+            store: Box::new(store),
+            trackers: Vec::new(),
+        }
+    }
+
+    pub fn with_tracker(mut self, tracker: Arc<dyn SnapshotState>) -> Self {
+        self.trackers.push(tracker);
+        self
+    }
+
+    /// Snapshot every tracker at `slot`
+    ///
+    /// Fails if two trackers share a snapshot name.
+    pub fn snapshot(&self, slot: u64) -> AnyResult<TrackerSnapshot> {
+        let mut trackers = BTreeMap::new();
+        for tracker in &self.trackers {
+            let name = tracker.snapshot_name().to_string();
+            if trackers.contains_key(&name) {
+                return Err(anyhow::anyhow!(
+                    "two trackers are snapshotted under the name {}",
+                    name
+                ));
+            }
+            trackers.insert(name, tracker.snapshot_state()?);
+        }
+        Ok(TrackerSnapshot { slot, trackers })
+    }
+
+    /// Snapshot every tracker and save it under `slot`
+    pub fn save(&self, slot: u64) -> AnyResult<()> {
+        self.store.save(&self.snapshot(slot)?)
+    }
+
+    /// Save at the slot of `checkpoint`
+    pub fn save_checkpoint(&self, checkpoint: &ResumeCheckpoint) -> AnyResult<()> {
+        self.save(checkpoint.slot())
+    }
+
+    /// Restore the trackers from the latest snapshot, returns its slot
+    ///
+    /// Trackers missing from the snapshot keep their state and are logged.
+    pub fn restore(&self) -> AnyResult<Option<u64>> {
+        let Some(snapshot) = self.store.load_latest()? else {
+            return Ok(None);
+        };
+        for tracker in &self.trackers {
+            match snapshot.trackers.get(tracker.snapshot_name()) {
+                Some(bytes) => tracker.restore_state(bytes)?,
+                None => warn!(
+                    "snapshot at slot {} has no state for tracker {}",
+                    snapshot.slot,
+                    tracker.snapshot_name()
+                ),
+            }
+        }
+        info!(
+            "restored {} trackers from the snapshot at slot {}",
+            self.trackers.len(),
+            snapshot.slot
+        );
+        Ok(Some(snapshot.slot))
+    }
+}
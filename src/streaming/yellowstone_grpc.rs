@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fmt, time::Duration};
+use std::{collections::HashMap, fmt};
 
 use chrono::Local;
 use futures::{channel::mpsc, sink::Sink, SinkExt, Stream, StreamExt};
@@ -7,23 +7,41 @@ use prost_types::Timestamp;
 use rustls::crypto::{ring::default_provider, CryptoProvider};
 use solana_sdk::{pubkey::Pubkey, signature::Signature};
 use solana_transaction_status::{EncodedTransactionWithStatusMeta, UiTransactionEncoding};
-use tonic::{transport::channel::ClientTlsConfig, Status};
+use tonic::Status;
+use tonic_health::pb::health_client::HealthClient;
 use yellowstone_grpc_client::{GeyserGrpcClient, Interceptor};
 use yellowstone_grpc_proto::geyser::{
-    subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest,
+    geyser_client::GeyserClient, subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest,
+    SubscribeRequestFilterBlocksMeta, SubscribeRequestFilterSlots,
     SubscribeRequestFilterTransactions, SubscribeRequestPing, SubscribeUpdate,
-    SubscribeUpdateTransaction,
+    SubscribeUpdateBlockMeta, SubscribeUpdateSlot, SubscribeUpdateTransaction,
+    SubscribeUpdateTransactionInfo,
 };
 
 use crate::common::AnyResult;
-use crate::streaming::event_parser::{EventParserFactory, Protocol, UnifiedEvent};
+use crate::streaming::block_events::{
+    BlockEventAggregator, BlockEvents, BlockMeta, BlockMetaEvent, SlotStatus, SlotUpdateEvent,
+};
+use crate::streaming::event_parser::{
+    common::{ClockHandle, FailedTxPolicy, ParseOptions},
+    core::{post_process::PostProcessorChain, TransactionInput},
+    ActiveProtocols, EventParserFactory, ProgramIdFilter, Protocol, UnifiedEvent,
+};
+use crate::streaming::grpc_config::GrpcConfig;
+use crate::streaming::shutdown::{StreamHandle, DEFAULT_SHUTDOWN_GRACE};
+use crate::streaming::interceptor::{
+    run_interceptor, SharedInterceptor, StreamInterceptor, StreamSource,
+};
+use crate::streaming::multiplex::SubscriptionMux;
+use crate::streaming::ordering::{EventSequencer, SequencedCallback};
+use crate::streaming::ring::{ring_buffer, RingBufferConfig, RingConsumer, RingProducer};
+use crate::streaming::runtime::StreamerRuntime;
+use crate::streaming::slot_gaps::SlotGapDetector;
+use crate::streaming::subscribe_request::SubscribeRequestBuilder;
 
 type TransactionsFilterMap = HashMap<String, SubscribeRequestFilterTransactions>;
 
-const CONNECT_TIMEOUT: u64 = 10;
-const REQUEST_TIMEOUT: u64 = 60;
 const CHANNEL_SIZE: usize = 1000;
-const MAX_DECODING_MESSAGE_SIZE: usize = 1024 * 1024 * 10;
 
 #[derive(Clone)]
 pub struct TransactionPretty {
@@ -31,7 +49,11 @@ pub struct TransactionPretty {
     pub block_time: Option<Timestamp>,
     pub signature: Signature,
     pub is_vote: bool,
+    /// Index of the transaction within its block
+    pub tx_index: u64,
     pub tx: EncodedTransactionWithStatusMeta,
+    /// When the update was received from the stream, 0 if unknown
+    pub received_time_ms: i64,
 }
 
 impl fmt::Debug for TransactionPretty {
@@ -48,6 +70,7 @@ impl fmt::Debug for TransactionPretty {
             .field("slot", &self.slot)
             .field("signature", &self.signature)
             .field("is_vote", &self.is_vote)
+            .field("tx_index", &self.tx_index)
             .field("tx", &TxWrap(&self.tx))
             .finish()
     }
@@ -66,17 +89,199 @@ impl From<(SubscribeUpdateTransaction, Option<Timestamp>)> for TransactionPretty
             block_time: block_time,
             signature: Signature::try_from(tx.signature.as_slice()).expect("valid signature"),
             is_vote: tx.is_vote,
+            tx_index: tx.index,
             tx: yellowstone_grpc_proto::convert_from::create_tx_with_meta(tx)
                 .expect("valid tx with meta")
                 .encode(UiTransactionEncoding::Base64, Some(u8::MAX), true)
                 .expect("failed to encode"),
+            received_time_ms: 0,
+        }
+    }
+}
+
+/// 未转换编码的gRPC交易更新
+struct GrpcTransaction {
+    slot: u64,
+    created_at: Option<Timestamp>,
+    info: SubscribeUpdateTransactionInfo,
+    received_time_ms: i64,
+}
+
+impl GrpcTransaction {
+    /// Decode the update once for all parsers, None if it cannot be decoded
+    fn decode(self) -> Option<DecodedGrpcTransaction> {
+        let signature = bs58::encode(&self.info.signature).into_string();
+        let tx_index = self.info.index;
+        match TransactionInput::from_grpc(self.info) {
+            Ok(input) => Some(DecodedGrpcTransaction {
+                slot: self.slot,
+                created_at: self.created_at,
+                signature,
+                tx_index,
+                received_time_ms: self.received_time_ms,
+                input,
+            }),
+            Err(e) => {
+                error!("Error decoding transaction {}: {}", signature, e);
+                None
+            }
+        }
+    }
+}
+
+/// 解码后的gRPC交易，由各协议的解析器共享
+struct DecodedGrpcTransaction {
+    slot: u64,
+    created_at: Option<Timestamp>,
+    signature: String,
+    tx_index: u64,
+    received_time_ms: i64,
+    input: TransactionInput,
+}
+
+impl DecodedGrpcTransaction {
+    /// Events of one protocol; the future owns its data so it can be spawned
+    fn parse(
+        &self,
+        protocol: Protocol,
+        bot_wallet: Option<Pubkey>,
+        parse_options: ParseOptions,
+        program_received_time_ms: i64,
+    ) -> impl std::future::Future<Output = Vec<Box<dyn UnifiedEvent>>> + Send + 'static {
+        let parser = EventParserFactory::create_parser(protocol);
+        let input = self.input.clone();
+        let signature = self.signature.clone();
+        let slot = self.slot;
+        let created_at = self.created_at.clone();
+        let tx_index = self.tx_index;
+        let received_time_ms = self.received_time_ms;
+        async move {
+            parser
+                .parse_transaction_input(
+                    input,
+                    &signature,
+                    Some(slot),
+                    created_at,
+                    program_received_time_ms,
+                    bot_wallet,
+                    parse_options,
+                )
+                .await
+                .map(|output| output.events)
+                .unwrap_or_else(|_e| vec![])
+                .into_iter()
+                .map(|mut event| {
+                    let metadata = event.metadata_mut();
+                    metadata.tx_index = Some(tx_index);
+                    metadata.grpc_received_time_ms = received_time_ms;
+                    event
+                })
+                .collect()
+        }
+    }
+}
+
+/// 读取端到解析端的交易通道
+enum TransactionSender {
+    Channel(mpsc::Sender<GrpcTransaction>),
+    Ring(RingProducer<GrpcTransaction>),
+}
+
+impl TransactionSender {
+    fn try_send(&mut self, transaction: GrpcTransaction) -> AnyResult<()> {
+        match self {
+            TransactionSender::Channel(tx) => Ok(tx.try_send(transaction)?),
+            TransactionSender::Ring(producer) => producer
+                .push(transaction)
+                .map_err(|_| anyhow::anyhow!("parser ring buffer is full")),
+        }
+    }
+}
+
+enum TransactionReceiver {
+    Channel(mpsc::Receiver<GrpcTransaction>),
+    Ring(RingConsumer<GrpcTransaction>, RingBufferConfig),
+}
+
+impl From<SubscribeUpdateBlockMeta> for BlockMeta {
+    fn from(meta: SubscribeUpdateBlockMeta) -> Self {
+        Self {
+            slot: meta.slot,
+            blockhash: meta.blockhash,
+            parent_slot: meta.parent_slot,
+            parent_blockhash: meta.parent_blockhash,
+            block_time: meta.block_time.map(|t| t.timestamp),
+            block_height: meta.block_height.map(|h| h.block_height),
+            executed_transaction_count: meta.executed_transaction_count,
         }
     }
 }
 
+impl From<SubscribeUpdateSlot> for SlotUpdateEvent {
+    fn from(update: SubscribeUpdateSlot) -> Self {
+        use yellowstone_grpc_proto::geyser::SlotStatus as GeyserSlotStatus;
+        let status = match GeyserSlotStatus::try_from(update.status) {
+            Ok(GeyserSlotStatus::SlotConfirmed) => SlotStatus::Confirmed,
+            Ok(GeyserSlotStatus::SlotFinalized) => SlotStatus::Finalized,
+            Ok(GeyserSlotStatus::SlotFirstShredReceived) => SlotStatus::FirstShredReceived,
+            Ok(GeyserSlotStatus::SlotCompleted) => SlotStatus::Completed,
+            Ok(GeyserSlotStatus::SlotCreatedBank) => SlotStatus::CreatedBank,
+            Ok(GeyserSlotStatus::SlotDead) => SlotStatus::Dead,
+            Ok(GeyserSlotStatus::SlotProcessed) | Err(_) => SlotStatus::Processed,
+        };
+        SlotUpdateEvent::new(
+            update.slot,
+            update.parent,
+            status,
+            update.dead_error,
+            chrono::Utc::now().timestamp_millis(),
+        )
+    }
+}
+
+/// 解码前检查交易是否涉及任一程序，非交易消息总是通过
+pub(crate) fn update_matches(msg: &SubscribeUpdate, filter: &ProgramIdFilter) -> bool {
+    let Some(UpdateOneof::Transaction(update)) = &msg.update_oneof else {
+        return true;
+    };
+    let Some(info) = &update.transaction else {
+        return true;
+    };
+    let static_keys = info
+        .transaction
+        .as_ref()
+        .and_then(|tx| tx.message.as_ref())
+        .map(|message| message.account_keys.iter())
+        .into_iter()
+        .flatten();
+    let loaded = info
+        .meta
+        .as_ref()
+        .map(|meta| {
+            meta.loaded_writable_addresses
+                .iter()
+                .chain(&meta.loaded_readonly_addresses)
+        })
+        .into_iter()
+        .flatten();
+    filter.matches_keys(static_keys.chain(loaded).map(|key| key.as_slice()))
+}
+
+/// 区块订阅中的消息
+enum BlockStreamUpdate {
+    Transaction(TransactionPretty),
+    BlockMeta(BlockMeta),
+}
+
 pub struct YellowstoneGrpc {
-    endpoint: String,
-    x_token: Option<String>,
+    pub(crate) endpoint: String,
+    config: GrpcConfig,
+    pub(crate) interceptor: Option<SharedInterceptor<SubscribeUpdate>>,
+    pub(crate) parse_options: ParseOptions,
+    ring_buffer: Option<RingBufferConfig>,
+    parser_runtime: Option<tokio::runtime::Handle>,
+    sequencer: Option<EventSequencer>,
+    slot_gap_detector: Option<SlotGapDetector>,
 }
 
 impl YellowstoneGrpc {
@@ -87,18 +292,134 @@ impl YellowstoneGrpc {
                 .map_err(|e| anyhow::anyhow!("Failed to install crypto provider: {:?}", e))?;
         }
 
-        Ok(Self { endpoint, x_token })
+        Ok(Self {
+            endpoint,
+            config: GrpcConfig {
+                x_token,
+                ..GrpcConfig::default()
+            },
+            interceptor: None,
+            parse_options: ParseOptions::default(),
+            ring_buffer: None,
+            parser_runtime: None,
+            sequencer: None,
+            slot_gap_detector: None,
+        })
+    }
+
+    /// Set the connection options
+    ///
+    /// The x-token passed to [`YellowstoneGrpc::new`] is kept unless `config`
+    /// sets one.
+    pub fn with_config(mut self, mut config: GrpcConfig) -> Self {
+        if config.x_token.is_none() {
+            config.x_token = self.config.x_token.take();
+        }
+        self.config = config;
+        self
+    }
+
+    pub fn config(&self) -> &GrpcConfig {
+        &self.config
+    }
+
+    /// Set how failed transactions are handled (skipped by default)
+    ///
+    /// The policy is applied to the subscription filter, so skipped transactions are
+    /// not sent by the server at all.
+    pub fn with_failed_tx_policy(mut self, failed_tx_policy: FailedTxPolicy) -> Self {
+        self.parse_options.failed_tx_policy = failed_tx_policy;
+        self
+    }
+
+    /// Attach the raw instruction data, accounts and stack height to every event
+    pub fn with_include_raw(mut self, include_raw: bool) -> Self {
+        self.parse_options.include_raw = include_raw;
+        self
+    }
+
+    /// Post-processors run on each transaction's events, replacing the default
+    /// dev/bot tagging chain
+    pub fn with_post_processors(mut self, post_processors: PostProcessorChain) -> Self {
+        self.parse_options.post_processors = post_processors;
+        self
+    }
+
+    /// Set a hook that sees every raw `SubscribeUpdate` before it is parsed
+    ///
+    /// The interceptor may mutate the message or return [`InterceptAction::Drop`] to
+    /// skip it. Pings are passed through the interceptor as well, so dropping them
+    /// stops the keepalive replies.
+    ///
+    /// [`InterceptAction::Drop`]: crate::streaming::interceptor::InterceptAction::Drop
+    pub fn with_interceptor(
+        mut self,
+        interceptor: impl StreamInterceptor<SubscribeUpdate> + 'static,
+    ) -> Self {
+        self.interceptor = Some(std::sync::Arc::new(interceptor));
+        self
+    }
+
+    /// Parse on a dedicated thread fed by a lock-free ring buffer
+    ///
+    /// Applies to [`YellowstoneGrpc::spawn_events_with_request`] and the methods
+    /// built on it. Removes the tokio channel wakeup between reading and parsing,
+    /// which adds jitter at the tail, at the cost of a thread that busy-waits
+    /// while idle. The stream stops if the parser falls behind by a full ring.
+    pub fn with_ring_buffer(mut self, config: RingBufferConfig) -> Self {
+        self.ring_buffer = Some(config);
+        self
+    }
+
+    /// Parse on the parser runtime of a [`StreamerRuntime`]
+    ///
+    /// Applies to [`YellowstoneGrpc::spawn_events_with_request`] and the methods
+    /// built on it; the subscription itself runs on the runtime it is started on,
+    /// normally [`StreamerRuntime::io_handle`].
+    pub fn with_runtime(mut self, runtime: &StreamerRuntime) -> Self {
+        self.parser_runtime = Some(runtime.parser_handle());
+        self
+    }
+
+    /// Deliver events in (slot, transaction index, instruction index) order with
+    /// `metadata.sequence` set, see [`EventSequencer`]
+    ///
+    /// Applies to [`YellowstoneGrpc::spawn_events_with_request`] and the methods
+    /// built on it. Events are held back until the stream moves past their slot;
+    /// the buffered ones are delivered when the stream ends or shuts down.
+    pub fn with_sequencer(mut self, sequencer: EventSequencer) -> Self {
+        self.sequencer = Some(sequencer);
+        self
+    }
+
+    /// Emit a `SlotGapDetectedEvent` after the slot update or block meta that
+    /// reveals slots missing from the stream, see [`SlotGapDetector`]
+    ///
+    /// Applies to [`YellowstoneGrpc::subscribe_chain_events`].
+    pub fn with_slot_gap_detector(mut self, detector: SlotGapDetector) -> Self {
+        self.slot_gap_detector = Some(detector);
+        self
     }
 
     pub async fn connect(&self) -> AnyResult<GeyserGrpcClient<impl Interceptor>> {
-        let builder = GeyserGrpcClient::build_from_shared(self.endpoint.clone())?
-            .x_token(self.x_token.clone())?
-            .tls_config(ClientTlsConfig::new().with_native_roots())?
-            .max_decoding_message_size(MAX_DECODING_MESSAGE_SIZE)
-            .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT))
-            .timeout(Duration::from_secs(REQUEST_TIMEOUT));
+        let channel = self.config.endpoint(&self.endpoint)?.connect().await?;
+        let interceptor = self.config.interceptor()?;
+        let mut geyser = GeyserClient::with_interceptor(channel.clone(), interceptor.clone())
+            .max_decoding_message_size(self.config.max_decoding_message_size);
+        if let Some(compression) = self.config.compression {
+            geyser = geyser
+                .send_compressed(compression.into())
+                .accept_compressed(compression.into());
+        }
+        Ok(GeyserGrpcClient::new(
+            HealthClient::with_interceptor(channel, interceptor),
+            geyser,
+        ))
+    }
 
-        Ok(builder.connect().await?)
+    /// Subscription request builder preset with this client's failed transaction policy
+    pub fn subscribe_request_builder(&self) -> SubscribeRequestBuilder {
+        SubscribeRequestBuilder::new().with_failed_tx_policy(self.parse_options.failed_tx_policy)
     }
 
     pub async fn subscribe_with_request(
@@ -137,7 +458,7 @@ impl YellowstoneGrpc {
             "client".to_string(),
             SubscribeRequestFilterTransactions {
                 vote: Some(false),
-                failed: Some(false),
+                failed: self.parse_options.failed_tx_policy.grpc_filter(),
                 signature: None,
                 account_include,
                 account_exclude,
@@ -151,13 +472,26 @@ impl YellowstoneGrpc {
         msg: SubscribeUpdate,
         tx: &mut mpsc::Sender<TransactionPretty>,
         subscribe_tx: &mut (impl Sink<SubscribeRequest, Error = mpsc::SendError> + Unpin),
+        clock: &ClockHandle,
+    ) -> AnyResult<()> {
+        Self::handle_update(msg, subscribe_tx, |sut, created_at| {
+            let mut transaction_pretty = TransactionPretty::from((sut, created_at));
+            transaction_pretty.received_time_ms = clock.now_ms();
+            tx.try_send(transaction_pretty)?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// 处理一条流消息，交易更新交给`on_transaction`，并响应ping
+    async fn handle_update(
+        msg: SubscribeUpdate,
+        subscribe_tx: &mut (impl Sink<SubscribeRequest, Error = mpsc::SendError> + Unpin),
+        on_transaction: impl FnOnce(SubscribeUpdateTransaction, Option<Timestamp>) -> AnyResult<()>,
     ) -> AnyResult<()> {
         let created_at = msg.created_at;
         match msg.update_oneof {
-            Some(UpdateOneof::Transaction(sut)) => {
-                let transaction_pretty = TransactionPretty::from((sut, created_at));
-                tx.try_send(transaction_pretty)?;
-            }
+            Some(UpdateOneof::Transaction(sut)) => on_transaction(sut, created_at)?,
             Some(UpdateOneof::Ping(_)) => {
                 subscribe_tx
                     .send(SubscribeRequest {
@@ -182,7 +516,7 @@ impl YellowstoneGrpc {
     ///
     /// # Parameters
     ///
-    /// * `protocols` - List of protocols to parse (e.g., PumpFun, PumpSwap, Bonk, RaydiumCpmm)
+    /// * `protocols` - Protocols to parse (e.g., PumpFun, PumpSwap, Bonk, RaydiumCpmm); pass an [`ActiveProtocols`] handle to enable or disable protocols while the stream runs
     /// * `bot_wallet` - Optional bot wallet address. If passed: in PumpFunTradeEvent if user is in the address, is_bot=true will be set. In BonkTradeEvent if payer is in the address, is_bot=true will be set. Default is false.
     /// * `account_include` - List of account addresses to include in the subscription
     /// * `account_exclude` - List of account addresses to exclude from the subscription
@@ -191,7 +525,7 @@ impl YellowstoneGrpc {
     /// * `callback` - Function to call when matching events are found
     pub async fn subscribe_events_v2<F>(
         &self,
-        protocols: Vec<Protocol>,
+        protocols: impl Into<ActiveProtocols>,
         bot_wallet: Option<Pubkey>,
         account_include: Vec<String>,
         account_exclude: Vec<String>,
@@ -202,33 +536,289 @@ impl YellowstoneGrpc {
     where
         F: Fn(Box<dyn UnifiedEvent>) + Send + Sync + 'static,
     {
-        if account_include.is_empty() && account_exclude.is_empty() && account_required.is_empty() {
-            return Err(anyhow::anyhow!(
-                "account_include or account_exclude or account_required cannot be empty"
-            ));
+        let mut builder = self
+            .subscribe_request_builder()
+            .include_accounts(account_include)
+            .exclude_accounts(account_exclude)
+            .require_accounts(account_required);
+        if let Some(commitment) = commitment {
+            builder = builder.with_commitment(commitment);
         }
+        let request = builder.build()?;
+        self.subscribe_events_with_request(protocols, bot_wallet, request, callback)
+            .await
+    }
 
-        let transactions =
-            self.get_subscribe_request_filter(account_include, account_exclude, account_required);
-        // Subscribe to events
-        let (mut subscribe_tx, mut stream) = self
-            .subscribe_with_request(transactions, commitment)
+    /// Subscribe to events with a prepared subscription request
+    ///
+    /// Use [`YellowstoneGrpc::subscribe_request_builder`] to derive the request from
+    /// the protocols and event types to parse instead of writing filters by hand.
+    /// Runs until Ctrl+C and then drains the stream with [`DEFAULT_SHUTDOWN_GRACE`];
+    /// use [`YellowstoneGrpc::spawn_events_with_request`] to control shutdown.
+    pub async fn subscribe_events_with_request<F>(
+        &self,
+        protocols: impl Into<ActiveProtocols>,
+        bot_wallet: Option<Pubkey>,
+        request: SubscribeRequest,
+        callback: F,
+    ) -> AnyResult<()>
+    where
+        F: Fn(Box<dyn UnifiedEvent>) + Send + Sync + 'static,
+    {
+        let handle = self
+            .spawn_events_with_request(protocols, bot_wallet, request, callback)
             .await?;
+        tokio::signal::ctrl_c().await?;
+        handle.shutdown(DEFAULT_SHUTDOWN_GRACE).await?;
+        Ok(())
+    }
+
+    /// 多路复用订阅 - 一个连接和一次解析服务多个逻辑订阅
+    ///
+    /// Subscribes to the programs of the protocols of `mux`'s current
+    /// subscriptions and routes every parsed event to the subscriptions it
+    /// matches, see [`SubscriptionMux`].
+    pub async fn spawn_multiplexed(
+        &self,
+        mux: &SubscriptionMux,
+        commitment: Option<CommitmentLevel>,
+    ) -> AnyResult<StreamHandle> {
+        let protocols = mux.protocols();
+        let mut builder = self
+            .subscribe_request_builder()
+            .with_protocols(&protocols.protocols());
+        if let Some(commitment) = commitment {
+            builder = builder.with_commitment(commitment);
+        }
+        let request = builder.build()?;
+        self.spawn_events_with_request(protocols, None, request, mux.callback())
+            .await
+    }
+
+    /// 在后台订阅事件，返回可有序停机的句柄
+    ///
+    /// Connects and subscribes before returning, so connection errors are returned
+    /// here. On [`StreamHandle::shutdown`] no new updates are read and the
+    /// transactions already received are parsed and delivered before it resolves.
+    pub async fn spawn_events_with_request<F>(
+        &self,
+        protocols: impl Into<ActiveProtocols>,
+        bot_wallet: Option<Pubkey>,
+        request: SubscribeRequest,
+        callback: F,
+    ) -> AnyResult<StreamHandle>
+    where
+        F: Fn(Box<dyn UnifiedEvent>) + Send + Sync + 'static,
+    {
+        let protocols: ActiveProtocols = protocols.into();
+        // Subscribe to events
+        let mut client = self.connect().await?;
+        let (mut subscribe_tx, mut stream) = client.subscribe_with_request(Some(request)).await?;
 
         // Create channel
-        let (mut tx, mut rx) = mpsc::channel::<TransactionPretty>(CHANNEL_SIZE);
+        let (mut tx, receiver) = match self.ring_buffer {
+            Some(config) => {
+                let (producer, consumer) = ring_buffer(config.capacity);
+                (
+                    TransactionSender::Ring(producer),
+                    TransactionReceiver::Ring(consumer, config),
+                )
+            }
+            None => {
+                let (tx, rx) = mpsc::channel::<GrpcTransaction>(CHANNEL_SIZE);
+                (
+                    TransactionSender::Channel(tx),
+                    TransactionReceiver::Channel(rx),
+                )
+            }
+        };
 
-        // Create callback function, wrap with Arc to share across multiple tasks
-        let callback = std::sync::Arc::new(Box::new(callback));
+        let interceptor = self.interceptor.clone();
+        let endpoint = self.endpoint.clone();
+        let clock = self.parse_options.clock.clone();
+        let parse_options = self.parse_options.clone();
+        let parser_runtime = self.parser_runtime.clone();
+        let stream_protocols = protocols.clone();
+        let callback = SequencedCallback::new(self.sequencer.clone(), callback);
+        let callback = move |event: Box<dyn UnifiedEvent>| callback.call(event);
+        Ok(StreamHandle::spawn(move |shutdown| async move {
+            // Read the stream until shutdown; dropping `tx` ends the processing loop
+            let read = async move {
+                loop {
+                    let message = tokio::select! {
+                        _ = shutdown.wait() => break,
+                        message = stream.next() => message,
+                    };
+                    let Some(message) = message else {
+                        break;
+                    };
+                    match message {
+                        Ok(mut msg) => {
+                            if !run_interceptor(
+                                &interceptor,
+                                &mut msg,
+                                StreamSource::YellowstoneGrpc,
+                                &endpoint,
+                            ) {
+                                continue;
+                            }
+                            if !update_matches(&msg, &stream_protocols.program_filter()) {
+                                continue;
+                            }
+                            // 交易保持protobuf格式，跳过RPC编码转换
+                            let handled =
+                                Self::handle_update(msg, &mut subscribe_tx, |sut, created_at| {
+                                    let info = sut.transaction.ok_or_else(|| {
+                                        anyhow::anyhow!("transaction update without transaction")
+                                    })?;
+                                    tx.try_send(GrpcTransaction {
+                                        slot: sut.slot,
+                                        created_at,
+                                        info,
+                                        received_time_ms: clock.now_ms(),
+                                    })?;
+                                    Ok(())
+                                })
+                                .await;
+                            if let Err(e) = handled {
+                                error!("Error handling message: {:?}", e);
+                                break;
+                            }
+                        }
+                        Err(error) => {
+                            error!("Stream error: {error:?}");
+                            break;
+                        }
+                    }
+                }
+            };
+
+            // Process transactions, including those queued before shutdown
+            let process = async move {
+                match receiver {
+                    TransactionReceiver::Channel(mut rx) => {
+                        while let Some(transaction) = rx.next().await {
+                            let events = Self::parse_grpc_transaction(
+                                transaction,
+                                bot_wallet,
+                                protocols.protocols(),
+                                parse_options.clone(),
+                                parser_runtime.as_ref(),
+                            )
+                            .await;
+                            for event in events {
+                                callback(event);
+                            }
+                        }
+                    }
+                    TransactionReceiver::Ring(consumer, config) => {
+                        let parser = std::thread::Builder::new()
+                            .name("solana-streamer-parser".to_string())
+                            .spawn(move || {
+                                Self::run_parser_thread(
+                                    consumer,
+                                    config,
+                                    bot_wallet,
+                                    protocols.protocols(),
+                                    parse_options,
+                                    callback,
+                                )
+                            });
+                        match parser {
+                            Ok(parser) => {
+                                let joined =
+                                    tokio::task::spawn_blocking(move || parser.join()).await;
+                                if !matches!(joined, Ok(Ok(()))) {
+                                    error!("Parser thread panicked");
+                                }
+                            }
+                            Err(e) => error!("Failed to start parser thread: {:?}", e),
+                        }
+                    }
+                }
+            };
+            futures::join!(read, process);
+            Ok(())
+        }))
+    }
+
+    /// 按区块订阅事件
+    ///
+    /// Takes the same filters as [`YellowstoneGrpc::subscribe_events_v2`], also
+    /// subscribes to block meta and calls `callback` once per block with all events
+    /// of the slot, the block hash, parent slot, block time and transaction count.
+    /// A block is complete when its block meta arrives, which is after its last
+    /// transaction at the subscribed commitment.
+    pub async fn subscribe_block_events<F>(
+        &self,
+        protocols: impl Into<ActiveProtocols>,
+        bot_wallet: Option<Pubkey>,
+        account_include: Vec<String>,
+        account_exclude: Vec<String>,
+        account_required: Vec<String>,
+        commitment: Option<CommitmentLevel>,
+        callback: F,
+    ) -> AnyResult<()>
+    where
+        F: Fn(BlockEvents) + Send + Sync + 'static,
+    {
+        let protocols: ActiveProtocols = protocols.into();
+        let subscribe_request = self
+            .subscribe_request_builder()
+            .include_accounts(account_include)
+            .exclude_accounts(account_exclude)
+            .require_accounts(account_required)
+            .with_commitment(commitment.unwrap_or(CommitmentLevel::Processed))
+            .with_blocks_meta(true)
+            .build()?;
+        let mut client = self.connect().await?;
+        let (mut subscribe_tx, mut stream) = client
+            .subscribe_with_request(Some(subscribe_request))
+            .await?;
+
+        let (mut tx, mut rx) = mpsc::channel::<BlockStreamUpdate>(CHANNEL_SIZE);
 
-        // Start task to process the stream
+        let interceptor = self.interceptor.clone();
+        let endpoint = self.endpoint.clone();
+        let clock = self.parse_options.clock.clone();
+        let stream_protocols = protocols.clone();
         tokio::spawn(async move {
             while let Some(message) = stream.next().await {
                 match message {
-                    Ok(msg) => {
-                        if let Err(e) =
-                            Self::handle_stream_message(msg, &mut tx, &mut subscribe_tx).await
-                        {
+                    Ok(mut msg) => {
+                        if !run_interceptor(
+                            &interceptor,
+                            &mut msg,
+                            StreamSource::YellowstoneGrpc,
+                            &endpoint,
+                        ) {
+                            continue;
+                        }
+                        if !update_matches(&msg, &stream_protocols.program_filter()) {
+                            continue;
+                        }
+                        let created_at = msg.created_at;
+                        let result = match msg.update_oneof {
+                            Some(UpdateOneof::Transaction(sut)) => {
+                                let mut transaction_pretty =
+                                    TransactionPretty::from((sut, created_at));
+                                transaction_pretty.received_time_ms = clock.now_ms();
+                                tx.try_send(BlockStreamUpdate::Transaction(transaction_pretty))
+                                    .map_err(|e| anyhow::anyhow!(e))
+                            }
+                            Some(UpdateOneof::BlockMeta(meta)) => tx
+                                .try_send(BlockStreamUpdate::BlockMeta(meta.into()))
+                                .map_err(|e| anyhow::anyhow!(e)),
+                            Some(UpdateOneof::Ping(_)) => subscribe_tx
+                                .send(SubscribeRequest {
+                                    ping: Some(SubscribeRequestPing { id: 1 }),
+                                    ..Default::default()
+                                })
+                                .await
+                                .map_err(|e| anyhow::anyhow!(e)),
+                            _ => Ok(()),
+                        };
+                        if let Err(e) = result {
                             error!("Error handling message: {:?}", e);
                             break;
                         }
@@ -241,18 +831,125 @@ impl YellowstoneGrpc {
             }
         });
 
-        // Process transactions
+        // 按slot聚合，区块元数据到达时整体回调
+        let parse_options = self.parse_options.clone();
         tokio::spawn(async move {
-            while let Some(transaction_pretty) = rx.next().await {
-                if let Err(e) = Self::process_event_transaction(
-                    transaction_pretty,
-                    &**callback,
-                    bot_wallet,
-                    protocols.clone(),
-                )
-                .await
-                {
-                    error!("Error processing transaction: {:?}", e);
+            let mut aggregator = BlockEventAggregator::new();
+            while let Some(update) = rx.next().await {
+                match update {
+                    BlockStreamUpdate::Transaction(transaction_pretty) => {
+                        let events = Self::parse_event_transaction(
+                            transaction_pretty,
+                            bot_wallet,
+                            protocols.protocols(),
+                            parse_options.clone(),
+                        )
+                        .await;
+                        for event in events {
+                            aggregator.push(event);
+                        }
+                    }
+                    BlockStreamUpdate::BlockMeta(meta) => callback(aggregator.complete(meta)),
+                }
+            }
+        });
+
+        tokio::signal::ctrl_c().await?;
+        Ok(())
+    }
+
+    /// 订阅slot状态和区块元数据事件
+    ///
+    /// Calls `callback` with a `SlotUpdateEvent` for every processed, confirmed,
+    /// finalized and dead slot and a `BlockMetaEvent` for every block, so consumers
+    /// can drive their own finality logic and measure latency. The callback has the
+    /// same type as the one of [`YellowstoneGrpc::subscribe_events_v2`], so both
+    /// can feed one `ParsedEventStream` or pipeline. Set `interslot_updates` to
+    /// also receive first-shred, created-bank and completed updates. With
+    /// [`YellowstoneGrpc::with_slot_gap_detector`] slots missing from the stream
+    /// are reported as `SlotGapDetectedEvent`s.
+    pub async fn subscribe_chain_events<F>(
+        &self,
+        interslot_updates: bool,
+        commitment: Option<CommitmentLevel>,
+        callback: F,
+    ) -> AnyResult<()>
+    where
+        F: Fn(Box<dyn UnifiedEvent>) + Send + Sync + 'static,
+    {
+        let subscribe_request = SubscribeRequest {
+            slots: HashMap::from([(
+                "client".to_string(),
+                SubscribeRequestFilterSlots {
+                    filter_by_commitment: Some(false),
+                    interslot_updates: Some(interslot_updates),
+                },
+            )]),
+            blocks_meta: HashMap::from([(
+                "client".to_string(),
+                SubscribeRequestFilterBlocksMeta {},
+            )]),
+            commitment: Some(commitment.unwrap_or(CommitmentLevel::Processed) as i32),
+            ..Default::default()
+        };
+        let mut client = self.connect().await?;
+        let (mut subscribe_tx, mut stream) = client
+            .subscribe_with_request(Some(subscribe_request))
+            .await?;
+
+        let interceptor = self.interceptor.clone();
+        let endpoint = self.endpoint.clone();
+        let slot_gap_detector = self.slot_gap_detector.clone();
+        let callback = move |event: Box<dyn UnifiedEvent>| {
+            let gap = slot_gap_detector
+                .as_ref()
+                .and_then(|detector| detector.observe_event(event.as_ref()));
+            callback(event);
+            if let Some(gap) = gap {
+                callback(Box::new(gap));
+            }
+        };
+        tokio::spawn(async move {
+            while let Some(message) = stream.next().await {
+                match message {
+                    Ok(mut msg) => {
+                        if !run_interceptor(
+                            &interceptor,
+                            &mut msg,
+                            StreamSource::YellowstoneGrpc,
+                            &endpoint,
+                        ) {
+                            continue;
+                        }
+                        match msg.update_oneof {
+                            Some(UpdateOneof::Slot(update)) => {
+                                callback(Box::new(SlotUpdateEvent::from(update)))
+                            }
+                            Some(UpdateOneof::BlockMeta(meta)) => callback(Box::new(
+                                BlockMetaEvent::new(
+                                    meta.into(),
+                                    chrono::Utc::now().timestamp_millis(),
+                                ),
+                            )),
+                            Some(UpdateOneof::Ping(_)) => {
+                                if let Err(e) = subscribe_tx
+                                    .send(SubscribeRequest {
+                                        ping: Some(SubscribeRequestPing { id: 1 }),
+                                        ..Default::default()
+                                    })
+                                    .await
+                                {
+                                    error!("Error handling message: {:?}", e);
+                                    break;
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    Err(error) => {
+                        error!("Stream error: {error:?}");
+                        break;
+                    }
                 }
             }
         });
@@ -307,12 +1004,24 @@ impl YellowstoneGrpc {
         let callback = std::sync::Arc::new(Box::new(callback));
 
         // 启动处理流的任务
+        let interceptor = self.interceptor.clone();
+        let endpoint = self.endpoint.clone();
+        let clock = self.parse_options.clock.clone();
         tokio::spawn(async move {
             while let Some(message) = stream.next().await {
                 match message {
-                    Ok(msg) => {
+                    Ok(mut msg) => {
+                        if !run_interceptor(
+                            &interceptor,
+                            &mut msg,
+                            StreamSource::YellowstoneGrpc,
+                            &endpoint,
+                        ) {
+                            continue;
+                        }
                         if let Err(e) =
-                            Self::handle_stream_message(msg, &mut tx, &mut subscribe_tx).await
+                            Self::handle_stream_message(msg, &mut tx, &mut subscribe_tx, &clock)
+                                .await
                         {
                             error!("Error handling message: {:?}", e);
                             break;
@@ -327,6 +1036,7 @@ impl YellowstoneGrpc {
         });
 
         // 处理交易
+        let parse_options = self.parse_options.clone();
         tokio::spawn(async move {
             while let Some(transaction_pretty) = rx.next().await {
                 if let Err(e) = Self::process_event_transaction(
@@ -334,6 +1044,7 @@ impl YellowstoneGrpc {
                     &**callback,
                     bot_wallet,
                     protocols.clone(),
+                    parse_options.clone(),
                 )
                 .await
                 {
@@ -351,12 +1062,31 @@ impl YellowstoneGrpc {
         callback: &F,
         bot_wallet: Option<Pubkey>,
         protocols: Vec<Protocol>,
+        parse_options: ParseOptions,
     ) -> AnyResult<()>
     where
         F: Fn(Box<dyn UnifiedEvent>) + Send + Sync,
     {
-        let program_received_time_ms = chrono::Utc::now().timestamp_millis();
+        let events =
+            Self::parse_event_transaction(transaction_pretty, bot_wallet, protocols, parse_options)
+                .await;
+        for event in events {
+            callback(event);
+        }
+        Ok(())
+    }
+
+    /// 用所有协议的解析器解析一笔交易
+    pub(crate) async fn parse_event_transaction(
+        transaction_pretty: TransactionPretty,
+        bot_wallet: Option<Pubkey>,
+        protocols: Vec<Protocol>,
+        parse_options: ParseOptions,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        let program_received_time_ms = parse_options.clock.now_ms();
+        let grpc_received_time_ms = transaction_pretty.received_time_ms;
         let slot = transaction_pretty.slot;
+        let tx_index = transaction_pretty.tx_index;
         let signature = transaction_pretty.signature.to_string();
         let mut futures = Vec::new();
         for protocol in protocols {
@@ -364,30 +1094,105 @@ impl YellowstoneGrpc {
             let tx_clone = transaction_pretty.tx.clone();
             let signature_clone = signature.clone();
             let bot_wallet_clone = bot_wallet.clone();
+            let parse_options = parse_options.clone();
 
             futures.push(tokio::spawn(async move {
                 parser
-                    .parse_transaction(
+                    .parse_transaction_with_errors(
                         tx_clone,
                         &signature_clone,
                         Some(slot),
                         transaction_pretty.block_time,
                         program_received_time_ms,
                         bot_wallet_clone,
+                        parse_options,
                     )
                     .await
+                    .map(|output| output.events)
                     .unwrap_or_else(|_e| vec![])
+                    .into_iter()
+                    .map(|mut event| {
+                        let metadata = event.metadata_mut();
+                        metadata.tx_index = Some(tx_index);
+                        metadata.grpc_received_time_ms = grpc_received_time_ms;
+                        event
+                    })
+                    .collect::<Vec<_>>()
             }));
         }
 
         let results = futures::future::join_all(futures).await;
-        for result in results {
-            if let Ok(events) = result {
+        results.into_iter().flatten().flatten().collect()
+    }
+
+    /// 用所有协议的解析器解析一笔未转换编码的gRPC交易
+    ///
+    /// The update is decoded once and shared by the parsers, which run on
+    /// `runtime` if given and on the current runtime otherwise.
+    async fn parse_grpc_transaction(
+        transaction: GrpcTransaction,
+        bot_wallet: Option<Pubkey>,
+        protocols: Vec<Protocol>,
+        parse_options: ParseOptions,
+        runtime: Option<&tokio::runtime::Handle>,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        let program_received_time_ms = parse_options.clock.now_ms();
+        let Some(transaction) = transaction.decode() else {
+            return vec![];
+        };
+        let futures: Vec<_> = protocols
+            .into_iter()
+            .map(|protocol| {
+                let parse = transaction.parse(
+                    protocol,
+                    bot_wallet,
+                    parse_options.clone(),
+                    program_received_time_ms,
+                );
+                match runtime {
+                    Some(runtime) => runtime.spawn(parse),
+                    None => tokio::spawn(parse),
+                }
+            })
+            .collect();
+
+        let results = futures::future::join_all(futures).await;
+        results.into_iter().flatten().flatten().collect()
+    }
+
+    /// 专用解析线程：从环形缓冲区读取并依次用各协议解析
+    ///
+    /// Parsers run on this thread without the tokio runtime; they do no IO, so
+    /// their futures complete on the first poll.
+    fn run_parser_thread<F>(
+        mut consumer: RingConsumer<GrpcTransaction>,
+        config: RingBufferConfig,
+        bot_wallet: Option<Pubkey>,
+        protocols: Vec<Protocol>,
+        parse_options: ParseOptions,
+        callback: F,
+    ) where
+        F: Fn(Box<dyn UnifiedEvent>),
+    {
+        if let Some(core) = config.core {
+            crate::common::affinity::pin_current_thread(core);
+        }
+        while let Some(transaction) = consumer.pop_wait(config.spin_limit) {
+            let program_received_time_ms = parse_options.clock.now_ms();
+            let Some(transaction) = transaction.decode() else {
+                continue;
+            };
+            for protocol in &protocols {
+                let events = futures::executor::block_on(transaction.parse(
+                    protocol.clone(),
+                    bot_wallet,
+                    parse_options.clone(),
+                    program_received_time_ms,
+                ));
                 for event in events {
                     callback(event);
                 }
             }
         }
-        Ok(())
     }
 }
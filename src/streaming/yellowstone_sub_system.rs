@@ -1,5 +1,6 @@
 use crate::{
     common::AnyResult,
+    streaming::interceptor::{run_interceptor, StreamSource},
     streaming::yellowstone_grpc::{TransactionPretty, YellowstoneGrpc},
 };
 use futures::{channel::mpsc, StreamExt};
@@ -45,12 +46,24 @@ impl YellowstoneGrpc {
 
         let callback = Box::new(callback);
 
+        let interceptor = self.interceptor.clone();
+        let endpoint = self.endpoint.clone();
+        let clock = self.parse_options.clock.clone();
         tokio::spawn(async move {
             while let Some(message) = stream.next().await {
                 match message {
-                    Ok(msg) => {
+                    Ok(mut msg) => {
+                        if !run_interceptor(
+                            &interceptor,
+                            &mut msg,
+                            StreamSource::YellowstoneGrpc,
+                            &endpoint,
+                        ) {
+                            continue;
+                        }
                         if let Err(e) =
-                            Self::handle_stream_message(msg, &mut tx, &mut subscribe_tx).await
+                            Self::handle_stream_message(msg, &mut tx, &mut subscribe_tx, &clock)
+                                .await
                         {
                             error!("Error handling message: {:?}", e);
                             break;
@@ -0,0 +1,89 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::protocols::pumpfun::parser::PUMPFUN_PROGRAM_ID;
+use solana_streamer_sdk::streaming::trackers::AccountDiffTracker;
+
+fn bonding_curve_data(virtual_sol_reserves: u64, real_sol_reserves: u64) -> Vec<u8> {
+    let mut data = vec![0u8; 8];
+    for value in [
+        1_000_000u64,
+        virtual_sol_reserves,
+        500_000,
+        real_sol_reserves,
+        1_000_000,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.push(0);
+    data
+}
+
+#[test]
+fn test_consecutive_account_updates_emit_field_diffs() {
+    let curve = Pubkey::new_unique();
+    let tracker = AccountDiffTracker::new().with_tracked_accounts([curve]);
+
+    // First update only records the snapshot
+    assert!(tracker
+        .apply_update(
+            &curve,
+            &PUMPFUN_PROGRAM_ID,
+            &bonding_curve_data(30, 0),
+            10,
+            None
+        )
+        .is_none());
+
+    let diff = tracker
+        .apply_update(
+            &curve,
+            &PUMPFUN_PROGRAM_ID,
+            &bonding_curve_data(45, 15),
+            11,
+            None,
+        )
+        .expect("reserves changed");
+    assert_eq!(diff.account_type, "BondingCurve");
+    assert_eq!(diff.previous_slot, 10);
+    assert_eq!(diff.changes.len(), 2);
+    assert_eq!(
+        diff.change("virtual_sol_reserves").and_then(|c| c.delta),
+        Some(15)
+    );
+    assert_eq!(
+        diff.change("real_sol_reserves").and_then(|c| c.delta),
+        Some(15)
+    );
+
+    // Identical and stale updates produce nothing
+    assert!(tracker
+        .apply_update(
+            &curve,
+            &PUMPFUN_PROGRAM_ID,
+            &bonding_curve_data(45, 15),
+            12,
+            None
+        )
+        .is_none());
+    assert!(tracker
+        .apply_update(
+            &curve,
+            &PUMPFUN_PROGRAM_ID,
+            &bonding_curve_data(1, 1),
+            5,
+            None
+        )
+        .is_none());
+
+    // Untracked accounts are ignored
+    let other = Pubkey::new_unique();
+    assert!(tracker
+        .apply_update(
+            &other,
+            &PUMPFUN_PROGRAM_ID,
+            &bonding_curve_data(1, 1),
+            12,
+            None
+        )
+        .is_none());
+    assert!(tracker.get_snapshot(&other).is_none());
+}
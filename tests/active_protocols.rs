@@ -0,0 +1,56 @@
+use solana_streamer_sdk::streaming::event_parser::{ActiveProtocols, EventParserFactory, Protocol};
+use std::sync::Arc;
+
+#[test]
+fn test_protocols_can_be_toggled_through_any_handle() {
+    let protocols =
+        ActiveProtocols::new(vec![Protocol::PumpFun, Protocol::Bonk, Protocol::PumpFun]);
+    let stream_handle = protocols.clone();
+    assert_eq!(
+        stream_handle.protocols(),
+        vec![Protocol::PumpFun, Protocol::Bonk]
+    );
+
+    assert!(protocols.disable(&Protocol::Bonk));
+    assert!(!protocols.disable(&Protocol::Bonk));
+    assert!(protocols.enable(Protocol::RaydiumCpmm));
+    assert!(!protocols.enable(Protocol::PumpFun));
+    assert_eq!(
+        stream_handle.protocols(),
+        vec![Protocol::PumpFun, Protocol::RaydiumCpmm]
+    );
+    assert!(!stream_handle.is_enabled(&Protocol::Bonk));
+}
+
+#[test]
+fn test_builtin_parsers_are_built_once_on_first_use() {
+    assert!(EventParserFactory::is_supported(&Protocol::RaydiumClmm));
+    assert!(!EventParserFactory::is_built(&Protocol::RaydiumClmm));
+
+    let first = EventParserFactory::create_parser(Protocol::RaydiumClmm);
+    assert!(EventParserFactory::is_built(&Protocol::RaydiumClmm));
+    let second = EventParserFactory::create_parser(Protocol::RaydiumClmm);
+    assert!(Arc::ptr_eq(&first, &second));
+}
+
+#[test]
+fn test_program_filter_follows_enabled_protocols() {
+    use solana_sdk::pubkey::Pubkey;
+    use solana_streamer_sdk::streaming::event_parser::protocols::{
+        bonk::parser::BONK_PROGRAM_ID, pumpfun::parser::PUMPFUN_PROGRAM_ID,
+    };
+
+    let protocols = ActiveProtocols::new(vec![Protocol::PumpFun]);
+    let filter = protocols.program_filter();
+    let unrelated = Pubkey::new_unique();
+    assert!(filter.matches_accounts(&[unrelated, PUMPFUN_PROGRAM_ID]));
+    assert!(!filter.matches_accounts(&[unrelated, BONK_PROGRAM_ID]));
+    assert!(filter.matches_keys([unrelated.as_ref(), PUMPFUN_PROGRAM_ID.as_ref()]));
+
+    protocols.enable(Protocol::Bonk);
+    assert!(protocols
+        .program_filter()
+        .matches_accounts(&[BONK_PROGRAM_ID]));
+    protocols.set(vec![]);
+    assert!(protocols.program_filter().is_empty());
+}
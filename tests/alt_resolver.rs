@@ -0,0 +1,138 @@
+use anyhow::Result;
+use solana_sdk::{
+    hash::Hash,
+    instruction::CompiledInstruction,
+    message::{v0, MessageHeader, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+use solana_streamer_sdk::streaming::{AltResolver, LookupTableLoader};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+struct StaticLoader {
+    addresses: Vec<Pubkey>,
+    loads: AtomicUsize,
+}
+
+#[async_trait::async_trait]
+impl LookupTableLoader for StaticLoader {
+    async fn load(&self, _table: &Pubkey) -> Result<Vec<Pubkey>> {
+        self.loads.fetch_add(1, Ordering::Relaxed);
+        Ok(self.addresses.clone())
+    }
+}
+
+#[tokio::test]
+async fn test_resolves_lookup_table_accounts_in_runtime_order() -> Result<()> {
+    let table = Pubkey::new_unique();
+    let addresses: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let loader = Arc::new(StaticLoader {
+        addresses: addresses.clone(),
+        loads: AtomicUsize::new(0),
+    });
+    let resolver = AltResolver::new(loader.clone());
+
+    let payer = Pubkey::new_unique();
+    let program = Pubkey::new_unique();
+    let message = v0::Message {
+        header: MessageHeader {
+            num_required_signatures: 1,
+            num_readonly_signed_accounts: 0,
+            num_readonly_unsigned_accounts: 1,
+        },
+        account_keys: vec![payer, program],
+        recent_blockhash: Hash::default(),
+        instructions: vec![CompiledInstruction::new_from_raw_parts(
+            1,
+            vec![],
+            vec![0, 2, 3],
+        )],
+        address_table_lookups: vec![v0::MessageAddressTableLookup {
+            account_key: table,
+            writable_indexes: vec![3],
+            readonly_indexes: vec![1],
+        }],
+    };
+    let tx = VersionedTransaction {
+        signatures: vec![Signature::default()],
+        message: VersionedMessage::V0(message),
+    };
+
+    let accounts = resolver.resolve_accounts(&tx).await?;
+    assert_eq!(accounts, vec![payer, program, addresses[3], addresses[1]]);
+
+    // Second resolution is served from the cache
+    resolver.resolve_accounts(&tx).await?;
+    assert_eq!(loader.loads.load(Ordering::Relaxed), 1);
+    assert_eq!(resolver.cached_tables(), 1);
+    Ok(())
+}
+
+struct FailingLoader;
+
+#[async_trait::async_trait]
+impl LookupTableLoader for FailingLoader {
+    async fn load(&self, table: &Pubkey) -> Result<Vec<Pubkey>> {
+        Err(anyhow::anyhow!("account {} not found", table))
+    }
+}
+
+fn transaction(address_table_lookups: Vec<v0::MessageAddressTableLookup>) -> VersionedTransaction {
+    let message = v0::Message {
+        header: MessageHeader {
+            num_required_signatures: 1,
+            num_readonly_signed_accounts: 0,
+            num_readonly_unsigned_accounts: 1,
+        },
+        account_keys: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+        recent_blockhash: Hash::default(),
+        instructions: vec![CompiledInstruction::new_from_raw_parts(
+            1,
+            vec![],
+            vec![0, 2],
+        )],
+        address_table_lookups,
+    };
+    VersionedTransaction {
+        signatures: vec![Signature::default()],
+        message: VersionedMessage::V0(message),
+    }
+}
+
+#[tokio::test]
+async fn test_unresolvable_lookup_tables_skip_the_transaction() {
+    let resolver = AltResolver::new(Arc::new(FailingLoader));
+    let tx = transaction(vec![v0::MessageAddressTableLookup {
+        account_key: Pubkey::new_unique(),
+        writable_indexes: vec![0],
+        readonly_indexes: vec![],
+    }]);
+    assert!(resolver.resolve_accounts(&tx).await.is_err());
+    assert_eq!(resolver.unresolved(), 0);
+    assert_eq!(resolver.resolve_for_parsing(&tx).await, None);
+    assert_eq!(resolver.unresolved(), 1);
+    assert_eq!(resolver.cached_tables(), 0);
+
+    // Transactions without lookups don't need the loader
+    let tx = transaction(vec![]);
+    let accounts = resolver.resolve_for_parsing(&tx).await.unwrap();
+    assert_eq!(accounts, tx.message.static_account_keys());
+    assert_eq!(resolver.unresolved(), 1);
+}
+
+#[tokio::test]
+async fn test_lookup_index_past_the_table_end_skips_the_transaction() {
+    let resolver = AltResolver::new(Arc::new(StaticLoader {
+        addresses: vec![Pubkey::new_unique()],
+        loads: AtomicUsize::new(0),
+    }));
+    let tx = transaction(vec![v0::MessageAddressTableLookup {
+        account_key: Pubkey::new_unique(),
+        writable_indexes: vec![],
+        readonly_indexes: vec![1],
+    }]);
+    assert_eq!(resolver.resolve_for_parsing(&tx).await, None);
+    assert_eq!(resolver.unresolved(), 1);
+}
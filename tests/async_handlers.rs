@@ -0,0 +1,61 @@
+#![cfg(feature = "runtime")]
+
+use solana_streamer_sdk::streaming::event_parser::protocols::{
+    pumpfun::PumpFunTradeEvent, pumpswap::PumpSwapBuyEvent,
+};
+use solana_streamer_sdk::streaming::AsyncHandlerRegistry;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_handlers_run_and_slow_handlers_time_out() {
+    let total = Arc::new(AtomicU64::new(0));
+    let sum = total.clone();
+    let dispatcher = AsyncHandlerRegistry::new()
+        .with_concurrency(2)
+        .with_timeout(Duration::from_millis(50))
+        .on("sum", move |e: PumpFunTradeEvent| {
+            let sum = sum.clone();
+            async move {
+                sum.fetch_add(e.sol_amount, Ordering::Relaxed);
+            }
+        })
+        .on_any("slow", |_| tokio::time::sleep(Duration::from_secs(10)))
+        .spawn();
+
+    for sol_amount in 1..=3 {
+        assert!(dispatcher.dispatch(Box::new(PumpFunTradeEvent {
+            sol_amount,
+            ..Default::default()
+        })));
+    }
+    assert!(dispatcher.dispatch(Box::new(PumpSwapBuyEvent::default())));
+
+    let stats = dispatcher.stats();
+    dispatcher.close().await.unwrap();
+    assert_eq!(total.load(Ordering::Relaxed), 6);
+    assert_eq!(stats.completed.load(Ordering::Relaxed), 3);
+    assert_eq!(stats.timed_out.load(Ordering::Relaxed), 4);
+    assert_eq!(stats.dropped.load(Ordering::Relaxed), 0);
+}
+
+#[tokio::test]
+async fn test_full_queue_drops_events() {
+    let dispatcher = AsyncHandlerRegistry::new()
+        .with_queue_capacity(1)
+        .with_concurrency(1)
+        .on_any("slow", |_| tokio::time::sleep(Duration::from_millis(20)))
+        .spawn();
+    let callback = dispatcher.callback();
+    for _ in 0..10 {
+        callback(Box::new(PumpSwapBuyEvent::default()));
+    }
+    drop(callback);
+
+    let stats = dispatcher.stats();
+    dispatcher.close().await.unwrap();
+    let dropped = stats.dropped.load(Ordering::Relaxed);
+    assert!(dropped > 0);
+    assert_eq!(stats.completed.load(Ordering::Relaxed) + dropped, 10);
+}
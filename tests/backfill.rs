@@ -0,0 +1,107 @@
+#![cfg(feature = "rpc")]
+
+use std::sync::{Arc, Mutex};
+
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_rpc_client_api::custom_error::{
+    JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE,
+    JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_SLOT_SKIPPED, JSON_RPC_SERVER_ERROR_SLOT_SKIPPED,
+};
+use solana_rpc_client_api::request::{RpcError, RpcResponseErrorData};
+use solana_streamer_sdk::common::SolanaRpcClient;
+use solana_streamer_sdk::streaming::backfill::Backfiller;
+use solana_streamer_sdk::streaming::checkpoint::ResumeCheckpoint;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::EventMetadata, protocols::pumpfun::PumpFunTradeEvent, UnifiedEvent,
+};
+use solana_streamer_sdk::streaming::replay::RpcBlockSource;
+
+fn rpc_error(code: i64) -> ClientError {
+    ClientError::from(ClientErrorKind::RpcError(RpcError::RpcResponseError {
+        code,
+        message: "test".to_string(),
+        data: RpcResponseErrorData::Empty,
+    }))
+}
+
+fn backfiller(checkpoint: &Arc<ResumeCheckpoint>) -> Backfiller {
+    // The client is never called by these tests
+    let client = Arc::new(SolanaRpcClient::new("http://127.0.0.1:1".to_string()));
+    Backfiller::new(client, vec![], checkpoint.clone())
+}
+
+fn event(slot: u64) -> Box<dyn UnifiedEvent> {
+    Box::new(PumpFunTradeEvent {
+        metadata: EventMetadata {
+            slot,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_block_not_available_is_not_a_skipped_slot() {
+    assert!(RpcBlockSource::is_skipped_slot(&rpc_error(
+        JSON_RPC_SERVER_ERROR_SLOT_SKIPPED
+    )));
+    assert!(RpcBlockSource::is_skipped_slot(&rpc_error(
+        JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_SLOT_SKIPPED
+    )));
+
+    let not_available = rpc_error(JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE);
+    assert!(!RpcBlockSource::is_skipped_slot(&not_available));
+    assert!(RpcBlockSource::is_block_not_available(&not_available));
+    assert!(!RpcBlockSource::is_block_not_available(&rpc_error(
+        JSON_RPC_SERVER_ERROR_SLOT_SKIPPED
+    )));
+}
+
+#[test]
+fn test_live_slots_advance_an_empty_checkpoint() {
+    let checkpoint = Arc::new(ResumeCheckpoint::new(0));
+    let backfiller = backfiller(&checkpoint);
+    assert!(backfiller.is_gap_filled());
+    let delivered = Arc::new(Mutex::new(Vec::new()));
+    let sink = delivered.clone();
+    let callback = backfiller.live_callback(move |event: Box<dyn UnifiedEvent>| {
+        sink.lock().unwrap().push(event.slot());
+    });
+
+    callback(event(5));
+    callback(event(5));
+    // Slot 5 is only complete once a later slot arrives
+    assert_eq!(checkpoint.slot(), 0);
+    callback(event(6));
+    assert_eq!(checkpoint.slot(), 5);
+    callback(event(8));
+    assert_eq!(checkpoint.slot(), 6);
+    assert_eq!(*delivered.lock().unwrap(), [5, 5, 6, 8]);
+}
+
+#[tokio::test]
+async fn test_live_slots_advance_the_checkpoint_once_the_gap_is_filled() {
+    let checkpoint = Arc::new(ResumeCheckpoint::new(10));
+    let backfiller = backfiller(&checkpoint);
+    assert!(!backfiller.is_gap_filled());
+    let callback = backfiller.live_callback(|_event: Box<dyn UnifiedEvent>| {});
+
+    // Delivered before the restart
+    callback(event(10));
+    callback(event(11));
+    callback(event(12));
+    callback(event(13));
+    // Not advanced while the gap may be unfilled
+    assert_eq!(checkpoint.slot(), 10);
+
+    // The live stream started right after the checkpoint, so there is no gap to
+    // fetch and the checkpoint catches up with the completed live slots
+    let noop = |_event: Box<dyn UnifiedEvent>| {};
+    let stats = backfiller.fill_gap(&noop).await.unwrap();
+    assert_eq!(stats.transactions, 0);
+    assert!(backfiller.is_gap_filled());
+    assert_eq!(checkpoint.slot(), 12);
+
+    callback(event(14));
+    assert_eq!(checkpoint.slot(), 13);
+}
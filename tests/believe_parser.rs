@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{DevAddressRegistry, EventType, InstructionIndex},
+    core::{BelieveDevTagger, EventPostProcessor, PostProcessContext},
+    protocols::believe::{
+        discriminators, parser::BELIEVE_PROGRAM_ID, register_pool_config, BelieveCreateTokenEvent,
+        BelieveTradeEvent,
+    },
+    EventParserFactory, Protocol, UnifiedEvent,
+};
+
+fn swap_instruction(accounts: &[Pubkey]) -> Vec<Box<dyn UnifiedEvent>> {
+    let mut data = discriminators::SWAP_IX.to_vec();
+    data.extend_from_slice(&500_000_000u64.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+    let instruction = CompiledInstruction {
+        program_id_index: 15,
+        accounts: (0..15).collect(),
+        data,
+    };
+    EventParserFactory::create_parser(Protocol::Believe).parse_events_from_instruction(
+        &instruction,
+        accounts,
+        "sig",
+        1,
+        None,
+        0,
+        InstructionIndex::outer(0),
+    )
+}
+
+#[test]
+fn test_believe_swap_requires_registered_config() {
+    let mut accounts: Vec<Pubkey> = (0..15).map(|_| Pubkey::new_unique()).collect();
+    accounts.push(BELIEVE_PROGRAM_ID);
+    // 未注册的配置属于其他发射台
+    assert!(swap_instruction(&accounts).is_empty());
+
+    register_pool_config(accounts[1]);
+    let events = swap_instruction(&accounts);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type(), EventType::BelieveTrade);
+    let trade = events[0]
+        .as_any()
+        .downcast_ref::<BelieveTradeEvent>()
+        .expect("Believe trade event");
+    assert_eq!(trade.pool, accounts[2]);
+    assert_eq!(trade.base_mint, accounts[7]);
+    assert_eq!(trade.payer, accounts[9]);
+    assert_eq!(trade.requested_amount_in, 500_000_000);
+}
+
+#[test]
+fn test_believe_dev_tagger_attributes_creator() {
+    let (creator, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let registry = Arc::new(DevAddressRegistry::new(16));
+    let tagger = BelieveDevTagger::new().with_registry(registry.clone());
+
+    let mut events: Vec<Box<dyn UnifiedEvent>> = vec![Box::new(BelieveCreateTokenEvent {
+        creator,
+        base_mint: mint,
+        ..Default::default()
+    })];
+    tagger.process(&mut events, &PostProcessContext::default());
+
+    // 后续交易中的创建者从注册表获得
+    let mut events: Vec<Box<dyn UnifiedEvent>> = vec![
+        Box::new(BelieveTradeEvent {
+            base_mint: mint,
+            payer: creator,
+            ..Default::default()
+        }),
+        Box::new(BelieveTradeEvent {
+            base_mint: mint,
+            payer: Pubkey::new_unique(),
+            ..Default::default()
+        }),
+    ];
+    tagger.process(&mut events, &PostProcessContext::default());
+    let trades: Vec<&BelieveTradeEvent> = events
+        .iter()
+        .filter_map(|event| event.as_any().downcast_ref::<BelieveTradeEvent>())
+        .collect();
+    assert!(trades[0].is_dev_create_token_trade);
+    assert!(!trades[1].is_dev_create_token_trade);
+    assert_eq!(trades[1].creator, creator);
+}
@@ -0,0 +1,91 @@
+use solana_streamer_sdk::streaming::event_parser::common::{EventMetadata, InstructionIndex};
+use solana_streamer_sdk::streaming::event_parser::protocols::pumpfun::PumpFunTradeEvent;
+use solana_streamer_sdk::streaming::event_parser::UnifiedEvent;
+use solana_streamer_sdk::streaming::{BlockEventAggregator, BlockMeta};
+
+fn trade(slot: u64, tx_index: u64, outer: u16) -> Box<dyn UnifiedEvent> {
+    Box::new(PumpFunTradeEvent {
+        metadata: EventMetadata {
+            slot,
+            tx_index: Some(tx_index),
+            index: InstructionIndex::outer(outer),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+fn meta(slot: u64) -> BlockMeta {
+    BlockMeta {
+        slot,
+        blockhash: format!("hash-{}", slot),
+        parent_slot: slot - 1,
+        executed_transaction_count: 1200,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_block_events_are_grouped_and_ordered() {
+    let mut aggregator = BlockEventAggregator::new();
+    aggregator.push(trade(100, 7, 0));
+    aggregator.push(trade(101, 0, 0));
+    aggregator.push(trade(100, 2, 3));
+    aggregator.push(trade(100, 2, 1));
+    assert_eq!(aggregator.pending_slots(), vec![100, 101]);
+
+    let block = aggregator.complete(meta(100));
+    assert_eq!(block.slot(), 100);
+    assert_eq!(block.meta.parent_slot, 99);
+    assert_eq!(block.tx_count(), 1200);
+    let order: Vec<(Option<u64>, InstructionIndex)> = block
+        .events
+        .iter()
+        .map(|event| (event.metadata().tx_index, event.index()))
+        .collect();
+    assert_eq!(
+        order,
+        vec![
+            (Some(2), InstructionIndex::outer(1)),
+            (Some(2), InstructionIndex::outer(3)),
+            (Some(7), InstructionIndex::outer(0)),
+        ]
+    );
+    assert_eq!(aggregator.pending_slots(), vec![101]);
+    assert!(aggregator.complete(meta(102)).events.is_empty());
+}
+
+#[test]
+fn test_incomplete_slots_are_bounded() {
+    let mut aggregator = BlockEventAggregator::new().with_max_pending_slots(2);
+    for slot in 10..14 {
+        aggregator.push(trade(slot, 0, 0));
+    }
+    assert_eq!(aggregator.pending_slots(), vec![12, 13]);
+    assert_eq!(aggregator.dropped_slots(), 2);
+}
+
+#[test]
+fn test_slot_and_block_meta_events_are_unified_events() {
+    use solana_streamer_sdk::streaming::event_parser::common::{event_from_json, EventType};
+    use solana_streamer_sdk::streaming::{BlockMetaEvent, SlotStatus, SlotUpdateEvent};
+
+    let processed = SlotUpdateEvent::new(100, Some(99), SlotStatus::Processed, None, 0);
+    let confirmed = SlotUpdateEvent::new(100, Some(99), SlotStatus::Confirmed, None, 0);
+    assert_eq!(processed.event_type(), EventType::SlotUpdate);
+    assert_ne!(processed.id(), confirmed.id());
+
+    let block = BlockMetaEvent::new(
+        BlockMeta {
+            block_time: Some(1_700_000_000),
+            ..meta(100)
+        },
+        0,
+    );
+    assert_eq!(block.metadata.block_time_ms, 1_700_000_000_000);
+    let restored = event_from_json("BlockMeta", &block.to_json()).unwrap();
+    assert_eq!(
+        restored.as_any().downcast_ref::<BlockMetaEvent>(),
+        Some(&block)
+    );
+}
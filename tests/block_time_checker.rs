@@ -0,0 +1,97 @@
+use solana_streamer_sdk::streaming::enrichment::{
+    BlockTimeAnomaly, BlockTimeChecker, BlockTimeHealth,
+};
+use solana_streamer_sdk::streaming::event_parser::{
+    common::EventMetadata, protocols::pumpfun::PumpFunTradeEvent, UnifiedEvent,
+};
+use solana_streamer_sdk::streaming::pipeline::EventMiddleware;
+
+const BLOCK_TIME_MS: i64 = 1_700_000_000_000;
+
+#[test]
+fn test_checker_classifies_anomalies_and_tracks_drift() {
+    let checker = BlockTimeChecker::new();
+
+    // 可信事件，区块时间只有秒级精度
+    assert_eq!(checker.check(100, BLOCK_TIME_MS, BLOCK_TIME_MS + 400), None);
+    assert_eq!(checker.check(101, BLOCK_TIME_MS, BLOCK_TIME_MS + 800), None);
+    assert_eq!(
+        checker.check(102, BLOCK_TIME_MS - 1_000, BLOCK_TIME_MS + 200),
+        None
+    );
+
+    assert_eq!(
+        checker.check(102, BLOCK_TIME_MS + 10_000, BLOCK_TIME_MS + 1_000),
+        Some(BlockTimeAnomaly::Future)
+    );
+    assert_eq!(
+        checker.check(102, BLOCK_TIME_MS - 200_000, BLOCK_TIME_MS),
+        Some(BlockTimeAnomaly::Stale)
+    );
+    assert_eq!(
+        checker.check(103, BLOCK_TIME_MS - 5_000, BLOCK_TIME_MS + 1_000),
+        Some(BlockTimeAnomaly::Backwards)
+    );
+    // Two slots can't take a minute
+    assert_eq!(
+        checker.check(104, BLOCK_TIME_MS + 60_000, BLOCK_TIME_MS + 60_500),
+        Some(BlockTimeAnomaly::TooFast)
+    );
+
+    let health = checker.health();
+    assert_eq!(
+        health,
+        BlockTimeHealth {
+            samples: 7,
+            suspect: 4,
+            future: 1,
+            stale: 1,
+            backwards: 1,
+            too_fast: 1,
+            last_drift_ms: 1_200,
+            min_drift_ms: 400,
+            max_drift_ms: 1_200,
+            total_drift_ms: 2_400,
+        }
+    );
+    assert_eq!(health.mean_drift_ms(), 800);
+
+    checker.reset();
+    assert_eq!(checker.health(), BlockTimeHealth::default());
+}
+
+#[test]
+fn test_stale_check_can_be_disabled_for_replays() {
+    let checker = BlockTimeChecker::new().with_max_lag(None);
+    assert_eq!(
+        checker.check(1, BLOCK_TIME_MS - 200_000, BLOCK_TIME_MS),
+        None
+    );
+    assert_eq!(checker.health().last_drift_ms, 200_000);
+}
+
+#[test]
+fn test_checker_stage_flags_suspect_events() {
+    let checker = BlockTimeChecker::new();
+    let event = |block_time_ms: i64| -> Box<dyn UnifiedEvent> {
+        Box::new(PumpFunTradeEvent {
+            metadata: EventMetadata {
+                slot: 10,
+                block_time_ms,
+                program_received_time_ms: BLOCK_TIME_MS,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    };
+
+    let events = checker.handle(event(BLOCK_TIME_MS - 500));
+    assert!(!events[0].metadata().block_time_suspect);
+    let events = checker.handle(event(BLOCK_TIME_MS + 10_000));
+    assert!(events[0].metadata().block_time_suspect);
+
+    // Events without a block time aren't checked
+    let events = checker.handle(event(0));
+    assert!(!events[0].metadata().block_time_suspect);
+    assert_eq!(checker.health().samples, 2);
+}
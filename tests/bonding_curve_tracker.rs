@@ -0,0 +1,170 @@
+use anyhow::Result;
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::{
+    protocols::pumpfun::PumpFunTradeEvent, EventParserFactory, Protocol,
+};
+use solana_streamer_sdk::streaming::pipeline::EventMiddleware;
+use solana_streamer_sdk::streaming::trackers::{
+    BondingCurveTracker, GraduationProgressEvent, PriceUpdatedEvent,
+};
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransactionWithStatusMeta,
+};
+use std::fs;
+use std::str::FromStr;
+
+/// The PumpFun trade of a fixture transaction
+async fn fixture_trade(path: &str) -> Result<PumpFunTradeEvent> {
+    let data = fs::read_to_string(path)?;
+    let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(&data)?;
+    let parser = EventParserFactory::create_parser(Protocol::PumpFun);
+    let events = parser
+        .parse_transaction(
+            EncodedTransactionWithStatusMeta {
+                transaction: tx.transaction.transaction.clone(),
+                meta: tx.transaction.meta.clone(),
+                version: tx.transaction.version,
+            },
+            "fixture",
+            Some(tx.slot),
+            None,
+            0,
+            None,
+        )
+        .await?;
+    Ok(events
+        .iter()
+        .find_map(|e| e.as_any().downcast_ref::<PumpFunTradeEvent>())
+        .expect("fixture has a trade")
+        .clone())
+}
+
+/// Bonding curve account data as laid out on chain
+fn curve_account(real_token_reserves: u64, complete: bool) -> Vec<u8> {
+    let mut data = vec![0u8; 8];
+    for value in [
+        1_073_000_000_000_000u64,
+        30_000_000_000,
+        real_token_reserves,
+        85_000_000_000,
+        1_000_000_000_000_000,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.push(complete as u8);
+    data
+}
+
+#[tokio::test]
+async fn test_trades_set_curve_state_and_price() -> Result<()> {
+    let tracker = BondingCurveTracker::new();
+    let trade = fixture_trade("tests/fixtures/pumpfun_cpi_tx.json").await?;
+    let mint = Pubkey::from_str("Ac9UhxTAvhbqC6e9LbKvHkMgtBt8kZSpmRQrVBqJpump")?;
+
+    let update = tracker.apply_trade(&trade).expect("trade carries reserves");
+    assert_eq!(update.mint, mint);
+    assert_eq!(
+        update.bonding_curve,
+        Pubkey::from_str("5YRpiJSMQwnG3Jpq1byRGedtXgrKQx2dJnsR1ZcDz3pH")?
+    );
+
+    let state = tracker.get_state(&mint).unwrap();
+    assert_eq!(state.virtual_sol_reserves, 41_133_990_957);
+    assert_eq!(state.virtual_token_reserves, 782_564_474_266_348);
+    assert_eq!(state.real_sol_reserves, 11_133_990_957);
+    assert_eq!(state.real_token_reserves, 502_664_474_266_348);
+    assert_eq!(state.last_slot, trade.metadata.slot);
+    assert!(!state.complete);
+
+    // 价格以每个完整代币的SOL计
+    let price = tracker.get_price(&mint).unwrap();
+    assert!((price - 5.256_306_963_788_894e-8).abs() < 1e-18);
+    assert_eq!(update.price, price);
+    let progress = tracker.get_progress_to_graduation(&mint).unwrap();
+    assert!((progress - 36.620_290_724).abs() < 1e-6);
+    assert_eq!(update.progress, progress);
+
+    // Older trades don't roll the curve back
+    let mut stale = fixture_trade("tests/fixtures/pumpfun_direct_tx.json").await?;
+    stale.mint = mint;
+    stale.metadata.slot = trade.metadata.slot - 1;
+    stale.metadata.id = "stale".to_string();
+    assert!(tracker.apply_trade(&stale).is_none());
+    assert_eq!(tracker.get_state(&mint).unwrap(), state);
+
+    // Trades parsed from the instruction alone carry no reserves
+    let instruction_only = PumpFunTradeEvent {
+        mint,
+        ..Default::default()
+    };
+    assert!(tracker.apply_trade(&instruction_only).is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pipeline_stage_tracks_progress_to_completion() -> Result<()> {
+    let tracker = BondingCurveTracker::new();
+    let trade = fixture_trade("tests/fixtures/pumpfun_direct_tx.json").await?;
+    let mint = trade.mint;
+
+    let events = tracker.handle(Box::new(trade.clone()));
+    assert_eq!(events.len(), 2);
+    let update = events[1]
+        .as_any()
+        .downcast_ref::<PriceUpdatedEvent>()
+        .unwrap();
+    assert!((update.progress - 28.946_548_033).abs() < 1e-6);
+
+    // The last tokens of the curve are bought
+    let mut last_buy = trade.clone();
+    last_buy.metadata.slot += 1;
+    last_buy.metadata.id = "last_buy".to_string();
+    last_buy.real_token_reserves = 0;
+    let events = tracker.handle(Box::new(last_buy));
+    let progress: Vec<&GraduationProgressEvent> = events
+        .iter()
+        .filter_map(|e| e.as_any().downcast_ref::<GraduationProgressEvent>())
+        .collect();
+    let thresholds: Vec<f64> = progress.iter().map(|p| p.threshold).collect();
+    assert_eq!(thresholds, [50.0, 75.0, 90.0, 100.0]);
+    assert!((progress[3].previous_progress - 28.946_548_033).abs() < 1e-6);
+    let state = tracker.get_state(&mint).unwrap();
+    assert!(state.complete);
+    assert_eq!(tracker.get_progress_to_graduation(&mint), Some(100.0));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_account_updates_of_known_curves() -> Result<()> {
+    let tracker = BondingCurveTracker::new();
+    let trade = fixture_trade("tests/fixtures/pumpfun_cpi_tx.json").await?;
+    tracker.apply_trade(&trade).unwrap();
+    let slot = trade.metadata.slot;
+
+    // The account doesn't store its mint, so unknown curves are ignored
+    assert!(tracker
+        .update_from_account(&Pubkey::new_unique(), &curve_account(1, false), slot + 1)
+        .is_none());
+    assert!(tracker
+        .update_from_account(&trade.bonding_curve, &[0u8; 16], slot + 1)
+        .is_none());
+
+    let state = tracker
+        .update_from_account(
+            &trade.bonding_curve,
+            &curve_account(400_000_000_000_000, false),
+            slot + 1,
+        )
+        .unwrap();
+    assert_eq!(state.mint, trade.mint);
+    assert_eq!(state.virtual_sol_reserves, 30_000_000_000);
+    assert_eq!(state.token_total_supply, 1_000_000_000_000_000);
+    assert!(!state.complete);
+
+    let state = tracker
+        .update_from_account(&trade.bonding_curve, &curve_account(0, true), slot + 2)
+        .unwrap();
+    assert!(state.complete);
+    assert_eq!(state.progress_to_graduation(), 100.0);
+    Ok(())
+}
@@ -1,41 +1,8 @@
 use anyhow::Result;
-use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::{commitment_config::CommitmentConfig, signature::Signature};
-use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
-use std::fs;
+use solana_streamer_sdk::streaming::fixtures::capture_at;
 use std::path::Path;
-use std::str::FromStr;
 use solana_streamer_sdk::streaming::event_parser::UnifiedEvent;
 
-async fn fetch_transaction_fixture(
-    signature: &str,
-    fixture_path: &Path,
-) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
-    if fixture_path.exists() {
-        let data = fs::read_to_string(fixture_path)?;
-        return Ok(serde_json::from_str(&data)?);
-    }
-
-    let client = RpcClient::new_with_commitment(
-        "https://api.mainnet-beta.solana.com".to_string(),
-        CommitmentConfig::confirmed(),
-    );
-
-    let tx = client
-        .get_transaction_with_config(
-            &Signature::from_str(signature)?,
-            solana_client::rpc_config::RpcTransactionConfig {
-                encoding: Some(UiTransactionEncoding::Base64),
-                commitment: Some(CommitmentConfig::confirmed()),
-                max_supported_transaction_version: Some(0),
-            },
-        )
-        .await?;
-
-    fs::write(fixture_path, serde_json::to_string_pretty(&tx)?)?;
-    Ok(tx)
-}
-
 #[tokio::test]
 async fn test_bonk_cpi_transaction_parsing() -> Result<()> {
     use solana_streamer_sdk::streaming::event_parser::{
@@ -46,7 +13,7 @@ async fn test_bonk_cpi_transaction_parsing() -> Result<()> {
     // Transaction with Bonk called via CPI (currently failing to parse correctly)
     let signature = "8QTg427xrHugSxamZCjXNXuyhkZdosPq8EkL7DZkJ82JB5sqR6igKsRgsD9jjwQjRyRrQFiZcDFeeT7uSxMwxXw";
     let fixture_path = Path::new("tests/fixtures/bonk_cpi_tx.json");
-    let tx = fetch_transaction_fixture(signature, fixture_path).await?;
+    let tx = capture_at(signature, fixture_path).await?;
 
     let parser = EventParserFactory::create_parser(Protocol::Bonk);
     let encoded_tx = EncodedTransactionWithStatusMeta {
@@ -126,7 +93,7 @@ async fn test_bonk_direct_transaction_parsing() -> Result<()> {
     // Transaction with Bonk called directly (for comparison)
     let signature = "3USu3YAsg2qBXKmMqZg4UUgJLj9yNmwQ2oyewmbZ1WtyqWmhGhd2B7aru976UCkEjV1w9AR8XjSpE1WxmCy81aKf";
     let fixture_path = Path::new("tests/fixtures/bonk_direct_tx.json");
-    let tx = fetch_transaction_fixture(signature, fixture_path).await?;
+    let tx = capture_at(signature, fixture_path).await?;
 
     let parser = EventParserFactory::create_parser(Protocol::Bonk);
     let encoded_tx = EncodedTransactionWithStatusMeta {
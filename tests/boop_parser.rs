@@ -0,0 +1,63 @@
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventType, InstructionIndex},
+    protocols::boop::{discriminators, parser::BOOP_PROGRAM_ID, BoopTradeEvent},
+    EventParserFactory, Protocol,
+};
+
+#[test]
+fn test_boop_buy_instruction() {
+    // 账户表: 0..13 为指令账户, 13 为程序
+    let mut accounts: Vec<Pubkey> = (0..13).map(|_| Pubkey::new_unique()).collect();
+    accounts.push(BOOP_PROGRAM_ID);
+
+    let mut data = discriminators::BUY_TOKEN_IX.to_vec();
+    data.extend_from_slice(&1_000_000_000u64.to_le_bytes());
+    data.extend_from_slice(&42u64.to_le_bytes());
+    let instruction = CompiledInstruction {
+        program_id_index: 13,
+        accounts: (0..13).collect(),
+        data,
+    };
+
+    let parser = EventParserFactory::create_parser(Protocol::Boop);
+    let events = parser.parse_events_from_instruction(
+        &instruction,
+        &accounts,
+        "sig",
+        1,
+        None,
+        0,
+        InstructionIndex::outer(0),
+    );
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type(), EventType::BoopBuy);
+
+    let trade = events[0]
+        .as_any()
+        .downcast_ref::<BoopTradeEvent>()
+        .expect("Boop trade event");
+    assert!(trade.is_buy);
+    assert_eq!(trade.mint, accounts[0]);
+    assert_eq!(trade.bonding_curve, accounts[1]);
+    assert_eq!(trade.user, accounts[6]);
+    assert_eq!(trade.amount, 1_000_000_000);
+    assert_eq!(trade.amount_out_min, 42);
+
+    // 账户不足时不产生事件
+    let short = CompiledInstruction {
+        accounts: (0..8).collect(),
+        ..instruction
+    };
+    assert!(parser
+        .parse_events_from_instruction(
+            &short,
+            &accounts,
+            "sig",
+            1,
+            None,
+            0,
+            InstructionIndex::outer(0),
+        )
+        .is_empty());
+}
@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use solana_streamer_sdk::streaming::event_parser::common::{EventType, ProtocolType};
+use solana_streamer_sdk::streaming::event_parser::core::{
+    protocol_health, record_decode, CircuitBreakerConfig, ParserCircuitBreaker,
+};
+
+// 计数是进程级的，每个测试使用不同的协议
+
+#[test]
+fn test_breaker_trips_on_failure_spike_and_recovers() {
+    let protocol = ProtocolType::Tensor;
+    let breaker = ParserCircuitBreaker::new(
+        CircuitBreakerConfig::new()
+            .with_window(Duration::from_secs(10))
+            .with_min_attempts(10)
+            .with_failure_ratio(0.5)
+            .with_passthrough(true),
+    );
+    assert!(breaker.check(&protocol, 0).is_none());
+
+    for i in 0..20 {
+        record_decode(&protocol, i % 4 == 0);
+    }
+    let event = breaker
+        .check(&protocol, 1_000)
+        .expect("breaker should trip");
+    assert_eq!(event.metadata.event_type, EventType::ParserDegraded);
+    assert_eq!(event.protocol, protocol);
+    assert_eq!((event.attempts, event.failures), (20, 15));
+    assert!(event.passthrough);
+    assert!(breaker.is_passthrough(&protocol));
+    assert_eq!(breaker.degraded_protocols(), vec![protocol.clone()]);
+    // 降级期间不重复发出事件
+    for _ in 0..20 {
+        record_decode(&protocol, false);
+    }
+    assert!(breaker.check(&protocol, 2_000).is_none());
+    assert_eq!(breaker.trips(&protocol), 1);
+
+    // 新窗口解码正常后恢复
+    assert!(breaker.check(&protocol, 12_000).is_none());
+    for _ in 0..20 {
+        record_decode(&protocol, true);
+    }
+    assert!(breaker.check(&protocol, 23_000).is_none());
+    assert!(!breaker.is_degraded(&protocol));
+
+    let health = protocol_health(&protocol);
+    assert_eq!((health.attempts, health.failures), (60, 35));
+}
+
+#[test]
+fn test_breaker_ignores_windows_below_min_attempts() {
+    let protocol = ProtocolType::MagicEden;
+    let breaker = ParserCircuitBreaker::new(CircuitBreakerConfig::new().with_min_attempts(50));
+    breaker.check(&protocol, 0);
+    for _ in 0..10 {
+        record_decode(&protocol, false);
+    }
+    assert!(breaker.check(&protocol, 1_000).is_none());
+    assert!(!breaker.is_degraded(&protocol));
+    assert!((protocol_health(&protocol).failure_ratio() - 1.0).abs() < f64::EPSILON);
+}
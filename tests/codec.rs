@@ -0,0 +1,44 @@
+use base64::Engine;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use solana_streamer_sdk::streaming::event_parser::common::codec::{
+    base58_decode, base64_decode, decode_pubkey, hex_decode, CodecError,
+};
+
+#[test]
+fn test_codec_matches_reference_decoders() {
+    let pubkey = Pubkey::new_unique();
+    assert_eq!(decode_pubkey(&pubkey.to_string()), Some(pubkey));
+    assert_eq!(
+        base58_decode(&pubkey.to_string()).unwrap(),
+        pubkey.to_bytes()
+    );
+    let signature = Signature::from([7u8; 64]);
+    assert_eq!(base58_decode(&signature.to_string()).unwrap(), [7u8; 64]);
+    // 指令数据长度任意
+    for data in [
+        vec![],
+        vec![0u8],
+        vec![0, 0, 1],
+        (0..40).collect::<Vec<u8>>(),
+    ] {
+        let encoded = bs58::encode(&data).into_string();
+        assert_eq!(base58_decode(&encoded).unwrap(), data);
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
+        assert_eq!(base64_decode(&encoded).unwrap(), data);
+        assert_eq!(hex_decode(&hex::encode(&data)).unwrap(), data);
+    }
+}
+
+#[test]
+fn test_codec_rejects_invalid_input() {
+    assert!(matches!(base58_decode("0OIl"), Err(CodecError::Base58(_))));
+    assert!(matches!(
+        base64_decode("not base64!"),
+        Err(CodecError::Base64(_))
+    ));
+    assert!(matches!(hex_decode("abc"), Err(CodecError::Hex(_))));
+    assert!(matches!(hex_decode("zz"), Err(CodecError::Hex(_))));
+    assert_eq!(decode_pubkey("too-short"), None);
+    // 31字节的值不是公钥
+    assert_eq!(decode_pubkey(&bs58::encode([1u8; 31]).into_string()), None);
+}
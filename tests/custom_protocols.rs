@@ -0,0 +1,32 @@
+use anyhow::Result;
+use solana_streamer_sdk::streaming::event_parser::{
+    protocols::pumpfun::{parser::PUMPFUN_PROGRAM_ID, PumpFunEventParser, PumpFunTradeEvent},
+    EventParserFactory, Protocol,
+};
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use std::fs;
+use std::str::FromStr;
+
+#[tokio::test]
+async fn test_registered_custom_protocol_parses_like_builtin() -> Result<()> {
+    let protocol = Protocol::Custom("pumpfun_fork".to_string());
+    assert!(!EventParserFactory::is_supported(&protocol));
+    assert!(Protocol::from_str("pumpfun_fork").is_err());
+
+    assert!(EventParserFactory::register(protocol.clone(), PumpFunEventParser::new()).is_none());
+    assert!(EventParserFactory::is_supported(&protocol));
+    assert_eq!(Protocol::from_str("pumpfun_fork")?, protocol);
+    assert_eq!(protocol.get_program_id(), vec![PUMPFUN_PROGRAM_ID]);
+    assert_eq!(EventParserFactory::supported_protocols().last(), Some(&protocol));
+
+    let data = fs::read_to_string("tests/fixtures/pumpfun_cpi_tx.json")?;
+    let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(&data)?;
+    let events = EventParserFactory::create_parser(protocol.clone())
+        .parse_transaction(tx.transaction, "custom", Some(tx.slot), None, 0, None)
+        .await?;
+    assert!(events.iter().any(|e| e.as_any().is::<PumpFunTradeEvent>()));
+
+    assert!(EventParserFactory::unregister(&protocol).is_some());
+    assert!(protocol.get_program_id().is_empty());
+    Ok(())
+}
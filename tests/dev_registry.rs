@@ -0,0 +1,109 @@
+use anyhow::Result;
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{DevAddressRegistry, ParseOptions},
+    core::PostProcessorChain,
+    protocols::pumpfun::PumpFunTradeEvent,
+    EventParserFactory, Protocol,
+};
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransactionWithStatusMeta,
+};
+use std::str::FromStr;
+use std::sync::Arc;
+
+#[test]
+fn test_registry_records_devs_per_mint() {
+    let registry = DevAddressRegistry::new(16);
+    let (mint, user, creator) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    registry.record(mint, user);
+    registry.record(mint, creator);
+    registry.record(mint, user);
+    registry.record(mint, Pubkey::default());
+
+    assert_eq!(registry.dev_addresses(&mint), vec![user, creator]);
+    assert!(registry.is_dev(&mint, &creator));
+    assert!(!registry.is_dev(&mint, &Pubkey::new_unique()));
+    assert!(!registry.is_dev(&Pubkey::new_unique(), &user));
+
+    assert_eq!(registry.remove(&mint), Some(vec![user, creator]));
+    assert!(registry.is_empty());
+    // 默认地址不记录
+    registry.record(Pubkey::new_unique(), Pubkey::default());
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn test_registry_evicts_least_recently_used_mints() {
+    let registry = DevAddressRegistry::new(2);
+    let dev = Pubkey::new_unique();
+    let mints: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    registry.record(mints[0], dev);
+    registry.record(mints[1], dev);
+    // A lookup keeps the first mint recent
+    assert!(registry.is_dev(&mints[0], &dev));
+    registry.record(mints[2], dev);
+
+    assert_eq!(registry.len(), 2);
+    assert!(registry.is_dev(&mints[0], &dev));
+    assert!(!registry.is_dev(&mints[1], &dev));
+
+    registry.resize(1);
+    assert_eq!(registry.len(), 1);
+    assert!(registry.is_dev(&mints[0], &dev));
+
+    registry.clear();
+    assert!(registry.is_empty());
+    // A capacity of zero still remembers one mint
+    let registry = DevAddressRegistry::new(0);
+    registry.record(mints[0], dev);
+    assert_eq!(registry.len(), 1);
+}
+
+/// Whether the trade of the direct buy fixture is tagged as a dev trade
+async fn is_dev_trade(registry: Arc<DevAddressRegistry>) -> Result<bool> {
+    let data = std::fs::read_to_string("tests/fixtures/pumpfun_direct_tx.json")?;
+    let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(&data)?;
+    let parser = EventParserFactory::create_parser(Protocol::PumpFun);
+    let options = ParseOptions::new()
+        .with_post_processors(PostProcessorChain::default_with_registry(registry));
+    let output = parser
+        .parse_transaction_with_errors(
+            EncodedTransactionWithStatusMeta {
+                transaction: tx.transaction.transaction.clone(),
+                meta: tx.transaction.meta.clone(),
+                version: tx.transaction.version,
+            },
+            "registry",
+            Some(tx.slot),
+            None,
+            0,
+            None,
+            options,
+        )
+        .await?;
+    let trade = output
+        .events
+        .iter()
+        .find_map(|e| e.as_any().downcast_ref::<PumpFunTradeEvent>())
+        .expect("fixture has a trade");
+    Ok(trade.is_dev_create_token_trade)
+}
+
+#[tokio::test]
+async fn test_chain_tags_trades_with_its_own_registry() -> Result<()> {
+    let mint = Pubkey::from_str("7k2255ueF3Ecnnjf9odEu7so3gmXKS8E29atDWmFpump")?;
+    let user = Pubkey::from_str("3HeEuccBzrTvWBvQGuiVgqbJcCpzTd4mFZjbKQoz5BYg")?;
+    assert!(!is_dev_trade(Arc::new(DevAddressRegistry::new(16))).await?);
+
+    // The create happened in an earlier transaction
+    let registry = Arc::new(DevAddressRegistry::new(16));
+    registry.record(mint, user);
+    assert!(is_dev_trade(registry).await?);
+    assert!(!DevAddressRegistry::global().is_dev(&mint, &user));
+    Ok(())
+}
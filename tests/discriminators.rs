@@ -0,0 +1,51 @@
+use solana_streamer_sdk::anchor_event_discriminator;
+use solana_streamer_sdk::streaming::event_parser::common::discriminator::{
+    event_discriminator, instruction_discriminator, EVENT_IX_TAG,
+};
+use solana_streamer_sdk::streaming::event_parser::protocols::{bonk, pumpfun, pumpswap};
+
+const TRADE_EVENT: &str = anchor_event_discriminator!("TradeEvent");
+
+#[test]
+fn test_discriminators_match_anchor() {
+    assert_eq!(
+        instruction_discriminator("buy"),
+        [102, 6, 61, 18, 1, 218, 235, 234]
+    );
+    assert_eq!(
+        instruction_discriminator("swap_base_output"),
+        [55, 217, 98, 86, 163, 74, 180, 173]
+    );
+    assert_eq!(
+        EVENT_IX_TAG,
+        [0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d]
+    );
+    assert_eq!(TRADE_EVENT, "0xe445a52e51cb9a1dbddb7fd34ee661ee");
+    assert_eq!(
+        hex::encode(event_discriminator("CreatePoolEvent")),
+        "b1310cd2a076a774"
+    );
+
+    assert_eq!(
+        pumpfun::discriminators::CREATE_TOKEN_EVENT,
+        "0xe445a52e51cb9a1d1b72a94ddeeb6376"
+    );
+    assert_eq!(
+        pumpswap::discriminators::WITHDRAW_IX,
+        &[183, 18, 70, 156, 148, 109, 161, 34]
+    );
+    assert_eq!(
+        bonk::discriminators::POOL_CREATE_EVENT,
+        "0xe445a52e51cb9a1d97d7e20976a173ae"
+    );
+}
+
+#[test]
+fn test_long_names_span_blocks() {
+    // 超过一个SHA-256块的预映像
+    let name = "a_very_long_instruction_name_that_spans_more_than_one_sha_block_for_sure_ok";
+    assert_eq!(
+        instruction_discriminator(name),
+        [136, 59, 107, 59, 168, 28, 64, 115]
+    );
+}
@@ -0,0 +1,84 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{encode_base64, event_discriminator, EventType},
+    protocols::drift::{parser::DRIFT_PROGRAM_ID, DriftFillEvent, MarketType},
+    EventParserFactory, Protocol,
+};
+
+fn some_u64(data: &mut Vec<u8>, value: u64) {
+    data.push(1);
+    data.extend_from_slice(&value.to_le_bytes());
+}
+
+/// OrderActionRecord of a taker filled by the AMM
+fn fill_record(action: u8, market_type: u8, filler: Pubkey, taker: Pubkey) -> Vec<u8> {
+    let mut data = event_discriminator("OrderActionRecord").to_vec();
+    data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+    data.extend_from_slice(&[action, 6]);
+    data.extend_from_slice(&0u16.to_le_bytes());
+    data.push(market_type);
+    data.push(1);
+    data.extend_from_slice(filler.as_ref());
+    some_u64(&mut data, 10_000);
+    some_u64(&mut data, 77);
+    some_u64(&mut data, 2_000_000_000);
+    some_u64(&mut data, 300_000_000);
+    some_u64(&mut data, 105_000);
+    // maker_fee, referrer_reward, quote_asset_amount_surplus, spot_fulfillment_method_fee
+    data.extend_from_slice(&[0, 0, 0, 0]);
+    data.push(1);
+    data.extend_from_slice(taker.as_ref());
+    data.push(1);
+    data.extend_from_slice(&12u32.to_le_bytes());
+    data.extend_from_slice(&[1, 0]);
+    some_u64(&mut data, 2_000_000_000);
+    some_u64(&mut data, 2_000_000_000);
+    some_u64(&mut data, 300_000_000);
+    // no maker
+    data.extend_from_slice(&[0; 6]);
+    data.extend_from_slice(&150_000_000i64.to_le_bytes());
+    data
+}
+
+#[tokio::test]
+async fn test_drift_fill_records_from_logs() {
+    let (filler, taker) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let logs: Vec<String> = vec![
+        format!("Program {} invoke [1]", DRIFT_PROGRAM_ID),
+        format!(
+            "Program data: {}",
+            encode_base64(&fill_record(2, 1, filler, taker))
+        ),
+        // placement records of the same instruction are not fills
+        format!(
+            "Program data: {}",
+            encode_base64(&fill_record(0, 1, filler, taker))
+        ),
+        format!(
+            "Program data: {}",
+            encode_base64(&fill_record(2, 0, filler, taker))
+        ),
+        format!("Program {} success", DRIFT_PROGRAM_ID),
+    ];
+
+    let events = EventParserFactory::create_parser(Protocol::Drift)
+        .parse_events_from_logs(&logs, "sig", Some(1), None, &[])
+        .await
+        .expect("logs parse");
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].event_type(), EventType::DriftPerpFill);
+    assert_eq!(events[1].event_type(), EventType::DriftSpotFill);
+
+    let fill = events[0]
+        .as_any()
+        .downcast_ref::<DriftFillEvent>()
+        .expect("fill event");
+    assert_eq!(fill.market_type, MarketType::Perp);
+    assert_eq!(fill.filler, Some(filler));
+    assert_eq!(fill.taker, Some(taker));
+    assert_eq!(fill.maker, None);
+    assert_eq!(fill.fill_record_id, Some(77));
+    assert_eq!(fill.base_asset_amount_filled, Some(2_000_000_000));
+    assert_eq!(fill.quote_asset_amount_filled, Some(300_000_000));
+    assert_eq!(fill.oracle_price, 150_000_000);
+}
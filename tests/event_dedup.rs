@@ -0,0 +1,144 @@
+use anyhow::Result;
+use prost_types::Timestamp;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventType, InstructionIndex, ProtocolType},
+    core::traits::GenericEventParseConfig,
+    protocols::pumpfun::PumpFunEventParser,
+    EventParser, EventParserFactory, Protocol, UnifiedEvent,
+};
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiCompiledInstruction};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+const FIXTURES: [(&str, Protocol); 6] = [
+    ("tests/fixtures/pumpfun_cpi_tx.json", Protocol::PumpFun),
+    ("tests/fixtures/pumpfun_direct_tx.json", Protocol::PumpFun),
+    ("tests/fixtures/pumpswap_cpi_tx.json", Protocol::PumpSwap),
+    (
+        "tests/fixtures/pumpswap_sell_cpi_tx.json",
+        Protocol::PumpSwap,
+    ),
+    ("tests/fixtures/bonk_cpi_tx.json", Protocol::Bonk),
+    ("tests/fixtures/bonk_direct_tx.json", Protocol::Bonk),
+];
+
+async fn parse_fixture(path: &str, parser: &dyn EventParser) -> Result<Vec<Box<dyn UnifiedEvent>>> {
+    let tx: EncodedConfirmedTransactionWithStatusMeta =
+        serde_json::from_str(&fs::read_to_string(path)?)?;
+    parser
+        .parse_transaction(tx.transaction, "sig", Some(tx.slot), None, 0, None)
+        .await
+}
+
+fn assert_one_event_per_action(path: &str, events: &[Box<dyn UnifiedEvent>]) {
+    let mut actions: HashSet<(u16, Option<u16>, EventType)> = HashSet::new();
+    let mut ids = HashSet::new();
+    for event in events {
+        let index = event.index();
+        assert!(
+            actions.insert((index.outer, index.inner, event.event_type())),
+            "{path}: {:?} at {index} emitted twice",
+            event.event_type()
+        );
+        assert!(ids.insert(event.id().to_string()), "{path}: duplicate id");
+    }
+}
+
+#[tokio::test]
+async fn test_fixtures_yield_one_event_per_action() -> Result<()> {
+    for (path, protocol) in FIXTURES {
+        let parser = EventParserFactory::create_parser(protocol);
+        let events = parse_fixture(path, parser.as_ref()).await?;
+        assert!(!events.is_empty(), "{path}: no events");
+        assert_one_event_per_action(path, &events);
+    }
+    Ok(())
+}
+
+/// PumpFun parser whose log events are emitted on their own
+struct LogEmittingPumpFun(PumpFunEventParser);
+
+#[async_trait::async_trait]
+impl EventParser for LogEmittingPumpFun {
+    fn parse_events_from_inner_instruction(
+        &self,
+        instruction: &UiCompiledInstruction,
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.0.parse_events_from_inner_instruction(
+            instruction,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn parse_events_from_instruction(
+        &self,
+        instruction: &CompiledInstruction,
+        accounts: &[Pubkey],
+        signature: &str,
+        slot: u64,
+        block_time: Option<Timestamp>,
+        program_received_time_ms: i64,
+        index: InstructionIndex,
+    ) -> Vec<Box<dyn UnifiedEvent>> {
+        self.0.parse_events_from_instruction(
+            instruction,
+            accounts,
+            signature,
+            slot,
+            block_time,
+            program_received_time_ms,
+            index,
+        )
+    }
+
+    fn get_inner_instruction_configs(
+        &self,
+    ) -> &HashMap<&'static str, Vec<GenericEventParseConfig>> {
+        self.0.get_inner_instruction_configs()
+    }
+
+    fn get_protocol_type(&self) -> ProtocolType {
+        self.0.get_protocol_type()
+    }
+
+    fn get_program_id(&self) -> Pubkey {
+        self.0.get_program_id()
+    }
+
+    fn emits_log_events(&self) -> bool {
+        true
+    }
+
+    fn should_handle(&self, program_id: &Pubkey) -> bool {
+        self.0.should_handle(program_id)
+    }
+
+    fn supported_program_ids(&self) -> Vec<Pubkey> {
+        self.0.supported_program_ids()
+    }
+}
+
+#[tokio::test]
+async fn test_log_events_with_an_instruction_event_are_merged() -> Result<()> {
+    // The trade is in the instruction, an emit_cpi! event and a Program data: log
+    let path = "tests/fixtures/pumpfun_cpi_tx.json";
+    let merged = parse_fixture(path, &PumpFunEventParser::new()).await?;
+    let standalone = parse_fixture(path, &LogEmittingPumpFun(PumpFunEventParser::new())).await?;
+    assert_one_event_per_action(path, &standalone);
+    assert_eq!(standalone.len(), merged.len());
+    for (a, b) in merged.iter().zip(standalone.iter()) {
+        assert_eq!(a.id(), b.id());
+        assert_eq!(a.content_hash(), b.content_hash());
+    }
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use anyhow::Result;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{
+        diff_events, event_from_json,
+        identity::{content_hash_of, stable_hash},
+        EventKey, InstructionIndex, EVENT_SCHEMA_VERSION,
+    },
+    EventParserFactory, Protocol, UnifiedEvent,
+};
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransactionWithStatusMeta,
+};
+use std::collections::HashSet;
+use std::fs;
+
+async fn parse_fixture(
+    path: &str,
+    protocol: Protocol,
+    program_received_time_ms: i64,
+) -> Result<Vec<Box<dyn UnifiedEvent>>> {
+    let tx: EncodedConfirmedTransactionWithStatusMeta =
+        serde_json::from_str(&fs::read_to_string(path)?)?;
+    let signature = tx
+        .transaction
+        .transaction
+        .decode()
+        .map(|t| t.signatures[0].to_string())
+        .unwrap_or_default();
+    let encoded_tx = EncodedTransactionWithStatusMeta {
+        transaction: tx.transaction.transaction.clone(),
+        meta: tx.transaction.meta.clone(),
+        version: tx.transaction.version,
+    };
+    let parser = EventParserFactory::create_parser(protocol);
+    parser
+        .parse_transaction(
+            encoded_tx,
+            &signature,
+            Some(tx.slot),
+            None,
+            program_received_time_ms,
+            None,
+        )
+        .await
+}
+
+#[tokio::test]
+async fn test_events_compare_by_identity_not_processing_time() -> Result<()> {
+    let path = "tests/fixtures/pumpfun_cpi_tx.json";
+    let first = parse_fixture(path, Protocol::PumpFun, 0).await?;
+    let second = parse_fixture(path, Protocol::PumpFun, 1_000).await?;
+    assert!(!first.is_empty(), "Should find at least one event");
+
+    // Same events parsed at different times hash and compare equal
+    assert!(diff_events(&first, &second).is_empty());
+    for (a, b) in first.iter().zip(second.iter()) {
+        assert!(a == b);
+        assert_eq!(a.content_hash(), b.content_hash());
+    }
+
+    // Set-based dedup across both runs
+    let unique: HashSet<&dyn UnifiedEvent> =
+        first.iter().chain(second.iter()).map(|e| e.as_ref()).collect();
+    let keys: HashSet<EventKey> = first.iter().map(|e| EventKey::of(e.as_ref())).collect();
+    assert_eq!(unique.len(), keys.len());
+
+    // A missing event shows up on one side of the diff only
+    let missing = EventKey::of(second[0].as_ref());
+    let remaining: Vec<Box<dyn UnifiedEvent>> = second
+        .iter()
+        .filter(|e| EventKey::of(e.as_ref()) != missing)
+        .map(|e| e.clone_boxed())
+        .collect();
+    let diff = diff_events(&first, &remaining);
+    assert_eq!(diff.only_left, vec![missing]);
+    assert!(diff.only_right.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_events_round_trip_through_json() -> Result<()> {
+    let events =
+        parse_fixture("tests/fixtures/pumpswap_cpi_tx.json", Protocol::PumpSwap, 0).await?;
+    assert!(!events.is_empty(), "Should find at least one event");
+    for event in &events {
+        let decoded = event_from_json(&event.event_type().to_string(), &event.to_json())?;
+        assert!(decoded.as_ref() == event.as_ref());
+        assert_eq!(decoded.content_hash(), event.content_hash());
+    }
+    assert!(event_from_json("NotAnEvent", "{}").is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_events_carry_schema_version() -> Result<()> {
+    let events =
+        parse_fixture("tests/fixtures/pumpswap_cpi_tx.json", Protocol::PumpSwap, 0).await?;
+    let event = events.first().expect("Should find at least one event");
+    assert_eq!(event.metadata().schema_version, EVENT_SCHEMA_VERSION);
+
+    // Payloads from before versioning decode as version 0
+    let mut payload: serde_json::Value = serde_json::from_str(&event.to_json())?;
+    payload["metadata"]
+        .as_object_mut()
+        .unwrap()
+        .remove("schema_version");
+    let decoded = event_from_json(&event.event_type().to_string(), &payload.to_string())?;
+    assert_eq!(decoded.metadata().schema_version, 0);
+
+    // The first release had fewer metadata fields and a string index
+    let first_release = [
+        "id",
+        "signature",
+        "slot",
+        "block_time",
+        "block_time_ms",
+        "program_received_time_ms",
+        "program_handle_time_consuming_ms",
+        "protocol",
+        "event_type",
+        "program_id",
+        "transfer_datas",
+    ];
+    payload["metadata"]
+        .as_object_mut()
+        .unwrap()
+        .retain(|field, _| first_release.contains(&field.as_str()));
+    let index = event.metadata().index;
+    metadata_index(&mut payload, &index.to_string());
+    let decoded = event_from_json(&event.event_type().to_string(), &payload.to_string())?;
+    let metadata = decoded.metadata();
+    assert_eq!(metadata.schema_version, 0);
+    assert!(metadata.index.same_instruction(&index));
+    assert_eq!(metadata.signature, event.metadata().signature);
+    assert!(metadata.signers.is_empty());
+
+    metadata_index(&mut payload, "");
+    let decoded = event_from_json(&event.event_type().to_string(), &payload.to_string())?;
+    assert_eq!(decoded.metadata().index, InstructionIndex::default());
+    metadata_index(&mut payload, "not an index");
+    assert!(event_from_json(&event.event_type().to_string(), &payload.to_string()).is_err());
+    Ok(())
+}
+
+fn metadata_index(payload: &mut serde_json::Value, index: &str) {
+    payload["metadata"]["index"] = index.into();
+}
+
+#[test]
+fn test_content_hash_is_pinned_fnv1a() {
+    // FNV-1a reference values: the hash must not change between builds
+    assert_eq!(stable_hash(b""), 0xcbf29ce484222325);
+    assert_eq!(stable_hash(b"a"), 0xaf63dc4c8601ec8c);
+    assert_eq!(stable_hash(b"foobar"), 0x85944171f73967e8);
+    assert_eq!(
+        content_hash_of(&serde_json::json!({ "a": 1 })),
+        0x9c3e82dd6fcae8b1
+    );
+}
+
+#[tokio::test]
+async fn test_content_hash_is_hash_of_normalized_json() -> Result<()> {
+    let events = parse_fixture("tests/fixtures/pumpfun_cpi_tx.json", Protocol::PumpFun, 0).await?;
+    let event = events.first().expect("Should find at least one event");
+    let mut normalized = event.clone_boxed();
+    normalized.metadata_mut().reset_processing_fields();
+    assert_eq!(
+        event.content_hash(),
+        stable_hash(normalized.to_json().as_bytes())
+    );
+    // Processing time doesn't change the hash, content does
+    normalized.metadata_mut().parse_end_time_ms += 1_000;
+    assert_eq!(normalized.content_hash(), event.content_hash());
+    normalized.metadata_mut().slot += 1;
+    assert_ne!(normalized.content_hash(), event.content_hash());
+    Ok(())
+}
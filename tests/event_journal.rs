@@ -0,0 +1,92 @@
+use std::io::Write;
+
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventMetadata, EventType},
+    protocols::pumpfun::PumpFunTradeEvent,
+    UnifiedEvent,
+};
+use solana_streamer_sdk::streaming::journal::crc32;
+use solana_streamer_sdk::streaming::{EventJournal, JournalConfig, JournalCursor};
+
+fn trade(slot: u64) -> PumpFunTradeEvent {
+    PumpFunTradeEvent {
+        metadata: EventMetadata {
+            slot,
+            event_type: EventType::PumpFunBuy,
+            ..Default::default()
+        },
+        sol_amount: slot * 1_000,
+        ..Default::default()
+    }
+}
+
+fn slots(journal: &EventJournal, offset: u64) -> Vec<(u64, u64)> {
+    journal
+        .read_from(offset)
+        .unwrap()
+        .map(|entry| {
+            let entry = entry.unwrap();
+            (entry.offset, entry.event.slot())
+        })
+        .collect()
+}
+
+#[test]
+fn test_crc32_matches_reference() {
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    assert_eq!(crc32(b""), 0);
+}
+
+#[test]
+fn test_journal_replays_from_cursor_after_crash() {
+    let dir = std::env::temp_dir().join(format!("journal-{}", Pubkey::new_unique()));
+    // 每个事件一个分段
+    let config = JournalConfig::new().with_max_segment_bytes(1);
+    let journal = EventJournal::open(&dir, config).unwrap();
+    for slot in 100..110 {
+        journal.append(&trade(slot)).unwrap();
+    }
+    journal.flush().unwrap();
+    assert_eq!(journal.next_offset(), 10);
+    assert_eq!(slots(&journal, 7), [(7, 107), (8, 108), (9, 109)]);
+
+    let entry = journal.read_from(3).unwrap().next().unwrap().unwrap();
+    let event = entry
+        .event
+        .as_any()
+        .downcast_ref::<PumpFunTradeEvent>()
+        .unwrap();
+    assert_eq!(event.sol_amount, 103_000);
+
+    // 崩溃时最后一条记录只写了一半
+    drop(journal);
+    let active = dir.join(format!("{:020}.journal", 9));
+    let mut file = std::fs::OpenOptions::new()
+        .append(true)
+        .open(&active)
+        .unwrap();
+    file.write_all(&[200, 0, 0, 0, 1, 2, 3, 4, 5]).unwrap();
+    drop(file);
+
+    let journal = EventJournal::open(&dir, config).unwrap();
+    assert_eq!(journal.next_offset(), 10);
+    assert_eq!(journal.append(&trade(110)).unwrap(), 10);
+    assert_eq!(slots(&journal, 9), [(9, 109), (10, 110)]);
+
+    // 游标在重启后保留
+    let cursor_path = dir.join("sink.cursor");
+    let cursor = JournalCursor::load(&cursor_path).unwrap();
+    assert_eq!(cursor.offset(), 0);
+    cursor.commit(5).unwrap();
+    cursor.commit(4).unwrap();
+    assert_eq!(JournalCursor::load(&cursor_path).unwrap().offset(), 5);
+
+    // 清理游标之前的分段
+    assert_eq!(journal.remove_before(5).unwrap(), 5);
+    assert_eq!(journal.first_offset().unwrap(), 5);
+    assert!(journal.read_from(0).is_err());
+    assert_eq!(slots(&journal, 5).len(), 6);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
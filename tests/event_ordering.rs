@@ -0,0 +1,83 @@
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventMetadata, InstructionIndex},
+    protocols::pumpfun::PumpFunTradeEvent,
+    UnifiedEvent,
+};
+use solana_streamer_sdk::streaming::{EventSequencer, OrderingConfig};
+
+fn event(slot: u64, tx_index: u64, outer: u16) -> Box<dyn UnifiedEvent> {
+    Box::new(PumpFunTradeEvent {
+        metadata: EventMetadata {
+            slot,
+            tx_index: Some(tx_index),
+            index: InstructionIndex::outer(outer),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+fn positions(events: &[Box<dyn UnifiedEvent>]) -> Vec<(u64, u64, u16, u64)> {
+    events
+        .iter()
+        .map(|event| {
+            let metadata = event.metadata();
+            (
+                metadata.slot,
+                metadata.tx_index.unwrap_or_default(),
+                metadata.index.outer,
+                metadata.sequence,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_sequencer_releases_slots_in_order() {
+    let sequencer = EventSequencer::new(OrderingConfig::new().with_slot_lag(1));
+    assert!(sequencer.push(event(10, 5, 1)).is_empty());
+    assert!(sequencer.push(event(10, 2, 3)).is_empty());
+    assert!(sequencer.push(event(10, 2, 0)).is_empty());
+    // 下一个slot不足以释放slot 10
+    assert!(sequencer.push(event(11, 0, 0)).is_empty());
+    assert_eq!(sequencer.buffered(), 4);
+
+    let ready = sequencer.push(event(12, 1, 0));
+    assert_eq!(
+        positions(&ready),
+        [(10, 2, 0, 1), (10, 2, 3, 2), (10, 5, 1, 3)]
+    );
+
+    // 已释放slot的迟到事件立即交付并计为乱序
+    let late = sequencer.push(event(10, 1, 0));
+    assert_eq!(positions(&late), [(10, 1, 0, 4)]);
+    assert_eq!(sequencer.out_of_order_events(), 1);
+
+    let rest = sequencer.flush();
+    assert_eq!(positions(&rest), [(11, 0, 0, 5), (12, 1, 0, 6)]);
+    assert_eq!(sequencer.next_sequence(), 7);
+    assert_eq!(sequencer.buffered(), 0);
+}
+
+#[test]
+fn test_sequencer_bounds_buffered_events() {
+    let sequencer = EventSequencer::new(
+        OrderingConfig::new()
+            .with_slot_lag(100)
+            .with_max_buffered_events(2),
+    );
+    assert!(sequencer.push(event(1, 0, 0)).is_empty());
+    assert!(sequencer.push(event(2, 0, 0)).is_empty());
+    let ready = sequencer.push(event(3, 0, 0));
+    assert_eq!(positions(&ready), [(1, 0, 0, 1)]);
+
+    // 包装的回调在丢弃时交付剩余事件
+    let delivered = std::sync::Mutex::new(Vec::new());
+    {
+        let callback = sequencer.wrap(|event: Box<dyn UnifiedEvent>| {
+            delivered.lock().unwrap().push(event.metadata().sequence)
+        });
+        callback.call(event(4, 0, 0));
+    }
+    assert_eq!(delivered.into_inner().unwrap(), [2, 3, 4]);
+}
@@ -0,0 +1,100 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use solana_streamer_sdk::streaming::event_parser::{
+    protocols::pumpfun::PumpFunTradeEvent, UnifiedEvent,
+};
+use solana_streamer_sdk::streaming::pipeline::{EventPipeline, FnMiddleware, HANDLER_STAGE};
+
+type Stage = fn(Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>>;
+
+fn event() -> Box<dyn UnifiedEvent> {
+    Box::new(PumpFunTradeEvent::default())
+}
+
+fn pass(event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+    vec![event]
+}
+
+fn slow(event: Box<dyn UnifiedEvent>) -> Vec<Box<dyn UnifiedEvent>> {
+    std::thread::sleep(Duration::from_millis(20));
+    vec![event]
+}
+
+#[test]
+fn test_event_past_deadline_is_flagged_with_the_slow_stage() {
+    let pipeline = EventPipeline::new()
+        .with_deadline(Duration::from_millis(5))
+        .with_stage(FnMiddleware::new("fast", pass as Stage))
+        .with_stage(FnMiddleware::new("slow", slow as Stage))
+        .with_stage(FnMiddleware::new("after", pass as Stage));
+    let metrics = pipeline.metrics();
+
+    let events = pipeline.process(event());
+    assert_eq!(events.len(), 1);
+    assert!(events[0].metadata().deadline_exceeded);
+
+    assert_eq!(metrics.events_processed(), 1);
+    assert_eq!(metrics.deadline_exceeded(), 1);
+    let (stage, stats) = metrics.slowest_stage().unwrap();
+    assert_eq!(stage, "slow");
+    assert_eq!(stats.deadline_exceeded, 1);
+    assert!(stats.max_us >= 20_000);
+    // 只记录越过期限的阶段
+    let stages = metrics.stage_stats();
+    assert_eq!(stages["fast"].deadline_exceeded, 0);
+    assert_eq!(stages["after"].deadline_exceeded, 0);
+    assert_eq!(stages["after"].calls, 1);
+}
+
+#[test]
+fn test_events_within_deadline_are_not_flagged() {
+    let pipeline = EventPipeline::new()
+        .with_deadline(Duration::from_secs(5))
+        .with_stage(FnMiddleware::new("fast", pass as Stage));
+    let events = pipeline.process(event());
+    assert!(!events[0].metadata().deadline_exceeded);
+    assert_eq!(pipeline.metrics().deadline_exceeded(), 0);
+    assert!(pipeline.metrics().slowest_stage().is_none());
+
+    // Without a deadline nothing is flagged however slow the stages are
+    let pipeline = EventPipeline::new().with_stage(FnMiddleware::new("slow", slow as Stage));
+    assert!(!pipeline.process(event())[0].metadata().deadline_exceeded);
+    assert_eq!(pipeline.metrics().deadline_exceeded(), 0);
+}
+
+#[test]
+fn test_dropped_events_skip_later_stages() {
+    let pipeline = EventPipeline::new()
+        .with_stage(FnMiddleware::new("drop", |_: Box<dyn UnifiedEvent>| vec![]))
+        .with_stage(FnMiddleware::new("after", pass as Stage));
+    assert!(pipeline.process(event()).is_empty());
+    let stages = pipeline.metrics().stage_stats();
+    assert_eq!(stages["drop"].calls, 1);
+    assert!(!stages.contains_key("after"));
+}
+
+#[test]
+fn test_slow_handler_is_counted_against_the_deadline() {
+    let pipeline = EventPipeline::new()
+        .with_deadline(Duration::from_millis(5))
+        .with_stage(FnMiddleware::new("fast", pass as Stage));
+    let metrics = pipeline.metrics();
+    let received = Arc::new(Mutex::new(vec![]));
+    let callback = pipeline.into_callback({
+        let received = received.clone();
+        move |event: Box<dyn UnifiedEvent>| {
+            std::thread::sleep(Duration::from_millis(20));
+            received
+                .lock()
+                .unwrap()
+                .push(event.metadata().deadline_exceeded);
+        }
+    });
+
+    callback(event());
+    // The deadline was crossed in the handler, after the event was delivered
+    assert_eq!(*received.lock().unwrap(), vec![false]);
+    assert_eq!(metrics.deadline_exceeded(), 1);
+    assert_eq!(metrics.slowest_stage().unwrap().0, HANDLER_STAGE);
+}
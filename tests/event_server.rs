@@ -0,0 +1,53 @@
+#![cfg(feature = "server")]
+
+use futures::StreamExt;
+use solana_sdk::signature::Signature;
+use solana_streamer_sdk::protos::events::event_stream_server::EventStream;
+use solana_streamer_sdk::protos::events::SubscribeEventsRequest;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventMetadata, EventType},
+    protocols::pumpfun::PumpFunTradeEvent,
+};
+use solana_streamer_sdk::streaming::server::EventServer;
+use solana_streamer_sdk::streaming::sink::{RedactionAction, RedactionConfig, RedactionRule};
+
+#[tokio::test]
+async fn test_redaction_applies_to_the_wire_message() {
+    let signature = Signature::new_unique().to_string();
+    let server = EventServer::new(16).with_redaction(
+        RedactionConfig::default()
+            .with_rule(RedactionRule {
+                field: "signature".to_string(),
+                action: RedactionAction::Hash,
+                event_types: vec![],
+            })
+            .with_rule(RedactionRule {
+                field: "metadata.id".to_string(),
+                action: RedactionAction::Remove,
+                event_types: vec![],
+            }),
+    );
+    let mut stream = server
+        .subscribe_events(tonic::Request::new(SubscribeEventsRequest::default()))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let mut metadata = EventMetadata {
+        signature: signature.clone(),
+        event_type: EventType::PumpFunBuy,
+        ..Default::default()
+    };
+    metadata.set_id("trade".to_string());
+    server.publish(&PumpFunTradeEvent {
+        metadata,
+        ..Default::default()
+    });
+
+    let message = stream.next().await.unwrap().unwrap();
+    assert!(message.id.is_empty());
+    assert!(!message.signature.is_empty());
+    assert_ne!(message.signature, signature);
+    assert!(!message.payload_json.contains(&signature));
+    assert!(message.metadata.is_none());
+}
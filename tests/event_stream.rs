@@ -0,0 +1,45 @@
+#![cfg(feature = "runtime")]
+
+use futures::StreamExt;
+use solana_streamer_sdk::streaming::event_parser::protocols::pumpfun::PumpFunTradeEvent;
+use solana_streamer_sdk::streaming::ParsedEventStream;
+
+#[tokio::test]
+async fn test_source_events_are_yielded_until_the_source_returns() {
+    let stream = ParsedEventStream::spawn(16, |callback| async move {
+        for sol_amount in 1..=3 {
+            callback(Box::new(PumpFunTradeEvent {
+                sol_amount,
+                ..Default::default()
+            }));
+        }
+        Ok(())
+    });
+
+    let (sender, receiver) = futures::channel::mpsc::channel(16);
+    stream.forward(sender).await.unwrap();
+    let amounts: Vec<u64> = receiver
+        .map(|event| {
+            event
+                .as_any()
+                .downcast_ref::<PumpFunTradeEvent>()
+                .unwrap()
+                .sol_amount
+        })
+        .collect()
+        .await;
+    assert_eq!(amounts, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn test_full_buffer_drops_and_counts_events() {
+    let mut stream = ParsedEventStream::spawn(1, |callback| async move {
+        for _ in 0..3 {
+            callback(Box::new(PumpFunTradeEvent::default()));
+        }
+        Ok(())
+    });
+    assert_eq!(stream.by_ref().count().await, 1);
+    assert_eq!(stream.dropped(), 2);
+    stream.join().await.unwrap();
+}
@@ -0,0 +1,79 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventMetadata, ViewError},
+    protocols::{
+        pumpfun::{PumpFunTradeEvent, PumpFunTradeEventView},
+        pumpswap::{PumpSwapBuyEvent, PumpSwapBuyEventView},
+    },
+};
+
+fn pumpfun_trade_bytes(is_buy: u8) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(&1_000u64.to_le_bytes());
+    data.extend_from_slice(&2_000u64.to_le_bytes());
+    data.push(is_buy);
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(&(-5i64).to_le_bytes());
+    for value in 3..7u64 {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(&100u64.to_le_bytes());
+    data.extend_from_slice(&10u64.to_le_bytes());
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(&30u64.to_le_bytes());
+    data.extend_from_slice(&3u64.to_le_bytes());
+    data
+}
+
+#[test]
+fn test_pumpfun_trade_view_matches_borsh() {
+    let data = pumpfun_trade_bytes(1);
+    let view = PumpFunTradeEventView::new(&data).unwrap();
+    let event = borsh::from_slice::<PumpFunTradeEvent>(&data).unwrap();
+    assert_eq!(view.mint(), &event.mint);
+    assert_eq!(view.timestamp(), -5);
+    assert!(view.is_buy());
+    assert_eq!(view.to_event(EventMetadata::default()), event);
+}
+
+#[test]
+fn test_pumpfun_trade_view_validates_layout() {
+    let data = pumpfun_trade_bytes(2);
+    assert_eq!(
+        PumpFunTradeEventView::new(&data).unwrap_err(),
+        ViewError::InvalidBool {
+            offset: 48,
+            value: 2
+        }
+    );
+    let mut data = pumpfun_trade_bytes(0);
+    data.push(0);
+    assert!(matches!(
+        PumpFunTradeEventView::new(&data),
+        Err(ViewError::Length {
+            expected: 217,
+            actual: 218
+        })
+    ));
+    assert!(PumpFunTradeEventView::new(&data[..100]).is_err());
+}
+
+#[test]
+fn test_pumpswap_buy_view_matches_borsh() {
+    let mut data = Vec::new();
+    for value in 0..14u64 {
+        data.extend_from_slice(&(value * 11).to_le_bytes());
+    }
+    for _ in 0..7 {
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+    }
+    data.extend_from_slice(&5u64.to_le_bytes());
+    data.extend_from_slice(&6u64.to_le_bytes());
+    let view = PumpSwapBuyEventView::new(&data).unwrap();
+    let event = borsh::from_slice::<PumpSwapBuyEvent>(&data).unwrap();
+    assert_eq!(view.user_quote_amount_in(), 143);
+    assert_eq!(view.coin_creator(), &event.coin_creator);
+    assert_eq!(view.to_event(EventMetadata::default()), event);
+}
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::common::AnyResult;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventMetadata, EventType},
+    protocols::pumpfun::PumpFunTradeEvent,
+};
+use solana_streamer_sdk::streaming::sink::{ExactlyOnceWriter, SinkRecord, TransactionalSink};
+use solana_streamer_sdk::streaming::{EventJournal, JournalConfig};
+
+#[derive(Default)]
+struct Table {
+    rows: Mutex<BTreeMap<String, String>>,
+    checkpoint: Mutex<u64>,
+    // 提交成功但返回错误，模拟提交结果未知
+    lose_ack: AtomicBool,
+}
+
+#[derive(Clone, Default)]
+struct MemorySink(Arc<Table>);
+
+impl TransactionalSink for MemorySink {
+    fn name(&self) -> &str {
+        "memory"
+    }
+
+    fn committed_sequence(&self) -> AnyResult<u64> {
+        Ok(*self.0.checkpoint.lock().unwrap())
+    }
+
+    fn commit(&self, records: &[SinkRecord], next_sequence: u64) -> AnyResult<()> {
+        let mut rows = self.0.rows.lock().unwrap();
+        for record in records {
+            rows.entry(record.key.dedup_key())
+                .or_insert_with(|| record.event_type.clone());
+        }
+        *self.0.checkpoint.lock().unwrap() = next_sequence;
+        if self.0.lose_ack.swap(false, Ordering::SeqCst) {
+            return Err(anyhow::anyhow!("connection reset"));
+        }
+        Ok(())
+    }
+}
+
+fn append_trades(journal: &EventJournal, slots: std::ops::Range<u64>) {
+    for slot in slots {
+        let mut metadata = EventMetadata {
+            slot,
+            event_type: EventType::PumpFunBuy,
+            ..Default::default()
+        };
+        metadata.set_id(format!("trade:{}", slot));
+        journal
+            .append(&PumpFunTradeEvent {
+                metadata,
+                ..Default::default()
+            })
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_sink_replay_writes_each_event_once() {
+    let dir = std::env::temp_dir().join(format!("exactly-once-{}", Pubkey::new_unique()));
+    let journal = EventJournal::open(&dir, JournalConfig::new()).unwrap();
+    append_trades(&journal, 0..5);
+
+    let sink = MemorySink::default();
+    let writer = ExactlyOnceWriter::new(sink.clone())
+        .unwrap()
+        .with_batch_size(2);
+    assert_eq!(writer.replay(&journal).unwrap(), 5);
+    assert_eq!(sink.0.rows.lock().unwrap().len(), 5);
+    assert_eq!(writer.committed_sequence(), 5);
+
+    // 提交的确认丢失，重放时整批重写，去重键吸收重复
+    append_trades(&journal, 5..8);
+    sink.0.lose_ack.store(true, Ordering::SeqCst);
+    assert!(writer.replay(&journal).is_err());
+    assert_eq!(writer.next_sequence(), 5);
+    assert_eq!(writer.replay(&journal).unwrap(), 3);
+    assert_eq!(sink.0.rows.lock().unwrap().len(), 8);
+    assert!(sink.0.rows.lock().unwrap().contains_key("7:trade:7"));
+
+    // 重启后从输出端的检查点继续
+    let restarted = ExactlyOnceWriter::new(sink.clone()).unwrap();
+    assert_eq!(restarted.next_sequence(), 8);
+    assert_eq!(restarted.replay(&journal).unwrap(), 0);
+
+    // 跳过的序号需要先重放
+    let event = PumpFunTradeEvent::default();
+    assert!(!restarted.write(3, &event).unwrap());
+    assert!(restarted.write(10, &event).is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_sink_ahead_of_the_journal_is_an_error() {
+    let dir = std::env::temp_dir().join(format!("exactly-once-{}", Pubkey::new_unique()));
+    let journal = Arc::new(EventJournal::open(&dir, JournalConfig::new()).unwrap());
+    append_trades(&journal, 0..3);
+
+    // The sink committed offsets the journal no longer has
+    let sink = MemorySink::default();
+    *sink.0.checkpoint.lock().unwrap() = 5;
+    let writer = ExactlyOnceWriter::new(sink.clone())
+        .unwrap()
+        .with_journal(journal.clone());
+    assert!(writer.check_journal(&journal).is_err());
+    assert!(writer.replay(&journal).is_err());
+
+    // Offsets handed out again are not silently skipped
+    let event = PumpFunTradeEvent::default();
+    let offset = journal.append(&event).unwrap();
+    assert_eq!(offset, 3);
+    assert!(writer.write(offset, &event).is_err());
+    assert!(sink.0.rows.lock().unwrap().is_empty());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
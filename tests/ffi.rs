@@ -0,0 +1,30 @@
+#![cfg(feature = "ffi")]
+
+use std::ffi::{c_char, c_void, CStr};
+
+use solana_streamer_sdk::ffi::{
+    solana_streamer_last_error, solana_streamer_parse_transaction, SOLANA_STREAMER_ERROR,
+};
+
+unsafe extern "C" fn count_event(_: *const c_char, _: *const c_char, user_data: *mut c_void) {
+    *(user_data as *mut usize) += 1;
+}
+
+#[test]
+fn test_invalid_input_reports_last_error() {
+    let mut events = 0usize;
+    let code = unsafe {
+        solana_streamer_parse_transaction(
+            c"not json".as_ptr(),
+            c"sig".as_ptr(),
+            c"PumpFun".as_ptr(),
+            0,
+            count_event,
+            &mut events as *mut usize as *mut c_void,
+        )
+    };
+    assert_eq!(code, SOLANA_STREAMER_ERROR);
+    assert_eq!(events, 0);
+    let error = unsafe { CStr::from_ptr(solana_streamer_last_error()) };
+    assert!(!error.to_str().unwrap().is_empty());
+}
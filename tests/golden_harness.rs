@@ -0,0 +1,42 @@
+#![cfg(feature = "rpc")]
+
+use serde_json::json;
+use solana_streamer_sdk::streaming::fixtures::{json_contains, to_golden_json, FixtureStore};
+
+const SIGNATURE: &str =
+    "3QLV6eN1W1pCWvS8pFYK4x7DCbQNsv16zNmjdpFvqbnzYCnMYEEfVWDsewmLJF1MByFBncaTcHXbqSpQhYHzqGEo";
+
+#[tokio::test]
+async fn test_fixture_events_are_redacted_and_matched() {
+    let dir = std::env::temp_dir().join("solana-streamer-golden-harness");
+    std::fs::create_dir_all(&dir).unwrap();
+    let store = FixtureStore::new(&dir);
+    std::fs::copy(
+        "tests/fixtures/pumpfun_cpi_tx.json",
+        store.fixture_path(SIGNATURE),
+    )
+    .unwrap();
+
+    let events = store.parse(SIGNATURE).await.unwrap();
+    let redacted = store.redact(&events);
+    let redacted = redacted.as_array().unwrap();
+    assert!(redacted
+        .iter()
+        .all(|event| event["metadata"].get("program_received_time_ms").is_none()));
+    assert!(redacted.iter().any(|event| json_contains(
+        event,
+        &json!({"sol_amount": 98019, "token_amount": 1864792795u64, "is_buy": true})
+    )));
+    assert_eq!(
+        to_golden_json(&json!(redacted)),
+        to_golden_json(&store.redact(&store.parse(SIGNATURE).await.unwrap()))
+    );
+}
+
+#[test]
+fn test_partial_json_matching() {
+    let actual = json!([{"a": 1, "b": {"c": 2, "d": 3}}]);
+    assert!(json_contains(&actual, &json!([{"b": {"c": 2}}])));
+    assert!(!json_contains(&actual, &json!([{"b": {"c": 3}}])));
+    assert!(!json_contains(&actual, &json!([{}, {}])));
+}
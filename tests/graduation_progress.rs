@@ -0,0 +1,80 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::protocols::bonk::{
+    BonkPoolCreateEvent, BonkTradeEvent,
+};
+use solana_streamer_sdk::streaming::pipeline::EventMiddleware;
+use solana_streamer_sdk::streaming::trackers::{BondingCurveTracker, GraduationProgressEvent};
+
+const TOTAL_BASE_SELL: u64 = 800_000_000_000_000;
+
+fn trade(mint: Pubkey, sold_percent: u64) -> BonkTradeEvent {
+    BonkTradeEvent {
+        base_token_mint: mint,
+        total_base_sell: TOTAL_BASE_SELL,
+        real_base_after: TOTAL_BASE_SELL / 100 * sold_percent,
+        ..Default::default()
+    }
+}
+
+fn thresholds_crossed(tracker: &BondingCurveTracker, event: BonkTradeEvent) -> Vec<f64> {
+    tracker
+        .handle(Box::new(event))
+        .iter()
+        .filter_map(|event| event.as_any().downcast_ref::<GraduationProgressEvent>())
+        .map(|progress| progress.threshold)
+        .collect()
+}
+
+#[test]
+fn test_bonk_trades_emit_crossed_thresholds() {
+    let tracker = BondingCurveTracker::new();
+    let mint = Pubkey::new_unique();
+    tracker.handle(Box::new(BonkPoolCreateEvent {
+        base_mint: mint,
+        ..Default::default()
+    }));
+
+    assert!(thresholds_crossed(&tracker, trade(mint, 40)).is_empty());
+    assert_eq!(thresholds_crossed(&tracker, trade(mint, 60)), [50.0]);
+    // Jumping past several thresholds reports each of them, lowest first
+    assert_eq!(thresholds_crossed(&tracker, trade(mint, 95)), [75.0, 90.0]);
+    assert!(thresholds_crossed(&tracker, trade(mint, 96)).is_empty());
+    assert_eq!(thresholds_crossed(&tracker, trade(mint, 100)), [100.0]);
+}
+
+#[test]
+fn test_unseen_mints_only_record_progress() {
+    let tracker = BondingCurveTracker::new().with_graduation_thresholds(vec![80.0]);
+    let mint = Pubkey::new_unique();
+    assert!(thresholds_crossed(&tracker, trade(mint, 85)).is_empty());
+    assert!(thresholds_crossed(&tracker, trade(mint, 70)).is_empty());
+    assert_eq!(thresholds_crossed(&tracker, trade(mint, 81)), [80.0]);
+}
+
+#[test]
+fn test_thresholds_are_reported_in_ascending_order() {
+    let tracker =
+        BondingCurveTracker::new().with_graduation_thresholds(vec![90.0, 25.0, 60.0, 25.0]);
+    let mint = Pubkey::new_unique();
+    tracker.handle(Box::new(BonkPoolCreateEvent {
+        base_mint: mint,
+        ..Default::default()
+    }));
+    assert_eq!(
+        thresholds_crossed(&tracker, trade(mint, 100)),
+        [25.0, 60.0, 90.0]
+    );
+
+    let mint = Pubkey::new_unique();
+    tracker.handle(Box::new(BonkPoolCreateEvent {
+        base_mint: mint,
+        ..Default::default()
+    }));
+    let events = tracker.apply_bonk_trade(&trade(mint, 70));
+    let thresholds: Vec<f64> = events.iter().map(|event| event.threshold).collect();
+    assert_eq!(thresholds, [25.0, 60.0]);
+    assert!(events
+        .iter()
+        .all(|event| event.previous_progress == 0.0 && event.progress == 70.0));
+    assert_ne!(events[0].metadata.id, events[1].metadata.id);
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "grpc")]
+
+use std::time::Duration;
+
+use solana_streamer_sdk::streaming::{GrpcCompression, GrpcConfig, YellowstoneGrpc};
+
+#[test]
+fn test_auth_and_custom_headers_are_added() {
+    let config = GrpcConfig::new()
+        .with_x_token("token")
+        .with_bearer_token("secret")
+        .with_header("x-client", "streamer")
+        .with_compression(GrpcCompression::Zstd)
+        .with_tcp_keepalive(Duration::from_secs(30));
+
+    let interceptor = config.interceptor().unwrap();
+    assert_eq!(
+        interceptor.header_names(),
+        vec!["x-token", "authorization", "x-client"]
+    );
+    assert!(config.endpoint("https://grpc.example:443").is_ok());
+
+    let invalid = GrpcConfig::new().with_header("bad header", "value");
+    assert!(invalid.interceptor().is_err());
+}
+
+#[test]
+fn test_client_keeps_x_token_of_constructor() {
+    let grpc = YellowstoneGrpc::new(
+        "https://grpc.example:443".to_string(),
+        Some("token".to_string()),
+    )
+    .unwrap()
+    .with_config(GrpcConfig::new().with_connect_timeout(Duration::from_secs(3)));
+
+    assert_eq!(grpc.config().x_token.as_deref(), Some("token"));
+    assert_eq!(grpc.config().connect_timeout, Duration::from_secs(3));
+    assert!(grpc.config().tls);
+}
@@ -0,0 +1,34 @@
+use solana_streamer_sdk::streaming::event_parser::protocols::{
+    pumpfun::PumpFunTradeEvent, pumpswap::PumpSwapBuyEvent,
+};
+use solana_streamer_sdk::streaming::HandlerRegistry;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn test_handlers_run_by_type_and_priority() {
+    let calls: Arc<Mutex<Vec<&'static str>>> = Arc::default();
+    let log = |name: &'static str| {
+        let calls = calls.clone();
+        move || calls.lock().unwrap().push(name)
+    };
+    let (trade, trade_first, any, unhandled) = (
+        log("trade"),
+        log("trade_first"),
+        log("any"),
+        log("unhandled"),
+    );
+    let registry = HandlerRegistry::new()
+        .on(move |_: &PumpFunTradeEvent| trade())
+        .on_any_with_priority(5, move |_| any())
+        .on_with_priority(10, move |_: &PumpFunTradeEvent| trade_first())
+        .on_unhandled(move |_| unhandled());
+    assert_eq!(registry.len(), 4);
+    assert!(registry.handles::<PumpFunTradeEvent>());
+
+    assert_eq!(registry.dispatch(&PumpFunTradeEvent::default()), 3);
+    assert_eq!(*calls.lock().unwrap(), vec!["trade_first", "any", "trade"]);
+
+    calls.lock().unwrap().clear();
+    assert_eq!(registry.dispatch(&PumpSwapBuyEvent::default()), 2);
+    assert_eq!(*calls.lock().unwrap(), vec!["any", "unhandled"]);
+}
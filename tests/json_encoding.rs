@@ -0,0 +1,80 @@
+use anyhow::Result;
+use solana_streamer_sdk::streaming::event_parser::{EventParserFactory, Protocol, UnifiedEvent};
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction,
+    EncodedTransactionWithStatusMeta, UiAddressTableLookup, UiCompiledInstruction, UiMessage,
+    UiRawMessage, UiTransaction,
+};
+use std::fs;
+
+/// The fixture with its transaction re-encoded as `Json`
+fn json_encoded(tx: &EncodedTransactionWithStatusMeta) -> EncodedTransactionWithStatusMeta {
+    let versioned = tx.transaction.decode().expect("binary fixture");
+    let message = &versioned.message;
+    let raw = UiRawMessage {
+        header: *message.header(),
+        account_keys: message
+            .static_account_keys()
+            .iter()
+            .map(|key| key.to_string())
+            .collect(),
+        recent_blockhash: message.recent_blockhash().to_string(),
+        instructions: message
+            .instructions()
+            .iter()
+            .map(|instruction| UiCompiledInstruction {
+                program_id_index: instruction.program_id_index,
+                accounts: instruction.accounts.clone(),
+                data: bs58::encode(&instruction.data).into_string(),
+                stack_height: None,
+            })
+            .collect(),
+        address_table_lookups: message.address_table_lookups().map(|lookups| {
+            lookups
+                .iter()
+                .map(|lookup| UiAddressTableLookup {
+                    account_key: lookup.account_key.to_string(),
+                    writable_indexes: lookup.writable_indexes.clone(),
+                    readonly_indexes: lookup.readonly_indexes.clone(),
+                })
+                .collect()
+        }),
+    };
+    EncodedTransactionWithStatusMeta {
+        transaction: EncodedTransaction::Json(UiTransaction {
+            signatures: versioned.signatures.iter().map(|s| s.to_string()).collect(),
+            message: UiMessage::Raw(raw),
+        }),
+        meta: tx.meta.clone(),
+        version: tx.version,
+    }
+}
+
+async fn parse(
+    tx: EncodedTransactionWithStatusMeta,
+    protocol: Protocol,
+) -> Result<Vec<Box<dyn UnifiedEvent>>> {
+    EventParserFactory::create_parser(protocol)
+        .parse_transaction(tx, "sig", Some(1), None, 0, None)
+        .await
+}
+
+#[tokio::test]
+async fn test_json_encoded_transactions_parse_like_binary() -> Result<()> {
+    for (path, protocol) in [
+        ("tests/fixtures/pumpfun_cpi_tx.json", Protocol::PumpFun),
+        ("tests/fixtures/pumpswap_cpi_tx.json", Protocol::PumpSwap),
+    ] {
+        let tx: EncodedConfirmedTransactionWithStatusMeta =
+            serde_json::from_str(&fs::read_to_string(path)?)?;
+        let binary = parse(tx.transaction.clone(), protocol.clone()).await?;
+        let json = parse(json_encoded(&tx.transaction), protocol).await?;
+        assert!(!binary.is_empty(), "{path}: no events");
+        assert_eq!(json.len(), binary.len(), "{path}");
+        for (a, b) in json.iter().zip(binary.iter()) {
+            assert_eq!(a.id(), b.id());
+            assert_eq!(a.content_hash(), b.content_hash());
+        }
+    }
+    Ok(())
+}
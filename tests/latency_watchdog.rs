@@ -0,0 +1,85 @@
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{ClockHandle, EventMetadata, EventType, MockClock},
+    protocols::pumpfun::PumpFunTradeEvent,
+    UnifiedEvent,
+};
+use solana_streamer_sdk::streaming::pipeline::{
+    EventMiddleware, LatencyBudgetExceededEvent, LatencyCause, LatencyWatchdog,
+};
+
+const BLOCK_TIME_MS: i64 = 1_700_000_000_000;
+
+/// 区块时间后 `received` 毫秒到达
+fn event(slot: u64, received: i64) -> Box<dyn UnifiedEvent> {
+    Box::new(PumpFunTradeEvent {
+        metadata: EventMetadata {
+            slot,
+            block_time_ms: BLOCK_TIME_MS,
+            grpc_received_time_ms: BLOCK_TIME_MS + received,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+fn alerts(events: &[Box<dyn UnifiedEvent>]) -> Vec<&LatencyBudgetExceededEvent> {
+    events
+        .iter()
+        .filter_map(|event| event.as_any().downcast_ref::<LatencyBudgetExceededEvent>())
+        .collect()
+}
+
+#[test]
+fn test_watchdog_alerts_once_per_excursion() {
+    let clock = MockClock::new(BLOCK_TIME_MS + 500);
+    let watchdog = LatencyWatchdog::new()
+        .with_budget_ms(1_000)
+        .with_window(10)
+        .with_clock(ClockHandle::new(clock.clone()));
+
+    // 全部在预算内
+    for slot in 0..10 {
+        assert!(alerts(&watchdog.handle(event(slot, 400))).is_empty());
+    }
+    assert_eq!(watchdog.stats().p99_ms, 500);
+    assert!(!watchdog.is_exceeded());
+
+    // 数据源延迟：消息晚到，本地处理很快
+    clock.set(BLOCK_TIME_MS + 3_000);
+    let mut raised = Vec::new();
+    for slot in 10..20 {
+        raised.extend(watchdog.handle(event(slot, 2_990)));
+    }
+    let raised = alerts(&raised);
+    assert_eq!(raised.len(), 1);
+    assert_eq!(
+        raised[0].metadata.event_type,
+        EventType::LatencyBudgetExceeded
+    );
+    assert_eq!(raised[0].metadata.slot, 19);
+    assert_eq!(raised[0].stats.p99_ms, 3_000);
+    assert_eq!(raised[0].cause, LatencyCause::Provider);
+
+    // 持续超标不重复告警
+    for slot in 20..30 {
+        assert!(alerts(&watchdog.handle(event(slot, 2_990))).is_empty());
+    }
+    assert_eq!(watchdog.alerts(), 1);
+
+    // 恢复后再次超标，这次是本地解析延迟
+    clock.set(BLOCK_TIME_MS + 500);
+    for slot in 30..40 {
+        watchdog.handle(event(slot, 100));
+    }
+    assert!(!watchdog.is_exceeded());
+    clock.set(BLOCK_TIME_MS + 2_000);
+    let mut raised = Vec::new();
+    for slot in 40..50 {
+        raised.extend(watchdog.handle(event(slot, 100)));
+    }
+    let raised = alerts(&raised);
+    assert_eq!(raised.len(), 1);
+    assert_eq!(raised[0].stats.local_p99_ms, 1_900);
+    assert_eq!(raised[0].cause, LatencyCause::Local);
+    assert_eq!(watchdog.alerts(), 2);
+}
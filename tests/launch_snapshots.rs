@@ -0,0 +1,82 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::ProtocolType,
+    protocols::pumpfun::{PumpFunCreateTokenEvent, PumpFunTradeEvent},
+    UnifiedEvent,
+};
+use solana_streamer_sdk::streaming::pipeline::EventMiddleware;
+use solana_streamer_sdk::streaming::trackers::{LaunchSnapshotEvent, LaunchSnapshotTracker};
+use std::time::Duration;
+
+fn create(mint: Pubkey, slot: u64, time_ms: i64) -> PumpFunCreateTokenEvent {
+    let mut event = PumpFunCreateTokenEvent {
+        mint,
+        creator: Pubkey::new_unique(),
+        ..Default::default()
+    };
+    event.metadata.slot = slot;
+    event.metadata.block_time_ms = time_ms;
+    event.metadata.protocol = ProtocolType::PumpFun;
+    event
+}
+
+fn trade(mint: Pubkey, is_buy: bool, slot: u64, time_ms: i64) -> PumpFunTradeEvent {
+    let mut event = PumpFunTradeEvent {
+        user: Pubkey::new_unique(),
+        mint,
+        is_buy,
+        sol_amount: 1_000_000,
+        token_amount: 5_000_000,
+        ..Default::default()
+    };
+    event.metadata.slot = slot;
+    event.metadata.block_time_ms = time_ms;
+    event.metadata.protocol = ProtocolType::PumpFun;
+    event
+}
+
+fn snapshots(events: Vec<Box<dyn UnifiedEvent>>) -> Vec<LaunchSnapshotEvent> {
+    events
+        .iter()
+        .filter_map(|event| event.as_any().downcast_ref::<LaunchSnapshotEvent>())
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn test_snapshot_after_first_trades() {
+    let tracker = LaunchSnapshotTracker::new().with_trades_per_launch(2);
+    let mint = Pubkey::new_unique();
+    assert!(snapshots(tracker.handle(Box::new(create(mint, 100, 10_000)))).is_empty());
+    assert!(snapshots(tracker.handle(Box::new(trade(mint, true, 100, 10_000)))).is_empty());
+    let snapshot = snapshots(tracker.handle(Box::new(trade(mint, false, 103, 11_200))));
+    assert_eq!(snapshot.len(), 1);
+    let snapshot = &snapshot[0];
+    assert!(snapshot.complete);
+    assert_eq!(snapshot.mint, mint);
+    assert_eq!(snapshot.created_slot, 100);
+    let deltas: Vec<(u64, i64, bool)> = snapshot
+        .trades
+        .iter()
+        .map(|t| (t.slots_since_creation, t.ms_since_creation, t.is_buy))
+        .collect();
+    assert_eq!(deltas, [(0, 0, true), (3, 1_200, false)]);
+    assert_eq!(snapshot.trades[0].base_amount, 5_000_000);
+    assert!(tracker.is_empty());
+    // Later trades are not captured again
+    assert!(snapshots(tracker.handle(Box::new(trade(mint, true, 104, 11_600)))).is_empty());
+}
+
+#[test]
+fn test_incomplete_snapshot_after_window() {
+    let tracker = LaunchSnapshotTracker::new()
+        .with_trades_per_launch(5)
+        .with_window(Duration::from_secs(60));
+    let mint = Pubkey::new_unique();
+    tracker.handle(Box::new(create(mint, 100, 10_000)));
+    tracker.handle(Box::new(trade(mint, true, 101, 10_400)));
+    let snapshot = snapshots(tracker.handle(Box::new(trade(mint, true, 400, 80_000))));
+    assert_eq!(snapshot.len(), 1);
+    assert!(!snapshot[0].complete);
+    assert_eq!(snapshot[0].trades.len(), 1);
+}
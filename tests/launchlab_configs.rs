@@ -0,0 +1,130 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::protocols::bonk::{
+    account_discriminators, parser::BONK_PROGRAM_ID, BonkTradeEvent, LaunchLabAccount, PoolStatus,
+};
+use solana_streamer_sdk::streaming::pipeline::EventMiddleware;
+use solana_streamer_sdk::streaming::trackers::LaunchLabConfigTracker;
+
+fn account(discriminator: [u8; 8], body: Vec<u8>) -> Vec<u8> {
+    // 末尾的保留填充
+    [&discriminator[..], &body, &[0u8; 64]].concat()
+}
+
+fn global_config(trade_fee_rate: u64, quote_mint: Pubkey) -> Vec<u8> {
+    let body = borsh::to_vec(&(
+        (1u64, 0u8, 0u16, 15_000_000u64, trade_fee_rate, 10_000u64),
+        (
+            10_000_000u64,
+            300_000u64,
+            200_000u64,
+            200_000u64,
+            30_000_000_000u64,
+        ),
+        [quote_mint, Pubkey::new_unique(), Pubkey::new_unique()],
+        [Pubkey::new_unique(), Pubkey::new_unique()],
+    ))
+    .unwrap();
+    account(account_discriminators::GLOBAL_CONFIG, body)
+}
+
+fn platform_config(fee_rate: u64, name: &str) -> Vec<u8> {
+    let mut name_bytes = [0u8; 64];
+    name_bytes[..name.len()].copy_from_slice(name.as_bytes());
+    let body = [
+        borsh::to_vec(&(1u64, Pubkey::new_unique(), Pubkey::new_unique())).unwrap(),
+        borsh::to_vec(&(0u64, 0u64, 0u64, fee_rate)).unwrap(),
+        name_bytes.to_vec(),
+    ]
+    .concat();
+    account(account_discriminators::PLATFORM_CONFIG, body)
+}
+
+fn pool_state(global: Pubkey, platform: Pubkey, total_quote_fund_raising: u64) -> Vec<u8> {
+    let body = borsh::to_vec(&(
+        (1u64, 255u8, 0u8, 6u8, 9u8, 1u8),
+        (
+            1_000_000_000_000_000u64,
+            793_100_000_000_000u64,
+            0u64,
+            0u64,
+            0u64,
+            0u64,
+        ),
+        (total_quote_fund_raising, 0u64, 0u64, 0u64),
+        [0u64; 5],
+        [global, platform, Pubkey::new_unique(), Pubkey::new_unique()],
+        [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ],
+    ))
+    .unwrap();
+    account(account_discriminators::POOL_STATE, body)
+}
+
+#[test]
+fn test_trades_correlate_with_config_accounts() {
+    let tracker = LaunchLabConfigTracker::new();
+    let (global, platform, pool) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let wsol = Pubkey::new_unique();
+
+    // 其他Anchor程序的同名账户不解码
+    let data = global_config(2_500, wsol);
+    assert!(tracker
+        .apply_account(&global, &Pubkey::new_unique(), &data, 1)
+        .is_none());
+    assert!(matches!(
+        tracker.apply_account(&global, &BONK_PROGRAM_ID, &data, 1),
+        Some(LaunchLabAccount::GlobalConfig(config)) if config.quote_mint == wsol
+    ));
+
+    // 交易事件带有配置账户，池子账户未加载前缺少平台配置
+    let trade = BonkTradeEvent {
+        pool_state: pool,
+        global_config: global,
+        platform_config: platform,
+        real_quote_after: 42_500_000_000,
+        ..Default::default()
+    };
+    tracker.handle(Box::new(trade.clone()));
+    assert_eq!(tracker.missing_configs(), vec![platform]);
+    let params = tracker.curve_params(&trade).unwrap();
+    assert_eq!(params.trade_fee_rate(), Some(2_500));
+    assert!(params.total_fee_ratio().is_none());
+    assert!(params.migration_progress(&trade).is_none());
+
+    tracker.apply_account(
+        &platform,
+        &BONK_PROGRAM_ID,
+        &platform_config(10_000, "bonk"),
+        1,
+    );
+    match tracker.apply_account(
+        &pool,
+        &BONK_PROGRAM_ID,
+        &pool_state(global, platform, 85_000_000_000),
+        2,
+    ) {
+        Some(LaunchLabAccount::PoolState(state)) => {
+            assert_eq!(state.status, PoolStatus::Fund);
+            assert_eq!(state.quote_decimals, 9);
+        }
+        other => panic!("unexpected account {:?}", other),
+    }
+    assert!(tracker.missing_configs().is_empty());
+
+    let params = tracker.curve_params(&trade).unwrap();
+    assert_eq!(
+        params.platform.as_ref().map(|p| p.name.as_str()),
+        Some("bonk")
+    );
+    assert_eq!(params.total_base_sell, 793_100_000_000_000);
+    assert_eq!(params.migrate_type, 1);
+    assert!((params.total_fee_ratio().unwrap() - 0.0125).abs() < 1e-12);
+    assert_eq!(params.migration_progress(&trade), Some(50.0));
+}
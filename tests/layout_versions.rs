@@ -0,0 +1,89 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventMetadata, EventType},
+    core::traits::{GenericEventParseConfig, LayoutVersion},
+    protocols::bonk::BonkTradeEvent,
+    UnifiedEvent,
+};
+
+// 当前布局：amount_in + amount_out
+fn parse_current(data: &[u8], metadata: EventMetadata) -> Option<Box<dyn UnifiedEvent>> {
+    let (amount_in, amount_out) = borsh::from_slice::<(u64, u64)>(data).ok()?;
+    Some(Box::new(BonkTradeEvent {
+        metadata,
+        amount_in,
+        amount_out,
+        ..Default::default()
+    }))
+}
+
+// 旧布局：只有amount_in
+fn parse_v1(data: &[u8], metadata: EventMetadata) -> Option<Box<dyn UnifiedEvent>> {
+    let amount_in = borsh::from_slice::<u64>(data).ok()?;
+    Some(Box::new(BonkTradeEvent {
+        metadata,
+        amount_in,
+        ..Default::default()
+    }))
+}
+
+fn parse_none(_: &[u8], _: &[Pubkey], _: EventMetadata) -> Option<Box<dyn UnifiedEvent>> {
+    None
+}
+
+fn metadata(slot: u64) -> EventMetadata {
+    EventMetadata {
+        slot,
+        event_type: EventType::BonkBuyExactIn,
+        ..Default::default()
+    }
+}
+
+fn amounts(event: Option<Box<dyn UnifiedEvent>>) -> Option<(u64, u64)> {
+    let event = event?;
+    let trade = event.as_any().downcast_ref::<BonkTradeEvent>()?;
+    Some((trade.amount_in, trade.amount_out))
+}
+
+#[test]
+fn test_layouts_fall_back_within_their_slot_range() {
+    let config = GenericEventParseConfig {
+        inner_instruction_discriminator: "",
+        instruction_discriminator: &[],
+        event_type: EventType::BonkBuyExactIn,
+        inner_instruction_parser: parse_current,
+        instruction_parser: parse_none,
+        layouts: Vec::new(),
+    }
+    .with_layout(
+        LayoutVersion::new("v1")
+            .with_slots(100, Some(1_000))
+            .with_inner_instruction_parser(parse_v1),
+    );
+    let current = borsh::to_vec(&(5u64, 7u64)).unwrap();
+    let v1 = borsh::to_vec(&5u64).unwrap();
+
+    assert_eq!(
+        amounts(config.parse_inner_instruction(&current, metadata(500))),
+        Some((5, 7))
+    );
+    assert_eq!(
+        amounts(config.parse_inner_instruction(&v1, metadata(500))),
+        Some((5, 0))
+    );
+    // 超出slot范围不使用旧布局，slot未知时都尝试
+    assert_eq!(
+        amounts(config.parse_inner_instruction(&v1, metadata(1_000))),
+        None
+    );
+    assert_eq!(
+        amounts(config.parse_inner_instruction(&v1, metadata(50))),
+        None
+    );
+    assert_eq!(
+        amounts(config.parse_inner_instruction(&v1, metadata(0))),
+        Some((5, 0))
+    );
+    // 没有指令解析器的布局被跳过
+    assert!(config.parse_instruction(&v1, &[], metadata(500)).is_none());
+}
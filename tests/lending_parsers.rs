@@ -0,0 +1,78 @@
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventType, InstructionIndex},
+    protocols::{
+        kamino::{self, parser::KAMINO_LEND_PROGRAM_ID, KaminoLiquidationEvent},
+        marginfi::{self, parser::MARGINFI_PROGRAM_ID, MarginfiLendingEvent},
+    },
+    EventParserFactory, Protocol, UnifiedEvent,
+};
+
+fn parse(
+    protocol: Protocol,
+    program_id: Pubkey,
+    account_count: u8,
+    data: Vec<u8>,
+) -> (Vec<Pubkey>, Vec<Box<dyn UnifiedEvent>>) {
+    let keys: Vec<Pubkey> = (0..account_count).map(|_| Pubkey::new_unique()).collect();
+    let mut accounts = keys.clone();
+    accounts.push(program_id);
+    let instruction = CompiledInstruction {
+        program_id_index: account_count,
+        accounts: (0..account_count).collect(),
+        data,
+    };
+    let events = EventParserFactory::create_parser(protocol).parse_events_from_instruction(
+        &instruction,
+        &accounts,
+        "sig",
+        1,
+        None,
+        0,
+        InstructionIndex::outer(0),
+    );
+    (keys, events)
+}
+
+#[test]
+fn test_marginfi_repay_all() {
+    let mut data = marginfi::discriminators::REPAY_IX.to_vec();
+    data.extend_from_slice(&1_500_000u64.to_le_bytes());
+    data.extend_from_slice(&[1, 1]);
+    let (keys, events) = parse(Protocol::Marginfi, MARGINFI_PROGRAM_ID, 7, data);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type(), EventType::MarginfiRepay);
+
+    let event = events[0]
+        .as_any()
+        .downcast_ref::<MarginfiLendingEvent>()
+        .expect("lending event");
+    assert_eq!(event.marginfi_account, keys[1]);
+    assert_eq!(event.authority, keys[2]);
+    assert_eq!(event.bank, keys[3]);
+    assert_eq!(event.amount, 1_500_000);
+    assert!(event.all);
+}
+
+#[test]
+fn test_kamino_liquidation_v2() {
+    let mut data = kamino::discriminators::LIQUIDATE_V2_IX.to_vec();
+    for value in [250_000_000u64, 240_000_000, 0] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    // v2 appends farm accounts
+    let (keys, events) = parse(Protocol::Kamino, KAMINO_LEND_PROGRAM_ID, 24, data);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type(), EventType::KaminoLiquidate);
+
+    let event = events[0]
+        .as_any()
+        .downcast_ref::<KaminoLiquidationEvent>()
+        .expect("liquidation event");
+    assert_eq!(event.liquidator, keys[0]);
+    assert_eq!(event.obligation, keys[1]);
+    assert_eq!(event.repay_mint, keys[5]);
+    assert_eq!(event.withdraw_mint, keys[8]);
+    assert_eq!(event.liquidity_amount, 250_000_000);
+    assert_eq!(event.min_acceptable_received_liquidity_amount, 240_000_000);
+}
@@ -0,0 +1,69 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::common::AnyResult;
+use solana_streamer_sdk::streaming::trackers::ShardedMap;
+use solana_streamer_sdk::streaming::{AltResolver, LookupTableLoader, MemoryBudget};
+use std::sync::Arc;
+
+struct NoLoader;
+
+#[async_trait::async_trait]
+impl LookupTableLoader for NoLoader {
+    async fn load(&self, table: &Pubkey) -> AnyResult<Vec<Pubkey>> {
+        Err(anyhow::anyhow!("{} not cached", table))
+    }
+}
+
+#[test]
+fn test_max_entries_evicts_least_recently_used() {
+    let map: ShardedMap<u64, u64> = ShardedMap::with_shards(16).with_max_entries(16 * 8);
+    let hot = 0u64;
+    map.insert(hot, 0);
+    for key in 1..10_000u64 {
+        map.insert(key, key);
+        // 热点key一直被访问，不会被淘汰
+        assert!(map.read(&hot, |_| ()).is_some());
+    }
+    assert!(map.len() <= 16 * 8);
+    assert!(map.contains_key(&hot));
+    assert!(map.contains_key(&9_999));
+}
+
+#[test]
+fn test_budget_accounts_and_evicts() {
+    let budget = MemoryBudget::unlimited();
+    let map: ShardedMap<Pubkey, u64> =
+        ShardedMap::with_shards(16).with_memory_budget(&budget, "test.map");
+    let keys: Vec<Pubkey> = (0..1_000).map(|_| Pubkey::new_unique()).collect();
+    for key in &keys {
+        map.upsert(*key, || 0, |value| *value += 1);
+    }
+    let entry = ShardedMap::<Pubkey, u64>::entry_size();
+    assert_eq!(budget.used(), 1_000 * entry);
+    map.remove(&keys[0]);
+    assert_eq!(budget.stats()[0].entries, 999);
+
+    budget.set_limit(500 * entry);
+    map.insert(Pubkey::new_unique(), 0);
+    map.insert(Pubkey::new_unique(), 0);
+    assert!(budget.evictions() > 0);
+    assert_eq!(budget.stats()[0].entries, map.len());
+
+    drop(map);
+    assert_eq!(budget.used(), 0);
+}
+
+#[test]
+fn test_alt_cache_shrinks_to_budget() {
+    let budget = MemoryBudget::new(4 * 1024);
+    let resolver = AltResolver::new(Arc::new(NoLoader)).with_memory_budget(&budget);
+    for _ in 0..10 {
+        let addresses = (0..32).map(|_| Pubkey::new_unique()).collect();
+        resolver.insert_table(Pubkey::new_unique(), addresses);
+    }
+    assert!(budget.used() <= budget.limit());
+    assert!(resolver.cached_tables() < 10);
+    let stats = &budget.stats()[0];
+    assert_eq!(stats.name, "alt_resolver.tables");
+    assert_eq!(stats.entries, resolver.cached_tables());
+    assert_eq!(stats.evictions as usize, 10 - resolver.cached_tables());
+}
@@ -0,0 +1,207 @@
+use solana_sdk::{
+    hash::Hash,
+    instruction::CompiledInstruction,
+    message::{Message, MessageHeader, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventType, InstructionIndex},
+    protocols::metaplex::{
+        discriminators, parser::TOKEN_METADATA_PROGRAM_ID, MetaplexUpdateMetadataEvent,
+        MetaplexVerification, MetaplexVerifyEvent,
+    },
+    EventParserFactory, Protocol,
+};
+use solana_transaction_status::{
+    EncodedTransactionWithStatusMeta, TransactionStatusMeta, UiTransactionEncoding,
+    VersionedTransactionWithStatusMeta,
+};
+
+fn push_string(data: &mut Vec<u8>, value: &str) {
+    data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    data.extend_from_slice(value.as_bytes());
+}
+
+#[test]
+fn test_metaplex_uri_change_and_authority_transfer() {
+    let (metadata_account, authority, new_authority) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let mut data = discriminators::UPDATE_METADATA_ACCOUNT_V2_IX.to_vec();
+    data.push(1);
+    push_string(&mut data, "Token");
+    push_string(&mut data, "TKN");
+    push_string(&mut data, "https://example.com/rugged.json");
+    data.extend_from_slice(&0u16.to_le_bytes());
+    // no creators, collection or uses
+    data.extend_from_slice(&[0, 0, 0]);
+    data.push(1);
+    data.extend_from_slice(new_authority.as_ref());
+    // primary_sale_happened unchanged, is_mutable = false
+    data.extend_from_slice(&[0, 1, 0]);
+
+    let accounts = vec![metadata_account, authority, TOKEN_METADATA_PROGRAM_ID];
+    let instruction = CompiledInstruction {
+        program_id_index: 2,
+        accounts: vec![0, 1],
+        data,
+    };
+    let events = EventParserFactory::create_parser(Protocol::Metaplex)
+        .parse_events_from_instruction(
+            &instruction,
+            &accounts,
+            "sig",
+            1,
+            None,
+            0,
+            InstructionIndex::outer(0),
+        );
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type(), EventType::MetaplexUpdateMetadata);
+
+    let event = events[0]
+        .as_any()
+        .downcast_ref::<MetaplexUpdateMetadataEvent>()
+        .expect("update event");
+    assert_eq!(event.metadata_account, metadata_account);
+    assert_eq!(event.update_authority, authority);
+    assert_eq!(event.new_update_authority, Some(new_authority));
+    assert_eq!(
+        event.uri.as_deref(),
+        Some("https://example.com/rugged.json")
+    );
+    assert_eq!(event.primary_sale_happened, None);
+    assert_eq!(event.is_mutable, Some(false));
+}
+
+/// Accounts of a collection verification, in the order the instructions take them
+struct CollectionAccounts {
+    metadata: Pubkey,
+    collection_authority: Pubkey,
+    payer: Pubkey,
+    update_authority: Pubkey,
+    collection_mint: Pubkey,
+    collection_metadata: Pubkey,
+    collection_edition: Pubkey,
+}
+
+impl CollectionAccounts {
+    fn new() -> Self {
+        Self {
+            metadata: Pubkey::new_unique(),
+            collection_authority: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            update_authority: Pubkey::new_unique(),
+            collection_mint: Pubkey::new_unique(),
+            collection_metadata: Pubkey::new_unique(),
+            collection_edition: Pubkey::new_unique(),
+        }
+    }
+
+    /// Transaction with one Token Metadata instruction of variant `tag`, encoded as
+    /// an RPC node returns it
+    ///
+    /// Account lists follow mpl-token-metadata: VerifySizedCollectionItem (30) takes
+    /// metadata, collection authority, payer, collection mint, collection metadata
+    /// and collection master edition; SetAndVerifyCollection (25) takes the item's
+    /// update authority before the collection mint.
+    fn transaction(&self, tag: u8) -> EncodedTransactionWithStatusMeta {
+        let mut accounts = vec![self.metadata, self.collection_authority, self.payer];
+        if tag == 25 || tag == 32 {
+            accounts.push(self.update_authority);
+        }
+        accounts.extend([
+            self.collection_mint,
+            self.collection_metadata,
+            self.collection_edition,
+        ]);
+        // 签名者（手续费支付者、集合权限）在前，程序账户在最后
+        let mut account_keys = vec![self.payer, self.collection_authority];
+        for key in &accounts {
+            if !account_keys.contains(key) {
+                account_keys.push(*key);
+            }
+        }
+        account_keys.push(TOKEN_METADATA_PROGRAM_ID);
+        let index = |key: &Pubkey| account_keys.iter().position(|k| k == key).unwrap() as u8;
+        let instruction = CompiledInstruction {
+            program_id_index: index(&TOKEN_METADATA_PROGRAM_ID),
+            accounts: accounts.iter().map(index).collect(),
+            data: vec![tag],
+        };
+        let num_accounts = account_keys.len();
+        let message = Message {
+            header: MessageHeader {
+                num_required_signatures: 2,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys,
+            recent_blockhash: Hash::new_unique(),
+            instructions: vec![instruction],
+        };
+        let transaction = VersionedTransaction {
+            signatures: vec![Signature::new_unique(), Signature::new_unique()],
+            message: VersionedMessage::Legacy(message),
+        };
+        let meta = TransactionStatusMeta {
+            status: Ok(()),
+            fee: 10_000,
+            pre_balances: vec![0; num_accounts],
+            post_balances: vec![0; num_accounts],
+            inner_instructions: Some(vec![]),
+            log_messages: Some(vec![
+                format!("Program {} invoke [1]", TOKEN_METADATA_PROGRAM_ID),
+                format!("Program {} success", TOKEN_METADATA_PROGRAM_ID),
+            ]),
+            ..Default::default()
+        };
+        VersionedTransactionWithStatusMeta { transaction, meta }
+            .encode(UiTransactionEncoding::Base64, Some(0), false)
+            .unwrap()
+    }
+}
+
+async fn verify_events(tx: EncodedTransactionWithStatusMeta) -> Vec<MetaplexVerifyEvent> {
+    EventParserFactory::create_parser(Protocol::Metaplex)
+        .parse_transaction(tx, "sig", Some(1), None, 0, None)
+        .await
+        .unwrap()
+        .iter()
+        .filter_map(|event| {
+            event
+                .as_any()
+                .downcast_ref::<MetaplexVerifyEvent>()
+                .cloned()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_sized_collection_item_verification() {
+    let accounts = CollectionAccounts::new();
+    for tag in [18, 30] {
+        let events = verify_events(accounts.transaction(tag)).await;
+        assert_eq!(events.len(), 1, "tag {}", tag);
+        assert_eq!(events[0].metadata_account, accounts.metadata);
+        assert_eq!(events[0].authority, accounts.collection_authority);
+        assert_eq!(events[0].verification, MetaplexVerification::Collection);
+        assert_eq!(events[0].collection_mint, accounts.collection_mint);
+    }
+}
+
+#[tokio::test]
+async fn test_set_and_verify_collection_reads_the_collection_mint() {
+    let accounts = CollectionAccounts::new();
+    for tag in [25, 32] {
+        let events = verify_events(accounts.transaction(tag)).await;
+        assert_eq!(events.len(), 1, "tag {}", tag);
+        assert_eq!(events[0].metadata_account, accounts.metadata);
+        assert_eq!(events[0].authority, accounts.collection_authority);
+        assert_eq!(events[0].collection_mint, accounts.collection_mint);
+    }
+}
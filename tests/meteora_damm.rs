@@ -0,0 +1,55 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::protocols::meteora_damm::vault::{
+    unwind_vault_transfers, METEORA_VAULT_PROGRAM_ID,
+};
+use solana_transaction_status::{UiCompiledInstruction, UiInnerInstructions, UiInstruction};
+
+const TOKEN_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+fn instruction(program: u8, accounts: Vec<u8>, data: &[u8], height: u32) -> UiInstruction {
+    UiInstruction::Compiled(UiCompiledInstruction {
+        program_id_index: program,
+        accounts,
+        data: bs58::encode(data).into_string(),
+        stack_height: Some(height),
+    })
+}
+
+fn transfer(amount: u64) -> Vec<u8> {
+    let mut data = vec![3];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data
+}
+
+#[test]
+fn test_unwind_vault_transfers_of_cpi_swap() {
+    // 0: vault程序, 1: token程序, 2..: 代币账户
+    let mut accounts = vec![METEORA_VAULT_PROGRAM_ID, TOKEN_PROGRAM_ID];
+    accounts.extend((0..6).map(|_| Pubkey::new_unique()));
+
+    // 聚合器在高度2调用AMM
+    let inner = UiInnerInstructions {
+        index: 0,
+        instructions: vec![
+            instruction(7, vec![], &[0; 16], 2),
+            instruction(0, vec![2, 3], &[0; 8], 3),
+            instruction(1, vec![2, 3, 4], &transfer(1_000), 4),
+            // vault LP mint
+            instruction(1, vec![5, 6, 4], &[7, 1, 0, 0, 0, 0, 0, 0, 0], 4),
+            instruction(0, vec![3, 2], &[1; 8], 3),
+            instruction(1, vec![6, 7, 4], &transfer(990), 4),
+            // 下一笔同层指令不属于本次兑换
+            instruction(1, vec![2, 3, 4], &transfer(5), 2),
+        ],
+    };
+
+    let transfers = unwind_vault_transfers(&inner, 0, &accounts);
+    assert_eq!(transfers.len(), 2);
+    assert_eq!(transfers[0].amount, 1_000);
+    assert_eq!(transfers[0].source, accounts[2]);
+    assert_eq!(transfers[1].amount, 990);
+    assert_eq!(transfers[1].destination, accounts[7]);
+
+    // 顶层指令取全部内联指令
+    assert_eq!(unwind_vault_transfers(&inner, -1, &accounts).len(), 3);
+}
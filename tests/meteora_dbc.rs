@@ -0,0 +1,43 @@
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventType, InstructionIndex},
+    protocols::meteora_dbc::{
+        discriminators, parser::METEORA_DBC_PROGRAM_ID, MeteoraDbcMigrateEvent, MigrationTarget,
+    },
+    EventParserFactory, Protocol,
+};
+
+#[test]
+fn test_meteora_dbc_migrate_damm_v2_instruction() {
+    let mut accounts: Vec<Pubkey> = (0..25).map(|_| Pubkey::new_unique()).collect();
+    accounts.push(METEORA_DBC_PROGRAM_ID);
+    let instruction = CompiledInstruction {
+        program_id_index: 25,
+        accounts: (0..25).collect(),
+        data: discriminators::MIGRATE_DAMM_V2_IX.to_vec(),
+    };
+
+    let events = EventParserFactory::create_parser(Protocol::MeteoraDbc)
+        .parse_events_from_instruction(
+            &instruction,
+            &accounts,
+            "sig",
+            1,
+            None,
+            0,
+            InstructionIndex::outer(0),
+        );
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type(), EventType::MeteoraDbcMigrate);
+
+    let migrate = events[0]
+        .as_any()
+        .downcast_ref::<MeteoraDbcMigrateEvent>()
+        .expect("migrate event");
+    assert_eq!(migrate.target, MigrationTarget::DammV2);
+    assert_eq!(migrate.pool, accounts[0]);
+    assert_eq!(migrate.config, accounts[2]);
+    assert_eq!(migrate.target_pool, accounts[4]);
+    assert_eq!(migrate.target_program, accounts[12]);
+    assert_eq!(migrate.base_mint, accounts[13]);
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "grpc")]
+
+use solana_streamer_sdk::streaming::{
+    enrichment::BlockTimeHealth, EndpointMode, MultiEndpointGrpc, YellowstoneGrpc,
+};
+
+#[test]
+fn test_endpoints_start_disconnected_in_priority_order() {
+    assert!(MultiEndpointGrpc::new(vec![]).is_err());
+
+    let clients = ["https://primary.example:443", "https://backup.example:443"]
+        .into_iter()
+        .map(|endpoint| YellowstoneGrpc::new(endpoint.to_string(), None).unwrap())
+        .collect();
+    let grpc = MultiEndpointGrpc::new(clients)
+        .unwrap()
+        .with_mode(EndpointMode::Race);
+
+    assert_eq!(grpc.mode(), EndpointMode::Race);
+    let health = grpc.health();
+    assert_eq!(health.len(), 2);
+    assert_eq!(health[0].endpoint, "https://primary.example:443");
+    assert!(health
+        .iter()
+        .all(|endpoint| !endpoint.connected && endpoint.messages == 0));
+    assert!(health
+        .iter()
+        .all(|endpoint| endpoint.block_time == BlockTimeHealth::default()));
+}
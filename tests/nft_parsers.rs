@@ -0,0 +1,109 @@
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventType, InstructionIndex},
+    protocols::{
+        magic_eden::{self, parser::MAGIC_EDEN_M2_PROGRAM_ID, MagicEdenSaleEvent},
+        tensor::{self, parser::TENSOR_COMP_PROGRAM_ID, TensorTakeEvent, TensorTarget},
+    },
+    EventParserFactory, Protocol,
+};
+
+#[test]
+fn test_tensor_collection_bid_fill() {
+    let (taker, bid, whitelist, asset) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let mut data = tensor::discriminators::TCOMP_NOOP_IX.to_vec();
+    // TcompEvent::Taker
+    data.push(1);
+    data.extend_from_slice(taker.as_ref());
+    data.push(1);
+    data.extend_from_slice(bid.as_ref());
+    // Target::Whitelist, no field
+    data.push(1);
+    data.extend_from_slice(whitelist.as_ref());
+    data.extend_from_slice(&[0, 0]);
+    data.extend_from_slice(&2_000_000_000u64.to_le_bytes());
+    data.extend_from_slice(&1u32.to_le_bytes());
+    for fee in [30_000_000u64, 0, 0, 100_000_000] {
+        data.extend_from_slice(&fee.to_le_bytes());
+    }
+    data.push(0);
+    data.push(1);
+    data.extend_from_slice(asset.as_ref());
+
+    let instruction = CompiledInstruction {
+        program_id_index: 0,
+        accounts: vec![],
+        data,
+    };
+    let events = EventParserFactory::create_parser(Protocol::Tensor).parse_events_from_instruction(
+        &instruction,
+        &[TENSOR_COMP_PROGRAM_ID],
+        "sig",
+        1,
+        None,
+        0,
+        InstructionIndex::inner(0, 2),
+    );
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type(), EventType::TensorBidFill);
+
+    let event = events[0]
+        .as_any()
+        .downcast_ref::<TensorTakeEvent>()
+        .expect("take event");
+    assert_eq!(event.taker, taker);
+    assert_eq!(event.target, TensorTarget::Whitelist);
+    assert_eq!(event.target_id, whitelist);
+    assert_eq!(event.amount, 2_000_000_000);
+    assert_eq!(event.tcomp_fee, 30_000_000);
+    assert_eq!(event.creator_fee, 100_000_000);
+    assert_eq!(event.asset_id, Some(asset));
+}
+
+#[test]
+fn test_magic_eden_execute_sale_fees() {
+    let keys: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+    let mut accounts = keys.clone();
+    accounts.push(MAGIC_EDEN_M2_PROGRAM_ID);
+
+    let mut data = magic_eden::discriminators::EXECUTE_SALE_V2_IX.to_vec();
+    data.extend_from_slice(&[254, 255]);
+    data.extend_from_slice(&1_000_000_000u64.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&0i64.to_le_bytes());
+    data.extend_from_slice(&(-1i64).to_le_bytes());
+    data.extend_from_slice(&(-50i16).to_le_bytes());
+    data.extend_from_slice(&250u16.to_le_bytes());
+    let instruction = CompiledInstruction {
+        program_id_index: 10,
+        accounts: (0..10).collect(),
+        data,
+    };
+    let events = EventParserFactory::create_parser(Protocol::MagicEden)
+        .parse_events_from_instruction(
+            &instruction,
+            &accounts,
+            "sig",
+            1,
+            None,
+            0,
+            InstructionIndex::outer(0),
+        );
+    assert_eq!(events.len(), 1);
+
+    let event = events[0]
+        .as_any()
+        .downcast_ref::<MagicEdenSaleEvent>()
+        .expect("sale event");
+    assert_eq!(event.buyer, keys[0]);
+    assert_eq!(event.seller, keys[1]);
+    assert_eq!(event.token_mint, keys[4]);
+    assert_eq!(event.price, 1_000_000_000);
+    assert_eq!(event.maker_fee, -5_000_000);
+    assert_eq!(event.taker_fee, 25_000_000);
+}
@@ -0,0 +1,159 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::common::{
+    EventMetadata, EventType, ProtocolType, TransferData,
+};
+use solana_streamer_sdk::streaming::event_parser::protocols::{
+    pumpfun::PumpFunTradeEvent, pumpswap::PumpSwapWithdrawEvent, raydium_cpmm::RaydiumCpmmSwapEvent,
+};
+use solana_streamer_sdk::streaming::normalize::{
+    LiquidityAction, LiquidityNormalizer, NormalizedLiquidityEvent, NormalizedTrade,
+    TradeNormalizer,
+};
+use solana_streamer_sdk::streaming::pipeline::EventMiddleware;
+use solana_streamer_sdk::streaming::trackers::pool::{USDC_MINT, WSOL_MINT};
+
+fn metadata(event_type: EventType, protocol: ProtocolType) -> EventMetadata {
+    EventMetadata {
+        signature: "sig".to_string(),
+        event_type,
+        protocol,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_pumpfun_sell_is_emitted_alongside() {
+    let (mint, user) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let event = PumpFunTradeEvent {
+        metadata: metadata(EventType::PumpFunSell, ProtocolType::PumpFun),
+        mint,
+        user,
+        is_buy: false,
+        token_amount: 2_000_000,
+        sol_amount: 1_010_000,
+        fee: 10_000,
+        ..Default::default()
+    };
+    let events = TradeNormalizer::new().handle(Box::new(event));
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].event_type(), EventType::PumpFunSell);
+
+    let trade = events[1]
+        .as_any()
+        .downcast_ref::<NormalizedTrade>()
+        .expect("normalized trade");
+    assert_eq!(trade.metadata.event_type, EventType::NormalizedTrade);
+    assert_eq!(trade.venue, ProtocolType::PumpFun);
+    assert_eq!(trade.trader, user);
+    assert_eq!((trade.mint_in, trade.amount_in), (mint, 2_000_000));
+    assert_eq!((trade.mint_out, trade.amount_out), (WSOL_MINT, 1_000_000));
+    assert!(!trade.is_buy);
+    assert_eq!(trade.price, Some(0.5));
+}
+
+#[test]
+fn test_cpmm_swap_takes_amounts_from_transfers() {
+    let (input_vault, output_vault) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let token = Pubkey::new_unique();
+    let mut metadata = metadata(
+        EventType::RaydiumCpmmSwapBaseInput,
+        ProtocolType::RaydiumCpmm,
+    );
+    metadata.transfer_datas = vec![
+        TransferData {
+            destination: input_vault,
+            amount: 5_000_000,
+            ..Default::default()
+        },
+        TransferData {
+            source: output_vault,
+            amount: 1_250,
+            ..Default::default()
+        },
+    ];
+    let event = RaydiumCpmmSwapEvent {
+        metadata,
+        amount_in: 5_000_000,
+        minimum_amount_out: 1_000,
+        input_vault,
+        output_vault,
+        input_token_mint: USDC_MINT,
+        output_token_mint: token,
+        ..Default::default()
+    };
+    let trade = NormalizedTrade::from_event(&event).expect("normalized trade");
+    assert_eq!(trade.amount_out, 1_250);
+    assert_eq!((trade.base_mint, trade.quote_mint), (token, USDC_MINT));
+    assert!(trade.is_buy);
+    assert_eq!(trade.price, Some(4_000.0));
+    assert_eq!(trade.usd_value, Some(5.0));
+}
+
+#[test]
+fn test_sol_usdc_pool_is_quoted_in_usdc() {
+    let (input_vault, output_vault) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut metadata = metadata(
+        EventType::RaydiumCpmmSwapBaseInput,
+        ProtocolType::RaydiumCpmm,
+    );
+    metadata.transfer_datas = vec![
+        TransferData {
+            destination: input_vault,
+            amount: 2_000_000_000,
+            mint: Some(WSOL_MINT),
+            decimals: Some(9),
+            ..Default::default()
+        },
+        TransferData {
+            source: output_vault,
+            amount: 300_000_000,
+            mint: Some(USDC_MINT),
+            decimals: Some(6),
+            ..Default::default()
+        },
+    ];
+    let event = RaydiumCpmmSwapEvent {
+        metadata,
+        input_vault,
+        output_vault,
+        input_token_mint: WSOL_MINT,
+        output_token_mint: USDC_MINT,
+        ..Default::default()
+    };
+    let trade = NormalizedTrade::from_event(&event).expect("normalized trade");
+    assert_eq!((trade.base_mint, trade.quote_mint), (WSOL_MINT, USDC_MINT));
+    assert!(!trade.is_buy);
+    assert_eq!(trade.ui_price, Some(150.0));
+    assert_eq!(trade.usd_price, Some(150.0));
+    assert_eq!(trade.usd_value, Some(300.0));
+}
+
+#[test]
+fn test_pumpswap_withdraw_liquidity_deltas() {
+    let (pool, user, base_mint) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let event = PumpSwapWithdrawEvent {
+        metadata: metadata(EventType::PumpSwapWithdraw, ProtocolType::PumpSwap),
+        pool,
+        user,
+        base_mint,
+        quote_mint: WSOL_MINT,
+        base_amount_out: 1_000,
+        quote_amount_out: 20,
+        lp_token_amount_in: 100,
+        ..Default::default()
+    };
+    let events = LiquidityNormalizer::new().handle(Box::new(event));
+    assert_eq!(events.len(), 2);
+    let liquidity = events[1]
+        .as_any()
+        .downcast_ref::<NormalizedLiquidityEvent>()
+        .expect("normalized liquidity event");
+    assert_eq!(liquidity.action, LiquidityAction::Withdraw);
+    assert_eq!((liquidity.pool, liquidity.provider), (pool, user));
+    assert_eq!((liquidity.base_delta, liquidity.quote_delta), (-1_000, -20));
+    assert_eq!(liquidity.lp_delta, -100);
+}
@@ -0,0 +1,115 @@
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_streamer_sdk::streaming::event_parser::{
+    common::InstructionIndex,
+    protocols::phoenix::{
+        parser::{LOG_IX_TAG, PHOENIX_PROGRAM_ID},
+        register_market, PhoenixFillEvent, PhoenixFillSummaryEvent, PhoenixMarketParams,
+    },
+    EventParserFactory, Protocol, UnifiedEvent,
+};
+
+/// Data of a `Log` instruction as Phoenix records it: the instruction tag, the
+/// borsh encoded `PhoenixMarketEvent::Header` and the other events after it
+fn log_instruction(market: Pubkey, signer: Pubkey, events: &[Vec<u8>]) -> Vec<u8> {
+    // Header variant and the Swap instruction that recorded the events
+    let mut data = vec![LOG_IX_TAG, 1, 0];
+    data.extend_from_slice(&7u64.to_le_bytes());
+    data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+    data.extend_from_slice(&42u64.to_le_bytes());
+    data.extend_from_slice(market.as_ref());
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(&(events.len() as u16).to_le_bytes());
+    for event in events {
+        data.extend_from_slice(event);
+    }
+    data
+}
+
+fn parse(data: Vec<u8>) -> Vec<Box<dyn UnifiedEvent>> {
+    let accounts = vec![PHOENIX_PROGRAM_ID];
+    let instruction = CompiledInstruction {
+        program_id_index: 0,
+        accounts: vec![],
+        data,
+    };
+    EventParserFactory::create_parser(Protocol::Phoenix).parse_events_from_instruction(
+        &instruction,
+        &accounts,
+        "sig",
+        1,
+        None,
+        0,
+        InstructionIndex::inner(0, 1),
+    )
+}
+
+#[test]
+fn test_phoenix_log_instruction_fills() {
+    let (market, signer, maker) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    // SOL/USDC: 0.001 SOL per lot, 0.001 USDC ticks
+    register_market(
+        market,
+        PhoenixMarketParams {
+            base_decimals: 9,
+            quote_decimals: 6,
+            base_lot_size: 1_000_000,
+            quote_lot_size: 1,
+            tick_size_in_quote_atoms_per_base_unit: 1_000,
+        },
+    );
+
+    let mut fill = vec![2];
+    fill.extend_from_slice(&0u16.to_le_bytes());
+    fill.extend_from_slice(maker.as_ref());
+    for value in [11u64, 150_000, 2_000, 0] {
+        fill.extend_from_slice(&value.to_le_bytes());
+    }
+    let mut summary = vec![6];
+    summary.extend_from_slice(&1u16.to_le_bytes());
+    summary.extend_from_slice(&0u128.to_le_bytes());
+    for value in [2_000u64, 300_000_000, 120_000] {
+        summary.extend_from_slice(&value.to_le_bytes());
+    }
+
+    let events = parse(log_instruction(market, signer, &[fill, summary]));
+    assert_eq!(events.len(), 2);
+    assert_ne!(events[0].id(), events[1].id());
+
+    let fill = events[0]
+        .as_any()
+        .downcast_ref::<PhoenixFillEvent>()
+        .expect("fill event");
+    assert_eq!(fill.market, market);
+    assert_eq!(fill.signer, signer);
+    assert_eq!(fill.maker, maker);
+    assert_eq!(fill.base_atoms_filled, 2_000_000_000);
+    assert_eq!(fill.quote_atoms_filled, 300_000_000);
+    assert_eq!(fill.price, Some(150.0));
+    assert_eq!(fill.size, Some(2.0));
+
+    let summary = events[1]
+        .as_any()
+        .downcast_ref::<PhoenixFillSummaryEvent>()
+        .expect("fill summary event");
+    assert_eq!(summary.total_fee_in_quote_atoms, 120_000);
+}
+
+#[test]
+fn test_phoenix_log_without_header_variant_is_ignored() {
+    let mut data = log_instruction(Pubkey::new_unique(), Pubkey::new_unique(), &[]);
+    assert_eq!(parse(data.clone()).len(), 0);
+    // The header without its variant byte isn't mistaken for one
+    data.remove(1);
+    let mut fill = vec![2];
+    fill.extend_from_slice(&0u16.to_le_bytes());
+    fill.extend_from_slice(Pubkey::new_unique().as_ref());
+    for value in [11u64, 150_000, 2_000, 0] {
+        fill.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(&fill);
+    assert!(parse(data).is_empty());
+}
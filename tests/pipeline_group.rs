@@ -0,0 +1,90 @@
+#![cfg(feature = "runtime")]
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use solana_streamer_sdk::common::AnyResult;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::EventMetadata, protocols::pumpfun::PumpFunTradeEvent, UnifiedEvent,
+};
+use solana_streamer_sdk::streaming::pipeline::{EventPipeline, PipelineCallback, PipelineGroup};
+use solana_streamer_sdk::streaming::StreamHandle;
+
+/// Source shaped like `spawn_events_with_request`: the stream runs in the task of
+/// the returned handle and stops reading on shutdown
+fn ticking_source(
+    running: Arc<AtomicBool>,
+) -> impl FnOnce(PipelineCallback) -> std::future::Ready<AnyResult<StreamHandle>> {
+    move |callback| {
+        std::future::ready(Ok(StreamHandle::spawn(move |shutdown| async move {
+            running.store(true, Ordering::SeqCst);
+            let mut slot = 0;
+            loop {
+                tokio::select! {
+                    _ = shutdown.wait() => break,
+                    _ = tokio::time::sleep(Duration::from_millis(1)) => {}
+                }
+                slot += 1;
+                callback(Box::new(PumpFunTradeEvent {
+                    metadata: EventMetadata {
+                        slot,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }));
+            }
+            running.store(false, Ordering::SeqCst);
+            Ok(())
+        })))
+    }
+}
+
+#[tokio::test]
+async fn test_stop_shuts_down_the_source_stream() {
+    let group = PipelineGroup::new();
+    let running = Arc::new(AtomicBool::new(false));
+    let delivered = Arc::new(AtomicU64::new(0));
+    let counter = delivered.clone();
+    group
+        .spawn(
+            "ticks",
+            EventPipeline::new(),
+            move |_event: Box<dyn UnifiedEvent>| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            },
+            ticking_source(running.clone()),
+        )
+        .await
+        .unwrap();
+    assert!(group.is_running("ticks"));
+
+    // A second pipeline with the same name is rejected
+    let duplicate = group
+        .spawn(
+            "ticks",
+            EventPipeline::new(),
+            |_event: Box<dyn UnifiedEvent>| {},
+            ticking_source(Arc::new(AtomicBool::new(false))),
+        )
+        .await;
+    assert!(duplicate.is_err());
+
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(running.load(Ordering::SeqCst));
+    let report = group
+        .stop("ticks", Duration::from_secs(1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(report.drained);
+    assert!(!running.load(Ordering::SeqCst));
+    assert!(!group.is_running("ticks"));
+
+    // Nothing is delivered after the stream stopped
+    let stopped_at = delivered.load(Ordering::SeqCst);
+    assert!(stopped_at > 0);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(delivered.load(Ordering::SeqCst), stopped_at);
+    assert!(group.stop("ticks", Duration::from_secs(1)).await.is_none());
+}
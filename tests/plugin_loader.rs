@@ -0,0 +1,167 @@
+#![cfg(feature = "plugins")]
+
+use serde_json::{json, Value};
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_streamer_sdk::streaming::event_parser::common::InstructionIndex;
+use solana_streamer_sdk::streaming::plugin::{
+    abi::{free_buffer, PluginBuffer},
+    PluginError, PluginEvent, PluginSet,
+};
+use solana_streamer_sdk::streaming::sink::EventSink;
+use std::env::consts::{DLL_EXTENSION, DLL_PREFIX};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Build the echo test plugin (tests/plugins/echo_plugin) into a fresh plugin directory
+fn echo_plugin_dir(name: &str) -> PathBuf {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("echo_plugin");
+    let manifest =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/plugins/echo_plugin/Cargo.toml");
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--quiet", "--manifest-path"])
+        .arg(manifest)
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "failed to build the echo plugin");
+
+    let library = format!("{}echo_plugin.{}", DLL_PREFIX, DLL_EXTENSION);
+    let dir =
+        std::env::temp_dir().join(format!("streamer-plugins-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::copy(target_dir.join("debug").join(&library), dir.join(&library)).unwrap();
+    dir
+}
+
+#[test]
+fn test_discover_reports_invalid_libraries() {
+    let dir = std::env::temp_dir().join(format!("streamer-plugins-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let bogus = dir.join(format!("bogus.{}", std::env::consts::DLL_EXTENSION));
+    fs::write(&bogus, b"not a shared library").unwrap();
+    fs::write(dir.join("README.txt"), b"ignored").unwrap();
+
+    let set = unsafe { PluginSet::discover(&dir) }.unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(set.plugins.is_empty());
+    assert_eq!(set.failed.len(), 1);
+    assert_eq!(set.failed[0].0, bogus);
+}
+
+#[test]
+fn test_plugin_buffer_round_trip() {
+    let buffer = PluginBuffer::from_vec(b"[]".to_vec());
+    assert_eq!(buffer.len, 2);
+    unsafe { free_buffer(buffer) };
+}
+
+#[test]
+fn test_loaded_plugin_parses_instructions() {
+    let dir = echo_plugin_dir("parser");
+    let set = unsafe { PluginSet::discover(&dir) }.unwrap();
+    assert!(set.failed.is_empty());
+    assert_eq!(set.plugins.len(), 1);
+    let plugin = &set.plugins[0];
+    let program_id = Pubkey::new_from_array([7; 32]);
+    assert_eq!(plugin.name(), "echo");
+    assert_eq!(plugin.program_ids(), &[program_id]);
+    assert!(plugin.is_parser() && plugin.is_sink());
+
+    let events = plugin
+        .parse_instruction(&program_id, &[1, 2, 3], &[Pubkey::new_unique()])
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![(
+            "Echo".to_string(),
+            json!({ "data": [1, 2, 3], "accounts": 1 })
+        )]
+    );
+    assert!(plugin
+        .parse_instruction(&program_id, &[0], &[])
+        .unwrap()
+        .is_empty());
+    assert!(matches!(
+        plugin.parse_instruction(&program_id, &[], &[]),
+        Err(PluginError::Call { code: 1, .. })
+    ));
+
+    // 作为解析器：指令账户按指令顺序传给插件
+    let parsers = set.parsers();
+    assert_eq!(parsers.len(), 1);
+    let accounts = vec![Pubkey::new_unique(), Pubkey::new_unique(), program_id];
+    let instruction = CompiledInstruction {
+        program_id_index: 2,
+        accounts: vec![0, 1],
+        data: vec![2, 9],
+    };
+    let events = parsers[0].parse_events_from_instruction(
+        &instruction,
+        &accounts,
+        "sig",
+        10,
+        None,
+        0,
+        InstructionIndex::outer(0),
+    );
+    let events: Vec<&PluginEvent> = events
+        .iter()
+        .map(|event| event.as_any().downcast_ref::<PluginEvent>().unwrap())
+        .collect();
+    assert_eq!(events.len(), 2);
+    assert_eq!(
+        (events[0].plugin.as_str(), events[0].name.as_str()),
+        ("echo", "Echo")
+    );
+    assert_eq!(events[0].fields, json!({ "data": [2, 9], "accounts": 2 }));
+    assert_eq!(events[0].metadata.slot, 10);
+    assert_eq!(events[0].metadata.program_id, program_id);
+    assert_eq!(
+        (events[1].name.as_str(), &events[1].fields),
+        ("Twice", &Value::Null)
+    );
+    assert_ne!(events[0].metadata.id, events[1].metadata.id);
+
+    // Instructions of other programs don't reach the plugin
+    let other = CompiledInstruction {
+        program_id_index: 0,
+        ..instruction
+    };
+    assert!(parsers[0]
+        .parse_events_from_instruction(
+            &other,
+            &accounts,
+            "sig",
+            10,
+            None,
+            0,
+            InstructionIndex::outer(1),
+        )
+        .is_empty());
+
+    drop(parsers);
+    drop(set);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_loaded_plugin_receives_sink_events() {
+    let dir = echo_plugin_dir("sink");
+    let set = unsafe { PluginSet::discover(&dir) }.unwrap();
+    let sinks = set.sinks();
+    assert_eq!(sinks.len(), 1);
+    let sink = &sinks[0];
+    assert_eq!(sink.name(), "echo");
+    sink.write("PumpFunTradeEvent", &json!({ "sol_amount": 1 }))
+        .unwrap();
+    let error = sink.write("Reject", &json!({})).unwrap_err();
+    assert!(error.to_string().contains("error code 2"), "{}", error);
+    sink.flush().unwrap();
+
+    drop(sinks);
+    drop(set);
+    fs::remove_dir_all(&dir).unwrap();
+}
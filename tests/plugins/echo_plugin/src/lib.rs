@@ -0,0 +1,110 @@
+//! 测试插件 - 把指令回显为事件
+//!
+//! Declares the plugin ABI types itself instead of depending on the streamer, as a
+//! plugin built by another compiler or in another language would. Parses
+//! instructions of the program `[7; 32]`:
+//! - empty data is an error (code 1),
+//! - a first byte of 0 yields no events,
+//! - any other data yields an `Echo` event, and a first byte of 2 also a `Twice` event.
+//!
+//! As a sink it accepts every event except the type `Reject` (code 2).
+
+use std::ffi::c_char;
+
+#[repr(C)]
+pub struct PluginBuffer {
+    pub ptr: *mut u8,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+#[repr(C)]
+pub struct PluginDeclaration {
+    pub abi_version: u32,
+    pub name: *const c_char,
+    pub program_ids: *const [u8; 32],
+    pub program_ids_len: usize,
+    pub parse_instruction: Option<
+        unsafe extern "C" fn(
+            *const [u8; 32],
+            *const u8,
+            usize,
+            *const [u8; 32],
+            usize,
+            *mut PluginBuffer,
+        ) -> i32,
+    >,
+    pub write_event: Option<unsafe extern "C" fn(*const u8, usize, *const u8, usize) -> i32>,
+    pub flush: Option<unsafe extern "C" fn() -> i32>,
+    pub free_buffer: Option<unsafe extern "C" fn(PluginBuffer)>,
+}
+
+unsafe impl Sync for PluginDeclaration {}
+
+static PROGRAM_IDS: [[u8; 32]; 1] = [[7; 32]];
+
+unsafe extern "C" fn parse_instruction(
+    _program_id: *const [u8; 32],
+    data: *const u8,
+    data_len: usize,
+    _accounts: *const [u8; 32],
+    accounts_len: usize,
+    out: *mut PluginBuffer,
+) -> i32 {
+    if data_len == 0 {
+        return 1;
+    }
+    let data = std::slice::from_raw_parts(data, data_len);
+    if data[0] == 0 {
+        return 0;
+    }
+    let mut json = format!(
+        r#"[{{"name":"Echo","fields":{{"data":{:?},"accounts":{}}}}}"#,
+        data, accounts_len
+    );
+    if data[0] == 2 {
+        json.push_str(r#",{"name":"Twice"}"#);
+    }
+    json.push(']');
+    let mut bytes = std::mem::ManuallyDrop::new(json.into_bytes());
+    *out = PluginBuffer {
+        ptr: bytes.as_mut_ptr(),
+        len: bytes.len(),
+        capacity: bytes.capacity(),
+    };
+    0
+}
+
+unsafe extern "C" fn write_event(
+    event_type: *const u8,
+    event_type_len: usize,
+    _json: *const u8,
+    _json_len: usize,
+) -> i32 {
+    if std::slice::from_raw_parts(event_type, event_type_len) == b"Reject" {
+        return 2;
+    }
+    0
+}
+
+unsafe extern "C" fn flush() -> i32 {
+    0
+}
+
+unsafe extern "C" fn free_buffer(buffer: PluginBuffer) {
+    if !buffer.ptr.is_null() {
+        drop(Vec::from_raw_parts(buffer.ptr, buffer.len, buffer.capacity));
+    }
+}
+
+#[no_mangle]
+pub static solana_streamer_plugin: PluginDeclaration = PluginDeclaration {
+    abi_version: 1,
+    name: c"echo".as_ptr(),
+    program_ids: PROGRAM_IDS.as_ptr(),
+    program_ids_len: PROGRAM_IDS.len(),
+    parse_instruction: Some(parse_instruction),
+    write_event: Some(write_event),
+    flush: Some(flush),
+    free_buffer: Some(free_buffer),
+};
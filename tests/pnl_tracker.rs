@@ -0,0 +1,131 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::EventMetadata, protocols::pumpfun::PumpFunTradeEvent,
+};
+use solana_streamer_sdk::streaming::pipeline::EventMiddleware;
+use solana_streamer_sdk::streaming::trackers::{pool::WSOL_MINT, PnlTracker, PositionClosedEvent};
+
+fn trade(
+    wallet: Pubkey,
+    mint: Pubkey,
+    is_buy: bool,
+    token_amount: u64,
+    sol_amount: u64,
+    slot: u64,
+) -> PumpFunTradeEvent {
+    PumpFunTradeEvent {
+        metadata: EventMetadata {
+            slot,
+            ..Default::default()
+        },
+        user: wallet,
+        mint,
+        is_buy,
+        token_amount,
+        sol_amount,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_average_cost_basis_and_pnl() {
+    let tracker = PnlTracker::new();
+    let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    assert!(tracker
+        .apply_event(&trade(wallet, mint, true, 1_000, 10_000, 1))
+        .is_none());
+    tracker.apply_event(&trade(wallet, mint, true, 1_000, 30_000, 2));
+    let position = tracker.get_position(&wallet, &mint).unwrap();
+    assert_eq!(position.quote_mint, WSOL_MINT);
+    assert_eq!(position.balance, 2_000);
+    assert_eq!(position.cost_basis, 40_000);
+    assert_eq!(position.total_cost, 40_000);
+
+    // 按最近成交价计算未实现盈亏
+    assert_eq!(tracker.get_last_price(&mint), Some(30.0));
+    assert_eq!(tracker.get_unrealized_pnl(&wallet, &mint), Some(20_000.0));
+
+    // Selling a quarter realizes a quarter of the cost basis
+    tracker.apply_event(&trade(wallet, mint, false, 500, 15_000, 3));
+    let position = tracker.get_position(&wallet, &mint).unwrap();
+    assert_eq!(position.balance, 1_500);
+    assert_eq!(position.cost_basis, 30_000);
+    assert_eq!(position.realized_pnl, 5_000);
+    assert_eq!(position.unrealized_pnl(20.0), 0.0);
+    assert!(position.is_open());
+    assert_eq!(position.last_slot, 3);
+}
+
+#[test]
+fn test_selling_more_than_the_position() {
+    let tracker = PnlTracker::new();
+    let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    tracker.apply_event(&trade(wallet, mint, true, 1_000, 20_000, 1));
+
+    // Only the tracked 1000 tokens have a cost basis
+    let closed = tracker
+        .apply_event(&trade(wallet, mint, false, 2_000, 30_000, 2))
+        .expect("the sell closes the position");
+    assert_eq!(closed.realized_pnl, -5_000);
+    assert_eq!(closed.total_sold, 2_000);
+    assert_eq!(closed.total_proceeds, 30_000);
+
+    let position = tracker.get_position(&wallet, &mint).unwrap();
+    assert_eq!(position.balance, 0);
+    assert_eq!(position.cost_basis, 0);
+    assert_eq!(position.untracked_sold, 1_000);
+    assert!(!position.is_open());
+
+    // Sells without a tracked buy don't realize anything
+    let other = Pubkey::new_unique();
+    assert!(tracker
+        .apply_event(&trade(other, mint, false, 500, 5_000, 3))
+        .is_none());
+    let position = tracker.get_position(&other, &mint).unwrap();
+    assert_eq!(position.untracked_sold, 500);
+    assert_eq!(position.realized_pnl, 0);
+
+    assert_eq!(tracker.get_realized_pnl(&wallet)[&WSOL_MINT], -5_000);
+    assert_eq!(tracker.get_positions(&wallet).len(), 1);
+    tracker.remove_wallet(&wallet);
+    assert!(tracker.get_position(&wallet, &mint).is_none());
+    assert_eq!(tracker.len(), 1);
+}
+
+#[test]
+fn test_fees_count_towards_cost_and_proceeds() {
+    let tracker = PnlTracker::new();
+    let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    tracker.apply_event(&PumpFunTradeEvent {
+        fee: 900,
+        creator_fee: 100,
+        ..trade(wallet, mint, true, 1_000, 9_000, 1)
+    });
+    let closed = tracker
+        .apply_event(&PumpFunTradeEvent {
+            fee: 1_000,
+            creator_fee: 200,
+            ..trade(wallet, mint, false, 1_000, 12_000, 2)
+        })
+        .unwrap();
+    assert_eq!(closed.total_cost, 10_000);
+    assert_eq!(closed.total_proceeds, 10_800);
+    assert_eq!(closed.realized_pnl, 800);
+}
+
+#[test]
+fn test_stage_emits_position_closed() {
+    let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let closed = |tracker: &PnlTracker| {
+        tracker.handle(Box::new(trade(wallet, mint, true, 1_000, 10_000, 1)));
+        tracker
+            .handle(Box::new(trade(wallet, mint, false, 1_000, 12_000, 2)))
+            .iter()
+            .filter_map(|e| e.as_any().downcast_ref::<PositionClosedEvent>())
+            .map(|e| e.realized_pnl)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(closed(&PnlTracker::new()), [2_000]);
+    assert!(closed(&PnlTracker::new().with_position_closed(false)).is_empty());
+}
@@ -0,0 +1,303 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventMetadata, ProtocolType, TransferData},
+    protocols::{
+        meteora_damm::{MeteoraDammLiquidityEvent, MeteoraDammSwapEvent},
+        pumpswap::PumpSwapBuyEvent,
+        raydium_cpmm::RaydiumCpmmSwapEvent,
+    },
+};
+use solana_streamer_sdk::streaming::trackers::{pool::WSOL_MINT, PoolTracker};
+
+struct CpmmPool {
+    pool: Pubkey,
+    mint: Pubkey,
+    base_vault: Pubkey,
+    quote_vault: Pubkey,
+}
+
+impl CpmmPool {
+    fn new() -> Self {
+        Self {
+            pool: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            base_vault: Pubkey::new_unique(),
+            quote_vault: Pubkey::new_unique(),
+        }
+    }
+
+    /// Buy of the token with 1 SOL for 5 tokens
+    fn buy(&self, slot: u64) -> RaydiumCpmmSwapEvent {
+        let user = Pubkey::new_unique();
+        RaydiumCpmmSwapEvent {
+            metadata: EventMetadata {
+                slot,
+                transfer_datas: vec![
+                    TransferData {
+                        source: user,
+                        destination: self.quote_vault,
+                        amount: 1_000_000_000,
+                        decimals: Some(9),
+                        mint: Some(WSOL_MINT),
+                        ..Default::default()
+                    },
+                    TransferData {
+                        source: self.base_vault,
+                        destination: user,
+                        amount: 5_000_000,
+                        decimals: Some(6),
+                        mint: Some(self.mint),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            pool_state: self.pool,
+            input_vault: self.quote_vault,
+            output_vault: self.base_vault,
+            input_token_mint: WSOL_MINT,
+            output_token_mint: self.mint,
+            ..Default::default()
+        }
+    }
+}
+
+#[test]
+fn test_vault_swaps_need_both_vaults_seeded() {
+    let tracker = PoolTracker::new();
+    let pool = CpmmPool::new();
+    let state = tracker.apply_event(&pool.buy(10)).unwrap();
+    assert_eq!(state.base_mint, pool.mint);
+    assert_eq!(state.quote_mint, WSOL_MINT);
+    assert_eq!(state.base_vault, pool.base_vault);
+    assert_eq!(state.quote_reserve, 1_000_000_000);
+    assert!(!state.reserves_known);
+    assert_eq!(tracker.get_price(&pool.pool), None);
+
+    // The quote reserve only holds deltas, so one seeded vault isn't enough
+    let state = tracker
+        .update_vault_balance(&pool.base_vault, 100_000_000_000, 11)
+        .unwrap();
+    assert!(state.base_seeded && !state.quote_seeded);
+    assert!(!state.reserves_known);
+    assert_eq!(tracker.get_price(&pool.pool), None);
+
+    let state = tracker
+        .update_vault_balance(&pool.quote_vault, 20_000_000_000, 12)
+        .unwrap();
+    assert!(state.reserves_known);
+    let price = tracker.get_price(&pool.pool).unwrap();
+    assert!((price - 0.0002).abs() < 1e-12);
+
+    // Later swaps move the seeded reserves, older balances are ignored
+    let state = tracker.apply_event(&pool.buy(13)).unwrap();
+    assert_eq!(state.base_reserve, 99_995_000_000);
+    assert_eq!(state.quote_reserve, 21_000_000_000);
+    assert!(tracker
+        .update_vault_balance(&pool.quote_vault, 1, 12)
+        .is_none());
+    assert!(tracker
+        .update_vault_balance(&Pubkey::new_unique(), 1, 14)
+        .is_none());
+}
+
+#[test]
+fn test_empty_vault_counts_as_seeded() {
+    let tracker = PoolTracker::new();
+    let pool = CpmmPool::new();
+    tracker.apply_event(&pool.buy(10)).unwrap();
+    tracker
+        .update_vault_balance(&pool.quote_vault, 0, 11)
+        .unwrap();
+    let state = tracker
+        .update_vault_balance(&pool.base_vault, 100_000_000_000, 11)
+        .unwrap();
+    assert!(state.reserves_known);
+    assert_eq!(state.quote_reserve, 0);
+    assert_eq!(tracker.get_price(&pool.pool), Some(0.0));
+}
+
+#[test]
+fn test_pumpswap_events_carry_exact_reserves() {
+    let tracker = PoolTracker::new();
+    let (pool, base_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let buy = PumpSwapBuyEvent {
+        metadata: EventMetadata {
+            slot: 10,
+            ..Default::default()
+        },
+        pool,
+        base_mint,
+        quote_mint: WSOL_MINT,
+        pool_base_token_account: Pubkey::new_unique(),
+        pool_quote_token_account: Pubkey::new_unique(),
+        pool_base_token_reserves: 1_000_000_000_000,
+        pool_quote_token_reserves: 50_000_000_000,
+        base_amount_out: 1_000_000,
+        quote_amount_in_with_lp_fee: 10_000_000,
+        ..Default::default()
+    };
+    let state = tracker.apply_event(&buy).unwrap();
+    assert!(state.reserves_known);
+    assert_eq!(state.base_reserve, 999_999_000_000);
+    assert_eq!(state.quote_reserve, 50_010_000_000);
+    assert_eq!(state.base_decimals, Some(6));
+    let price = tracker.get_price(&pool).unwrap();
+    assert!((price - 50.01 / 999_999.0).abs() < 1e-12);
+    assert!((tracker.get_tvl(&pool).unwrap() - 100.02).abs() < 1e-9);
+
+    // Events of older slots don't roll the pool back
+    let stale = PumpSwapBuyEvent {
+        metadata: EventMetadata {
+            slot: 9,
+            ..Default::default()
+        },
+        ..buy
+    };
+    assert!(tracker.apply_event(&stale).is_none());
+    assert_eq!(
+        tracker.get_state(&pool).unwrap().base_reserve,
+        999_999_000_000
+    );
+}
+
+/// Meteora DAMM pool of a token (side a) against WSOL (side b)
+struct MeteoraPool {
+    pool: Pubkey,
+    mint: Pubkey,
+    a_token_vault: Pubkey,
+    b_token_vault: Pubkey,
+    user: Pubkey,
+}
+
+impl MeteoraPool {
+    fn new() -> Self {
+        Self {
+            pool: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            a_token_vault: Pubkey::new_unique(),
+            b_token_vault: Pubkey::new_unique(),
+            user: Pubkey::new_unique(),
+        }
+    }
+
+    /// Vault transfer of `amount` tokens into (`deposit`) or out of a token vault
+    fn transfer(&self, a_side: bool, amount: u64, deposit: bool) -> TransferData {
+        let (vault, mint, decimals) = if a_side {
+            (self.a_token_vault, self.mint, 6)
+        } else {
+            (self.b_token_vault, WSOL_MINT, 9)
+        };
+        let (source, destination) = if deposit {
+            (self.user, vault)
+        } else {
+            (vault, self.user)
+        };
+        TransferData {
+            source,
+            destination,
+            amount,
+            decimals: Some(decimals),
+            mint: Some(mint),
+            ..Default::default()
+        }
+    }
+
+    fn metadata(&self, slot: u64, transfer_datas: Vec<TransferData>) -> EventMetadata {
+        EventMetadata {
+            slot,
+            transfer_datas,
+            ..Default::default()
+        }
+    }
+
+    /// Buy of the token with 1 SOL for 5 tokens; the protocol fee skips the vaults
+    fn buy(&self, slot: u64) -> MeteoraDammSwapEvent {
+        let protocol_fee = TransferData {
+            source: self.user,
+            destination: Pubkey::new_unique(),
+            amount: 2_000_000,
+            mint: Some(WSOL_MINT),
+            ..Default::default()
+        };
+        MeteoraDammSwapEvent {
+            metadata: self.metadata(
+                slot,
+                vec![
+                    protocol_fee,
+                    self.transfer(false, 1_000_000_000, true),
+                    self.transfer(true, 5_000_000, false),
+                ],
+            ),
+            pool: self.pool,
+            a_token_vault: self.a_token_vault,
+            b_token_vault: self.b_token_vault,
+            ..Default::default()
+        }
+    }
+
+    /// Balanced deposit (`add`) or withdrawal of 1 token and 0.2 SOL
+    fn liquidity(&self, slot: u64, add: bool) -> MeteoraDammLiquidityEvent {
+        MeteoraDammLiquidityEvent {
+            metadata: self.metadata(
+                slot,
+                vec![
+                    self.transfer(true, 1_000_000, add),
+                    self.transfer(false, 200_000_000, add),
+                ],
+            ),
+            pool: self.pool,
+            a_token_vault: self.a_token_vault,
+            b_token_vault: self.b_token_vault,
+            ..Default::default()
+        }
+    }
+}
+
+#[test]
+fn test_meteora_damm_tracks_shared_vault_deltas() {
+    let tracker = PoolTracker::new();
+    let pool = MeteoraPool::new();
+    let state = tracker.apply_event(&pool.buy(10)).unwrap();
+    assert_eq!(state.protocol, ProtocolType::MeteoraDamm);
+    assert_eq!(state.base_mint, pool.mint);
+    assert_eq!(state.quote_mint, WSOL_MINT);
+    assert_eq!(state.base_vault, pool.a_token_vault);
+    assert_eq!(state.quote_reserve, 1_000_000_000);
+    assert_eq!(state.base_decimals, Some(6));
+    assert!(!state.reserves_known);
+
+    // Token vaults are shared by every pool of the mint, so their balances don't seed
+    assert!(tracker
+        .update_vault_balance(&pool.a_token_vault, 1, 11)
+        .is_none());
+    assert!(tracker
+        .update_vault_balance(&pool.b_token_vault, 1, 11)
+        .is_none());
+
+    tracker
+        .apply_reserves(
+            pool.pool,
+            ProtocolType::MeteoraDamm,
+            pool.mint,
+            WSOL_MINT,
+            100_000_000_000,
+            20_000_000_000,
+            11,
+        )
+        .unwrap();
+    let price = tracker.get_price(&pool.pool).unwrap();
+    assert!((price - 0.0002).abs() < 1e-12);
+
+    let state = tracker.apply_event(&pool.liquidity(12, true)).unwrap();
+    assert_eq!(state.base_reserve, 100_001_000_000);
+    assert_eq!(state.quote_reserve, 20_200_000_000);
+    let state = tracker.apply_event(&pool.liquidity(13, false)).unwrap();
+    assert_eq!(state.base_reserve, 100_000_000_000);
+    assert_eq!(state.quote_reserve, 20_000_000_000);
+
+    let state = tracker.apply_event(&pool.buy(14)).unwrap();
+    assert_eq!(state.base_reserve, 99_995_000_000);
+    assert_eq!(state.quote_reserve, 21_000_000_000);
+    assert!(state.reserves_known);
+}
@@ -0,0 +1,117 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::common::DevAddressRegistry;
+use solana_streamer_sdk::streaming::event_parser::core::{
+    EventPostProcessor, PostProcessContext, PostProcessorChain, PumpFunDevTagger,
+};
+use solana_streamer_sdk::streaming::event_parser::protocols::pumpfun::{
+    PumpFunCreateTokenEvent, PumpFunTradeEvent,
+};
+use solana_streamer_sdk::streaming::event_parser::UnifiedEvent;
+use std::sync::Arc;
+
+struct DropSmallTrades;
+
+impl EventPostProcessor for DropSmallTrades {
+    fn name(&self) -> &str {
+        "drop_small_trades"
+    }
+
+    fn process(&self, events: &mut Vec<Box<dyn UnifiedEvent>>, _context: &PostProcessContext) {
+        events.retain(|event| {
+            event
+                .as_any()
+                .downcast_ref::<PumpFunTradeEvent>()
+                .map_or(true, |trade| trade.sol_amount >= 100)
+        });
+    }
+}
+
+fn trade(user: Pubkey, mint: Pubkey, sol_amount: u64) -> Box<dyn UnifiedEvent> {
+    Box::new(PumpFunTradeEvent {
+        user,
+        mint,
+        sol_amount,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_chain_tags_and_filters_events() {
+    let (dev, bot, mint) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let registry = Arc::new(DevAddressRegistry::new(16));
+    let chain = PostProcessorChain::empty()
+        .with(PumpFunDevTagger::new().with_registry(registry.clone()))
+        .with(DropSmallTrades);
+    assert_eq!(
+        chain.names(),
+        vec!["pumpfun_dev_tagger", "drop_small_trades"]
+    );
+
+    let mut events: Vec<Box<dyn UnifiedEvent>> = vec![
+        Box::new(PumpFunCreateTokenEvent {
+            user: dev,
+            mint,
+            ..Default::default()
+        }),
+        trade(dev, mint, 500),
+        trade(bot, mint, 200),
+        trade(Pubkey::new_unique(), mint, 10),
+    ];
+    chain.run(
+        &mut events,
+        &PostProcessContext {
+            bot_wallet: Some(bot),
+        },
+    );
+
+    assert_eq!(events.len(), 3);
+    let trades: Vec<&PumpFunTradeEvent> = events
+        .iter()
+        .filter_map(|event| event.as_any().downcast_ref::<PumpFunTradeEvent>())
+        .collect();
+    assert!(trades[0].is_dev_create_token_trade);
+    assert!(trades[1].is_bot && !trades[1].is_dev_create_token_trade);
+    assert!(registry.is_dev(&mint, &dev));
+    assert!(!DevAddressRegistry::global().is_dev(&mint, &dev));
+}
+
+#[test]
+fn test_default_chain_can_be_trimmed() {
+    let chain = PostProcessorChain::default().without(PumpFunDevTagger::NAME);
+    assert_eq!(
+        chain.names(),
+        vec!["bonk_dev_tagger", "believe_dev_tagger"]
+    );
+}
+
+#[test]
+fn test_referral_tagger_attributes_memos() {
+    use solana_sdk::instruction::CompiledInstruction;
+    use solana_streamer_sdk::streaming::event_parser::common::{extract_memos, MEMO_PROGRAM_ID};
+    use solana_streamer_sdk::streaming::event_parser::core::ReferralTagger;
+
+    let accounts = vec![Pubkey::new_unique(), MEMO_PROGRAM_ID];
+    let instructions = vec![
+        CompiledInstruction::new_from_raw_parts(0, vec![1, 2], vec![]),
+        CompiledInstruction::new_from_raw_parts(1, b"axiom:ref=abc".to_vec(), vec![]),
+    ];
+    let memos = extract_memos(&instructions, &[], &accounts);
+    assert_eq!(memos, vec!["axiom:ref=abc".to_string()]);
+
+    let mut event = trade(Pubkey::new_unique(), Pubkey::new_unique(), 100);
+    event.metadata_mut().memos = memos;
+    let mut events = vec![event, trade(Pubkey::new_unique(), Pubkey::new_unique(), 100)];
+    let tagger = ReferralTagger::new()
+        .with_prefix("photon", "Photon")
+        .with_prefix("axiom:", "Axiom");
+    PostProcessorChain::empty()
+        .with(tagger)
+        .run(&mut events, &PostProcessContext::default());
+
+    assert_eq!(events[0].metadata().referral.as_deref(), Some("Axiom"));
+    assert_eq!(events[1].metadata().referral, None);
+}
@@ -0,0 +1,32 @@
+use anyhow::Result;
+use solana_streamer_sdk::streaming::event_parser::protocols::pumpfun::parser::PUMPFUN_PROGRAM_ID;
+use solana_streamer_sdk::streaming::simulation::ProtocolScaffold;
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use std::fs;
+
+#[test]
+fn test_scaffold_infers_pumpfun_buy_and_trade_event() -> Result<()> {
+    let data = fs::read_to_string("tests/fixtures/pumpfun_cpi_tx.json")?;
+    let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(&data)?;
+    let mut scaffold = ProtocolScaffold::new("pumpfun_copy", PUMPFUN_PROGRAM_ID);
+    let calls = scaffold.add_transaction("fixture", &tx.transaction.transaction)?;
+    assert!(calls >= 2);
+
+    let instructions = scaffold.instructions();
+    let buy = instructions
+        .iter()
+        .find(|candidate| candidate.name() == "buy")
+        .expect("buy instruction");
+    assert!(!buy.accounts.is_empty());
+    assert!(buy.accounts.iter().any(|role| role.signer));
+
+    let events = scaffold.events();
+    assert!(events.iter().any(|candidate| candidate.name() == "trade"));
+
+    let files = scaffold.render();
+    assert!(files
+        .parser_rs
+        .contains("instruction_discriminator(\"buy\")"));
+    assert!(files.events_rs.contains("pub struct PumpfunCopyBuyEvent"));
+    Ok(())
+}
@@ -1,39 +1,8 @@
 use anyhow::Result;
-use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::{commitment_config::CommitmentConfig, signature::Signature};
-use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use solana_streamer_sdk::streaming::fixtures::capture_at;
 use std::fs;
 use std::path::Path;
-use std::str::FromStr;
-
-async fn fetch_transaction_fixture(
-    signature: &str,
-    fixture_path: &Path,
-) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
-    if fixture_path.exists() {
-        let data = fs::read_to_string(fixture_path)?;
-        return Ok(serde_json::from_str(&data)?);
-    }
-
-    let client = RpcClient::new_with_commitment(
-        "https://api.mainnet-beta.solana.com".to_string(),
-        CommitmentConfig::confirmed(),
-    );
-
-    let tx = client
-        .get_transaction_with_config(
-            &Signature::from_str(signature)?,
-            solana_client::rpc_config::RpcTransactionConfig {
-                encoding: Some(UiTransactionEncoding::Base64),
-                commitment: Some(CommitmentConfig::confirmed()),
-                max_supported_transaction_version: Some(0),
-            },
-        )
-        .await?;
-
-    fs::write(fixture_path, serde_json::to_string_pretty(&tx)?)?;
-    Ok(tx)
-}
 
 #[tokio::test]
 async fn test_pumpfun_cpi_transaction_parsing() -> Result<()> {
@@ -45,7 +14,7 @@ async fn test_pumpfun_cpi_transaction_parsing() -> Result<()> {
     // Transaction with PumpFun called via CPI (inner instruction)
     let signature = "3QLV6eN1W1pCWvS8pFYK4x7DCbQNsv16zNmjdpFvqbnzYCnMYEEfVWDsewmLJF1MByFBncaTcHXbqSpQhYHzqGEo";
     let fixture_path = Path::new("tests/fixtures/pumpfun_cpi_tx.json");
-    let tx = fetch_transaction_fixture(signature, fixture_path).await?;
+    let tx = capture_at(signature, fixture_path).await?;
 
     let parser = EventParserFactory::create_parser(Protocol::PumpFun);
     let encoded_tx = EncodedTransactionWithStatusMeta {
@@ -123,7 +92,7 @@ async fn test_pumpfun_direct_transaction_parsing() -> Result<()> {
     let signature = "2ghHZXwyU6K1Q8KMJbLJg37ktmyctKmdzzZKGDvHk1MR865dDYyo8SfrKvmvijT43P6hdu6ozPtATiMeg2STszhc";
     let fixture_path = Path::new("tests/fixtures/pumpfun_direct_tx.json");
     
-    let tx = fetch_transaction_fixture(signature, fixture_path).await?;
+    let tx = capture_at(signature, fixture_path).await?;
     let parser = EventParserFactory::create_parser(Protocol::PumpFun);
     let encoded_tx = EncodedTransactionWithStatusMeta {
         transaction: tx.transaction.transaction.clone(),
@@ -187,4 +156,653 @@ async fn test_pumpfun_direct_transaction_parsing() -> Result<()> {
     assert_eq!(trade_event.is_dev_create_token_trade, false);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_pruned_transaction_parses_without_panicking() -> Result<()> {
+    use solana_streamer_sdk::streaming::event_parser::{
+        common::ParseOptions, EventParserFactory, Protocol,
+    };
+    use solana_transaction_status::{
+        option_serializer::OptionSerializer, EncodedTransactionWithStatusMeta,
+    };
+
+    let data = fs::read_to_string("tests/fixtures/pumpfun_cpi_tx.json")?;
+    let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(&data)?;
+    let mut meta = tx.transaction.meta.clone().expect("fixture has meta");
+    // Simulate a pruned RPC response
+    meta.inner_instructions = OptionSerializer::None;
+    meta.loaded_addresses = OptionSerializer::Skip;
+
+    let parser = EventParserFactory::create_parser(Protocol::PumpFun);
+    let output = parser
+        .parse_transaction_with_errors(
+            EncodedTransactionWithStatusMeta {
+                transaction: tx.transaction.transaction.clone(),
+                meta: Some(meta),
+                version: tx.transaction.version,
+            },
+            "pruned",
+            Some(tx.slot),
+            None,
+            0,
+            None,
+            ParseOptions::default(),
+        )
+        .await?;
+    // PumpFun is only invoked through CPI, so without inner instructions there's no
+    // trade instruction for the logged TradeEvent to merge into and nothing is emitted
+    assert!(output.events.is_empty(), "unexpected events: {:?}", output.events);
+    // The outer instructions still decode, the missing data isn't an error
+    assert!(output.errors.is_empty(), "unexpected errors: {:?}", output.errors);
+
+    // Missing meta is a whole-transaction error rather than a panic
+    let missing = parser
+        .parse_transaction_with_errors(
+            EncodedTransactionWithStatusMeta {
+                transaction: tx.transaction.transaction.clone(),
+                meta: None,
+                version: tx.transaction.version,
+            },
+            "pruned",
+            Some(tx.slot),
+            None,
+            0,
+            None,
+            ParseOptions::default(),
+        )
+        .await;
+    assert!(missing.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_include_raw_attaches_originating_instruction() -> Result<()> {
+    use solana_streamer_sdk::streaming::event_parser::{
+        common::ParseOptions, protocols::pumpfun::parser::PUMPFUN_PROGRAM_ID,
+        EventParserFactory, Protocol,
+    };
+    use solana_transaction_status::EncodedTransactionWithStatusMeta;
+
+    let data = fs::read_to_string("tests/fixtures/pumpfun_cpi_tx.json")?;
+    let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(&data)?;
+    let parser = EventParserFactory::create_parser(Protocol::PumpFun);
+    let output = parser
+        .parse_transaction_with_errors(
+            EncodedTransactionWithStatusMeta {
+                transaction: tx.transaction.transaction.clone(),
+                meta: tx.transaction.meta.clone(),
+                version: tx.transaction.version,
+            },
+            "raw",
+            Some(tx.slot),
+            None,
+            0,
+            None,
+            ParseOptions::new().with_include_raw(true),
+        )
+        .await?;
+    assert!(!output.events.is_empty(), "Should find at least one event");
+    for event in &output.events {
+        let raw = event.metadata().raw.as_ref().expect("raw instruction attached");
+        assert_eq!(raw.program_id, PUMPFUN_PROGRAM_ID);
+        assert!(!raw.data.is_empty());
+        assert!(!raw.accounts.is_empty());
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_metadata_carries_fee_and_signers() -> Result<()> {
+    use solana_streamer_sdk::streaming::event_parser::{EventParserFactory, Protocol};
+
+    let data = fs::read_to_string("tests/fixtures/pumpfun_cpi_tx.json")?;
+    let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(&data)?;
+    let fee_payer = tx.transaction.transaction.decode().unwrap().message.static_account_keys()[0];
+    let parser = EventParserFactory::create_parser(Protocol::PumpFun);
+    let events = parser
+        .parse_transaction(tx.transaction, "fee", Some(tx.slot), None, 0, None)
+        .await?;
+    assert!(!events.is_empty(), "Should find at least one event");
+    for event in &events {
+        let metadata = event.metadata();
+        assert_eq!(metadata.fee, 22500);
+        assert_eq!(metadata.fee_payer(), Some(fee_payer));
+        assert_eq!(metadata.tx_index, None);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_event_ids_are_anchored_to_the_instruction() -> Result<()> {
+    use solana_streamer_sdk::streaming::event_parser::{
+        common::{event_id, EventType, InstructionIndex},
+        protocols::pumpfun::PumpFunTradeEvent,
+        EventParserFactory, Protocol,
+    };
+    use solana_transaction_status::option_serializer::OptionSerializer;
+
+    let data = fs::read_to_string("tests/fixtures/pumpfun_cpi_tx.json")?;
+    let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(&data)?;
+    let parser = EventParserFactory::create_parser(Protocol::PumpFun);
+    let events = parser
+        .parse_transaction(tx.transaction.clone(), "ids", Some(tx.slot), None, 0, None)
+        .await?;
+    let trade = events
+        .iter()
+        .find_map(|e| e.as_any().downcast_ref::<PumpFunTradeEvent>())
+        .expect("Should find a PumpFun trade event");
+    assert_eq!(trade.metadata.index, InstructionIndex::inner(2, 6));
+    assert_eq!(
+        trade.metadata.id,
+        event_id("ids", &InstructionIndex::inner(2, 6), &EventType::PumpFunBuy)
+    );
+    assert_eq!(trade.virtual_sol_reserves, 41133990957);
+
+    // Without the self-CPI the event from the logs still merges by ID
+    let mut log_only = tx.transaction.clone();
+    if let Some(OptionSerializer::Some(inner)) =
+        log_only.meta.as_mut().map(|meta| &mut meta.inner_instructions)
+    {
+        for group in inner.iter_mut() {
+            group.instructions.truncate(11);
+        }
+    }
+    let events = parser
+        .parse_transaction(log_only, "ids", Some(tx.slot), None, 0, None)
+        .await?;
+    let merged = events
+        .iter()
+        .find_map(|e| e.as_any().downcast_ref::<PumpFunTradeEvent>())
+        .expect("Should find a PumpFun trade event");
+    assert_eq!(merged.metadata.id, trade.metadata.id);
+    assert_eq!(merged.virtual_sol_reserves, 41133990957);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_decoded_instructions_use_idl_names() -> Result<()> {
+    use solana_streamer_sdk::streaming::event_parser::{
+        common::{InstructionIndex, ParseOptions},
+        DecodedInstructionEvent, EventParserFactory, Protocol,
+    };
+
+    let data = fs::read_to_string("tests/fixtures/pumpfun_cpi_tx.json")?;
+    let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(&data)?;
+    let parser = EventParserFactory::create_parser(Protocol::PumpFun);
+    let output = parser
+        .parse_transaction_with_errors(
+            tx.transaction.clone(),
+            "decoded",
+            Some(tx.slot),
+            None,
+            0,
+            None,
+            ParseOptions::new().with_decoded_instructions(true),
+        )
+        .await?;
+    let buy = output
+        .events
+        .iter()
+        .find_map(|e| e.as_any().downcast_ref::<DecodedInstructionEvent>())
+        .expect("Should find a decoded instruction");
+    assert_eq!(buy.name, "buy");
+    assert_eq!(buy.metadata.index, InstructionIndex::inner(2, 6));
+    assert_eq!(buy.args["amount"], 1864792795u64);
+    assert_eq!(buy.args["max_sol_cost"], 99979u64);
+    assert_eq!(
+        buy.account("user").map(|user| user.to_string()).as_deref(),
+        Some("DRUujjQPsCqNFnaqY1c6FaSbDsS6BwLqm7hGPUMaJPF6")
+    );
+
+    // Off by default
+    let events = parser
+        .parse_transaction(tx.transaction.clone(), "decoded", Some(tx.slot), None, 0, None)
+        .await?;
+    assert!(events
+        .iter()
+        .all(|e| e.as_any().downcast_ref::<DecodedInstructionEvent>().is_none()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_clock_sets_parse_timestamps() -> Result<()> {
+    use solana_streamer_sdk::streaming::event_parser::{
+        common::{ClockHandle, MockClock, ParseOptions},
+        EventParserFactory, Protocol,
+    };
+    use solana_transaction_status::EncodedTransactionWithStatusMeta;
+
+    let data = fs::read_to_string("tests/fixtures/pumpfun_cpi_tx.json")?;
+    let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(&data)?;
+    let clock = MockClock::new(1_700_000_000_250);
+    let parser = EventParserFactory::create_parser(Protocol::PumpFun);
+    let output = parser
+        .parse_transaction_with_errors(
+            EncodedTransactionWithStatusMeta {
+                transaction: tx.transaction.transaction.clone(),
+                meta: tx.transaction.meta.clone(),
+                version: tx.transaction.version,
+            },
+            "clock",
+            Some(tx.slot),
+            None,
+            1_700_000_000_000,
+            None,
+            ParseOptions::new().with_clock(ClockHandle::new(clock.clone())),
+        )
+        .await?;
+    assert!(!output.events.is_empty(), "Should find at least one event");
+    for event in &output.events {
+        let metadata = event.metadata();
+        assert_eq!(metadata.parse_start_time_ms, 1_700_000_000_000);
+        assert_eq!(metadata.parse_end_time_ms, 1_700_000_000_250);
+        assert_eq!(metadata.program_handle_time_consuming_ms, 250);
+    }
+
+    clock.advance(50);
+    assert_eq!(ClockHandle::new(clock).now_ms(), 1_700_000_000_300);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unknown_instructions_are_captured() -> Result<()> {
+    use solana_streamer_sdk::streaming::event_parser::{
+        common::{InstructionIndex, ParseOptions, ProtocolType},
+        protocols::pumpfun::parser::PUMPFUN_PROGRAM_ID,
+        EventParserFactory, Protocol, UnknownProgramEvent,
+    };
+
+    // 将CPI调用的buy指令改写为未配置的鉴别器
+    let mut json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string("tests/fixtures/pumpfun_cpi_tx.json")?)?;
+    let buy = &mut json["meta"]["innerInstructions"]
+        .as_array_mut()
+        .unwrap()
+        .iter_mut()
+        .find(|group| group["index"] == 2)
+        .unwrap()["instructions"][6]["data"];
+    let mut data = bs58::decode(buy.as_str().unwrap()).into_vec()?;
+    data[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    *buy = bs58::encode(&data).into_string().into();
+    let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_value(json)?;
+
+    let parser = EventParserFactory::create_parser(Protocol::PumpFun);
+    let output = parser
+        .parse_transaction_with_errors(
+            tx.transaction.clone(),
+            "unknown",
+            Some(tx.slot),
+            None,
+            0,
+            None,
+            ParseOptions::new().with_unknown_instructions(true),
+        )
+        .await?;
+    let unknown: Vec<&UnknownProgramEvent> = output
+        .events
+        .iter()
+        .filter_map(|e| e.as_any().downcast_ref::<UnknownProgramEvent>())
+        .collect();
+    // 已配置的交易事件自调用不算未知指令
+    assert_eq!(unknown.len(), 1);
+    assert_eq!(unknown[0].discriminator, [1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(unknown[0].discriminator_hex(), "0102030405060708");
+    assert!(!unknown[0].is_event());
+    assert_eq!(unknown[0].instruction.data, data);
+    assert_eq!(unknown[0].instruction.program_id, PUMPFUN_PROGRAM_ID);
+    assert_eq!(unknown[0].metadata.index, InstructionIndex::inner(2, 6));
+    assert_eq!(unknown[0].metadata.protocol, ProtocolType::PumpFun);
+
+    // Off by default
+    let events = parser
+        .parse_transaction(tx.transaction, "unknown", Some(tx.slot), None, 0, None)
+        .await?;
+    assert!(events
+        .iter()
+        .all(|e| e.as_any().downcast_ref::<UnknownProgramEvent>().is_none()));
+    Ok(())
+}
+
+#[test]
+fn test_admin_instructions_parse() {
+    use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+    use solana_streamer_sdk::streaming::event_parser::{
+        common::InstructionIndex,
+        protocols::pumpfun::{
+            discriminators, parser::PUMPFUN_PROGRAM_ID, PumpFunCollectCreatorFeeEvent,
+            PumpFunEventParser, PumpFunSetParamsEvent,
+        },
+        EventParser,
+    };
+    use solana_transaction_status::UiCompiledInstruction;
+
+    let parser = PumpFunEventParser::new();
+    let mut accounts = vec![PUMPFUN_PROGRAM_ID];
+    accounts.extend((0..7).map(|_| Pubkey::new_unique()));
+    let set_params = |args: Vec<u8>| {
+        let instruction = CompiledInstruction {
+            program_id_index: 0,
+            accounts: (1..=7).collect(),
+            data: [discriminators::SET_PARAMS_IX, &args[..]].concat(),
+        };
+        let events = parser.parse_events_from_instruction(
+            &instruction,
+            &accounts,
+            "admin",
+            1,
+            None,
+            0,
+            InstructionIndex::outer(0),
+        );
+        events
+            .iter()
+            .find_map(|e| e.as_any().downcast_ref::<PumpFunSetParamsEvent>())
+            .cloned()
+    };
+
+    // 当前布局由IDL生成，见test_admin_instructions_match_the_idl；原始布局以fee_recipient开头
+    let fee_recipient = Pubkey::new_unique();
+    let v1 = borsh::to_vec(&(fee_recipient, 1u64, 2u64, 3u64, 4u64, 100u64)).unwrap();
+    let event = set_params(v1).expect("original layout should decode");
+    assert_eq!(event.fee_recipient, fee_recipient);
+    assert_eq!(event.initial_virtual_token_reserves, 1);
+    assert_eq!(event.fee_basis_points, 100);
+    assert_eq!(event.creator_fee_basis_points, 0);
+
+    let creator = Pubkey::new_unique();
+    let event_data = [
+        hex::decode(&discriminators::COLLECT_CREATOR_FEE_EVENT[2..]).unwrap(),
+        borsh::to_vec(&(1_750_000_000i64, creator, 42_000u64)).unwrap(),
+    ]
+    .concat();
+    let events = parser.parse_events_from_inner_instruction(
+        &UiCompiledInstruction {
+            program_id_index: 0,
+            accounts: vec![],
+            data: bs58::encode(event_data).into_string(),
+            stack_height: Some(2),
+        },
+        "admin",
+        1,
+        None,
+        0,
+        InstructionIndex::inner(0, 1),
+    );
+    let collect = events
+        .iter()
+        .find_map(|e| e.as_any().downcast_ref::<PumpFunCollectCreatorFeeEvent>())
+        .expect("Should find a creator fee collection");
+    assert_eq!(collect.creator, creator);
+    assert_eq!(collect.creator_fee, 42_000);
+}
+
+#[tokio::test]
+async fn test_admin_instructions_match_the_idl() -> Result<()> {
+    use serde_json::Value;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_streamer_sdk::streaming::event_parser::{
+        common::ParseOptions,
+        protocols::pumpfun::{
+            parser::PUMPFUN_PROGRAM_ID, PumpFunSetCreatorEvent, PumpFunSetParamsEvent,
+            PumpFunWithdrawEvent,
+        },
+        DecodedInstructionEvent, EventParserFactory, Protocol, UnifiedEvent,
+    };
+    use solana_streamer_sdk::streaming::simulation::{FixtureGenerator, Idl};
+
+    // 指令数据按打包的IDL随机生成，再由IDL解码器按参数名解码，与解析器的偏移量无关
+    async fn parse(idl: &Idl, instruction: &str, seed: u64) -> Result<Vec<Box<dyn UnifiedEvent>>> {
+        let simulated = FixtureGenerator::new(idl.clone(), PUMPFUN_PROGRAM_ID)
+            .with_seed(seed)
+            .transaction(instruction, &[])?;
+        let output = EventParserFactory::create_parser(Protocol::PumpFun)
+            .parse_transaction_with_errors(
+                simulated.tx,
+                instruction,
+                Some(simulated.slot),
+                None,
+                0,
+                None,
+                ParseOptions::new().with_decoded_instructions(true),
+            )
+            .await?;
+        assert!(output.errors.is_empty(), "{:?}", output.errors);
+        Ok(output.events)
+    }
+
+    fn decoded(events: &[Box<dyn UnifiedEvent>]) -> &DecodedInstructionEvent {
+        events
+            .iter()
+            .find_map(|e| e.as_any().downcast_ref::<DecodedInstructionEvent>())
+            .expect("the instruction decodes with the IDL")
+    }
+
+    let idl = Idl::from_json(&fs::read_to_string(
+        "src/streaming/event_parser/protocols/pumpfun/idl.json",
+    )?)?;
+    let key = |pubkey: &Pubkey| Value::from(pubkey.to_string());
+
+    for seed in [1, 2, 3] {
+        let events = parse(&idl, "set_params", seed).await?;
+        let args = &decoded(&events).args;
+        let event = events
+            .iter()
+            .find_map(|e| e.as_any().downcast_ref::<PumpFunSetParamsEvent>())
+            .expect("set_params parses");
+        assert_eq!(Some(event.global), decoded(&events).account("global"));
+        assert_eq!(Some(event.authority), decoded(&events).account("authority"));
+        assert_eq!(
+            args["initial_virtual_token_reserves"],
+            event.initial_virtual_token_reserves
+        );
+        assert_eq!(
+            args["initial_virtual_sol_reserves"],
+            event.initial_virtual_sol_reserves
+        );
+        assert_eq!(
+            args["initial_real_token_reserves"],
+            event.initial_real_token_reserves
+        );
+        assert_eq!(args["token_total_supply"], event.token_total_supply);
+        assert_eq!(args["fee_basis_points"], event.fee_basis_points);
+        assert_eq!(args["withdraw_authority"], key(&event.withdraw_authority));
+        assert_eq!(args["enable_migrate"], event.enable_migrate);
+        assert_eq!(args["pool_migration_fee"], event.pool_migration_fee);
+        assert_eq!(
+            args["creator_fee_basis_points"],
+            event.creator_fee_basis_points
+        );
+        assert_eq!(event.fee_recipients.len(), 7);
+        assert_eq!(
+            args["fee_recipients"],
+            Value::from(event.fee_recipients.iter().map(key).collect::<Vec<_>>())
+        );
+        assert_eq!(
+            args["set_creator_authority"],
+            key(&event.set_creator_authority)
+        );
+    }
+
+    let events = parse(&idl, "set_creator", 4).await?;
+    let ix = decoded(&events);
+    let event = events
+        .iter()
+        .find_map(|e| e.as_any().downcast_ref::<PumpFunSetCreatorEvent>())
+        .expect("set_creator parses");
+    assert_eq!(ix.args["creator"], key(&event.creator));
+    assert_eq!(
+        Some(event.set_creator_authority),
+        ix.account("set_creator_authority")
+    );
+    assert_eq!(Some(event.mint), ix.account("mint"));
+    assert_eq!(Some(event.bonding_curve), ix.account("bonding_curve"));
+
+    let events = parse(&idl, "withdraw", 5).await?;
+    let ix = decoded(&events);
+    let event = events
+        .iter()
+        .find_map(|e| e.as_any().downcast_ref::<PumpFunWithdrawEvent>())
+        .expect("withdraw parses");
+    assert_eq!(Some(event.global), ix.account("global"));
+    assert_eq!(Some(event.mint), ix.account("mint"));
+    assert_eq!(Some(event.bonding_curve), ix.account("bonding_curve"));
+    assert_eq!(
+        Some(event.associated_bonding_curve),
+        ix.account("associated_bonding_curve")
+    );
+    assert_eq!(Some(event.user), ix.account("user"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_versioned_transaction_parsing_applies_parse_options() -> Result<()> {
+    use solana_streamer_sdk::streaming::event_parser::{
+        common::{FailedTxPolicy, ParseOptions},
+        core::{EventPostProcessor, PostProcessContext, PostProcessorChain},
+        EventParserFactory, Protocol, UnifiedEvent,
+    };
+
+    struct DropAll;
+
+    impl EventPostProcessor for DropAll {
+        fn name(&self) -> &str {
+            "drop_all"
+        }
+
+        fn process(&self, events: &mut Vec<Box<dyn UnifiedEvent>>, _context: &PostProcessContext) {
+            events.clear();
+        }
+    }
+
+    // The ShredStream path only has the transaction itself
+    let data = fs::read_to_string("tests/fixtures/pumpfun_direct_tx.json")?;
+    let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(&data)?;
+    let versioned_tx = tx.transaction.transaction.decode().unwrap();
+    let accounts = versioned_tx.message.static_account_keys().to_vec();
+    let parser = EventParserFactory::create_parser(Protocol::PumpFun);
+    let parse = |options: ParseOptions| {
+        parser.parse_versioned_transaction_with_accounts(
+            &versioned_tx,
+            &accounts,
+            "shred",
+            Some(tx.slot),
+            None,
+            0,
+            None,
+            options,
+        )
+    };
+
+    let output = parse(ParseOptions::new().with_include_raw(true)).await?;
+    assert!(!output.events.is_empty(), "Should find at least one event");
+    for event in &output.events {
+        assert!(event.metadata().raw.is_some());
+        assert!(!event.metadata().transaction_failed);
+    }
+    assert!(parse(ParseOptions::default()).await?.events[0]
+        .metadata()
+        .raw
+        .is_none());
+
+    let drop_all =
+        ParseOptions::new().with_post_processors(PostProcessorChain::empty().with(DropAll));
+    assert!(parse(drop_all).await?.events.is_empty());
+
+    // Without a status the transaction counts as successful
+    let only_failed = ParseOptions::new().with_failed_tx_policy(FailedTxPolicy::OnlyFailed);
+    assert!(parse(only_failed).await?.events.is_empty());
+    Ok(())
+}
+
+/// The direct buy fixture marked as failed by its third instruction
+fn failed_direct_tx() -> Result<EncodedConfirmedTransactionWithStatusMeta> {
+    let data = fs::read_to_string("tests/fixtures/pumpfun_direct_tx.json")?;
+    let mut value: serde_json::Value = serde_json::from_str(&data)?;
+    let err = serde_json::json!({ "InstructionError": [2, { "Custom": 6002 }] });
+    value["meta"]["err"] = err.clone();
+    value["meta"]["status"] = serde_json::json!({ "Err": err });
+    Ok(serde_json::from_value(value)?)
+}
+
+async fn parse_with_policy(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+    policy: solana_streamer_sdk::streaming::event_parser::common::FailedTxPolicy,
+) -> Result<Vec<Box<dyn solana_streamer_sdk::streaming::event_parser::UnifiedEvent>>> {
+    use solana_streamer_sdk::streaming::event_parser::{
+        common::ParseOptions, EventParserFactory, Protocol,
+    };
+    use solana_transaction_status::EncodedTransactionWithStatusMeta;
+
+    let parser = EventParserFactory::create_parser(Protocol::PumpFun);
+    let output = parser
+        .parse_transaction_with_errors(
+            EncodedTransactionWithStatusMeta {
+                transaction: tx.transaction.transaction.clone(),
+                meta: tx.transaction.meta.clone(),
+                version: tx.transaction.version,
+            },
+            "failed",
+            Some(tx.slot),
+            None,
+            0,
+            None,
+            ParseOptions::new().with_failed_tx_policy(policy),
+        )
+        .await?;
+    Ok(output.events)
+}
+
+#[tokio::test]
+async fn test_failed_transaction_skipped_by_default() -> Result<()> {
+    use solana_streamer_sdk::streaming::event_parser::common::FailedTxPolicy;
+
+    assert_eq!(FailedTxPolicy::default(), FailedTxPolicy::Skip);
+    let events = parse_with_policy(&failed_direct_tx()?, FailedTxPolicy::Skip).await?;
+    assert!(events.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_transaction_parsed_with_flag() -> Result<()> {
+    use solana_streamer_sdk::streaming::event_parser::{
+        common::FailedTxPolicy, protocols::pumpfun::PumpFunTradeEvent,
+    };
+
+    let events = parse_with_policy(&failed_direct_tx()?, FailedTxPolicy::ParseWithFlag).await?;
+    assert!(events
+        .iter()
+        .any(|e| e.as_any().downcast_ref::<PumpFunTradeEvent>().is_some()));
+    for event in &events {
+        let metadata = event.metadata();
+        assert!(metadata.transaction_failed);
+        assert!(metadata
+            .transaction_error
+            .as_deref()
+            .is_some_and(|error| error.contains("custom program error")));
+    }
+
+    // Successful transactions are parsed too, without the flag
+    let data = fs::read_to_string("tests/fixtures/pumpfun_direct_tx.json")?;
+    let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(&data)?;
+    let events = parse_with_policy(&tx, FailedTxPolicy::ParseWithFlag).await?;
+    assert!(!events.is_empty());
+    assert!(events.iter().all(|e| !e.metadata().transaction_failed));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_only_failed_transactions_parsed() -> Result<()> {
+    use solana_streamer_sdk::streaming::event_parser::common::FailedTxPolicy;
+
+    let events = parse_with_policy(&failed_direct_tx()?, FailedTxPolicy::OnlyFailed).await?;
+    assert!(!events.is_empty());
+    assert!(events.iter().all(|e| e.metadata().transaction_failed));
+
+    let data = fs::read_to_string("tests/fixtures/pumpfun_direct_tx.json")?;
+    let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(&data)?;
+    assert!(parse_with_policy(&tx, FailedTxPolicy::OnlyFailed)
+        .await?
+        .is_empty());
+    Ok(())
+}
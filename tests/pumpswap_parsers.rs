@@ -1,39 +1,6 @@
 use anyhow::Result;
-use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::{commitment_config::CommitmentConfig, signature::Signature};
-use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
-use std::fs;
+use solana_streamer_sdk::streaming::fixtures::capture_at;
 use std::path::Path;
-use std::str::FromStr;
-
-async fn fetch_transaction_fixture(
-    signature: &str,
-    fixture_path: &Path,
-) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
-    if fixture_path.exists() {
-        let data = fs::read_to_string(fixture_path)?;
-        return Ok(serde_json::from_str(&data)?);
-    }
-
-    let client = RpcClient::new_with_commitment(
-        "https://api.mainnet-beta.solana.com".to_string(),
-        CommitmentConfig::confirmed(),
-    );
-
-    let tx = client
-        .get_transaction_with_config(
-            &Signature::from_str(signature)?,
-            solana_client::rpc_config::RpcTransactionConfig {
-                encoding: Some(UiTransactionEncoding::Base64),
-                commitment: Some(CommitmentConfig::confirmed()),
-                max_supported_transaction_version: Some(0),
-            },
-        )
-        .await?;
-
-    fs::write(fixture_path, serde_json::to_string_pretty(&tx)?)?;
-    Ok(tx)
-}
 
 #[tokio::test]
 async fn test_pumpswap_buy_cpi_transaction_parsing() -> Result<()> {
@@ -45,7 +12,7 @@ async fn test_pumpswap_buy_cpi_transaction_parsing() -> Result<()> {
     // Transaction with PumpSwap called via CPI (currently failing to parse correctly)
     let signature = "56RbkzmAEtd88ZeiBigh41kPThpoFqZoxj9tULQJe7xRBAcdRYxREuNBRUW5f2jJASZ81aNhxe8EBej258q76AuH";
     let fixture_path = Path::new("tests/fixtures/pumpswap_cpi_tx.json");
-    let tx = fetch_transaction_fixture(signature, fixture_path).await?;
+    let tx = capture_at(signature, fixture_path).await?;
 
     let parser = EventParserFactory::create_parser(Protocol::PumpSwap);
     let encoded_tx = EncodedTransactionWithStatusMeta {
@@ -111,6 +78,33 @@ async fn test_pumpswap_buy_cpi_transaction_parsing() -> Result<()> {
     assert_eq!(buy_event.coin_creator_vault_ata.to_string(), "DGGpxm8H8Bj5Dc1wZaLzPPVuiXsJTHoxvP5iR8tje1BK");
     assert_eq!(buy_event.coin_creator_vault_authority.to_string(), "CUdmpcWRWqE6Q3wkGeFXrQ4WyukBzhiuVeDTNV5sMknJ");
 
+    // Called by the HuTk router, itself called by the outer BN111 program
+    let callers: Vec<String> = buy_event
+        .metadata
+        .caller_program_ids
+        .iter()
+        .map(|program_id| program_id.to_string())
+        .collect();
+    assert_eq!(
+        callers,
+        [
+            "BN111JnbLtbmQqqiCh7h2pDKhAhMx4wi77Mj7jJFbyp8",
+            "HuTkmnrv4zPnArMqpbMbFhfwzTR7xfWQZHH1aQKzDKFZ"
+        ]
+    );
+
+    // The token balance changes agree with the decoded amounts
+    use solana_streamer_sdk::streaming::event_parser::common::net_token_change;
+    let changes = &buy_event.metadata.token_balance_changes;
+    assert_eq!(
+        net_token_change(changes, &buy_event.pool, &buy_event.base_mint),
+        -(buy_event.base_amount_out as i128)
+    );
+    assert_eq!(
+        net_token_change(changes, &buy_event.pool, &buy_event.quote_mint),
+        buy_event.quote_amount_in_with_lp_fee as i128
+    );
+
     Ok(())
 }
 
@@ -124,7 +118,7 @@ async fn test_pumpswap_sell_cpi_transaction_parsing() -> Result<()> {
     // Transaction with PumpSwap sell called via CPI
     let signature = "27f6P1sV4sDJqvgbto5yyqRVn86pcDW1sqfMMSP2p3yNdFM27aBqr4Sn6ZDvkhAG12Dxm4Ehy1T8xDFQ3GywMAxA";
     let fixture_path = Path::new("tests/fixtures/pumpswap_sell_cpi_tx.json");
-    let tx = fetch_transaction_fixture(signature, fixture_path).await?;
+    let tx = capture_at(signature, fixture_path).await?;
 
     let parser = EventParserFactory::create_parser(Protocol::PumpSwap);
     let encoded_tx = EncodedTransactionWithStatusMeta {
@@ -191,4 +185,103 @@ async fn test_pumpswap_sell_cpi_transaction_parsing() -> Result<()> {
     assert_eq!(sell_event.coin_creator_vault_authority.to_string(), "Ex512LiimjSvh5ex1ypMyL19wzvKVrr4Dbzg4Ai5vaW9");
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_admin_events_parse() {
+    use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+    use solana_streamer_sdk::streaming::event_parser::{
+        common::InstructionIndex,
+        protocols::pumpswap::{
+            discriminators, parser::PUMPSWAP_PROGRAM_ID, PumpSwapEventParser,
+            PumpSwapSetCoinCreatorEvent, PumpSwapUpdateFeeConfigEvent,
+        },
+        EventParser,
+    };
+    use solana_transaction_status::UiCompiledInstruction;
+
+    let parser = PumpSwapEventParser::new();
+    let mut accounts = vec![PUMPSWAP_PROGRAM_ID];
+    accounts.extend((0..5).map(|_| Pubkey::new_unique()));
+    let recipients: [Pubkey; 8] = std::array::from_fn(|_| Pubkey::new_unique());
+    let authority = Pubkey::new_unique();
+
+    let args = borsh::to_vec(&(20u64, 5u64, recipients, 5u64, authority)).unwrap();
+    let instruction = CompiledInstruction {
+        program_id_index: 0,
+        accounts: (1..=5).collect(),
+        data: [discriminators::UPDATE_FEE_CONFIG_IX, &args[..]].concat(),
+    };
+    let events = parser.parse_events_from_instruction(
+        &instruction,
+        &accounts,
+        "admin",
+        1,
+        None,
+        0,
+        InstructionIndex::outer(0),
+    );
+    let event = events
+        .iter()
+        .find_map(|e| e.as_any().downcast_ref::<PumpSwapUpdateFeeConfigEvent>())
+        .expect("update_fee_config should decode");
+    assert_eq!(event.admin, accounts[1]);
+    assert_eq!(event.global_config, accounts[2]);
+    assert_eq!(event.protocol_fee_recipients, recipients);
+    assert_eq!(event.admin_set_coin_creator_authority, authority);
+    assert_eq!(event.total_fee_basis_points(), 30);
+
+    // 早于创建者手续费的事件没有末尾两个字段
+    let admin = Pubkey::new_unique();
+    let inner = |data: Vec<u8>| {
+        parser.parse_events_from_inner_instruction(
+            &UiCompiledInstruction {
+                program_id_index: 0,
+                accounts: vec![],
+                data: bs58::encode(data).into_string(),
+                stack_height: Some(2),
+            },
+            "admin",
+            1,
+            None,
+            0,
+            InstructionIndex::inner(0, 1),
+        )
+    };
+    let events = inner(
+        [
+            hex::decode(&discriminators::UPDATE_FEE_CONFIG_EVENT[2..]).unwrap(),
+            borsh::to_vec(&(1_740_000_000i64, admin, 25u64, 5u64, recipients)).unwrap(),
+        ]
+        .concat(),
+    );
+    let event = events
+        .iter()
+        .find_map(|e| e.as_any().downcast_ref::<PumpSwapUpdateFeeConfigEvent>())
+        .expect("legacy fee config event should decode");
+    assert_eq!(event.admin, admin);
+    assert_eq!(event.lp_fee_basis_points, 25);
+    assert_eq!(event.coin_creator_fee_basis_points, 0);
+
+    // 两种创建者来源的事件都解析为同一类型
+    let (mint, pool, coin_creator) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    for discriminator in [
+        discriminators::SET_BONDING_CURVE_COIN_CREATOR_EVENT,
+        discriminators::SET_METAPLEX_COIN_CREATOR_EVENT,
+    ] {
+        let source = Pubkey::new_unique();
+        let events = inner(
+            [
+                hex::decode(&discriminator[2..]).unwrap(),
+                borsh::to_vec(&(1_750_000_000i64, mint, pool, source, coin_creator)).unwrap(),
+            ]
+            .concat(),
+        );
+        let event = events
+            .iter()
+            .find_map(|e| e.as_any().downcast_ref::<PumpSwapSetCoinCreatorEvent>())
+            .expect("set_coin_creator event should decode");
+        assert_eq!((event.pool, event.source), (pool, source));
+        assert_eq!(event.coin_creator, coin_creator);
+    }
+}
@@ -0,0 +1,161 @@
+#![cfg(feature = "runtime")]
+
+use std::time::{Duration, Instant};
+
+use solana_streamer_sdk::streaming::replay::{
+    ArchivedTransaction, Breakpoint, FixtureSource, ReplayControl, ReplayEngine,
+};
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+
+/// One archived transaction per slot, signed "sig-<slot>", one second apart
+fn source(slots: &[u64]) -> FixtureSource {
+    let data = std::fs::read_to_string("tests/fixtures/pumpfun_direct_tx.json").unwrap();
+    let confirmed: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(&data).unwrap();
+    let archived = ArchivedTransaction::from_confirmed(confirmed);
+    FixtureSource::from_transactions(
+        slots
+            .iter()
+            .map(|slot| ArchivedTransaction {
+                signature: format!("sig-{}", slot),
+                slot: *slot,
+                block_time: Some(*slot as i64),
+                ..archived.clone()
+            })
+            .collect(),
+    )
+}
+
+fn engine(control: &ReplayControl) -> ReplayEngine {
+    ReplayEngine::new(vec![])
+        .as_fast_as_possible()
+        .with_control(control.clone())
+}
+
+fn at(slot: u64) -> Option<(u64, String)> {
+    Some((slot, format!("sig-{}", slot)))
+}
+
+/// Wait until the replay reaches a state, failing after a few seconds
+async fn wait_until(condition: impl Fn() -> bool) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !condition() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("replay did not reach the expected state");
+}
+
+/// Give a paused replay time to (wrongly) move on
+async fn settle() {
+    tokio::time::sleep(Duration::from_millis(50)).await;
+}
+
+#[tokio::test]
+async fn test_pause_holds_replay_until_resumed() {
+    let control = ReplayControl::new();
+    control.pause();
+    let engine = engine(&control);
+    let (stats, _) = tokio::join!(engine.run(source(&[10, 11, 12]), |_| {}), async {
+        settle().await;
+        assert!(control.is_paused());
+        assert_eq!(control.position(), None);
+        control.resume();
+    });
+    let stats = stats.unwrap();
+    assert_eq!(stats.transactions, 3);
+    assert_eq!((stats.first_slot, stats.last_slot), (Some(10), Some(12)));
+    assert_eq!(control.position(), at(12));
+}
+
+#[tokio::test]
+async fn test_slot_breakpoint_pauses_before_crossing_slot() {
+    let control = ReplayControl::new();
+    control.add_breakpoint(Breakpoint::Slot(12));
+    let engine = engine(&control);
+    let (stats, _) = tokio::join!(engine.run(source(&[10, 11, 13, 14]), |_| {}), async {
+        // Slot 12 has no transaction, so the replay stops before slot 13
+        wait_until(|| control.is_paused()).await;
+        settle().await;
+        assert_eq!(control.position(), at(11));
+
+        control.step();
+        wait_until(|| control.position() == at(13)).await;
+        settle().await;
+        // Later slots don't cross the breakpoint again
+        assert_eq!(control.position(), at(13));
+        assert!(control.is_paused());
+        control.resume();
+    });
+    assert_eq!(stats.unwrap().transactions, 4);
+    assert_eq!(control.position(), at(14));
+}
+
+#[tokio::test]
+async fn test_signature_breakpoint_and_removal() {
+    let control = ReplayControl::new();
+    control.add_breakpoint(Breakpoint::Signature("sig-11".to_string()));
+    control.add_breakpoint(Breakpoint::Signature("sig-12".to_string()));
+    control.remove_breakpoint(&Breakpoint::Signature("sig-12".to_string()));
+    let engine = engine(&control);
+    let (stats, _) = tokio::join!(engine.run(source(&[10, 11, 12]), |_| {}), async {
+        wait_until(|| control.is_paused()).await;
+        settle().await;
+        assert_eq!(control.position(), at(10));
+        control.resume();
+    });
+    assert_eq!(stats.unwrap().transactions, 3);
+    assert_eq!(control.position(), at(12));
+
+    // Cleared breakpoints don't pause a later replay
+    control.add_breakpoint(Breakpoint::Slot(0));
+    control.clear_breakpoints();
+    let stats = engine.run(source(&[20, 21]), |_| {}).await.unwrap();
+    assert_eq!(stats.transactions, 2);
+    assert!(!control.is_paused());
+}
+
+#[tokio::test]
+async fn test_single_step_advances_one_transaction_per_step() {
+    let control = ReplayControl::new();
+    control.set_single_step(true);
+    assert!(control.is_paused());
+    let engine = engine(&control);
+    let (stats, _) = tokio::join!(engine.run(source(&[10, 11, 12]), |_| {}), async {
+        settle().await;
+        assert_eq!(control.position(), None);
+        control.step();
+        wait_until(|| control.position() == at(10)).await;
+        control.step();
+        wait_until(|| control.position() == at(11)).await;
+        settle().await;
+        assert_eq!(control.position(), at(11));
+
+        control.set_single_step(false);
+        control.resume();
+    });
+    assert_eq!(stats.unwrap().transactions, 3);
+    assert_eq!(control.position(), at(12));
+}
+
+#[tokio::test]
+async fn test_speed_scales_block_time_gaps() {
+    let control = ReplayControl::new();
+    control.set_speed(Some(0.0));
+    assert_eq!(control.speed(), None);
+    control.set_speed(Some(-1.0));
+    assert_eq!(control.speed(), None);
+
+    // Two blocks one second apart at 10x take about 100ms
+    let engine = ReplayEngine::new(vec![]).with_speed(10.0);
+    assert_eq!(engine.control().speed(), Some(10.0));
+    let started = Instant::now();
+    engine.run(source(&[10, 11]), |_| {}).await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(100));
+
+    // As fast as possible skips the wait
+    let engine = engine.as_fast_as_possible();
+    let started = Instant::now();
+    engine.run(source(&[10, 11]), |_| {}).await.unwrap();
+    assert!(started.elapsed() < Duration::from_millis(100));
+}
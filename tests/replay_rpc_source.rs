@@ -0,0 +1,153 @@
+#![cfg(feature = "rpc")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde_json::{json, Value};
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_rpc_client::rpc_client::RpcClientConfig;
+use solana_rpc_client::rpc_sender::{RpcSender, RpcTransportStats};
+use solana_rpc_client_api::custom_error::JSON_RPC_SERVER_ERROR_SLOT_SKIPPED;
+use solana_rpc_client_api::request::{RpcError, RpcRequest, RpcResponseErrorData};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_streamer_sdk::common::SolanaRpcClient;
+use solana_streamer_sdk::streaming::replay::{ReplaySource, RpcBlockSource};
+
+/// getBlock served from a fixed set of blocks; other slots are skipped
+#[derive(Default)]
+struct BlockSender {
+    blocks: HashMap<u64, Value>,
+    requested: Arc<Mutex<Vec<u64>>>,
+}
+
+#[async_trait::async_trait]
+impl RpcSender for BlockSender {
+    async fn send(
+        &self,
+        request: RpcRequest,
+        params: Value,
+    ) -> solana_rpc_client_api::client_error::Result<Value> {
+        if request == RpcRequest::GetVersion {
+            return Ok(json!({"solana-core": "2.1.16"}));
+        }
+        assert_eq!(request, RpcRequest::GetBlock);
+        let slot = params[0].as_u64().unwrap();
+        self.requested.lock().unwrap().push(slot);
+        self.blocks.get(&slot).cloned().ok_or_else(|| {
+            ClientError::from(ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                code: JSON_RPC_SERVER_ERROR_SLOT_SKIPPED,
+                message: format!("Slot {} was skipped", slot),
+                data: RpcResponseErrorData::Empty,
+            }))
+        })
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        RpcTransportStats::default()
+    }
+
+    fn url(&self) -> String {
+        "mock".to_string()
+    }
+}
+
+/// A block with the transactions of the given fixtures
+fn block(slot: u64, block_time: i64, fixtures: &[&str]) -> Value {
+    let transactions: Vec<Value> = fixtures
+        .iter()
+        .map(|name| {
+            let data = std::fs::read_to_string(format!("tests/fixtures/{}", name)).unwrap();
+            let fixture: Value = serde_json::from_str(&data).unwrap();
+            json!({
+                "transaction": fixture["transaction"],
+                "meta": fixture["meta"],
+                "version": fixture["version"],
+            })
+        })
+        .collect();
+    json!({
+        "previousBlockhash": "11111111111111111111111111111111",
+        "blockhash": "11111111111111111111111111111111",
+        "parentSlot": slot - 1,
+        "transactions": transactions,
+        "blockTime": block_time,
+        "blockHeight": slot,
+    })
+}
+
+fn client(sender: BlockSender) -> Arc<SolanaRpcClient> {
+    Arc::new(SolanaRpcClient::new_sender(
+        sender,
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ))
+}
+
+#[tokio::test]
+async fn test_slot_range_is_inclusive_and_skips_empty_slots() {
+    let requested = Arc::new(Mutex::new(vec![]));
+    let sender = BlockSender {
+        blocks: HashMap::from([
+            (
+                10,
+                block(
+                    10,
+                    1_000,
+                    &["pumpfun_cpi_tx.json", "pumpfun_direct_tx.json"],
+                ),
+            ),
+            (12, block(12, 1_001, &["bonk_cpi_tx.json"])),
+            // Outside the range, never fetched
+            (13, block(13, 1_002, &["bonk_direct_tx.json"])),
+        ]),
+        requested: requested.clone(),
+    };
+    let mut source = RpcBlockSource::new(client(sender), 10, 12);
+    assert_eq!(source.next_slot(), 10);
+
+    let mut transactions = vec![];
+    while let Some(tx) = source.next().await.unwrap() {
+        transactions.push(tx);
+    }
+    let positions: Vec<(u64, Option<u64>, Option<i64>)> = transactions
+        .iter()
+        .map(|tx| (tx.slot, tx.tx_index, tx.block_time))
+        .collect();
+    assert_eq!(
+        positions,
+        vec![
+            (10, Some(0), Some(1_000)),
+            (10, Some(1), Some(1_000)),
+            (12, Some(0), Some(1_001)),
+        ]
+    );
+    assert!(transactions.iter().all(|tx| !tx.signature.is_empty()));
+    assert_ne!(transactions[0].signature, transactions[1].signature);
+    assert_eq!(*requested.lock().unwrap(), vec![10, 11, 12]);
+    assert_eq!(source.next_slot(), 13);
+    assert!(source.next().await.unwrap().is_none());
+    assert_eq!(requested.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_empty_range_fetches_nothing() {
+    let requested = Arc::new(Mutex::new(vec![]));
+    let sender = BlockSender {
+        blocks: HashMap::from([(10, block(10, 1_000, &["pumpfun_cpi_tx.json"]))]),
+        requested: requested.clone(),
+    };
+    let mut source = RpcBlockSource::new(client(sender), 11, 10);
+    assert!(source.next().await.unwrap().is_none());
+    assert!(requested.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_skipped_slot_has_no_transactions() {
+    let sender = BlockSender::default();
+    let requested = sender.requested.clone();
+    let client = client(sender);
+    assert!(RpcBlockSource::fetch_block(&client, 42)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(*requested.lock().unwrap(), vec![42]);
+}
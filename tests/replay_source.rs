@@ -0,0 +1,96 @@
+#![cfg(feature = "runtime")]
+
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::replay::{ArchivedTransaction, FixtureSource, ReplaySource};
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+
+fn fixture(name: &str) -> Value {
+    let data = std::fs::read_to_string(format!("tests/fixtures/{}", name)).unwrap();
+    serde_json::from_str(&data).unwrap()
+}
+
+/// A fixture moved to another slot
+fn fixture_at(name: &str, slot: u64) -> Value {
+    let mut value = fixture(name);
+    value["slot"] = slot.into();
+    value
+}
+
+async fn drain(source: &mut impl ReplaySource) -> Vec<ArchivedTransaction> {
+    let mut transactions = vec![];
+    while let Some(tx) = source.next().await.unwrap() {
+        transactions.push(tx);
+    }
+    transactions
+}
+
+#[tokio::test]
+async fn test_fixture_directory_replays_in_slot_order() {
+    let mut source = FixtureSource::load("tests/fixtures").unwrap();
+    assert_eq!(source.len(), 6);
+    let transactions = drain(&mut source).await;
+    let slots: Vec<u64> = transactions.iter().map(|tx| tx.slot).collect();
+    assert_eq!(
+        slots,
+        vec![356399841, 356410419, 356503771, 356503799, 356560168, 356561623]
+    );
+    assert!(transactions.iter().all(|tx| !tx.signature.is_empty()));
+    assert!(transactions.iter().all(|tx| tx.tx_index.is_none()));
+    assert_eq!(transactions[0].block_time, Some(1753747643));
+    assert!(source.is_empty());
+    assert!(source.next().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_json_array_and_jsonl_files_are_merged_by_slot() {
+    let dir = std::env::temp_dir().join(format!("replay-source-{}", Pubkey::new_unique()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let array = Value::Array(vec![
+        fixture_at("pumpfun_cpi_tx.json", 30),
+        fixture_at("bonk_cpi_tx.json", 10),
+    ]);
+    std::fs::write(dir.join("a.json"), array.to_string()).unwrap();
+    let lines = [
+        fixture_at("pumpswap_cpi_tx.json", 40).to_string(),
+        String::new(),
+        fixture_at("pumpfun_direct_tx.json", 20).to_string(),
+    ];
+    std::fs::write(dir.join("b.jsonl"), lines.join("\n")).unwrap();
+    std::fs::write(
+        dir.join("c.json"),
+        fixture_at("bonk_direct_tx.json", 25).to_string(),
+    )
+    .unwrap();
+    // Files of other types are ignored
+    std::fs::write(dir.join("notes.txt"), "not a fixture").unwrap();
+
+    let mut source = FixtureSource::load(&dir).unwrap();
+    let slots: Vec<u64> = drain(&mut source).await.iter().map(|tx| tx.slot).collect();
+    assert_eq!(slots, vec![10, 20, 25, 30, 40]);
+
+    // A single file loads on its own
+    let source = FixtureSource::load(dir.join("b.jsonl")).unwrap();
+    assert_eq!(source.len(), 2);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_from_transactions_sorts_by_slot() {
+    let confirmed: EncodedConfirmedTransactionWithStatusMeta =
+        serde_json::from_value(fixture("pumpfun_direct_tx.json")).unwrap();
+    let archived = ArchivedTransaction::from_confirmed(confirmed);
+    assert_eq!(archived.slot, 356410419);
+    assert_eq!(archived.block_time, Some(1753751878));
+    assert_eq!(archived.tx_index, None);
+    assert!(!archived.signature.is_empty());
+
+    let at = |slot: u64| ArchivedTransaction {
+        slot,
+        ..archived.clone()
+    };
+    let mut source = FixtureSource::from_transactions(vec![at(12), at(10), at(11)]);
+    let slots: Vec<u64> = drain(&mut source).await.iter().map(|tx| tx.slot).collect();
+    assert_eq!(slots, vec![10, 11, 12]);
+    assert!(FixtureSource::from_transactions(vec![]).is_empty());
+}
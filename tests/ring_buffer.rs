@@ -0,0 +1,75 @@
+use solana_streamer_sdk::streaming::ring_buffer;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_full_ring_hands_value_back() {
+    let (mut producer, mut consumer) = ring_buffer::<u32>(3);
+    assert_eq!(producer.capacity(), 4);
+    for i in 0..4 {
+        producer.push(i).unwrap();
+    }
+    assert_eq!(producer.push(4), Err(4));
+    assert_eq!(consumer.pop(), Some(0));
+    producer.push(4).unwrap();
+    let drained: Vec<u32> = std::iter::from_fn(|| consumer.pop()).collect();
+    assert_eq!(drained, vec![1, 2, 3, 4]);
+    assert!(producer.is_empty());
+}
+
+#[test]
+fn test_values_cross_threads_in_order() {
+    let (mut producer, mut consumer) = ring_buffer::<u64>(64);
+    let reader = std::thread::spawn(move || {
+        let mut received = vec![];
+        // 生产者关闭后仍会读完剩余的值
+        while let Some(value) = consumer.pop_wait(100) {
+            received.push(value);
+        }
+        received
+    });
+    for i in 0..100_000u64 {
+        let mut value = i;
+        while let Err(rejected) = producer.push(value) {
+            value = rejected;
+            std::hint::spin_loop();
+        }
+    }
+    drop(producer);
+    let received = reader.join().unwrap();
+    assert_eq!(received, (0..100_000).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_unread_values_are_dropped() {
+    let value = Arc::new(());
+    let (mut producer, consumer) = ring_buffer(8);
+    producer.push(value.clone()).unwrap();
+    producer.push(value.clone()).unwrap();
+    assert_eq!(Arc::strong_count(&value), 3);
+    drop(producer);
+    drop(consumer);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+/// Wait for a condition set by another thread, failing after a few seconds
+fn wait_until(condition: impl Fn() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !condition() {
+        assert!(Instant::now() < deadline, "condition not reached");
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[test]
+fn test_idle_consumer_parks_until_woken() {
+    let (mut producer, mut consumer) = ring_buffer::<u32>(8);
+    let reader = std::thread::spawn(move || (consumer.pop_wait(10), consumer.pop_wait(10)));
+    // 空闲的消费者不再占用CPU
+    wait_until(|| producer.is_consumer_parked());
+    producer.push(7).unwrap();
+
+    wait_until(|| producer.is_consumer_parked());
+    drop(producer);
+    assert_eq!(reader.join().unwrap(), (Some(7), None));
+}
@@ -0,0 +1,125 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::enrichment::{RiskAnnotator, RiskRule, TokenRiskInfo};
+use solana_streamer_sdk::streaming::event_parser::{
+    common::RiskFlags,
+    protocols::{
+        metaplex::MetaplexCreateMetadataEvent,
+        pumpfun::{PumpFunCreateTokenEvent, PumpFunTradeEvent},
+    },
+    UnifiedEvent,
+};
+use solana_streamer_sdk::streaming::pipeline::EventMiddleware;
+
+fn create(mint: Pubkey, creator: Pubkey) -> PumpFunCreateTokenEvent {
+    PumpFunCreateTokenEvent {
+        mint,
+        creator,
+        token_total_supply: 1_000_000,
+        ..Default::default()
+    }
+}
+
+fn buy(mint: Pubkey, user: Pubkey, token_amount: u64) -> PumpFunTradeEvent {
+    PumpFunTradeEvent {
+        mint,
+        user,
+        is_buy: true,
+        sol_amount: 1_000_000,
+        token_amount,
+        ..Default::default()
+    }
+}
+
+fn risk(annotator: &RiskAnnotator, event: Box<dyn UnifiedEvent>) -> RiskFlags {
+    annotator.handle(event).remove(0).metadata().risk
+}
+
+fn mint_account(mint_authority: Option<Pubkey>, freeze_authority: Option<Pubkey>) -> Vec<u8> {
+    let mut data = vec![0u8; 82];
+    if let Some(authority) = mint_authority {
+        data[0] = 1;
+        data[4..36].copy_from_slice(authority.as_ref());
+    }
+    data[36..44].copy_from_slice(&1_000_000u64.to_le_bytes());
+    data[44] = 6;
+    data[45] = 1;
+    if let Some(authority) = freeze_authority {
+        data[46] = 1;
+        data[50..82].copy_from_slice(authority.as_ref());
+    }
+    data
+}
+
+#[test]
+fn test_dev_holding_and_mutable_metadata() {
+    let annotator = RiskAnnotator::new();
+    let (mint, creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+    assert!(risk(&annotator, Box::new(create(mint, creator))).is_empty());
+    let metadata = MetaplexCreateMetadataEvent {
+        mint,
+        is_mutable: true,
+        ..Default::default()
+    };
+    annotator.handle(Box::new(metadata));
+
+    // 其他人买入不计入创建者持仓
+    let flags = risk(
+        &annotator,
+        Box::new(buy(mint, Pubkey::new_unique(), 500_000)),
+    );
+    assert_eq!(flags, RiskFlags::MUTABLE_METADATA);
+    let flags = risk(&annotator, Box::new(buy(mint, creator, 150_000)));
+    assert_eq!(flags, RiskFlags::MUTABLE_METADATA | RiskFlags::DEV_HOLDING);
+    assert_eq!(
+        annotator.token_info(&mint).unwrap().dev_holding_bps(),
+        Some(1_500)
+    );
+
+    let relaxed = RiskAnnotator::new().with_dev_holding_threshold(2_000);
+    relaxed.handle(Box::new(create(mint, creator)));
+    assert!(risk(&relaxed, Box::new(buy(mint, creator, 150_000))).is_empty());
+}
+
+#[test]
+fn test_authorities_from_mint_account() {
+    let annotator = RiskAnnotator::new();
+    let mint = Pubkey::new_unique();
+    assert!(
+        annotator.update_from_mint_account(mint, &mint_account(Some(Pubkey::new_unique()), None))
+    );
+    let flags = risk(&annotator, Box::new(buy(mint, Pubkey::new_unique(), 1)));
+    assert_eq!(flags, RiskFlags::MINT_AUTHORITY);
+
+    annotator.update_from_mint_account(mint, &mint_account(None, Some(Pubkey::new_unique())));
+    let flags = risk(&annotator, Box::new(buy(mint, Pubkey::new_unique(), 1)));
+    assert_eq!(flags, RiskFlags::FREEZE_AUTHORITY);
+
+    assert!(!annotator.update_from_mint_account(mint, &[0u8; 10]));
+}
+
+struct Blocklist(Pubkey);
+
+impl RiskRule for Blocklist {
+    fn name(&self) -> &str {
+        "blocklisted_creator"
+    }
+
+    fn flag(&self) -> RiskFlags {
+        RiskFlags::custom(0)
+    }
+
+    fn check(&self, _event: &dyn UnifiedEvent, token: &TokenRiskInfo) -> bool {
+        token.creator == Some(self.0)
+    }
+}
+
+#[test]
+fn test_custom_rule() {
+    let creator = Pubkey::new_unique();
+    let annotator = RiskAnnotator::empty().with_rule(Blocklist(creator));
+    let mint = Pubkey::new_unique();
+    let flags = risk(&annotator, Box::new(create(mint, creator)));
+    assert!(flags.contains(RiskFlags::custom(0)));
+    assert_eq!(flags.bits(), 1 << 16);
+    assert_eq!(annotator.rules(), vec!["blocklisted_creator"]);
+}
@@ -0,0 +1,41 @@
+use anyhow::Result;
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{register_router, router_of, Router, JUPITER_V6_PROGRAM_ID},
+    EventParserFactory, Protocol, UnifiedEvent,
+};
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use std::fs;
+use std::str::FromStr;
+
+async fn parse_fixture(path: &str, protocol: Protocol) -> Result<Vec<Box<dyn UnifiedEvent>>> {
+    let tx: EncodedConfirmedTransactionWithStatusMeta =
+        serde_json::from_str(&fs::read_to_string(path)?)?;
+    EventParserFactory::create_parser(protocol)
+        .parse_transaction(tx.transaction, "sig", Some(tx.slot), None, 0, None)
+        .await
+}
+
+#[tokio::test]
+async fn test_events_are_tagged_with_the_router_on_the_caller_chain() -> Result<()> {
+    assert_eq!(router_of(&JUPITER_V6_PROGRAM_ID), Some(Router::Jupiter));
+
+    // PumpSwap is reached through HuTk, which the outer BN111 program calls
+    let hutk = Pubkey::from_str("HuTkmnrv4zPnArMqpbMbFhfwzTR7xfWQZHH1aQKzDKFZ")?;
+    register_router(hutk, Router::Custom("hutk".to_string()));
+    let events = parse_fixture("tests/fixtures/pumpswap_cpi_tx.json", Protocol::PumpSwap).await?;
+    assert!(!events.is_empty());
+    for event in &events {
+        assert_eq!(
+            event.metadata().routed_via,
+            Some(Router::Custom("hutk".to_string()))
+        );
+    }
+
+    let events = parse_fixture("tests/fixtures/pumpfun_direct_tx.json", Protocol::PumpFun).await?;
+    assert!(!events.is_empty());
+    assert!(events
+        .iter()
+        .all(|event| event.metadata().routed_via.is_none()));
+    Ok(())
+}
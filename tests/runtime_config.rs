@@ -0,0 +1,44 @@
+use solana_streamer_sdk::streaming::RuntimeConfig;
+
+fn thread_name(handle: &tokio::runtime::Handle) -> String {
+    let name = handle.spawn(async { std::thread::current().name().map(str::to_string) });
+    futures::executor::block_on(name)
+        .unwrap()
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_separate_runtimes() {
+    let runtime = RuntimeConfig::new()
+        .with_io_threads(1)
+        .with_parser_threads(2)
+        .build()
+        .unwrap();
+    assert!(!runtime.is_shared());
+    assert_eq!(thread_name(&runtime.io_handle()), "solana-streamer-io");
+    assert_eq!(
+        thread_name(&runtime.parser_handle()),
+        "solana-streamer-parser"
+    );
+    assert_eq!(runtime.block_on(async { 1 + 1 }), 2);
+}
+
+#[test]
+fn test_shared_runtime() {
+    let runtime = RuntimeConfig::new()
+        .with_shared_runtime(true)
+        .build()
+        .unwrap();
+    assert!(runtime.is_shared());
+    assert_eq!(thread_name(&runtime.parser_handle()), "solana-streamer");
+}
+
+#[test]
+fn test_unknown_core_is_rejected() {
+    let error = RuntimeConfig::new()
+        .with_parser_cores(vec![usize::MAX])
+        .build()
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("does not exist"));
+}
@@ -0,0 +1,68 @@
+use std::sync::atomic::Ordering;
+
+use solana_streamer_sdk::streaming::event_parser::common::{
+    EventMetadata, EventType, ProtocolType,
+};
+use solana_streamer_sdk::streaming::event_parser::protocols::pumpfun::{
+    PumpFunCreateTokenEvent, PumpFunTradeEvent,
+};
+use solana_streamer_sdk::streaming::event_parser::UnifiedEvent;
+use solana_streamer_sdk::streaming::pipeline::{EventPipeline, SamplingStage};
+
+fn metadata(event_type: EventType, protocol: ProtocolType) -> EventMetadata {
+    EventMetadata {
+        event_type,
+        protocol,
+        ..Default::default()
+    }
+}
+
+fn trade() -> Box<dyn UnifiedEvent> {
+    Box::new(PumpFunTradeEvent {
+        metadata: metadata(EventType::PumpFunBuy, ProtocolType::PumpFun),
+        ..Default::default()
+    })
+}
+
+fn create() -> Box<dyn UnifiedEvent> {
+    Box::new(PumpFunCreateTokenEvent {
+        metadata: metadata(EventType::PumpFunCreateToken, ProtocolType::PumpFun),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_event_type_rate_overrides_protocol_rate() {
+    let sampling = SamplingStage::new()
+        .with_seed(7)
+        .with_protocol_rate(ProtocolType::PumpFun, 0.0)
+        .with_event_type_rate(EventType::PumpFunBuy, 0.1)
+        .with_event_type_rate(EventType::PumpFunCreateToken, 1.0);
+    let stats = sampling.stats();
+    let pipeline = EventPipeline::new().with_stage(sampling);
+
+    let creates: usize = (0..100).map(|_| pipeline.process(create()).len()).sum();
+    let trades: usize = (0..10_000).map(|_| pipeline.process(trade()).len()).sum();
+    assert_eq!(creates, 100);
+    assert!((800..1200).contains(&trades), "kept {} trades", trades);
+    assert_eq!(
+        stats.passed.load(Ordering::Relaxed) as usize,
+        creates + trades
+    );
+    assert_eq!(
+        stats.sampled_out.load(Ordering::Relaxed) as usize,
+        10_000 - trades
+    );
+}
+
+#[test]
+fn test_rate_limit_caps_burst() {
+    let sampling = SamplingStage::new().with_event_type_limit(EventType::PumpFunBuy, 5);
+    let kept = (0..50)
+        .filter(|_| sampling.accepts(&EventType::PumpFunBuy, &ProtocolType::PumpFun))
+        .count();
+    assert_eq!(kept, 5);
+    assert_eq!(sampling.stats().rate_limited.load(Ordering::Relaxed), 45);
+    // Other event types are not limited
+    assert!(sampling.accepts(&EventType::PumpFunSell, &ProtocolType::PumpFun));
+}
@@ -0,0 +1,53 @@
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventType, InstructionIndex},
+    protocols::sanctum::{
+        discriminators, parser::SANCTUM_SPL_MULTI_STAKE_POOL_PROGRAM_ID, SanctumStakePoolEvent,
+    },
+    EventParserFactory, Protocol,
+};
+
+#[test]
+fn test_sanctum_deposit_sol_with_slippage() {
+    let keys: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+    let mut accounts = keys.clone();
+    accounts.push(SANCTUM_SPL_MULTI_STAKE_POOL_PROGRAM_ID);
+
+    let mut data = discriminators::DEPOSIT_SOL_WITH_SLIPPAGE_IX.to_vec();
+    data.extend_from_slice(&5_000_000_000u64.to_le_bytes());
+    data.extend_from_slice(&4_100_000_000u64.to_le_bytes());
+    let instruction = CompiledInstruction {
+        program_id_index: 8,
+        accounts: (0..8).collect(),
+        data,
+    };
+
+    let parser = EventParserFactory::create_parser(Protocol::Sanctum);
+    assert!(parser.should_handle(&SANCTUM_SPL_MULTI_STAKE_POOL_PROGRAM_ID));
+    let events = parser.parse_events_from_instruction(
+        &instruction,
+        &accounts,
+        "sig",
+        1,
+        None,
+        0,
+        InstructionIndex::outer(0),
+    );
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type(), EventType::SanctumDepositSol);
+
+    let event = events[0]
+        .as_any()
+        .downcast_ref::<SanctumStakePoolEvent>()
+        .expect("stake pool event");
+    assert_eq!(
+        event.metadata.program_id,
+        SANCTUM_SPL_MULTI_STAKE_POOL_PROGRAM_ID
+    );
+    assert_eq!(event.stake_pool, keys[0]);
+    assert_eq!(event.user, keys[3]);
+    assert_eq!(event.pool_mint, keys[7]);
+    assert_eq!(event.amount, 5_000_000_000);
+    assert_eq!(event.minimum_out, 4_100_000_000);
+    assert!(event.is_deposit);
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "server")]
+
+use solana_streamer_sdk::streaming::security::{
+    AuthError, ServerAuth, NONCE_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use std::collections::HashMap;
+
+const PATH: &str = "/solana_streamer.events.EventStream/SubscribeEvents";
+
+#[test]
+fn test_hmac_auth_checks_signature_and_clock_skew() {
+    let secret = b"shared-secret".to_vec();
+    let auth = ServerAuth::hmac(secret.clone());
+    let now = 1_700_000_000;
+    let signature = ServerAuth::sign(&secret, now, "nonce-1", PATH);
+    let timestamp = now.to_string();
+    let headers = HashMap::from([
+        (TIMESTAMP_HEADER, timestamp.as_str()),
+        (NONCE_HEADER, "nonce-1"),
+        (SIGNATURE_HEADER, signature.as_str()),
+    ]);
+    let lookup = |name: &str| headers.get(name).copied();
+
+    assert_eq!(
+        auth.verify(lookup, PATH, now + 600),
+        Err(AuthError::ExpiredTimestamp)
+    );
+    assert_eq!(
+        ServerAuth::hmac(b"other".to_vec()).verify(lookup, PATH, now),
+        Err(AuthError::InvalidSignature)
+    );
+    // The signature is bound to the method it was made for
+    assert_eq!(
+        auth.verify(lookup, "/other.Service/Method", now),
+        Err(AuthError::InvalidSignature)
+    );
+    assert_eq!(auth.verify(lookup, PATH, now + 5), Ok(()));
+
+    let token = ServerAuth::token("abc");
+    assert!(token.verify(|_| Some("Bearer abc"), PATH, now).is_ok());
+    assert_eq!(
+        token.verify(|_| Some("Bearer abd"), PATH, now),
+        Err(AuthError::InvalidToken)
+    );
+}
+
+#[test]
+fn test_hmac_auth_rejects_replayed_nonces() {
+    let auth = ServerAuth::hmac(b"shared-secret".to_vec());
+    let now = 1_700_000_000;
+    let headers: HashMap<&str, String> = auth.client_headers(PATH, now).into_iter().collect();
+    let lookup = |name: &str| headers.get(name).map(|value| value.as_str());
+
+    assert_eq!(auth.verify(lookup, PATH, now), Ok(()));
+    assert_eq!(
+        auth.verify(lookup, PATH, now + 1),
+        Err(AuthError::ReplayedNonce)
+    );
+    // A clone shares the nonces seen by the server
+    assert_eq!(
+        auth.clone().verify(lookup, PATH, now + 1),
+        Err(AuthError::ReplayedNonce)
+    );
+    // Past the skew window the timestamp itself is rejected
+    assert_eq!(
+        auth.verify(lookup, PATH, now + 600),
+        Err(AuthError::ExpiredTimestamp)
+    );
+
+    // Fresh headers get a fresh nonce
+    let fresh: HashMap<&str, String> = auth.client_headers(PATH, now).into_iter().collect();
+    assert_ne!(fresh[NONCE_HEADER], headers[NONCE_HEADER]);
+    assert_eq!(
+        auth.verify(
+            |name| fresh.get(name).map(|value| value.as_str()),
+            PATH,
+            now
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_hmac_auth_handles_extreme_timestamps() {
+    let auth = ServerAuth::hmac(b"shared-secret".to_vec());
+    let now = 1_700_000_000;
+    for timestamp in [i64::MIN, i64::MAX] {
+        let timestamp = timestamp.to_string();
+        let headers = HashMap::from([
+            (TIMESTAMP_HEADER, timestamp.as_str()),
+            (NONCE_HEADER, "nonce"),
+            (SIGNATURE_HEADER, "00"),
+        ]);
+        assert_eq!(
+            auth.verify(|name| headers.get(name).copied(), PATH, now),
+            Err(AuthError::ExpiredTimestamp)
+        );
+    }
+}
+
+#[test]
+fn test_client_headers_pass_server_verification() {
+    let now = 1_700_000_000;
+    for auth in [
+        ServerAuth::token("abc"),
+        ServerAuth::hmac(b"shared-secret".to_vec()),
+    ] {
+        let headers: HashMap<&str, String> = auth.client_headers(PATH, now).into_iter().collect();
+        assert_eq!(
+            auth.verify(
+                |name| headers.get(name).map(|value| value.as_str()),
+                PATH,
+                now
+            ),
+            Ok(())
+        );
+    }
+    assert!(ServerAuth::None.client_headers(PATH, now).is_empty());
+}
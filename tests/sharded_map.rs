@@ -0,0 +1,35 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::trackers::ShardedMap;
+use std::sync::Arc;
+use std::thread;
+
+#[test]
+fn test_concurrent_upserts_are_not_lost() {
+    let map: Arc<ShardedMap<Pubkey, u64>> = Arc::new(ShardedMap::with_shards(20));
+    assert_eq!(map.shard_count(), 32);
+    let mints: Arc<Vec<Pubkey>> = Arc::new((0..64).map(|_| Pubkey::new_unique()).collect());
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let map = map.clone();
+            let mints = mints.clone();
+            thread::spawn(move || {
+                for i in 0..1_000 {
+                    map.upsert(mints[i % mints.len()], || 0, |count| *count += 1);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(map.len(), 64);
+    let total: u64 = map.collect_values(|_, _| true).iter().sum();
+    assert_eq!(total, 8_000);
+
+    let first = mints[0];
+    map.retain(|mint, _| *mint != first);
+    assert!(!map.contains_key(&first));
+    assert_eq!(map.len(), 63);
+}
@@ -0,0 +1,40 @@
+use solana_streamer_sdk::streaming::event_parser::protocols::pumpfun::PumpFunTradeEvent;
+use solana_streamer_sdk::streaming::pipeline::{EventPipeline, FanOut, SharedEvent};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn test_fan_out_shares_one_event_between_handlers() {
+    let received: Arc<Mutex<Vec<SharedEvent>>> = Arc::default();
+    let first = received.clone();
+    let second = received.clone();
+    let callback = FanOut::new()
+        .with_handler(move |event| first.lock().unwrap().push(event))
+        .with_handler(move |event| second.lock().unwrap().push(event))
+        .into_callback();
+
+    callback(Box::new(PumpFunTradeEvent {
+        sol_amount: 42,
+        ..Default::default()
+    }));
+
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 2);
+    assert!(Arc::ptr_eq(&received[0], &received[1]));
+    let trade = received[0]
+        .as_any()
+        .downcast_ref::<PumpFunTradeEvent>()
+        .unwrap();
+    assert_eq!(trade.sol_amount, 42);
+}
+
+#[test]
+fn test_shared_callback_runs_pipeline_stages() {
+    let received: Arc<Mutex<Vec<SharedEvent>>> = Arc::default();
+    let sink = received.clone();
+    let callback =
+        EventPipeline::new().into_shared_callback(move |event| sink.lock().unwrap().push(event));
+
+    callback(Box::new(PumpFunTradeEvent::default()));
+
+    assert_eq!(received.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "runtime")]
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use solana_streamer_sdk::streaming::checkpoint::ResumeCheckpoint;
+use solana_streamer_sdk::streaming::StreamHandle;
+
+#[tokio::test]
+async fn test_shutdown_drains_queued_work_and_flushes() {
+    let (sender, mut receiver) = tokio::sync::mpsc::channel::<u64>(16);
+    let processed = Arc::new(AtomicU64::new(0));
+    let checkpoint = Arc::new(ResumeCheckpoint::new(0));
+    let counter = processed.clone();
+    let source_checkpoint = checkpoint.clone();
+    let handle = StreamHandle::spawn(move |shutdown| async move {
+        shutdown.wait().await;
+        // Work received before the signal is still processed
+        receiver.close();
+        while let Some(slot) = receiver.recv().await {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            counter.fetch_add(1, Ordering::SeqCst);
+            source_checkpoint.advance(slot)?;
+        }
+        Ok(())
+    });
+    let flushed = Arc::new(AtomicU64::new(0));
+    let flush_counter = flushed.clone();
+    let handle = handle
+        .with_checkpoint(checkpoint.clone())
+        .with_flush("counter", move || {
+            flush_counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        });
+    for slot in 1..=3 {
+        sender.send(slot).await.unwrap();
+    }
+
+    let report = handle.shutdown(Duration::from_secs(5)).await.unwrap();
+    assert!(report.drained);
+    assert_eq!(processed.load(Ordering::SeqCst), 3);
+    assert_eq!(checkpoint.slot(), 3);
+    assert_eq!(flushed.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_shutdown_aborts_after_grace_and_still_flushes() {
+    let handle = StreamHandle::spawn(|_shutdown| async move {
+        std::future::pending::<()>().await;
+        Ok(())
+    });
+    let flushed = Arc::new(AtomicU64::new(0));
+    let flush_counter = flushed.clone();
+    let handle = handle.with_flush("counter", move || {
+        flush_counter.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    });
+
+    let report = handle.shutdown(Duration::from_millis(20)).await.unwrap();
+    assert!(!report.drained);
+    assert_eq!(flushed.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_source_error_is_returned() {
+    let handle = StreamHandle::spawn(|_shutdown| async move { Err(anyhow::anyhow!("boom")) });
+    let error = handle.shutdown(Duration::from_secs(1)).await.unwrap_err();
+    assert_eq!(error.to_string(), "boom");
+}
@@ -0,0 +1,85 @@
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{ClockHandle, EventMetadata, EventType, MockClock},
+    protocols::pumpfun::PumpFunTradeEvent,
+    UnifiedEvent,
+};
+use solana_streamer_sdk::streaming::pipeline::EventMiddleware;
+use solana_streamer_sdk::streaming::{
+    BlockMeta, BlockMetaEvent, SlotGapDetectedEvent, SlotGapDetector, SlotStatus, SlotUpdateEvent,
+};
+
+fn slot_update(slot: u64, parent: u64, status: SlotStatus) -> Box<dyn UnifiedEvent> {
+    Box::new(SlotUpdateEvent::new(slot, Some(parent), status, None, 0))
+}
+
+fn gaps(events: &[Box<dyn UnifiedEvent>]) -> Vec<(u64, u64)> {
+    events
+        .iter()
+        .filter_map(|event| event.as_any().downcast_ref::<SlotGapDetectedEvent>())
+        .map(|gap| (gap.from, gap.to))
+        .collect()
+}
+
+#[test]
+fn test_parent_slots_tell_skips_from_gaps() {
+    let detector = SlotGapDetector::new();
+    assert!(gaps(&detector.handle(slot_update(100, 99, SlotStatus::Processed))).is_empty());
+    // 101-103 被leader跳过，不是缺口
+    assert!(gaps(&detector.handle(slot_update(104, 100, SlotStatus::Processed))).is_empty());
+    // 确认和死亡的旧slot不影响检测
+    assert!(gaps(&detector.handle(slot_update(100, 99, SlotStatus::Confirmed))).is_empty());
+    assert!(gaps(&detector.handle(slot_update(110, 104, SlotStatus::Dead))).is_empty());
+
+    // 父slot 107 未出现在流中：105-107 缺失，108 被跳过
+    let block = BlockMeta {
+        slot: 109,
+        parent_slot: 107,
+        ..Default::default()
+    };
+    let events = detector.handle(Box::new(BlockMetaEvent::new(block, 0)));
+    assert_eq!(events.len(), 2);
+    assert_eq!(gaps(&events), [(105, 107)]);
+    assert_eq!(events[1].event_type(), EventType::SlotGapDetected);
+    assert_eq!(events[1].slot(), 109);
+
+    assert_eq!(detector.highest_slot(), Some(109));
+    assert_eq!(detector.gaps_detected(), 1);
+    assert_eq!(detector.missing_slots(), 3);
+}
+
+#[test]
+fn test_event_slots_report_jumps_past_tolerance() {
+    let trade = |slot: u64| -> Box<dyn UnifiedEvent> {
+        Box::new(PumpFunTradeEvent {
+            metadata: EventMetadata {
+                slot,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    };
+    // 默认不跟踪交易事件
+    let detector = SlotGapDetector::new();
+    detector.handle(trade(10));
+    assert!(gaps(&detector.handle(trade(50))).is_empty());
+    assert_eq!(detector.highest_slot(), None);
+
+    let detector = SlotGapDetector::new()
+        .with_event_slots(true)
+        .with_max_skipped_slots(4);
+    detector.handle(trade(10));
+    assert!(gaps(&detector.handle(trade(15))).is_empty());
+    assert_eq!(gaps(&detector.handle(trade(21))), [(16, 20)]);
+}
+
+#[test]
+fn test_gap_detection_time_comes_from_clock() {
+    let clock = MockClock::new(1_000);
+    let detector = SlotGapDetector::new().with_clock(ClockHandle::new(clock.clone()));
+    detector.observe(100, Some(99));
+    clock.advance(250);
+    let gap = detector.observe(105, Some(103)).unwrap();
+    assert_eq!((gap.from, gap.to, gap.missing_slots()), (101, 103, 3));
+    assert_eq!(gap.metadata.slot, 105);
+    assert_eq!(gap.metadata.program_received_time_ms, 1_250);
+}
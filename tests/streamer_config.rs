@@ -0,0 +1,110 @@
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventType, FailedTxPolicy},
+    Protocol,
+};
+use solana_streamer_sdk::streaming::{SinkConfig, StreamerConfig};
+
+const CONFIG: &str = r#"
+protocols = ["PumpFun", "pumpswap"]
+
+[[endpoints]]
+url = "https://grpc.example.com:443"
+x_token = "secret"
+compression = "zstd"
+
+[filters]
+account_include = ["6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P"]
+event_types = ["PumpFunBuy"]
+failed_tx_policy = "ParseWithFlag"
+
+[[sinks]]
+type = "json_lines"
+path = "events.jsonl"
+
+[performance]
+channel_capacity = 500
+
+[checkpoint]
+path = "checkpoint.json"
+"#;
+
+#[test]
+fn test_from_toml() {
+    let config = StreamerConfig::from_toml(CONFIG).unwrap();
+    assert_eq!(
+        config.protocols().unwrap(),
+        vec![Protocol::PumpFun, Protocol::PumpSwap]
+    );
+    assert_eq!(config.endpoints[0].x_token.as_deref(), Some("secret"));
+    assert_eq!(
+        config.parse_options().failed_tx_policy,
+        FailedTxPolicy::ParseWithFlag
+    );
+    assert!(config.filters.accepts(&EventType::PumpFunBuy));
+    assert!(!config.filters.accepts(&EventType::PumpFunSell));
+    assert!(matches!(config.sinks[0], SinkConfig::JsonLines { .. }));
+    assert_eq!(config.performance.channel_capacity, 500);
+    // 未配置的参数取默认值
+    assert_eq!(config.performance.dedup_window, 100_000);
+    assert_eq!(config.checkpoint.unwrap().save_interval_slots, 100);
+}
+
+#[test]
+fn test_validation_reports_every_problem() {
+    let config = r#"
+protocols = ["PumpFun", "NoSuchDex"]
+
+[[endpoints]]
+url = "grpc.example.com"
+compression = "brotli"
+
+[filters]
+account_include = ["not-a-pubkey"]
+
+[performance]
+channel_capacity = 0
+"#;
+    let error = StreamerConfig::from_toml(config).unwrap_err().to_string();
+    for expected in [
+        "endpoints[0].url",
+        "endpoints[0].compression",
+        "protocols[1]",
+        "filters.account_include[0]",
+        "performance.channel_capacity",
+    ] {
+        assert!(error.contains(expected), "{expected} missing from {error}");
+    }
+
+    let error = StreamerConfig::from_toml("protocol = [\"PumpFun\"]").unwrap_err();
+    assert!(error.to_string().contains("protocol"));
+}
+
+#[test]
+fn test_from_vars() {
+    let vars = [
+        ("SOLANA_STREAMER_ENDPOINT", "http://127.0.0.1:10000"),
+        ("SOLANA_STREAMER_X_TOKEN", "token"),
+        ("SOLANA_STREAMER_PROTOCOLS", "PumpFun, Bonk"),
+        ("SOLANA_STREAMER_FAILED_TX_POLICY", "OnlyFailed"),
+        ("SOLANA_STREAMER_CHANNEL_CAPACITY", "42"),
+        ("PATH", "/usr/bin"),
+    ]
+    .map(|(key, value)| (key.to_string(), value.to_string()));
+    let config = StreamerConfig::from_vars(vars).unwrap();
+    assert_eq!(config.endpoints.len(), 1);
+    assert_eq!(config.endpoints[0].url, "http://127.0.0.1:10000");
+    assert_eq!(config.endpoints[0].x_token.as_deref(), Some("token"));
+    assert_eq!(
+        config.protocols().unwrap(),
+        vec![Protocol::PumpFun, Protocol::Bonk]
+    );
+    assert_eq!(config.filters.failed_tx_policy, FailedTxPolicy::OnlyFailed);
+    assert_eq!(config.performance.channel_capacity, 42);
+
+    let error = StreamerConfig::from_vars([(
+        "SOLANA_STREAMER_CHANNEL_CAPACITY".to_string(),
+        "many".to_string(),
+    )])
+    .unwrap_err();
+    assert!(error.to_string().contains("CHANNEL_CAPACITY"));
+}
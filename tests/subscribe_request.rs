@@ -0,0 +1,52 @@
+#![cfg(feature = "grpc")]
+
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventType, FailedTxPolicy},
+    protocols::{
+        pumpfun::parser::PUMPFUN_PROGRAM_ID, raydium_cpmm::parser::RAYDIUM_CPMM_PROGRAM_ID,
+    },
+    Protocol,
+};
+use solana_streamer_sdk::streaming::subscribe_request::FILTER_NAME;
+use solana_streamer_sdk::streaming::SubscribeRequestBuilder;
+use yellowstone_grpc_proto::geyser::CommitmentLevel;
+
+#[test]
+fn test_program_ids_are_derived_from_protocols_and_event_types() {
+    let request = SubscribeRequestBuilder::new()
+        .with_protocols(&[Protocol::PumpFun])
+        .with_event_types(&[EventType::RaydiumCpmmSwapBaseInput, EventType::PumpFunBuy])
+        .exclude_accounts(["Bot1111111111111111111111111111111111111111"])
+        .with_commitment(CommitmentLevel::Confirmed)
+        .with_blocks_meta(true)
+        .build()
+        .unwrap();
+
+    let filter = &request.transactions[FILTER_NAME];
+    assert_eq!(
+        filter.account_include,
+        vec![
+            PUMPFUN_PROGRAM_ID.to_string(),
+            RAYDIUM_CPMM_PROGRAM_ID.to_string()
+        ]
+    );
+    assert_eq!(filter.account_exclude.len(), 1);
+    assert_eq!(filter.vote, Some(false));
+    assert_eq!(request.commitment, Some(CommitmentLevel::Confirmed as i32));
+    assert!(request.blocks_meta.contains_key(FILTER_NAME));
+}
+
+#[test]
+fn test_failed_policy_and_empty_filters() {
+    let builder = SubscribeRequestBuilder::new();
+    assert_eq!(builder.transactions_filter().failed, Some(false));
+    assert!(builder.build().is_err());
+
+    let request = builder
+        .with_failed_tx_policy(FailedTxPolicy::ParseWithFlag)
+        .require_accounts([PUMPFUN_PROGRAM_ID])
+        .build()
+        .unwrap();
+    assert_eq!(request.transactions[FILTER_NAME].failed, None);
+    assert!(request.blocks_meta.is_empty());
+}
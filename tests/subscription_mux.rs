@@ -0,0 +1,99 @@
+use std::sync::{Arc, Mutex};
+
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventMetadata, EventType},
+    protocols::{bonk::BonkTradeEvent, pumpfun::PumpFunTradeEvent},
+    Protocol, UnifiedEvent,
+};
+use solana_streamer_sdk::streaming::pipeline::{SharedEvent, Watchlist};
+use solana_streamer_sdk::streaming::{SubscriptionMux, SubscriptionSpec};
+
+fn pumpfun_buy() -> Arc<dyn UnifiedEvent> {
+    Arc::new(PumpFunTradeEvent {
+        metadata: EventMetadata {
+            event_type: EventType::PumpFunBuy,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+fn bonk_buy(signer: Pubkey) -> Arc<dyn UnifiedEvent> {
+    Arc::new(BonkTradeEvent {
+        metadata: EventMetadata {
+            event_type: EventType::BonkBuyExactIn,
+            signers: vec![signer],
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_mux_routes_one_parse_to_matching_subscriptions() {
+    let mux = SubscriptionMux::new();
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let recorder = |name: &'static str| {
+        let received = received.clone();
+        move |event: SharedEvent| received.lock().unwrap().push((name, event.event_type()))
+    };
+    let wallet = Pubkey::new_unique();
+
+    let pumpfun = mux.add(
+        SubscriptionSpec::new("pumpfun").with_protocols(vec![Protocol::PumpFun]),
+        recorder("pumpfun"),
+    );
+    mux.add(
+        SubscriptionSpec::new("copy")
+            .with_protocols(vec![Protocol::Bonk])
+            .with_watchlist(Watchlist::new().with_wallet(wallet)),
+        recorder("copy"),
+    );
+    // 没有协议的订阅只接收其他订阅解析的事件
+    mux.add(
+        SubscriptionSpec::new("buys")
+            .with_watchlist(Watchlist::new().with_event_type(EventType::PumpFunBuy)),
+        recorder("buys"),
+    );
+    assert_eq!(
+        mux.protocols().protocols(),
+        [Protocol::PumpFun, Protocol::Bonk]
+    );
+
+    // 同一个事件对象交给多个订阅
+    let event = pumpfun_buy();
+    assert_eq!(mux.dispatch(event.clone()), 2);
+    assert_eq!(Arc::strong_count(&event), 1);
+    assert_eq!(mux.dispatch(bonk_buy(wallet)), 1);
+    assert_eq!(mux.dispatch(bonk_buy(Pubkey::new_unique())), 0);
+    assert_eq!(mux.unrouted(), 1);
+    assert_eq!(
+        *received.lock().unwrap(),
+        [
+            ("pumpfun", EventType::PumpFunBuy),
+            ("buys", EventType::PumpFunBuy),
+            ("copy", EventType::BonkBuyExactIn),
+        ]
+    );
+
+    let delivered: Vec<(String, u64)> = mux
+        .stats()
+        .into_iter()
+        .map(|stats| (stats.name, stats.delivered))
+        .collect();
+    assert_eq!(
+        delivered,
+        [
+            ("pumpfun".to_string(), 1),
+            ("copy".to_string(), 1),
+            ("buys".to_string(), 1),
+        ]
+    );
+
+    // 移除订阅后不再解析它独有的协议
+    assert!(mux.remove(pumpfun));
+    assert!(!mux.remove(pumpfun));
+    assert_eq!(mux.protocols().protocols(), [Protocol::Bonk]);
+    assert_eq!(mux.len(), 2);
+}
@@ -0,0 +1,180 @@
+#![cfg(feature = "snapshots")]
+
+use std::sync::Arc;
+
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::checkpoint::ResumeCheckpoint;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::EventMetadata, protocols::pumpfun::PumpFunTradeEvent,
+};
+use solana_streamer_sdk::streaming::trackers::{
+    BondingCurveTracker, FileSnapshotStore, PnlTracker, SnapshotManager, SnapshotStore,
+};
+
+fn buy(wallet: Pubkey, mint: Pubkey, slot: u64) -> PumpFunTradeEvent {
+    PumpFunTradeEvent {
+        metadata: EventMetadata {
+            slot,
+            ..Default::default()
+        },
+        user: wallet,
+        mint,
+        bonding_curve: Pubkey::new_unique(),
+        is_buy: true,
+        token_amount: 1_000_000,
+        sol_amount: 10_000_000,
+        virtual_sol_reserves: 30_000_000_000,
+        virtual_token_reserves: 1_073_000_000_000_000,
+        real_token_reserves: 793_100_000_000_000,
+        ..Default::default()
+    }
+}
+
+fn buy_with_id(wallet: Pubkey, mint: Pubkey, slot: u64, id: &str) -> PumpFunTradeEvent {
+    let mut trade = buy(wallet, mint, slot);
+    trade.metadata.set_id(id.to_string());
+    trade
+}
+
+fn temp_store(keep: usize) -> FileSnapshotStore {
+    let dir = std::env::temp_dir().join(format!("snapshots-{}", Pubkey::new_unique()));
+    FileSnapshotStore::new(dir).unwrap().with_keep(keep)
+}
+
+#[test]
+fn test_trackers_resume_from_latest_snapshot() {
+    let store = temp_store(2);
+    let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let curves = Arc::new(BondingCurveTracker::new());
+    let pnl = Arc::new(PnlTracker::new());
+    let trade = buy(wallet, mint, 100);
+    curves.apply_trade(&trade);
+    pnl.apply_event(&trade);
+
+    let checkpoint = ResumeCheckpoint::new(0);
+    checkpoint.advance(100).unwrap();
+    let snapshots = SnapshotManager::new(store.clone())
+        .with_tracker(curves.clone())
+        .with_tracker(pnl.clone());
+    snapshots.save_checkpoint(&checkpoint).unwrap();
+
+    // Restarted process
+    let restored_curves = Arc::new(BondingCurveTracker::new());
+    let restored_pnl = Arc::new(PnlTracker::new());
+    let restored = SnapshotManager::new(store)
+        .with_tracker(restored_curves.clone())
+        .with_tracker(restored_pnl.clone());
+    assert_eq!(restored.restore().unwrap(), Some(100));
+    assert_eq!(restored_curves.get_state(&mint), curves.get_state(&mint));
+    assert_eq!(
+        restored_pnl.get_position(&wallet, &mint),
+        pnl.get_position(&wallet, &mint)
+    );
+    assert_eq!(
+        restored_pnl.get_last_price(&mint),
+        pnl.get_last_price(&mint)
+    );
+}
+
+#[test]
+fn test_restore_replaces_state_and_prunes_old_snapshots() {
+    let store = temp_store(2);
+    let pnl = Arc::new(PnlTracker::new());
+    let snapshots = SnapshotManager::new(store.clone()).with_tracker(pnl.clone());
+    assert_eq!(snapshots.restore().unwrap(), None);
+
+    let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    snapshots.save(10).unwrap();
+    pnl.apply_event(&buy(wallet, mint, 20));
+    snapshots.save(20).unwrap();
+    pnl.apply_event(&buy(wallet, Pubkey::new_unique(), 30));
+    snapshots.save(30).unwrap();
+    assert_eq!(store.slots().unwrap(), [20, 30]);
+
+    // A torn latest snapshot falls back to the one before it
+    std::fs::write(store.path_for(30), b"torn").unwrap();
+    assert_eq!(store.load_latest().unwrap().unwrap().slot, 20);
+    assert_eq!(snapshots.restore().unwrap(), Some(20));
+    assert_eq!(pnl.len(), 1);
+    assert!(pnl.get_position(&wallet, &mint).is_some());
+}
+
+#[test]
+fn test_replay_after_restore_skips_applied_events() {
+    let store = temp_store(2);
+    let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let pnl = Arc::new(PnlTracker::new());
+    let curves = Arc::new(BondingCurveTracker::new());
+    let first = buy_with_id(wallet, mint, 100, "a");
+    let second = buy_with_id(wallet, mint, 101, "b");
+    for trade in [&first, &second] {
+        pnl.apply_event(trade);
+        curves.apply_trade(trade);
+    }
+
+    // The checkpoint is behind the trackers: slot 101 isn't complete yet
+    let checkpoint = ResumeCheckpoint::new(100);
+    SnapshotManager::new(store.clone())
+        .with_tracker(pnl.clone())
+        .with_tracker(curves.clone())
+        .save_checkpoint(&checkpoint)
+        .unwrap();
+    assert_eq!(pnl.applied_position().slot, 101);
+
+    let restored_pnl = Arc::new(PnlTracker::new());
+    let restored_curves = Arc::new(BondingCurveTracker::new());
+    let resume = SnapshotManager::new(store)
+        .with_tracker(restored_pnl.clone())
+        .with_tracker(restored_curves.clone())
+        .restore()
+        .unwrap();
+    assert_eq!(resume, Some(100));
+
+    // The stream resumes after slot 100 and replays slot 101 with a trade that
+    // wasn't applied before the snapshot
+    let third = buy_with_id(wallet, mint, 101, "c");
+    assert!(restored_curves.apply_trade(&first).is_none());
+    assert!(restored_curves.apply_trade(&second).is_none());
+    assert!(restored_curves.apply_trade(&third).is_some());
+    for trade in [&first, &second, &third] {
+        restored_pnl.apply_event(trade);
+    }
+    let position = restored_pnl.get_position(&wallet, &mint).unwrap();
+    assert_eq!(position.balance, 3 * first.token_amount);
+    assert_eq!(position.cost_basis, 3 * first.sol_amount);
+    assert_eq!(restored_pnl.applied_position().slot, 101);
+
+    // Later slots are applied as usual
+    restored_pnl.apply_event(&buy_with_id(wallet, mint, 102, "a"));
+    let position = restored_pnl.get_position(&wallet, &mint).unwrap();
+    assert_eq!(position.balance, 4 * first.token_amount);
+}
+
+#[test]
+fn test_trackers_of_the_same_type_need_distinct_names() {
+    let store = temp_store(2);
+    let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let retail = Arc::new(PnlTracker::new().with_snapshot_name("pnl.retail"));
+    let whales = Arc::new(PnlTracker::new().with_snapshot_name("pnl.whales"));
+    retail.apply_event(&buy(wallet, mint, 10));
+    SnapshotManager::new(store.clone())
+        .with_tracker(retail.clone())
+        .with_tracker(whales.clone())
+        .save(10)
+        .unwrap();
+
+    let restored_retail = Arc::new(PnlTracker::new().with_snapshot_name("pnl.retail"));
+    let restored_whales = Arc::new(PnlTracker::new().with_snapshot_name("pnl.whales"));
+    SnapshotManager::new(store.clone())
+        .with_tracker(restored_retail.clone())
+        .with_tracker(restored_whales.clone())
+        .restore()
+        .unwrap();
+    assert!(restored_retail.get_position(&wallet, &mint).is_some());
+    assert!(restored_whales.is_empty());
+
+    let clashing = SnapshotManager::new(store)
+        .with_tracker(Arc::new(PnlTracker::new()))
+        .with_tracker(Arc::new(PnlTracker::new()));
+    assert!(clashing.save(20).is_err());
+}
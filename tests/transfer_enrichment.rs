@@ -0,0 +1,33 @@
+use anyhow::Result;
+use solana_streamer_sdk::streaming::event_parser::{EventParserFactory, Protocol};
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use std::fs;
+
+#[tokio::test]
+async fn test_token_transfers_carry_mint_and_decimals() -> Result<()> {
+    let mut token_transfers = 0;
+    for (path, protocol) in [
+        ("tests/fixtures/pumpfun_cpi_tx.json", Protocol::PumpFun),
+        (
+            "tests/fixtures/pumpswap_sell_cpi_tx.json",
+            Protocol::PumpSwap,
+        ),
+        ("tests/fixtures/bonk_cpi_tx.json", Protocol::Bonk),
+    ] {
+        let tx: EncodedConfirmedTransactionWithStatusMeta =
+            serde_json::from_str(&fs::read_to_string(path)?)?;
+        let events = EventParserFactory::create_parser(protocol)
+            .parse_transaction(tx.transaction, "sig", Some(tx.slot), None, 0, None)
+            .await?;
+        for transfer in events.iter().flat_map(|e| &e.metadata().transfer_datas) {
+            if transfer.token_program == solana_sdk::system_program::ID {
+                continue;
+            }
+            token_transfers += 1;
+            assert!(transfer.mint.is_some(), "{path}: {transfer:?}");
+            assert!(transfer.decimals.is_some(), "{path}: {transfer:?}");
+        }
+    }
+    assert!(token_transfers > 0);
+    Ok(())
+}
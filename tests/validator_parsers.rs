@@ -0,0 +1,79 @@
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventType, InstructionIndex},
+    core::traits::EventParser,
+    protocols::{
+        stake::{discriminators as stake_discriminators, parser::STAKE_PROGRAM_ID},
+        vote::{discriminators as vote_discriminators, parser::VOTE_PROGRAM_ID, VoteEvent},
+        StakeEventParser, VoteEventParser,
+    },
+};
+
+#[test]
+fn test_vote_tower_sync_latency_and_credits() {
+    let (vote_account, authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut data = vote_discriminators::TOWER_SYNC_IX.to_vec();
+    data.extend_from_slice(&1_000u64.to_le_bytes());
+    // short_vec of (varint offset, confirmation count): slots 1001, 1003, 1203
+    data.push(3);
+    data.extend_from_slice(&[1, 3, 2, 2, 0xc8, 0x01, 1]);
+    data.extend_from_slice(&[7u8; 32]);
+    data.push(1);
+    data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+    // block_id
+    data.extend_from_slice(&[9u8; 32]);
+
+    let accounts = vec![vote_account, authority, VOTE_PROGRAM_ID];
+    let instruction = CompiledInstruction {
+        program_id_index: 2,
+        accounts: vec![0, 1],
+        data,
+    };
+    let events = VoteEventParser::new().parse_events_from_instruction(
+        &instruction,
+        &accounts,
+        "sig",
+        1_208,
+        None,
+        0,
+        InstructionIndex::outer(0),
+    );
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type(), EventType::VoteCast);
+    let vote = events[0]
+        .as_any()
+        .downcast_ref::<VoteEvent>()
+        .expect("vote event");
+    assert_eq!(vote.vote_account, vote_account);
+    assert_eq!(vote.vote_authority, authority);
+    assert_eq!(vote.root, Some(1_000));
+    assert_eq!(vote.last_voted_slot, 1_203);
+    assert_eq!(vote.vote_latency, 5);
+    assert_eq!(vote.credits, 13);
+    assert_eq!(vote.timestamp, Some(1_700_000_000));
+}
+
+#[test]
+fn test_stake_withdraw() {
+    let keys: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+    let mut accounts = keys.clone();
+    accounts.push(STAKE_PROGRAM_ID);
+    let mut data = stake_discriminators::WITHDRAW_IX.to_vec();
+    data.extend_from_slice(&2_000_000_000u64.to_le_bytes());
+    let instruction = CompiledInstruction {
+        program_id_index: 5,
+        accounts: vec![0, 1, 2, 3, 4],
+        data,
+    };
+    let events = StakeEventParser::new().parse_events_from_instruction(
+        &instruction,
+        &accounts,
+        "sig",
+        1,
+        None,
+        0,
+        InstructionIndex::outer(0),
+    );
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type(), EventType::StakeWithdraw);
+}
@@ -0,0 +1,66 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::ProtocolType, protocols::pumpfun::PumpFunTradeEvent, UnifiedEvent,
+};
+use solana_streamer_sdk::streaming::trackers::{
+    SessionTracker, WalletSessionClosedEvent, WalletSessionOpenedEvent,
+};
+use std::time::Duration;
+
+fn trade(
+    user: Pubkey,
+    mint: Pubkey,
+    is_buy: bool,
+    sol_amount: u64,
+    time_ms: i64,
+) -> PumpFunTradeEvent {
+    let mut event = PumpFunTradeEvent {
+        user,
+        mint,
+        is_buy,
+        sol_amount,
+        token_amount: 1_000,
+        ..Default::default()
+    };
+    event.metadata.block_time_ms = time_ms;
+    event.metadata.protocol = ProtocolType::PumpFun;
+    event
+}
+
+#[test]
+fn test_sessions_split_on_idle_gap() {
+    let tracker = SessionTracker::new().with_idle_gap(Duration::from_secs(60));
+    let (wallet, mint_a, mint_b) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+
+    let events = tracker.apply_event(&trade(wallet, mint_a, true, 500, 1_000));
+    assert_eq!(events.len(), 1);
+    assert!(events[0].as_any().is::<WalletSessionOpenedEvent>());
+    assert!(tracker
+        .apply_event(&trade(wallet, mint_b, true, 300, 30_000))
+        .is_empty());
+    assert!(tracker
+        .apply_event(&trade(wallet, mint_a, false, 700, 50_000))
+        .is_empty());
+
+    let session = tracker.get_session(&wallet).unwrap();
+    assert_eq!((session.trades, session.buys, session.sells), (3, 2, 1));
+    assert_eq!(session.tokens, vec![mint_a, mint_b]);
+    assert_eq!(session.venues, vec![ProtocolType::PumpFun]);
+    assert_eq!(session.net_sol_flow, -100);
+
+    let events = tracker.apply_event(&trade(wallet, mint_a, true, 100, 200_000));
+    assert_eq!(events.len(), 2);
+    let closed = events[0]
+        .as_any()
+        .downcast_ref::<WalletSessionClosedEvent>()
+        .unwrap();
+    assert_eq!(closed.session.duration_ms(), 49_000);
+    assert_eq!(tracker.get_session(&wallet).unwrap().started_ms, 200_000);
+
+    assert_eq!(tracker.close_idle(300_000).len(), 1);
+    assert!(tracker.is_empty());
+}
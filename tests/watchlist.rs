@@ -0,0 +1,70 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::{
+    common::{EventType, ProtocolType},
+    protocols::pumpfun::PumpFunTradeEvent,
+    UnifiedEvent,
+};
+use solana_streamer_sdk::streaming::pipeline::{EventMiddleware, Watchlist, WatchlistFilter};
+
+fn trade(user: Pubkey, mint: Pubkey) -> Box<dyn UnifiedEvent> {
+    let mut event = PumpFunTradeEvent {
+        user,
+        mint,
+        is_buy: true,
+        sol_amount: 1_000,
+        token_amount: 1_000,
+        ..Default::default()
+    };
+    event.metadata.event_type = EventType::PumpFunBuy;
+    event.metadata.protocol = ProtocolType::PumpFun;
+    Box::new(event)
+}
+
+fn passes(filter: &WatchlistFilter, event: Box<dyn UnifiedEvent>) -> bool {
+    !filter.handle(event).is_empty()
+}
+
+#[test]
+fn test_watchlist_updates_apply_to_next_event() {
+    let (wallet, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mint = Pubkey::new_unique();
+    let filter = WatchlistFilter::new(Watchlist::new().with_wallet(wallet));
+    assert!(passes(&filter, trade(wallet, mint)));
+    assert!(!passes(&filter, trade(other, mint)));
+
+    assert_eq!(filter.add_wallet(other), 1);
+    assert!(passes(&filter, trade(other, mint)));
+    filter.remove_wallet(&wallet);
+    assert!(!passes(&filter, trade(wallet, mint)));
+
+    filter.replace(Watchlist::new().with_mint(mint));
+    assert!(passes(&filter, trade(wallet, mint)));
+    assert!(!passes(&filter, trade(wallet, Pubkey::new_unique())));
+    assert_eq!(filter.version(), 3);
+
+    filter.replace(Watchlist::new().with_event_type(EventType::PumpFunSell));
+    assert!(!passes(&filter, trade(wallet, mint)));
+}
+
+#[test]
+fn test_reload_from_file() {
+    let wallet = Pubkey::new_unique();
+    let path = std::env::temp_dir().join(format!("watchlist-{}.json", Pubkey::new_unique()));
+    std::fs::write(&path, format!(r#"{{"wallets": ["{}"]}}"#, wallet)).unwrap();
+    let filter = WatchlistFilter::from_file(&path).unwrap();
+    assert!(filter.watchlist().wallets.contains(&wallet));
+    assert!(!filter.reload_if_changed().unwrap());
+
+    let mint = Pubkey::new_unique();
+    std::fs::write(&path, format!(r#"{{"mints": ["{}"]}}"#, mint)).unwrap();
+    filter.reload().unwrap();
+    assert!(filter.watchlist().wallets.is_empty());
+    assert!(passes(&filter, trade(Pubkey::new_unique(), mint)));
+
+    // 损坏的文件保留之前的列表
+    std::fs::write(&path, r#"{"wallets": ["not-a-pubkey"]}"#).unwrap();
+    let error = filter.reload().unwrap_err().to_string();
+    assert!(error.contains("wallets[0]"), "{error}");
+    assert!(filter.watchlist().mints.contains(&mint));
+    std::fs::remove_file(&path).unwrap();
+}